@@ -0,0 +1,759 @@
+#![no_std]
+use soroban_sdk::{
+    contract, contractimpl, contracttype, symbol_short, vec, xdr::ToXdr, Address, Bytes, BytesN,
+    Env, Map, Symbol, Vec,
+};
+
+use mobility_types::{access, changes, page_result, ttl, upgrade, Gate, PageRequest, Role, TtlConfig};
+
+mobility_types::contract_errors! {
+    BadReveal = 33,
+    FewJurors = 55,
+    NoJuror = 71,
+}
+
+/// Represents a registered juror
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Juror {
+    pub address: Address,
+    pub stake: i128,
+    pub equity_score: i32, // 0-100 score reported by the equity oracle
+    pub active: bool,
+}
+
+/// Represents a dispute between two parties
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Dispute {
+    pub id: u32,
+    pub claimant: Address,
+    pub respondent: Address,
+    pub subject: Symbol, // What is being disputed (asset id, proposal id, ...)
+    pub round: u32, // 0 for the first panel, incremented on each appeal
+    pub jurors: Vec<Address>, // Current panel
+    pub selection_seed: BytesN<32>, // PRNG seed that drew the current panel
+    pub status: Symbol, // "commit", "reveal", "ruled", "final"
+    pub commit_end: u64,
+    pub reveal_end: u64,
+    pub votes_for: u32, // Revealed votes for the claimant
+    pub votes_against: u32,
+    pub ruling: Option<bool>, // true if the claimant won
+    pub ruled_at: u64,
+}
+
+/// A juror's committed and (later) revealed vote
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct JurorVote {
+    pub commitment: BytesN<32>, // sha256(vote byte || salt)
+    pub revealed: bool,
+    pub vote: bool,
+}
+
+/// A dispute that changed, at the ledger it was last modified in
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DisputeChange {
+    pub id: u32,
+    pub ledger: u32,
+}
+
+page_result!(DisputeChangePage, DisputeChange);
+
+/// Contract data structure
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DataKey {
+    pub admin: Address,
+    pub oracle: Address, // Equity oracle address
+    pub jurors: Map<Address, Juror>,
+    pub juror_list: Vec<Address>,
+    pub delegates: Map<Address, Address>, // party -> delegate acting on their behalf
+    pub votes: Map<(u32, u32, Address), JurorVote>, // (dispute_id, round, juror) -> vote
+    pub next_dispute_id: u32,
+    pub paused: bool, // Set by admin or by a failed invariant check when auto_pause is on
+    pub disabled_features: Vec<Symbol>, // Entrypoint features switched off by admin
+    pub auto_pause: bool,
+}
+
+/// Persistent entries for per-record state, so a call reads and rewrites only
+/// the records it touches instead of the whole instance entry
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum StorageKey {
+    Dispute(u32),
+    DisputeIds, // Every dispute id, in filing order
+}
+
+const DATA_KEY: Symbol = symbol_short!("DATA_KEY");
+
+// Set once by initialize to the storage layout version; its presence marks the contract initialized
+const INIT_KEY: Symbol = symbol_short!("INIT");
+const CONTRACT_VERSION: u32 = 2;
+
+// Panel sizes grow with each appeal: 3, 7, 15
+const BASE_PANEL_SIZE: u32 = 3;
+const MAX_APPEALS: u32 = 2;
+
+const COMMIT_PERIOD: u64 = 3 * 86_400;
+const REVEAL_PERIOD: u64 = 2 * 86_400;
+const APPEAL_WINDOW: u64 = 2 * 86_400;
+
+#[contract]
+pub struct DisputeResolution;
+
+#[contractimpl]
+impl DisputeResolution {
+    /// Initialize the contract. Can only be called once.
+    pub fn initialize(env: &Env, admin: Address, oracle: Address) -> Result<(), ContractError> {
+        admin.require_auth();
+
+        if env.storage().instance().has(&INIT_KEY) || env.storage().instance().has(&DATA_KEY) {
+            return Err(ContractError::AlreadyInitialized);
+        }
+
+        let data = DataKey {
+            admin,
+            oracle,
+            jurors: Map::new(env),
+            juror_list: vec![env],
+            delegates: Map::new(env),
+            votes: Map::new(env),
+            next_dispute_id: 1,
+            paused: false,
+            disabled_features: Vec::new(env),
+            auto_pause: false,
+        };
+        ttl::set_instance(env, &DATA_KEY, &data);
+        ttl::set_instance(env, &INIT_KEY, &CONTRACT_VERSION);
+
+        Ok(())
+    }
+
+    /// Change the oracle set at initialization without touching other state (oracle manager only)
+    pub fn reconfigure(env: &Env, caller: Address, oracle: Address) -> Result<(), ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::OracleManager, &caller)?;
+
+        data.oracle = oracle.clone();
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        env.events().publish((symbol_short!("reconfig"),), oracle);
+
+        Ok(())
+    }
+
+    /// Get the storage layout version recorded at initialization, or 0 if uninitialized
+    pub fn get_version(env: &Env) -> u32 {
+        env.storage().instance().get(&INIT_KEY).unwrap_or(0)
+    }
+
+    /// Version of the running code; `get_version` lags behind it until `migrate` runs
+    pub fn version(_env: &Env) -> u32 {
+        CONTRACT_VERSION
+    }
+
+    /// Replace the contract's code with an uploaded wasm, keeping its storage (admin only).
+    /// The new code's `migrate` must run before storage written by the old layout is used
+    pub fn upgrade(env: &Env, caller: Address, new_wasm_hash: BytesN<32>) -> Result<(), ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+        upgrade::upgrade(env, new_wasm_hash);
+
+        Ok(())
+    }
+
+    /// Bring storage up to this code's layout version after an upgrade (admin only)
+    pub fn migrate(env: &Env, caller: Address) -> Result<u32, ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+
+        let stored: u32 = env.storage().instance().get(&INIT_KEY).unwrap_or(0);
+        let version = upgrade::migrate(env, stored, CONTRACT_VERSION, Self::migrate_step)?;
+        ttl::set_instance(env, &INIT_KEY, &version);
+
+        Ok(version)
+    }
+
+    /// Rewrite storage from layout version `from` to `from + 1`. Each release that changes
+    /// the layout adds its step here
+    fn migrate_step(_env: &Env, _from: u32) -> Result<(), ContractError> {
+        Ok(())
+    }
+
+
+    /// Register as a juror or top up an existing stake
+    pub fn register_juror(env: &Env, juror: Address, stake: i128) -> Result<i128, ContractError> {
+        juror.require_auth();
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        if stake <= 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        let mut record = match data.jurors.get(juror.clone()) {
+            Some(existing) => existing,
+            None => {
+                data.juror_list.push_back(juror.clone());
+                Juror {
+                    address: juror.clone(),
+                    stake: 0,
+                    equity_score: 0,
+                    active: true,
+                }
+            }
+        };
+        record.stake += stake;
+        record.active = true;
+        let total = record.stake;
+        data.jurors.set(juror, record);
+
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        Ok(total)
+    }
+
+    /// Stop being selected for new panels
+    pub fn deactivate_juror(env: &Env, juror: Address) -> Result<(), ContractError> {
+        juror.require_auth();
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        let mut record = data.jurors.get(juror.clone()).ok_or(ContractError::NoJuror)?;
+        record.active = false;
+        data.jurors.set(juror, record);
+
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        Ok(())
+    }
+
+    /// Update a juror's equity score (oracle only)
+    pub fn update_juror_equity(env: &Env, juror: Address, equity_score: i32) -> Result<(), ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        data.oracle.require_auth();
+
+        if !(0..=100).contains(&equity_score) {
+            return Err(ContractError::InvalidInput);
+        }
+
+        let mut record = data.jurors.get(juror.clone()).ok_or(ContractError::NoJuror)?;
+        record.equity_score = equity_score;
+        data.jurors.set(juror, record);
+
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        Ok(())
+    }
+
+    /// Declare the delegate acting on a party's behalf, excluded from its panels
+    pub fn set_delegate(env: &Env, party: Address, delegate: Address) -> Result<(), ContractError> {
+        party.require_auth();
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        data.delegates.set(party, delegate);
+
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        Ok(())
+    }
+
+    /// Open a dispute and draw its first juror panel
+    pub fn open_dispute(
+        env: &Env,
+        claimant: Address,
+        respondent: Address,
+        subject: Symbol,
+    ) -> Result<u32, ContractError> {
+        claimant.require_auth();
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        Gate::new(data.paused, &data.disabled_features, &symbol_short!("open")).check()?;
+
+        if claimant == respondent {
+            return Err(ContractError::SameParty);
+        }
+
+        let dispute_id = data.next_dispute_id;
+        let (jurors, selection_seed) =
+            Self::select_panel(env, &data, dispute_id, 0, &claimant, &respondent, BASE_PANEL_SIZE)?;
+
+        let now = env.ledger().timestamp();
+        let dispute = Dispute {
+            id: dispute_id,
+            claimant,
+            respondent,
+            subject,
+            round: 0,
+            jurors: jurors.clone(),
+            selection_seed,
+            status: symbol_short!("commit"),
+            commit_end: now + COMMIT_PERIOD,
+            reveal_end: now + COMMIT_PERIOD + REVEAL_PERIOD,
+            votes_for: 0,
+            votes_against: 0,
+            ruling: None,
+            ruled_at: 0,
+        };
+
+        Self::save_dispute(env, &dispute);
+
+        changes::touch(env, symbol_short!("dispute"), dispute_id);
+        env.events().publish(
+            (symbol_short!("opened"), dispute_id),
+            (dispute.claimant.clone(), dispute.respondent.clone(), dispute.subject.clone()),
+        );
+        data.next_dispute_id += 1;
+
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        env.events().publish((symbol_short!("panel"), dispute_id), (0u32, jurors));
+
+        Ok(dispute_id)
+    }
+
+    /// Commit a hidden vote: sha256 of one byte (1 = claimant, 0 = respondent) followed by a salt
+    pub fn commit_vote(env: &Env, juror: Address, dispute_id: u32, commitment: BytesN<32>) -> Result<(), ContractError> {
+        juror.require_auth();
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        Gate::new(data.paused, &data.disabled_features, &symbol_short!("commit")).check()?;
+
+        let dispute = Self::load_dispute(env, &dispute_id).ok_or(ContractError::NotFound)?;
+
+        if dispute.status != symbol_short!("commit") || env.ledger().timestamp() > dispute.commit_end {
+            return Err(ContractError::InvalidStatus);
+        }
+
+        if !dispute.jurors.contains(&juror) {
+            return Err(ContractError::Unauthorized);
+        }
+
+        let key = (dispute_id, dispute.round, juror);
+        if data.votes.contains_key(key.clone()) {
+            return Err(ContractError::Done);
+        }
+
+        data.votes.set(key, JurorVote {
+            commitment,
+            revealed: false,
+            vote: false,
+        });
+
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        Ok(())
+    }
+
+    /// Reveal a previously committed vote
+    pub fn reveal_vote(env: &Env, juror: Address, dispute_id: u32, vote: bool, salt: BytesN<32>) -> Result<(), ContractError> {
+        juror.require_auth();
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        Gate::new(data.paused, &data.disabled_features, &symbol_short!("reveal")).check()?;
+
+        let mut dispute = Self::load_dispute(env, &dispute_id).ok_or(ContractError::NotFound)?;
+
+        let now = env.ledger().timestamp();
+        if dispute.status == symbol_short!("commit") && now > dispute.commit_end {
+            dispute.status = symbol_short!("reveal");
+        }
+
+        if dispute.status != symbol_short!("reveal") || now > dispute.reveal_end {
+            return Err(ContractError::InvalidStatus);
+        }
+
+        let key = (dispute_id, dispute.round, juror);
+        let mut juror_vote = data.votes.get(key.clone()).ok_or(ContractError::NotFound)?;
+
+        if juror_vote.revealed {
+            return Err(ContractError::Done);
+        }
+
+        if Self::vote_commitment(env, vote, salt) != juror_vote.commitment {
+            return Err(ContractError::BadReveal);
+        }
+
+        juror_vote.revealed = true;
+        juror_vote.vote = vote;
+        data.votes.set(key, juror_vote);
+
+        if vote {
+            dispute.votes_for += 1;
+        } else {
+            dispute.votes_against += 1;
+        }
+        Self::save_dispute(env, &dispute);
+        changes::touch(env, symbol_short!("dispute"), dispute_id);
+
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        Ok(())
+    }
+
+    /// Tally revealed votes once the reveal period has ended
+    pub fn finalize_round(env: &Env, dispute_id: u32) -> Result<bool, ContractError> {
+        let mut dispute = Self::load_dispute(env, &dispute_id).ok_or(ContractError::NotFound)?;
+
+        if dispute.status != symbol_short!("commit") && dispute.status != symbol_short!("reveal") {
+            return Err(ContractError::InvalidStatus);
+        }
+
+        let now = env.ledger().timestamp();
+        if now <= dispute.reveal_end {
+            return Err(ContractError::TooEarly);
+        }
+
+        // Ties and empty panels leave the respondent's position standing
+        let ruling = dispute.votes_for > dispute.votes_against;
+        dispute.ruling = Some(ruling);
+        dispute.ruled_at = now;
+        dispute.status = if dispute.round >= MAX_APPEALS {
+            symbol_short!("final")
+        } else {
+            symbol_short!("ruled")
+        };
+        Self::save_dispute(env, &dispute);
+        changes::touch(env, symbol_short!("dispute"), dispute_id);
+
+        env.events().publish((symbol_short!("ruling"), dispute_id), (dispute.round, ruling));
+
+        Ok(ruling)
+    }
+
+    /// Appeal a ruling to a larger, freshly drawn panel
+    pub fn appeal(env: &Env, party: Address, dispute_id: u32) -> Result<Vec<Address>, ContractError> {
+        party.require_auth();
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        let mut dispute = Self::load_dispute(env, &dispute_id).ok_or(ContractError::NotFound)?;
+
+        if party != dispute.claimant && party != dispute.respondent {
+            return Err(ContractError::Unauthorized);
+        }
+
+        if dispute.status != symbol_short!("ruled") {
+            return Err(ContractError::InvalidStatus);
+        }
+
+        let now = env.ledger().timestamp();
+        if now > dispute.ruled_at + APPEAL_WINDOW {
+            return Err(ContractError::Expired);
+        }
+
+        let round = dispute.round + 1;
+        let panel_size = BASE_PANEL_SIZE * 2_u32.pow(round) + (2_u32.pow(round) - 1);
+        let (jurors, selection_seed) = Self::select_panel(
+            env,
+            &data,
+            dispute_id,
+            round,
+            &dispute.claimant,
+            &dispute.respondent,
+            panel_size,
+        )?;
+
+        dispute.round = round;
+        dispute.jurors = jurors.clone();
+        dispute.selection_seed = selection_seed;
+        dispute.status = symbol_short!("commit");
+        dispute.commit_end = now + COMMIT_PERIOD;
+        dispute.reveal_end = now + COMMIT_PERIOD + REVEAL_PERIOD;
+        dispute.votes_for = 0;
+        dispute.votes_against = 0;
+        dispute.ruling = None;
+        dispute.ruled_at = 0;
+        Self::save_dispute(env, &dispute);
+        changes::touch(env, symbol_short!("dispute"), dispute_id);
+
+        env.events().publish((symbol_short!("panel"), dispute_id), (round, jurors.clone()));
+
+        Ok(jurors)
+    }
+
+    /// Check core accounting invariants; emits an alarm and optionally pauses on violation
+    pub fn verify_invariants(env: &Env) -> Vec<Symbol> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        let mut violations: Vec<Symbol> = vec![env];
+
+        // Revealed votes can't outnumber the panel
+        for id in Self::dispute_ids(env).iter() {
+            let dispute = Self::load_dispute(env, &id).unwrap();
+            if dispute.votes_for + dispute.votes_against > dispute.jurors.len() {
+                violations.push_back(symbol_short!("tally"));
+                break;
+            }
+        }
+
+        if !violations.is_empty() {
+            env.events().publish((symbol_short!("inv_alarm"),), violations.clone());
+            if data.auto_pause && !data.paused {
+                data.paused = true;
+                ttl::set_instance(env, &DATA_KEY, &data);
+            }
+        }
+
+        violations
+    }
+
+    /// Enable or disable pausing when an invariant check fails (pauser only)
+    pub fn set_auto_pause(env: &Env, caller: Address, enabled: bool) -> Result<(), ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Pauser, &caller)?;
+
+        data.auto_pause = enabled;
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        Ok(())
+    }
+
+    /// Halt state-changing entrypoints, e.g. while an oracle is compromised; views keep working (pauser only)
+    pub fn pause(env: &Env, caller: Address) -> Result<(), ContractError> {
+        Self::set_paused(env, caller, true)
+    }
+
+    /// Resume state-changing entrypoints after a pause (pauser only)
+    pub fn unpause(env: &Env, caller: Address) -> Result<(), ContractError> {
+        Self::set_paused(env, caller, false)
+    }
+
+    /// Whether state-changing entrypoints are halted
+    pub fn is_paused(env: &Env) -> bool {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        data.paused
+    }
+
+    fn set_paused(env: &Env, caller: Address, paused: bool) -> Result<(), ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Pauser, &caller)?;
+
+        data.paused = paused;
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        let topic = if paused { symbol_short!("paused") } else { symbol_short!("unpaused") };
+        env.events().publish((topic,), env.ledger().timestamp());
+
+        Ok(())
+    }
+
+    /// Switch one entrypoint feature on or off without pausing the whole contract (pauser only)
+    pub fn set_feature(env: &Env, caller: Address, feature: Symbol, enabled: bool) -> Result<(), ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Pauser, &caller)?;
+
+        if let Some(index) = data.disabled_features.first_index_of(&feature) {
+            data.disabled_features.remove(index);
+        }
+        if !enabled {
+            data.disabled_features.push_back(feature.clone());
+        }
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        env.events().publish((symbol_short!("feature"), feature), enabled);
+
+        Ok(())
+    }
+
+    /// Whether an entrypoint feature is switched on
+    pub fn is_feature_enabled(env: &Env, feature: Symbol) -> bool {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        !data.disabled_features.contains(&feature)
+    }
+
+    /// Grant a role to an account (admin only)
+    pub fn grant_role(env: &Env, caller: Address, role: Role, account: Address) -> Result<(), ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+        access::grant(env, role, &account);
+
+        Ok(())
+    }
+
+    /// Revoke a role previously granted to an account (admin only)
+    pub fn revoke_role(env: &Env, caller: Address, role: Role, account: Address) -> Result<(), ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+        access::revoke(env, role, &account).map_err(ContractError::from)
+    }
+
+    /// Whether an account holds a role; the admin holds every role
+    pub fn has_role(env: &Env, role: Role, account: Address) -> bool {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        access::holds(env, &data.admin, role, &account)
+    }
+
+    /// Extend the TTL of the contract instance and its code. Open to anyone, so a keeper can
+    /// stop a quiet contract from being archived between writes
+    pub fn bump_storage(env: &Env) {
+        ttl::extend_instance(env);
+    }
+
+    /// Set the remaining TTL below which writes extend an entry, and the TTL they extend it to (admin only)
+    pub fn set_ttl_config(env: &Env, caller: Address, threshold: u32, extend_to: u32) -> Result<(), ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+        ttl::set_config(env, threshold, extend_to).map_err(ContractError::from)
+    }
+
+    /// Get the storage TTL configuration
+    pub fn get_ttl_config(env: &Env) -> TtlConfig {
+        ttl::config(env)
+    }
+
+    /// Push change notifications to a relay contract, or stop with None (admin only)
+    pub fn set_relay(env: &Env, caller: Address, relay: Option<Address>) -> Result<(), ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+        changes::set_relay(env, relay);
+
+        Ok(())
+    }
+
+    /// Get a page of disputes created or modified after a ledger with their last-modified ledger,
+    /// in ledger order, so indexers can sync incrementally. Supported kinds: "dispute"
+    pub fn get_changes_since(env: &Env, ledger_seq: u32, kind: Symbol, page: PageRequest) -> Result<DisputeChangePage, ContractError> {
+        if kind != symbol_short!("dispute") {
+            return Err(ContractError::InvalidInput);
+        }
+        let (items, next_cursor) = changes::changes_since(env, kind, ledger_seq, &page, |id, ledger| DisputeChange { id, ledger });
+        Ok(DisputeChangePage { items, next_cursor })
+    }
+
+    /// Load a dispute from its own entry
+    fn load_dispute(env: &Env, dispute_id: &u32) -> Option<Dispute> {
+        env.storage().persistent().get(&StorageKey::Dispute(*dispute_id))
+    }
+
+    /// Write a dispute, indexing it the first time it is written
+    fn save_dispute(env: &Env, dispute: &Dispute) {
+        let key = StorageKey::Dispute(dispute.id);
+        if !env.storage().persistent().has(&key) {
+            let mut ids = Self::dispute_ids(env);
+            ids.push_back(dispute.id);
+            ttl::set_persistent(env, &StorageKey::DisputeIds, &ids);
+        }
+        ttl::set_persistent(env, &key, dispute);
+    }
+
+    /// Every dispute id, in filing order
+    fn dispute_ids(env: &Env) -> Vec<u32> {
+        env.storage().persistent().get(&StorageKey::DisputeIds).unwrap_or(vec![env])
+    }
+
+    /// Get a dispute
+    pub fn get_dispute(env: &Env, dispute_id: u32) -> Result<Dispute, ContractError> {
+        Self::load_dispute(env, &dispute_id).ok_or(ContractError::NotFound)
+    }
+
+    /// Get a juror's vote for a given dispute round
+    pub fn get_juror_vote(env: &Env, dispute_id: u32, round: u32, juror: Address) -> Option<JurorVote> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        data.votes.get((dispute_id, round, juror))
+    }
+
+    /// Get a juror's registration
+    pub fn get_juror(env: &Env, juror: Address) -> Result<Juror, ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        data.jurors.get(juror).ok_or(ContractError::NoJuror)
+    }
+
+    /// Compute the commitment for a vote, so jurors and verifiers can check it
+    pub fn vote_commitment(env: &Env, vote: bool, salt: BytesN<32>) -> BytesN<32> {
+        let mut payload = Bytes::new(env);
+        payload.push_back(if vote { 1 } else { 0 });
+        payload.append(&salt.into());
+        env.crypto().sha256(&payload)
+    }
+
+    /// Draw a panel weighted by stake and equity score, excluding parties and their delegates
+    fn select_panel(
+        env: &Env,
+        data: &DataKey,
+        dispute_id: u32,
+        round: u32,
+        claimant: &Address,
+        respondent: &Address,
+        panel_size: u32,
+    ) -> Result<(Vec<Address>, BytesN<32>), ContractError> {
+        // Build the eligible candidate list with selection weights
+        let mut candidates: Vec<Address> = vec![env];
+        let mut weights: Vec<i128> = vec![env];
+        for juror in data.juror_list.iter() {
+            let record = data.jurors.get(juror.clone()).unwrap();
+            if !record.active || record.stake <= 0 {
+                continue;
+            }
+            if Self::is_conflicted(data, &juror, claimant) || Self::is_conflicted(data, &juror, respondent) {
+                continue;
+            }
+            // Equity score adds up to 100% on top of the stake weight. Every eligible juror keeps
+            // a nonzero chance, since a zero weight marks a juror already drawn
+            candidates.push_back(juror);
+            weights.push_back((record.stake * (100 + record.equity_score as i128) / 100).max(1));
+        }
+
+        if candidates.len() < panel_size {
+            return Err(ContractError::FewJurors);
+        }
+
+        // Public inputs alone would let a party time the filing to pick its panel, so the seed
+        // mixes in the network PRNG. It is stored on the dispute so anyone can replay the draw
+        let entropy: BytesN<32> = env.prng().gen();
+        let seed = env.crypto().sha256(
+            &(entropy, dispute_id, round, env.ledger().sequence(), claimant.clone(), respondent.clone()).to_xdr(env),
+        );
+        env.prng().seed(seed.clone().into());
+
+        let mut panel: Vec<Address> = vec![env];
+        while panel.len() < panel_size {
+            let mut total_weight: i128 = 0;
+            for weight in weights.iter() {
+                total_weight += weight;
+            }
+
+            // Walk the cumulative weights to the drawn point
+            let draw = env.prng().gen_range::<u64>(0..total_weight as u64) as i128;
+            let mut cumulative: i128 = 0;
+            for i in 0..weights.len() {
+                let weight = weights.get(i).unwrap();
+                if weight == 0 {
+                    continue;
+                }
+                cumulative += weight;
+                if draw < cumulative {
+                    panel.push_back(candidates.get(i).unwrap());
+                    // Select without replacement
+                    weights.set(i, 0);
+                    break;
+                }
+            }
+        }
+
+        Ok((panel, seed))
+    }
+
+    /// A juror is conflicted if they are the party, the party's delegate, or delegate to the party
+    fn is_conflicted(data: &DataKey, juror: &Address, party: &Address) -> bool {
+        if juror == party {
+            return true;
+        }
+        if data.delegates.get(party.clone()) == Some(juror.clone()) {
+            return true;
+        }
+        data.delegates.get(juror.clone()) == Some(party.clone())
+    }
+}
+
+#[cfg(test)]
+mod test;
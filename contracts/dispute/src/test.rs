@@ -0,0 +1,214 @@
+#![cfg(test)]
+
+extern crate std;
+
+use super::*;
+use soroban_sdk::{
+    symbol_short, Address, BytesN, Env, TryFromVal,
+    testutils::{Address as _, Events, Ledger},
+    xdr::{LedgerKey, ScAddress, ScVal},
+};
+use mobility_types::ttl::{DAY_IN_LEDGERS, DEFAULT_EXTEND_TO, MAX_EXTEND_TO};
+
+/// Register a contract with `count` fresh jurors of equal stake
+fn setup(env: &Env, count: u32) -> (DisputeResolutionClient<'_>, std::vec::Vec<Address>) {
+    let contract_id = env.register_contract(None, DisputeResolution);
+    let client = DisputeResolutionClient::new(env, &contract_id);
+    client.initialize(&Address::generate(env), &Address::generate(env));
+
+    let mut jurors = std::vec::Vec::new();
+    for _ in 0..count {
+        let juror = Address::generate(env);
+        client.register_juror(&juror, &1_000);
+        jurors.push(juror);
+    }
+    (client, jurors)
+}
+
+/// Ledger the contract instance stays live until, read from a ledger snapshot
+fn instance_live_until(env: &Env, contract_id: &Address) -> u32 {
+    let contract = ScAddress::try_from(contract_id).unwrap();
+    env.to_ledger_snapshot()
+        .ledger_entries
+        .iter()
+        .find_map(|(key, (_, live_until))| match key.as_ref() {
+            LedgerKey::ContractData(data) if data.contract == contract && data.key == ScVal::LedgerKeyContractInstance => *live_until,
+            _ => None,
+        })
+        .unwrap()
+}
+
+#[test]
+fn test_panel_excludes_parties_and_their_delegates() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, jurors) = setup(&env, 5);
+
+    // The claimant is a juror and the respondent named another as its delegate
+    let claimant = jurors[0].clone();
+    let respondent = Address::generate(&env);
+    client.set_delegate(&respondent, &jurors[1]);
+
+    // Three jurors are left, exactly one panel's worth
+    let dispute_id = client.open_dispute(&claimant, &respondent, &symbol_short!("ebike_001"));
+    let panel = client.get_dispute(&dispute_id).jurors;
+    assert_eq!(panel.len(), 3);
+    assert!(!panel.contains(&jurors[0]));
+    assert!(!panel.contains(&jurors[1]));
+
+    // A juror who delegates to a party is conflicted too, leaving too few for a panel
+    client.set_delegate(&jurors[2], &claimant);
+    assert_eq!(
+        client.try_open_dispute(&claimant, &respondent, &symbol_short!("ebike_002")),
+        Err(Ok(ContractError::FewJurors))
+    );
+}
+
+#[test]
+fn test_commit_reveal_and_tied_rounds_favor_the_respondent() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _) = setup(&env, 3);
+    let claimant = Address::generate(&env);
+    let respondent = Address::generate(&env);
+
+    let dispute_id = client.open_dispute(&claimant, &respondent, &symbol_short!("ebike_001"));
+    let panel = client.get_dispute(&dispute_id).jurors;
+    let (first, second, silent) = (panel.get(0).unwrap(), panel.get(1).unwrap(), panel.get(2).unwrap());
+    let salt = BytesN::from_array(&env, &[9; 32]);
+
+    // Only panel members commit, once each
+    let outsider = Address::generate(&env);
+    let commitment = client.vote_commitment(&true, &salt);
+    assert_eq!(client.try_commit_vote(&outsider, &dispute_id, &commitment), Err(Ok(ContractError::Unauthorized)));
+    client.commit_vote(&first, &dispute_id, &commitment);
+    assert_eq!(client.try_commit_vote(&first, &dispute_id, &commitment), Err(Ok(ContractError::Done)));
+    client.commit_vote(&second, &dispute_id, &client.vote_commitment(&false, &salt));
+    client.commit_vote(&silent, &dispute_id, &client.vote_commitment(&true, &salt));
+
+    // Reveals open after the commit period and must match the commitment
+    assert_eq!(client.try_reveal_vote(&first, &dispute_id, &true, &salt), Err(Ok(ContractError::InvalidStatus)));
+    env.ledger().with_mut(|ledger| ledger.timestamp += COMMIT_PERIOD + 1);
+    assert_eq!(client.try_reveal_vote(&first, &dispute_id, &false, &salt), Err(Ok(ContractError::BadReveal)));
+    client.reveal_vote(&first, &dispute_id, &true, &salt);
+    assert_eq!(client.try_reveal_vote(&first, &dispute_id, &true, &salt), Err(Ok(ContractError::Done)));
+    client.reveal_vote(&second, &dispute_id, &false, &salt);
+    assert!(client.get_juror_vote(&dispute_id, &0, &first).unwrap().vote);
+
+    // An unrevealed vote doesn't count, so one vote each way is a tie
+    assert_eq!(client.try_finalize_round(&dispute_id), Err(Ok(ContractError::TooEarly)));
+    env.ledger().with_mut(|ledger| ledger.timestamp += REVEAL_PERIOD);
+    assert!(!client.finalize_round(&dispute_id));
+
+    let dispute = client.get_dispute(&dispute_id);
+    assert_eq!((dispute.votes_for, dispute.votes_against), (1, 1));
+    assert_eq!(dispute.ruling, Some(false));
+    assert_eq!(dispute.status, symbol_short!("ruled"));
+}
+
+#[test]
+fn test_appeals_draw_larger_panels_until_final() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _) = setup(&env, 15);
+    let claimant = Address::generate(&env);
+    let respondent = Address::generate(&env);
+
+    let dispute_id = client.open_dispute(&claimant, &respondent, &symbol_short!("ebike_001"));
+    let first_seed = client.get_dispute(&dispute_id).selection_seed;
+
+    // Only parties appeal, and only a ruled round
+    assert_eq!(client.try_appeal(&claimant, &dispute_id), Err(Ok(ContractError::InvalidStatus)));
+    env.ledger().with_mut(|ledger| ledger.timestamp += COMMIT_PERIOD + REVEAL_PERIOD + 1);
+    client.finalize_round(&dispute_id);
+    assert_eq!(client.try_appeal(&Address::generate(&env), &dispute_id), Err(Ok(ContractError::Unauthorized)));
+
+    // Panels grow 3, 7, 15, each drawn from a fresh seed
+    assert_eq!(client.appeal(&claimant, &dispute_id).len(), 7);
+    let dispute = client.get_dispute(&dispute_id);
+    assert_eq!(dispute.round, 1);
+    assert_eq!(dispute.status, symbol_short!("commit"));
+    assert_ne!(dispute.selection_seed, first_seed);
+
+    env.ledger().with_mut(|ledger| ledger.timestamp += COMMIT_PERIOD + REVEAL_PERIOD + 1);
+    client.finalize_round(&dispute_id);
+    assert_eq!(client.appeal(&respondent, &dispute_id).len(), 15);
+
+    // The last round's ruling can't be appealed
+    env.ledger().with_mut(|ledger| ledger.timestamp += COMMIT_PERIOD + REVEAL_PERIOD + 1);
+    client.finalize_round(&dispute_id);
+    assert_eq!(client.get_dispute(&dispute_id).status, symbol_short!("final"));
+    assert_eq!(client.try_appeal(&claimant, &dispute_id), Err(Ok(ContractError::InvalidStatus)));
+}
+
+#[test]
+fn test_migrate_walks_stored_layouts_up_to_the_running_version() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, DisputeResolution);
+    let client = DisputeResolutionClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &Address::generate(&env));
+    assert_eq!(client.get_version(), client.version());
+
+    // Only the admin may swap code or migrate storage
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_upgrade(&stranger, &BytesN::from_array(&env, &[0; 32])),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    assert_eq!(client.try_migrate(&stranger), Err(Ok(ContractError::Unauthorized)));
+
+    // Storage already at the running layout is left alone
+    assert_eq!(client.migrate(&admin), CONTRACT_VERSION);
+
+    // Storage from an older layout is brought up to date and announced
+    env.as_contract(&contract_id, || env.storage().instance().set(&INIT_KEY, &1u32));
+    assert_eq!(client.get_version(), 1);
+    assert_eq!(client.migrate(&admin), CONTRACT_VERSION);
+    assert_eq!(client.get_version(), CONTRACT_VERSION);
+    let (_, topics, payload) = env.events().all().last().unwrap();
+    assert_eq!(Symbol::try_from_val(&env, &topics.get(0).unwrap()).unwrap(), symbol_short!("migrated"));
+    assert_eq!(<(u32, u32)>::try_from_val(&env, &payload).unwrap(), (1, CONTRACT_VERSION));
+
+    // Storage written by newer code than is running is refused
+    env.as_contract(&contract_id, || env.storage().instance().set(&INIT_KEY, &(CONTRACT_VERSION + 1)));
+    assert_eq!(client.try_migrate(&admin), Err(Ok(ContractError::BadLegacy)));
+}
+
+#[test]
+fn test_storage_ttls_follow_the_configured_threshold_and_extension() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, DisputeResolution);
+    let client = DisputeResolutionClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &Address::generate(&env));
+    let start = env.ledger().sequence();
+    assert_eq!(instance_live_until(&env, &contract_id), start + DEFAULT_EXTEND_TO);
+
+    assert_eq!(
+        client.try_set_ttl_config(&Address::generate(&env), &(10 * DAY_IN_LEDGERS), &(60 * DAY_IN_LEDGERS)),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_set_ttl_config(&admin, &(60 * DAY_IN_LEDGERS), &(60 * DAY_IN_LEDGERS)),
+        Err(Ok(ContractError::InvalidInput))
+    );
+    assert_eq!(
+        client.try_set_ttl_config(&admin, &(10 * DAY_IN_LEDGERS), &(MAX_EXTEND_TO + 1)),
+        Err(Ok(ContractError::InvalidInput))
+    );
+    client.set_ttl_config(&admin, &(10 * DAY_IN_LEDGERS), &(60 * DAY_IN_LEDGERS));
+    assert_eq!(client.get_ttl_config(), TtlConfig { threshold: 10 * DAY_IN_LEDGERS, extend_to: 60 * DAY_IN_LEDGERS });
+
+    // A bump with more than the threshold left changes nothing
+    env.ledger().with_mut(|ledger| ledger.sequence_number += 100 * DAY_IN_LEDGERS);
+    client.bump_storage();
+    assert_eq!(instance_live_until(&env, &contract_id), start + DEFAULT_EXTEND_TO);
+
+    // Once under the threshold, anyone's bump extends the instance to the configured TTL
+    env.ledger().with_mut(|ledger| ledger.sequence_number += 15 * DAY_IN_LEDGERS);
+    client.bump_storage();
+    assert_eq!(instance_live_until(&env, &contract_id), env.ledger().sequence() + 60 * DAY_IN_LEDGERS);
+}
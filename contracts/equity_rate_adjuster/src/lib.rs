@@ -1,8 +1,19 @@
 #![no_std]
+// Entrypoints take their arguments flat, so signed and relayed calls carry many
+#![allow(clippy::too_many_arguments)]
 use soroban_sdk::{
-    contract, contractimpl, contracttype, symbol_short, vec, Address, Env, Map, Symbol, Vec,
+    contract, contractimpl, contracttype, symbol_short, vec, xdr::ToXdr, Address, Bytes, BytesN,
+    Env, Map, Symbol, Vec,
 };
 
+use mobility_types::{access, attestation, changes, ids, journal, page_result, timelock, trustline, ttl, upgrade, ChangeLogPage, ChangeValue, FeederKey, EpochSchedule, Gate, EquityOracleInterface, GovernanceClient, LoanPoolClient, PageRequest, RecordChange, RecordChangePage, PendingChange, RateStats, RiskData, TokenClient, Role, TtlConfig, MAX_PAGE_SIZE};
+
+mobility_types::contract_errors! {
+    ApplicationNotFound = 9,
+    ExperimentLive = 52,
+    OutOfBand = 109,
+}
+
 /// Represents urban data used for AI-driven rate adjustments
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -27,223 +38,2383 @@ pub struct LoanApplication {
     pub adjusted_rate: i32, // AI-adjusted rate (percentage)
     pub equity_score: i32, // AI-calculated equity score (0-100)
     pub urban_data: UrbanData,
-    pub status: Symbol, // "pending", "approved", "rejected", "active", "completed"
+    pub status: Symbol, // "pending", "approved", "rejected", "active", "completed", "defaulted"
     pub created_at: u64,
 }
 
+page_result!(ApplicationPage, LoanApplication);
+
+/// Principal disbursed on an active loan
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LoanRecord {
+    pub application_id: Symbol,
+    pub borrower: Address,
+    pub principal: i128, // Capital drawn from the loan pool and sent to the borrower
+    pub outstanding_principal: i128,
+    pub disbursed_at: u64,
+    pub amortization_start: u64, // First installment falls due one payment interval after this
+    pub accrued_interest: i128, // Interest accrued up to accrued_at and not yet paid
+    pub accrued_at: u64,
+    pub principal_repaid: i128,
+    pub interest_repaid: i128,
+    pub installment: i128, // Principal due each payment interval
+    pub late_fees: i128, // Charged on delinquency and not yet paid
+    pub stage: Symbol, // "current", "delinq", "default"
+}
+
+/// Hypothetical urban conditions used for what-if scoring
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UrbanDataInput {
+    pub income_level: i32, // 1-10 scale (1 = lowest income)
+    pub pollution_level: i32, // 1-10 scale (1 = lowest pollution)
+    pub public_transport_score: i32, // 1-10 scale (1 = poorest access)
+    pub population_density: i32, // 1-10 scale (1 = lowest density)
+}
+
+/// Result of running the scoring pipeline against hypothetical inputs
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScoreSimulation {
+    pub location: Symbol,
+    pub current_equity_score: i32, // Score from cached/mock data for the location
+    pub current_rate: i32,
+    pub simulated_equity_score: i32,
+    pub simulated_rate: i32,
+    pub score_delta: i32,
+    pub rate_delta: i32,
+}
+
+/// A borrower's interest payments within a rebate quarter
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BorrowerQuarter {
+    pub borrower: Address,
+    pub interest_paid: i128,
+    pub late_payments: i32,
+    pub equity_score: i32, // Highest equity score among the borrower's paid loans
+    pub rebate: i128, // Allocated when the quarter is finalized
+    pub claimed: bool,
+}
+
+/// Treasury-funded interest rebate pool for a quarter
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RebatePool {
+    pub quarter: u64,
+    pub pool_size: i128, // Set by governance
+    pub allocated: i128,
+    pub finalized: bool,
+}
+
+/// Canonical equity score for a location, cached in its own storage entry
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CachedEquityScore {
+    pub location: Symbol,
+    pub equity_score: i32,
+    pub source_timestamp: u64, // Timestamp of the urban data the score was derived from
+    pub updated_at: u64,
+}
+
+/// Hard min/max band for a rate parameter, bound by a governance referendum
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PolicyBand {
+    pub parameter: Symbol, // "base_rate", "max_adj", or "rate" (model output)
+    pub min: i128,
+    pub max: i128,
+    pub set_at: u64,
+}
+
+/// Down payment a prospective borrower is saving toward
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SavingsEscrow {
+    pub borrower: Address,
+    pub target: i128,
+    pub balance: i128,
+    pub target_reached: bool, // Cleared again by any withdrawal
+    pub reached_at: u64,
+}
+
+/// A borrower's zone and personal score over their loan term
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScoreTrajectory {
+    pub borrower: Address,
+    pub started_at: u64, // First interest payment on the loan
+    pub start_zone_score: i32,
+    pub zone_score: i32, // Latest equity score of the loan's location
+    pub personal_score: i32, // Derived from repayment and participation, starts at PERSONAL_SCORE_BASE
+    pub on_time_payments: u32,
+    pub late_payments: u32,
+    pub participation: u32, // Oracle-reported program activities (workshops, ride logs, votes)
+    pub history: Vec<(u64, i32, i32)>, // (timestamp, zone score, personal score) after each update
+    pub reward_tier: u32, // Highest improvement reward tier already claimed
+}
+
+/// Outcome counters for one arm of a rate experiment
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ArmMetrics {
+    pub applications: u32,
+    pub approved: u32,
+    pub rate_sum: i64, // Sum of adjusted rates, for the arm's average rate
+    pub on_time_payments: u32,
+    pub late_payments: u32,
+    pub interest_paid: i128,
+}
+
+/// Governance-run A/B test of an alternative rate model on a share of new applications
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RateExperiment {
+    pub id: u32,
+    pub name: Symbol,
+    pub treatment_bps: u32, // Share of borrowers assigned to the alternative model
+    pub alt_max_adjustment: i32, // Alternative model's maximum equity adjustment
+    pub alt_base_offset: i32, // Added to the base rate under the alternative model
+    pub starts_at: u64,
+    pub ends_at: u64, // Ended early if governance stops the experiment
+    pub control: ArmMetrics,
+    pub treatment: ArmMetrics,
+}
+
+/// Liveness of a registered oracle
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OracleHealth {
+    pub oracle: Address,
+    pub last_heartbeat: u64,
+    pub stale: bool,
+    pub primary: bool, // The oracle currently allowed to post urban data
+}
+
+/// Reason code attached to a rejection or veto
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum RejectionReason {
+    IncompleteInformation = 1,
+    Affordability = 2,
+    IneligibleLocation = 3,
+    Duplicate = 4,
+    PolicyViolation = 5,
+    SuspectedFraud = 6,
+    Other = 7,
+}
+
+/// Why a record was rejected, with hashes of any off-chain evidence
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RejectionRecord {
+    pub reason: RejectionReason,
+    pub evidence: Vec<BytesN<32>>, // Empty if no evidence was attached
+    pub timestamp: u64,
+}
+
+/// Anonymized rate model inputs and output at one step of an application's life, exported
+/// so the off-chain model can be retrained on on-chain decisions and their outcomes
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RateLogEntry {
+    pub seq: u32,
+    pub application_id: Symbol, // Joins a decision to its outcomes without naming the borrower
+    pub status: Symbol, // "pending" when the rate is set, then each status the application moves to
+    pub location: Symbol,
+    pub income_level: i32,
+    pub pollution_level: i32,
+    pub public_transport_score: i32,
+    pub population_density: i32,
+    pub equity_score: i32,
+    pub base_rate: i32,
+    pub adjusted_rate: i32,
+    pub requested_amount: i128,
+    pub timestamp: u64,
+}
+
+page_result!(RateLogPage, RateLogEntry);
+
+/// Compact audit record for an equity boost, bonus, or rate discount
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BoostAuditRecord {
+    pub subject: Address, // Who received the boost
+    pub amount: i128, // Size of the boost (units depend on rule)
+    pub rule: Symbol, // Which fairness rule granted it
+    pub data_version: u64, // Timestamp of the data the rule was evaluated against
+    pub timestamp: u64,
+}
+
+page_result!(BoostAuditPage, BoostAuditRecord);
+
 /// Contract data structure
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct DataKey {
     pub admin: Address,
     pub oracle: Address, // AI oracle address
-    pub applications: Map<Symbol, LoanApplication>,
     pub urban_data_cache: Map<Symbol, UrbanData>,
     pub base_rate: i32, // Default base rate (percentage)
     pub max_rate_adjustment: i32, // Maximum rate adjustment (percentage)
+    pub rebate_pools: Map<u64, RebatePool>,
+    pub borrower_quarters: Map<u64, Map<Address, BorrowerQuarter>>, // quarter -> borrower -> payments
+    pub policy_bands: Map<Symbol, PolicyBand>, // parameter -> band set by referendum
+    pub relay_keys: Map<Address, BytesN<32>>, // user -> ed25519 key authorizing relayed calls
+    pub relay_nonces: Map<Address, u64>, // user -> next expected relay nonce
+    pub savings: Map<Address, SavingsEscrow>, // borrower -> down payment escrow
+    pub paused: bool, // Set by admin or by a failed invariant check when auto_pause is on
+    pub disabled_features: Vec<Symbol>, // Entrypoint features switched off by admin
+    pub auto_pause: bool,
+    pub origination_fees: Map<Symbol, i128>, // application_id -> platform fee charged at approval
+    pub oracle_heartbeats: Map<Address, u64>, // registered oracle -> last heartbeat
+    pub rejections: Map<Symbol, RejectionRecord>, // application_id -> rejection reason
+    pub id_sequences: Map<Address, u32>, // borrower -> applications created, mixed into IDs
+    pub trajectories: Map<Address, ScoreTrajectory>, // borrower -> score trajectory over the loan term
+    pub rate_step_downs: Map<Address, i32>, // borrower -> rate points off their next application
+    pub funding_priority: Map<Address, bool>, // borrower -> priority access to the next funding round
+    pub experiments: Map<u32, RateExperiment>,
+    pub active_experiment: Option<u32>,
+    pub next_experiment_id: u32,
+    pub experiment_arms: Map<Symbol, (u32, bool)>, // application_id -> (experiment, in treatment arm)
+    pub risk_data: Map<Symbol, RiskData>, // location -> oracle-reported operating risk
+}
+
+/// Persistent entries for per-record state, so a call reads and rewrites only
+/// the records it touches instead of the whole instance entry
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum StorageKey {
+    Application(Symbol),
+    ApplicationIds, // Every application id, in submission order
+    BorrowerApplications(Address), // borrower -> their application ids, in submission order
+    Loan(Symbol), // application_id -> LoanRecord once disbursed
+    DefaultedAsset(Symbol), // asset_id -> application whose loan on it defaulted
+    RateLog(u32), // Append-only rate model changelog, by sequence
+    RateLogCount,
+    Governance, // Sets quarterly rebate pool sizes
+    LoanPool, // Holds the canonical equity score of listed assets
 }
 
-const DATA_KEY: Symbol = symbol_short!("DATA_KEY");
+const DATA_KEY: Symbol = symbol_short!("DATA_KEY");
+
+// Set once by initialize to the storage layout version; its presence marks the contract initialized
+const INIT_KEY: Symbol = symbol_short!("INIT");
+const CONTRACT_VERSION: u32 = 2;
+// Prefix for per-location equity score cache entries
+const EQ_SCORE_KEY: Symbol = symbol_short!("EQ_SCORE");
+// Persistent entries: (KPI_KEY, epoch) -> RateStats for loans approved that epoch
+const KPI_KEY: Symbol = symbol_short!("KPI");
+// Journal streams of BoostAuditRecords, by (BOOST_BY_SUBJECT, subject) and (BOOST_BY_RULE, rule)
+const BOOST_BY_SUBJECT: Symbol = symbol_short!("BOOST_SUB");
+const BOOST_BY_RULE: Symbol = symbol_short!("BOOST_RUL");
+
+// Amortization starts after a 30-day grace period from disbursement
+const AMORTIZATION_GRACE: u64 = 30 * 86_400;
+// Interest accrues at the adjusted annual rate, pro rata by the second
+const SECONDS_PER_YEAR: u64 = 365 * 86_400;
+
+// Principal amortizes in 12 monthly installments. An installment unpaid 7 days past its due date
+// makes the loan delinquent and charges a 5% late fee on it; 90 days past due it defaults
+const LOAN_INSTALLMENTS: u32 = 12;
+const LOAN_PAYMENT_INTERVAL: u64 = 30 * 86_400;
+const PAYMENT_GRACE: u64 = 7 * 86_400;
+const LOAN_DEFAULT_AFTER: u64 = 90 * 86_400;
+const LATE_FEE_BPS: i128 = 500;
+
+// Interest rebates are computed per budget cycle ("quarter") for borrowers in the top equity band
+const REBATE_EQUITY_BAND: i32 = 80;
+// A rebate never exceeds this share of the interest the borrower paid
+const MAX_REBATE_PERCENT: i128 = 50;
+
+// Oracles are marked stale when they miss heartbeats for this long
+const ORACLE_HEARTBEAT_TIMEOUT: u64 = 6 * 3_600;
+
+// Borrowers who reached their savings target get a score boost and a lower rate tier
+const SAVINGS_SCORE_BOOST: i32 = 10;
+const SAVINGS_RATE_DISCOUNT: i32 = 1;
+
+// Personal score starts here and moves with repayment and participation
+const PERSONAL_SCORE_BASE: i32 = 50;
+const MAX_TRAJECTORY_POINTS: u32 = 48;
+
+// Personal score gains that unlock each improvement reward tier.
+// Tier 1 takes a point off the next rate; tier 2 takes another and grants funding priority.
+const RATE_STEP_IMPROVEMENT: i32 = 10;
+const PRIORITY_IMPROVEMENT: i32 = 20;
+
+// Rate experiments treat at most half of new borrowers for at most six months
+const MAX_TREATMENT_BPS: u32 = 5_000;
+const MAX_EXPERIMENT_DURATION: u64 = 180 * 86_400;
+const MAX_ALT_BASE_OFFSET: i32 = 5;
+
+#[contract]
+pub struct EquityRateAdjuster;
+
+#[contractimpl]
+impl EquityRateAdjuster {
+    /// Initialize the contract with admin and AI oracle. Can only be called once.
+    pub fn initialize(env: &Env, admin: Address, oracle: Address, base_rate: i32) -> Result<(), ContractError> {
+        admin.require_auth();
+
+        if env.storage().instance().has(&INIT_KEY) || env.storage().instance().has(&DATA_KEY) {
+            return Err(ContractError::AlreadyInitialized);
+        }
+
+        let mut oracle_heartbeats = Map::new(env);
+        oracle_heartbeats.set(oracle.clone(), env.ledger().timestamp());
+
+        let data = DataKey {
+            admin,
+            oracle,
+            urban_data_cache: Map::new(env),
+            base_rate,
+            max_rate_adjustment: 15, // 15% maximum adjustment
+            rebate_pools: Map::new(env),
+            borrower_quarters: Map::new(env),
+            policy_bands: Map::new(env),
+            relay_keys: Map::new(env),
+            relay_nonces: Map::new(env),
+            savings: Map::new(env),
+            paused: false,
+            disabled_features: Vec::new(env),
+            auto_pause: false,
+            origination_fees: Map::new(env),
+            oracle_heartbeats,
+            rejections: Map::new(env),
+            id_sequences: Map::new(env),
+            trajectories: Map::new(env),
+            rate_step_downs: Map::new(env),
+            funding_priority: Map::new(env),
+            experiments: Map::new(env),
+            active_experiment: None,
+            next_experiment_id: 1,
+            experiment_arms: Map::new(env),
+            risk_data: Map::new(env),
+        };
+        ttl::set_instance(env, &DATA_KEY, &data);
+        ttl::set_instance(env, &INIT_KEY, &CONTRACT_VERSION);
+
+        Ok(())
+    }
+
+    /// Change the oracle and base rate set at initialization without touching other state (governance only)
+    pub fn reconfigure(env: &Env, oracle: Address, base_rate: i32) -> Result<(), ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        Self::governance(env)?.require_auth();
+
+        if !(1..=100).contains(&base_rate) {
+            return Err(ContractError::InvalidInput);
+        }
+        Self::check_policy_band(&data, &symbol_short!("base_rate"), base_rate as i128)?;
+
+        // A new oracle starts with a fresh heartbeat
+        if oracle != data.oracle {
+            data.oracle_heartbeats.set(oracle.clone(), env.ledger().timestamp());
+        }
+        data.oracle = oracle.clone();
+        data.base_rate = base_rate;
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        env.events().publish((symbol_short!("reconfig"),), (oracle, base_rate));
+
+        Ok(())
+    }
+
+    /// Set the base rate, within any policy band bound by referendum (governance only)
+    pub fn set_base_rate(env: &Env, base_rate: i32) -> Result<(), ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        Self::governance(env)?.require_auth();
+
+        if !(1..=100).contains(&base_rate) {
+            return Err(ContractError::InvalidInput);
+        }
+        Self::check_policy_band(&data, &symbol_short!("base_rate"), base_rate as i128)?;
+
+        data.base_rate = base_rate;
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        env.events().publish((symbol_short!("base_rate"),), base_rate);
+
+        Ok(())
+    }
+
+    /// Get the storage layout version recorded at initialization, or 0 if uninitialized
+    pub fn get_version(env: &Env) -> u32 {
+        env.storage().instance().get(&INIT_KEY).unwrap_or(0)
+    }
+
+    /// Version of the running code; `get_version` lags behind it until `migrate` runs
+    pub fn version(_env: &Env) -> u32 {
+        CONTRACT_VERSION
+    }
+
+    /// Replace the contract's code with an uploaded wasm, keeping its storage (admin only).
+    /// The new code's `migrate` must run before storage written by the old layout is used
+    pub fn upgrade(env: &Env, caller: Address, new_wasm_hash: BytesN<32>) -> Result<(), ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+        upgrade::upgrade(env, new_wasm_hash);
+
+        Ok(())
+    }
+
+    /// Bring storage up to this code's layout version after an upgrade (admin only)
+    pub fn migrate(env: &Env, caller: Address) -> Result<u32, ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+
+        let stored: u32 = env.storage().instance().get(&INIT_KEY).unwrap_or(0);
+        let version = upgrade::migrate(env, stored, CONTRACT_VERSION, Self::migrate_step)?;
+        ttl::set_instance(env, &INIT_KEY, &version);
+
+        Ok(version)
+    }
+
+    /// Rewrite storage from layout version `from` to `from + 1`. Each release that changes
+    /// the layout adds its step here
+    fn migrate_step(_env: &Env, _from: u32) -> Result<(), ContractError> {
+        Ok(())
+    }
+
+
+    /// Submit a loan application with AI-driven rate adjustment
+    pub fn submit_application(
+        env: &Env,
+        borrower: Address,
+        asset_id: Symbol,
+        requested_amount: i128,
+        location: Symbol,
+    ) -> Result<Symbol, ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        // No new loans against assets in a program that is winding down
+        let winding_down = Self::loan_pool(env)
+            .ok()
+            .map(|loan_pool| LoanPoolClient::new(env, &loan_pool).is_winding_down(&asset_id))
+            .unwrap_or(false);
+        Gate::new(data.paused, &data.disabled_features, &symbol_short!("apply"))
+            .program(winding_down)
+            .check()?;
+        
+        // Validate amount
+        if requested_amount <= 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        Self::ensure_oracle_live(env, &data)?;
+
+        // Generate application ID
+        let application_id = Self::generate_application_id(env, &mut data, &borrower, &asset_id);
+
+        // Get or fetch urban data for the location
+        let urban_data = Self::get_urban_data(env, &location);
+
+        // Use the loan pool's score for listed assets so both contracts agree
+        let mut equity_score = Self::asset_equity_score(env, &asset_id, &urban_data);
+
+        // A completed down payment boosts the score and unlocks a lower rate tier
+        let mut rate_base = data.base_rate;
+        if Self::has_savings_bonus(&data, &borrower) {
+            equity_score = (equity_score + SAVINGS_SCORE_BOOST).min(100);
+            rate_base -= SAVINGS_RATE_DISCOUNT;
+        }
+
+        // Claimed improvement rewards are spent on the next application
+        if let Some(step_down) = data.rate_step_downs.get(borrower.clone()) {
+            rate_base -= step_down;
+            data.rate_step_downs.remove(borrower.clone());
+        }
+
+        // Calculate AI-adjusted interest rate, under the alternative model for treated borrowers
+        let experiment_arm = Self::experiment_arm(env, &data, &borrower);
+        let adjusted_rate = match &experiment_arm {
+            Some((experiment, true)) => Self::model_rate(
+                &data,
+                &(rate_base + experiment.alt_base_offset),
+                &equity_score,
+                &urban_data,
+                experiment.alt_max_adjustment,
+            ),
+            _ => Self::calculate_adjusted_rate(env, &rate_base, &equity_score, &urban_data),
+        };
+        if let Some((mut experiment, treated)) = experiment_arm {
+            let arm = if treated { &mut experiment.treatment } else { &mut experiment.control };
+            arm.applications += 1;
+            arm.rate_sum += adjusted_rate as i64;
+            data.experiment_arms.set(application_id.clone(), (experiment.id, treated));
+            data.experiments.set(experiment.id, experiment);
+        }
+
+        // Audit any rate discount granted against the base rate
+        if adjusted_rate < data.base_rate {
+            let discount = (data.base_rate - adjusted_rate) as i128;
+            Self::log_boost(env, &borrower, discount, symbol_short!("rate_disc"), urban_data.timestamp);
+        }
+
+        let application = LoanApplication {
+            id: application_id.clone(),
+            borrower,
+            asset_id,
+            requested_amount,
+            base_rate: data.base_rate,
+            adjusted_rate,
+            equity_score,
+            urban_data: urban_data.clone(),
+            status: symbol_short!("pending"),
+            created_at: env.ledger().timestamp(),
+        };
+
+        // Store application
+        Self::save_application(env, &application);
+        changes::touch(env, symbol_short!("app"), application_id.clone());
+        env.events().publish(
+            (symbol_short!("applied"), application_id.clone()),
+            (application.borrower, application.requested_amount, application.adjusted_rate),
+        );
+        
+        // Cache urban data
+        data.urban_data_cache.set(location, urban_data);
+        
+        ttl::set_instance(env, &DATA_KEY, &data);
+        
+        Ok(application_id)
+    }
+
+    /// Submit a loan application on a borrower's behalf; a sponsor submits and pays fees
+    pub fn relay_submit_application(
+        env: &Env,
+        sponsor: Address,
+        borrower: Address,
+        asset_id: Symbol,
+        requested_amount: i128,
+        location: Symbol,
+        nonce: u64,
+        signature: BytesN<64>,
+    ) -> Result<Symbol, ContractError> {
+        sponsor.require_auth();
+
+        let payload = (
+            env.current_contract_address(),
+            Symbol::new(env, "submit_application"),
+            borrower.clone(),
+            asset_id.clone(),
+            requested_amount,
+            location.clone(),
+            nonce,
+        )
+            .to_xdr(env);
+        Self::verify_relayed_call(env, &borrower, payload, nonce, signature)?;
+
+        Self::submit_application(env, borrower, asset_id, requested_amount, location)
+    }
+
+    /// Register the ed25519 key that signs a user's relayed calls
+    pub fn register_relay_key(env: &Env, user: Address, public_key: BytesN<32>) -> Result<(), ContractError> {
+        user.require_auth();
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        data.relay_keys.set(user, public_key);
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        Ok(())
+    }
+
+    /// Get the nonce a user's next relayed call must be signed with
+    pub fn get_relay_nonce(env: &Env, user: Address) -> u64 {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        data.relay_nonces.get(user).unwrap_or(0)
+    }
+
+    /// Check a user's signature over a relayed call payload and consume its nonce
+    fn verify_relayed_call(env: &Env, user: &Address, payload: Bytes, nonce: u64, signature: BytesN<64>) -> Result<(), ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        let public_key = data.relay_keys.get(user.clone()).ok_or(ContractError::NotFound)?;
+        if nonce != data.relay_nonces.get(user.clone()).unwrap_or(0) {
+            return Err(ContractError::BadNonce);
+        }
+
+        // Panics if the signature does not match
+        env.crypto().ed25519_verify(&public_key, &payload, &signature);
+
+        data.relay_nonces.set(user.clone(), nonce + 1);
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        Ok(())
+    }
+
+    /// Open or retarget a down payment savings escrow
+    pub fn open_savings(env: &Env, borrower: Address, target: i128) -> Result<(), ContractError> {
+        borrower.require_auth();
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        if target <= 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        let mut escrow = data.savings.get(borrower.clone()).unwrap_or(SavingsEscrow {
+            borrower: borrower.clone(),
+            target,
+            balance: 0,
+            target_reached: false,
+            reached_at: 0,
+        });
+        escrow.target = target;
+        if escrow.balance < target {
+            escrow.target_reached = false;
+            escrow.reached_at = 0;
+        }
+        Self::update_savings_target(env, &mut escrow);
+        data.savings.set(borrower, escrow);
+
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        Ok(())
+    }
+
+    /// Add to a savings escrow; the deposit is held by this contract in the settlement asset
+    pub fn deposit_savings(env: &Env, borrower: Address, amount: i128) -> Result<i128, ContractError> {
+        borrower.require_auth();
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        if amount <= 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        let mut escrow = data.savings.get(borrower.clone()).ok_or(ContractError::Nothing)?;
+        let token = Self::settlement_asset(env)?;
+        TokenClient::new(env, &token).transfer(&borrower, &env.current_contract_address(), &amount);
+
+        escrow.balance += amount;
+        Self::update_savings_target(env, &mut escrow);
+        let balance = escrow.balance;
+        data.savings.set(borrower, escrow);
+
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        Ok(balance)
+    }
+
+    /// Withdraw from a savings escrow; this forfeits the bonus until the target is reached again
+    pub fn withdraw_savings(env: &Env, borrower: Address, amount: i128) -> Result<i128, ContractError> {
+        borrower.require_auth();
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        let mut escrow = data.savings.get(borrower.clone()).ok_or(ContractError::Nothing)?;
+
+        if amount <= 0 || amount > escrow.balance {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        let token = Self::settlement_asset(env)?;
+        trustline::check_trustline(env, &token, &borrower)?;
+
+        escrow.balance -= amount;
+        escrow.target_reached = false;
+        escrow.reached_at = 0;
+        let balance = escrow.balance;
+        data.savings.set(borrower.clone(), escrow);
+
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        TokenClient::new(env, &token).transfer(&env.current_contract_address(), &borrower, &amount);
+
+        Ok(balance)
+    }
+
+    /// Get a borrower's savings escrow
+    pub fn get_savings(env: &Env, borrower: Address) -> Result<SavingsEscrow, ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        data.savings.get(borrower).ok_or(ContractError::Nothing)
+    }
+
+    /// Mark the target reached once the balance covers it
+    fn update_savings_target(env: &Env, escrow: &mut SavingsEscrow) {
+        if !escrow.target_reached && escrow.balance >= escrow.target {
+            escrow.target_reached = true;
+            escrow.reached_at = env.ledger().timestamp();
+            env.events().publish((symbol_short!("sav_goal"), escrow.borrower.clone()), escrow.balance);
+        }
+    }
+
+    /// Whether a borrower currently qualifies for the savings bonus
+    fn has_savings_bonus(data: &DataKey, borrower: &Address) -> bool {
+        match data.savings.get(borrower.clone()) {
+            Some(escrow) => escrow.target_reached,
+            None => false,
+        }
+    }
+
+    /// Approve a loan application (admin only)
+    pub fn approve_application(env: &Env, caller: Address, application_id: Symbol) -> Result<(), ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        
+        // Only admin can approve applications
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+
+        let mut application = Self::load_application(env, &application_id).ok_or(ContractError::ApplicationNotFound)?;
+        
+        if application.status != symbol_short!("pending") {
+            return Err(ContractError::InvalidStatus);
+        }
+
+        application.status = symbol_short!("approved");
+        Self::save_application(env, &application);
+        changes::touch(env, symbol_short!("app"), application_id.clone());
+        env.events().publish((symbol_short!("approved"), application_id.clone()), application.adjusted_rate);
+
+        // Funding priority from an improvement reward covers one approved loan
+        data.funding_priority.remove(application.borrower);
+
+        // Count the discount delivered toward the epoch's KPIs
+        let key = (KPI_KEY, Self::epoch_schedule(env).epoch_at(env.ledger().timestamp()));
+        let mut stats: RateStats = env.storage().persistent().get(&key).unwrap_or_default();
+        stats.loans += 1;
+        stats.discount_bps += (application.base_rate - application.adjusted_rate) as i128 * 100;
+        ttl::set_persistent(env, &key, &stats);
+
+        Self::record_arm_outcome(&mut data, &application_id, |arm| arm.approved += 1);
+
+        // Origination fee follows the governance sliding scale for the zone's equity score
+        if let Ok(governance) = Self::governance(env) {
+            let fee_bps = GovernanceClient::new(env, &governance).get_fee_bps(&application.equity_score);
+            let fee = application.requested_amount * fee_bps as i128 / 10_000;
+            data.origination_fees.set(application_id.clone(), fee);
+            env.events().publish(
+                (symbol_short!("fee"), application_id.clone()),
+                (symbol_short!("orig"), application.equity_score, fee_bps, fee),
+            );
+        }
+        
+        ttl::set_instance(env, &DATA_KEY, &data);
+        
+        Ok(())
+    }
+
+    /// Disburse an approved loan (treasurer only): draw the asset's capital from the loan pool, send it to
+    /// the borrower in the settlement asset and start tracking outstanding principal
+    pub fn disburse(env: &Env, caller: Address, application_id: Symbol) -> Result<LoanRecord, ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Treasurer, &caller)?;
+
+        Gate::new(data.paused, &data.disabled_features, &symbol_short!("disburse")).check()?;
+
+        let mut application = Self::load_application(env, &application_id).ok_or(ContractError::ApplicationNotFound)?;
+        if application.status != symbol_short!("approved") {
+            return Err(ContractError::InvalidStatus);
+        }
+
+        let loan_pool = LoanPoolClient::new(env, &Self::loan_pool(env)?);
+        let token = loan_pool.get_settlement_asset().ok_or(ContractError::NoSettlementAsset)?;
+        trustline::check_trustline(env, &token, &application.borrower)?;
+
+        let principal = loan_pool.release_loan_capital(&application_id);
+        TokenClient::new(env, &token).transfer(&env.current_contract_address(), &application.borrower, &principal);
+
+        let now = env.ledger().timestamp();
+        let loan = LoanRecord {
+            application_id: application_id.clone(),
+            borrower: application.borrower.clone(),
+            principal,
+            outstanding_principal: principal,
+            disbursed_at: now,
+            amortization_start: now + AMORTIZATION_GRACE,
+            accrued_interest: 0,
+            accrued_at: now,
+            principal_repaid: 0,
+            interest_repaid: 0,
+            installment: (principal + LOAN_INSTALLMENTS as i128 - 1) / LOAN_INSTALLMENTS as i128,
+            late_fees: 0,
+            stage: symbol_short!("current"),
+        };
+        ttl::set_persistent(env, &StorageKey::Loan(application_id.clone()), &loan);
+
+        application.status = symbol_short!("active");
+        Self::save_application(env, &application);
+        changes::touch(env, symbol_short!("app"), application_id.clone());
+        env.events().publish((symbol_short!("disbursed"), application_id), (application.borrower, principal));
+
+        Ok(loan)
+    }
+
+    /// Get the disbursed principal of an active or completed loan
+    pub fn get_loan(env: &Env, application_id: Symbol) -> Result<LoanRecord, ContractError> {
+        env.storage()
+            .persistent()
+            .get(&StorageKey::Loan(application_id))
+            .ok_or(ContractError::NotFound)
+    }
+
+    /// Get the interest owed on a loan as of now, including interest not yet checkpointed
+    pub fn get_accrued_interest(env: &Env, application_id: Symbol) -> Result<i128, ContractError> {
+        let application = Self::load_application(env, &application_id).ok_or(ContractError::ApplicationNotFound)?;
+        let loan = Self::get_loan(env, application_id)?;
+        Ok(Self::accrue(env, &application, loan).accrued_interest)
+    }
+
+    /// Repay an active loan in the settlement asset, late fees and interest first, then principal. The
+    /// payment is recorded on the loan pool's ledger for the asset, which takes the tokens, and the
+    /// loan completes once nothing is owed. Returns the balance left
+    pub fn repay(env: &Env, application_id: Symbol, amount: i128) -> Result<i128, ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        Gate::new(data.paused, &data.disabled_features, &symbol_short!("repay")).check()?;
+
+        let mut application = Self::load_application(env, &application_id).ok_or(ContractError::ApplicationNotFound)?;
+        if application.status != symbol_short!("active") && application.status != symbol_short!("defaulted") {
+            return Err(ContractError::InvalidStatus);
+        }
+        application.borrower.require_auth();
+
+        let mut loan = Self::accrue(env, &application, Self::get_loan(env, application_id.clone())?);
+        let balance = loan.outstanding_principal + loan.accrued_interest + loan.late_fees;
+        if amount <= 0 || amount > balance {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        // Late fees come off first, then interest, then principal
+        let fees = amount.min(loan.late_fees);
+        let interest = (amount - fees).min(loan.accrued_interest);
+        let principal = amount - fees - interest;
+        LoanPoolClient::new(env, &Self::loan_pool(env)?).record_loan_repayment(
+            &application.borrower,
+            &application.asset_id,
+            &principal,
+            &(fees + interest),
+        );
+        loan.late_fees -= fees;
+        loan.accrued_interest -= interest;
+        loan.interest_repaid += interest;
+        loan.outstanding_principal -= principal;
+        loan.principal_repaid += principal;
+
+        // Catching up on missed installments cures a delinquency, but not a default
+        if loan.stage == symbol_short!("delinq") && Self::overdue_since(env, &loan).is_none() {
+            loan.stage = symbol_short!("current");
+            env.events().publish((symbol_short!("delinq"), application_id.clone()), loan.stage.clone());
+        }
+        ttl::set_persistent(env, &StorageKey::Loan(application_id.clone()), &loan);
+
+        env.events().publish((symbol_short!("repaid"), application_id.clone()), (principal, interest));
+
+        let remaining = balance - amount;
+        if remaining == 0 {
+            // A defaulted loan repaid in full no longer holds back the asset's benefits
+            env.storage().persistent().remove(&StorageKey::DefaultedAsset(application.asset_id.clone()));
+            application.status = symbol_short!("completed");
+            Self::save_application(env, &application);
+            changes::touch(env, symbol_short!("app"), application_id.clone());
+            env.events().publish((symbol_short!("completed"), application_id), loan.interest_repaid);
+        }
+
+        Ok(remaining)
+    }
+
+    /// Mark a loan delinquent once an installment is unpaid past the grace period, charging a late
+    /// fee on it. Anyone may call it
+    pub fn mark_delinquent(env: &Env, application_id: Symbol) -> Result<i128, ContractError> {
+        let application = Self::load_application(env, &application_id).ok_or(ContractError::ApplicationNotFound)?;
+        if application.status != symbol_short!("active") {
+            return Err(ContractError::InvalidStatus);
+        }
+
+        let mut loan = Self::get_loan(env, application_id.clone())?;
+        if loan.stage != symbol_short!("current") {
+            return Err(ContractError::InvalidStatus);
+        }
+
+        let due_at = Self::overdue_since(env, &loan).ok_or(ContractError::TooEarly)?;
+        if env.ledger().timestamp() <= due_at + PAYMENT_GRACE {
+            return Err(ContractError::TooEarly);
+        }
+
+        let late_fee = loan.installment * LATE_FEE_BPS / 10_000;
+        loan.late_fees += late_fee;
+        loan.stage = symbol_short!("delinq");
+        ttl::set_persistent(env, &StorageKey::Loan(application_id.clone()), &loan);
+
+        env.events().publish((symbol_short!("delinq"), application_id), loan.stage);
+        env.events().publish((symbol_short!("late_fee"), application.asset_id), late_fee);
+
+        Ok(late_fee)
+    }
+
+    /// Mark a delinquent loan defaulted once an installment is unpaid for the default period.
+    /// Anyone may call it. Other contracts see the default through is_asset_defaulted
+    pub fn mark_defaulted(env: &Env, application_id: Symbol) -> Result<i128, ContractError> {
+        let mut application = Self::load_application(env, &application_id).ok_or(ContractError::ApplicationNotFound)?;
+        if application.status != symbol_short!("active") {
+            return Err(ContractError::InvalidStatus);
+        }
+
+        let mut loan = Self::accrue(env, &application, Self::get_loan(env, application_id.clone())?);
+        if loan.stage != symbol_short!("delinq") {
+            return Err(ContractError::InvalidStatus);
+        }
+
+        let due_at = Self::overdue_since(env, &loan).ok_or(ContractError::TooEarly)?;
+        if env.ledger().timestamp() <= due_at + LOAN_DEFAULT_AFTER {
+            return Err(ContractError::TooEarly);
+        }
+
+        loan.stage = symbol_short!("default");
+        ttl::set_persistent(env, &StorageKey::Loan(application_id.clone()), &loan);
+        ttl::set_persistent(env, &StorageKey::DefaultedAsset(application.asset_id.clone()), &application_id);
+
+        application.status = symbol_short!("defaulted");
+        Self::save_application(env, &application);
+        changes::touch(env, symbol_short!("app"), application_id.clone());
+
+        let balance = loan.outstanding_principal + loan.accrued_interest + loan.late_fees;
+        env.events().publish((symbol_short!("delinq"), application_id.clone()), loan.stage);
+        env.events().publish((symbol_short!("defaulted"), application_id), (application.asset_id, balance));
+
+        Ok(balance)
+    }
+
+    /// Due date of the oldest installment still unpaid, if it has passed
+    fn overdue_since(env: &Env, loan: &LoanRecord) -> Option<u64> {
+        if loan.outstanding_principal <= 0 || loan.installment <= 0 {
+            return None;
+        }
+        let covered = (loan.principal_repaid / loan.installment) as u64;
+        if covered >= LOAN_INSTALLMENTS as u64 {
+            return None;
+        }
+        let due_at = loan.amortization_start + (covered + 1) * LOAN_PAYMENT_INTERVAL;
+        (env.ledger().timestamp() > due_at).then_some(due_at)
+    }
+
+    /// Bring a loan's accrued interest up to now at the application's adjusted rate
+    fn accrue(env: &Env, application: &LoanApplication, mut loan: LoanRecord) -> LoanRecord {
+        let now = env.ledger().timestamp();
+        if now > loan.accrued_at {
+            let elapsed = (now - loan.accrued_at) as i128;
+            loan.accrued_interest +=
+                loan.outstanding_principal * application.adjusted_rate as i128 * elapsed / (100 * SECONDS_PER_YEAR as i128);
+            loan.accrued_at = now;
+        }
+        loan
+    }
+
+    /// Get the origination fee charged for an approved application
+    pub fn get_origination_fee(env: &Env, application_id: Symbol) -> i128 {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        data.origination_fees.get(application_id).unwrap_or(0)
+    }
+
+    /// Reject a loan application (admin only)
+    pub fn reject_application(
+        env: &Env,
+        caller: Address,
+        application_id: Symbol,
+        reason: RejectionReason,
+        evidence: Option<BytesN<32>>,
+    ) -> Result<(), ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        
+        // Only admin can reject applications
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+
+        let mut application = Self::load_application(env, &application_id).ok_or(ContractError::ApplicationNotFound)?;
+        
+        if application.status != symbol_short!("pending") {
+            return Err(ContractError::InvalidStatus);
+        }
+
+        application.status = symbol_short!("rejected");
+        Self::save_application(env, &application);
+        changes::touch(env, symbol_short!("app"), application_id.clone());
+
+        let mut hashes = Vec::new(env);
+        if let Some(hash) = evidence.clone() {
+            hashes.push_back(hash);
+        }
+        data.rejections.set(application_id.clone(), RejectionRecord {
+            reason,
+            evidence: hashes,
+            timestamp: env.ledger().timestamp(),
+        });
+        env.events().publish((symbol_short!("rejected"), application_id.clone()), (reason, evidence));
+        
+        ttl::set_instance(env, &DATA_KEY, &data);
+        
+        Ok(())
+    }
+
+    /// Get the reason an application was rejected
+    pub fn get_rejection(env: &Env, application_id: Symbol) -> Option<RejectionRecord> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        data.rejections.get(application_id)
+    }
+
+    /// Get a page of applications created or modified after a ledger with their last-modified ledger,
+    /// in ledger order, so indexers can sync incrementally. Supported kinds: "app"
+    pub fn get_changes_since(env: &Env, ledger_seq: u32, kind: Symbol, page: PageRequest) -> Result<RecordChangePage, ContractError> {
+        if kind != symbol_short!("app") {
+            return Err(ContractError::InvalidInput);
+        }
+        let (items, next_cursor) = changes::changes_since(env, kind, ledger_seq, &page, |id, ledger| RecordChange { id, ledger });
+        Ok(RecordChangePage { items, next_cursor })
+    }
+
+    /// Load an application from its own entry
+    fn load_application(env: &Env, application_id: &Symbol) -> Option<LoanApplication> {
+        env.storage().persistent().get(&StorageKey::Application(application_id.clone()))
+    }
+
+    /// Write an application, indexing it the first time it is written
+    fn save_application(env: &Env, application: &LoanApplication) {
+        let key = StorageKey::Application(application.id.clone());
+        let previous_status = env.storage().persistent().get::<_, LoanApplication>(&key).map(|previous| previous.status);
+        if previous_status.is_none() {
+            let mut ids = Self::application_ids(env);
+            ids.push_back(application.id.clone());
+            ttl::set_persistent(env, &StorageKey::ApplicationIds, &ids);
+
+            let mut borrower_ids = Self::borrower_application_ids(env, &application.borrower);
+            borrower_ids.push_back(application.id.clone());
+            ttl::set_persistent(env, &StorageKey::BorrowerApplications(application.borrower.clone()), &borrower_ids);
+        }
+        ttl::set_persistent(env, &key, application);
+
+        if previous_status != Some(application.status.clone()) {
+            Self::log_rate(env, application);
+        }
+    }
+
+    /// Append an application's current rate inputs, output and status to the changelog
+    fn log_rate(env: &Env, application: &LoanApplication) {
+        let seq: u32 = env.storage().persistent().get(&StorageKey::RateLogCount).unwrap_or(0);
+        let urban_data = &application.urban_data;
+        let entry = RateLogEntry {
+            seq,
+            application_id: application.id.clone(),
+            status: application.status.clone(),
+            location: urban_data.location.clone(),
+            income_level: urban_data.income_level,
+            pollution_level: urban_data.pollution_level,
+            public_transport_score: urban_data.public_transport_score,
+            population_density: urban_data.population_density,
+            equity_score: application.equity_score,
+            base_rate: application.base_rate,
+            adjusted_rate: application.adjusted_rate,
+            requested_amount: application.requested_amount,
+            timestamp: env.ledger().timestamp(),
+        };
+        ttl::set_persistent(env, &StorageKey::RateLog(seq), &entry);
+        ttl::set_persistent(env, &StorageKey::RateLogCount, &(seq + 1));
+    }
+
+    /// Page through the rate model changelog in the order entries were appended. Experiment
+    /// assignments join on application id through `get_experiment_arm`
+    pub fn get_rate_log_page(env: &Env, page: PageRequest) -> RateLogPage {
+        let count = Self::get_rate_log_count(env);
+        let end = page.cursor.saturating_add(page.limit.min(MAX_PAGE_SIZE)).min(count);
+        let mut items = Vec::new(env);
+        for seq in page.cursor..end {
+            items.push_back(env.storage().persistent().get(&StorageKey::RateLog(seq)).unwrap());
+        }
+
+        RateLogPage {
+            items,
+            next_cursor: (end < count).then_some(end),
+        }
+    }
+
+    /// Number of entries in the rate model changelog
+    pub fn get_rate_log_count(env: &Env) -> u32 {
+        env.storage().persistent().get(&StorageKey::RateLogCount).unwrap_or(0)
+    }
+
+    /// Every application id, in submission order
+    fn application_ids(env: &Env) -> Vec<Symbol> {
+        env.storage().persistent().get(&StorageKey::ApplicationIds).unwrap_or(vec![env])
+    }
+
+    /// A borrower's application ids, in submission order
+    fn borrower_application_ids(env: &Env, borrower: &Address) -> Vec<Symbol> {
+        env.storage()
+            .persistent()
+            .get(&StorageKey::BorrowerApplications(borrower.clone()))
+            .unwrap_or(vec![env])
+    }
+
+    /// Load the applications a page of ids covers
+    fn applications_page(env: &Env, ids: &Vec<Symbol>, page: &PageRequest) -> ApplicationPage {
+        let (items, next_cursor) = page.collect(env, ids, |id| Self::load_application(env, &id).unwrap());
+        ApplicationPage { items, next_cursor }
+    }
+
+    /// Get application details
+    pub fn get_application(env: &Env, application_id: Symbol) -> Result<LoanApplication, ContractError> {
+        Self::load_application(env, &application_id).ok_or(ContractError::ApplicationNotFound)
+    }
+
+    /// Get all applications for a borrower
+    pub fn get_borrower_applications(env: &Env, borrower: Address) -> Vec<LoanApplication> {
+        let mut applications = vec![env];
+        
+        for id in Self::borrower_application_ids(env, &borrower).iter() {
+            applications.push_back(Self::load_application(env, &id).unwrap());
+        }
+        
+        applications
+    }
+
+    /// Get a page of a borrower's applications in submission order
+    pub fn get_borrower_applications_page(env: &Env, borrower: Address, page: PageRequest) -> ApplicationPage {
+        Self::applications_page(env, &Self::borrower_application_ids(env, &borrower), &page)
+    }
+
+    /// Get a page of all applications in submission order
+    pub fn get_applications_page(env: &Env, page: PageRequest) -> ApplicationPage {
+        Self::applications_page(env, &Self::application_ids(env), &page)
+    }
+
+    /// Update urban data (oracle only)
+    pub fn update_urban_data(
+        env: &Env,
+        location: Symbol,
+        income_level: i32,
+        pollution_level: i32,
+        public_transport_score: i32,
+        population_density: i32,
+    ) -> Result<(), ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        
+        // Only oracle can update urban data
+        data.oracle.require_auth();
+
+        Self::store_urban_data(env, data, location, income_level, pollution_level, public_transport_score, population_density)
+    }
+
+    /// Update urban data from a payload signed by a registered feeder key; anyone may relay it
+    pub fn submit_signed_urban_data(
+        env: &Env,
+        feeder: BytesN<32>,
+        location: Symbol,
+        income_level: i32,
+        pollution_level: i32,
+        public_transport_score: i32,
+        population_density: i32,
+        nonce: u64,
+        timestamp: u64,
+        signature: BytesN<64>,
+    ) -> Result<(), ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        attestation::verify_feeder(
+            env,
+            &feeder,
+            Symbol::new(env, "update_urban_data"),
+            (location.clone(), income_level, pollution_level, public_transport_score, population_density),
+            nonce,
+            timestamp,
+            &signature,
+        )?;
+
+        Self::store_urban_data(env, data, location, income_level, pollution_level, public_transport_score, population_density)
+    }
+
+    /// Register an ed25519 feeder key that signs oracle submissions, and how long a signed payload
+    /// stays valid (oracle manager only)
+    pub fn register_feeder(env: &Env, caller: Address, public_key: BytesN<32>, max_age: u64) -> Result<(), ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::OracleManager, &caller)?;
+        attestation::register_feeder(env, &public_key, max_age, env.ledger().timestamp()).map_err(ContractError::from)
+    }
+
+    /// Enable or disable one feeder key, leaving the others signing (oracle manager only)
+    pub fn set_feeder_enabled(env: &Env, caller: Address, public_key: BytesN<32>, enabled: bool) -> Result<(), ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::OracleManager, &caller)?;
+        attestation::set_feeder_enabled(env, &public_key, enabled).map_err(ContractError::from)
+    }
+
+    /// Schedule a new feeder key to replace an old one at `effective_at` (oracle manager only)
+    pub fn rotate_feeder(
+        env: &Env,
+        caller: Address,
+        old_key: BytesN<32>,
+        new_key: BytesN<32>,
+        max_age: u64,
+        effective_at: u64,
+    ) -> Result<(), ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::OracleManager, &caller)?;
+        attestation::rotate_feeder(env, &old_key, &new_key, max_age, effective_at).map_err(ContractError::from)
+    }
+
+    /// Get every registered feeder key with its state
+    pub fn get_feeders(env: &Env) -> Vec<FeederKey> {
+        attestation::feeders(env)
+    }
+
+    /// Cache urban data for a location once the submitting oracle is authenticated
+    fn store_urban_data(
+        env: &Env,
+        mut data: DataKey,
+        location: Symbol,
+        income_level: i32,
+        pollution_level: i32,
+        public_transport_score: i32,
+        population_density: i32,
+    ) -> Result<(), ContractError> {
+        Gate::new(data.paused, &data.disabled_features, &symbol_short!("oracle")).check()?;
+
+        // Data from a stale oracle is excluded
+        if Self::is_oracle_stale(env, &data, &data.oracle) {
+            return Err(ContractError::Stale);
+        }
+
+        let urban_data = UrbanData {
+            location: location.clone(),
+            income_level,
+            pollution_level,
+            public_transport_score,
+            population_density,
+            timestamp: env.ledger().timestamp(),
+        };
+
+        data.urban_data_cache.set(location, urban_data.clone());
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        // Refresh the canonical score cache and notify dependents
+        Self::refresh_equity_score_cache(env, &urban_data);
+        
+        Ok(())
+    }
+
+    /// Get the cached canonical equity score for a location
+    pub fn get_cached_equity_score(env: &Env, location: Symbol) -> Result<CachedEquityScore, ContractError> {
+        env.storage()
+            .persistent()
+            .get(&(EQ_SCORE_KEY, location))
+            .ok_or(ContractError::NotFound)
+    }
+
+    /// Get urban data for a location
+    pub fn get_urban_data_for_location(env: &Env, location: Symbol) -> Result<UrbanData, ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        data.urban_data_cache.get(location).ok_or(ContractError::NotFound)
+    }
+
+    /// Update operating risk data for a location (oracle only)
+    pub fn update_risk_data(
+        env: &Env,
+        location: Symbol,
+        theft_rate: u32,
+        vandalism_incidents: u32,
+        weather_downtime: u32,
+    ) -> Result<i32, ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        // Only oracle can update risk data
+        data.oracle.require_auth();
+
+        // Data from a stale oracle is excluded
+        if Self::is_oracle_stale(env, &data, &data.oracle) {
+            return Err(ContractError::Stale);
+        }
+
+        let risk = RiskData {
+            location: location.clone(),
+            theft_rate,
+            vandalism_incidents,
+            weather_downtime,
+            timestamp: env.ledger().timestamp(),
+        };
+        let premium = risk.premium();
+
+        data.risk_data.set(location.clone(), risk);
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        env.events().publish((symbol_short!("risk"), location), premium);
+
+        Ok(premium)
+    }
+
+    /// Get operating risk data for a location
+    pub fn get_risk_data(env: &Env, location: Symbol) -> Result<RiskData, ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        data.risk_data.get(location).ok_or(ContractError::NotFound)
+    }
+
+    /// Calculate rate adjustment based on equity factors
+    pub fn calculate_rate_adjustment(
+        env: &Env,
+        location: Symbol,
+    ) -> Result<i32, ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        Self::ensure_oracle_live(env, &data)?;
+
+        let urban_data = Self::get_urban_data(env, &location);
+        let equity_score = Self::canonical_equity_score(env, &urban_data);
+        let adjusted_rate = Self::calculate_adjusted_rate(env, &data.base_rate, &equity_score, &urban_data);
+        
+        Ok(adjusted_rate - data.base_rate)
+    }
+
+    /// Simulate how hypothetical urban data would shift a location's equity score and rate.
+    /// Runs the same pipeline as `submit_application` but never writes state.
+    pub fn simulate_score(
+        env: &Env,
+        location: Symbol,
+        input: UrbanDataInput,
+    ) -> Result<ScoreSimulation, ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        // Validate hypothetical inputs (1-10 scales)
+        for value in [
+            input.income_level,
+            input.pollution_level,
+            input.public_transport_score,
+            input.population_density,
+        ] {
+            if !(1..=10).contains(&value) {
+                return Err(ContractError::InvalidInput);
+            }
+        }
+
+        // Baseline from current (cached or mock) data
+        let current_data = Self::get_urban_data(env, &location);
+        let current_equity_score = Self::canonical_equity_score(env, &current_data);
+        let current_rate = Self::calculate_adjusted_rate(env, &data.base_rate, &current_equity_score, &current_data);
+
+        // Hypothetical scenario
+        let simulated_data = UrbanData {
+            location: location.clone(),
+            income_level: input.income_level,
+            pollution_level: input.pollution_level,
+            public_transport_score: input.public_transport_score,
+            population_density: input.population_density,
+            timestamp: env.ledger().timestamp(),
+        };
+        let simulated_equity_score = Self::calculate_equity_score(&simulated_data);
+        let simulated_rate = Self::calculate_adjusted_rate(env, &data.base_rate, &simulated_equity_score, &simulated_data);
+
+        Ok(ScoreSimulation {
+            location,
+            current_equity_score,
+            current_rate,
+            simulated_equity_score,
+            simulated_rate,
+            score_delta: simulated_equity_score - current_equity_score,
+            rate_delta: simulated_rate - current_rate,
+        })
+    }
+
+    /// Record an interest payment on an active loan (oracle only)
+    pub fn record_interest_payment(
+        env: &Env,
+        application_id: Symbol,
+        interest_paid: i128,
+        on_time: bool,
+    ) -> Result<(), ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        // Only oracle can report payments
+        data.oracle.require_auth();
+
+        if interest_paid < 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        let application = Self::load_application(env, &application_id).ok_or(ContractError::ApplicationNotFound)?;
+
+        let quarter = Self::current_quarter(env);
+        let mut borrowers = data.borrower_quarters.get(quarter).unwrap_or(Map::new(env));
+        let mut record = borrowers.get(application.borrower.clone()).unwrap_or(BorrowerQuarter {
+            borrower: application.borrower.clone(),
+            interest_paid: 0,
+            late_payments: 0,
+            equity_score: 0,
+            rebate: 0,
+            claimed: false,
+        });
+
+        Self::record_arm_outcome(&mut data, &application_id, |arm| {
+            arm.interest_paid += interest_paid;
+            if on_time {
+                arm.on_time_payments += 1;
+            } else {
+                arm.late_payments += 1;
+            }
+        });
+
+        record.interest_paid += interest_paid;
+        if !on_time {
+            record.late_payments += 1;
+        }
+        if application.equity_score > record.equity_score {
+            record.equity_score = application.equity_score;
+        }
+
+        borrowers.set(application.borrower.clone(), record);
+        data.borrower_quarters.set(quarter, borrowers);
+
+        let mut trajectory = Self::load_trajectory(env, &data, &application);
+        if on_time {
+            trajectory.on_time_payments += 1;
+        } else {
+            trajectory.late_payments += 1;
+        }
+        Self::update_trajectory(env, &mut data, &application, trajectory);
+
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        Ok(())
+    }
+
+    /// Record a borrower's participation in a program activity on an active loan (oracle only)
+    pub fn record_participation(env: &Env, application_id: Symbol) -> Result<i32, ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        // Only oracle can report participation
+        data.oracle.require_auth();
+
+        let application = Self::load_application(env, &application_id).ok_or(ContractError::ApplicationNotFound)?;
+
+        let mut trajectory = Self::load_trajectory(env, &data, &application);
+        trajectory.participation += 1;
+        let personal_score = Self::update_trajectory(env, &mut data, &application, trajectory);
+
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        Ok(personal_score)
+    }
+
+    /// Claim the improvement rewards a borrower's personal score has unlocked
+    pub fn claim_improvement_reward(env: &Env, borrower: Address) -> Result<u32, ContractError> {
+        borrower.require_auth();
+
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        Gate::new(data.paused, &data.disabled_features, &symbol_short!("improve")).check()?;
+
+        let mut trajectory = data.trajectories.get(borrower.clone()).ok_or(ContractError::NotFound)?;
+
+        let improvement = trajectory.personal_score - PERSONAL_SCORE_BASE;
+        let tier = if improvement >= PRIORITY_IMPROVEMENT {
+            2
+        } else if improvement >= RATE_STEP_IMPROVEMENT {
+            1
+        } else {
+            0
+        };
+        if tier <= trajectory.reward_tier {
+            return Err(ContractError::Nothing);
+        }
+
+        // One rate point per newly reached tier
+        let new_steps = (tier - trajectory.reward_tier) as i32;
+        let step_down = data.rate_step_downs.get(borrower.clone()).unwrap_or(0) + new_steps;
+        data.rate_step_downs.set(borrower.clone(), step_down);
+        if tier >= 2 {
+            data.funding_priority.set(borrower.clone(), true);
+        }
+
+        trajectory.reward_tier = tier;
+        data.trajectories.set(borrower.clone(), trajectory);
+        Self::log_boost(env, &borrower, new_steps as i128, symbol_short!("imp_rwd"), env.ledger().timestamp());
+
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        Ok(tier)
+    }
+
+    /// Get a borrower's score trajectory
+    pub fn get_score_trajectory(env: &Env, borrower: Address) -> Result<ScoreTrajectory, ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        data.trajectories.get(borrower).ok_or(ContractError::NotFound)
+    }
+
+    /// Get a borrower's unspent rate step-down and whether they hold funding priority
+    pub fn get_improvement_rewards(env: &Env, borrower: Address) -> (i32, bool) {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        (
+            data.rate_step_downs.get(borrower.clone()).unwrap_or(0),
+            data.funding_priority.get(borrower).unwrap_or(false),
+        )
+    }
+
+    /// A borrower's trajectory, started from the loan's current zone score on first use
+    fn load_trajectory(env: &Env, data: &DataKey, application: &LoanApplication) -> ScoreTrajectory {
+        data.trajectories.get(application.borrower.clone()).unwrap_or_else(|| {
+            let zone_score = Self::zone_score(env, application);
+            ScoreTrajectory {
+                borrower: application.borrower.clone(),
+                started_at: env.ledger().timestamp(),
+                start_zone_score: zone_score,
+                zone_score,
+                personal_score: PERSONAL_SCORE_BASE,
+                on_time_payments: 0,
+                late_payments: 0,
+                participation: 0,
+                history: vec![env],
+                reward_tier: 0,
+            }
+        })
+    }
+
+    /// Recompute both scores, append a history point and store the trajectory
+    fn update_trajectory(
+        env: &Env,
+        data: &mut DataKey,
+        application: &LoanApplication,
+        mut trajectory: ScoreTrajectory,
+    ) -> i32 {
+        // On-time payments and participation each add up to 30 and 20 points; late payments cost 10
+        let repayment = (2 * trajectory.on_time_payments as i32).min(30) - 10 * trajectory.late_payments as i32;
+        let participation = (2 * trajectory.participation as i32).min(20);
+        trajectory.personal_score = (PERSONAL_SCORE_BASE + repayment + participation).clamp(0, 100);
+        trajectory.zone_score = Self::zone_score(env, application);
+
+        // Keep the most recent points only
+        if trajectory.history.len() >= MAX_TRAJECTORY_POINTS {
+            trajectory.history.pop_front();
+        }
+        trajectory
+            .history
+            .push_back((env.ledger().timestamp(), trajectory.zone_score, trajectory.personal_score));
+
+        let personal_score = trajectory.personal_score;
+        data.trajectories.set(application.borrower.clone(), trajectory);
+        personal_score
+    }
+
+    /// Current cached equity score of a loan's location, or its score at submission
+    fn zone_score(env: &Env, application: &LoanApplication) -> i32 {
+        let cached: Option<CachedEquityScore> =
+            env.storage().persistent().get(&(EQ_SCORE_KEY, application.urban_data.location.clone()));
+        cached.map(|entry| entry.equity_score).unwrap_or(application.equity_score)
+    }
+
+    /// Current rebate quarter: the budget cycle of the shared epoch calendar
+    fn current_quarter(env: &Env) -> u64 {
+        Self::epoch_schedule(env).cycle_at(env.ledger().timestamp()) as u64
+    }
+
+    /// Shared epoch calendar held by governance, or the default before governance is set
+    fn epoch_schedule(env: &Env) -> EpochSchedule {
+        match Self::governance(env) {
+            Ok(governance) => GovernanceClient::new(env, &governance).get_epoch_schedule(),
+            Err(_) => EpochSchedule::default_schedule(),
+        }
+    }
+
+    /// Queue the treasury-funded rebate pool for a quarter behind the timelock; returns the change id (governance only).
+    /// The pool is drawn from governance in the settlement asset when the change is executed.
+    pub fn set_rebate_pool(env: &Env, quarter: u64, pool_size: i128) -> Result<u32, ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        // Only governance can size rebate pools
+        let governance = Self::governance(env)?;
+        governance.require_auth();
+
+        let value = ChangeValue::Encoded((quarter, pool_size).to_xdr(env));
+        timelock::queue(env, symbol_short!("rebate"), value, timelock::MIN_DELAY, timelock::MIN_DELAY, |change| {
+            Self::validate_admin_change(env, &data, change)
+        })
+    }
+
+    /// Reject negative pool sizes and pools that were already allocated
+    fn check_rebate_pool(data: &DataKey, quarter: u64, pool_size: i128) -> Result<(), ContractError> {
+        if pool_size < 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        if let Some(pool) = data.rebate_pools.get(quarter) {
+            if pool.finalized {
+                return Err(ContractError::Done);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Allocate a finished quarter's rebate pool to eligible borrowers.
+    /// Borrowers in the top equity band with no late payments share the pool
+    /// in proportion to equity score x interest paid.
+    pub fn finalize_rebate_quarter(env: &Env, quarter: u64) -> Result<i128, ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        if quarter >= Self::current_quarter(env) {
+            return Err(ContractError::TooEarly);
+        }
+
+        let mut pool = data.rebate_pools.get(quarter).ok_or(ContractError::NoAddress)?;
+        if pool.finalized {
+            return Err(ContractError::Done);
+        }
+
+        let mut borrowers = data.borrower_quarters.get(quarter).unwrap_or(Map::new(env));
+
+        let mut total_weight: i128 = 0;
+        for (_, record) in borrowers.iter() {
+            if Self::is_rebate_eligible(&record) {
+                total_weight += record.interest_paid * record.equity_score as i128;
+            }
+        }
+
+        if total_weight > 0 {
+            for (borrower, mut record) in borrowers.iter() {
+                if !Self::is_rebate_eligible(&record) {
+                    continue;
+                }
+                let weight = record.interest_paid * record.equity_score as i128;
+                let mut rebate = pool.pool_size * weight / total_weight;
+                let cap = record.interest_paid * MAX_REBATE_PERCENT / 100;
+                if rebate > cap {
+                    rebate = cap;
+                }
+                record.rebate = rebate;
+                pool.allocated += rebate;
+                borrowers.set(borrower.clone(), record);
+
+                if rebate > 0 {
+                    Self::log_boost(env, &borrower, rebate, symbol_short!("int_rebat"), quarter);
+                }
+            }
+        }
+
+        pool.finalized = true;
+        let allocated = pool.allocated;
+        let unallocated = pool.pool_size - pool.allocated;
+        data.rebate_pools.set(quarter, pool);
+        data.borrower_quarters.set(quarter, borrowers);
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        // Whatever the caps left unallocated goes back to the treasury
+        if unallocated > 0 {
+            let token = Self::settlement_asset(env)?;
+            TokenClient::new(env, &token).transfer(&env.current_contract_address(), &Self::governance(env)?, &unallocated);
+        }
+
+        Ok(allocated)
+    }
+
+    /// Claim an allocated interest rebate for a quarter
+    pub fn claim_rebate(env: &Env, borrower: Address, quarter: u64) -> Result<i128, ContractError> {
+        borrower.require_auth();
+        Self::settle_rebate(env, borrower, quarter)
+    }
+
+    /// Claim a rebate on a borrower's behalf; a sponsor submits and pays fees
+    pub fn relay_claim_rebate(
+        env: &Env,
+        sponsor: Address,
+        borrower: Address,
+        quarter: u64,
+        nonce: u64,
+        signature: BytesN<64>,
+    ) -> Result<i128, ContractError> {
+        sponsor.require_auth();
+
+        let payload = (
+            env.current_contract_address(),
+            Symbol::new(env, "claim_rebate"),
+            borrower.clone(),
+            quarter,
+            nonce,
+        )
+            .to_xdr(env);
+        Self::verify_relayed_call(env, &borrower, payload, nonce, signature)?;
+
+        Self::settle_rebate(env, borrower, quarter)
+    }
+
+    /// Mark a borrower's finalized rebate as claimed and pay it out in the settlement asset
+    fn settle_rebate(env: &Env, borrower: Address, quarter: u64) -> Result<i128, ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        Gate::new(data.paused, &data.disabled_features, &symbol_short!("rebate")).check()?;
+
+        let pool = data.rebate_pools.get(quarter).ok_or(ContractError::NoAddress)?;
+        if !pool.finalized {
+            return Err(ContractError::InvalidStatus);
+        }
+
+        let mut borrowers = data.borrower_quarters.get(quarter).ok_or(ContractError::Nothing)?;
+        let mut record = borrowers.get(borrower.clone()).ok_or(ContractError::Nothing)?;
+
+        if record.claimed || record.rebate == 0 {
+            return Err(ContractError::Nothing);
+        }
+
+        let token = Self::settlement_asset(env)?;
+        trustline::check_trustline(env, &token, &borrower)?;
+
+        record.claimed = true;
+        borrowers.set(borrower.clone(), record.clone());
+        data.borrower_quarters.set(quarter, borrowers);
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        TokenClient::new(env, &token).transfer(&env.current_contract_address(), &borrower, &record.rebate);
+
+        Ok(record.rebate)
+    }
+
+    /// Get a borrower's payments and rebate for a quarter
+    pub fn get_borrower_quarter(env: &Env, borrower: Address, quarter: u64) -> Result<BorrowerQuarter, ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        let borrowers = data.borrower_quarters.get(quarter).ok_or(ContractError::NotFound)?;
+        borrowers.get(borrower).ok_or(ContractError::NotFound)
+    }
+
+    /// Get the rebate pool for a quarter
+    pub fn get_rebate_pool(env: &Env, quarter: u64) -> Result<RebatePool, ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        data.rebate_pools.get(quarter).ok_or(ContractError::NoAddress)
+    }
+
+    /// Top equity band borrowers who paid every installment on time
+    fn is_rebate_eligible(record: &BorrowerQuarter) -> bool {
+        record.equity_score >= REBATE_EQUITY_BAND && record.late_payments == 0 && record.interest_paid > 0
+    }
+
+    /// Start an A/B test routing a share of new borrowers to an alternative rate model (governance only)
+    pub fn start_rate_experiment(
+        env: &Env,
+        name: Symbol,
+        treatment_bps: u32,
+        alt_max_adjustment: i32,
+        alt_base_offset: i32,
+        duration: u64,
+    ) -> Result<u32, ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        let governance = Self::governance(env)?;
+        governance.require_auth();
+
+        if Self::active_experiment(env, &data).is_some() {
+            return Err(ContractError::ExperimentLive);
+        }
+
+        if treatment_bps == 0
+            || treatment_bps > MAX_TREATMENT_BPS
+            || !(0..=50).contains(&alt_max_adjustment)
+            || alt_base_offset.abs() > MAX_ALT_BASE_OFFSET
+            || duration == 0
+            || duration > MAX_EXPERIMENT_DURATION
+        {
+            return Err(ContractError::InvalidInput);
+        }
+
+        let empty = ArmMetrics {
+            applications: 0,
+            approved: 0,
+            rate_sum: 0,
+            on_time_payments: 0,
+            late_payments: 0,
+            interest_paid: 0,
+        };
+        let experiment_id = data.next_experiment_id;
+        let now = env.ledger().timestamp();
+        data.experiments.set(experiment_id, RateExperiment {
+            id: experiment_id,
+            name: name.clone(),
+            treatment_bps,
+            alt_max_adjustment,
+            alt_base_offset,
+            starts_at: now,
+            ends_at: now + duration,
+            control: empty.clone(),
+            treatment: empty,
+        });
+        data.active_experiment = Some(experiment_id);
+        data.next_experiment_id += 1;
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        env.events().publish((symbol_short!("exp_start"), experiment_id), (name, treatment_bps));
+
+        Ok(experiment_id)
+    }
+
+    /// Stop the running experiment early; its metrics keep recording payments (governance only)
+    pub fn end_rate_experiment(env: &Env) -> Result<u32, ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        let governance = Self::governance(env)?;
+        governance.require_auth();
+
+        let mut experiment = Self::active_experiment(env, &data).ok_or(ContractError::NotFound)?;
+        experiment.ends_at = env.ledger().timestamp();
+        data.experiments.set(experiment.id, experiment.clone());
+        data.active_experiment = None;
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        env.events().publish((symbol_short!("exp_end"), experiment.id), experiment.ends_at);
+
+        Ok(experiment.id)
+    }
+
+    /// Get a rate experiment and its per-arm metrics
+    pub fn get_rate_experiment(env: &Env, experiment_id: u32) -> Result<RateExperiment, ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        data.experiments.get(experiment_id).ok_or(ContractError::NotFound)
+    }
+
+    /// Get the experiment and arm (true = treatment) an application was assigned to
+    pub fn get_experiment_arm(env: &Env, application_id: Symbol) -> Option<(u32, bool)> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        data.experiment_arms.get(application_id)
+    }
+
+    /// The running experiment, if its period hasn't ended
+    fn active_experiment(env: &Env, data: &DataKey) -> Option<RateExperiment> {
+        let experiment = data.experiments.get(data.active_experiment?)?;
+        if env.ledger().timestamp() >= experiment.ends_at {
+            return None;
+        }
+        Some(experiment)
+    }
+
+    /// Assign a borrower to an arm of the running experiment from a hash of their address,
+    /// so the same borrower always lands in the same arm
+    fn experiment_arm(env: &Env, data: &DataKey, borrower: &Address) -> Option<(RateExperiment, bool)> {
+        let experiment = Self::active_experiment(env, data)?;
+        let hash = env.crypto().sha256(&borrower.clone().to_xdr(env)).to_array();
+        let bucket = u32::from_be_bytes([hash[0], hash[1], hash[2], hash[3]]) % 10_000;
+        let treated = bucket < experiment.treatment_bps;
+        Some((experiment, treated))
+    }
+
+    /// Update the metrics of the arm an application was assigned to, if any
+    fn record_arm_outcome(data: &mut DataKey, application_id: &Symbol, update: impl FnOnce(&mut ArmMetrics)) {
+        let (experiment_id, treated) = match data.experiment_arms.get(application_id.clone()) {
+            Some(arm) => arm,
+            None => return,
+        };
+        if let Some(mut experiment) = data.experiments.get(experiment_id) {
+            update(if treated { &mut experiment.treatment } else { &mut experiment.control });
+            data.experiments.set(experiment_id, experiment);
+        }
+    }
+
+    /// Queue a min/max band approved by referendum for a rate parameter behind the timelock;
+    /// returns the change id (governance only)
+    pub fn set_policy_band(env: &Env, parameter: Symbol, min: i128, max: i128) -> Result<u32, ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        // Only governance can bind policy bands
+        let governance = Self::governance(env)?;
+        governance.require_auth();
+
+        let value = ChangeValue::Encoded((parameter, min, max).to_xdr(env));
+        timelock::queue(env, symbol_short!("band"), value, timelock::MIN_DELAY, timelock::MIN_DELAY, |change| {
+            Self::validate_admin_change(env, &data, change)
+        })
+    }
+
+    /// Reject bands on unknown parameters and inverted bands
+    fn check_band_terms(parameter: &Symbol, min: i128, max: i128) -> Result<(), ContractError> {
+        if *parameter != symbol_short!("base_rate")
+            && *parameter != symbol_short!("max_adj")
+            && *parameter != symbol_short!("rate")
+        {
+            return Err(ContractError::InvalidInput);
+        }
+
+        if min > max {
+            return Err(ContractError::InvalidInput);
+        }
+
+        Ok(())
+    }
+
+    /// Get the policy band bound to a rate parameter
+    pub fn get_policy_band(env: &Env, parameter: Symbol) -> Result<PolicyBand, ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        data.policy_bands.get(parameter).ok_or(ContractError::NotFound)
+    }
+
+    /// Reject parameter values outside a referendum-bound band
+    fn check_policy_band(data: &DataKey, parameter: &Symbol, value: i128) -> Result<(), ContractError> {
+        if let Some(band) = data.policy_bands.get(parameter.clone()) {
+            if value < band.min || value > band.max {
+                return Err(ContractError::OutOfBand);
+            }
+        }
+        Ok(())
+    }
+
+    /// Check core accounting invariants; emits an alarm and optionally pauses on violation
+    pub fn verify_invariants(env: &Env) -> Vec<Symbol> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        let mut violations: Vec<Symbol> = vec![env];
 
-#[contract]
-pub struct EquityRateAdjuster;
+        // Rebates allocated in a quarter can't exceed its pool
+        for (quarter, pool) in data.rebate_pools.iter() {
+            let mut allocated: i128 = 0;
+            if let Some(borrowers) = data.borrower_quarters.get(quarter) {
+                for (_, record) in borrowers.iter() {
+                    allocated += record.rebate;
+                }
+            }
+            if allocated > pool.pool_size {
+                violations.push_back(symbol_short!("rebates"));
+                break;
+            }
+        }
 
-#[contractimpl]
-impl EquityRateAdjuster {
-    /// Initialize the contract with admin and AI oracle
-    pub fn initialize(env: &Env, admin: Address, oracle: Address, base_rate: i32) {
-        let data = DataKey {
-            admin,
-            oracle,
-            applications: Map::new(env),
-            urban_data_cache: Map::new(env),
-            base_rate,
-            max_rate_adjustment: 15, // 15% maximum adjustment
-        };
-        env.storage().instance().set(&DATA_KEY, &data);
+        for (_, escrow) in data.savings.iter() {
+            if escrow.balance < 0 || (escrow.target_reached && escrow.balance < escrow.target) {
+                violations.push_back(symbol_short!("savings"));
+                break;
+            }
+        }
+
+        if !violations.is_empty() {
+            env.events().publish((symbol_short!("inv_alarm"),), violations.clone());
+            if data.auto_pause && !data.paused {
+                data.paused = true;
+                ttl::set_instance(env, &DATA_KEY, &data);
+            }
+        }
+
+        violations
     }
 
-    /// Submit a loan application with AI-driven rate adjustment
-    pub fn submit_application(
-        env: &Env,
-        borrower: Address,
-        asset_id: Symbol,
-        requested_amount: i128,
-        location: Symbol,
-    ) -> Result<Symbol, Symbol> {
+    /// Enable or disable pausing when an invariant check fails (pauser only)
+    pub fn set_auto_pause(env: &Env, caller: Address, enabled: bool) -> Result<(), ContractError> {
         let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
-        
-        // Validate amount
-        if requested_amount <= 0 {
-            return Err(symbol_short!("INVALID_AMOUNT"));
-        }
 
-        // Generate application ID
-        let application_id = Self::generate_application_id(env, &borrower, &asset_id);
+        access::check(env, &data.admin, Role::Pauser, &caller)?;
 
-        // Get or fetch urban data for the location
-        let urban_data = Self::get_urban_data(env, &location);
+        data.auto_pause = enabled;
+        ttl::set_instance(env, &DATA_KEY, &data);
 
-        // Calculate equity score using AI oracle
-        let equity_score = Self::calculate_equity_score(env, &urban_data);
+        Ok(())
+    }
 
-        // Calculate AI-adjusted interest rate
-        let adjusted_rate = Self::calculate_adjusted_rate(env, &data.base_rate, &equity_score, &urban_data);
+    /// Halt state-changing entrypoints, e.g. while an oracle is compromised; views keep working (pauser only)
+    pub fn pause(env: &Env, caller: Address) -> Result<(), ContractError> {
+        Self::set_paused(env, caller, true)
+    }
 
-        let application = LoanApplication {
-            id: application_id.clone(),
-            borrower,
-            asset_id,
-            requested_amount,
-            base_rate: data.base_rate,
-            adjusted_rate,
-            equity_score,
-            urban_data: urban_data.clone(),
-            status: symbol_short!("pending"),
-            created_at: env.ledger().timestamp(),
-        };
+    /// Resume state-changing entrypoints after a pause (pauser only)
+    pub fn unpause(env: &Env, caller: Address) -> Result<(), ContractError> {
+        Self::set_paused(env, caller, false)
+    }
 
-        // Store application
-        data.applications.set(&application_id, &application);
-        
-        // Cache urban data
-        data.urban_data_cache.set(&location, &urban_data);
-        
-        env.storage().instance().set(&DATA_KEY, &data);
-        
-        Ok(application_id)
+    /// Whether state-changing entrypoints are halted
+    pub fn is_paused(env: &Env) -> bool {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        data.paused
     }
 
-    /// Approve a loan application (admin only)
-    pub fn approve_application(env: &Env, application_id: Symbol) -> Result<(), Symbol> {
+    fn set_paused(env: &Env, caller: Address, paused: bool) -> Result<(), ContractError> {
         let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
-        
-        // Only admin can approve applications
-        if env.current_contract_address() != data.admin {
-            return Err(symbol_short!("UNAUTHORIZED"));
-        }
 
-        let mut application = data.applications.get(&application_id).ok_or(symbol_short!("APPLICATION_NOT_FOUND"))?;
-        
-        if application.status != symbol_short!("pending") {
-            return Err(symbol_short!("INVALID_STATUS"));
-        }
+        access::check(env, &data.admin, Role::Pauser, &caller)?;
+
+        data.paused = paused;
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        let topic = if paused { symbol_short!("paused") } else { symbol_short!("unpaused") };
+        env.events().publish((topic,), env.ledger().timestamp());
 
-        application.status = symbol_short!("approved");
-        data.applications.set(&application_id, &application);
-        
-        env.storage().instance().set(&DATA_KEY, &data);
-        
         Ok(())
     }
 
-    /// Reject a loan application (admin only)
-    pub fn reject_application(env: &Env, application_id: Symbol) -> Result<(), Symbol> {
+    /// Switch one entrypoint feature on or off without pausing the whole contract (pauser only)
+    pub fn set_feature(env: &Env, caller: Address, feature: Symbol, enabled: bool) -> Result<(), ContractError> {
         let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
-        
-        // Only admin can reject applications
-        if env.current_contract_address() != data.admin {
-            return Err(symbol_short!("UNAUTHORIZED"));
-        }
 
-        let mut application = data.applications.get(&application_id).ok_or(symbol_short!("APPLICATION_NOT_FOUND"))?;
-        
-        if application.status != symbol_short!("pending") {
-            return Err(symbol_short!("INVALID_STATUS"));
+        access::check(env, &data.admin, Role::Pauser, &caller)?;
+
+        if let Some(index) = data.disabled_features.first_index_of(&feature) {
+            data.disabled_features.remove(index);
+        }
+        if !enabled {
+            data.disabled_features.push_back(feature.clone());
         }
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        env.events().publish((symbol_short!("feature"), feature), enabled);
 
-        application.status = symbol_short!("rejected");
-        data.applications.set(&application_id, &application);
-        
-        env.storage().instance().set(&DATA_KEY, &data);
-        
         Ok(())
     }
 
-    /// Get application details
-    pub fn get_application(env: &Env, application_id: Symbol) -> Result<LoanApplication, Symbol> {
+    /// Whether an entrypoint feature is switched on
+    pub fn is_feature_enabled(env: &Env, feature: Symbol) -> bool {
         let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
-        data.applications.get(&application_id).ok_or(symbol_short!("APPLICATION_NOT_FOUND"))
+        !data.disabled_features.contains(&feature)
     }
 
-    /// Get all applications for a borrower
-    pub fn get_borrower_applications(env: &Env, borrower: Address) -> Vec<LoanApplication> {
+    /// Grant a role to an account (admin only)
+    pub fn grant_role(env: &Env, caller: Address, role: Role, account: Address) -> Result<(), ContractError> {
         let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
-        let mut applications = vec![env];
-        
-        for (_, application) in data.applications.iter() {
-            if application.borrower == borrower {
-                applications.push_back(&application);
-            }
-        }
-        
-        applications
+
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+        access::grant(env, role, &account);
+
+        Ok(())
     }
 
-    /// Update urban data (oracle only)
-    pub fn update_urban_data(
+    /// Revoke a role previously granted to an account (admin only)
+    pub fn revoke_role(env: &Env, caller: Address, role: Role, account: Address) -> Result<(), ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+        access::revoke(env, role, &account).map_err(ContractError::from)
+    }
+
+    /// Whether an account holds a role; the admin holds every role
+    pub fn has_role(env: &Env, role: Role, account: Address) -> bool {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        access::holds(env, &data.admin, role, &account)
+    }
+
+    /// Extend the TTL of the contract instance and its code. Open to anyone, so a keeper can
+    /// stop a quiet contract from being archived between writes
+    pub fn bump_storage(env: &Env) {
+        ttl::extend_instance(env);
+    }
+
+    /// Set the remaining TTL below which writes extend an entry, and the TTL they extend it to (admin only)
+    pub fn set_ttl_config(env: &Env, caller: Address, threshold: u32, extend_to: u32) -> Result<(), ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+        ttl::set_config(env, threshold, extend_to).map_err(ContractError::from)
+    }
+
+    /// Get the storage TTL configuration
+    pub fn get_ttl_config(env: &Env) -> TtlConfig {
+        ttl::config(env)
+    }
+
+    /// Push change notifications to a relay contract, or stop with None (admin only)
+    pub fn set_relay(env: &Env, caller: Address, relay: Option<Address>) -> Result<(), ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+        changes::set_relay(env, relay);
+
+        Ok(())
+    }
+
+    /// Queue an admin change behind the timelock (admin only).
+    /// Supported actions: "base_rate", "max_adj", "oracle", "gov", "loan_pool"
+    pub fn queue_admin_change(
         env: &Env,
-        location: Symbol,
-        income_level: i32,
-        pollution_level: i32,
-        public_transport_score: i32,
-        population_density: i32,
-    ) -> Result<(), Symbol> {
+        caller: Address,
+        action: Symbol,
+        value: i128,
+        new_address: Option<Address>,
+        delay: u64,
+    ) -> Result<u32, ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        // Only admin can queue changes
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+
+        timelock::queue(env, action, ChangeValue::new(value, new_address), delay, timelock::MIN_DELAY, |change| {
+            Self::validate_admin_change(env, &data, change)
+        })
+    }
+
+    /// Execute a queued admin change once its timelock has elapsed (executor only)
+    pub fn execute_admin_change(env: &Env, caller: Address, change_id: u32) -> Result<(), ContractError> {
         let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
-        
-        // Only oracle can update urban data
-        if env.current_contract_address() != data.oracle {
-            return Err(symbol_short!("UNAUTHORIZED"));
+
+        // Only executors can execute changes
+        access::check(env, &data.admin, Role::Executor, &caller)?;
+
+        timelock::execute(env, change_id, |change| Self::apply_admin_change(env, &mut data, change))?;
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        Ok(())
+    }
+
+    /// Cancel a queued admin change during its cancellation window (admin only)
+    pub fn cancel_admin_change(env: &Env, caller: Address, change_id: u32) -> Result<(), ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        // Only admin can cancel changes
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+
+        timelock::cancel(env, change_id).map_err(ContractError::from)
+    }
+
+    /// Get all admin changes waiting out their timelock
+    pub fn get_pending_changes(env: &Env) -> Vec<PendingChange> {
+        timelock::pending(env)
+    }
+
+    /// Get a page of the append-only log of executed and cancelled admin changes
+    pub fn get_change_log(env: &Env, page: PageRequest) -> ChangeLogPage {
+        timelock::log(env, page)
+    }
+
+    /// Check that a queued change names a known action with a valid value
+    fn validate_admin_change(env: &Env, data: &DataKey, change: &PendingChange) -> Result<(), ContractError> {
+        if change.action == symbol_short!("base_rate") {
+            // Base rate (1-100%)
+            let value = change.number()?;
+            if !(1..=100).contains(&value) {
+                return Err(ContractError::InvalidInput);
+            }
+            Self::check_policy_band(data, &change.action, value)?;
+        } else if change.action == symbol_short!("max_adj") {
+            // Maximum adjustment (0-50%)
+            let value = change.number()?;
+            if !(0..=50).contains(&value) {
+                return Err(ContractError::InvalidInput);
+            }
+            Self::check_policy_band(data, &change.action, value)?;
+        } else if change.action == symbol_short!("oracle")
+            || change.action == symbol_short!("gov")
+            || change.action == symbol_short!("loan_pool")
+        {
+            change.address()?;
+        } else if change.action == symbol_short!("rebate") {
+            let (quarter, pool_size): (u64, i128) = change.decode(env)?;
+            Self::check_rebate_pool(data, quarter, pool_size)?;
+        } else if change.action == symbol_short!("band") {
+            let (parameter, min, max): (Symbol, i128, i128) = change.decode(env)?;
+            Self::check_band_terms(&parameter, min, max)?;
+        } else {
+            return Err(ContractError::InvalidInput);
         }
 
-        let urban_data = UrbanData {
-            location: location.clone(),
-            income_level,
-            pollution_level,
-            public_transport_score,
-            population_density,
+        Ok(())
+    }
+
+    /// Apply an admin change to contract state
+    fn apply_admin_change(env: &Env, data: &mut DataKey, change: &PendingChange) -> Result<(), ContractError> {
+        // Bands may have been set, and pools finalized, while the change was queued
+        Self::validate_admin_change(env, data, change)?;
+
+        if change.action == symbol_short!("base_rate") {
+            data.base_rate = change.number()? as i32;
+        } else if change.action == symbol_short!("max_adj") {
+            data.max_rate_adjustment = change.number()? as i32;
+        } else if change.action == symbol_short!("oracle") {
+            data.oracle = change.address()?;
+        } else if change.action == symbol_short!("gov") {
+            ttl::set_persistent(env, &StorageKey::Governance, &change.address()?);
+        } else if change.action == symbol_short!("loan_pool") {
+            ttl::set_persistent(env, &StorageKey::LoanPool, &change.address()?);
+        } else if change.action == symbol_short!("rebate") {
+            let (quarter, pool_size): (u64, i128) = change.decode(env)?;
+
+            // Resizing a pending pool only moves the difference
+            let funded = data.rebate_pools.get(quarter).map(|pool| pool.pool_size).unwrap_or(0);
+            let token = TokenClient::new(env, &Self::settlement_asset(env)?);
+            let governance = Self::governance(env)?;
+            if pool_size > funded {
+                token.transfer(&governance, &env.current_contract_address(), &(pool_size - funded));
+            } else if pool_size < funded {
+                token.transfer(&env.current_contract_address(), &governance, &(funded - pool_size));
+            }
+
+            data.rebate_pools.set(quarter, RebatePool {
+                quarter,
+                pool_size,
+                allocated: 0,
+                finalized: false,
+            });
+        } else if change.action == symbol_short!("band") {
+            let (parameter, min, max): (Symbol, i128, i128) = change.decode(env)?;
+            data.policy_bands.set(parameter.clone(), PolicyBand {
+                parameter,
+                min,
+                max,
+                set_at: env.ledger().timestamp(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Governance contract, once linked through an admin change
+    fn governance(env: &Env) -> Result<Address, ContractError> {
+        env.storage()
+            .persistent()
+            .get(&StorageKey::Governance)
+            .ok_or(ContractError::NoAddress)
+    }
+
+    /// Loan pool contract, once linked through an admin change
+    fn loan_pool(env: &Env) -> Result<Address, ContractError> {
+        env.storage()
+            .persistent()
+            .get(&StorageKey::LoanPool)
+            .ok_or(ContractError::NoAddress)
+    }
+
+    /// Settlement asset of the linked loan pool
+    fn settlement_asset(env: &Env) -> Result<Address, ContractError> {
+        LoanPoolClient::new(env, &Self::loan_pool(env)?)
+            .get_settlement_asset()
+            .ok_or(ContractError::NoSettlementAsset)
+    }
+
+    /// Get a page of boost audit records for an address, oldest first
+    pub fn get_boost_audit_by_address(env: &Env, subject: Address, page: PageRequest) -> BoostAuditPage {
+        let (items, next_cursor) = journal::page(env, &(BOOST_BY_SUBJECT, subject), &page);
+        BoostAuditPage { items, next_cursor }
+    }
+
+    /// Get a page of boost audit records for a rule ("rate_disc"), oldest first
+    pub fn get_boost_audit_by_rule(env: &Env, rule: Symbol, page: PageRequest) -> BoostAuditPage {
+        let (items, next_cursor) = journal::page(env, &(BOOST_BY_RULE, rule), &page);
+        BoostAuditPage { items, next_cursor }
+    }
+
+    /// Append a boost audit record to its subject's and its rule's streams
+    fn log_boost(env: &Env, subject: &Address, amount: i128, rule: Symbol, data_version: u64) {
+        let record = BoostAuditRecord {
+            subject: subject.clone(),
+            amount,
+            rule: rule.clone(),
+            data_version,
             timestamp: env.ledger().timestamp(),
         };
+        journal::append(env, &(BOOST_BY_SUBJECT, subject.clone()), &record);
+        journal::append(env, &(BOOST_BY_RULE, rule), &record);
+    }
+
+    /// Generate unique application ID
+    fn generate_application_id(env: &Env, data: &mut DataKey, borrower: &Address, asset_id: &Symbol) -> Symbol {
+        let timestamp = env.ledger().timestamp();
+
+        // The borrower's sequence number keeps IDs unique within a single ledger
+        let sequence = data.id_sequences.get(borrower.clone()).unwrap_or(0);
+        data.id_sequences.set(borrower.clone(), sequence + 1);
+
+        let preimage = (borrower.clone(), asset_id.clone(), timestamp, sequence).to_xdr(env);
+        ids::derive(env, &preimage)
+    }
+
+    /// Equity score the loan pool stores for an asset, or the canonical location score
+    /// for assets it hasn't listed
+    fn asset_equity_score(env: &Env, asset_id: &Symbol, urban_data: &UrbanData) -> i32 {
+        let listed = Self::loan_pool(env)
+            .ok()
+            .and_then(|loan_pool| LoanPoolClient::new(env, &loan_pool).get_asset_equity_score(asset_id));
+
+        listed.unwrap_or_else(|| Self::canonical_equity_score(env, urban_data))
+    }
+
+    /// Use the cached score when it was derived from this urban data, otherwise compute it
+    fn canonical_equity_score(env: &Env, urban_data: &UrbanData) -> i32 {
+        let cached: Option<CachedEquityScore> = env
+            .storage()
+            .persistent()
+            .get(&(EQ_SCORE_KEY, urban_data.location.clone()));
+
+        match cached {
+            Some(entry) if entry.source_timestamp == urban_data.timestamp => entry.equity_score,
+            _ => Self::calculate_equity_score(urban_data),
+        }
+    }
+
+    /// Recompute a location's canonical score and emit an event when it changes
+    fn refresh_equity_score_cache(env: &Env, urban_data: &UrbanData) {
+        let key = (EQ_SCORE_KEY, urban_data.location.clone());
+        let previous: Option<CachedEquityScore> = env.storage().persistent().get(&key);
+        let equity_score = Self::calculate_equity_score(urban_data);
+
+        ttl::set_persistent(env, &key, &CachedEquityScore {
+            location: urban_data.location.clone(),
+            equity_score,
+            source_timestamp: urban_data.timestamp,
+            updated_at: env.ledger().timestamp(),
+        });
+
+        let previous_score = previous.map(|entry| entry.equity_score);
+        if previous_score != Some(equity_score) {
+            env.events().publish(
+                (symbol_short!("eq_score"), urban_data.location.clone()),
+                (previous_score, equity_score),
+            );
+        }
+    }
+
+    /// Register an additional oracle for liveness monitoring (oracle manager only)
+    pub fn register_oracle(env: &Env, caller: Address, oracle: Address) -> Result<(), ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::OracleManager, &caller)?;
+
+        data.oracle_heartbeats.set(oracle, env.ledger().timestamp());
+        ttl::set_instance(env, &DATA_KEY, &data);
 
-        data.urban_data_cache.set(&location, &urban_data);
-        env.storage().instance().set(&DATA_KEY, &data);
-        
         Ok(())
     }
 
-    /// Get urban data for a location
-    pub fn get_urban_data_for_location(env: &Env, location: Symbol) -> Result<UrbanData, Symbol> {
-        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
-        data.urban_data_cache.get(&location).ok_or(symbol_short!("DATA_NOT_FOUND"))
+    /// Post a liveness heartbeat
+    pub fn heartbeat(env: &Env, oracle: Address) -> Result<(), ContractError> {
+        oracle.require_auth();
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        // The primary oracle is always registered, including after an oracle change
+        if oracle != data.oracle && !data.oracle_heartbeats.contains_key(oracle.clone()) {
+            return Err(ContractError::Unauthorized);
+        }
+
+        data.oracle_heartbeats.set(oracle, env.ledger().timestamp());
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        Ok(())
     }
 
-    /// Calculate rate adjustment based on equity factors
-    pub fn calculate_rate_adjustment(
-        env: &Env,
-        location: Symbol,
-    ) -> Result<i32, Symbol> {
+    /// Get liveness of every registered oracle
+    pub fn get_oracle_health(env: &Env) -> Vec<OracleHealth> {
         let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
-        let urban_data = Self::get_urban_data(env, &location);
-        let equity_score = Self::calculate_equity_score(env, &urban_data);
-        let adjusted_rate = Self::calculate_adjusted_rate(env, &data.base_rate, &equity_score, &urban_data);
-        
-        Ok(adjusted_rate - data.base_rate)
+        let mut health = vec![env];
+
+        if !data.oracle_heartbeats.contains_key(data.oracle.clone()) {
+            health.push_back(OracleHealth {
+                oracle: data.oracle.clone(),
+                last_heartbeat: 0,
+                stale: true,
+                primary: true,
+            });
+        }
+
+        for (oracle, last_heartbeat) in data.oracle_heartbeats.iter() {
+            health.push_back(OracleHealth {
+                stale: Self::is_oracle_stale(env, &data, &oracle),
+                primary: oracle == data.oracle,
+                oracle,
+                last_heartbeat,
+            });
+        }
+
+        health
     }
 
-    /// Generate unique application ID
-    fn generate_application_id(env: &Env, borrower: &Address, asset_id: &Symbol) -> Symbol {
-        let borrower_str = borrower.to_string();
-        let asset_str = asset_id.to_string();
-        let timestamp = env.ledger().timestamp();
-        
-        let combined = format!("{}_{}_{}", borrower_str, asset_str, timestamp);
-        let hash = env.crypto().sha256(&combined.as_bytes());
-        
-        // Convert first 8 bytes to symbol
-        let mut id_bytes = [0u8; 8];
-        id_bytes.copy_from_slice(&hash[0..8]);
-        
-        Symbol::from_bytes(&id_bytes)
+    /// An oracle is stale if it never posted a heartbeat or its last one has lapsed
+    fn is_oracle_stale(env: &Env, data: &DataKey, oracle: &Address) -> bool {
+        match data.oracle_heartbeats.get(oracle.clone()) {
+            Some(last_heartbeat) => env.ledger().timestamp() > last_heartbeat + ORACLE_HEARTBEAT_TIMEOUT,
+            None => true,
+        }
+    }
+
+    /// Rate calculations depend solely on the primary oracle, so block them while it is stale
+    fn ensure_oracle_live(env: &Env, data: &DataKey) -> Result<(), ContractError> {
+        if Self::is_oracle_stale(env, data, &data.oracle) {
+            return Err(ContractError::Stale);
+        }
+        Ok(())
     }
 
     /// Get urban data (fetch from oracle or use cached)
@@ -251,7 +2422,7 @@ impl EquityRateAdjuster {
         let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
         
         // Try to get cached data first
-        if let Some(cached_data) = data.urban_data_cache.get(location) {
+        if let Some(cached_data) = data.urban_data_cache.get(location.clone()) {
             return cached_data;
         }
 
@@ -262,8 +2433,7 @@ impl EquityRateAdjuster {
 
     /// Generate mock urban data for demo purposes
     fn generate_mock_urban_data(env: &Env, location: &Symbol) -> UrbanData {
-        let location_str = location.to_string();
-        let hash = env.crypto().sha256(&location_str.as_bytes());
+        let hash = env.crypto().sha256(&location.clone().to_xdr(env)).to_array();
         
         // Generate deterministic but varied data based on location
         let income_level = ((hash[0] as i32) % 10) + 1;
@@ -282,7 +2452,7 @@ impl EquityRateAdjuster {
     }
 
     /// Calculate equity score using AI oracle (mocked for demo)
-    fn calculate_equity_score(env: &Env, urban_data: &UrbanData) -> i32 {
+    fn calculate_equity_score(urban_data: &UrbanData) -> i32 {
         // In a real implementation, this would call the AI oracle
         // For demo purposes, we'll use a weighted algorithm
         
@@ -301,7 +2471,7 @@ impl EquityRateAdjuster {
         score += urban_data.population_density * 3;
         
         // Normalize to 0-100 range
-        score = score / 4;
+        score /= 4;
         if score > 100 {
             score = 100;
         }
@@ -312,9 +2482,13 @@ impl EquityRateAdjuster {
     /// Calculate AI-adjusted interest rate
     fn calculate_adjusted_rate(env: &Env, base_rate: &i32, equity_score: &i32, urban_data: &UrbanData) -> i32 {
         let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
-        
+        Self::model_rate(&data, base_rate, equity_score, urban_data, data.max_rate_adjustment)
+    }
+
+    /// Rate model with an explicit maximum equity adjustment
+    fn model_rate(data: &DataKey, base_rate: &i32, equity_score: &i32, urban_data: &UrbanData, max_adjustment: i32) -> i32 {
         // Higher equity scores get lower rates
-        let equity_adjustment = (100 - equity_score) * data.max_rate_adjustment / 100;
+        let equity_adjustment = (100 - equity_score) * max_adjustment / 100;
         
         // Additional adjustments based on urban factors
         let mut additional_adjustment = 0;
@@ -335,7 +2509,19 @@ impl EquityRateAdjuster {
         }
         
         let total_adjustment = equity_adjustment + additional_adjustment;
-        let adjusted_rate = base_rate - total_adjustment;
+
+        // Locations with high theft, vandalism or weather downtime carry a bounded premium
+        let risk_premium = data.risk_data.get(urban_data.location.clone()).map(|risk| risk.premium()).unwrap_or(0);
+        let mut adjusted_rate = base_rate - total_adjustment + risk_premium;
+
+        // Model output must stay within any referendum-bound rate band
+        if let Some(band) = data.policy_bands.get(symbol_short!("rate")) {
+            if (adjusted_rate as i128) < band.min {
+                adjusted_rate = band.min as i32;
+            } else if (adjusted_rate as i128) > band.max {
+                adjusted_rate = band.max as i32;
+            }
+        }
         
         // Ensure rate doesn't go below 1%
         if adjusted_rate < 1 {
@@ -347,18 +2533,18 @@ impl EquityRateAdjuster {
 
     /// Get contract statistics
     pub fn get_stats(env: &Env) -> (i32, i32, i32) {
-        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
-        
         let mut pending = 0;
         let mut approved = 0;
         let mut rejected = 0;
         
-        for (_, application) in data.applications.iter() {
-            match application.status.as_str() {
-                "pending" => pending += 1,
-                "approved" => approved += 1,
-                "rejected" => rejected += 1,
-                _ => {}
+        for id in Self::application_ids(env).iter() {
+            let application = Self::load_application(env, &id).unwrap();
+            if application.status == symbol_short!("pending") {
+                pending += 1;
+            } else if application.status == symbol_short!("approved") {
+                approved += 1;
+            } else if application.status == symbol_short!("rejected") {
+                rejected += 1;
             }
         }
         
@@ -366,5 +2552,39 @@ impl EquityRateAdjuster {
     }
 }
 
+#[contractimpl]
+impl EquityOracleInterface for EquityRateAdjuster {
+    /// Get just the cached equity score for a location, for cross-contract readers
+    fn peek_equity_score(env: &Env, location: Symbol) -> Option<i32> {
+        let cached: Option<CachedEquityScore> = env.storage().persistent().get(&(EQ_SCORE_KEY, location));
+        cached.map(|entry| entry.equity_score)
+    }
+
+    /// Get the latest operating risk data for a location, for asset listings
+    fn peek_risk_data(env: &Env, location: Symbol) -> Option<RiskData> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        data.risk_data.get(location)
+    }
+
+    /// Get the terms of an approved or active application, for the loan pool's repayment schedule
+    fn peek_approved_loan(env: &Env, application_id: Symbol) -> Option<(Address, Symbol, i32, i128)> {
+        let application = Self::load_application(env, &application_id)?;
+        if application.status != symbol_short!("approved") && application.status != symbol_short!("active") {
+            return None;
+        }
+        Some((application.borrower, application.asset_id, application.adjusted_rate, application.requested_amount))
+    }
+
+    /// Whether the loan financing an asset has defaulted, so other contracts can pause its benefits
+    fn is_asset_defaulted(env: &Env, asset_id: Symbol) -> bool {
+        env.storage().persistent().has(&StorageKey::DefaultedAsset(asset_id))
+    }
+
+    /// Get the rate discounts delivered in a calendar epoch, for the governance KPI dashboard
+    fn get_epoch_rate_stats(env: &Env, epoch: u32) -> RateStats {
+        env.storage().persistent().get(&(KPI_KEY, epoch)).unwrap_or_default()
+    }
+}
+
 #[cfg(test)]
 mod test;
@@ -0,0 +1,816 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events, Ledger},
+    token::StellarAssetClient,
+    vec,
+    xdr::ToXdr,
+    Address, BytesN, Env, IntoVal, Val,
+};
+use ed25519_dalek::{Signer, SigningKey};
+
+/// Stands in for the loan pool, serving the settlement asset and the asset scores a test registers
+#[contract]
+struct StubLoanPool;
+
+#[contractimpl]
+impl StubLoanPool {
+    pub fn settle_in(env: Env, token: Address) {
+        env.storage().instance().set(&symbol_short!("token"), &token);
+    }
+
+    pub fn get_settlement_asset(env: Env) -> Option<Address> {
+        env.storage().instance().get(&symbol_short!("token"))
+    }
+
+    pub fn list_asset(env: Env, asset_id: Symbol, equity_score: i32) {
+        env.storage().persistent().set(&asset_id, &equity_score);
+    }
+
+    pub fn get_asset_equity_score(env: Env, asset_id: Symbol) -> Option<i32> {
+        env.storage().persistent().get(&asset_id)
+    }
+
+    pub fn is_winding_down(_env: Env, _asset_id: Symbol) -> bool {
+        false
+    }
+
+    /// Hand `amount` of the pool's tokens to the caller of the next release
+    pub fn hold_release(env: Env, amount: i128) {
+        env.storage().instance().set(&symbol_short!("release"), &amount);
+    }
+
+    pub fn release_loan_capital(env: Env, _application_id: Symbol) -> i128 {
+        let amount: i128 = env.storage().instance().get(&symbol_short!("release")).unwrap();
+        let token: Address = env.storage().instance().get(&symbol_short!("token")).unwrap();
+        let adjuster: Address = env.storage().instance().get(&symbol_short!("adjuster")).unwrap();
+        TokenClient::new(&env, &token).transfer(&env.current_contract_address(), &adjuster, &amount);
+        amount
+    }
+
+    pub fn set_adjuster(env: Env, adjuster: Address) {
+        env.storage().instance().set(&symbol_short!("adjuster"), &adjuster);
+    }
+
+    pub fn record_loan_repayment(env: Env, payer: Address, _asset_id: Symbol, principal: i128, interest: i128) -> i128 {
+        let token: Address = env.storage().instance().get(&symbol_short!("token")).unwrap();
+        TokenClient::new(&env, &token).transfer(&payer, &env.current_contract_address(), &(principal + interest));
+        principal
+    }
+}
+
+/// Stands in for governance, serving the default epoch calendar and no origination fee
+#[contract]
+struct StubGovernance;
+
+#[contractimpl]
+impl StubGovernance {
+    pub fn get_fee_bps(_env: Env, _equity_score: i32) -> u32 {
+        0
+    }
+
+    pub fn get_epoch_schedule(_env: Env) -> EpochSchedule {
+        EpochSchedule::default_schedule()
+    }
+}
+
+/// Apply an address change through the timelock
+fn link(env: &Env, client: &EquityRateAdjusterClient, admin: &Address, action: Symbol, address: &Address) {
+    let change_id = client.queue_admin_change(admin, &action, &0, &Some(address.clone()), &86_400);
+    env.ledger().with_mut(|ledger| ledger.timestamp += 86_400);
+    client.execute_admin_change(admin, &change_id);
+}
+
+/// Link stub governance and a stub loan pool settled in a new Stellar Asset Contract; returns
+/// (governance, token)
+fn settle_in_token(env: &Env, client: &EquityRateAdjusterClient, admin: &Address) -> (Address, Address) {
+    let token = env.register_stellar_asset_contract(Address::generate(env));
+    let loan_pool = env.register_contract(None, StubLoanPool);
+    StubLoanPoolClient::new(env, &loan_pool).settle_in(&token);
+    let governance = env.register_contract(None, StubGovernance);
+    link(env, client, admin, symbol_short!("loan_pool"), &loan_pool);
+    link(env, client, admin, symbol_short!("gov"), &governance);
+    (governance, token)
+}
+
+/// Sign an urban data submission the way a feeder would
+fn sign_urban_data(
+    env: &Env,
+    contract_id: &Address,
+    key: &SigningKey,
+    values: (Symbol, i32, i32, i32, i32),
+    nonce: u64,
+    timestamp: u64,
+) -> BytesN<64> {
+    let values: Val = values.into_val(env);
+    let payload = (contract_id.clone(), Symbol::new(env, "update_urban_data"), nonce, timestamp, values).to_xdr(env);
+    let mut buffer = [0u8; 256];
+    let message = &mut buffer[..payload.len() as usize];
+    payload.copy_into_slice(message);
+    BytesN::from_array(env, &key.sign(message).to_bytes())
+}
+
+#[test]
+fn test_same_ledger_applications_get_distinct_ids() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, EquityRateAdjuster);
+    let client = EquityRateAdjusterClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    client.initialize(&admin, &oracle, &8);
+
+    // Same borrower, same asset, same ledger timestamp
+    let borrower = Address::generate(&env);
+    let asset_id = symbol_short!("ebike_001");
+    let location = symbol_short!("downtown");
+    let mut ids = vec![&env];
+    for _ in 0..3 {
+        let application_id = client.submit_application(&borrower, &asset_id, &5_000, &location);
+        assert!(!ids.contains(&application_id));
+        ids.push_back(application_id);
+    }
+
+    for application_id in ids.iter() {
+        assert_eq!(client.get_application(&application_id).borrower, borrower);
+    }
+}
+
+#[test]
+fn test_improving_borrower_unlocks_rewards() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, EquityRateAdjuster);
+    let client = EquityRateAdjusterClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+
+    let oracle = Address::generate(&env);
+    client.initialize(&admin, &oracle, &8);
+
+    let borrower = Address::generate(&env);
+    let application_id = client.submit_application(&borrower, &symbol_short!("ebike_001"), &5_000, &symbol_short!("downtown"));
+
+    for _ in 0..5 {
+        client.record_interest_payment(&application_id, &100, &true);
+    }
+    // Payments are reported under the oracle's signature
+    assert_eq!(env.auths().first().unwrap().0, oracle);
+    assert_eq!(client.get_score_trajectory(&borrower).personal_score, 60);
+    assert_eq!(client.claim_improvement_reward(&borrower), 1);
+    assert_eq!(client.get_improvement_rewards(&borrower), (1, false));
+
+    // Participation carries the borrower into the priority tier
+    for _ in 0..5 {
+        client.record_participation(&application_id);
+    }
+    assert_eq!(client.claim_improvement_reward(&borrower), 2);
+    assert_eq!(client.get_improvement_rewards(&borrower), (2, true));
+    assert_eq!(client.get_score_trajectory(&borrower).history.len(), 10);
+
+    // The step-down is spent on the next application
+    client.submit_application(&borrower, &symbol_short!("ebike_002"), &5_000, &symbol_short!("downtown"));
+    assert_eq!(client.get_improvement_rewards(&borrower), (0, true));
+}
+
+#[test]
+fn test_application_pages_follow_submission_order() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, EquityRateAdjuster);
+    let client = EquityRateAdjusterClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    client.initialize(&admin, &oracle, &8);
+
+    let borrower = Address::generate(&env);
+    let other = Address::generate(&env);
+    let asset_id = symbol_short!("ebike_001");
+    let location = symbol_short!("downtown");
+    let mut mine = vec![&env];
+    for _ in 0..3 {
+        mine.push_back(client.submit_application(&borrower, &asset_id, &5_000, &location));
+        client.submit_application(&other, &asset_id, &5_000, &location);
+    }
+
+    // The borrower's index holds only their applications
+    let page = client.get_borrower_applications_page(&borrower, &PageRequest { cursor: 1, limit: 5 });
+    assert_eq!(page.items.len(), 2);
+    assert_eq!(page.items.get(0).unwrap().id, mine.get(1).unwrap());
+    assert_eq!(page.items.get(1).unwrap().id, mine.get(2).unwrap());
+    assert_eq!(page.next_cursor, None);
+
+    // Following next_cursor walks the whole list
+    let first = client.get_applications_page(&PageRequest::first(4));
+    assert_eq!(first.items.len(), 4);
+    assert_eq!(first.next_cursor, Some(4));
+    let rest = client.get_applications_page(&PageRequest { cursor: 4, limit: 4 });
+    assert_eq!(rest.items.len(), 2);
+    assert_eq!(rest.next_cursor, None);
+}
+
+#[test]
+fn test_feeder_keys_rotate_and_disable_independently() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, EquityRateAdjuster);
+    let client = EquityRateAdjusterClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    client.initialize(&admin, &oracle, &8);
+
+    // Feeder keys are managed by an account granted the oracle manager role
+    let manager = Address::generate(&env);
+    client.grant_role(&admin, &Role::OracleManager, &manager);
+    let primary = BytesN::from_array(&env, &[1; 32]);
+    let backup = BytesN::from_array(&env, &[2; 32]);
+    let replacement = BytesN::from_array(&env, &[3; 32]);
+    client.register_feeder(&manager, &primary, &300);
+    client.register_feeder(&manager, &backup, &300);
+    assert_eq!(client.try_register_feeder(&manager, &backup, &300), Err(Ok(ContractError::KeyExists)));
+
+    // Disabling one key leaves the other registered and enabled
+    client.set_feeder_enabled(&manager, &backup, &false);
+    let submit = |feeder: &BytesN<32>| {
+        client.try_submit_signed_urban_data(
+            feeder,
+            &symbol_short!("downtown"),
+            &30_000,
+            &40,
+            &60,
+            &5_000,
+            &1,
+            &env.ledger().timestamp(),
+            &BytesN::from_array(&env, &[0; 64]),
+        )
+    };
+    assert_eq!(submit(&backup), Err(Ok(ContractError::KeyInactive)));
+    assert_eq!(submit(&replacement), Err(Ok(ContractError::NotFound)));
+
+    // A rotation keeps the old key until the handover, then only the new key is active
+    let handover = env.ledger().timestamp() + 3_600;
+    client.rotate_feeder(&manager, &primary, &replacement, &300, &handover);
+    assert_eq!(submit(&replacement), Err(Ok(ContractError::KeyInactive)));
+    env.ledger().with_mut(|ledger| ledger.timestamp = handover);
+    assert_eq!(submit(&primary), Err(Ok(ContractError::KeyInactive)));
+
+    let feeders = client.get_feeders();
+    assert_eq!(feeders.len(), 3);
+    assert_eq!(feeders.get(0).unwrap().retires_at, Some(handover));
+    assert!(feeders.get(2).unwrap().is_active(handover));
+}
+
+#[test]
+fn test_signed_urban_data_is_accepted_once_and_only_while_fresh() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|ledger| ledger.timestamp = 10_000);
+    let contract_id = env.register_contract(None, EquityRateAdjuster);
+    let client = EquityRateAdjusterClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &Address::generate(&env), &8);
+    let key = SigningKey::from_bytes(&[7; 32]);
+    let feeder = BytesN::from_array(&env, &key.verifying_key().to_bytes());
+    client.register_feeder(&admin, &feeder, &300);
+
+    // Any relayer can deliver a payload the feeder signed
+    let location = symbol_short!("downtown");
+    let values = (location.clone(), 3, 7, 2, 8);
+    let submit = |nonce: u64, timestamp: u64, signature: &BytesN<64>| {
+        client.try_submit_signed_urban_data(&feeder, &location, &3, &7, &2, &8, &nonce, &timestamp, signature)
+    };
+    let now = env.ledger().timestamp();
+    let signature = sign_urban_data(&env, &contract_id, &key, values.clone(), 1, now - 60);
+    assert_eq!(submit(1, now - 60, &signature), Ok(Ok(())));
+    let stored = client.get_urban_data_for_location(&location);
+    assert_eq!(
+        (stored.income_level, stored.pollution_level, stored.public_transport_score, stored.population_density),
+        (3, 7, 2, 8)
+    );
+
+    // The same payload can't be replayed
+    assert_eq!(submit(1, now - 60, &signature), Err(Ok(ContractError::BadNonce)));
+
+    // Payloads dated in the future or past the freshness window are refused
+    let signature = sign_urban_data(&env, &contract_id, &key, values.clone(), 2, now + 1);
+    assert_eq!(submit(2, now + 1, &signature), Err(Ok(ContractError::TooEarly)));
+    let signature = sign_urban_data(&env, &contract_id, &key, values.clone(), 2, now - 301);
+    assert_eq!(submit(2, now - 301, &signature), Err(Ok(ContractError::Stale)));
+
+    // A skipped nonce is fine as long as it increases
+    let signature = sign_urban_data(&env, &contract_id, &key, values, 5, now - 300);
+    assert_eq!(submit(5, now - 300, &signature), Ok(Ok(())));
+    assert_eq!(client.get_feeders().get(0).unwrap().signer.last_nonce, 5);
+}
+
+#[test]
+fn test_rate_log_records_decisions_and_outcomes() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, EquityRateAdjuster);
+    let client = EquityRateAdjusterClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    client.initialize(&admin, &oracle, &8);
+
+    let borrower = Address::generate(&env);
+    let location = symbol_short!("downtown");
+    let first = client.submit_application(&borrower, &symbol_short!("ebike_001"), &5_000, &location);
+    client.submit_application(&borrower, &symbol_short!("ebike_002"), &5_000, &location);
+    client.approve_application(&admin, &first);
+    assert_eq!(client.get_rate_log_count(), 3);
+
+    // Each status change appends an entry carrying the inputs and the rate they produced
+    let page = client.get_rate_log_page(&PageRequest::first(2));
+    assert_eq!(page.items.len(), 2);
+    assert_eq!(page.next_cursor, Some(2));
+    assert_eq!(page.items.get(0).unwrap().status, symbol_short!("pending"));
+
+    let application = client.get_application(&first);
+    let outcome = client.get_rate_log_page(&PageRequest { cursor: 2, limit: 2 }).items.get(0).unwrap();
+    assert_eq!(outcome.application_id, first);
+    assert_eq!(outcome.status, symbol_short!("approved"));
+    assert_eq!(outcome.adjusted_rate, application.adjusted_rate);
+    assert_eq!(outcome.income_level, application.urban_data.income_level);
+}
+
+#[test]
+fn test_claimed_rebates_are_paid_from_the_funded_pool() {
+    let env = Env::default();
+    // The treasury transfer is authorized by governance beneath the executor's call
+    env.mock_all_auths_allowing_non_root_auth();
+    let contract_id = env.register_contract(None, EquityRateAdjuster);
+    let client = EquityRateAdjusterClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    client.initialize(&admin, &oracle, &8);
+    let (governance, token_id) = settle_in_token(&env, &client, &admin);
+    let token = TokenClient::new(&env, &token_id);
+    StellarAssetClient::new(&env, &token_id).mint(&governance, &1_000);
+
+    // A top-band borrower pays interest on time through the first quarter
+    client.heartbeat(&oracle);
+    let location = symbol_short!("eastside");
+    client.update_urban_data(&location, &1, &30, &1, &10);
+    let borrower = Address::generate(&env);
+    let application_id = client.submit_application(&borrower, &symbol_short!("ebike_001"), &5_000, &location);
+    client.record_interest_payment(&application_id, &100, &true);
+
+    // The pool is drawn from governance when the change executes, and resizing moves only the difference
+    let change_id = client.set_rebate_pool(&0, &300);
+    env.ledger().with_mut(|ledger| ledger.timestamp += 86_400);
+    client.execute_admin_change(&admin, &change_id);
+    assert_eq!(token.balance(&contract_id), 300);
+    let change_id = client.set_rebate_pool(&0, &120);
+    env.ledger().with_mut(|ledger| ledger.timestamp += 86_400);
+    client.execute_admin_change(&admin, &change_id);
+    assert_eq!(token.balance(&contract_id), 120);
+    assert_eq!(token.balance(&governance), 880);
+
+    // The rebate is capped at half the interest paid; the rest of the pool returns to governance
+    env.ledger().with_mut(|ledger| ledger.timestamp = EpochSchedule::default_schedule().length * 3);
+    assert_eq!(client.finalize_rebate_quarter(&0), 50);
+    assert_eq!(token.balance(&governance), 950);
+
+    assert_eq!(client.claim_rebate(&borrower, &0), 50);
+    assert_eq!(token.balance(&borrower), 50);
+    assert_eq!(token.balance(&contract_id), 0);
+    assert_eq!(client.try_claim_rebate(&borrower, &0), Err(Ok(ContractError::Nothing)));
+}
+
+#[test]
+fn test_savings_escrow_holds_and_returns_the_deposits() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, EquityRateAdjuster);
+    let client = EquityRateAdjusterClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &Address::generate(&env), &8);
+    let (_, token_id) = settle_in_token(&env, &client, &admin);
+    let token = TokenClient::new(&env, &token_id);
+    let borrower = Address::generate(&env);
+    StellarAssetClient::new(&env, &token_id).mint(&borrower, &500);
+
+    // Deposits are pulled into escrow and count toward the target
+    client.open_savings(&borrower, &300);
+    assert_eq!(client.deposit_savings(&borrower, &200), 200);
+    assert_eq!(client.deposit_savings(&borrower, &100), 300);
+    assert!(client.get_savings(&borrower).target_reached);
+    assert_eq!(token.balance(&borrower), 200);
+    assert_eq!(token.balance(&contract_id), 300);
+
+    // A withdrawal sends the tokens back and forfeits the bonus
+    assert_eq!(client.withdraw_savings(&borrower, &120), 180);
+    assert!(!client.get_savings(&borrower).target_reached);
+    assert_eq!(token.balance(&borrower), 320);
+    assert_eq!(token.balance(&contract_id), 180);
+    assert_eq!(client.try_withdraw_savings(&borrower, &181), Err(Ok(ContractError::InvalidAmount)));
+}
+
+#[test]
+fn test_boost_audit_pages_by_subject_and_rule() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, EquityRateAdjuster);
+    let client = EquityRateAdjusterClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &Address::generate(&env), &8);
+
+    // Each borrower gets a rate discount, then claims both reward tiers; every grant is audited
+    let improve = |borrower: &Address, asset_id: Symbol| {
+        let application_id = client.submit_application(borrower, &asset_id, &5_000, &symbol_short!("downtown"));
+        for _ in 0..5 {
+            client.record_interest_payment(&application_id, &100, &true);
+        }
+        client.claim_improvement_reward(borrower);
+        for _ in 0..5 {
+            client.record_participation(&application_id);
+        }
+        client.claim_improvement_reward(borrower);
+    };
+    let (first, second) = (Address::generate(&env), Address::generate(&env));
+    improve(&first, symbol_short!("ebike_001"));
+    improve(&second, symbol_short!("ebike_002"));
+
+    // A subject's stream holds only their records
+    let page = client.get_boost_audit_by_address(&first, &PageRequest::first(1));
+    assert_eq!(page.items.len(), 1);
+    assert_eq!(page.items.get(0).unwrap().subject, first);
+    assert_eq!(page.items.get(0).unwrap().rule, symbol_short!("rate_disc"));
+    assert_eq!(page.next_cursor, Some(1));
+    let rest = client.get_boost_audit_by_address(&first, &PageRequest { cursor: 1, limit: 10 });
+    assert_eq!(rest.items.len(), 2);
+    assert!(rest.items.iter().all(|record| record.subject == first && record.rule == symbol_short!("imp_rwd")));
+    assert_eq!(rest.next_cursor, None);
+
+    // A rule's stream holds every subject's records in the order they were granted
+    let rewards = client.get_boost_audit_by_rule(&symbol_short!("imp_rwd"), &PageRequest::first(10));
+    assert_eq!(rewards.items.len(), 4);
+    assert_eq!(rewards.items.get(2).unwrap().subject, second);
+    assert_eq!(rewards.next_cursor, None);
+}
+
+#[test]
+fn test_score_simulation_is_deterministic_and_read_only() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, EquityRateAdjuster);
+    let client = EquityRateAdjusterClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    client.initialize(&admin, &oracle, &8);
+
+    let location = symbol_short!("downtown");
+    client.update_urban_data(&location, &5, &5, &5, &5);
+    assert_eq!(client.get_cached_equity_score(&location).equity_score, 37);
+
+    // Lower income, dirtier air, poorer transit and denser housing all raise the score
+    let input = UrbanDataInput { income_level: 1, pollution_level: 10, public_transport_score: 1, population_density: 10 };
+    let simulation = client.simulate_score(&location, &input);
+    assert_eq!(simulation.current_equity_score, 37);
+    assert_eq!(simulation.simulated_equity_score, 65);
+    assert_eq!(simulation.score_delta, 28);
+    assert_eq!(simulation.rate_delta, simulation.simulated_rate - simulation.current_rate);
+
+    // The same inputs give the same answer, and nothing is written
+    assert_eq!(client.simulate_score(&location, &input), simulation);
+    assert_eq!(client.get_cached_equity_score(&location).equity_score, 37);
+    assert_eq!(client.get_urban_data_for_location(&location).income_level, 5);
+
+    let out_of_range = UrbanDataInput { income_level: 11, ..input };
+    assert_eq!(client.try_simulate_score(&location, &out_of_range), Err(Ok(ContractError::InvalidInput)));
+}
+
+#[test]
+fn test_referendum_bands_bound_the_base_rate_and_model_output() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, EquityRateAdjuster);
+    let client = EquityRateAdjusterClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    client.initialize(&admin, &oracle, &8);
+    link(&env, &client, &admin, symbol_short!("gov"), &env.register_contract(None, StubGovernance));
+
+    assert_eq!(client.try_set_policy_band(&symbol_short!("apr"), &1, &5), Err(Ok(ContractError::InvalidInput)));
+    assert_eq!(client.try_set_policy_band(&symbol_short!("base_rate"), &9, &5), Err(Ok(ContractError::InvalidInput)));
+
+    // Bands bind once their timelock has run
+    for (parameter, min, max) in [(symbol_short!("base_rate"), 4, 10), (symbol_short!("rate"), 9, 9)] {
+        let change_id = client.set_policy_band(&parameter, &min, &max);
+        assert_eq!(client.try_get_policy_band(&parameter), Err(Ok(ContractError::NotFound)));
+        env.ledger().with_mut(|ledger| ledger.timestamp += timelock::MIN_DELAY);
+        client.execute_admin_change(&admin, &change_id);
+        let band = client.get_policy_band(&parameter);
+        assert_eq!((band.min, band.max), (min, max));
+    }
+
+    assert_eq!(client.try_set_base_rate(&12), Err(Ok(ContractError::OutOfBand)));
+    client.set_base_rate(&6);
+
+    // Whatever the zone, the model's rate is held to the band
+    client.heartbeat(&oracle);
+    client.update_urban_data(&symbol_short!("downtown"), &1, &10, &1, &10);
+    assert_eq!(client.calculate_rate_adjustment(&symbol_short!("downtown")), 3);
+}
+
+/// The (previous, new) scores of every equity score update published so far
+fn score_updates(env: &Env) -> Vec<(Option<i32>, i32)> {
+    let mut updates = vec![env];
+    for (_, topics, data) in env.events().all().iter() {
+        let topic: Symbol = topics.get(0).unwrap().into_val(env);
+        if topic == symbol_short!("eq_score") {
+            updates.push_back(data.into_val(env));
+        }
+    }
+    updates
+}
+
+#[test]
+fn test_equity_score_cache_announces_only_real_changes() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, EquityRateAdjuster);
+    let client = EquityRateAdjusterClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    client.initialize(&admin, &oracle, &8);
+
+    let location = symbol_short!("downtown");
+    assert_eq!(client.peek_equity_score(&location), None);
+    assert_eq!(client.try_get_cached_equity_score(&location), Err(Ok(ContractError::NotFound)));
+
+    client.update_urban_data(&location, &5, &5, &5, &5);
+    assert_eq!(score_updates(&env), vec![&env, (None, 37)]);
+    assert_eq!(client.peek_equity_score(&location), Some(37));
+
+    // Fresh data with the same score refreshes the entry without invalidating readers
+    env.ledger().with_mut(|ledger| ledger.timestamp += 3_600);
+    client.heartbeat(&oracle);
+    client.update_urban_data(&location, &5, &5, &5, &5);
+    assert_eq!(score_updates(&env), vec![&env, (None, 37)]);
+    let cached = client.get_cached_equity_score(&location);
+    assert_eq!(cached.source_timestamp, env.ledger().timestamp());
+    assert_eq!(cached.equity_score, 37);
+
+    client.update_urban_data(&location, &1, &10, &1, &10);
+    assert_eq!(score_updates(&env), vec![&env, (None, 37), (Some(37), 65)]);
+    assert_eq!(client.peek_equity_score(&location), Some(65));
+}
+
+#[test]
+fn test_lapsed_oracle_heartbeat_blocks_rate_calculations() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, EquityRateAdjuster);
+    let client = EquityRateAdjusterClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let (oracle, backup) = (Address::generate(&env), Address::generate(&env));
+    client.initialize(&admin, &oracle, &8);
+    let location = symbol_short!("downtown");
+    client.update_urban_data(&location, &5, &5, &5, &5);
+    let rate = client.calculate_rate_adjustment(&location);
+
+    assert_eq!(client.try_heartbeat(&backup), Err(Ok(ContractError::Unauthorized)));
+    client.register_oracle(&admin, &backup);
+    let health = client.get_oracle_health();
+    assert_eq!(health.len(), 2);
+    assert!(health.iter().all(|oracle| !oracle.stale));
+
+    // Six hours without a heartbeat is fine, a second more is not
+    env.ledger().with_mut(|ledger| ledger.timestamp += 6 * 3_600);
+    client.heartbeat(&backup);
+    assert_eq!(client.calculate_rate_adjustment(&location), rate);
+    env.ledger().with_mut(|ledger| ledger.timestamp += 1);
+    let health = client.get_oracle_health();
+    let primary = health.iter().find(|oracle| oracle.primary).unwrap();
+    assert!(primary.stale);
+    assert!(!health.iter().find(|oracle| !oracle.primary).unwrap().stale);
+
+    // The backup being live doesn't stand in for the primary
+    assert_eq!(client.try_calculate_rate_adjustment(&location), Err(Ok(ContractError::Stale)));
+    assert_eq!(client.try_update_urban_data(&location, &5, &5, &5, &5), Err(Ok(ContractError::Stale)));
+
+    client.heartbeat(&oracle);
+    assert!(client.get_oracle_health().iter().all(|oracle| !oracle.stale));
+    assert_eq!(client.calculate_rate_adjustment(&location), rate);
+}
+
+#[test]
+fn test_rejected_application_keeps_its_reason_and_evidence() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, EquityRateAdjuster);
+    let client = EquityRateAdjusterClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &Address::generate(&env), &8);
+    let borrower = Address::generate(&env);
+    let application_id = client.submit_application(&borrower, &symbol_short!("ebike_001"), &5_000, &symbol_short!("downtown"));
+
+    let reason = RejectionReason::IneligibleLocation;
+    let evidence = BytesN::from_array(&env, &[4; 32]);
+    assert_eq!(
+        client.try_reject_application(&borrower, &application_id, &reason, &None),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    assert_eq!(client.get_rejection(&application_id), None);
+    client.reject_application(&admin, &application_id, &reason, &Some(evidence.clone()));
+
+    assert_eq!(client.get_application(&application_id).status, symbol_short!("rejected"));
+    let rejection = client.get_rejection(&application_id).unwrap();
+    assert_eq!(rejection.reason, reason);
+    assert_eq!(rejection.evidence, vec![&env, evidence.clone()]);
+    let (_, topics, payload) = env.events().all().last().unwrap();
+    assert_eq!(topics, (symbol_short!("rejected"), application_id.clone()).into_val(&env));
+    let payload: (RejectionReason, Option<BytesN<32>>) = payload.into_val(&env);
+    assert_eq!(payload, (reason, Some(evidence)));
+
+    // A decision is final
+    assert_eq!(
+        client.try_reject_application(&admin, &application_id, &RejectionReason::Duplicate, &None),
+        Err(Ok(ContractError::InvalidStatus))
+    );
+}
+
+#[test]
+fn test_listed_assets_are_scored_by_the_loan_pool() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, EquityRateAdjuster);
+    let client = EquityRateAdjusterClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    client.initialize(&admin, &oracle, &8);
+    let location = symbol_short!("downtown");
+    client.update_urban_data(&location, &5, &5, &5, &5);
+
+    let loan_pool = env.register_contract(None, StubLoanPool);
+    link(&env, &client, &admin, symbol_short!("loan_pool"), &loan_pool);
+    client.heartbeat(&oracle);
+    let (listed, unlisted) = (symbol_short!("ebike_001"), symbol_short!("ebike_002"));
+    StubLoanPoolClient::new(&env, &loan_pool).list_asset(&listed, &80);
+
+    // The pool's score wins over the location's; unlisted assets fall back to the location
+    let borrower = Address::generate(&env);
+    let scored = client.get_application(&client.submit_application(&borrower, &listed, &5_000, &location));
+    assert_eq!(scored.equity_score, 80);
+    let fallback = client.get_application(&client.submit_application(&borrower, &unlisted, &5_000, &location));
+    assert_eq!(fallback.equity_score, 37);
+    assert_eq!(fallback.equity_score, client.peek_equity_score(&location).unwrap());
+    assert_ne!(scored.adjusted_rate, fallback.adjusted_rate);
+}
+
+#[test]
+fn test_rate_experiment_splits_borrowers_into_stable_arms() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, EquityRateAdjuster);
+    let client = EquityRateAdjusterClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    client.initialize(&admin, &oracle, &8);
+    settle_in_token(&env, &client, &admin);
+    client.heartbeat(&oracle);
+    let location = symbol_short!("downtown");
+    client.update_urban_data(&location, &5, &5, &5, &5);
+    let asset_id = symbol_short!("ebike_001");
+    let control_rate = client.get_application(&client.submit_application(&admin, &asset_id, &5_000, &location)).adjusted_rate;
+
+    let name = symbol_short!("offset");
+    assert_eq!(client.try_start_rate_experiment(&name, &5_001, &15, &3, &86_400), Err(Ok(ContractError::InvalidInput)));
+    assert_eq!(client.try_start_rate_experiment(&name, &5_000, &15, &6, &86_400), Err(Ok(ContractError::InvalidInput)));
+    let experiment_id = client.start_rate_experiment(&name, &5_000, &0, &3, &(30 * 86_400));
+    assert_eq!(client.try_start_rate_experiment(&name, &5_000, &15, &3, &86_400), Err(Ok(ContractError::ExperimentLive)));
+
+    // Half the borrowers are priced with no equity adjustment on a base three points higher,
+    // and keep their arm
+    let mut treated_apps = 0;
+    for _ in 0..16 {
+        env.budget().reset_unlimited();
+        let borrower = Address::generate(&env);
+        let application_id = client.submit_application(&borrower, &asset_id, &5_000, &location);
+        let (arm_experiment, treated) = client.get_experiment_arm(&application_id).unwrap();
+        assert_eq!(arm_experiment, experiment_id);
+        let expected = if treated { 11 } else { control_rate };
+        assert_eq!(client.get_application(&application_id).adjusted_rate, expected);
+        let again = client.submit_application(&borrower, &asset_id, &5_000, &location);
+        assert_eq!(client.get_experiment_arm(&again), Some((experiment_id, treated)));
+        if treated {
+            treated_apps += 2;
+            client.approve_application(&admin, &application_id);
+            client.record_interest_payment(&application_id, &100, &true);
+        }
+    }
+
+    let experiment = client.get_rate_experiment(&experiment_id);
+    assert!(treated_apps > 0 && treated_apps < 32);
+    assert_eq!(experiment.treatment.applications, treated_apps);
+    assert_eq!(experiment.control.applications, 32 - treated_apps);
+    assert_eq!(experiment.treatment.rate_sum, 11 * treated_apps as i64);
+    assert_eq!(experiment.treatment.approved, treated_apps / 2);
+    assert_eq!(experiment.treatment.interest_paid, 100 * (treated_apps / 2) as i128);
+    assert_eq!((experiment.control.approved, experiment.control.on_time_payments), (0, 0));
+
+    // Once ended, new applications are priced by the live model alone
+    assert_eq!(client.end_rate_experiment(), experiment_id);
+    assert_eq!(client.get_rate_experiment(&experiment_id).ends_at, env.ledger().timestamp());
+    let after = client.submit_application(&Address::generate(&env), &asset_id, &5_000, &location);
+    assert_eq!(client.get_experiment_arm(&after), None);
+    assert_eq!(client.try_end_rate_experiment(), Err(Ok(ContractError::NotFound)));
+}
+
+#[test]
+fn test_location_risk_adds_a_bounded_premium_to_the_rate() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, EquityRateAdjuster);
+    let client = EquityRateAdjusterClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &Address::generate(&env), &8);
+    let location = symbol_short!("downtown");
+    client.update_urban_data(&location, &1, &10, &1, &10);
+    let adjustment = client.calculate_rate_adjustment(&location);
+    assert_eq!(client.try_get_risk_data(&location), Err(Ok(ContractError::NotFound)));
+
+    // Two points for theft and one for vandalism
+    assert_eq!(client.update_risk_data(&location, &45, &60, &0), 3);
+    let risk = client.get_risk_data(&location);
+    assert_eq!((risk.theft_rate, risk.vandalism_incidents, risk.weather_downtime), (45, 60, 0));
+    assert_eq!(client.peek_risk_data(&location), Some(risk));
+    assert_eq!(client.calculate_rate_adjustment(&location), adjustment + 3);
+
+    // However risky the location, the premium stops at three points
+    assert_eq!(client.update_risk_data(&location, &200, &500, &2_000), 3);
+    assert_eq!(client.calculate_rate_adjustment(&location), adjustment + 3);
+    assert_eq!(client.update_risk_data(&location, &0, &0, &0), 0);
+    assert_eq!(client.calculate_rate_adjustment(&location), adjustment);
+}
+
+#[test]
+fn test_missed_installments_move_a_loan_to_delinquent_then_default() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, EquityRateAdjuster);
+    let client = EquityRateAdjusterClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    client.initialize(&admin, &oracle, &8);
+    let token_id = env.register_stellar_asset_contract(Address::generate(&env));
+    let token = TokenClient::new(&env, &token_id);
+    let loan_pool = env.register_contract(None, StubLoanPool);
+    let pool = StubLoanPoolClient::new(&env, &loan_pool);
+    pool.settle_in(&token_id);
+    pool.set_adjuster(&contract_id);
+    pool.hold_release(&1_200);
+    StellarAssetClient::new(&env, &token_id).mint(&loan_pool, &1_200);
+    link(&env, &client, &admin, symbol_short!("loan_pool"), &loan_pool);
+    link(&env, &client, &admin, symbol_short!("gov"), &env.register_contract(None, StubGovernance));
+
+    client.heartbeat(&oracle);
+    let location = symbol_short!("eastside");
+    client.update_urban_data(&location, &1, &30, &1, &10);
+    let borrower = Address::generate(&env);
+    let asset_id = symbol_short!("ebike_001");
+    let application_id = client.submit_application(&borrower, &asset_id, &1_200, &location);
+    client.approve_application(&admin, &application_id);
+    let loan = client.disburse(&admin, &application_id);
+    assert_eq!(token.balance(&borrower), 1_200);
+    assert_eq!(loan.installment, 100);
+
+    // The first installment falls due a payment interval after the 30-day grace period
+    let due = loan.amortization_start + 30 * 86_400;
+    env.ledger().with_mut(|ledger| ledger.timestamp = due + 7 * 86_400);
+    assert_eq!(client.try_mark_delinquent(&application_id), Err(Ok(ContractError::TooEarly)));
+    env.ledger().with_mut(|ledger| ledger.timestamp += 1);
+    assert_eq!(client.mark_delinquent(&application_id), 5);
+    assert_eq!(client.get_loan(&application_id).stage, symbol_short!("delinq"));
+    assert_eq!(client.try_mark_delinquent(&application_id), Err(Ok(ContractError::InvalidStatus)));
+    assert_eq!(client.try_mark_defaulted(&application_id), Err(Ok(ContractError::TooEarly)));
+
+    // Paying the fee, the interest and the missed installment cures it
+    let catch_up = 5 + client.get_accrued_interest(&application_id) + 100;
+    client.repay(&application_id, &catch_up);
+    assert_eq!(token.balance(&borrower), 1_200 - catch_up);
+    assert_eq!(token.balance(&loan_pool), catch_up);
+    let loan = client.get_loan(&application_id);
+    assert_eq!((loan.stage, loan.late_fees, loan.outstanding_principal), (symbol_short!("current"), 0, 1_100));
+
+    // The second installment goes unpaid for the whole default period
+    let due = due + 30 * 86_400;
+    env.ledger().with_mut(|ledger| ledger.timestamp = due + 7 * 86_400 + 1);
+    client.mark_delinquent(&application_id);
+    env.ledger().with_mut(|ledger| ledger.timestamp = due + 90 * 86_400 + 1);
+    let balance = client.mark_defaulted(&application_id);
+    assert_eq!(balance, 1_100 + 5 + client.get_accrued_interest(&application_id));
+    assert_eq!(client.get_application(&application_id).status, symbol_short!("defaulted"));
+    assert!(client.is_asset_defaulted(&asset_id));
+
+    // Repaying a defaulted loan in full, fees and interest included, clears the asset's default
+    StellarAssetClient::new(&env, &token_id).mint(&borrower, &200);
+    assert_eq!(client.repay(&application_id, &balance), 0);
+    assert_eq!(token.balance(&borrower), 1_200 + 200 - catch_up - balance);
+    assert_eq!(token.balance(&loan_pool), catch_up + balance);
+    assert_eq!(client.get_application(&application_id).status, symbol_short!("completed"));
+    assert!(!client.is_asset_defaulted(&asset_id));
+}
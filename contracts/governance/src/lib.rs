@@ -1,26 +1,92 @@
 #![no_std]
+// Entrypoints take their arguments flat, so signed and relayed calls carry many
+#![allow(clippy::too_many_arguments)]
 use soroban_sdk::{
-    contract, contractimpl, contracttype, symbol_short, vec, Address, Env, Map, Symbol, Vec,
+    auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation},
+    contract, contractimpl, contracttype, symbol_short, vec, xdr::ToXdr, Address, Bytes, BytesN,
+    Env, IntoVal, InvokeError, Map, String, Symbol, TryFromVal, Val, Vec,
 };
 
+use mobility_types::{access, attestation, changes, ids, journal, page_result, rebalance, timelock, ttl, upgrade, AllocationWeight, ApprovalRule, ChangeLogPage, ChangeValue, Payee, PendingChange, FeederKey, DistributorClient, EpochKpis, EquityOracleClient, Gate, EpochSchedule, GovernanceInterface, LoanPoolClient, Metadata, PageRequest, RecordChange, RecordChangePage, PendingAction, Role, TokenClient, TtlConfig};
+
+mobility_types::contract_errors! {
+    NoCoreVote = 66,
+    Pending = 113,
+    ProposalExists = 117,
+    ProposalNotFound = 119,
+    Sealed = 126,
+    UnknownProposalType = 133,
+    Delegated = 142,
+    CircularDelegation = 143,
+}
+
 /// Represents a governance proposal
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Proposal {
     pub id: Symbol,
-    pub title: Symbol,
-    pub description: Symbol,
+    pub metadata: Metadata, // Title and description, committed by hash
     pub proposer: Address,
-    pub proposal_type: Symbol, // "asset_funding", "rate_adjustment", "policy_change"
-    pub target_asset: Option<Symbol>, // For asset-specific proposals
+    pub proposal_type: Symbol, // "asset_funding", "rate_adjustment", "policy_change", "emergency", "rebalance"
+    pub target_asset: Symbol, // For asset-specific proposals; empty otherwise
     pub amount: Option<i128>, // For funding proposals
     pub start_time: u64,
     pub end_time: u64,
     pub status: Symbol, // "active", "passed", "failed", "executed"
-    pub yes_votes: i128,
+    pub yes_votes: i128, // Filled from the per-option accumulators when read or finalized
     pub no_votes: i128,
     pub total_votes: i128,
     pub equity_boost_threshold: i32, // Minimum equity score for boost
+    pub snapshot_ledger: u32, // Ledger at creation; stake must predate it by the minimum stake age
+    pub policy_bands: Vec<PolicyBand>, // Rate bands a policy_change proposal binds; empty for other types
+    pub emergency_request: Option<u32>, // Distributor emergency payout request approved by this vote
+    pub calls: Vec<ProposalCall>, // Contract calls made when the proposal is executed
+    pub rebalance_targets: Vec<AllocationWeight>, // For rebalance proposals investing pooled capital; empty otherwise
+    pub voting_mode: VotingMode, // How stake turns into voting power, fixed at creation
+}
+
+/// Cumulative tally of a proposal at a keeper-triggered checkpoint
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TallyCheckpoint {
+    pub proposal_id: Symbol,
+    pub sequence: u32, // 1 for the first checkpoint of a proposal
+    pub yes_power: i128,
+    pub no_power: i128,
+    pub abstain_power: i128,
+    pub votes_cast: u32,
+    pub turnout_bps: i128, // Power cast as a share of the electorate's power under the proposal's voting mode
+    pub timestamp: u64,
+}
+
+page_result!(ProposalPage, Proposal);
+
+/// How a voter's stake turns into voting power on a proposal
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum VotingMode {
+    Linear, // Power equals stake
+    Quadratic, // Power is the integer square root of stake
+    OnePersonOneVote, // Every voter with stake has one unit of power
+}
+
+/// A call a proposal makes when executed. Calls targeting governance itself set one of its
+/// numeric parameters (see PARAM_ACTIONS) to a single i128 argument
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalCall {
+    pub target: Address,
+    pub function: Symbol,
+    pub args: Vec<Val>,
+}
+
+/// Rate parameter band proposed by a policy_change referendum
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PolicyBand {
+    pub parameter: Symbol, // "base_rate", "max_adj", or "rate"
+    pub min: i128,
+    pub max: i128,
 }
 
 /// Represents a voter's participation
@@ -30,13 +96,36 @@ pub struct Vote {
     pub voter: Address,
     pub proposal_id: Symbol,
     pub vote: Symbol, // "yes", "no", "abstain"
-    pub voting_power: i128,
+    pub voting_power: i128, // Stake-derived power, including power delegated to the voter
     pub equity_boost: i128,
     pub total_power: i128,
     pub equity_score: i32,
     pub timestamp: u64,
 }
 
+/// Who cast the vote a participation badge records
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Carrier {
+    Voter, // The badge holder voted directly
+    Delegate(Address), // A delegate carried the holder's power
+}
+
+/// Non-transferable record that a voter took part in a finalized proposal
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParticipationBadge {
+    pub proposal_id: Symbol,
+    pub voter: Address,
+    pub vote: Symbol,
+    pub total_power: i128, // Zero when the voter's power was carried by a delegate
+    pub carried_by: Carrier,
+    pub outcome: Symbol, // Proposal status when the badge was issued
+    pub issued_at: u64,
+}
+
+page_result!(BadgePage, ParticipationBadge);
+
 /// Represents a voter's stake and equity data
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -47,6 +136,154 @@ pub struct VoterData {
     pub voting_power: i128,
     pub last_vote_time: u64,
     pub total_votes_cast: i32,
+    pub equity_updated_at: u64, // When the oracle last updated the equity score
+    pub stake_since_ledger: u32, // Ledger of the latest stake increase
+    pub prior_stake: i128, // Matured stake held before the latest increase
+}
+
+/// Voter data as stored in the legacy monolithic layout, before stake-age tracking
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LegacyVoterData {
+    pub address: Address,
+    pub stake_amount: i128,
+    pub equity_score: i32,
+    pub voting_power: i128,
+    pub last_vote_time: u64,
+    pub total_votes_cast: i32,
+    pub equity_updated_at: u64,
+}
+
+/// Progress of moving legacy monolithic data into the keyed layout
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MigrationState {
+    pub core_done: bool, // Instance data rebuilt under the new key
+    pub votes_cursor: u32, // Legacy votes moved so far
+    pub votes_done: bool,
+    pub voters_cursor: u32, // Legacy voters moved so far
+    pub voters_done: bool,
+    pub sealed: bool, // Legacy key removed; no further migration possible
+}
+
+/// Optional self-published profile for a borrower, investor, or operator
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Profile {
+    pub owner: Address,
+    pub name_hash: BytesN<32>, // Hash of the display name; the name itself stays off-chain
+    pub preferred_zone: Symbol, // Empty if the owner named no zone
+    pub role_flags: u32, // Roles the owner claims (ROLE_* bits)
+    pub verified_roles: u32, // Claimed roles confirmed by the oracle
+    pub updated_at: u64,
+}
+
+/// Compact audit record for an equity boost, bonus, or rate discount
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BoostAuditRecord {
+    pub subject: Address, // Who received the boost
+    pub amount: i128, // Size of the boost (units depend on rule)
+    pub rule: Symbol, // Which fairness rule granted it
+    pub data_version: u64, // Timestamp of the data the rule was evaluated against
+    pub timestamp: u64,
+}
+
+page_result!(BoostAuditPage, BoostAuditRecord);
+
+/// Portfolio health alert reported by the loan pool
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PortfolioAlert {
+    pub delinquency_bps: i32, // Share of outstanding principal delinquent or defaulted
+    pub above_threshold: bool, // false when delinquency has recovered below the threshold
+    pub timestamp: u64,
+}
+
+page_result!(PortfolioAlertPage, PortfolioAlert);
+
+/// Outcome of a single cross-contract call made while executing a proposal
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExecutionAction {
+    pub target: Address,
+    pub function: Symbol,
+    pub success: bool,
+}
+
+/// On-chain record of a proposal execution attempt
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExecutionReceipt {
+    pub proposal_id: Symbol,
+    pub ledger: u32,
+    pub executor: Address,
+    pub actions: Vec<ExecutionAction>,
+    pub succeeded: bool, // false when any action failed; the proposal stays "passed" and a retry skips completed actions
+    pub return_hash: BytesN<32>, // sha256 of the actions' return values
+    pub timestamp: u64,
+}
+
+/// Reason code attached to a rejection or veto
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum RejectionReason {
+    IncompleteInformation = 1,
+    Affordability = 2,
+    IneligibleLocation = 3,
+    Duplicate = 4,
+    PolicyViolation = 5,
+    SuspectedFraud = 6,
+    Other = 7,
+}
+
+/// Why a record was rejected, with hashes of any off-chain evidence
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RejectionRecord {
+    pub reason: RejectionReason,
+    pub evidence: Vec<BytesN<32>>, // Empty if no evidence was attached
+    pub timestamp: u64,
+}
+
+/// Platform fee rate applied to assets at or above a zone equity score
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeTier {
+    pub min_equity_score: i32,
+    pub fee_bps: u32,
+}
+
+/// Program-wide figures for one calendar year, gathered from every contract's epoch counters
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AnnualSummary {
+    pub year: u32,
+    pub first_epoch: u32, // Epochs that began within the year, as [first_epoch, end_epoch)
+    pub end_epoch: u32,
+    pub financed: i128,
+    pub repaid_principal: i128,
+    pub repaid_interest: i128,
+    pub defaults: u32,
+    pub distributions: u32,
+    pub distributed: i128,
+    pub platform_fees: i128,
+    pub rides: i128,
+    pub underserved_rides: i128,
+    pub loans_approved: u32,
+    pub rate_discount_bps: i128, // Sum over approved loans of (base rate - adjusted rate)
+    pub proposals: u32, // Proposals opened during the year
+    pub proposals_passed: u32, // Of those, passed or executed
+}
+
+/// Frozen annual summary with the hash an audited report can cite
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AnnualReport {
+    pub summary: AnnualSummary,
+    pub content_hash: BytesN<32>, // sha256 of the summary's XDR encoding
+    pub finalized_at: u64,
 }
 
 /// Contract data structure
@@ -56,349 +293,2637 @@ pub struct DataKey {
     pub admin: Address,
     pub oracle: Address, // Equity oracle address
     pub loan_pool: Address, // Loan pool contract address
-    pub proposals: Map<Symbol, Proposal>,
-    pub votes: Map<Symbol, Vec<Vote>>, // proposal_id -> votes
-    pub voters: Map<Address, VoterData>,
+    pub total_voting_power: i128, // Sum of voting_power across registered voters
+    pub total_quadratic_power: i128, // Sum of quadratic power across registered voters
+    pub staked_voter_count: u32, // Registered voters with stake, the one-person-one-vote electorate
+    pub voter_count: u32,
     pub min_proposal_duration: u64, // Minimum proposal duration in seconds
     pub quorum_threshold: i32, // Minimum participation percentage
     pub equity_boost_multiplier: i32, // Multiplier for equity-boosted votes
+    pub treasury_balance: i128, // Funds available for asset_funding proposals, held in the loan pool's settlement asset
+    pub total_escrowed: i128, // Portion of the treasury locked by open proposals
+    pub escrows: Map<Symbol, i128>, // proposal_id -> escrowed amount
+    pub paused: bool, // Set by admin or by a failed invariant check when auto_pause is on
+    pub disabled_features: Vec<Symbol>, // Entrypoint features switched off by admin
+    pub auto_pause: bool,
+    pub fee_schedule: Vec<FeeTier>, // Ascending by min_equity_score; first tier starts at 0
+    pub min_stake_age_ledgers: u32, // Stake must predate a proposal by this many ledgers to count
+    pub epoch_schedule: EpochSchedule, // Shared calendar read by the distributor and rate adjuster
+    pub gas_rebate_stake_threshold: i128, // Voters staking less than this earn fee rebates
+    pub gas_rebate_per_vote: i128, // Rebate per vote on a finalized proposal, in stroops
+    pub approval_rule: ApprovalRule, // Threshold and approvers for large disbursements
+    pub pending_actions: Map<u32, PendingAction>, // action_id -> large disbursement awaiting approvals
+    pub next_action_id: u32,
+}
+
+const DATA_KEY: Symbol = symbol_short!("DATA_V2");
+// Monolithic layout with votes and voters held inline; drained by migrate_chunk
+const LEGACY_DATA_KEY: Symbol = symbol_short!("DATA_KEY");
+
+// Set once by initialize to the storage layout version; its presence marks the contract initialized
+const INIT_KEY: Symbol = symbol_short!("INIT");
+const CONTRACT_VERSION: u32 = 2;
+const MIGRATION_KEY: Symbol = symbol_short!("MIGRATE");
+// Set only by initialize_sandbox; waives timelocks and enables the sandbox helpers
+const SANDBOX_KEY: Symbol = symbol_short!("SANDBOX");
+
+/// Persistent entries for per-record state that has moved out of the instance data
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum StorageKey {
+    Proposal(Symbol),
+    ProposalIds, // Every proposal id, in creation order
+    ActiveProposalIds, // Ids of proposals still open for voting, in creation order
+    AnnualReport(u32), // year -> frozen AnnualReport
+    RateAdjuster, // Target of policy_change band referenda
+    RevenueDistributor, // Target of emergency fund payout votes
+    ExecutionReceipt(Symbol), // proposal_id -> latest execution attempt
+    CompletedActions(Symbol), // proposal_id -> indices of actions that succeeded in an earlier attempt
+    Veto(Symbol), // proposal_id -> veto reason
+    RelayKey(Address), // user -> ed25519 key authorizing relayed calls
+    RelayNonce(Address), // user -> next expected relay nonce
+    IdSequence(Address), // proposer -> proposals created, mixed into IDs
+    GasRebatePool(u32), // epoch -> treasury funds left for rebates
+    ProposalBondBps, // Share of a funding request its proposer bonds, once changed from the default
+    Bond(Symbol), // proposal_id -> (proposer, bonded amount) until the proposal settles
 }
 
-const DATA_KEY: Symbol = symbol_short!("DATA_KEY");
+// Votes, tallies, and voter data live in their own persistent entries so a vote
+// touches a fixed number of small entries no matter how many voters there are
+const VOTE_KEY: Symbol = symbol_short!("VOTE"); // (VOTE_KEY, proposal_id, voter) -> Vote
+const VOTE_AT_KEY: Symbol = symbol_short!("VOTE_AT"); // (VOTE_AT_KEY, proposal_id, index) -> voter
+const VOTE_COUNT_KEY: Symbol = symbol_short!("VOTE_CNT"); // (VOTE_COUNT_KEY, proposal_id) -> u32
+const TALLY_KEY: Symbol = symbol_short!("TALLY"); // (TALLY_KEY, proposal_id, option) -> i128
+const VOTER_KEY: Symbol = symbol_short!("VOTER"); // (VOTER_KEY, voter) -> VoterData
+const PROFILE_KEY: Symbol = symbol_short!("PROFILE"); // (PROFILE_KEY, owner) -> Profile
+const GAS_VOTES_KEY: Symbol = symbol_short!("GAS_VOTES"); // (GAS_VOTES_KEY, voter, epoch) -> proposal ids
+const DELEGATE_KEY: Symbol = symbol_short!("DELEGATE"); // (DELEGATE_KEY, delegator) -> delegate
+const DELEGATORS_KEY: Symbol = symbol_short!("DELEGATRS"); // (DELEGATORS_KEY, delegate) -> Vec<Address>
+const CAST_BY_KEY: Symbol = symbol_short!("CAST_BY"); // (CAST_BY_KEY, proposal_id, delegator) -> delegate who carried their power
+const BADGE_KEY: Symbol = symbol_short!("BADGE"); // (BADGE_KEY, voter, proposal_id) -> ParticipationBadge
+const BADGES_KEY: Symbol = symbol_short!("BADGES"); // (BADGES_KEY, voter) -> proposal ids with a badge
+const CHECKPOINT_KEY: Symbol = symbol_short!("TALLY_CP"); // (CHECKPOINT_KEY, proposal_id) -> latest TallyCheckpoint
+// Journal streams of BoostAuditRecords, indexed both ways
+const BOOST_BY_SUBJECT: Symbol = symbol_short!("BOOST_SUB"); // (BOOST_BY_SUBJECT, subject)
+const BOOST_BY_RULE: Symbol = symbol_short!("BOOST_RUL"); // (BOOST_BY_RULE, rule)
+const ALERTS_KEY: Symbol = symbol_short!("ALERTS"); // Journal stream of PortfolioAlerts
+
+// Profile role flags
+const ROLE_BORROWER: u32 = 1;
+const ROLE_INVESTOR: u32 = 2;
+const ROLE_OPERATOR: u32 = 4;
+const ALL_ROLES: u32 = ROLE_BORROWER | ROLE_INVESTOR | ROLE_OPERATOR;
+
+
+// Emergency payout votes run for a fixed day regardless of the minimum duration
+const EMERGENCY_VOTE_DURATION: u64 = 86_400;
+
+// Minimum stake age can be set up to ~7 days of 5s ledgers
+const MAX_STAKE_AGE_LEDGERS: u32 = 120_960;
+
+// Gas rebates are capped at 0.1 XLM per vote
+const MAX_GAS_REBATE_PER_VOTE: i128 = 1_000_000;
+
+// Funding proposals bond 1% of the amount they escrow unless changed
+const DEFAULT_PROPOSAL_BOND_BPS: i128 = 100;
+
+// Delegated power is followed at most this many hops when a delegate votes
+const MAX_DELEGATION_DEPTH: u32 = 4;
+
+// Numeric admin-change actions a policy_change proposal can apply directly
+const PARAM_ACTIONS: [&str; 6] = ["quorum", "boost_mul", "min_dur", "stake_age", "gas_stake", "gas_vote"];
+
+// Tally checkpoints of one proposal are at least 6 hours apart
+const TALLY_CHECKPOINT_INTERVAL: u64 = 21_600;
+
+// Platform fees are capped at 10%
+const MAX_FEE_BPS: u32 = 1_000;
+
+// An annual report reads every contract's counters once per epoch, so short epochs are capped
+const MAX_REPORT_EPOCHS: u32 = 400;
+
+/// Integer square root, rounded down; zero for non-positive values
+fn isqrt(value: i128) -> i128 {
+    if value <= 0 {
+        return 0;
+    }
+    let mut root = value;
+    let mut next = (root + 1) / 2;
+    while next < root {
+        root = next;
+        next = (root + value / root) / 2;
+    }
+    root
+}
 
 #[contract]
 pub struct Governance;
 
 #[contractimpl]
 impl Governance {
-    /// Initialize the contract
+    /// Initialize the contract. Can only be called once.
     pub fn initialize(
         env: &Env,
         admin: Address,
         oracle: Address,
         loan_pool: Address,
         min_proposal_duration: u64,
-    ) {
-        let data = DataKey {
-            admin,
-            oracle,
-            loan_pool,
-            proposals: Map::new(env),
-            votes: Map::new(env),
-            voters: Map::new(env),
-            min_proposal_duration,
-            quorum_threshold: 10, // 10% minimum participation
-            equity_boost_multiplier: 150, // 50% boost for high-equity voters
-        };
-        env.storage().instance().set(&DATA_KEY, &data);
+    ) -> Result<(), ContractError> {
+        admin.require_auth();
+
+        // Deployments still on the legacy layout are initialized too
+        let storage = env.storage().instance();
+        if storage.has(&INIT_KEY) || storage.has(&DATA_KEY) || storage.has(&LEGACY_DATA_KEY) {
+            return Err(ContractError::AlreadyInitialized);
+        }
+
+        let data = Self::default_data(env, admin, oracle, loan_pool, min_proposal_duration);
+        ttl::set_instance(env, &DATA_KEY, &data);
+        ttl::set_instance(env, &INIT_KEY, &CONTRACT_VERSION);
+
+        Ok(())
     }
 
-    /// Create a new governance proposal
-    pub fn create_proposal(
-        env: &Env,
-        proposer: Address,
-        title: Symbol,
-        description: Symbol,
-        proposal_type: Symbol,
-        target_asset: Option<Symbol>,
-        amount: Option<i128>,
-        duration: u64,
-    ) -> Result<Symbol, Symbol> {
+    /// Change the oracle, loan pool and minimum proposal duration set at initialization
+    /// without touching other state (oracle manager only)
+    pub fn reconfigure(env: &Env, caller: Address, oracle: Address, loan_pool: Address, min_proposal_duration: u64) -> Result<(), ContractError> {
         let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
-        
-        // Validate duration
-        if duration < data.min_proposal_duration {
-            return Err(symbol_short!("DURATION_TOO_SHORT"));
-        }
 
-        // Generate proposal ID
-        let proposal_id = Self::generate_proposal_id(env, &proposer, &title);
+        access::check(env, &data.admin, Role::OracleManager, &caller)?;
 
-        // Check if proposal already exists
-        if data.proposals.contains_key(&proposal_id) {
-            return Err(symbol_short!("PROPOSAL_EXISTS"));
+        if !(1..=31_536_000).contains(&min_proposal_duration) {
+            return Err(ContractError::InvalidInput);
         }
 
-        let current_time = env.ledger().timestamp();
-        let end_time = current_time + duration;
+        data.oracle = oracle.clone();
+        data.loan_pool = loan_pool.clone();
+        data.min_proposal_duration = min_proposal_duration;
+        ttl::set_instance(env, &DATA_KEY, &data);
 
-        let proposal = Proposal {
-            id: proposal_id.clone(),
-            title,
-            description,
-            proposer,
-            proposal_type,
-            target_asset,
-            amount,
-            start_time: current_time,
-            end_time,
-            status: symbol_short!("active"),
-            yes_votes: 0,
-            no_votes: 0,
-            total_votes: 0,
-            equity_boost_threshold: 70, // 70% equity score for boost
-        };
+        env.events().publish((symbol_short!("reconfig"),), (oracle, loan_pool, min_proposal_duration));
 
-        data.proposals.set(&proposal_id, &proposal);
-        data.votes.set(&proposal_id, vec![env]);
-        
-        env.storage().instance().set(&DATA_KEY, &data);
-        
-        Ok(proposal_id)
+        Ok(())
     }
 
-    /// Vote on a proposal with equity-weighted voting power
-    pub fn vote(
+    /// Initialize a sandbox instance for piloting governance flows on testnet. Timelocks and stake
+    /// maturity are waived, epochs count from deployment and the sandbox helpers are enabled.
+    /// An instance can only become a sandbox here, at initialization
+    pub fn initialize_sandbox(
         env: &Env,
-        voter: Address,
-        proposal_id: Symbol,
-        vote_choice: Symbol,
-    ) -> Result<i128, Symbol> {
-        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
-        
-        // Get proposal
-        let mut proposal = data.proposals.get(&proposal_id).ok_or(symbol_short!("PROPOSAL_NOT_FOUND"))?;
-        
-        // Check if proposal is still active
-        if proposal.status != symbol_short!("active") {
-            return Err(symbol_short!("PROPOSAL_NOT_ACTIVE"));
-        }
+        admin: Address,
+        oracle: Address,
+        loan_pool: Address,
+        min_proposal_duration: u64,
+    ) -> Result<(), ContractError> {
+        Self::initialize(env, admin, oracle, loan_pool, min_proposal_duration)?;
 
-        let current_time = env.ledger().timestamp();
-        if current_time > proposal.end_time {
-            return Err(symbol_short!("VOTING_ENDED"));
-        }
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        data.min_stake_age_ledgers = 0;
+        data.epoch_schedule.start = env.ledger().timestamp();
+        ttl::set_instance(env, &DATA_KEY, &data);
+        ttl::set_instance(env, &SANDBOX_KEY, &true);
 
-        // Get or create voter data
-        let mut voter_data = data.voters.get(&voter).unwrap_or(VoterData {
-            address: voter.clone(),
-            stake_amount: 0,
-            equity_score: 0,
-            voting_power: 0,
-            last_vote_time: 0,
-            total_votes_cast: 0,
-        });
+        env.events().publish((symbol_short!("sandbox"),), data.admin);
 
-        // Calculate voting power based on stake and equity
-        let voting_power = Self::calculate_voting_power(env, &voter_data);
-        let equity_boost = Self::calculate_equity_boost(env, &voter_data, &proposal);
-        let total_power = voting_power + equity_boost;
+        Ok(())
+    }
 
-        // Create vote record
-        let vote = Vote {
-            voter: voter.clone(),
-            proposal_id: proposal_id.clone(),
-            vote: vote_choice.clone(),
-            voting_power,
-            equity_boost,
-            total_power,
-            equity_score: voter_data.equity_score,
-            timestamp: current_time,
-        };
+    /// Whether this instance was initialized as a sandbox
+    pub fn is_sandbox(env: &Env) -> bool {
+        env.storage().instance().has(&SANDBOX_KEY)
+    }
 
-        // Update proposal votes
-        let mut votes = data.votes.get(&proposal_id).unwrap_or(vec![env]);
-        
-        // Check if voter already voted
-        for existing_vote in votes.iter() {
-            if existing_vote.voter == voter {
-                return Err(symbol_short!("ALREADY_VOTED"));
-            }
-        }
+    /// Credit test stake to a voter, keeping their equity score (sandbox only, treasurer only)
+    pub fn sandbox_mint(env: &Env, caller: Address, voter: Address, amount: i128) -> Result<i128, ContractError> {
+        Self::require_sandbox(env)?;
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
 
-        votes.push_back(&vote);
-        data.votes.set(&proposal_id, &votes);
+        access::check(env, &data.admin, Role::Treasurer, &caller)?;
 
-        // Update proposal totals
-        if vote_choice == symbol_short!("yes") {
-            proposal.yes_votes += total_power;
-        } else if vote_choice == symbol_short!("no") {
-            proposal.no_votes += total_power;
+        if amount <= 0 {
+            return Err(ContractError::InvalidAmount);
         }
-        // Abstain votes don't count toward totals
 
-        proposal.total_votes += total_power;
-        data.proposals.set(&proposal_id, &proposal);
+        let (stake, equity_score) = Self::load_voter(env, &voter)
+            .map(|voter_data| (voter_data.stake_amount, voter_data.equity_score))
+            .unwrap_or((0, 0));
+        Self::store_voter_data(env, data, voter, stake + amount, equity_score)?;
 
-        // Update voter data
-        voter_data.last_vote_time = current_time;
-        voter_data.total_votes_cast += 1;
-        data.voters.set(&voter, &voter_data);
-        
-        env.storage().instance().set(&DATA_KEY, &data);
-        
-        Ok(total_power)
+        Ok(stake + amount)
     }
 
-    /// Execute a passed proposal
-    pub fn execute_proposal(env: &Env, proposal_id: Symbol) -> Result<(), Symbol> {
+    /// Move the shared epoch calendar forward by whole epochs and return the new current epoch
+    /// (sandbox only, admin only)
+    pub fn sandbox_advance_epochs(env: &Env, caller: Address, epochs: u32) -> Result<u32, ContractError> {
+        Self::require_sandbox(env)?;
         let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
-        
-        // Only admin can execute proposals
-        if env.current_contract_address() != data.admin {
-            return Err(symbol_short!("UNAUTHORIZED"));
-        }
 
-        let mut proposal = data.proposals.get(&proposal_id).ok_or(symbol_short!("PROPOSAL_NOT_FOUND"))?;
-        
-        if proposal.status != symbol_short!("passed") {
-            return Err(symbol_short!("PROPOSAL_NOT_PASSED"));
-        }
+        access::check(env, &data.admin, Role::Admin, &caller)?;
 
-        // Execute based on proposal type
-        match proposal.proposal_type.as_str() {
-            "asset_funding" => {
-                // In a real implementation, this would trigger funding
-                // For demo purposes, we'll just mark as executed
-            },
-            "rate_adjustment" => {
-                // In a real implementation, this would adjust rates
-            },
-            "policy_change" => {
-                // In a real implementation, this would update policies
-            },
-            _ => return Err(symbol_short!("UNKNOWN_PROPOSAL_TYPE")),
-        }
+        // Pulling the calendar start back makes later epochs begin earlier
+        let shift = epochs as u64 * data.epoch_schedule.length;
+        data.epoch_schedule.start = data.epoch_schedule.start.checked_sub(shift).ok_or(ContractError::InvalidInput)?;
+        ttl::set_instance(env, &DATA_KEY, &data);
 
-        proposal.status = symbol_short!("executed");
-        data.proposals.set(&proposal_id, &proposal);
-        
-        env.storage().instance().set(&DATA_KEY, &data);
-        
-        Ok(())
+        env.events().publish((symbol_short!("calendar"),), data.epoch_schedule.clone());
+
+        Ok(data.epoch_schedule.epoch_at(env.ledger().timestamp()))
     }
 
-    /// Finalize voting and determine proposal outcome
-    pub fn finalize_proposal(env: &Env, proposal_id: Symbol) -> Result<Symbol, Symbol> {
-        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
-        
-        let mut proposal = data.proposals.get(&proposal_id).ok_or(symbol_short!("PROPOSAL_NOT_FOUND"))?;
-        
-        if proposal.status != symbol_short!("active") {
-            return Err(symbol_short!("PROPOSAL_NOT_ACTIVE"));
+    fn require_sandbox(env: &Env) -> Result<(), ContractError> {
+        if !Self::is_sandbox(env) {
+            return Err(ContractError::Unauthorized);
         }
+        Ok(())
+    }
 
-        let current_time = env.ledger().timestamp();
-        if current_time <= proposal.end_time {
-            return Err(symbol_short!("VOTING_NOT_ENDED"));
-        }
+    /// Get the storage layout version recorded at initialization, or 0 if uninitialized
+    pub fn get_version(env: &Env) -> u32 {
+        env.storage().instance().get(&INIT_KEY).unwrap_or(0)
+    }
 
-        // Calculate total possible votes (all stakeholders)
-        let total_possible_votes = Self::calculate_total_possible_votes(env);
-        let participation_rate = if total_possible_votes > 0 {
-            proposal.total_votes * 100 / total_possible_votes
-        } else {
-            0
-        };
+    /// Version of the running code; `get_version` lags behind it until `migrate` runs
+    pub fn version(_env: &Env) -> u32 {
+        CONTRACT_VERSION
+    }
 
-        // Check quorum
-        if participation_rate < data.quorum_threshold {
-            proposal.status = symbol_short!("failed");
-            data.proposals.set(&proposal_id, &proposal);
-            env.storage().instance().set(&DATA_KEY, &data);
-            return Ok(symbol_short!("failed"));
-        }
+    /// Replace the contract's code with an uploaded wasm, keeping its storage (admin only).
+    /// The new code's `migrate` must run before storage written by the old layout is used
+    pub fn upgrade(env: &Env, caller: Address, new_wasm_hash: BytesN<32>) -> Result<(), ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
 
-        // Determine outcome
-        let outcome = if proposal.yes_votes > proposal.no_votes {
-            proposal.status = symbol_short!("passed");
-            symbol_short!("passed")
-        } else {
-            proposal.status = symbol_short!("failed");
-            symbol_short!("failed")
-        };
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+        upgrade::upgrade(env, new_wasm_hash);
 
-        data.proposals.set(&proposal_id, &proposal);
-        env.storage().instance().set(&DATA_KEY, &data);
-        
+        Ok(())
+    }
+
+    /// Bring storage up to this code's layout version after an upgrade (admin only)
+    pub fn migrate(env: &Env, caller: Address) -> Result<u32, ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+
+        let stored: u32 = env.storage().instance().get(&INIT_KEY).unwrap_or(0);
+        let version = upgrade::migrate(env, stored, CONTRACT_VERSION, Self::migrate_step)?;
+        ttl::set_instance(env, &INIT_KEY, &version);
+
+        Ok(version)
+    }
+
+    /// Rewrite storage from layout version `from` to `from + 1`. Each release that changes
+    /// the layout adds its step here. Deployments moved off the legacy layout by `migrate_chunk`
+    /// have no recorded version and start from 0
+    fn migrate_step(_env: &Env, _from: u32) -> Result<(), ContractError> {
+        Ok(())
+    }
+
+
+    /// Contract data with default settings
+    fn default_data(
+        env: &Env,
+        admin: Address,
+        oracle: Address,
+        loan_pool: Address,
+        min_proposal_duration: u64,
+    ) -> DataKey {
+        DataKey {
+            admin,
+            oracle,
+            loan_pool,
+            total_voting_power: 0,
+            total_quadratic_power: 0,
+            staked_voter_count: 0,
+            voter_count: 0,
+            min_proposal_duration,
+            quorum_threshold: 10, // 10% minimum participation
+            equity_boost_multiplier: 150, // 50% boost for high-equity voters
+            treasury_balance: 0,
+            total_escrowed: 0,
+            escrows: Map::new(env),
+            paused: false,
+            disabled_features: Vec::new(env),
+            auto_pause: false,
+            fee_schedule: vec![
+                env,
+                FeeTier { min_equity_score: 0, fee_bps: 200 },
+                FeeTier { min_equity_score: 60, fee_bps: 100 },
+                FeeTier { min_equity_score: 80, fee_bps: 50 },
+            ],
+            min_stake_age_ledgers: 17_280, // ~1 day of 5s ledgers
+            epoch_schedule: EpochSchedule::default_schedule(),
+            gas_rebate_stake_threshold: 1_000,
+            gas_rebate_per_vote: 100_000, // 0.01 XLM, several times a typical vote fee
+            approval_rule: ApprovalRule::disabled(env),
+            pending_actions: Map::new(env),
+            next_action_id: 1,
+        }
+    }
+
+    /// Create a new governance proposal
+    pub fn create_proposal(
+        env: &Env,
+        proposer: Address,
+        metadata: Metadata,
+        proposal_type: Symbol,
+        target_asset: Option<Symbol>,
+        amount: Option<i128>,
+        duration: u64,
+    ) -> Result<Symbol, ContractError> {
+        Self::new_proposal(env, proposer, metadata, proposal_type, target_asset, amount, duration, vec![env], None)
+    }
+
+    /// Create a proposal whose votes are weighted by the given voting mode instead of linear stake
+    pub fn create_proposal_with_mode(
+        env: &Env,
+        proposer: Address,
+        metadata: Metadata,
+        proposal_type: Symbol,
+        target_asset: Option<Symbol>,
+        amount: Option<i128>,
+        duration: u64,
+        voting_mode: VotingMode,
+    ) -> Result<Symbol, ContractError> {
+        let proposal_id = Self::new_proposal(env, proposer, metadata, proposal_type, target_asset, amount, duration, vec![env], None)?;
+
+        let mut proposal = Self::load_proposal(env, &proposal_id).unwrap();
+        proposal.voting_mode = voting_mode;
+        Self::save_proposal(env, &proposal);
+
+        Ok(proposal_id)
+    }
+
+    /// Create a proposal that makes the given calls when executed. asset_funding calls go to the
+    /// loan pool's create_asset, rate_adjustment calls to the rate adjuster's set_base_rate, and
+    /// policy_change calls to governance's own parameters
+    pub fn create_executable_proposal(
+        env: &Env,
+        proposer: Address,
+        metadata: Metadata,
+        proposal_type: Symbol,
+        target_asset: Option<Symbol>,
+        amount: Option<i128>,
+        duration: u64,
+        calls: Vec<ProposalCall>,
+    ) -> Result<Symbol, ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        Self::validate_calls(env, &data, &proposal_type, &calls)?;
+
+        let proposal_id = Self::new_proposal(env, proposer, metadata, proposal_type, target_asset, amount, duration, vec![env], None)?;
+
+        let mut proposal = Self::load_proposal(env, &proposal_id).unwrap();
+        proposal.calls = calls;
+        Self::save_proposal(env, &proposal);
+
+        Ok(proposal_id)
+    }
+
+    /// Check that a proposal's calls match what its type is allowed to do
+    fn validate_calls(env: &Env, data: &DataKey, proposal_type: &Symbol, calls: &Vec<ProposalCall>) -> Result<(), ContractError> {
+        for call in calls.iter() {
+            let allowed = if *proposal_type == Symbol::new(env, "asset_funding") {
+                call.target == data.loan_pool && call.function == Symbol::new(env, "create_asset")
+            } else if *proposal_type == Symbol::new(env, "rate_adjustment") {
+                Self::rate_adjuster(env).ok() == Some(call.target.clone()) && call.function == Symbol::new(env, "set_base_rate")
+            } else if *proposal_type == Symbol::new(env, "policy_change") {
+                call.target == env.current_contract_address()
+                    && call.args.len() == 1
+                    && PARAM_ACTIONS.iter().any(|action| call.function == Symbol::new(env, action))
+            } else {
+                false
+            };
+            if !allowed {
+                return Err(ContractError::InvalidInput);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Create a policy_change proposal that, once executed, binds a rate adjuster parameter to a band
+    pub fn create_policy_proposal(
+        env: &Env,
+        proposer: Address,
+        metadata: Metadata,
+        band: PolicyBand,
+        duration: u64,
+    ) -> Result<Symbol, ContractError> {
+        if band.min > band.max {
+            return Err(ContractError::InvalidInput);
+        }
+
+        Self::new_proposal(
+            env,
+            proposer,
+            metadata,
+            Symbol::new(env, "policy_change"),
+            None,
+            None,
+            duration,
+            vec![env, band],
+            None,
+        )
+    }
+
+    /// Create a short vote approving a guardian's emergency fund payout request on the distributor
+    pub fn create_emergency_proposal(
+        env: &Env,
+        proposer: Address,
+        metadata: Metadata,
+        request_id: u32,
+    ) -> Result<Symbol, ContractError> {
+        Self::new_proposal(
+            env,
+            proposer,
+            metadata,
+            Symbol::new(env, "emergency"),
+            None,
+            None,
+            EMERGENCY_VOTE_DURATION,
+            vec![env],
+            Some(request_id),
+        )
+    }
+
+    /// Create a proposal that, once executed, invests the loan pool's LP vault capital across
+    /// assets still raising by the given weights
+    pub fn create_rebalance_proposal(
+        env: &Env,
+        proposer: Address,
+        metadata: Metadata,
+        targets: Vec<AllocationWeight>,
+        duration: u64,
+    ) -> Result<Symbol, ContractError> {
+        rebalance::validate_targets(&targets)?;
+
+        let proposal_id = Self::new_proposal(
+            env,
+            proposer,
+            metadata,
+            Symbol::new(env, "rebalance"),
+            None,
+            None,
+            duration,
+            vec![env],
+            None,
+        )?;
+
+        let mut proposal = Self::load_proposal(env, &proposal_id).unwrap();
+        proposal.rebalance_targets = targets;
+        Self::save_proposal(env, &proposal);
+
+        Ok(proposal_id)
+    }
+
+    /// Validate and store a new proposal
+    fn new_proposal(
+        env: &Env,
+        proposer: Address,
+        metadata: Metadata,
+        proposal_type: Symbol,
+        target_asset: Option<Symbol>,
+        amount: Option<i128>,
+        duration: u64,
+        policy_bands: Vec<PolicyBand>,
+        emergency_request: Option<u32>,
+    ) -> Result<Symbol, ContractError> {
+        proposer.require_auth();
+
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        
+        // Validate duration; emergency votes use their own fixed window
+        if emergency_request.is_none() && duration < data.min_proposal_duration {
+            return Err(ContractError::InvalidInput);
+        }
+
+        // No new proposals for assets in a program that is winding down
+        let winding_down = target_asset.as_ref()
+            .map(|asset_id| LoanPoolClient::new(env, &data.loan_pool).is_winding_down(asset_id))
+            .unwrap_or(false);
+        Gate::new(data.paused, &data.disabled_features, &symbol_short!("propose"))
+            .program(winding_down)
+            .check()?;
+        metadata.check()?;
+
+        // Generate proposal ID
+        let proposal_id = Self::generate_proposal_id(env, &proposer, &metadata);
+
+        // Check if proposal already exists
+        if Self::load_proposal(env, &proposal_id).is_some() {
+            return Err(ContractError::ProposalExists);
+        }
+
+        // Escrow requested funds for funding proposals so execution cannot fail later. The
+        // proposer bonds a share of the request, so locking up the treasury isn't free
+        if proposal_type == Symbol::new(env, "asset_funding") {
+            let requested = amount.ok_or(ContractError::Nothing)?;
+            if requested <= 0 {
+                return Err(ContractError::InvalidAmount);
+            }
+            if target_asset.is_none() {
+                return Err(ContractError::InvalidInput);
+            }
+            if data.treasury_balance - data.total_escrowed < requested {
+                return Err(ContractError::NoFunds);
+            }
+            data.total_escrowed += requested;
+            data.escrows.set(proposal_id.clone(), requested);
+            Self::take_bond(env, &data, &proposal_id, &proposer, requested)?;
+        }
+
+        let current_time = env.ledger().timestamp();
+        let end_time = current_time + duration;
+
+        let proposal = Proposal {
+            id: proposal_id.clone(),
+            metadata,
+            proposer,
+            proposal_type,
+            target_asset: target_asset.clone().unwrap_or(Symbol::new(env, "")),
+            amount,
+            start_time: current_time,
+            end_time,
+            status: symbol_short!("active"),
+            yes_votes: 0,
+            no_votes: 0,
+            total_votes: 0,
+            equity_boost_threshold: 70, // 70% equity score for boost
+            snapshot_ledger: env.ledger().sequence(),
+            policy_bands,
+            emergency_request,
+            calls: vec![env],
+            rebalance_targets: vec![env],
+            voting_mode: VotingMode::Linear,
+        };
+
+        Self::save_proposal(env, &proposal);
+
+        changes::touch(env, symbol_short!("proposal"), proposal_id.clone());
+        env.events().publish(
+            (symbol_short!("proposed"), proposal_id.clone()),
+            (proposal.proposer, proposal.proposal_type, proposal.end_time),
+        );
+        
+        ttl::set_instance(env, &DATA_KEY, &data);
+        
+        Ok(proposal_id)
+    }
+
+    /// Vote on a proposal with equity-weighted voting power
+    pub fn vote(
+        env: &Env,
+        voter: Address,
+        proposal_id: Symbol,
+        vote_choice: Symbol,
+    ) -> Result<i128, ContractError> {
+        voter.require_auth();
+
+        Self::cast_vote(env, voter, proposal_id, vote_choice)
+    }
+
+    /// Record an authorized voter's vote
+    fn cast_vote(
+        env: &Env,
+        voter: Address,
+        proposal_id: Symbol,
+        vote_choice: Symbol,
+    ) -> Result<i128, ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        Gate::new(data.paused, &data.disabled_features, &symbol_short!("vote")).check()?;
+        
+        // Get proposal
+        let proposal = Self::load_proposal(env, &proposal_id).ok_or(ContractError::ProposalNotFound)?;
+        
+        // Check if proposal is still active
+        if proposal.status != symbol_short!("active") {
+            return Err(ContractError::InvalidStatus);
+        }
+
+        let current_time = env.ledger().timestamp();
+        if current_time > proposal.end_time {
+            return Err(ContractError::Expired);
+        }
+
+        // Voters who delegated vote through their delegate until they undelegate
+        if env.storage().persistent().has(&(DELEGATE_KEY, voter.clone())) {
+            return Err(ContractError::Delegated);
+        }
+
+        // Get or create voter data
+        let mut voter_data = Self::load_voter(env, &voter).unwrap_or(VoterData {
+            address: voter.clone(),
+            stake_amount: 0,
+            equity_score: 0,
+            voting_power: 0,
+            last_vote_time: 0,
+            total_votes_cast: 0,
+            equity_updated_at: 0,
+            stake_since_ledger: 0,
+            prior_stake: 0,
+        });
+
+        // Calculate voting power based on stake eligible at the proposal snapshot and equity
+        let snapshot = Self::snapshot_voter(env, &data, &voter_data, &proposal);
+        let equity_boost = Self::calculate_equity_boost(env, &snapshot, &proposal);
+
+        // Check if voter already voted, directly or through a delegate
+        let vote_key = (VOTE_KEY, proposal_id.clone(), voter.clone());
+        if env.storage().persistent().has(&vote_key)
+            || env.storage().persistent().has(&(CAST_BY_KEY, proposal_id.clone(), voter.clone()))
+        {
+            return Err(ContractError::AlreadyVoted);
+        }
+
+        // Delegators' stake-derived power joins the delegate's; the equity boost stays the delegate's own
+        let delegated_power = Self::carry_delegated_power(env, &data, &proposal, &voter, &voter, 1);
+        let voting_power = Self::calculate_voting_power(env, &snapshot, proposal.voting_mode) + delegated_power;
+        let total_power = voting_power + equity_boost;
+
+        // Create vote record
+        let vote = Vote {
+            voter: voter.clone(),
+            proposal_id: proposal_id.clone(),
+            vote: vote_choice.clone(),
+            voting_power,
+            equity_boost,
+            total_power,
+            equity_score: voter_data.equity_score,
+            timestamp: current_time,
+        };
+
+        // Store the vote and its position without touching the proposal record
+        let count_key = (VOTE_COUNT_KEY, proposal_id.clone());
+        let vote_count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+        ttl::set_persistent(env, &vote_key, &vote);
+        ttl::set_persistent(env, &(VOTE_AT_KEY, proposal_id.clone(), vote_count), &voter);
+        ttl::set_persistent(env, &count_key, &(vote_count + 1));
+
+        // Update the per-option accumulators
+        if vote_choice == symbol_short!("yes") || vote_choice == symbol_short!("no") {
+            Self::add_tally(env, &proposal_id, vote_choice, total_power);
+        }
+        // Abstain votes don't count toward totals
+
+        Self::add_tally(env, &proposal_id, symbol_short!("total"), total_power);
+        changes::touch(env, symbol_short!("proposal"), proposal_id.clone());
+        env.events().publish((symbol_short!("voted"), proposal_id.clone()), (voter.clone(), vote.vote, total_power));
+
+        // Low-stake voters can claim back their fees once the proposal is finalized
+        if voter_data.stake_amount < data.gas_rebate_stake_threshold {
+            let gas_key = (GAS_VOTES_KEY, voter.clone(), data.epoch_schedule.epoch_at(current_time));
+            let mut rebate_votes: Vec<Symbol> = env.storage().persistent().get(&gas_key).unwrap_or(vec![env]);
+            rebate_votes.push_back(proposal_id.clone());
+            ttl::set_persistent(env, &gas_key, &rebate_votes);
+        }
+
+        // Audit the equity voting boost
+        if equity_boost > 0 {
+            Self::log_boost(env, &voter, equity_boost, symbol_short!("vote_bst"), voter_data.equity_updated_at);
+        }
+
+        // Update voter data
+        voter_data.last_vote_time = current_time;
+        voter_data.total_votes_cast += 1;
+        ttl::set_persistent(env, &(VOTER_KEY, voter), &voter_data);
+        
+        Ok(total_power)
+    }
+
+    /// Publish a proposal's cumulative tally while voting is open, so dashboards can chart
+    /// momentum from events. Anyone may call it, at most once per checkpoint interval
+    pub fn checkpoint_tally(env: &Env, proposal_id: Symbol) -> Result<TallyCheckpoint, ContractError> {
+        let proposal = Self::load_proposal(env, &proposal_id).ok_or(ContractError::ProposalNotFound)?;
+
+        if proposal.status != symbol_short!("active") {
+            return Err(ContractError::InvalidStatus);
+        }
+
+        let now = env.ledger().timestamp();
+        if now > proposal.end_time {
+            return Err(ContractError::Expired);
+        }
+
+        let key = (CHECKPOINT_KEY, proposal_id.clone());
+        let previous: Option<TallyCheckpoint> = env.storage().persistent().get(&key);
+        if let Some(previous) = &previous {
+            if now < previous.timestamp + TALLY_CHECKPOINT_INTERVAL {
+                return Err(ContractError::TooEarly);
+            }
+        }
+
+        let proposal = Self::with_tally(env, proposal);
+        let possible_votes = Self::calculate_total_possible_votes(env, proposal.voting_mode);
+        let checkpoint = TallyCheckpoint {
+            proposal_id: proposal_id.clone(),
+            sequence: previous.map_or(1, |previous| previous.sequence + 1),
+            yes_power: proposal.yes_votes,
+            no_power: proposal.no_votes,
+            abstain_power: proposal.total_votes - proposal.yes_votes - proposal.no_votes,
+            votes_cast: env.storage().persistent().get(&(VOTE_COUNT_KEY, proposal_id.clone())).unwrap_or(0),
+            turnout_bps: if possible_votes > 0 { proposal.total_votes * 10_000 / possible_votes } else { 0 },
+            timestamp: now,
+        };
+        ttl::set_persistent(env, &key, &checkpoint);
+
+        env.events().publish((symbol_short!("tally_cp"), proposal_id), checkpoint.clone());
+
+        Ok(checkpoint)
+    }
+
+    /// Get the latest tally checkpoint published for a proposal
+    pub fn get_tally_checkpoint(env: &Env, proposal_id: Symbol) -> Option<TallyCheckpoint> {
+        env.storage().persistent().get(&(CHECKPOINT_KEY, proposal_id))
+    }
+
+    /// Delegate stake-derived voting power to another voter. Fails if it would close a delegation loop
+    pub fn delegate(env: &Env, from: Address, to: Address) -> Result<(), ContractError> {
+        from.require_auth();
+
+        // Walk the delegate's own chain; reaching `from` means a loop
+        let mut current = to.clone();
+        loop {
+            if current == from {
+                return Err(ContractError::CircularDelegation);
+            }
+            match env.storage().persistent().get::<_, Address>(&(DELEGATE_KEY, current.clone())) {
+                Some(next) => current = next,
+                None => break,
+            }
+        }
+
+        Self::remove_delegation(env, &from);
+        ttl::set_persistent(env, &(DELEGATE_KEY, from.clone()), &to);
+        let delegators_key = (DELEGATORS_KEY, to.clone());
+        let mut delegators: Vec<Address> = env.storage().persistent().get(&delegators_key).unwrap_or(vec![env]);
+        delegators.push_back(from.clone());
+        ttl::set_persistent(env, &delegators_key, &delegators);
+
+        env.events().publish((symbol_short!("delegate"), from), to);
+
+        Ok(())
+    }
+
+    /// Take back delegated voting power
+    pub fn undelegate(env: &Env, from: Address) -> Result<(), ContractError> {
+        from.require_auth();
+
+        if !Self::remove_delegation(env, &from) {
+            return Err(ContractError::NotFound);
+        }
+
+        env.events().publish((symbol_short!("undelegat"), from), ());
+
+        Ok(())
+    }
+
+    /// Get who a voter has delegated to, if anyone
+    pub fn get_delegation(env: &Env, address: Address) -> Option<Address> {
+        env.storage().persistent().get(&(DELEGATE_KEY, address))
+    }
+
+    /// Get the voters delegating directly to an address
+    pub fn get_delegators(env: &Env, address: Address) -> Vec<Address> {
+        env.storage().persistent().get(&(DELEGATORS_KEY, address)).unwrap_or(vec![env])
+    }
+
+    /// Drop a voter's delegation, returning whether there was one
+    fn remove_delegation(env: &Env, from: &Address) -> bool {
+        let delegate: Address = match env.storage().persistent().get(&(DELEGATE_KEY, from.clone())) {
+            Some(delegate) => delegate,
+            None => return false,
+        };
+        env.storage().persistent().remove(&(DELEGATE_KEY, from.clone()));
+
+        let delegators_key = (DELEGATORS_KEY, delegate);
+        let mut delegators: Vec<Address> = env.storage().persistent().get(&delegators_key).unwrap_or(vec![env]);
+        if let Some(index) = delegators.first_index_of(from) {
+            delegators.remove(index);
+        }
+        ttl::set_persistent(env, &delegators_key, &delegators);
+
+        true
+    }
+
+    /// Sum the snapshot voting power delegated to `delegate`, following chains up to
+    /// MAX_DELEGATION_DEPTH, and mark each counted delegator as carried by `carrier` on the proposal.
+    /// Delegators who already voted on the proposal, or were already carried, are skipped
+    fn carry_delegated_power(
+        env: &Env,
+        data: &DataKey,
+        proposal: &Proposal,
+        delegate: &Address,
+        carrier: &Address,
+        depth: u32,
+    ) -> i128 {
+        if depth > MAX_DELEGATION_DEPTH {
+            return 0;
+        }
+
+        let delegators: Vec<Address> = env.storage().persistent().get(&(DELEGATORS_KEY, delegate.clone())).unwrap_or(vec![env]);
+        let mut total: i128 = 0;
+        for delegator in delegators.iter() {
+            let cast_key = (CAST_BY_KEY, proposal.id.clone(), delegator.clone());
+            if env.storage().persistent().has(&(VOTE_KEY, proposal.id.clone(), delegator.clone()))
+                || env.storage().persistent().has(&cast_key)
+            {
+                continue;
+            }
+            ttl::set_persistent(env, &cast_key, carrier);
+
+            if let Some(voter_data) = Self::load_voter(env, &delegator) {
+                let snapshot = Self::snapshot_voter(env, data, &voter_data, proposal);
+                total += Self::calculate_voting_power(env, &snapshot, proposal.voting_mode);
+            }
+            total += Self::carry_delegated_power(env, data, proposal, &delegator, carrier, depth + 1);
+        }
+
+        total
+    }
+
+    /// Issue (or return the existing) participation badge for a voter on a finalized proposal.
+    /// Returns None when the voter neither voted nor had their power carried by a delegate
+    fn issue_badge(env: &Env, voter: &Address, proposal: &Proposal) -> Option<ParticipationBadge> {
+        let badge_key = (BADGE_KEY, voter.clone(), proposal.id.clone());
+        if let Some(badge) = env.storage().persistent().get(&badge_key) {
+            return Some(badge);
+        }
+
+        let (vote, total_power, carried_by) = match Self::get_vote(env, proposal.id.clone(), voter.clone()) {
+            Some(vote) => (vote.vote, vote.total_power, Carrier::Voter),
+            None => {
+                let carrier: Address = env.storage().persistent().get(&(CAST_BY_KEY, proposal.id.clone(), voter.clone()))?;
+                let carried = Self::get_vote(env, proposal.id.clone(), carrier.clone())?;
+                (carried.vote, 0, Carrier::Delegate(carrier))
+            }
+        };
+
+        let badge = ParticipationBadge {
+            proposal_id: proposal.id.clone(),
+            voter: voter.clone(),
+            vote,
+            total_power,
+            carried_by,
+            outcome: proposal.status.clone(),
+            issued_at: env.ledger().timestamp(),
+        };
+        ttl::set_persistent(env, &badge_key, &badge);
+
+        let index_key = (BADGES_KEY, voter.clone());
+        let mut ids: Vec<Symbol> = env.storage().persistent().get(&index_key).unwrap_or(vec![env]);
+        ids.push_back(proposal.id.clone());
+        ttl::set_persistent(env, &index_key, &ids);
+
+        env.events().publish((symbol_short!("badge"), voter.clone()), proposal.id.clone());
+
+        Some(badge)
+    }
+
+    /// Read a proposal's accumulator for one option ("yes", "no") or for "total"
+    fn tally(env: &Env, proposal_id: &Symbol, option: Symbol) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&(TALLY_KEY, proposal_id.clone(), option))
+            .unwrap_or(0)
+    }
+
+    /// Add voting power to one of a proposal's accumulators
+    fn add_tally(env: &Env, proposal_id: &Symbol, option: Symbol, amount: i128) {
+        let current = Self::tally(env, proposal_id, option.clone());
+        ttl::set_persistent(env, &(TALLY_KEY, proposal_id.clone(), option), &(current + amount));
+    }
+
+    /// Copy the live accumulators into a proposal's tally fields
+    fn with_tally(env: &Env, mut proposal: Proposal) -> Proposal {
+        proposal.yes_votes = Self::tally(env, &proposal.id, symbol_short!("yes"));
+        proposal.no_votes = Self::tally(env, &proposal.id, symbol_short!("no"));
+        proposal.total_votes = Self::tally(env, &proposal.id, symbol_short!("total"));
+        proposal
+    }
+
+    /// Load a proposal from its own entry
+    fn load_proposal(env: &Env, proposal_id: &Symbol) -> Option<Proposal> {
+        env.storage().persistent().get(&StorageKey::Proposal(proposal_id.clone()))
+    }
+
+    /// Write a proposal, indexing it the first time it is written
+    fn save_proposal(env: &Env, proposal: &Proposal) {
+        let key = StorageKey::Proposal(proposal.id.clone());
+        if !env.storage().persistent().has(&key) {
+            let mut ids = Self::proposal_ids(env);
+            ids.push_back(proposal.id.clone());
+            ttl::set_persistent(env, &StorageKey::ProposalIds, &ids);
+        }
+        ttl::set_persistent(env, &key, proposal);
+
+        // Keep the active index in step with the proposal's status
+        let mut active = Self::active_proposal_ids(env);
+        let index = active.first_index_of(&proposal.id);
+        let is_active = proposal.status == symbol_short!("active");
+        match index {
+            None if is_active => active.push_back(proposal.id.clone()),
+            Some(i) if !is_active => {
+                active.remove(i);
+            }
+            _ => return,
+        }
+        ttl::set_persistent(env, &StorageKey::ActiveProposalIds, &active);
+    }
+
+    /// Every proposal id, in creation order
+    fn proposal_ids(env: &Env) -> Vec<Symbol> {
+        env.storage().persistent().get(&StorageKey::ProposalIds).unwrap_or(vec![env])
+    }
+
+    /// Ids of proposals still open for voting, in creation order
+    fn active_proposal_ids(env: &Env) -> Vec<Symbol> {
+        env.storage().persistent().get(&StorageKey::ActiveProposalIds).unwrap_or(vec![env])
+    }
+
+    /// Load the proposals a page of ids covers
+    fn proposals_page(env: &Env, ids: &Vec<Symbol>, page: &PageRequest) -> ProposalPage {
+        let (items, next_cursor) = page.collect(env, ids, |id| Self::load_proposal(env, &id).unwrap());
+        ProposalPage { items, next_cursor }
+    }
+
+    /// Load a registered voter's stake and equity data
+    fn load_voter(env: &Env, voter: &Address) -> Option<VoterData> {
+        env.storage().persistent().get(&(VOTER_KEY, voter.clone()))
+    }
+
+    /// Cast a vote on a voter's behalf; a sponsor submits and pays fees
+    pub fn relay_vote(
+        env: &Env,
+        sponsor: Address,
+        voter: Address,
+        proposal_id: Symbol,
+        vote_choice: Symbol,
+        nonce: u64,
+        signature: BytesN<64>,
+    ) -> Result<i128, ContractError> {
+        sponsor.require_auth();
+
+        let payload = (
+            env.current_contract_address(),
+            symbol_short!("vote"),
+            voter.clone(),
+            proposal_id.clone(),
+            vote_choice.clone(),
+            nonce,
+        )
+            .to_xdr(env);
+        Self::verify_relayed_call(env, &voter, payload, nonce, signature)?;
+
+        Self::cast_vote(env, voter, proposal_id, vote_choice)
+    }
+
+    /// Register the ed25519 key that signs a user's relayed calls
+    pub fn register_relay_key(env: &Env, user: Address, public_key: BytesN<32>) -> Result<(), ContractError> {
+        user.require_auth();
+
+        ttl::set_persistent(env, &StorageKey::RelayKey(user), &public_key);
+
+        Ok(())
+    }
+
+    /// Get the nonce a user's next relayed call must be signed with
+    pub fn get_relay_nonce(env: &Env, user: Address) -> u64 {
+        env.storage().persistent().get(&StorageKey::RelayNonce(user)).unwrap_or(0)
+    }
+
+    /// Check a user's signature over a relayed call payload and consume its nonce
+    fn verify_relayed_call(env: &Env, user: &Address, payload: Bytes, nonce: u64, signature: BytesN<64>) -> Result<(), ContractError> {
+        let public_key: BytesN<32> = env.storage()
+            .persistent()
+            .get(&StorageKey::RelayKey(user.clone()))
+            .ok_or(ContractError::NotFound)?;
+        if nonce != Self::get_relay_nonce(env, user.clone()) {
+            return Err(ContractError::BadNonce);
+        }
+
+        // Panics if the signature does not match
+        env.crypto().ed25519_verify(&public_key, &payload, &signature);
+
+        ttl::set_persistent(env, &StorageKey::RelayNonce(user.clone()), &(nonce + 1));
+
+        Ok(())
+    }
+
+    /// Execute a passed proposal
+    pub fn execute_proposal(env: &Env, caller: Address, proposal_id: Symbol) -> Result<(), ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        // Only executors can execute proposals
+        caller.require_auth();
+        Gate::new(data.paused, &data.disabled_features, &symbol_short!("execute"))
+            .role(access::holds(env, &data.admin, Role::Executor, &caller))
+            .check()?;
+
+        let mut proposal = Self::load_proposal(env, &proposal_id).ok_or(ContractError::ProposalNotFound)?;
+        
+        if proposal.status != symbol_short!("passed") {
+            return Err(ContractError::InvalidStatus);
+        }
+
+        // Actions that succeeded in an earlier attempt are not repeated
+        let completed: Vec<u32> = env
+            .storage()
+            .persistent()
+            .get(&StorageKey::CompletedActions(proposal_id.clone()))
+            .unwrap_or(vec![env]);
+        let mut actions: Vec<ExecutionAction> = vec![env];
+        let mut return_values: Vec<Val> = vec![env];
+
+        // Execute based on proposal type
+        let proposal_type = proposal.proposal_type.clone();
+        if proposal_type == Symbol::new(env, "policy_change") {
+            // Bind the rate adjuster to the bands approved by this referendum
+            for band in proposal.policy_bands.iter() {
+                let rate_adjuster = Self::rate_adjuster(env).map_err(|_| ContractError::NotFound)?;
+                Self::invoke_action(
+                    env,
+                    &completed,
+                    &mut actions,
+                    &mut return_values,
+                    rate_adjuster,
+                    Symbol::new(env, "set_policy_band"),
+                    vec![env, band.parameter.into_val(env), band.min.into_val(env), band.max.into_val(env)],
+                );
+            }
+        } else if proposal_type == symbol_short!("emergency") {
+            // Release the guardian's emergency fund payout on the distributor
+            if let Some(request_id) = proposal.emergency_request {
+                let distributor = Self::revenue_distributor(env).map_err(|_| ContractError::NotFound)?;
+                Self::invoke_action(
+                    env,
+                    &completed,
+                    &mut actions,
+                    &mut return_values,
+                    distributor,
+                    Symbol::new(env, "approve_emergency_payout"),
+                    vec![env, request_id.into_val(env)],
+                );
+            }
+        } else if proposal_type == symbol_short!("rebalance") {
+            // Move pooled capital to the approved allocation; the pool records before and after
+            if !proposal.rebalance_targets.is_empty() {
+                let targets = proposal.rebalance_targets.clone();
+                Self::invoke_action(
+                    env,
+                    &completed,
+                    &mut actions,
+                    &mut return_values,
+                    data.loan_pool.clone(),
+                    Symbol::new(env, "rebalance_pool"),
+                    vec![env, proposal.id.into_val(env), targets.into_val(env)],
+                );
+            }
+        } else if proposal_type != Symbol::new(env, "asset_funding") && proposal_type != Symbol::new(env, "rate_adjustment") {
+            return Err(ContractError::UnknownProposalType);
+        }
+
+        // Make the calls stored on the proposal; parameter calls to governance itself apply in place
+        for call in proposal.calls.iter() {
+            if completed.contains(actions.len()) {
+                actions.push_back(ExecutionAction { target: call.target, function: call.function, success: true });
+            } else if call.target == env.current_contract_address() {
+                let success = Self::apply_param(env, &mut data, &call.function, call.args.get(0));
+                actions.push_back(ExecutionAction { target: call.target, function: call.function, success });
+            } else {
+                Self::invoke_action(env, &completed, &mut actions, &mut return_values, call.target, call.function, call.args);
+            }
+        }
+
+        let succeeded = actions.iter().all(|action| action.success);
+        let receipt = ExecutionReceipt {
+            proposal_id: proposal_id.clone(),
+            ledger: env.ledger().sequence(),
+            executor: caller,
+            actions,
+            succeeded,
+            return_hash: env.crypto().sha256(&return_values.to_xdr(env)),
+            timestamp: env.ledger().timestamp(),
+        };
+        ttl::set_persistent(env, &StorageKey::ExecutionReceipt(proposal_id.clone()), &receipt);
+        env.events().publish((symbol_short!("executed"), proposal_id.clone()), succeeded);
+
+        // Keep the proposal executable and its escrow locked if any action failed,
+        // remembering which actions already took effect
+        if !succeeded {
+            let mut done: Vec<u32> = vec![env];
+            for (index, action) in receipt.actions.iter().enumerate() {
+                if action.success {
+                    done.push_back(index as u32);
+                }
+            }
+            ttl::set_persistent(env, &StorageKey::CompletedActions(proposal_id.clone()), &done);
+            ttl::set_instance(env, &DATA_KEY, &data);
+            return Ok(());
+        }
+        env.storage().persistent().remove(&StorageKey::CompletedActions(proposal_id.clone()));
+
+        // Spend escrowed funds from the treasury as an investment in the proposal's asset
+        if let Some(escrowed) = data.escrows.get(proposal_id.clone()) {
+            Self::consume_approval(env, &mut data, symbol_short!("spend"), proposal_id.clone(), None, escrowed)?;
+            Self::invest_treasury(env, &data, &proposal.target_asset, escrowed)?;
+            data.treasury_balance -= escrowed;
+            data.total_escrowed -= escrowed;
+            data.escrows.remove(proposal_id.clone());
+        }
+
+        proposal.status = symbol_short!("executed");
+        Self::save_proposal(env, &proposal);
+        changes::touch(env, symbol_short!("proposal"), proposal_id.clone());
+        
+        ttl::set_instance(env, &DATA_KEY, &data);
+        
+        Ok(())
+    }
+
+    /// Invoke one execution action, recording whether it succeeded and what it returned.
+    /// An action completed by an earlier attempt is recorded as succeeded without being invoked again
+    fn invoke_action(
+        env: &Env,
+        completed: &Vec<u32>,
+        actions: &mut Vec<ExecutionAction>,
+        return_values: &mut Vec<Val>,
+        target: Address,
+        function: Symbol,
+        args: Vec<Val>,
+    ) {
+        if completed.contains(actions.len()) {
+            actions.push_back(ExecutionAction { target, function, success: true });
+            return;
+        }
+
+        let result = env.try_invoke_contract::<Val, InvokeError>(&target, &function, args);
+        let success = match result {
+            Ok(Ok(value)) => {
+                return_values.push_back(value);
+                true
+            }
+            _ => false,
+        };
+        actions.push_back(ExecutionAction { target, function, success });
+    }
+
+    /// Apply a numeric governance setting from a policy_change proposal through the same
+    /// validation as timelocked admin changes, returning whether it was applied
+    fn apply_param(env: &Env, data: &mut DataKey, function: &Symbol, value: Option<Val>) -> bool {
+        let value = match value.map(|value| i128::try_from_val(env, &value)) {
+            Some(Ok(value)) => value,
+            _ => return false,
+        };
+
+        let now = env.ledger().timestamp();
+        let change = PendingChange { id: 0, action: function.clone(), value: ChangeValue::Number(value), queued_at: now, eta: now };
+        if Self::apply_admin_change(env, data, &change).is_err() {
+            return false;
+        }
+
+        env.events().publish((symbol_short!("param"), function.clone()), value);
+        true
+    }
+
+    /// Get the receipt of the latest execution attempt for a proposal
+    pub fn get_execution_receipt(env: &Env, proposal_id: Symbol) -> Result<ExecutionReceipt, ContractError> {
+        env.storage()
+            .persistent()
+            .get(&StorageKey::ExecutionReceipt(proposal_id))
+            .ok_or(ContractError::NotFound)
+    }
+
+    /// Finalize voting and determine proposal outcome
+    pub fn finalize_proposal(env: &Env, proposal_id: Symbol) -> Result<Symbol, ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        
+        let mut proposal = Self::load_proposal(env, &proposal_id).ok_or(ContractError::ProposalNotFound)?;
+        
+        if proposal.status != symbol_short!("active") {
+            return Err(ContractError::InvalidStatus);
+        }
+
+        let current_time = env.ledger().timestamp();
+        if current_time <= proposal.end_time {
+            return Err(ContractError::TooEarly);
+        }
+
+        // Snapshot the accumulators onto the proposal
+        proposal = Self::with_tally(env, proposal);
+
+        // Calculate total possible votes (all stakeholders)
+        let total_possible_votes = Self::calculate_total_possible_votes(env, proposal.voting_mode);
+        let participation_rate = if total_possible_votes > 0 {
+            proposal.total_votes * 100 / total_possible_votes
+        } else {
+            0
+        };
+
+        // Check quorum; a funding proposal that misses it forfeits its bond to the treasury
+        if participation_rate < data.quorum_threshold as i128 {
+            Self::release_escrow(&mut data, &proposal_id);
+            Self::settle_bond(env, &mut data, &proposal_id, false)?;
+            proposal.status = symbol_short!("failed");
+            Self::save_proposal(env, &proposal);
+            changes::touch(env, symbol_short!("proposal"), proposal_id.clone());
+            env.events().publish((symbol_short!("finalized"), proposal_id.clone()), proposal.status);
+            ttl::set_instance(env, &DATA_KEY, &data);
+            return Ok(symbol_short!("failed"));
+        }
+
+        // Determine outcome
+        Self::settle_bond(env, &mut data, &proposal_id, true)?;
+        let outcome = if proposal.yes_votes > proposal.no_votes {
+            proposal.status = symbol_short!("passed");
+            symbol_short!("passed")
+        } else {
+            Self::release_escrow(&mut data, &proposal_id);
+            proposal.status = symbol_short!("failed");
+            symbol_short!("failed")
+        };
+
+        Self::save_proposal(env, &proposal);
+
+        changes::touch(env, symbol_short!("proposal"), proposal_id.clone());
+        env.events().publish((symbol_short!("finalized"), proposal_id.clone()), outcome.clone());
+        ttl::set_instance(env, &DATA_KEY, &data);
+        
         Ok(outcome)
     }
 
-    /// Update voter's stake and equity data
-    pub fn update_voter_data(
-        env: &Env,
-        voter: Address,
-        stake_amount: i128,
-        equity_score: i32,
-    ) -> Result<(), Symbol> {
+    /// Veto an active or passed proposal (admin only), releasing any escrowed funds. The
+    /// proposer's bond is kept by the treasury
+    pub fn veto_proposal(
+        env: &Env,
+        caller: Address,
+        proposal_id: Symbol,
+        reason: RejectionReason,
+        evidence: Option<BytesN<32>>,
+    ) -> Result<(), ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        // Only admin can veto proposals
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+
+        let mut proposal = Self::load_proposal(env, &proposal_id).ok_or(ContractError::ProposalNotFound)?;
+
+        if proposal.status != symbol_short!("active") && proposal.status != symbol_short!("passed") {
+            return Err(ContractError::InvalidStatus);
+        }
+
+        Self::release_escrow(&mut data, &proposal_id);
+        Self::settle_bond(env, &mut data, &proposal_id, false)?;
+
+        proposal.status = symbol_short!("vetoed");
+        Self::save_proposal(env, &proposal);
+        changes::touch(env, symbol_short!("proposal"), proposal_id.clone());
+
+        let mut hashes = Vec::new(env);
+        if let Some(hash) = evidence.clone() {
+            hashes.push_back(hash);
+        }
+        ttl::set_persistent(env, &StorageKey::Veto(proposal_id.clone()), &RejectionRecord {
+            reason,
+            evidence: hashes,
+            timestamp: env.ledger().timestamp(),
+        });
+        env.events().publish((symbol_short!("vetoed"), proposal_id.clone()), (reason, evidence));
+
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        Ok(())
+    }
+
+    /// Get the reason a proposal was vetoed
+    pub fn get_veto(env: &Env, proposal_id: Symbol) -> Option<RejectionRecord> {
+        env.storage().persistent().get(&StorageKey::Veto(proposal_id))
+    }
+
+    /// Deposit funds into the governance treasury from the caller's balance of the loan pool's
+    /// settlement asset (treasurer only)
+    pub fn deposit_treasury(env: &Env, caller: Address, amount: i128) -> Result<i128, ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        // Only treasurers can fund the treasury
+        access::check(env, &data.admin, Role::Treasurer, &caller)?;
+
+        if amount <= 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        let token = Self::treasury_token(env, &data)?;
+        TokenClient::new(env, &token).transfer(&caller, &env.current_contract_address(), &amount);
+        data.treasury_balance += amount;
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        Ok(data.treasury_balance)
+    }
+
+    /// Move treasury funds into an epoch's voting-gas rebate pool (treasurer only)
+    pub fn fund_gas_rebates(env: &Env, caller: Address, epoch: u32, amount: i128) -> Result<i128, ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Treasurer, &caller)?;
+
+        if amount <= 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        // Escrowed funding proposals keep priority over rebates
+        if data.treasury_balance - data.total_escrowed < amount {
+            return Err(ContractError::NoFunds);
+        }
+
+        Self::consume_approval(env, &mut data, symbol_short!("spend"), symbol_short!("gas_rbt"), None, amount)?;
+
+        data.treasury_balance -= amount;
+        let pool = Self::get_gas_rebate_pool(env, epoch) + amount;
+        ttl::set_persistent(env, &StorageKey::GasRebatePool(epoch), &pool);
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        Ok(pool)
+    }
+
+    /// Claim fee rebates for an epoch's votes on proposals that have since been finalized.
+    /// Votes on proposals still open stay claimable later.
+    pub fn claim_gas_rebate(env: &Env, voter: Address, epoch: u32) -> Result<i128, ContractError> {
+        voter.require_auth();
+
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        if data.epoch_schedule.epoch_end(epoch) > env.ledger().timestamp() {
+            return Err(ContractError::TooEarly);
+        }
+
+        let gas_key = (GAS_VOTES_KEY, voter.clone(), epoch);
+        let rebate_votes: Vec<Symbol> = env.storage().persistent().get(&gas_key).ok_or(ContractError::Nothing)?;
+
+        let mut finalized: u32 = 0;
+        let mut open: Vec<Symbol> = vec![env];
+        for proposal_id in rebate_votes.iter() {
+            match Self::load_proposal(env, &proposal_id) {
+                Some(proposal) if proposal.status == symbol_short!("active") => open.push_back(proposal_id),
+                Some(proposal) if Self::issue_badge(env, &voter, &proposal).is_some() => finalized += 1,
+                _ => {}
+            }
+        }
+
+        if finalized == 0 {
+            return Err(ContractError::Nothing);
+        }
+
+        // Rebates are paid while the epoch's pool lasts
+        let pool = Self::get_gas_rebate_pool(env, epoch);
+        let rebate = (finalized as i128 * data.gas_rebate_per_vote).min(pool);
+        if rebate <= 0 {
+            return Err(ContractError::NoFunds);
+        }
+
+        ttl::set_persistent(env, &StorageKey::GasRebatePool(epoch), &(pool - rebate));
+        let token = Self::treasury_token(env, &data)?;
+        TokenClient::new(env, &token).transfer(&env.current_contract_address(), &voter, &rebate);
+
+        if open.is_empty() {
+            env.storage().persistent().remove(&gas_key);
+        } else {
+            ttl::set_persistent(env, &gas_key, &open);
+        }
+
+        env.events().publish((symbol_short!("gas_rbt"), voter), (epoch, rebate));
+
+        Ok(rebate)
+    }
+
+    /// Get an epoch's remaining voting-gas rebate pool
+    pub fn get_gas_rebate_pool(env: &Env, epoch: u32) -> i128 {
+        env.storage().persistent().get(&StorageKey::GasRebatePool(epoch)).unwrap_or(0)
+    }
+
+    /// Replace the platform fee schedule (admin only)
+    pub fn set_fee_schedule(env: &Env, caller: Address, tiers: Vec<FeeTier>) -> Result<(), ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+
+        if tiers.is_empty() || tiers.get(0).unwrap().min_equity_score != 0 {
+            return Err(ContractError::InvalidInput);
+        }
+
+        let mut previous_score = -1;
+        for tier in tiers.iter() {
+            if tier.min_equity_score <= previous_score || tier.min_equity_score > 100 || tier.fee_bps > MAX_FEE_BPS {
+                return Err(ContractError::InvalidInput);
+            }
+            previous_score = tier.min_equity_score;
+        }
+
+        data.fee_schedule = tiers;
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        Ok(())
+    }
+
+    /// Replace the shared epoch calendar (admin only)
+    pub fn set_epoch_schedule(env: &Env, caller: Address, schedule: EpochSchedule) -> Result<(), ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+
+        if !schedule.is_valid() {
+            return Err(ContractError::InvalidInput);
+        }
+
+        data.epoch_schedule = schedule.clone();
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        env.events().publish((symbol_short!("calendar"),), schedule);
+
+        Ok(())
+    }
+
+    /// Get the current budget cycle of the shared epoch calendar
+    pub fn get_current_cycle(env: &Env) -> u32 {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        data.epoch_schedule.cycle_at(env.ledger().timestamp())
+    }
+
+    /// Get the platform fee schedule
+    pub fn get_fee_schedule(env: &Env) -> Vec<FeeTier> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        data.fee_schedule
+    }
+
+    /// Set the amount above which disbursements need approvals, and who can give them (admin only)
+    pub fn set_approval_rule(env: &Env, caller: Address, rule: ApprovalRule) -> Result<(), ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+
+        if !rule.is_valid() {
+            return Err(ContractError::InvalidInput);
+        }
+
+        data.approval_rule = rule;
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        Ok(())
+    }
+
+    /// Open a pending action for a large disbursement ("spend" of treasury funds), counting the proposer's approval
+    pub fn propose_action(
+        env: &Env,
+        approver: Address,
+        kind: Symbol,
+        reference: Symbol,
+        recipient: Option<Address>,
+        amount: i128,
+    ) -> Result<u32, ContractError> {
+        approver.require_auth();
+
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        if kind != symbol_short!("spend") {
+            return Err(ContractError::InvalidInput);
+        }
+
+        if amount <= 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        let action_id = data.next_action_id;
+        let now = env.ledger().timestamp();
+        let mut action = PendingAction {
+            id: action_id,
+            kind: kind.clone(),
+            reference,
+            recipient: recipient.into(),
+            amount,
+            approvals: vec![env],
+            created_at: now,
+        };
+        action.approve(&data.approval_rule, &approver, now)?;
+
+        data.pending_actions.set(action_id, action);
+        data.next_action_id += 1;
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        env.events().publish((symbol_short!("act_new"), action_id), (kind, amount));
+
+        Ok(action_id)
+    }
+
+    /// Approve a pending action and return its approval count
+    pub fn approve_action(env: &Env, approver: Address, action_id: u32) -> Result<u32, ContractError> {
+        approver.require_auth();
+
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        let mut action = data.pending_actions.get(action_id).ok_or(ContractError::NotFound)?;
+
+        let approvals = action.approve(&data.approval_rule, &approver, env.ledger().timestamp())?;
+        data.pending_actions.set(action_id, action);
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        env.events().publish((symbol_short!("act_appr"), action_id), approver);
+
+        Ok(approvals)
+    }
+
+    /// Drop pending actions whose approval window has passed. Anyone can call this.
+    pub fn prune_expired_actions(env: &Env) -> u32 {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        let now = env.ledger().timestamp();
+
+        let mut expired = vec![env];
+        for (action_id, action) in data.pending_actions.iter() {
+            if action.is_expired(now) {
+                expired.push_back(action_id);
+            }
+        }
+        for action_id in expired.iter() {
+            data.pending_actions.remove(action_id);
+        }
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        expired.len()
+    }
+
+    /// Get a pending action
+    pub fn get_pending_action(env: &Env, action_id: u32) -> Result<PendingAction, ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        data.pending_actions.get(action_id).ok_or(ContractError::NotFound)
+    }
+
+    /// Get the large-disbursement approval rule
+    pub fn get_approval_rule(env: &Env) -> ApprovalRule {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        data.approval_rule
+    }
+
+    /// Get a page of proposals created or modified after a ledger with their last-modified ledger,
+    /// in ledger order, so indexers can sync incrementally. Supported kinds: "proposal"
+    pub fn get_changes_since(env: &Env, ledger_seq: u32, kind: Symbol, page: PageRequest) -> Result<RecordChangePage, ContractError> {
+        if kind != symbol_short!("proposal") {
+            return Err(ContractError::InvalidInput);
+        }
+        let (items, next_cursor) = changes::changes_since(env, kind, ledger_seq, &page, |id, ledger| RecordChange { id, ledger });
+        Ok(RecordChangePage { items, next_cursor })
+    }
+
+    /// Get treasury balance and the amount currently escrowed
+    pub fn get_treasury(env: &Env) -> (i128, i128) {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        (data.treasury_balance, data.total_escrowed)
+    }
+
+    /// Get the amount escrowed for a proposal
+    pub fn get_proposal_escrow(env: &Env, proposal_id: Symbol) -> i128 {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        data.escrows.get(proposal_id).unwrap_or(0)
+    }
+
+    /// Update voter's stake and equity data
+    pub fn update_voter_data(
+        env: &Env,
+        voter: Address,
+        stake_amount: i128,
+        equity_score: i32,
+    ) -> Result<(), ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        
+        // Only oracle can update voter data
+        data.oracle.require_auth();
+
+        Self::store_voter_data(env, data, voter, stake_amount, equity_score)
+    }
+
+    /// Update voter data from a payload signed by a registered feeder key; anyone may relay it
+    pub fn submit_signed_voter_data(
+        env: &Env,
+        feeder: BytesN<32>,
+        voter: Address,
+        stake_amount: i128,
+        equity_score: i32,
+        nonce: u64,
+        timestamp: u64,
+        signature: BytesN<64>,
+    ) -> Result<(), ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        attestation::verify_feeder(
+            env,
+            &feeder,
+            Symbol::new(env, "update_voter_data"),
+            (voter.clone(), stake_amount, equity_score),
+            nonce,
+            timestamp,
+            &signature,
+        )?;
+
+        Self::store_voter_data(env, data, voter, stake_amount, equity_score)
+    }
+
+    /// Register an ed25519 feeder key that signs oracle submissions, and how long a signed payload
+    /// stays valid (oracle manager only)
+    pub fn register_feeder(env: &Env, caller: Address, public_key: BytesN<32>, max_age: u64) -> Result<(), ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::OracleManager, &caller)?;
+        attestation::register_feeder(env, &public_key, max_age, env.ledger().timestamp()).map_err(ContractError::from)
+    }
+
+    /// Enable or disable one feeder key, leaving the others signing (oracle manager only)
+    pub fn set_feeder_enabled(env: &Env, caller: Address, public_key: BytesN<32>, enabled: bool) -> Result<(), ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::OracleManager, &caller)?;
+        attestation::set_feeder_enabled(env, &public_key, enabled).map_err(ContractError::from)
+    }
+
+    /// Schedule a new feeder key to replace an old one at `effective_at` (oracle manager only)
+    pub fn rotate_feeder(
+        env: &Env,
+        caller: Address,
+        old_key: BytesN<32>,
+        new_key: BytesN<32>,
+        max_age: u64,
+        effective_at: u64,
+    ) -> Result<(), ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::OracleManager, &caller)?;
+        attestation::rotate_feeder(env, &old_key, &new_key, max_age, effective_at).map_err(ContractError::from)
+    }
+
+    /// Get every registered feeder key with its state
+    pub fn get_feeders(env: &Env) -> Vec<FeederKey> {
+        attestation::feeders(env)
+    }
+
+    /// Apply a voter's stake and equity update once the submitting oracle is authenticated
+    fn store_voter_data(env: &Env, mut data: DataKey, voter: Address, stake_amount: i128, equity_score: i32) -> Result<(), ContractError> {
+        Gate::new(data.paused, &data.disabled_features, &symbol_short!("oracle")).check()?;
+
+        let previous = Self::load_voter(env, &voter);
+        let mut voter_data = previous.clone().unwrap_or(VoterData {
+            address: voter.clone(),
+            stake_amount: 0,
+            equity_score: 0,
+            voting_power: 0,
+            last_vote_time: 0,
+            total_votes_cast: 0,
+            equity_updated_at: 0,
+            stake_since_ledger: 0,
+            prior_stake: 0,
+        });
+
+        // Track when stake grew so fresh stake can be excluded from in-flight votes
+        let current_ledger = env.ledger().sequence();
+        if stake_amount > voter_data.stake_amount {
+            let matured = voter_data.stake_since_ledger.saturating_add(data.min_stake_age_ledgers) <= current_ledger;
+            if matured {
+                voter_data.prior_stake = voter_data.stake_amount;
+            }
+            voter_data.stake_since_ledger = current_ledger;
+        } else if stake_amount < voter_data.prior_stake {
+            voter_data.prior_stake = stake_amount;
+        }
+
+        voter_data.stake_amount = stake_amount;
+        voter_data.equity_score = equity_score;
+        voter_data.equity_updated_at = env.ledger().timestamp();
+        voter_data.voting_power = Self::calculate_voting_power(env, &voter_data, VotingMode::Linear);
+
+        // Keep the aggregates used for quorum and stats in step
+        match &previous {
+            Some(previous) => Self::remove_power_totals(env, &mut data, previous),
+            None => data.voter_count += 1,
+        }
+        Self::add_power_totals(env, &mut data, &voter_data);
+
+        ttl::set_persistent(env, &(VOTER_KEY, voter), &voter_data);
+        ttl::set_instance(env, &DATA_KEY, &data);
+        
+        Ok(())
+    }
+
+    /// Record a portfolio delinquency alert (loan pool only)
+    pub fn record_portfolio_alert(
+        env: &Env,
+        source: Address,
+        delinquency_bps: i32,
+        above_threshold: bool,
+    ) -> Result<(), ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        // Only the loan pool can report portfolio health
+        if source != data.loan_pool {
+            return Err(ContractError::Unauthorized);
+        }
+        source.require_auth();
+
+        journal::append(env, &ALERTS_KEY, &PortfolioAlert {
+            delinquency_bps,
+            above_threshold,
+            timestamp: env.ledger().timestamp(),
+        });
+
+        Ok(())
+    }
+
+    /// Get a page of portfolio delinquency alerts reported by the loan pool, oldest first
+    pub fn get_portfolio_alerts(env: &Env, page: PageRequest) -> PortfolioAlertPage {
+        let (items, next_cursor) = journal::page(env, &ALERTS_KEY, &page);
+        PortfolioAlertPage { items, next_cursor }
+    }
+
+    /// Check an off-chain title and description document against a proposal's metadata commitment
+    pub fn verify_proposal_metadata(env: &Env, proposal_id: Symbol, content: Bytes) -> Result<bool, ContractError> {
+        let proposal = Self::load_proposal(env, &proposal_id).ok_or(ContractError::ProposalNotFound)?;
+        Ok(proposal.metadata.verify(env, &content))
+    }
+
+    /// Get proposal details
+    pub fn get_proposal(env: &Env, proposal_id: Symbol) -> Result<Proposal, ContractError> {
+        let proposal = Self::load_proposal(env, &proposal_id).ok_or(ContractError::ProposalNotFound)?;
+        Ok(Self::with_tally(env, proposal))
+    }
+
+    /// Get votes for a proposal
+    pub fn get_proposal_votes(env: &Env, proposal_id: Symbol) -> Vec<Vote> {
+        let vote_count: u32 = env.storage().persistent().get(&(VOTE_COUNT_KEY, proposal_id.clone())).unwrap_or(0);
+        let mut votes = vec![env];
+
+        for index in 0..vote_count {
+            let voter: Address = env.storage().persistent().get(&(VOTE_AT_KEY, proposal_id.clone(), index)).unwrap();
+            votes.push_back(env.storage().persistent().get(&(VOTE_KEY, proposal_id.clone(), voter)).unwrap());
+        }
+
+        votes
+    }
+
+    /// Get a single voter's vote on a proposal
+    pub fn get_vote(env: &Env, proposal_id: Symbol, voter: Address) -> Option<Vote> {
+        env.storage().persistent().get(&(VOTE_KEY, proposal_id, voter))
+    }
+
+    /// Issue participation badges to the direct voters of a finalized proposal, a page at a time
+    pub fn issue_badges(env: &Env, proposal_id: Symbol, start: u32, limit: u32) -> Result<u32, ContractError> {
+        let proposal = Self::load_proposal(env, &proposal_id).ok_or(ContractError::ProposalNotFound)?;
+        if proposal.status == symbol_short!("active") {
+            return Err(ContractError::InvalidStatus);
+        }
+
+        let count: u32 = env.storage().persistent().get(&(VOTE_COUNT_KEY, proposal_id.clone())).unwrap_or(0);
+        let end = start.saturating_add(limit).min(count);
+        let mut issued = 0;
+        for index in start..end {
+            let voter: Address = env.storage().persistent().get(&(VOTE_AT_KEY, proposal_id.clone(), index)).unwrap();
+            if !env.storage().persistent().has(&(BADGE_KEY, voter.clone(), proposal_id.clone()))
+                && Self::issue_badge(env, &voter, &proposal).is_some()
+            {
+                issued += 1;
+            }
+        }
+
+        Ok(issued)
+    }
+
+    /// Issue a voter's participation badge for a finalized proposal, including power carried by a delegate
+    pub fn claim_badge(env: &Env, voter: Address, proposal_id: Symbol) -> Result<ParticipationBadge, ContractError> {
+        let proposal = Self::load_proposal(env, &proposal_id).ok_or(ContractError::ProposalNotFound)?;
+        if proposal.status == symbol_short!("active") {
+            return Err(ContractError::InvalidStatus);
+        }
+
+        Self::issue_badge(env, &voter, &proposal).ok_or(ContractError::NotFound)
+    }
+
+    /// Get a page of a voter's participation badges, oldest first
+    pub fn get_badges(env: &Env, voter: Address, page: PageRequest) -> BadgePage {
+        let ids: Vec<Symbol> = env.storage().persistent().get(&(BADGES_KEY, voter.clone())).unwrap_or(vec![env]);
+        let (items, next_cursor) = page.collect(env, &ids, |proposal_id| {
+            env.storage().persistent().get(&(BADGE_KEY, voter.clone(), proposal_id)).unwrap()
+        });
+        BadgePage { items, next_cursor }
+    }
+
+    /// Get a voter's badge for one proposal
+    pub fn get_badge(env: &Env, voter: Address, proposal_id: Symbol) -> Option<ParticipationBadge> {
+        env.storage().persistent().get(&(BADGE_KEY, voter, proposal_id))
+    }
+
+    /// Create or update the caller's profile. Dropping a role also drops its verification.
+    pub fn set_profile(
+        env: &Env,
+        owner: Address,
+        name_hash: BytesN<32>,
+        preferred_zone: Option<Symbol>,
+        role_flags: u32,
+    ) -> Result<(), ContractError> {
+        owner.require_auth();
+
+        if role_flags & !ALL_ROLES != 0 {
+            return Err(ContractError::InvalidInput);
+        }
+
+        let key = (PROFILE_KEY, owner.clone());
+        let previous: Option<Profile> = env.storage().persistent().get(&key);
+        let verified_roles = previous.map(|profile| profile.verified_roles & role_flags).unwrap_or(0);
+
+        ttl::set_persistent(env, &key, &Profile {
+            owner: owner.clone(),
+            name_hash,
+            preferred_zone: preferred_zone.unwrap_or(Symbol::new(env, "")),
+            role_flags,
+            verified_roles,
+            updated_at: env.ledger().timestamp(),
+        });
+
+        env.events().publish((symbol_short!("profile"), owner), role_flags);
+
+        Ok(())
+    }
+
+    /// Delete the caller's profile
+    pub fn clear_profile(env: &Env, owner: Address) -> Result<(), ContractError> {
+        owner.require_auth();
+
+        let key = (PROFILE_KEY, owner.clone());
+        if !env.storage().persistent().has(&key) {
+            return Err(ContractError::NotFound);
+        }
+        env.storage().persistent().remove(&key);
+
+        Ok(())
+    }
+
+    /// Confirm which of a profile's claimed roles are genuine (oracle only).
+    /// Roles that were not claimed are ignored.
+    pub fn verify_profile_roles(env: &Env, owner: Address, roles: u32) -> Result<u32, ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        // Only oracle can verify roles
+        data.oracle.require_auth();
+
+        let key = (PROFILE_KEY, owner);
+        let mut profile: Profile = env.storage().persistent().get(&key).ok_or(ContractError::NotFound)?;
+
+        profile.verified_roles = roles & profile.role_flags;
+        ttl::set_persistent(env, &key, &profile);
+
+        Ok(profile.verified_roles)
+    }
+
+    /// Get an address's profile, if it has published one
+    pub fn get_profile(env: &Env, owner: Address) -> Option<Profile> {
+        env.storage().persistent().get(&(PROFILE_KEY, owner))
+    }
+
+    /// Get voter data
+    pub fn get_voter_data(env: &Env, voter: Address) -> Result<VoterData, ContractError> {
+        Self::load_voter(env, &voter).ok_or(ContractError::NotFound)
+    }
+
+    /// Check core accounting invariants; emits an alarm and optionally pauses on violation
+    pub fn verify_invariants(env: &Env) -> Vec<Symbol> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        let mut violations: Vec<Symbol> = vec![env];
+
+        // Each proposal's accumulators must equal the sum of its recorded votes
+        for proposal_id in Self::proposal_ids(env).iter() {
+            let proposal = Self::with_tally(env, Self::load_proposal(env, &proposal_id).unwrap());
+            let mut total: i128 = 0;
+            for vote in Self::get_proposal_votes(env, proposal_id).iter() {
+                total += vote.total_power;
+            }
+            if total != proposal.total_votes || proposal.yes_votes + proposal.no_votes > proposal.total_votes {
+                violations.push_back(symbol_short!("tally"));
+                break;
+            }
+        }
+
+        // Escrows must add up and be backed by the treasury
+        let mut escrowed: i128 = 0;
+        for (_, amount) in data.escrows.iter() {
+            escrowed += amount;
+        }
+        if escrowed != data.total_escrowed || data.total_escrowed > data.treasury_balance {
+            violations.push_back(symbol_short!("escrow"));
+        }
+
+        if !violations.is_empty() {
+            env.events().publish((symbol_short!("inv_alarm"),), violations.clone());
+            if data.auto_pause && !data.paused {
+                data.paused = true;
+                ttl::set_instance(env, &DATA_KEY, &data);
+            }
+        }
+
+        violations
+    }
+
+    /// Enable or disable pausing when an invariant check fails (pauser only)
+    pub fn set_auto_pause(env: &Env, caller: Address, enabled: bool) -> Result<(), ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Pauser, &caller)?;
+
+        data.auto_pause = enabled;
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        Ok(())
+    }
+
+    /// Halt state-changing entrypoints, e.g. while an oracle is compromised; views keep working (pauser only)
+    pub fn pause(env: &Env, caller: Address) -> Result<(), ContractError> {
+        Self::set_paused(env, caller, true)
+    }
+
+    /// Resume state-changing entrypoints after a pause (pauser only)
+    pub fn unpause(env: &Env, caller: Address) -> Result<(), ContractError> {
+        Self::set_paused(env, caller, false)
+    }
+
+    /// Whether state-changing entrypoints are halted
+    pub fn is_paused(env: &Env) -> bool {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        data.paused
+    }
+
+    fn set_paused(env: &Env, caller: Address, paused: bool) -> Result<(), ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Pauser, &caller)?;
+
+        data.paused = paused;
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        let topic = if paused { symbol_short!("paused") } else { symbol_short!("unpaused") };
+        env.events().publish((topic,), env.ledger().timestamp());
+
+        Ok(())
+    }
+
+    /// Switch one entrypoint feature on or off without pausing the whole contract (pauser only)
+    pub fn set_feature(env: &Env, caller: Address, feature: Symbol, enabled: bool) -> Result<(), ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Pauser, &caller)?;
+
+        if let Some(index) = data.disabled_features.first_index_of(&feature) {
+            data.disabled_features.remove(index);
+        }
+        if !enabled {
+            data.disabled_features.push_back(feature.clone());
+        }
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        env.events().publish((symbol_short!("feature"), feature), enabled);
+
+        Ok(())
+    }
+
+    /// Whether an entrypoint feature is switched on
+    pub fn is_feature_enabled(env: &Env, feature: Symbol) -> bool {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        !data.disabled_features.contains(&feature)
+    }
+
+    /// Grant a role to an account (admin only)
+    pub fn grant_role(env: &Env, caller: Address, role: Role, account: Address) -> Result<(), ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+        access::grant(env, role, &account);
+
+        Ok(())
+    }
+
+    /// Revoke a role previously granted to an account (admin only)
+    pub fn revoke_role(env: &Env, caller: Address, role: Role, account: Address) -> Result<(), ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+        access::revoke(env, role, &account).map_err(ContractError::from)
+    }
+
+    /// Whether an account holds a role; the admin holds every role
+    pub fn has_role(env: &Env, role: Role, account: Address) -> bool {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        access::holds(env, &data.admin, role, &account)
+    }
+
+    /// Extend the TTL of the contract instance and its code. Open to anyone, so a keeper can
+    /// stop a quiet contract from being archived between writes
+    pub fn bump_storage(env: &Env) {
+        ttl::extend_instance(env);
+    }
+
+    /// Set the remaining TTL below which writes extend an entry, and the TTL they extend it to (admin only)
+    pub fn set_ttl_config(env: &Env, caller: Address, threshold: u32, extend_to: u32) -> Result<(), ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+        ttl::set_config(env, threshold, extend_to).map_err(ContractError::from)
+    }
+
+    /// Get the storage TTL configuration
+    pub fn get_ttl_config(env: &Env) -> TtlConfig {
+        ttl::config(env)
+    }
+
+    /// Push change notifications to a relay contract, or stop with None (admin only)
+    pub fn set_relay(env: &Env, caller: Address, relay: Option<Address>) -> Result<(), ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+        changes::set_relay(env, relay);
+
+        Ok(())
+    }
+
+    /// Move one chunk of legacy monolithic data into the keyed layout (legacy admin only).
+    /// Kinds run in order: "core" (instance settings and proposals, pauses the contract),
+    /// then "votes" and "voters" in chunks of `limit` starting at `cursor`.
+    /// Returns the cursor to pass next; the kind is complete once it stops advancing.
+    pub fn migrate_chunk(env: &Env, kind: Symbol, cursor: u32, limit: u32) -> Result<u32, ContractError> {
+        let mut state: MigrationState = env.storage().instance().get(&MIGRATION_KEY).unwrap_or(MigrationState {
+            core_done: false,
+            votes_cursor: 0,
+            votes_done: false,
+            voters_cursor: 0,
+            voters_done: false,
+            sealed: false,
+        });
+
+        if state.sealed {
+            return Err(ContractError::Sealed);
+        }
+
+        let legacy: Map<Symbol, Val> = env
+            .storage()
+            .instance()
+            .get(&LEGACY_DATA_KEY)
+            .ok_or(ContractError::NotFound)?;
+
+        let admin = Self::legacy_field::<Address>(env, &legacy, "admin")?;
+        admin.require_auth();
+
+        let next = if kind == symbol_short!("core") {
+            if state.core_done {
+                return Err(ContractError::Done);
+            }
+            Self::migrate_core(env, &legacy, admin)?;
+            state.core_done = true;
+            0
+        } else {
+            if !state.core_done {
+                return Err(ContractError::NoCoreVote);
+            }
+            if limit == 0 {
+                return Err(ContractError::InvalidInput);
+            }
+
+            let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+            let next = if kind == symbol_short!("votes") {
+                if cursor != state.votes_cursor {
+                    return Err(ContractError::InvalidInput);
+                }
+                let next = Self::migrate_votes(env, &legacy, cursor, limit)?;
+                state.votes_done = next == cursor;
+                state.votes_cursor = next;
+                next
+            } else if kind == symbol_short!("voters") {
+                if cursor != state.voters_cursor {
+                    return Err(ContractError::InvalidInput);
+                }
+                let next = Self::migrate_voters(env, &mut data, &legacy, cursor, limit)?;
+                state.voters_done = next == cursor;
+                state.voters_cursor = next;
+                next
+            } else {
+                return Err(ContractError::InvalidInput);
+            };
+            ttl::set_instance(env, &DATA_KEY, &data);
+            next
+        };
+
+        ttl::set_instance(env, &MIGRATION_KEY, &state);
+        env.events().publish((symbol_short!("migrate"), kind), next);
+
+        Ok(next)
+    }
+
+    /// Remove the legacy key once every kind has been migrated, and resume (admin only)
+    pub fn finalize_migration(env: &Env, caller: Address) -> Result<(), ContractError> {
         let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
-        
-        // Only oracle can update voter data
-        if env.current_contract_address() != data.oracle {
-            return Err(symbol_short!("UNAUTHORIZED"));
+
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+
+        let mut state: MigrationState = env.storage().instance().get(&MIGRATION_KEY).ok_or(ContractError::NoCoreVote)?;
+        if state.sealed {
+            return Err(ContractError::Sealed);
+        }
+        if !state.core_done || !state.votes_done || !state.voters_done {
+            return Err(ContractError::Pending);
         }
 
-        let mut voter_data = data.voters.get(&voter).unwrap_or(VoterData {
-            address: voter.clone(),
-            stake_amount: 0,
-            equity_score: 0,
-            voting_power: 0,
-            last_vote_time: 0,
-            total_votes_cast: 0,
-        });
+        env.storage().instance().remove(&LEGACY_DATA_KEY);
+        state.sealed = true;
+        ttl::set_instance(env, &MIGRATION_KEY, &state);
 
-        voter_data.stake_amount = stake_amount;
-        voter_data.equity_score = equity_score;
-        voter_data.voting_power = Self::calculate_voting_power(env, &voter_data);
+        data.paused = false;
+        ttl::set_instance(env, &DATA_KEY, &data);
 
-        data.voters.set(&voter, &voter_data);
-        env.storage().instance().set(&DATA_KEY, &data);
-        
         Ok(())
     }
 
-    /// Get proposal details
-    pub fn get_proposal(env: &Env, proposal_id: Symbol) -> Result<Proposal, Symbol> {
-        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
-        data.proposals.get(&proposal_id).ok_or(symbol_short!("PROPOSAL_NOT_FOUND"))
+    /// Get migration progress, if a migration has started
+    pub fn get_migration_state(env: &Env) -> Option<MigrationState> {
+        env.storage().instance().get(&MIGRATION_KEY)
     }
 
-    /// Get votes for a proposal
-    pub fn get_proposal_votes(env: &Env, proposal_id: Symbol) -> Vec<Vote> {
+    /// Read one field of the legacy monolithic data
+    fn legacy_field<T: TryFromVal<Env, Val>>(env: &Env, legacy: &Map<Symbol, Val>, name: &str) -> Result<T, ContractError> {
+        let value = legacy.get(Symbol::new(env, name)).ok_or(ContractError::BadLegacy)?;
+        T::try_from_val(env, &value).map_err(|_| ContractError::BadLegacy)
+    }
+
+    /// Rebuild instance data from the legacy fields that still exist, defaulting new ones,
+    /// and pause until the votes and voters have been moved
+    fn migrate_core(env: &Env, legacy: &Map<Symbol, Val>, admin: Address) -> Result<(), ContractError> {
+        let template = Self::default_data(
+            env,
+            admin,
+            Self::legacy_field(env, legacy, "oracle")?,
+            Self::legacy_field(env, legacy, "loan_pool")?,
+            Self::legacy_field(env, legacy, "min_proposal_duration")?,
+        );
+
+        // Proposals gain a snapshot ledger; legacy ones let all existing stake count
+        // Legacy proposals are read field by field, since their optional fields have no fixed layout
+        let legacy_proposals: Map<Symbol, Map<Symbol, Val>> = Self::legacy_field(env, legacy, "proposals")?;
+        for (_, old) in legacy_proposals.iter() {
+            let title: Symbol = Self::legacy_field(env, &old, "title")?;
+            let description: Symbol = Self::legacy_field(env, &old, "description")?;
+            let target_asset: Option<Symbol> = Self::legacy_field(env, &old, "target_asset")?;
+            let policy_band: Option<PolicyBand> = Self::legacy_field(env, &old, "policy_band")?;
+            Self::save_proposal(env, &Proposal {
+                id: Self::legacy_field(env, &old, "id")?,
+                // Legacy text was on-chain, so commit to it as the document
+                metadata: Metadata {
+                    content_hash: env.crypto().sha256(&(title.clone(), description).to_xdr(env)),
+                    uri: String::from_str(env, ""),
+                    label: title,
+                },
+                proposer: Self::legacy_field(env, &old, "proposer")?,
+                proposal_type: Self::legacy_field(env, &old, "proposal_type")?,
+                target_asset: target_asset.unwrap_or(Symbol::new(env, "")),
+                amount: Self::legacy_field(env, &old, "amount")?,
+                start_time: Self::legacy_field(env, &old, "start_time")?,
+                end_time: Self::legacy_field(env, &old, "end_time")?,
+                status: Self::legacy_field(env, &old, "status")?,
+                yes_votes: Self::legacy_field(env, &old, "yes_votes")?,
+                no_votes: Self::legacy_field(env, &old, "no_votes")?,
+                total_votes: Self::legacy_field(env, &old, "total_votes")?,
+                equity_boost_threshold: Self::legacy_field(env, &old, "equity_boost_threshold")?,
+                snapshot_ledger: 0,
+                policy_bands: match policy_band {
+                    Some(band) => vec![env, band],
+                    None => vec![env],
+                },
+                emergency_request: Self::legacy_field(env, &old, "emergency_request")?,
+                calls: vec![env],
+                rebalance_targets: vec![env],
+                voting_mode: VotingMode::Linear,
+            });
+        }
+
+        // Keep every field the new layout still has; voter aggregates are rebuilt by "voters"
+        let mut fields: Map<Symbol, Val> = Map::try_from_val(env, &IntoVal::<Env, Val>::into_val(&template, env)).unwrap();
+        for (name, _) in fields.clone().iter() {
+            if let Some(value) = legacy.get(name.clone()) {
+                fields.set(name, value);
+            }
+        }
+        fields.set(Symbol::new(env, "total_voting_power"), 0i128.into_val(env));
+        fields.set(Symbol::new(env, "total_quadratic_power"), 0i128.into_val(env));
+        fields.set(Symbol::new(env, "staked_voter_count"), 0u32.into_val(env));
+        fields.set(Symbol::new(env, "voter_count"), 0u32.into_val(env));
+        fields.set(Symbol::new(env, "paused"), true.into_val(env));
+
+        let data = DataKey::try_from_val(env, &fields.to_val()).map_err(|_| ContractError::BadLegacy)?;
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        Ok(())
+    }
+
+    /// Move up to `limit` legacy votes, counted across proposals in key order, starting at `cursor`
+    fn migrate_votes(env: &Env, legacy: &Map<Symbol, Val>, cursor: u32, limit: u32) -> Result<u32, ContractError> {
+        let legacy_votes: Map<Symbol, Vec<Vote>> = Self::legacy_field(env, legacy, "votes")?;
+
+        let mut position: u32 = 0;
+        let mut moved: u32 = 0;
+        for (proposal_id, votes) in legacy_votes.iter() {
+            if position + votes.len() <= cursor {
+                position += votes.len();
+                continue;
+            }
+            for index in 0..votes.len() {
+                if position < cursor {
+                    position += 1;
+                    continue;
+                }
+                if moved == limit {
+                    return Ok(cursor + moved);
+                }
+
+                let vote = votes.get(index).unwrap();
+                ttl::set_persistent(env, &(VOTE_KEY, proposal_id.clone(), vote.voter.clone()), &vote);
+                ttl::set_persistent(env, &(VOTE_AT_KEY, proposal_id.clone(), index), &vote.voter);
+                ttl::set_persistent(env, &(VOTE_COUNT_KEY, proposal_id.clone()), &(index + 1));
+                if vote.vote == symbol_short!("yes") || vote.vote == symbol_short!("no") {
+                    Self::add_tally(env, &proposal_id, vote.vote.clone(), vote.total_power);
+                }
+                Self::add_tally(env, &proposal_id, symbol_short!("total"), vote.total_power);
+
+                position += 1;
+                moved += 1;
+            }
+        }
+
+        Ok(cursor + moved)
+    }
+
+    /// Move up to `limit` legacy voters starting at `cursor`, rebuilding the voter aggregates
+    fn migrate_voters(
+        env: &Env,
+        data: &mut DataKey,
+        legacy: &Map<Symbol, Val>,
+        cursor: u32,
+        limit: u32,
+    ) -> Result<u32, ContractError> {
+        let legacy_voters: Map<Address, LegacyVoterData> = Self::legacy_field(env, legacy, "voters")?;
+
+        let mut moved: u32 = 0;
+        for (voter, old) in legacy_voters.iter().skip(cursor as usize).take(limit as usize) {
+
+            // Existing stake predates every open proposal, so all of it has matured
+            let voter_data = VoterData {
+                address: old.address,
+                stake_amount: old.stake_amount,
+                equity_score: old.equity_score,
+                voting_power: old.voting_power,
+                last_vote_time: old.last_vote_time,
+                total_votes_cast: old.total_votes_cast,
+                equity_updated_at: old.equity_updated_at,
+                stake_since_ledger: 0,
+                prior_stake: old.stake_amount,
+            };
+
+            Self::add_power_totals(env, data, &voter_data);
+            data.voter_count += 1;
+            ttl::set_persistent(env, &(VOTER_KEY, voter), &voter_data);
+            moved += 1;
+        }
+
+        Ok(cursor + moved)
+    }
+
+    /// Queue an admin change behind the timelock (admin only).
+    /// Supported actions: "quorum", "boost_mul", "min_dur", "oracle", "loan_pool", "rate_adj", "rev_dist", "stake_age", "gas_stake", "gas_vote", "bond_bps"
+    pub fn queue_admin_change(
+        env: &Env,
+        caller: Address,
+        action: Symbol,
+        value: i128,
+        new_address: Option<Address>,
+        delay: u64,
+    ) -> Result<u32, ContractError> {
         let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
-        data.votes.get(&proposal_id).unwrap_or(vec![env])
+
+        // Only admin can queue changes
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+
+        let min_delay = if Self::is_sandbox(env) { 0 } else { timelock::MIN_DELAY };
+        timelock::queue(env, action, ChangeValue::new(value, new_address), delay, min_delay, Self::validate_admin_change)
     }
 
-    /// Get voter data
-    pub fn get_voter_data(env: &Env, voter: Address) -> Result<VoterData, Symbol> {
+    /// Execute a queued admin change once its timelock has elapsed (executor only)
+    pub fn execute_admin_change(env: &Env, caller: Address, change_id: u32) -> Result<(), ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        // Only executors can execute changes
+        access::check(env, &data.admin, Role::Executor, &caller)?;
+
+        timelock::execute(env, change_id, |change| Self::apply_admin_change(env, &mut data, change))?;
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        Ok(())
+    }
+
+    /// Cancel a queued admin change during its cancellation window (admin only)
+    pub fn cancel_admin_change(env: &Env, caller: Address, change_id: u32) -> Result<(), ContractError> {
         let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
-        data.voters.get(&voter).ok_or(symbol_short!("VOTER_NOT_FOUND"))
+
+        // Only admin can cancel changes
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+
+        timelock::cancel(env, change_id).map_err(ContractError::from)
+    }
+
+    /// Get all admin changes waiting out their timelock
+    pub fn get_pending_changes(env: &Env) -> Vec<PendingChange> {
+        timelock::pending(env)
+    }
+
+    /// Get a page of the append-only log of executed and cancelled admin changes
+    pub fn get_change_log(env: &Env, page: PageRequest) -> ChangeLogPage {
+        timelock::log(env, page)
+    }
+
+    /// Check that a queued change names a known action with a valid value
+    fn validate_admin_change(change: &PendingChange) -> Result<(), ContractError> {
+        if change.action == symbol_short!("oracle")
+            || change.action == symbol_short!("loan_pool")
+            || change.action == symbol_short!("rate_adj")
+            || change.action == symbol_short!("rev_dist")
+        {
+            change.address()?;
+            return Ok(());
+        }
+
+        let value = change.number()?;
+        let valid = if change.action == symbol_short!("quorum") {
+            // Quorum percentage (1-100%)
+            (1..=100).contains(&value)
+        } else if change.action == symbol_short!("boost_mul") {
+            // Boost multiplier (100-300%)
+            (100..=300).contains(&value)
+        } else if change.action == symbol_short!("min_dur") {
+            // Minimum proposal duration (up to one year)
+            (1..=31_536_000).contains(&value)
+        } else if change.action == symbol_short!("stake_age") {
+            // Stake age between 0 and ~7 days of ledgers
+            (0..=MAX_STAKE_AGE_LEDGERS as i128).contains(&value)
+        } else if change.action == symbol_short!("gas_stake") {
+            // Any non-negative stake threshold
+            value >= 0
+        } else if change.action == symbol_short!("gas_vote") {
+            // Rebate per vote up to 0.1 XLM
+            (0..=MAX_GAS_REBATE_PER_VOTE).contains(&value)
+        } else if change.action == symbol_short!("bond_bps") {
+            // Bond of 0.01-100% of a funding request
+            (1..=10_000).contains(&value)
+        } else {
+            false
+        };
+        if !valid {
+            return Err(ContractError::InvalidInput);
+        }
+
+        Ok(())
+    }
+
+    /// Apply an admin change to contract state
+    fn apply_admin_change(env: &Env, data: &mut DataKey, change: &PendingChange) -> Result<(), ContractError> {
+        Self::validate_admin_change(change)?;
+
+        if change.action == symbol_short!("quorum") {
+            data.quorum_threshold = change.number()? as i32;
+        } else if change.action == symbol_short!("boost_mul") {
+            data.equity_boost_multiplier = change.number()? as i32;
+        } else if change.action == symbol_short!("min_dur") {
+            data.min_proposal_duration = change.number()? as u64;
+        } else if change.action == symbol_short!("oracle") {
+            data.oracle = change.address()?;
+        } else if change.action == symbol_short!("loan_pool") {
+            data.loan_pool = change.address()?;
+        } else if change.action == symbol_short!("rate_adj") {
+            ttl::set_persistent(env, &StorageKey::RateAdjuster, &change.address()?);
+        } else if change.action == symbol_short!("rev_dist") {
+            ttl::set_persistent(env, &StorageKey::RevenueDistributor, &change.address()?);
+        } else if change.action == symbol_short!("stake_age") {
+            data.min_stake_age_ledgers = change.number()? as u32;
+        } else if change.action == symbol_short!("gas_stake") {
+            data.gas_rebate_stake_threshold = change.number()?;
+        } else if change.action == symbol_short!("gas_vote") {
+            data.gas_rebate_per_vote = change.number()?;
+        } else if change.action == symbol_short!("bond_bps") {
+            ttl::set_persistent(env, &StorageKey::ProposalBondBps, &change.number()?);
+        }
+
+        Ok(())
+    }
+
+    /// Rate adjuster contract, once linked through an admin change
+    fn rate_adjuster(env: &Env) -> Result<Address, ContractError> {
+        env.storage()
+            .persistent()
+            .get(&StorageKey::RateAdjuster)
+            .ok_or(ContractError::NoAddress)
+    }
+
+    /// Revenue distributor contract, once linked through an admin change
+    fn revenue_distributor(env: &Env) -> Result<Address, ContractError> {
+        env.storage()
+            .persistent()
+            .get(&StorageKey::RevenueDistributor)
+            .ok_or(ContractError::NoAddress)
+    }
+
+    /// Get a page of boost audit records for an address, oldest first
+    pub fn get_boost_audit_by_address(env: &Env, subject: Address, page: PageRequest) -> BoostAuditPage {
+        let (items, next_cursor) = journal::page(env, &(BOOST_BY_SUBJECT, subject), &page);
+        BoostAuditPage { items, next_cursor }
+    }
+
+    /// Get a page of boost audit records for a rule ("vote_bst"), oldest first
+    pub fn get_boost_audit_by_rule(env: &Env, rule: Symbol, page: PageRequest) -> BoostAuditPage {
+        let (items, next_cursor) = journal::page(env, &(BOOST_BY_RULE, rule), &page);
+        BoostAuditPage { items, next_cursor }
+    }
+
+    /// Append a boost audit record
+    fn log_boost(env: &Env, subject: &Address, amount: i128, rule: Symbol, data_version: u64) {
+        let record = BoostAuditRecord {
+            subject: subject.clone(),
+            amount,
+            rule: rule.clone(),
+            data_version,
+            timestamp: env.ledger().timestamp(),
+        };
+        journal::append(env, &(BOOST_BY_SUBJECT, subject.clone()), &record);
+        journal::append(env, &(BOOST_BY_RULE, rule), &record);
     }
 
     /// Get all active proposals
     pub fn get_active_proposals(env: &Env) -> Vec<Proposal> {
-        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
         let mut active_proposals = vec![env];
         
-        for (_, proposal) in data.proposals.iter() {
-            if proposal.status == symbol_short!("active") {
-                active_proposals.push_back(&proposal);
-            }
+        for proposal_id in Self::active_proposal_ids(env).iter() {
+            active_proposals.push_back(Self::load_proposal(env, &proposal_id).unwrap());
         }
         
         active_proposals
     }
 
+    /// Get a page of active proposals in creation order; the cursor is an offset into the active set
+    pub fn get_active_proposals_page(env: &Env, page: PageRequest) -> ProposalPage {
+        Self::proposals_page(env, &Self::active_proposal_ids(env), &page)
+    }
+
+    /// Get a page of all proposals in creation order
+    pub fn get_proposals_page(env: &Env, page: PageRequest) -> ProposalPage {
+        Self::proposals_page(env, &Self::proposal_ids(env), &page)
+    }
+
     /// Generate unique proposal ID
-    fn generate_proposal_id(env: &Env, proposer: &Address, title: &Symbol) -> Symbol {
-        let proposer_str = proposer.to_string();
-        let title_str = title.to_string();
+    fn generate_proposal_id(env: &Env, proposer: &Address, metadata: &Metadata) -> Symbol {
         let timestamp = env.ledger().timestamp();
-        
-        let combined = format!("prop_{}_{}_{}", proposer_str, title_str, timestamp);
-        let hash = env.crypto().sha256(&combined.as_bytes());
-        
-        // Convert first 8 bytes to symbol
-        let mut id_bytes = [0u8; 8];
-        id_bytes.copy_from_slice(&hash[0..8]);
-        
-        Symbol::from_bytes(&id_bytes)
+
+        // The proposer's sequence number keeps IDs unique within a single ledger
+        let sequence_key = StorageKey::IdSequence(proposer.clone());
+        let sequence: u32 = env.storage().persistent().get(&sequence_key).unwrap_or(0);
+        ttl::set_persistent(env, &sequence_key, &(sequence + 1));
+
+        // The document's content hash stands in for its title, which lives off-chain
+        let mut preimage = (proposer.clone(), timestamp, sequence).to_xdr(env);
+        preimage.append(&metadata.content_hash.clone().into());
+        ids::derive(env, &preimage)
+    }
+
+    /// Use up the pending action approving a disbursement above the threshold
+    fn consume_approval(
+        env: &Env,
+        data: &mut DataKey,
+        kind: Symbol,
+        reference: Symbol,
+        recipient: Option<Address>,
+        amount: i128,
+    ) -> Result<(), ContractError> {
+        if !data.approval_rule.applies(amount) {
+            return Ok(());
+        }
+
+        let now = env.ledger().timestamp();
+        let payee = Payee::from(recipient);
+        for (action_id, action) in data.pending_actions.iter() {
+            if action.covers(&data.approval_rule, &kind, &reference, &payee, amount, now) {
+                data.pending_actions.remove(action_id);
+                env.events().publish((symbol_short!("act_exec"), action_id), (kind, amount));
+                return Ok(());
+            }
+        }
+
+        Err(ContractError::NeedsApproval)
+    }
+
+    /// Settlement asset of the linked loan pool, which the treasury holds
+    fn treasury_token(env: &Env, data: &DataKey) -> Result<Address, ContractError> {
+        LoanPoolClient::new(env, &data.loan_pool)
+            .get_settlement_asset()
+            .ok_or(ContractError::NoSettlementAsset)
+    }
+
+    /// Pull a funding proposal's bond from its proposer
+    fn take_bond(env: &Env, data: &DataKey, proposal_id: &Symbol, proposer: &Address, requested: i128) -> Result<(), ContractError> {
+        let bond_bps: i128 = env.storage().persistent().get(&StorageKey::ProposalBondBps).unwrap_or(DEFAULT_PROPOSAL_BOND_BPS);
+        let bond = (requested * bond_bps / 10_000).max(1);
+
+        let token = Self::treasury_token(env, data)?;
+        TokenClient::new(env, &token).transfer(proposer, &env.current_contract_address(), &bond);
+        ttl::set_persistent(env, &StorageKey::Bond(proposal_id.clone()), &(proposer.clone(), bond));
+
+        env.events().publish((symbol_short!("bonded"), proposal_id.clone()), (proposer.clone(), bond));
+
+        Ok(())
+    }
+
+    /// Return a proposal's bond to its proposer, or keep it in the treasury
+    fn settle_bond(env: &Env, data: &mut DataKey, proposal_id: &Symbol, returned: bool) -> Result<(), ContractError> {
+        let key = StorageKey::Bond(proposal_id.clone());
+        let Some((proposer, bond)) = env.storage().persistent().get::<_, (Address, i128)>(&key) else {
+            return Ok(());
+        };
+        env.storage().persistent().remove(&key);
+
+        if returned {
+            let token = Self::treasury_token(env, data)?;
+            TokenClient::new(env, &token).transfer(&env.current_contract_address(), &proposer, &bond);
+        } else {
+            data.treasury_balance += bond;
+        }
+
+        env.events().publish((symbol_short!("bond"), proposal_id.clone()), (proposer, bond, returned));
+
+        Ok(())
+    }
+
+    /// Invest treasury funds in a loan pool asset, authorizing the pool to pull them
+    fn invest_treasury(env: &Env, data: &DataKey, asset_id: &Symbol, amount: i128) -> Result<(), ContractError> {
+        let token = Self::treasury_token(env, data)?;
+        let treasury = env.current_contract_address();
+        env.authorize_as_current_contract(vec![
+            env,
+            InvokerContractAuthEntry::Contract(SubContractInvocation {
+                context: ContractContext {
+                    contract: token,
+                    fn_name: symbol_short!("transfer"),
+                    args: (treasury.clone(), data.loan_pool.clone(), amount).into_val(env),
+                },
+                sub_invocations: vec![env],
+            }),
+        ]);
+        env.invoke_contract::<i32>(
+            &data.loan_pool,
+            &symbol_short!("invest"),
+            vec![env, treasury.into_val(env), asset_id.into_val(env), amount.into_val(env)],
+        );
+
+        Ok(())
+    }
+
+    /// Return a proposal's escrow to the free treasury balance
+    fn release_escrow(data: &mut DataKey, proposal_id: &Symbol) {
+        if let Some(escrowed) = data.escrows.get(proposal_id.clone()) {
+            data.total_escrowed -= escrowed;
+            data.escrows.remove(proposal_id.clone());
+        }
     }
 
     /// Calculate voting power based on stake
-    fn calculate_voting_power(env: &Env, voter_data: &VoterData) -> i128 {
-        // Base voting power is 1:1 with stake amount
-        voter_data.stake_amount
+    fn calculate_voting_power(_env: &Env, voter_data: &VoterData, mode: VotingMode) -> i128 {
+        match mode {
+            VotingMode::Linear => voter_data.stake_amount,
+            VotingMode::Quadratic => isqrt(voter_data.stake_amount),
+            VotingMode::OnePersonOneVote => (voter_data.stake_amount > 0) as i128,
+        }
+    }
+
+    /// Count a voter toward the electorate totals of every voting mode
+    fn add_power_totals(env: &Env, data: &mut DataKey, voter_data: &VoterData) {
+        data.total_voting_power += voter_data.voting_power;
+        data.total_quadratic_power += Self::calculate_voting_power(env, voter_data, VotingMode::Quadratic);
+        data.staked_voter_count += Self::calculate_voting_power(env, voter_data, VotingMode::OnePersonOneVote) as u32;
+    }
+
+    /// Take a voter's previous data out of the electorate totals
+    fn remove_power_totals(env: &Env, data: &mut DataKey, voter_data: &VoterData) {
+        data.total_voting_power -= voter_data.voting_power;
+        data.total_quadratic_power -= Self::calculate_voting_power(env, voter_data, VotingMode::Quadratic);
+        data.staked_voter_count -= Self::calculate_voting_power(env, voter_data, VotingMode::OnePersonOneVote) as u32;
+    }
+
+    /// Voter data as of a proposal's snapshot: stake raised within the minimum stake age of
+    /// the proposal's creation counts only up to the stake held before the increase
+    fn snapshot_voter(env: &Env, data: &DataKey, voter_data: &VoterData, proposal: &Proposal) -> VoterData {
+        let mut snapshot = voter_data.clone();
+        if voter_data.stake_since_ledger.saturating_add(data.min_stake_age_ledgers) > proposal.snapshot_ledger {
+            snapshot.stake_amount = voter_data.prior_stake;
+        }
+        snapshot.voting_power = Self::calculate_voting_power(env, &snapshot, proposal.voting_mode);
+        snapshot
     }
 
     /// Calculate equity boost for voting power
@@ -409,22 +2934,20 @@ impl Governance {
         if voter_data.equity_score >= proposal.equity_boost_threshold {
             // Calculate boost as percentage of base voting power
             let boost_percentage = data.equity_boost_multiplier - 100; // 50% boost
-            voter_data.voting_power * boost_percentage / 100
+            voter_data.voting_power * boost_percentage as i128 / 100
         } else {
             0
         }
     }
 
     /// Calculate total possible votes from all stakeholders
-    fn calculate_total_possible_votes(env: &Env) -> i128 {
+    fn calculate_total_possible_votes(env: &Env, mode: VotingMode) -> i128 {
         let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
-        
-        let mut total = 0;
-        for (_, voter_data) in data.voters.iter() {
-            total += voter_data.voting_power;
+        match mode {
+            VotingMode::Linear => data.total_voting_power,
+            VotingMode::Quadratic => data.total_quadratic_power,
+            VotingMode::OnePersonOneVote => data.staked_voter_count as i128,
         }
-        
-        total
     }
 
     /// Get governance statistics
@@ -434,23 +2957,166 @@ impl Governance {
         let mut total_proposals = 0;
         let mut active_proposals = 0;
         let mut passed_proposals = 0;
-        let mut total_voters = 0;
+        let total_voters = data.voter_count as i32;
         
-        for (_, proposal) in data.proposals.iter() {
+        for proposal_id in Self::proposal_ids(env).iter() {
+            let proposal = Self::load_proposal(env, &proposal_id).unwrap();
             total_proposals += 1;
-            match proposal.status.as_str() {
-                "active" => active_proposals += 1,
-                "passed" => passed_proposals += 1,
-                _ => {}
+            if proposal.status == symbol_short!("active") {
+                active_proposals += 1;
+            } else if proposal.status == symbol_short!("passed") {
+                passed_proposals += 1;
             }
         }
         
-        for (_, _) in data.voters.iter() {
-            total_voters += 1;
-        }
-        
         (total_proposals, active_proposals, passed_proposals, total_voters)
     }
+
+    /// Get an epoch's public goods KPIs, derived from the loan pool, rate adjuster and distributor
+    /// counters. Contracts not yet connected contribute zeros
+    pub fn get_kpis(env: &Env, epoch: u32) -> EpochKpis {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        let financing = LoanPoolClient::new(env, &data.loan_pool).get_epoch_financing(&epoch);
+        let rates = Self::rate_adjuster(env).ok()
+            .map(|rate_adjuster| EquityOracleClient::new(env, &rate_adjuster).get_epoch_rate_stats(&epoch))
+            .unwrap_or_default();
+        let rides = Self::revenue_distributor(env).ok()
+            .map(|distributor| DistributorClient::new(env, &distributor).get_epoch_rides(&epoch))
+            .unwrap_or_default();
+
+        EpochKpis::derive(epoch, &financing, &rates, &rides)
+    }
+
+    /// Freeze a finished calendar year's program-wide summary, once. Anyone can call this after
+    /// the year ends; contracts not yet connected contribute zeros
+    pub fn finalize_year(env: &Env, year: u32) -> Result<AnnualReport, ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        if env.storage().persistent().has(&StorageKey::AnnualReport(year)) {
+            return Err(ContractError::Done);
+        }
+        let schedule = &data.epoch_schedule;
+        let (year_start, year_end) = (schedule.year_start(year), schedule.year_start(year + 1));
+        if env.ledger().timestamp() < year_end {
+            return Err(ContractError::TooEarly);
+        }
+        let (first_epoch, end_epoch) = schedule.epochs_in_year(year);
+        if end_epoch - first_epoch > MAX_REPORT_EPOCHS {
+            return Err(ContractError::OverLimit);
+        }
+
+        let mut summary = AnnualSummary {
+            year,
+            first_epoch,
+            end_epoch,
+            financed: 0,
+            repaid_principal: 0,
+            repaid_interest: 0,
+            defaults: 0,
+            distributions: 0,
+            distributed: 0,
+            platform_fees: 0,
+            rides: 0,
+            underserved_rides: 0,
+            loans_approved: 0,
+            rate_discount_bps: 0,
+            proposals: 0,
+            proposals_passed: 0,
+        };
+
+        let loan_pool = LoanPoolClient::new(env, &data.loan_pool);
+        let rate_adjuster = Self::rate_adjuster(env).ok().map(|rate_adjuster| EquityOracleClient::new(env, &rate_adjuster));
+        let distributor = Self::revenue_distributor(env).ok().map(|distributor| DistributorClient::new(env, &distributor));
+        for epoch in first_epoch..end_epoch {
+            let financing = loan_pool.get_epoch_financing(&epoch);
+            let servicing = loan_pool.get_epoch_servicing(&epoch);
+            summary.financed += financing.financed;
+            summary.repaid_principal += servicing.repaid_principal;
+            summary.repaid_interest += servicing.repaid_interest;
+            summary.defaults += servicing.defaults;
+
+            if let Some(rate_adjuster) = &rate_adjuster {
+                let rates = rate_adjuster.get_epoch_rate_stats(&epoch);
+                summary.loans_approved += rates.loans;
+                summary.rate_discount_bps += rates.discount_bps;
+            }
+
+            if let Some(distributor) = &distributor {
+                let rides = distributor.get_epoch_rides(&epoch);
+                let distributions = distributor.get_epoch_distributions(&epoch);
+                summary.rides += rides.rides;
+                summary.underserved_rides += rides.underserved_rides;
+                summary.distributions += distributions.distributions;
+                summary.distributed += distributions.distributed;
+                summary.platform_fees += distributions.platform_fees;
+            }
+        }
+
+        for proposal_id in Self::proposal_ids(env).iter() {
+            let proposal = Self::load_proposal(env, &proposal_id).unwrap();
+            if proposal.start_time < year_start || proposal.start_time >= year_end {
+                continue;
+            }
+            summary.proposals += 1;
+            if proposal.status == symbol_short!("passed") || proposal.status == symbol_short!("executed") {
+                summary.proposals_passed += 1;
+            }
+        }
+
+        let report = AnnualReport {
+            content_hash: env.crypto().sha256(&summary.clone().to_xdr(env)),
+            summary,
+            finalized_at: env.ledger().timestamp(),
+        };
+        ttl::set_persistent(env, &StorageKey::AnnualReport(year), &report);
+
+        env.events().publish((symbol_short!("annual"), year), report.content_hash.clone());
+
+        Ok(report)
+    }
+
+    /// Get a finalized annual report
+    pub fn get_annual_report(env: &Env, year: u32) -> Result<AnnualReport, ContractError> {
+        env.storage()
+            .persistent()
+            .get(&StorageKey::AnnualReport(year))
+            .ok_or(ContractError::NotFound)
+    }
+}
+
+#[contractimpl]
+impl GovernanceInterface for Governance {
+    /// Get the platform fee rate for a zone equity score
+    fn get_fee_bps(env: &Env, equity_score: i32) -> u32 {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        let mut fee_bps = 0;
+
+        for tier in data.fee_schedule.iter() {
+            if equity_score >= tier.min_equity_score {
+                fee_bps = tier.fee_bps;
+            }
+        }
+
+        fee_bps
+    }
+
+    /// Get the shared epoch calendar
+    fn get_epoch_schedule(env: &Env) -> EpochSchedule {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        data.epoch_schedule
+    }
+
+    /// Whether a voter holds a participation badge for a proposal
+    fn has_badge(env: &Env, voter: Address, proposal_id: Symbol) -> bool {
+        env.storage().persistent().has(&(BADGE_KEY, voter, proposal_id))
+    }
+
+    /// Number of participation badges a voter holds
+    fn get_badge_count(env: &Env, voter: Address) -> u32 {
+        let ids: Vec<Symbol> = env.storage().persistent().get(&(BADGES_KEY, voter)).unwrap_or(vec![env]);
+        ids.len()
+    }
 }
 
 #[cfg(test)]
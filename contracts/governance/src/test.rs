@@ -0,0 +1,1398 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    symbol_short, vec, xdr::ScVal, Address, Bytes, BytesN, Env, IntoVal, Symbol, TryFromVal, Val,
+    testutils::{Address as _, Events, Ledger, MockAuth, MockAuthInvoke},
+    token::StellarAssetClient,
+};
+use mobility_types::{metadata::MAX_URI_LEN, DistributionStats, FinancingStats, RateStats, RideStats, ServicingStats};
+
+/// Stands in for the loan pool: settles in one token and takes investments in it
+#[contract]
+struct StubLoanPool;
+
+#[contractimpl]
+impl StubLoanPool {
+    pub fn set_settlement_asset(env: Env, token: Address) {
+        env.storage().instance().set(&symbol_short!("token"), &token);
+    }
+
+    pub fn get_settlement_asset(env: Env) -> Option<Address> {
+        env.storage().instance().get(&symbol_short!("token"))
+    }
+
+    pub fn is_winding_down(_env: Env, _asset_id: Symbol) -> bool {
+        false
+    }
+
+    pub fn invest(env: Env, investor: Address, asset_id: Symbol, amount: i128) -> i32 {
+        investor.require_auth();
+        let token: Address = env.storage().instance().get(&symbol_short!("token")).unwrap();
+        TokenClient::new(&env, &token).transfer(&investor, &env.current_contract_address(), &amount);
+        env.storage().instance().set(&asset_id, &amount);
+        0
+    }
+
+    pub fn record_pool_epoch(env: Env, epoch: u32, financing: FinancingStats, servicing: ServicingStats) {
+        env.storage().instance().set(&(symbol_short!("financed"), epoch), &financing);
+        env.storage().instance().set(&(symbol_short!("serviced"), epoch), &servicing);
+    }
+
+    pub fn get_epoch_financing(env: Env, epoch: u32) -> FinancingStats {
+        env.storage().instance().get(&(symbol_short!("financed"), epoch)).unwrap_or_default()
+    }
+
+    pub fn get_epoch_servicing(env: Env, epoch: u32) -> ServicingStats {
+        env.storage().instance().get(&(symbol_short!("serviced"), epoch)).unwrap_or_default()
+    }
+}
+
+/// Stands in for the rate adjuster: counts each base rate it is set to and refuses rates
+/// that haven't been allowed
+#[contract]
+struct StubRateAdjuster;
+
+#[contractimpl]
+impl StubRateAdjuster {
+    pub fn allow(env: Env, rate: i32) {
+        env.storage().instance().set(&(symbol_short!("allowed"), rate), &true);
+    }
+
+    pub fn set_base_rate(env: Env, rate: i32) -> Result<u32, ContractError> {
+        if !env.storage().instance().has(&(symbol_short!("allowed"), rate)) {
+            return Err(ContractError::InvalidInput);
+        }
+        let applied = Self::applied(env.clone(), rate) + 1;
+        env.storage().instance().set(&rate, &applied);
+        Ok(applied)
+    }
+
+    pub fn applied(env: Env, rate: i32) -> u32 {
+        env.storage().instance().get(&rate).unwrap_or(0)
+    }
+
+    pub fn set_policy_band(env: Env, parameter: Symbol, min: i128, max: i128) -> u32 {
+        env.storage().instance().set(&parameter, &(min, max));
+        0
+    }
+
+    pub fn band(env: Env, parameter: Symbol) -> Option<(i128, i128)> {
+        env.storage().instance().get(&parameter)
+    }
+
+    pub fn record_rate_stats(env: Env, epoch: u32, rates: RateStats) {
+        env.storage().instance().set(&(symbol_short!("rates"), epoch), &rates);
+    }
+
+    pub fn get_epoch_rate_stats(env: Env, epoch: u32) -> RateStats {
+        env.storage().instance().get(&(symbol_short!("rates"), epoch)).unwrap_or_default()
+    }
+}
+
+/// Stands in for the revenue distributor, serving the epoch counters a test records
+#[contract]
+struct StubDistributor;
+
+#[contractimpl]
+impl StubDistributor {
+    pub fn record_rides(env: Env, epoch: u32, rides: RideStats, distributions: DistributionStats) {
+        env.storage().instance().set(&(symbol_short!("rides"), epoch), &rides);
+        env.storage().instance().set(&(symbol_short!("distrib"), epoch), &distributions);
+    }
+
+    pub fn get_epoch_rides(env: Env, epoch: u32) -> RideStats {
+        env.storage().instance().get(&(symbol_short!("rides"), epoch)).unwrap_or_default()
+    }
+
+    pub fn get_epoch_distributions(env: Env, epoch: u32) -> DistributionStats {
+        env.storage().instance().get(&(symbol_short!("distrib"), epoch)).unwrap_or_default()
+    }
+}
+
+/// Metadata committing to a document that is just the label's text
+fn metadata(env: &Env, label: &str) -> Metadata {
+    Metadata {
+        content_hash: env.crypto().sha256(&Bytes::from_slice(env, label.as_bytes())),
+        uri: String::from_str(env, ""),
+        label: Symbol::new(env, label),
+    }
+}
+
+/// Carry a value over to another Env through its XDR form
+fn ported<T: IntoVal<Env, Val> + TryFromVal<Env, Val> + Clone>(from: &Env, to: &Env, value: &T) -> T {
+    let xdr = ScVal::try_from_val(from, &value.clone().into_val(from)).unwrap();
+    T::try_from_val(to, &Val::try_from_val(to, &xdr).unwrap()).unwrap()
+}
+
+/// Return the CPU instructions one vote costs against the current ledger, without casting it.
+/// The vote runs in a fresh Env loaded from a snapshot, so like a real transaction it only pays
+/// for the entries it touches, not for every entry the test has written so far
+fn measure_vote(env: &Env, contract_id: &Address, voter: &Address, proposal_id: &Symbol) -> u64 {
+    let fresh = Env::from_ledger_snapshot(env.to_ledger_snapshot());
+    fresh.mock_all_auths();
+    let contract_id = fresh.register_contract(&ported(env, &fresh, contract_id), Governance);
+    let client = GovernanceClient::new(&fresh, &contract_id);
+    let (voter, proposal_id): (Address, Symbol) = (ported(env, &fresh, voter), ported(env, &fresh, proposal_id));
+
+    fresh.budget().reset_unlimited();
+    client.vote(&voter, &proposal_id, &symbol_short!("yes"));
+    fresh.budget().cpu_instruction_cost()
+}
+
+/// Casts `count` votes and returns the cost of the 11th and the last one.
+fn vote_cost_spread(count: u32) -> (u64, u64) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, Governance);
+    let client = GovernanceClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let loan_pool = Address::generate(&env);
+
+    client.initialize(&admin, &oracle, &loan_pool, &86_400);
+
+    // Equity scores stay below the boost threshold so no audit records are appended
+    let mut voters = vec![&env];
+    for _ in 0..count {
+        let voter = Address::generate(&env);
+        env.budget().reset_unlimited();
+        client.update_voter_data(&voter, &1_000, &50);
+        voters.push_back(voter);
+    }
+
+    // Let the stake age past the eligibility window before the proposal snapshot
+    env.ledger().with_mut(|ledger| ledger.sequence_number += 17_280);
+
+    let proposer = Address::generate(&env);
+    let proposal_id = client.create_proposal(
+        &proposer,
+        &metadata(&env, "bench"),
+        &Symbol::new(&env, "rate_adjustment"),
+        &None,
+        &None,
+        &(7 * 86_400),
+    );
+
+    let mut first_cost = 0;
+    let mut last_cost = 0;
+    for (i, voter) in voters.iter().enumerate() {
+        if i == 10 {
+            first_cost = measure_vote(&env, &contract_id, &voter, &proposal_id);
+        } else if i as u32 == count - 1 {
+            last_cost = measure_vote(&env, &contract_id, &voter, &proposal_id);
+        }
+        env.budget().reset_unlimited();
+        client.vote(&voter, &proposal_id, &symbol_short!("yes"));
+    }
+
+    let proposal = client.get_proposal(&proposal_id);
+    assert_eq!(proposal.yes_votes, proposal.total_votes);
+    assert_eq!(proposal.total_votes, count as i128 * 1_000);
+    assert_eq!(client.get_stats().3, count as i32);
+
+    (first_cost, last_cost)
+}
+
+#[test]
+fn test_vote_cost_does_not_grow_with_a_few_dozen_voters() {
+    let (first_cost, last_cost) = vote_cost_spread(60);
+    assert!(last_cost <= first_cost * 5 / 4, "vote cost grew from {} to {}", first_cost, last_cost);
+}
+
+// Takes a couple of minutes; run with `cargo test -- --ignored`
+#[test]
+#[ignore]
+fn test_vote_cost_stays_flat_as_voters_grow() {
+    let (first_cost, last_cost) = vote_cost_spread(1_000);
+    // Only the host's ordered storage lookups grow, logarithmically
+    assert!(last_cost <= first_cost * 5 / 4, "vote cost grew from {} to {}", first_cost, last_cost);
+}
+
+#[test]
+fn test_fresh_stake_excluded_from_in_flight_vote() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, Governance);
+    let client = GovernanceClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let loan_pool = Address::generate(&env);
+    client.initialize(&admin, &oracle, &loan_pool, &86_400);
+
+    let veteran = Address::generate(&env);
+    let newcomer = Address::generate(&env);
+    client.update_voter_data(&veteran, &1_000, &50);
+    env.ledger().with_mut(|ledger| ledger.sequence_number += 17_280);
+
+    let proposal_id = client.create_proposal(
+        &Address::generate(&env),
+        &metadata(&env, "swing"),
+        &Symbol::new(&env, "rate_adjustment"),
+        &None,
+        &None,
+        &(7 * 86_400),
+    );
+
+    // Stake bought after the snapshot doesn't count, and topping up only counts the matured part
+    client.update_voter_data(&newcomer, &50_000, &50);
+    client.update_voter_data(&veteran, &5_000, &50);
+
+    assert_eq!(client.vote(&newcomer, &proposal_id, &symbol_short!("yes")), 0);
+    assert_eq!(client.vote(&veteran, &proposal_id, &symbol_short!("no")), 1_000);
+}
+
+#[test]
+fn test_delegated_power_counts_once_and_loops_are_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, Governance);
+    let client = GovernanceClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let loan_pool = Address::generate(&env);
+    client.initialize(&admin, &oracle, &loan_pool, &86_400);
+
+    let small = Address::generate(&env);
+    let delegate = Address::generate(&env);
+    client.update_voter_data(&small, &200, &50);
+    client.update_voter_data(&delegate, &1_000, &50);
+    env.ledger().with_mut(|ledger| ledger.sequence_number += 17_280);
+
+    client.delegate(&small, &delegate);
+    assert_eq!(client.get_delegation(&small), Some(delegate.clone()));
+    assert_eq!(
+        client.try_delegate(&delegate, &small),
+        Err(Ok(ContractError::CircularDelegation))
+    );
+
+    let proposal_id = client.create_proposal(
+        &Address::generate(&env),
+        &metadata(&env, "delegated"),
+        &Symbol::new(&env, "rate_adjustment"),
+        &None,
+        &None,
+        &(7 * 86_400),
+    );
+
+    assert_eq!(
+        client.try_vote(&small, &proposal_id, &symbol_short!("yes")),
+        Err(Ok(ContractError::Delegated))
+    );
+    assert_eq!(client.vote(&delegate, &proposal_id, &symbol_short!("yes")), 1_200);
+
+    // Taking the power back doesn't let it be cast twice on the same proposal
+    client.undelegate(&small);
+    assert_eq!(
+        client.try_vote(&small, &proposal_id, &symbol_short!("no")),
+        Err(Ok(ContractError::AlreadyVoted))
+    );
+}
+
+#[test]
+fn test_badges_issued_once_per_participant_after_finalization() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, Governance);
+    let client = GovernanceClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let loan_pool = Address::generate(&env);
+    client.initialize(&admin, &oracle, &loan_pool, &86_400);
+
+    let small = Address::generate(&env);
+    let delegate = Address::generate(&env);
+    let absent = Address::generate(&env);
+    client.update_voter_data(&small, &200, &50);
+    client.update_voter_data(&delegate, &1_000, &50);
+    env.ledger().with_mut(|ledger| ledger.sequence_number += 17_280);
+    client.delegate(&small, &delegate);
+
+    let proposal_id = client.create_proposal(
+        &Address::generate(&env),
+        &metadata(&env, "badges"),
+        &Symbol::new(&env, "rate_adjustment"),
+        &None,
+        &None,
+        &(7 * 86_400),
+    );
+    client.vote(&delegate, &proposal_id, &symbol_short!("yes"));
+
+    assert_eq!(
+        client.try_claim_badge(&delegate, &proposal_id),
+        Err(Ok(ContractError::InvalidStatus))
+    );
+
+    env.ledger().with_mut(|ledger| ledger.timestamp += 8 * 86_400);
+    let outcome = client.finalize_proposal(&proposal_id);
+
+    assert_eq!(client.issue_badges(&proposal_id, &0, &10), 1);
+    assert_eq!(client.issue_badges(&proposal_id, &0, &10), 0);
+    assert!(client.has_badge(&delegate, &proposal_id));
+    assert_eq!(client.get_badge(&delegate, &proposal_id).unwrap().outcome, outcome);
+
+    // Delegators claim their own badge, with the power credited to the delegate
+    let carried = client.claim_badge(&small, &proposal_id);
+    assert_eq!(carried.carried_by, Carrier::Delegate(delegate.clone()));
+    assert_eq!(carried.total_power, 0);
+    assert_eq!(client.get_badge_count(&small), 1);
+    let badges = client.get_badges(&small, &PageRequest::first(10));
+    assert_eq!(badges.items, vec![&env, carried]);
+    assert_eq!(badges.next_cursor, None);
+
+    assert_eq!(
+        client.try_claim_badge(&absent, &proposal_id),
+        Err(Ok(ContractError::NotFound))
+    );
+    assert_eq!(client.get_badges(&absent, &PageRequest::first(10)).items.len(), 0);
+}
+
+#[test]
+fn test_same_ledger_proposals_get_distinct_ids() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, Governance);
+    let client = GovernanceClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let loan_pool = Address::generate(&env);
+    client.initialize(&admin, &oracle, &loan_pool, &86_400);
+
+    // Same proposer, same title, same ledger timestamp
+    let proposer = Address::generate(&env);
+    let mut ids = vec![&env];
+    for _ in 0..3 {
+        let proposal_id = client.create_proposal(
+            &proposer,
+            &metadata(&env, "repeat"),
+            &Symbol::new(&env, "rate_adjustment"),
+            &None,
+            &None,
+            &(7 * 86_400),
+        );
+        assert!(!ids.contains(&proposal_id));
+        ids.push_back(proposal_id);
+    }
+
+    for proposal_id in ids.iter() {
+        assert_eq!(client.get_proposal(&proposal_id).proposer, proposer);
+    }
+}
+
+#[test]
+fn test_initialize_cannot_wipe_state() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, Governance);
+    let client = GovernanceClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let loan_pool = Address::generate(&env);
+    client.initialize(&admin, &oracle, &loan_pool, &86_400);
+    assert_eq!(client.get_version(), client.version());
+
+    let proposal_id = client.create_proposal(
+        &Address::generate(&env),
+        &metadata(&env, "keep"),
+        &Symbol::new(&env, "rate_adjustment"),
+        &None,
+        &None,
+        &(7 * 86_400),
+    );
+
+    let attacker = Address::generate(&env);
+    assert_eq!(
+        client.try_initialize(&attacker, &attacker, &loan_pool, &86_400),
+        Err(Ok(ContractError::AlreadyInitialized))
+    );
+    assert!(client.try_get_proposal(&proposal_id).is_ok());
+}
+
+#[test]
+fn test_changes_since_returns_latest_modification_once() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, Governance);
+    let client = GovernanceClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let loan_pool = Address::generate(&env);
+    client.initialize(&admin, &oracle, &loan_pool, &86_400);
+    let kind = Symbol::new(&env, "proposal");
+
+    let proposer = Address::generate(&env);
+    let create = |label: &str| {
+        client.create_proposal(&proposer, &metadata(&env, label), &Symbol::new(&env, "rate_adjustment"), &None, &None, &(7 * 86_400))
+    };
+    let first = create("first");
+    let start = env.ledger().sequence();
+
+    env.ledger().with_mut(|ledger| ledger.sequence_number += 1);
+    let second = create("second");
+
+    // The first proposal is modified again later and only its latest change is reported
+    env.ledger().with_mut(|ledger| ledger.sequence_number += 1);
+    client.update_voter_data(&proposer, &1_000, &50);
+    client.vote(&proposer, &first, &symbol_short!("yes"));
+
+    let change = |id: &Symbol, ledger: u32| RecordChange { id: id.clone(), ledger };
+    let changes = client.get_changes_since(&start, &kind, &PageRequest::first(10));
+    assert_eq!(changes.items, vec![&env, change(&second, start + 1), change(&first, start + 2)]);
+    assert_eq!(changes.next_cursor, None);
+    assert_eq!(client.get_changes_since(&(start + 1), &kind, &PageRequest::first(10)).items, vec![&env, change(&first, start + 2)]);
+
+    // A short page hands back the cursor of the next journal entry
+    let page = client.get_changes_since(&start, &kind, &PageRequest::first(1));
+    assert_eq!(page.items, vec![&env, change(&second, start + 1)]);
+    let rest = client.get_changes_since(&start, &kind, &PageRequest { cursor: page.next_cursor.unwrap(), limit: 1 });
+    assert_eq!(rest.items, vec![&env, change(&first, start + 2)]);
+    assert_eq!(rest.next_cursor, None);
+}
+
+#[test]
+fn test_rebalance_proposal_rejects_bad_allocations() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, Governance);
+    let client = GovernanceClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let loan_pool = Address::generate(&env);
+    client.initialize(&admin, &oracle, &loan_pool, &86_400);
+
+    let proposer = Address::generate(&env);
+    let weight = |asset: &str, weight_bps: u32| AllocationWeight { asset_id: Symbol::new(&env, asset), weight_bps };
+    let propose = |targets| client.try_create_rebalance_proposal(&proposer, &metadata(&env, "rebal"), &targets, &(7 * 86_400));
+
+    // Over-allocated, duplicated and empty vectors never reach a vote
+    assert_eq!(propose(vec![&env, weight("bus1", 6_000), weight("bike1", 5_000)]), Err(Ok(ContractError::InvalidInput)));
+    assert_eq!(propose(vec![&env, weight("bus1", 3_000), weight("bus1", 3_000)]), Err(Ok(ContractError::InvalidInput)));
+    assert_eq!(propose(vec![&env]), Err(Ok(ContractError::InvalidInput)));
+
+    let proposal_id = propose(vec![&env, weight("bus1", 6_000), weight("bike1", 4_000)]).unwrap().unwrap();
+    let proposal = client.get_proposal(&proposal_id);
+    assert_eq!(proposal.proposal_type, Symbol::new(&env, "rebalance"));
+    assert_eq!(proposal.rebalance_targets.len(), 2);
+}
+
+#[test]
+fn test_voting_modes_follow_their_power_curves() {
+    for (stake, root) in [(0, 0), (1, 1), (99, 9), (100, 10), (10_000, 100), (1_000_000, 1_000), (1_000_001, 1_000)] {
+        assert_eq!(isqrt(stake), root);
+    }
+    assert_eq!(isqrt(-4), 0);
+
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, Governance);
+    let client = GovernanceClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let loan_pool = Address::generate(&env);
+    client.initialize(&admin, &oracle, &loan_pool, &86_400);
+
+    let whale = Address::generate(&env);
+    let small = Address::generate(&env);
+    client.update_voter_data(&whale, &1_000_000, &50);
+    client.update_voter_data(&small, &100, &50);
+    env.ledger().with_mut(|ledger| ledger.sequence_number += 17_280);
+
+    let propose = |mode: VotingMode| {
+        client.create_proposal_with_mode(
+            &Address::generate(&env),
+            &metadata(&env, "curve"),
+            &Symbol::new(&env, "rate_adjustment"),
+            &None,
+            &None,
+            &(7 * 86_400),
+            &mode,
+        )
+    };
+
+    // The whale outweighs the small holder 10000:1 linearly, 100:1 quadratically and 1:1 per person
+    for (mode, whale_power, small_power) in [
+        (VotingMode::Linear, 1_000_000, 100),
+        (VotingMode::Quadratic, 1_000, 10),
+        (VotingMode::OnePersonOneVote, 1, 1),
+    ] {
+        let proposal_id = propose(mode);
+        assert_eq!(client.get_proposal(&proposal_id).voting_mode, mode);
+        assert_eq!(client.vote(&whale, &proposal_id, &symbol_short!("yes")), whale_power);
+        assert_eq!(client.vote(&small, &proposal_id, &symbol_short!("no")), small_power);
+    }
+}
+
+#[test]
+fn test_sandbox_helpers_only_run_on_sandbox_instances() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|ledger| ledger.timestamp = 1_700_000_000);
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let loan_pool = Address::generate(&env);
+    let voter = Address::generate(&env);
+
+    // A production instance keeps its timelock and refuses the helpers
+    let production_id = env.register_contract(None, Governance);
+    let production = GovernanceClient::new(&env, &production_id);
+    production.initialize(&admin, &oracle, &loan_pool, &86_400);
+    assert!(!production.is_sandbox());
+    assert_eq!(production.try_sandbox_mint(&admin, &voter, &1_000), Err(Ok(ContractError::Unauthorized)));
+    assert_eq!(production.try_sandbox_advance_epochs(&admin, &1), Err(Ok(ContractError::Unauthorized)));
+    assert_eq!(
+        production.try_queue_admin_change(&admin, &symbol_short!("quorum"), &20, &None, &0),
+        Err(Ok(ContractError::InvalidInput))
+    );
+
+    let sandbox_id = env.register_contract(None, Governance);
+    let sandbox = GovernanceClient::new(&env, &sandbox_id);
+    sandbox.initialize_sandbox(&admin, &oracle, &loan_pool, &60);
+    assert!(sandbox.is_sandbox());
+    assert_eq!(sandbox.try_initialize(&admin, &oracle, &loan_pool, &60), Err(Ok(ContractError::AlreadyInitialized)));
+
+    // Test stake is minted by an account granted the treasurer role
+    let treasurer = Address::generate(&env);
+    sandbox.grant_role(&admin, &Role::Treasurer, &treasurer);
+    assert_eq!(sandbox.sandbox_mint(&treasurer, &voter, &1_000), 1_000);
+    assert_eq!(sandbox.sandbox_mint(&treasurer, &voter, &500), 1_500);
+    assert_eq!(sandbox.try_sandbox_mint(&voter, &voter, &500), Err(Ok(ContractError::Unauthorized)));
+    assert_eq!(sandbox.get_voter_data(&voter).stake_amount, 1_500);
+    assert_eq!(sandbox.sandbox_advance_epochs(&admin, &3), 3);
+
+    // Timelocked changes can be applied straight away
+    let executor = Address::generate(&env);
+    sandbox.grant_role(&admin, &Role::Executor, &executor);
+    let change_id = sandbox.queue_admin_change(&admin, &symbol_short!("quorum"), &20, &None, &0);
+    sandbox.execute_admin_change(&executor, &change_id);
+}
+
+#[test]
+fn test_proposals_votes_and_voter_updates_carry_the_actors_signature() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, Governance);
+    let client = GovernanceClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let loan_pool = Address::generate(&env);
+    client.initialize(&admin, &oracle, &loan_pool, &86_400);
+    assert_eq!(env.auths().first().unwrap().0, admin);
+
+    // Voter data is signed by the oracle, not the contract's own address
+    let voter = Address::generate(&env);
+    client.update_voter_data(&voter, &1_000, &50);
+    assert_eq!(env.auths().first().unwrap().0, oracle);
+
+    let proposer = Address::generate(&env);
+    let proposal_id = client.create_proposal(
+        &proposer,
+        &metadata(&env, "signed"),
+        &Symbol::new(&env, "rate_adjustment"),
+        &None,
+        &None,
+        &(7 * 86_400),
+    );
+    assert_eq!(env.auths().first().unwrap().0, proposer);
+
+    // Only the voter's own signature is accepted for their vote
+    let vote = MockAuthInvoke {
+        contract: &contract_id,
+        fn_name: "vote",
+        args: (&voter, &proposal_id, symbol_short!("yes")).into_val(&env),
+        sub_invokes: &[],
+    };
+    client.mock_auths(&[MockAuth { address: &voter, invoke: &vote }]).vote(&voter, &proposal_id, &symbol_short!("yes"));
+    assert_eq!(env.auths().len(), 1);
+    assert_eq!(env.auths().first().unwrap().0, voter);
+}
+
+#[test]
+fn test_sponsors_cannot_vote_or_propose_for_others() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, Governance);
+    let client = GovernanceClient::new(&env, &contract_id);
+    client.initialize(&Address::generate(&env), &Address::generate(&env), &Address::generate(&env), &86_400);
+    let voter = Address::generate(&env);
+    let sponsor = Address::generate(&env);
+    client.update_voter_data(&voter, &1_000, &50);
+
+    // Creating a proposal directly asks for the proposer's signature and no one else's
+    let duration = 7 * 86_400u64;
+    let create = MockAuthInvoke {
+        contract: &contract_id,
+        fn_name: "create_proposal",
+        args: (&voter, metadata(&env, "direct"), Symbol::new(&env, "rate_adjustment"), None::<Symbol>, None::<i128>, duration).into_val(&env),
+        sub_invokes: &[],
+    };
+    let proposal_id = client
+        .mock_auths(&[MockAuth { address: &voter, invoke: &create }])
+        .create_proposal(&voter, &metadata(&env, "direct"), &Symbol::new(&env, "rate_adjustment"), &None, &None, &duration);
+    assert_eq!(env.auths().len(), 1);
+    assert_eq!(env.auths().first().unwrap().0, voter);
+
+    // A sponsor's own signature doesn't stand in for the voter's: without a registered key, the
+    // relayed vote is refused and nothing is counted
+    env.mock_all_auths();
+    let signature = BytesN::from_array(&env, &[0; 64]);
+    assert_eq!(
+        client.try_relay_vote(&sponsor, &voter, &proposal_id, &symbol_short!("yes"), &0, &signature),
+        Err(Ok(ContractError::NotFound))
+    );
+    assert_eq!(client.get_proposal(&proposal_id).total_votes, 0);
+    assert_eq!(client.get_relay_nonce(&voter), 0);
+}
+
+#[test]
+fn test_treasury_holds_real_funds_and_funding_proposals_bond() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, Governance);
+    let client = GovernanceClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let pool_id = env.register_contract(None, StubLoanPool);
+    let token_id = env.register_stellar_asset_contract(Address::generate(&env));
+    let token = TokenClient::new(&env, &token_id);
+    StubLoanPoolClient::new(&env, &pool_id).set_settlement_asset(&token_id);
+    client.initialize(&admin, &Address::generate(&env), &pool_id, &86_400);
+
+    let voter = Address::generate(&env);
+    let proposer = Address::generate(&env);
+    StellarAssetClient::new(&env, &token_id).mint(&admin, &10_000);
+    StellarAssetClient::new(&env, &token_id).mint(&proposer, &1_000);
+    client.update_voter_data(&voter, &1_000, &50);
+    env.ledger().with_mut(|ledger| ledger.sequence_number += 17_280);
+
+    // Deposits move tokens into the treasury
+    client.deposit_treasury(&admin, &10_000);
+    assert_eq!(token.balance(&contract_id), 10_000);
+    assert_eq!(client.get_treasury(), (10_000, 0));
+
+    // A funding proposal escrows its request and bonds 1% of it
+    let propose = |label: &str, amount: i128| {
+        client.try_create_proposal(
+            &proposer,
+            &metadata(&env, label),
+            &Symbol::new(&env, "asset_funding"),
+            &Some(symbol_short!("bus_001")),
+            &Some(amount),
+            &(7 * 86_400),
+        )
+    };
+    let funded = propose("buses", 6_000).unwrap().unwrap();
+    assert_eq!(token.balance(&proposer), 940);
+    assert_eq!(client.get_proposal_escrow(&funded), 6_000);
+    assert_eq!(propose("more_buses", 5_000), Err(Ok(ContractError::NoFunds)));
+    let ignored = propose("vans", 4_000).unwrap().unwrap();
+    assert_eq!(token.balance(&proposer), 900);
+
+    // A proposal that reaches quorum returns its bond; one that misses it forfeits the bond
+    client.vote(&voter, &funded, &symbol_short!("yes"));
+    env.ledger().with_mut(|ledger| ledger.timestamp += 7 * 86_400 + 1);
+    assert_eq!(client.finalize_proposal(&funded), symbol_short!("passed"));
+    assert_eq!(token.balance(&proposer), 960);
+    assert_eq!(client.finalize_proposal(&ignored), symbol_short!("failed"));
+    assert_eq!(token.balance(&proposer), 960);
+    assert_eq!(client.get_treasury(), (10_040, 6_000));
+
+    // Executing spends the escrow as the treasury's investment in the asset
+    client.execute_proposal(&admin, &funded);
+    assert_eq!(token.balance(&pool_id), 6_000);
+    assert_eq!(token.balance(&contract_id), 4_040);
+    assert_eq!(client.get_treasury(), (4_040, 0));
+}
+
+#[test]
+fn test_execution_receipt_names_the_executor() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, Governance);
+    let client = GovernanceClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &Address::generate(&env), &Address::generate(&env), &86_400);
+
+    let voter = Address::generate(&env);
+    client.update_voter_data(&voter, &1_000, &50);
+    env.ledger().with_mut(|ledger| ledger.sequence_number += 17_280);
+    let proposal_id = client.create_proposal(&voter, &metadata(&env, "rates"), &Symbol::new(&env, "rate_adjustment"), &None, &None, &(7 * 86_400));
+    client.vote(&voter, &proposal_id, &symbol_short!("yes"));
+    env.ledger().with_mut(|ledger| ledger.timestamp += 7 * 86_400 + 1);
+    assert_eq!(client.finalize_proposal(&proposal_id), symbol_short!("passed"));
+
+    // The receipt records the account that executed, not the admin
+    let executor = Address::generate(&env);
+    client.grant_role(&admin, &Role::Executor, &executor);
+    client.execute_proposal(&executor, &proposal_id);
+    let receipt = client.get_execution_receipt(&proposal_id);
+    assert_eq!(receipt.executor, executor);
+    assert!(receipt.succeeded);
+    assert_eq!(client.get_proposal(&proposal_id).status, symbol_short!("executed"));
+}
+
+#[test]
+fn test_partially_failed_execution_resumes_without_repeating_actions() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, Governance);
+    let client = GovernanceClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &Address::generate(&env), &Address::generate(&env), &86_400);
+    let adjuster_id = env.register_contract(None, StubRateAdjuster);
+    let adjuster = StubRateAdjusterClient::new(&env, &adjuster_id);
+    let change_id = client.queue_admin_change(&admin, &symbol_short!("rate_adj"), &0, &Some(adjuster_id.clone()), &86_400);
+    env.ledger().with_mut(|ledger| ledger.timestamp += 86_400);
+    client.execute_admin_change(&admin, &change_id);
+
+    let voter = Address::generate(&env);
+    client.update_voter_data(&voter, &1_000, &50);
+    env.ledger().with_mut(|ledger| ledger.sequence_number += 17_280);
+    let rate_call = |rate: i32| ProposalCall {
+        target: adjuster_id.clone(),
+        function: Symbol::new(&env, "set_base_rate"),
+        args: vec![&env, rate.into_val(&env)],
+    };
+    let proposal_id = client.create_executable_proposal(
+        &voter,
+        &metadata(&env, "rates"),
+        &Symbol::new(&env, "rate_adjustment"),
+        &None,
+        &None,
+        &(7 * 86_400),
+        &vec![&env, rate_call(6), rate_call(9)],
+    );
+    client.vote(&voter, &proposal_id, &symbol_short!("yes"));
+    env.ledger().with_mut(|ledger| ledger.timestamp += 7 * 86_400 + 1);
+    client.finalize_proposal(&proposal_id);
+
+    // The second call fails, so the proposal stays passed with the first already applied
+    adjuster.allow(&6);
+    client.execute_proposal(&admin, &proposal_id);
+    let receipt = client.get_execution_receipt(&proposal_id);
+    assert!(!receipt.succeeded);
+    assert!(receipt.actions.get(0).unwrap().success);
+    assert!(!receipt.actions.get(1).unwrap().success);
+    assert_eq!(client.get_proposal(&proposal_id).status, symbol_short!("passed"));
+    assert_eq!(adjuster.applied(&6), 1);
+
+    // A retry only makes the call that failed
+    adjuster.allow(&9);
+    client.execute_proposal(&admin, &proposal_id);
+    assert!(client.get_execution_receipt(&proposal_id).succeeded);
+    assert_eq!(client.get_proposal(&proposal_id).status, symbol_short!("executed"));
+    assert_eq!(adjuster.applied(&6), 1);
+    assert_eq!(adjuster.applied(&9), 1);
+}
+
+#[test]
+fn test_passed_policy_referendum_binds_the_rate_adjusters_bands() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, Governance);
+    let client = GovernanceClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &Address::generate(&env), &Address::generate(&env), &86_400);
+    let adjuster_id = env.register_contract(None, StubRateAdjuster);
+    let adjuster = StubRateAdjusterClient::new(&env, &adjuster_id);
+    let change_id = client.queue_admin_change(&admin, &symbol_short!("rate_adj"), &0, &Some(adjuster_id.clone()), &86_400);
+    env.ledger().with_mut(|ledger| ledger.timestamp += 86_400);
+    client.execute_admin_change(&admin, &change_id);
+
+    let voter = Address::generate(&env);
+    client.update_voter_data(&voter, &1_000, &50);
+    env.ledger().with_mut(|ledger| ledger.sequence_number += 17_280);
+
+    let inverted = PolicyBand { parameter: symbol_short!("base_rate"), min: 10, max: 4 };
+    assert_eq!(
+        client.try_create_policy_proposal(&voter, &metadata(&env, "band"), &inverted, &(7 * 86_400)),
+        Err(Ok(ContractError::InvalidInput))
+    );
+
+    let band = PolicyBand { parameter: symbol_short!("base_rate"), min: 4, max: 10 };
+    let proposal_id = client.create_policy_proposal(&voter, &metadata(&env, "band"), &band, &(7 * 86_400));
+    assert_eq!(client.get_proposal(&proposal_id).policy_bands, vec![&env, band]);
+    client.vote(&voter, &proposal_id, &symbol_short!("yes"));
+    env.ledger().with_mut(|ledger| ledger.timestamp += 7 * 86_400 + 1);
+    client.finalize_proposal(&proposal_id);
+
+    // Nothing binds until the passed referendum is executed
+    assert_eq!(adjuster.band(&symbol_short!("base_rate")), None);
+    client.execute_proposal(&admin, &proposal_id);
+    assert_eq!(adjuster.band(&symbol_short!("base_rate")), Some((4, 10)));
+    assert_eq!(client.get_proposal(&proposal_id).status, symbol_short!("executed"));
+}
+
+#[test]
+fn test_fee_schedule_slides_with_the_zone_equity_score() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, Governance);
+    let client = GovernanceClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &Address::generate(&env), &Address::generate(&env), &86_400);
+
+    let tier = |min_equity_score: i32, fee_bps: u32| FeeTier { min_equity_score, fee_bps };
+    let schedule = vec![&env, tier(0, 200), tier(50, 100), tier(80, 0)];
+    client.set_fee_schedule(&admin, &schedule);
+    assert_eq!(client.get_fee_schedule(), schedule);
+
+    // Each score pays the rate of the highest tier it reaches
+    assert_eq!(client.get_fee_bps(&30), 200);
+    assert_eq!(client.get_fee_bps(&50), 100);
+    assert_eq!(client.get_fee_bps(&79), 100);
+    assert_eq!(client.get_fee_bps(&95), 0);
+
+    for invalid in [
+        vec![&env, tier(10, 200)],
+        vec![&env, tier(0, 200), tier(80, 0), tier(50, 100)],
+        vec![&env, tier(0, 200), tier(101, 0)],
+        vec![&env, tier(0, MAX_FEE_BPS + 1)],
+    ] {
+        assert_eq!(client.try_set_fee_schedule(&admin, &invalid), Err(Ok(ContractError::InvalidInput)));
+    }
+    assert_eq!(client.try_set_fee_schedule(&Address::generate(&env), &schedule), Err(Ok(ContractError::Unauthorized)));
+    assert_eq!(client.get_fee_schedule(), schedule);
+}
+
+#[test]
+fn test_veto_records_its_reason_and_keeps_the_bond() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, Governance);
+    let client = GovernanceClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let pool_id = env.register_contract(None, StubLoanPool);
+    let token_id = env.register_stellar_asset_contract(Address::generate(&env));
+    let token = TokenClient::new(&env, &token_id);
+    StubLoanPoolClient::new(&env, &pool_id).set_settlement_asset(&token_id);
+    client.initialize(&admin, &Address::generate(&env), &pool_id, &86_400);
+
+    let proposer = Address::generate(&env);
+    StellarAssetClient::new(&env, &token_id).mint(&admin, &10_000);
+    StellarAssetClient::new(&env, &token_id).mint(&proposer, &1_000);
+    client.deposit_treasury(&admin, &10_000);
+    let proposal_id = client.create_proposal(
+        &proposer,
+        &metadata(&env, "buses"),
+        &Symbol::new(&env, "asset_funding"),
+        &Some(symbol_short!("bus_001")),
+        &Some(6_000),
+        &(7 * 86_400),
+    );
+    assert_eq!(client.get_treasury(), (10_000, 6_000));
+
+    let evidence = BytesN::from_array(&env, &[9; 32]);
+    assert_eq!(
+        client.try_veto_proposal(&proposer, &proposal_id, &RejectionReason::SuspectedFraud, &Some(evidence.clone())),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    assert_eq!(client.get_veto(&proposal_id), None);
+    client.veto_proposal(&admin, &proposal_id, &RejectionReason::SuspectedFraud, &Some(evidence.clone()));
+
+    let veto = client.get_veto(&proposal_id).unwrap();
+    assert_eq!(veto.reason, RejectionReason::SuspectedFraud);
+    assert_eq!(veto.evidence, vec![&env, evidence.clone()]);
+    assert_eq!(veto.timestamp, env.ledger().timestamp());
+    let (_, topics, payload) = env.events().all().last().unwrap();
+    assert_eq!(topics, (symbol_short!("vetoed"), proposal_id.clone()).into_val(&env));
+    let payload: (RejectionReason, Option<BytesN<32>>) = payload.into_val(&env);
+    assert_eq!(payload, (RejectionReason::SuspectedFraud, Some(evidence)));
+
+    // The escrow is released back to the treasury, which also keeps the proposer's bond
+    assert_eq!(client.get_proposal(&proposal_id).status, symbol_short!("vetoed"));
+    assert_eq!(token.balance(&proposer), 940);
+    assert_eq!(client.get_treasury(), (10_060, 0));
+    assert_eq!(token.balance(&contract_id), 10_060);
+    assert_eq!(
+        client.try_veto_proposal(&admin, &proposal_id, &RejectionReason::Duplicate, &None),
+        Err(Ok(ContractError::InvalidStatus))
+    );
+}
+
+#[test]
+fn test_votes_are_looked_up_per_proposal_and_voter() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, Governance);
+    let client = GovernanceClient::new(&env, &contract_id);
+    client.initialize(&Address::generate(&env), &Address::generate(&env), &Address::generate(&env), &86_400);
+
+    let (voter, other) = (Address::generate(&env), Address::generate(&env));
+    client.update_voter_data(&voter, &1_000, &50);
+    client.update_voter_data(&other, &1_000, &50);
+    env.ledger().with_mut(|ledger| ledger.sequence_number += 17_280);
+    let propose = |label: &str| {
+        client.create_proposal(
+            &Address::generate(&env),
+            &metadata(&env, label),
+            &Symbol::new(&env, "rate_adjustment"),
+            &None,
+            &None,
+            &(7 * 86_400),
+        )
+    };
+    let (first, second) = (propose("first"), propose("second"));
+
+    assert_eq!(client.get_vote(&first, &voter), None);
+    let power = client.vote(&voter, &first, &symbol_short!("no"));
+    client.vote(&other, &second, &symbol_short!("yes"));
+
+    let vote = client.get_vote(&first, &voter).unwrap();
+    assert_eq!((vote.voter, vote.proposal_id, vote.vote), (voter.clone(), first.clone(), symbol_short!("no")));
+    assert_eq!(vote.total_power, power);
+    assert_eq!(vote.timestamp, env.ledger().timestamp());
+    assert_eq!(client.get_vote(&second, &voter), None);
+    assert_eq!(client.get_vote(&first, &other), None);
+
+    assert_eq!(client.try_vote(&voter, &first, &symbol_short!("yes")), Err(Ok(ContractError::AlreadyVoted)));
+    assert_eq!(client.get_vote(&first, &voter).unwrap().vote, symbol_short!("no"));
+    client.vote(&voter, &second, &symbol_short!("yes"));
+    assert_eq!(client.get_proposal(&second).total_votes, 2 * power);
+}
+
+#[test]
+fn test_epoch_schedule_drives_budget_cycles_and_year_boundaries() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, Governance);
+    let client = GovernanceClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &Address::generate(&env), &Address::generate(&env), &86_400);
+    assert_eq!(client.get_epoch_schedule(), EpochSchedule::default_schedule());
+
+    // Ten-day epochs, three to a cycle, an hour east of UTC
+    let schedule = EpochSchedule { start: 1_000, length: 10 * 86_400, epochs_per_cycle: 3, tz_offset: 3_600 };
+    assert_eq!(
+        client.try_set_epoch_schedule(&admin, &EpochSchedule { length: 0, ..schedule.clone() }),
+        Err(Ok(ContractError::InvalidInput))
+    );
+    assert_eq!(
+        client.try_set_epoch_schedule(&admin, &EpochSchedule { epochs_per_cycle: 0, ..schedule.clone() }),
+        Err(Ok(ContractError::InvalidInput))
+    );
+    assert_eq!(
+        client.try_set_epoch_schedule(&Address::generate(&env), &schedule),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    client.set_epoch_schedule(&admin, &schedule);
+    assert_eq!(client.get_epoch_schedule(), schedule);
+
+    env.ledger().with_mut(|ledger| ledger.timestamp = schedule.epoch_start(3) - 1);
+    assert_eq!(client.get_current_cycle(), 0);
+    env.ledger().with_mut(|ledger| ledger.timestamp = schedule.epoch_start(3));
+    assert_eq!(client.get_current_cycle(), 1);
+    assert_eq!(schedule.epoch_at(env.ledger().timestamp()), 3);
+
+    // Jan 1 2024 starts at local midnight, an hour before UTC midnight
+    assert_eq!(schedule.year_start(2024), 1_704_067_200 - 3_600);
+    let (first, end) = schedule.epochs_in_year(2024);
+    assert!(schedule.epoch_start(first) >= schedule.year_start(2024));
+    assert!(schedule.epoch_start(first - 1) < schedule.year_start(2024));
+    assert!(schedule.epoch_start(end) >= schedule.year_start(2025));
+}
+
+#[test]
+fn test_profiles_keep_only_verified_roles_their_owner_still_claims() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, Governance);
+    let client = GovernanceClient::new(&env, &contract_id);
+    client.initialize(&Address::generate(&env), &Address::generate(&env), &Address::generate(&env), &86_400);
+
+    let owner = Address::generate(&env);
+    let name_hash = BytesN::from_array(&env, &[3; 32]);
+    assert_eq!(client.get_profile(&owner), None);
+    assert_eq!(client.try_set_profile(&owner, &name_hash, &None, &8), Err(Ok(ContractError::InvalidInput)));
+    assert_eq!(client.try_verify_profile_roles(&owner, &ROLE_INVESTOR), Err(Ok(ContractError::NotFound)));
+
+    let zone = symbol_short!("downtown");
+    client.set_profile(&owner, &name_hash, &Some(zone.clone()), &(ROLE_BORROWER | ROLE_INVESTOR));
+    let profile = client.get_profile(&owner).unwrap();
+    assert_eq!((profile.name_hash, profile.preferred_zone), (name_hash.clone(), zone));
+    assert_eq!((profile.role_flags, profile.verified_roles), (ROLE_BORROWER | ROLE_INVESTOR, 0));
+
+    // Only claimed roles can be confirmed
+    assert_eq!(client.verify_profile_roles(&owner, &(ROLE_INVESTOR | ROLE_OPERATOR)), ROLE_INVESTOR);
+
+    // Dropping a claim drops its verification; claiming it again doesn't restore it
+    client.set_profile(&owner, &name_hash, &None, &ROLE_BORROWER);
+    let profile = client.get_profile(&owner).unwrap();
+    assert_eq!(profile.preferred_zone, Symbol::new(&env, ""));
+    assert_eq!(profile.verified_roles, 0);
+    client.set_profile(&owner, &name_hash, &None, &(ROLE_BORROWER | ROLE_INVESTOR));
+    assert_eq!(client.get_profile(&owner).unwrap().verified_roles, 0);
+
+    client.clear_profile(&owner);
+    assert_eq!(client.get_profile(&owner), None);
+    assert_eq!(client.try_clear_profile(&owner), Err(Ok(ContractError::NotFound)));
+}
+
+#[test]
+fn test_low_stake_voters_claim_gas_rebates_for_finalized_votes() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, Governance);
+    let client = GovernanceClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let pool_id = env.register_contract(None, StubLoanPool);
+    let token_id = env.register_stellar_asset_contract(Address::generate(&env));
+    let token = TokenClient::new(&env, &token_id);
+    StubLoanPoolClient::new(&env, &pool_id).set_settlement_asset(&token_id);
+    client.initialize(&admin, &Address::generate(&env), &pool_id, &86_400);
+    StellarAssetClient::new(&env, &token_id).mint(&admin, &1_000_000);
+    client.deposit_treasury(&admin, &1_000_000);
+
+    // Rebates are 100_000 a vote for voters staking under 1_000, from a 150_000 pool
+    assert_eq!(client.try_fund_gas_rebates(&admin, &0, &2_000_000), Err(Ok(ContractError::NoFunds)));
+    assert_eq!(client.fund_gas_rebates(&admin, &0, &150_000), 150_000);
+    assert_eq!(client.get_treasury(), (850_000, 0));
+
+    let (small, large) = (Address::generate(&env), Address::generate(&env));
+    client.update_voter_data(&small, &500, &50);
+    client.update_voter_data(&large, &5_000, &50);
+    env.ledger().with_mut(|ledger| ledger.sequence_number += 17_280);
+    let propose = |label: &str, duration: u64| {
+        client.create_proposal(
+            &Address::generate(&env),
+            &metadata(&env, label),
+            &Symbol::new(&env, "rate_adjustment"),
+            &None,
+            &None,
+            &duration,
+        )
+    };
+    let (short, long) = (propose("short", 7 * 86_400), propose("long", 60 * 86_400));
+    for proposal_id in [&short, &long] {
+        client.vote(&small, proposal_id, &symbol_short!("yes"));
+        client.vote(&large, proposal_id, &symbol_short!("yes"));
+    }
+
+    // Claims open once the epoch is over, and only for finalized proposals
+    assert_eq!(client.try_claim_gas_rebate(&small, &0), Err(Ok(ContractError::TooEarly)));
+    env.ledger().with_mut(|ledger| ledger.timestamp = EpochSchedule::default_schedule().epoch_end(0));
+    assert_eq!(client.try_claim_gas_rebate(&small, &0), Err(Ok(ContractError::Nothing)));
+    client.finalize_proposal(&short);
+    assert_eq!(client.claim_gas_rebate(&small, &0), 100_000);
+    assert_eq!(token.balance(&small), 100_000);
+    assert_eq!(client.try_claim_gas_rebate(&small, &0), Err(Ok(ContractError::Nothing)));
+    assert_eq!(client.try_claim_gas_rebate(&large, &0), Err(Ok(ContractError::Nothing)));
+
+    // The vote left open stays claimable, paid from whatever the pool has left
+    env.ledger().with_mut(|ledger| ledger.timestamp = 60 * 86_400 + 1);
+    client.finalize_proposal(&long);
+    assert_eq!(client.claim_gas_rebate(&small, &0), 50_000);
+    assert_eq!(token.balance(&small), 150_000);
+    assert_eq!(client.get_gas_rebate_pool(&0), 0);
+    assert_eq!(token.balance(&contract_id), 850_000);
+    assert_eq!(client.try_claim_gas_rebate(&small, &0), Err(Ok(ContractError::Nothing)));
+}
+
+#[test]
+fn test_large_treasury_spends_need_fresh_approvals() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, Governance);
+    let client = GovernanceClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let pool_id = env.register_contract(None, StubLoanPool);
+    let token_id = env.register_stellar_asset_contract(Address::generate(&env));
+    StubLoanPoolClient::new(&env, &pool_id).set_settlement_asset(&token_id);
+    client.initialize(&admin, &Address::generate(&env), &pool_id, &86_400);
+    StellarAssetClient::new(&env, &token_id).mint(&admin, &1_000_000);
+    client.deposit_treasury(&admin, &1_000_000);
+
+    let (first, second, outsider) = (Address::generate(&env), Address::generate(&env), Address::generate(&env));
+    let invalid = ApprovalRule { threshold: 100_000, required: 3, approvers: vec![&env, first.clone(), second.clone()] };
+    assert_eq!(client.try_set_approval_rule(&admin, &invalid), Err(Ok(ContractError::InvalidInput)));
+    client.set_approval_rule(&admin, &ApprovalRule { required: 2, ..invalid });
+
+    // Small spends go through; large ones need two approvals for that exact spend
+    assert_eq!(client.fund_gas_rebates(&admin, &0, &50_000), 50_000);
+    assert_eq!(client.try_fund_gas_rebates(&admin, &0, &150_000), Err(Ok(ContractError::NeedsApproval)));
+    let spend = symbol_short!("spend");
+    let gas = symbol_short!("gas_rbt");
+    assert_eq!(client.try_propose_action(&first, &symbol_short!("withdraw"), &gas, &None, &150_000), Err(Ok(ContractError::InvalidInput)));
+    let stale = client.propose_action(&first, &spend, &gas, &None, &150_000);
+
+    // Approvals lapse after three days
+    env.ledger().with_mut(|ledger| ledger.timestamp += mobility_types::approvals::APPROVAL_TTL);
+    assert_eq!(client.try_approve_action(&second, &stale), Err(Ok(ContractError::Expired)));
+    assert_eq!(client.prune_expired_actions(), 1);
+    assert_eq!(client.try_get_pending_action(&stale), Err(Ok(ContractError::NotFound)));
+
+    let action_id = client.propose_action(&first, &spend, &gas, &None, &100_001);
+    assert_eq!(client.try_approve_action(&outsider, &action_id), Err(Ok(ContractError::Unauthorized)));
+    assert_eq!(client.approve_action(&second, &action_id), 2);
+    assert_eq!(client.try_fund_gas_rebates(&admin, &0, &150_000), Err(Ok(ContractError::NeedsApproval)));
+    assert_eq!(client.fund_gas_rebates(&admin, &0, &100_001), 150_001);
+    assert_eq!(client.try_get_pending_action(&action_id), Err(Ok(ContractError::NotFound)));
+    assert_eq!(client.get_treasury(), (849_999, 0));
+}
+
+#[test]
+fn test_proposal_lifecycle_is_observable_from_events() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, Governance);
+    let client = GovernanceClient::new(&env, &contract_id);
+    client.initialize(&Address::generate(&env), &Address::generate(&env), &Address::generate(&env), &86_400);
+    let voter = Address::generate(&env);
+    client.update_voter_data(&voter, &1_000, &50);
+    env.ledger().with_mut(|ledger| ledger.sequence_number += 17_280);
+
+    let proposal_id = client.create_proposal(
+        &Address::generate(&env),
+        &metadata(&env, "events"),
+        &Symbol::new(&env, "rate_adjustment"),
+        &None,
+        &None,
+        &(7 * 86_400),
+    );
+    let power = client.vote(&voter, &proposal_id, &symbol_short!("yes"));
+    env.ledger().with_mut(|ledger| ledger.timestamp += 7 * 86_400 + 1);
+    client.finalize_proposal(&proposal_id);
+
+    let mut lifecycle = vec![&env];
+    for (contract, topics, payload) in env.events().all().iter() {
+        if contract != contract_id || topics.len() != 2 {
+            continue;
+        }
+        let name = Symbol::try_from_val(&env, &topics.get(0).unwrap()).unwrap();
+        if Symbol::try_from_val(&env, &topics.get(1).unwrap()) != Ok(proposal_id.clone()) {
+            continue;
+        }
+        if name == symbol_short!("voted") {
+            let (from, choice, total): (Address, Symbol, i128) = payload.into_val(&env);
+            assert_eq!((from, choice, total), (voter.clone(), symbol_short!("yes"), power));
+        }
+        if name == symbol_short!("finalized") {
+            assert_eq!(Symbol::try_from_val(&env, &payload).unwrap(), symbol_short!("passed"));
+        }
+        lifecycle.push_back(name);
+    }
+    assert_eq!(lifecycle, vec![&env, symbol_short!("proposed"), symbol_short!("voted"), symbol_short!("finalized")]);
+}
+
+#[test]
+fn test_epoch_kpis_combine_each_contracts_counters() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, Governance);
+    let client = GovernanceClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let loan_pool_id = env.register_contract(None, StubLoanPool);
+    client.initialize(&admin, &Address::generate(&env), &loan_pool_id, &86_400);
+    let epoch = 3;
+    StubLoanPoolClient::new(&env, &loan_pool_id).record_pool_epoch(
+        &epoch,
+        &FinancingStats { financed: 12_000, investor_count: 4, diversity_bps: 7_000 },
+        &ServicingStats::default(),
+    );
+
+    // Counters from contracts that aren't linked yet read as zero
+    let kpis = client.get_kpis(&epoch);
+    assert_eq!((kpis.financed, kpis.investor_count, kpis.investor_diversity_bps), (12_000, 4, 7_000));
+    assert_eq!((kpis.underserved_rides, kpis.cost_per_underserved_ride, kpis.avg_rate_discount_bps), (0, None, None));
+
+    let adjuster_id = env.register_contract(None, StubRateAdjuster);
+    let distributor_id = env.register_contract(None, StubDistributor);
+    for (action, linked) in [(symbol_short!("rate_adj"), &adjuster_id), (symbol_short!("rev_dist"), &distributor_id)] {
+        let change_id = client.queue_admin_change(&admin, &action, &0, &Some(linked.clone()), &86_400);
+        env.ledger().with_mut(|ledger| ledger.timestamp += 86_400);
+        client.execute_admin_change(&admin, &change_id);
+    }
+    StubRateAdjusterClient::new(&env, &adjuster_id).record_rate_stats(&epoch, &RateStats { loans: 3, discount_bps: 450 });
+    StubDistributorClient::new(&env, &distributor_id).record_rides(
+        &epoch,
+        &RideStats { rides: 900, underserved_rides: 400 },
+        &DistributionStats::default(),
+    );
+
+    // 12000 financed over 400 underserved rides, and 450 bps of discounts over 3 loans
+    let kpis = client.get_kpis(&epoch);
+    assert_eq!(kpis.underserved_rides, 400);
+    assert_eq!(kpis.cost_per_underserved_ride, Some(30));
+    assert_eq!(kpis.avg_rate_discount_bps, Some(150));
+    assert_eq!(
+        client.get_kpis(&(epoch + 1)),
+        EpochKpis {
+            epoch: epoch + 1,
+            financed: 0,
+            underserved_rides: 0,
+            cost_per_underserved_ride: None,
+            avg_rate_discount_bps: None,
+            investor_count: 0,
+            investor_diversity_bps: 0,
+        }
+    );
+}
+
+#[test]
+fn test_tally_checkpoints_publish_running_totals_at_most_every_interval() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, Governance);
+    let client = GovernanceClient::new(&env, &contract_id);
+    client.initialize(&Address::generate(&env), &Address::generate(&env), &Address::generate(&env), &86_400);
+    let voters = [Address::generate(&env), Address::generate(&env), Address::generate(&env)];
+    for voter in &voters {
+        client.update_voter_data(voter, &1_000, &50);
+    }
+    env.ledger().with_mut(|ledger| ledger.sequence_number += 17_280);
+    let proposal_id = client.create_proposal(
+        &Address::generate(&env),
+        &metadata(&env, "momentum"),
+        &Symbol::new(&env, "rate_adjustment"),
+        &None,
+        &None,
+        &(7 * 86_400),
+    );
+    assert_eq!(client.try_checkpoint_tally(&symbol_short!("missing")), Err(Ok(ContractError::ProposalNotFound)));
+
+    let opening = client.checkpoint_tally(&proposal_id);
+    assert_eq!((opening.sequence, opening.yes_power, opening.votes_cast, opening.turnout_bps), (1, 0, 0, 0));
+    assert_eq!(client.try_checkpoint_tally(&proposal_id), Err(Ok(ContractError::TooEarly)));
+
+    // Two of three equally weighted voters split yes and no
+    let power = client.vote(&voters[0], &proposal_id, &symbol_short!("yes"));
+    client.vote(&voters[1], &proposal_id, &symbol_short!("no"));
+    env.ledger().with_mut(|ledger| ledger.timestamp += 21_600);
+    let checkpoint = client.checkpoint_tally(&proposal_id);
+    assert_eq!(checkpoint.sequence, 2);
+    assert_eq!((checkpoint.yes_power, checkpoint.no_power, checkpoint.abstain_power), (power, power, 0));
+    assert_eq!(checkpoint.votes_cast, 2);
+    assert_eq!(checkpoint.turnout_bps, 6_666);
+    assert_eq!(client.get_tally_checkpoint(&proposal_id), Some(checkpoint.clone()));
+
+    let (_, topics, payload) = env.events().all().last().unwrap();
+    assert_eq!(Symbol::try_from_val(&env, &topics.get(0).unwrap()).unwrap(), symbol_short!("tally_cp"));
+    assert_eq!(TallyCheckpoint::try_from_val(&env, &payload).unwrap(), checkpoint);
+
+    // Checkpoints stop once voting closes
+    env.ledger().with_mut(|ledger| ledger.timestamp += 7 * 86_400);
+    assert_eq!(client.try_checkpoint_tally(&proposal_id), Err(Ok(ContractError::Expired)));
+    client.finalize_proposal(&proposal_id);
+    assert_eq!(client.try_checkpoint_tally(&proposal_id), Err(Ok(ContractError::InvalidStatus)));
+}
+
+#[test]
+fn test_proposal_text_is_committed_by_hash_and_checked_against_the_document() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, Governance);
+    let client = GovernanceClient::new(&env, &contract_id);
+    client.initialize(&Address::generate(&env), &Address::generate(&env), &Address::generate(&env), &86_400);
+    let proposer = Address::generate(&env);
+    let propose = |metadata: &Metadata| {
+        client.try_create_proposal(
+            &proposer,
+            metadata,
+            &Symbol::new(&env, "rate_adjustment"),
+            &None,
+            &None,
+            &(7 * 86_400),
+        )
+    };
+
+    // A title and description far past a symbol's 32 characters, in two languages
+    let document = Bytes::from_slice(
+        &env,
+        "{\"en\":{\"title\":\"Extend the eastside e-bike pilot through the winter season\",\
+\"description\":\"Keep the forty e-bikes deployed in the eastside corridor on the road until March\"},\
+\"es\":{\"title\":\"Ampliar el piloto de bicicletas electricas del este durante el invierno\"}}"
+            .as_bytes(),
+    );
+    let uri = [b'u'; MAX_URI_LEN as usize + 1];
+    let mut committed = Metadata {
+        content_hash: env.crypto().sha256(&document),
+        uri: String::from_str(&env, core::str::from_utf8(&uri).unwrap()),
+        label: symbol_short!("ebike_ext"),
+    };
+    assert_eq!(propose(&committed), Err(Ok(ContractError::InvalidInput)));
+
+    committed.uri = String::from_str(&env, core::str::from_utf8(&uri[..MAX_URI_LEN as usize]).unwrap());
+    let proposal_id = propose(&committed).unwrap().unwrap();
+    assert_eq!(client.get_proposal(&proposal_id).metadata, committed);
+    assert!(client.verify_proposal_metadata(&proposal_id, &document));
+
+    let mut altered = document.clone();
+    altered.set(2, b'E');
+    assert!(!client.verify_proposal_metadata(&proposal_id, &altered));
+    assert_eq!(client.try_verify_proposal_metadata(&symbol_short!("missing"), &document), Err(Ok(ContractError::ProposalNotFound)));
+}
+
+#[test]
+fn test_annual_report_freezes_a_years_counters_with_a_content_hash() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, Governance);
+    let client = GovernanceClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let loan_pool_id = env.register_contract(None, StubLoanPool);
+    client.initialize(&admin, &Address::generate(&env), &loan_pool_id, &86_400);
+    let adjuster_id = env.register_contract(None, StubRateAdjuster);
+    let distributor_id = env.register_contract(None, StubDistributor);
+    for (action, linked) in [(symbol_short!("rate_adj"), &adjuster_id), (symbol_short!("rev_dist"), &distributor_id)] {
+        let change_id = client.queue_admin_change(&admin, &action, &0, &Some(linked.clone()), &86_400);
+        env.ledger().with_mut(|ledger| ledger.timestamp += 86_400);
+        client.execute_admin_change(&admin, &change_id);
+    }
+
+    // With monthly epochs from 1970, epochs 0 through 11 begin in 1970 and epoch 12 in 1971
+    let loan_pool = StubLoanPoolClient::new(&env, &loan_pool_id);
+    let financed = |financed| FinancingStats { financed, investor_count: 1, diversity_bps: 0 };
+    loan_pool.record_pool_epoch(&0, &financed(5_000), &ServicingStats::default());
+    loan_pool.record_pool_epoch(&4, &financed(0), &ServicingStats { repaid_principal: 1_000, repaid_interest: 80, defaults: 1 });
+    loan_pool.record_pool_epoch(&11, &financed(3_000), &ServicingStats::default());
+    loan_pool.record_pool_epoch(&12, &financed(9_999), &ServicingStats::default());
+    StubRateAdjusterClient::new(&env, &adjuster_id).record_rate_stats(&2, &RateStats { loans: 2, discount_bps: 300 });
+    StubDistributorClient::new(&env, &distributor_id).record_rides(
+        &5,
+        &RideStats { rides: 500, underserved_rides: 200 },
+        &DistributionStats { distributions: 3, distributed: 2_000, platform_fees: 40 },
+    );
+
+    // One proposal passes and one gets no votes
+    let voter = Address::generate(&env);
+    client.update_voter_data(&voter, &1_000, &50);
+    env.ledger().with_mut(|ledger| ledger.sequence_number += 17_280);
+    let propose = |label: &str| {
+        client.create_proposal(
+            &Address::generate(&env),
+            &metadata(&env, label),
+            &Symbol::new(&env, "rate_adjustment"),
+            &None,
+            &None,
+            &(7 * 86_400),
+        )
+    };
+    let (passed, ignored) = (propose("passed"), propose("ignored"));
+    client.vote(&voter, &passed, &symbol_short!("yes"));
+    env.ledger().with_mut(|ledger| ledger.timestamp += 7 * 86_400 + 1);
+    client.finalize_proposal(&passed);
+    client.finalize_proposal(&ignored);
+
+    let year_end = EpochSchedule::default_schedule().year_start(1971);
+    env.ledger().with_mut(|ledger| ledger.timestamp = year_end - 1);
+    assert_eq!(client.try_finalize_year(&1970), Err(Ok(ContractError::TooEarly)));
+
+    // Activity in the new year stays out of the old year's report
+    env.ledger().with_mut(|ledger| ledger.timestamp = year_end);
+    propose("next_year");
+    let report = client.finalize_year(&1970);
+    let summary = &report.summary;
+    assert_eq!((summary.first_epoch, summary.end_epoch), (0, 12));
+    assert_eq!((summary.financed, summary.repaid_principal, summary.repaid_interest, summary.defaults), (8_000, 1_000, 80, 1));
+    assert_eq!((summary.loans_approved, summary.rate_discount_bps), (2, 300));
+    assert_eq!((summary.rides, summary.underserved_rides), (500, 200));
+    assert_eq!((summary.distributions, summary.distributed, summary.platform_fees), (3, 2_000, 40));
+    assert_eq!((summary.proposals, summary.proposals_passed), (2, 1));
+    assert_eq!(report.content_hash, env.crypto().sha256(&summary.clone().to_xdr(&env)));
+    assert_eq!(report.finalized_at, year_end);
+
+    // The report is frozen once written
+    assert_eq!(client.get_annual_report(&1970), report);
+    loan_pool.record_pool_epoch(&3, &financed(1), &ServicingStats::default());
+    assert_eq!(client.try_finalize_year(&1970), Err(Ok(ContractError::Done)));
+    assert_eq!(client.get_annual_report(&1970), report);
+    assert_eq!(client.try_get_annual_report(&1971), Err(Ok(ContractError::NotFound)));
+}
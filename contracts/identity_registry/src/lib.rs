@@ -0,0 +1,425 @@
+#![no_std]
+//! Permissioned data room for sensitive borrower documents.
+//!
+//! Attestations and rejection evidence elsewhere in the workspace carry only a
+//! sha256 of the underlying document. The registry maps each hash to a commitment
+//! to the locator of the data room copy and records who may resolve it: the
+//! document owner, plus approved reviewers the owner or admin has granted
+//! access, optionally until an expiry. Ledger state and transaction arguments are
+//! public, so owners register only the sha256 of a locator the data room gateway
+//! holds (a wrapped key or signed path), never the locator or the document, and
+//! the gateway serves the copy whose locator matches only after `resolve` succeeds.
+use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, vec, Address, BytesN, Env, Symbol, Vec};
+
+use mobility_types::{access, ttl, upgrade, Gate, Role, TtlConfig};
+
+mobility_types::contract_errors! {}
+
+/// A document registered in the data room
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DataRoomDocument {
+    pub content_hash: BytesN<32>, // sha256 referenced on-chain as attestation or evidence
+    pub owner: Address, // Borrower or applicant the document belongs to
+    pub locator_hash: BytesN<32>, // sha256 of the gateway's reference to the data room copy
+    pub registered_at: u64,
+}
+
+/// A reviewer's access to one document
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AccessGrant {
+    pub granted_by: Address,
+    pub granted_at: u64,
+    pub expires_at: Option<u64>, // None for access until revoked
+}
+
+/// Contract data structure
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DataKey {
+    pub admin: Address,
+    pub reviewers: Vec<Address>, // Approved reviewers eligible for grants
+    pub paused: bool,
+    pub disabled_features: Vec<Symbol>, // Entrypoint features switched off by admin
+}
+
+/// Persistent entries for per-document state
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum StorageKey {
+    Document(BytesN<32>),
+    Grant(BytesN<32>, Address), // (content_hash, reviewer)
+    Grantees(BytesN<32>), // Reviewers holding a grant on a document, in grant order
+}
+
+const DATA_KEY: Symbol = symbol_short!("DATA_KEY");
+
+// Set once by initialize to the storage layout version; its presence marks the contract initialized
+const INIT_KEY: Symbol = symbol_short!("INIT");
+const CONTRACT_VERSION: u32 = 1;
+
+#[contract]
+pub struct IdentityRegistry;
+
+#[contractimpl]
+impl IdentityRegistry {
+    /// Initialize the contract. Can only be called once.
+    pub fn initialize(env: &Env, admin: Address) -> Result<(), ContractError> {
+        admin.require_auth();
+
+        if env.storage().instance().has(&INIT_KEY) || env.storage().instance().has(&DATA_KEY) {
+            return Err(ContractError::AlreadyInitialized);
+        }
+
+        let data = DataKey {
+            admin,
+            reviewers: vec![env],
+            paused: false,
+            disabled_features: Vec::new(env),
+        };
+        ttl::set_instance(env, &DATA_KEY, &data);
+        ttl::set_instance(env, &INIT_KEY, &CONTRACT_VERSION);
+
+        Ok(())
+    }
+
+    /// Get the storage layout version recorded at initialization, or 0 if uninitialized
+    pub fn get_version(env: &Env) -> u32 {
+        env.storage().instance().get(&INIT_KEY).unwrap_or(0)
+    }
+
+    /// Version of the running code; `get_version` lags behind it until `migrate` runs
+    pub fn version(_env: &Env) -> u32 {
+        CONTRACT_VERSION
+    }
+
+    /// Replace the contract's code with an uploaded wasm, keeping its storage (admin only).
+    /// The new code's `migrate` must run before storage written by the old layout is used
+    pub fn upgrade(env: &Env, caller: Address, new_wasm_hash: BytesN<32>) -> Result<(), ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+        upgrade::upgrade(env, new_wasm_hash);
+
+        Ok(())
+    }
+
+    /// Bring storage up to this code's layout version after an upgrade (admin only)
+    pub fn migrate(env: &Env, caller: Address) -> Result<u32, ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+
+        let stored: u32 = env.storage().instance().get(&INIT_KEY).unwrap_or(0);
+        let version = upgrade::migrate(env, stored, CONTRACT_VERSION, Self::migrate_step)?;
+        ttl::set_instance(env, &INIT_KEY, &version);
+
+        Ok(version)
+    }
+
+    /// Rewrite storage from layout version `from` to `from + 1`. Each release that changes
+    /// the layout adds its step here
+    fn migrate_step(_env: &Env, _from: u32) -> Result<(), ContractError> {
+        Ok(())
+    }
+
+    /// Approve or remove a reviewer (admin only). Removing a reviewer cuts off every grant they hold
+    pub fn set_reviewer(env: &Env, caller: Address, reviewer: Address, approved: bool) -> Result<(), ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+
+        if let Some(index) = data.reviewers.first_index_of(&reviewer) {
+            data.reviewers.remove(index);
+        }
+        if approved {
+            data.reviewers.push_back(reviewer.clone());
+        }
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        env.events().publish((symbol_short!("reviewer"), reviewer), approved);
+
+        Ok(())
+    }
+
+    /// Whether an address is an approved reviewer
+    pub fn is_reviewer(env: &Env, reviewer: Address) -> bool {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        data.reviewers.contains(&reviewer)
+    }
+
+    /// Register the sha256 of a document's data room locator under the hash referenced on-chain
+    pub fn register_document(env: &Env, owner: Address, content_hash: BytesN<32>, locator_hash: BytesN<32>) -> Result<(), ContractError> {
+        owner.require_auth();
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        Gate::new(data.paused, &data.disabled_features, &symbol_short!("register")).check()?;
+
+        let key = StorageKey::Document(content_hash.clone());
+        if let Some(existing) = env.storage().persistent().get::<_, DataRoomDocument>(&key) {
+            if existing.owner != owner {
+                return Err(ContractError::Unauthorized);
+            }
+        }
+
+        ttl::set_persistent(env, &key, &DataRoomDocument {
+            content_hash: content_hash.clone(),
+            owner: owner.clone(),
+            locator_hash,
+            registered_at: env.ledger().timestamp(),
+        });
+
+        // The locator commitment is withheld from events; only the content hash and owner are public
+        env.events().publish((symbol_short!("doc_reg"), content_hash), owner);
+
+        Ok(())
+    }
+
+    /// Let an approved reviewer resolve a document, until `expires_at` if given (owner or admin)
+    pub fn grant_access(
+        env: &Env,
+        granter: Address,
+        content_hash: BytesN<32>,
+        reviewer: Address,
+        expires_at: Option<u64>,
+    ) -> Result<(), ContractError> {
+        granter.require_auth();
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        Gate::new(data.paused, &data.disabled_features, &symbol_short!("grant")).check()?;
+
+        let document = Self::load_document(env, &content_hash)?;
+        if granter != document.owner && !access::holds(env, &data.admin, Role::Admin, &granter) {
+            return Err(ContractError::Unauthorized);
+        }
+        if !data.reviewers.contains(&reviewer) {
+            return Err(ContractError::Unauthorized);
+        }
+        if let Some(expires_at) = expires_at {
+            if expires_at <= env.ledger().timestamp() {
+                return Err(ContractError::InvalidInput);
+            }
+        }
+
+        let grantees_key = StorageKey::Grantees(content_hash.clone());
+        let mut grantees: Vec<Address> = env.storage().persistent().get(&grantees_key).unwrap_or(vec![env]);
+        if !grantees.contains(&reviewer) {
+            grantees.push_back(reviewer.clone());
+            ttl::set_persistent(env, &grantees_key, &grantees);
+        }
+
+        ttl::set_persistent(
+            env,
+            &StorageKey::Grant(content_hash.clone(), reviewer.clone()),
+            &AccessGrant {
+                granted_by: granter,
+                granted_at: env.ledger().timestamp(),
+                expires_at,
+            },
+        );
+
+        env.events().publish((symbol_short!("granted"), content_hash), (reviewer, expires_at));
+
+        Ok(())
+    }
+
+    /// Withdraw a reviewer's access to a document (owner or admin)
+    pub fn revoke_access(env: &Env, revoker: Address, content_hash: BytesN<32>, reviewer: Address) -> Result<(), ContractError> {
+        revoker.require_auth();
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        let document = Self::load_document(env, &content_hash)?;
+        if revoker != document.owner && !access::holds(env, &data.admin, Role::Admin, &revoker) {
+            return Err(ContractError::Unauthorized);
+        }
+
+        let key = StorageKey::Grant(content_hash.clone(), reviewer.clone());
+        if !env.storage().persistent().has(&key) {
+            return Err(ContractError::Unauthorized);
+        }
+        env.storage().persistent().remove(&key);
+
+        let grantees_key = StorageKey::Grantees(content_hash.clone());
+        let mut grantees: Vec<Address> = env.storage().persistent().get(&grantees_key).unwrap_or(vec![env]);
+        if let Some(index) = grantees.first_index_of(&reviewer) {
+            grantees.remove(index);
+            ttl::set_persistent(env, &grantees_key, &grantees);
+        }
+
+        env.events().publish((symbol_short!("revoked"), content_hash), reviewer);
+
+        Ok(())
+    }
+
+    /// Whether a caller may resolve a document: its owner, or an approved reviewer with a live grant
+    pub fn can_resolve(env: &Env, caller: Address, content_hash: BytesN<32>) -> bool {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        match Self::load_document(env, &content_hash) {
+            Ok(document) => Self::has_access(env, &data, &document, &caller),
+            Err(_) => false,
+        }
+    }
+
+    /// Resolve a document for an authorized caller, logging the access. Returns the locator
+    /// commitment the gateway checks its copy's locator against
+    pub fn resolve(env: &Env, caller: Address, content_hash: BytesN<32>) -> Result<BytesN<32>, ContractError> {
+        caller.require_auth();
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        Gate::new(data.paused, &data.disabled_features, &symbol_short!("resolve")).check()?;
+
+        let document = Self::load_document(env, &content_hash)?;
+        if !Self::has_access(env, &data, &document, &caller) {
+            return Err(ContractError::Unauthorized);
+        }
+
+        env.events().publish((symbol_short!("resolved"), content_hash), caller);
+
+        Ok(document.locator_hash)
+    }
+
+    /// Reviewers currently able to resolve a document, for the owner's audit view
+    pub fn get_grantees(env: &Env, content_hash: BytesN<32>) -> Vec<Address> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        let mut live: Vec<Address> = vec![env];
+        let Ok(document) = Self::load_document(env, &content_hash) else {
+            return live;
+        };
+
+        let grantees: Vec<Address> = env.storage().persistent().get(&StorageKey::Grantees(content_hash)).unwrap_or(vec![env]);
+        for reviewer in grantees.iter() {
+            if reviewer != document.owner && Self::has_access(env, &data, &document, &reviewer) {
+                live.push_back(reviewer);
+            }
+        }
+        live
+    }
+
+    /// Get a document's owner and registration time; the locator commitment is only returned by `resolve`
+    pub fn get_document_owner(env: &Env, content_hash: BytesN<32>) -> Result<(Address, u64), ContractError> {
+        let document = Self::load_document(env, &content_hash)?;
+        Ok((document.owner, document.registered_at))
+    }
+
+    /// Halt state-changing entrypoints, e.g. while an oracle is compromised; views keep working (pauser only)
+    pub fn pause(env: &Env, caller: Address) -> Result<(), ContractError> {
+        Self::set_paused(env, caller, true)
+    }
+
+    /// Resume state-changing entrypoints after a pause (pauser only)
+    pub fn unpause(env: &Env, caller: Address) -> Result<(), ContractError> {
+        Self::set_paused(env, caller, false)
+    }
+
+    /// Whether state-changing entrypoints are halted
+    pub fn is_paused(env: &Env) -> bool {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        data.paused
+    }
+
+    fn set_paused(env: &Env, caller: Address, paused: bool) -> Result<(), ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Pauser, &caller)?;
+
+        data.paused = paused;
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        let topic = if paused { symbol_short!("paused") } else { symbol_short!("unpaused") };
+        env.events().publish((topic,), env.ledger().timestamp());
+
+        Ok(())
+    }
+
+    /// Switch one entrypoint feature on or off without pausing the whole contract (pauser only)
+    pub fn set_feature(env: &Env, caller: Address, feature: Symbol, enabled: bool) -> Result<(), ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Pauser, &caller)?;
+
+        if let Some(index) = data.disabled_features.first_index_of(&feature) {
+            data.disabled_features.remove(index);
+        }
+        if !enabled {
+            data.disabled_features.push_back(feature.clone());
+        }
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        env.events().publish((symbol_short!("feature"), feature), enabled);
+
+        Ok(())
+    }
+
+    /// Grant a role to an account (admin only)
+    pub fn grant_role(env: &Env, caller: Address, role: Role, account: Address) -> Result<(), ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+        access::grant(env, role, &account);
+
+        Ok(())
+    }
+
+    /// Revoke a role previously granted to an account (admin only)
+    pub fn revoke_role(env: &Env, caller: Address, role: Role, account: Address) -> Result<(), ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+        access::revoke(env, role, &account).map_err(ContractError::from)
+    }
+
+    /// Whether an account holds a role; the admin holds every role
+    pub fn has_role(env: &Env, role: Role, account: Address) -> bool {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        access::holds(env, &data.admin, role, &account)
+    }
+
+    /// Extend the TTL of the contract instance and its code. Open to anyone, so a keeper can
+    /// stop a quiet contract from being archived between writes
+    pub fn bump_storage(env: &Env) {
+        ttl::extend_instance(env);
+    }
+
+    /// Set the remaining TTL below which writes extend an entry, and the TTL they extend it to (admin only)
+    pub fn set_ttl_config(env: &Env, caller: Address, threshold: u32, extend_to: u32) -> Result<(), ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+        ttl::set_config(env, threshold, extend_to).map_err(ContractError::from)
+    }
+
+    /// Get the storage TTL configuration
+    pub fn get_ttl_config(env: &Env) -> TtlConfig {
+        ttl::config(env)
+    }
+
+    /// Load a registered document
+    fn load_document(env: &Env, content_hash: &BytesN<32>) -> Result<DataRoomDocument, ContractError> {
+        env.storage()
+            .persistent()
+            .get(&StorageKey::Document(content_hash.clone()))
+            .ok_or(ContractError::NotFound)
+    }
+
+    /// Owner access always holds; reviewers need approval and an unexpired grant
+    fn has_access(env: &Env, data: &DataKey, document: &DataRoomDocument, caller: &Address) -> bool {
+        if *caller == document.owner {
+            return true;
+        }
+        if !data.reviewers.contains(caller) {
+            return false;
+        }
+        let grant: Option<AccessGrant> = env
+            .storage()
+            .persistent()
+            .get(&StorageKey::Grant(document.content_hash.clone(), caller.clone()));
+        match grant {
+            Some(grant) => grant.expires_at.is_none_or(|expires_at| env.ledger().timestamp() < expires_at),
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,102 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    Address, Bytes, BytesN, Env,
+    testutils::{Address as _, Ledger},
+};
+
+#[test]
+fn test_documents_resolve_only_for_owner_and_granted_reviewers() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, IdentityRegistry);
+    let client = IdentityRegistryClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    let borrower = Address::generate(&env);
+    let reviewer = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    client.set_reviewer(&admin, &reviewer, &true);
+
+    // Only a commitment to the locator goes on-chain
+    let locator_hash: BytesN<32> = env.crypto().sha256(&Bytes::from_slice(&env, b"vault://wrapped-key"));
+    let content_hash: BytesN<32> = env.crypto().sha256(&Bytes::from_slice(&env, b"payslip.pdf"));
+    client.register_document(&borrower, &content_hash, &locator_hash);
+
+    // Unapproved addresses can't be granted access, and nobody resolves without a grant
+    assert_eq!(
+        client.try_grant_access(&borrower, &content_hash, &stranger, &None),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    assert_eq!(client.try_resolve(&reviewer, &content_hash), Err(Ok(ContractError::Unauthorized)));
+    assert_eq!(client.resolve(&borrower, &content_hash), locator_hash);
+
+    let now = env.ledger().timestamp();
+    client.grant_access(&borrower, &content_hash, &reviewer, &Some(now + 3_600));
+    assert_eq!(client.resolve(&reviewer, &content_hash), locator_hash);
+    assert_eq!(client.get_grantees(&content_hash).len(), 1);
+
+    // Grants lapse at their expiry and with the reviewer's approval
+    env.ledger().with_mut(|ledger| ledger.timestamp = now + 3_600);
+    assert!(!client.can_resolve(&reviewer, &content_hash));
+
+    client.grant_access(&borrower, &content_hash, &reviewer, &None);
+    assert!(client.can_resolve(&reviewer, &content_hash));
+    client.set_reviewer(&admin, &reviewer, &false);
+    assert!(!client.can_resolve(&reviewer, &content_hash));
+    assert!(!client.can_resolve(&stranger, &content_hash));
+}
+
+#[test]
+fn test_granted_admin_role_can_manage_access() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, IdentityRegistry);
+    let client = IdentityRegistryClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    let borrower = Address::generate(&env);
+    let reviewer = Address::generate(&env);
+    let officer = Address::generate(&env);
+    client.set_reviewer(&admin, &reviewer, &true);
+
+    let content_hash: BytesN<32> = env.crypto().sha256(&Bytes::from_slice(&env, b"lease.pdf"));
+    client.register_document(&borrower, &content_hash, &env.crypto().sha256(&Bytes::from_slice(&env, b"vault://lease")));
+
+    // Other roles don't stand in for the admin
+    client.grant_role(&admin, &Role::Pauser, &officer);
+    assert!(client.has_role(&Role::Pauser, &officer));
+    assert!(!client.has_role(&Role::Admin, &officer));
+    assert_eq!(
+        client.try_grant_access(&officer, &content_hash, &reviewer, &None),
+        Err(Ok(ContractError::Unauthorized))
+    );
+
+    // An admin holds every role
+    client.grant_role(&admin, &Role::Admin, &officer);
+    assert!(client.has_role(&Role::Treasurer, &officer));
+    client.grant_access(&officer, &content_hash, &reviewer, &None);
+    assert!(client.can_resolve(&reviewer, &content_hash));
+
+    // Revoking a grant drops the reviewer from the document's grantees
+    client.revoke_access(&officer, &content_hash, &reviewer);
+    assert!(!client.can_resolve(&reviewer, &content_hash));
+    assert_eq!(client.get_grantees(&content_hash).len(), 0);
+    let stored: Vec<Address> = env.as_contract(&contract_id, || {
+        env.storage().persistent().get(&StorageKey::Grantees(content_hash.clone())).unwrap()
+    });
+    assert!(stored.is_empty());
+    client.grant_access(&officer, &content_hash, &reviewer, &None);
+    assert_eq!(client.get_grantees(&content_hash), vec![&env, reviewer.clone()]);
+
+    client.revoke_role(&admin, &Role::Admin, &officer);
+    assert_eq!(client.try_revoke_role(&admin, &Role::Admin, &officer), Err(Ok(ContractError::NotFound)));
+    assert_eq!(
+        client.try_revoke_access(&officer, &content_hash, &reviewer),
+        Err(Ok(ContractError::Unauthorized))
+    );
+}
@@ -1,22 +1,52 @@
 #![no_std]
+// Entrypoints take their arguments flat, so listings and role-gated calls carry many
+#![allow(clippy::too_many_arguments)]
 use soroban_sdk::{
-    contract, contractimpl, contracttype, symbol_short, vec, Address, Env, Map, Symbol, Vec,
+    contract, contractimpl, contracttype, symbol_short, vec, xdr::ToXdr, Address, Bytes, BytesN, Env, Error, IntoVal, Map,
+    Symbol, TryFromVal, Val, Vec,
 };
 
+use mobility_types::{access, changes, journal, metrics, page_result, rebalance, seasonality, timelock, trustline, ttl, upgrade, AllocationWeight, ApprovalRule, ChangeLogPage, ChangeValue, PendingChange, Payee, EpochSchedule, FinancingStats, Gate, EquityOracleClient, GovernanceClient, LoanPoolInterface, Metadata, PageRequest, RecordChange, RecordChangePage, PendingAction, PositionMarketClient, RebalanceRecord, RiskData, SeasonalBaseline, ServicingStats, ShareTokenClient, TokenClient, Role, TtlConfig};
+
+mobility_types::contract_errors! {
+    ApplicationNotFound = 9,
+    AssetExists = 10,
+    Locked = 58,
+    LowEquityScore = 59,
+    Notice = 108,
+    OverBudget = 110,
+    Pending = 113,
+    Priority = 116,
+}
+
 /// Represents a mobility asset that can be funded
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct MobilityAsset {
     pub id: Symbol,
-    pub name: Symbol,
+    pub metadata: Metadata, // Display name and description, committed by hash
     pub asset_type: Symbol, // "e-bike", "shuttle", "scooter"
     pub target_amount: i128,
     pub funded_amount: i128,
     pub location: Symbol, // City/zone identifier
     pub equity_score: i32, // AI-calculated equity score (0-100)
-    pub status: Symbol, // "funding", "funded", "deployed", "completed"
-    pub investors: Vec<Address>,
+    pub status: Symbol, // "proposed", "funding", "funded", "deployed", "completed", "cancelled", "expired", "retired", "closed"
     pub created_at: u64,
+    pub program: Symbol, // Funding program the asset is listed under
+    pub poll_results: Map<u32, Symbol>, // poll_id -> winning option of closed asset polls
+    pub accessibility: Symbol, // Oracle-attested feature: "wheelchr" shuttle, "adaptive" bike; empty if none
+    pub funding_deadline: u64, // Funding closes at this time; 0 until the asset opens for funding
+}
+
+page_result!(AssetPage, MobilityAsset);
+
+/// Funding-stage assets that raise less than `min_funded_bps` of their target within
+/// `window` seconds of opening can be cancelled and refunded
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FundingVelocity {
+    pub min_funded_bps: u32,
+    pub window: u64,
 }
 
 /// Represents an investor's contribution
@@ -30,263 +60,4909 @@ pub struct Investment {
     pub timestamp: u64,
 }
 
+/// An investor's outstanding principal by asset, zone and program
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InvestorExposure {
+    pub total: i128,
+    pub by_asset: Map<Symbol, i128>,
+    pub by_zone: Map<Symbol, i128>,
+    pub by_program: Map<Symbol, i128>, // Programs are the pool's tranches
+}
+
+/// Caps an investor sets on their own exposure; 0 leaves a dimension uncapped
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExposureLimit {
+    pub max_total: i128,
+    pub max_per_asset: i128,
+    pub max_per_zone: i128,
+    pub max_per_program: i128,
+}
+
+/// Valuation inputs for a long-lived asset, used to compute its net asset value
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AssetValuation {
+    pub asset_id: Symbol,
+    pub principal_repaid: i128, // Principal returned by the operator so far
+    pub accrued_interest: i128, // Interest earned but not yet received
+    pub expected_loss: i128, // Oracle estimate of unrecoverable principal
+    pub liquidity: i128, // Repaid cash available to service redemptions
+    pub redeemed_units: i128, // Investment units already paid out
+}
+
+/// An investor's request to exit a position at net asset value
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExitRequest {
+    pub id: u32,
+    pub investor: Address,
+    pub asset_id: Symbol,
+    pub units: i128, // Investment units to redeem
+    pub filled_units: i128,
+    pub paid_amount: i128,
+    pub requested_at: u64,
+    pub status: Symbol, // "queued", "filled", "cancelled"
+}
+
+/// Payment terms and delinquency state for a deployed asset
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LoanServicing {
+    pub asset_id: Symbol,
+    pub payment_interval: u64, // Seconds between scheduled payments
+    pub amount_due: i128, // Amount due each period
+    pub next_due_at: u64,
+    pub paid_this_period: i128,
+    pub stage: Symbol, // "current", "grace", "reminder", "restruct", "delinq", "default"
+    pub stage_entered_at: u64,
+}
+
+/// One scheduled loan payment
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Installment {
+    pub number: u32, // 1-based position in the schedule
+    pub due_at: u64,
+    pub principal: i128,
+    pub interest: i128, // On the principal still outstanding over the period
+    pub paid: i128, // Applied to interest first, then principal
+}
+
+/// Amortizing repayment plan for a deployed asset's loan, priced at the application's adjusted rate
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RepaymentSchedule {
+    pub asset_id: Symbol,
+    pub application_id: Symbol,
+    pub borrower: Address,
+    pub principal: i128,
+    pub annual_rate: i32, // Adjusted rate (percentage)
+    pub interval: u64, // Seconds between installments
+    pub installments: Vec<Installment>,
+    pub next_installment: u32, // Index of the first installment not fully paid
+    pub total_paid: i128,
+    pub created_at: u64,
+    pub completed_at: Option<u64>,
+}
+
+/// A borrower's standing authorization for keepers to pull scheduled installments.
+/// Pulls go through the settlement token's allowance, which the borrower grants the pool
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Autopay {
+    pub asset_id: Symbol,
+    pub borrower: Address,
+    pub max_amount: i128, // Most a single pull may take
+    pub enrolled_at: u64,
+    pub last_installment: u32, // Number of the last installment collected, 0 before the first
+    pub active: bool, // Cleared by the borrower or by a failed pull
+}
+
+/// One recipient's share of operator-facing releases for an asset
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PayoutSplit {
+    pub recipient: Address, // Operator, maintenance reserve, driver co-op, ...
+    pub share_bps: u32, // Share in basis points; an asset's shares sum to 10000
+}
+
+/// Sunset plan for a funding program: no new assets or investments, and
+/// residual unreleased capital is split among the plan's recipients at settlement
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WindDown {
+    pub program: Symbol,
+    pub recipients: Vec<PayoutSplit>, // Residual balance split; shares sum to 10000
+    pub started_at: u64,
+    pub settled_at: Option<u64>,
+    pub residual: i128, // Amount credited to recipients at settlement
+}
+
+/// Sponsor capital escrowed against investor losses in a program or zone. Defaults there are
+/// covered from the escrow, up to what remains of it, before investors take a write-off
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FirstLossGuarantee {
+    pub sponsor: Address,
+    pub kind: Symbol, // "program" or "zone"
+    pub scope: Symbol, // Program or zone the guarantee covers
+    pub cap: i128, // Total escrowed by the sponsor
+    pub absorbed: i128, // Losses covered so far
+    pub expires_at: u64, // Unused escrow can be released to the sponsor from this time
+    pub released: i128, // Unused escrow returned to the sponsor
+    pub closed: bool,
+}
+
+/// Governance-set inputs for pricing asset insurance premiums, in basis points of insured value per year
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PremiumParams {
+    pub base_bps: u32,
+    pub risk_point_bps: u32, // Added per point of location risk premium
+    pub type_loadings: Map<Symbol, u32>, // asset_type -> added bps
+    pub breach_bps: u32, // Added per consecutive SLA breach of the asset's operator
+}
+
+/// A premium quote for insuring one asset
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PremiumQuote {
+    pub asset_id: Symbol,
+    pub insured_value: i128,
+    pub risk_points: i32, // Location risk premium from the oracle feed, 0..=MAX_RISK_PREMIUM
+    pub operator_breaches: u32,
+    pub premium_bps: u32,
+    pub annual_premium: i128,
+    pub quoted_at: u64,
+}
+
+/// Service levels an operator commits to before an asset is deployed
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OperatorSla {
+    pub asset_id: Symbol,
+    pub operator: Address, // Payout split recipient whose share is adjusted on breach
+    pub min_availability_bps: u32, // Share of service hours the fleet must be available
+    pub max_response_secs: u64, // Longest acceptable incident response time
+    pub min_underserved_bps: u32, // Share of rides that must start in underserved zones
+    pub epochs_reported: u32,
+    pub consecutive_breaches: u32,
+    pub haircut_bps: u32, // Current cut to the operator's payout share, held in the penalty reserve
+    pub last_reported_at: u64,
+}
+
+/// Principal rolled from a completed asset into a follow-on asset or the LP vault
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecycleRecord {
+    pub investor: Address,
+    pub from_asset: Symbol,
+    pub to_asset: Symbol, // Empty when the principal went to the LP vault
+    pub units: i128, // Units of the completed asset retired
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+page_result!(RecyclePage, RecycleRecord);
+
+/// Principal returned to or lost by an investor, kept for tax reporting
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PrincipalEvent {
+    pub investor: Address,
+    pub asset_id: Symbol,
+    pub kind: Symbol, // "redeemed", "recycled", "writeoff", "refunded", "covered", "salvage"
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+/// Salvage sale of a retired asset and how its proceeds were applied
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SalvageRecord {
+    pub asset_id: Symbol,
+    pub proceeds: i128, // Oracle-attested sale proceeds
+    pub loan_settled: i128, // Applied to the outstanding loan balance
+    pub distributed: i128, // Remainder paid to investors by share
+    pub recorded_at: u64,
+}
+
+/// Operational poll open to an asset's investors and local riders
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AssetPoll {
+    pub id: u32,
+    pub asset_id: Symbol,
+    pub question: Symbol, // e.g. "ext_hours", "relocate"
+    pub options: Vec<Symbol>,
+    pub tallies: Map<Symbol, u32>, // option -> votes (one per eligible address)
+    pub voters: Map<Address, bool>,
+    pub end_time: u64,
+    pub status: Symbol, // "open", "closed"
+    pub result: Symbol, // Winning option; empty on a tie or no votes
+}
+
+/// Material update posted by an asset's operator for its investors
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BulletinEntry {
+    pub id: u32, // Sequence within the asset's bulletin
+    pub asset_id: Symbol,
+    pub metadata: Metadata, // Title and full notice, published off-chain
+    pub category: Symbol, // "maint", "route", "finance", "safety", "other"
+    pub posted_by: Address,
+    pub timestamp: u64,
+}
+
+page_result!(BulletinPage, BulletinEntry);
+
+/// Investment budget an owner pre-authorizes for a session key, so repeat
+/// investments are signed by the session key instead of the owner
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SessionBudget {
+    pub owner: Address,
+    pub session_key: Address,
+    pub budget: i128, // Most the session key may invest in total
+    pub spent: i128,
+    pub expires_at: u64,
+}
+
+/// Governance-set yearly capital target for an underserved zone
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AllocationTarget {
+    pub zone: Symbol, // Matches MobilityAsset.location
+    pub annual_target: i128,
+    pub funded_amount: i128, // Capital invested in the zone since period_start
+    pub period_start: u64,
+    pub quarters_checked: u32, // Quarter ends already evaluated (0-4)
+}
+
+/// Compact audit record for an equity boost, bonus, or rate discount
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BoostAuditRecord {
+    pub subject: Address, // Who received the boost
+    pub amount: i128, // Size of the boost (units depend on rule)
+    pub rule: Symbol, // Which fairness rule granted it
+    pub data_version: u64, // Timestamp of the data the rule was evaluated against
+    pub timestamp: u64,
+}
+
+page_result!(BoostAuditPage, BoostAuditRecord);
+
 /// Contract data structure
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct DataKey {
     pub admin: Address,
-    pub assets: Map<Symbol, MobilityAsset>,
-    pub investments: Vec<Investment>,
     pub total_pool_balance: i128,
     pub equity_oracle: Address, // AI oracle address for equity calculations
+    pub loyalty_reserve: i128, // Funds backing credit conversions
+    pub next_exit_id: u32,
+    pub delinquency_alert_bps: i32, // Portfolio delinquency threshold in basis points
+    pub delinquency_alert_active: bool,
+    pub payouts_owed: i128, // Withdrawable payouts plus penalty reserves, all owed out of the pool
+    pub next_poll_id: u32,
+    pub lp_vault_total: i128, // Sum of every investor's LP vault balance
+    pub paused: bool, // Set by admin or by a failed invariant check when auto_pause is on
+    pub disabled_features: Vec<Symbol>, // Entrypoint features switched off by admin
+    pub auto_pause: bool,
+    pub approval_rule: ApprovalRule, // Threshold and approvers for large disbursements
+    pub pending_actions: Map<u32, PendingAction>, // action_id -> large disbursement awaiting approvals
+    pub next_action_id: u32,
+    pub accessibility_multiplier_bps: u32, // Scales investor equity bonuses on accessible assets
+    pub next_rebalance_id: u32,
+    pub funding_period: u64, // Time an asset has to reach its target once it opens for funding
+}
+
+/// Persistent entries for per-record state, so a call reads and rewrites only
+/// the records it touches instead of the whole instance entry
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum StorageKey {
+    Asset(Symbol),
+    RiskDisclosure(Symbol), // asset_id -> RiskData of its location as last reported by the oracle
+    AssetIds, // Every listed asset id, in listing order
+    Investments(Address, Symbol), // (investor, asset_id) -> Vec<Investment>, oldest first
+    Schedule(Symbol), // asset_id -> RepaymentSchedule
+    Autopay(Symbol), // asset_id -> borrower's autopay enrollment
+    Rebalance(u32), // rebalance_id -> RebalanceRecord
+    Refunded(Address, Symbol), // (investor, asset_id) -> contribution returned from an expired asset
+    Guarantee(Symbol, Symbol), // (kind, scope) -> FirstLossGuarantee
+    Salvage(Symbol), // asset_id -> SalvageRecord
+    Baseline(Symbol, Symbol), // (zone, asset_type) -> SeasonalBaseline
+    PositionMarket, // Secondary market positions are minted into
+    PositionLocked(Address, Symbol), // (investor, asset_id) -> principal backing minted positions
+    ShareBalance(Symbol, Address), // (asset_id, holder) -> share tokens held, one per open unit
+    ShareSupply(Symbol), // asset_id -> share tokens outstanding
+    ShareHolders(Symbol), // asset_id -> every account that has held the asset's shares, in order of first receipt
+    ShareToken(Symbol), // asset_id -> SEP-41 token fronting the asset's shares
+    Governance, // Notified when portfolio delinquency crosses the threshold
+    EquitySource, // Rate adjuster holding the canonical score cache
+    SettlementAsset, // SAC that investments and payouts settle in
+    PremiumParams,
+    FundingVelocity, // Minimum funding pace before a listing is cancelled
+    LoyaltyCredits(Address), // rider -> zone -> credits
+    Valuation(Symbol), // asset_id -> AssetValuation
+    Servicing(Symbol), // asset_id -> LoanServicing
+    EquityFloor(Symbol), // program -> minimum location equity score
+    Pledges(Symbol), // asset_id -> investor -> pledged amount
+    PriorityWindow(Symbol), // asset_id -> end of pledger priority window
+    PayoutSplits(Symbol), // asset_id -> operator payout split
+    Released(Symbol), // asset_id -> capital released to the operator side
+    PayoutBalance(Address), // recipient -> withdrawable payouts
+    AllocationTarget(Symbol), // zone -> yearly target
+    AllocationZones, // Every zone with an allocation target, in order of first target
+    LocalRider(Symbol, Address), // (location, rider) -> true once the oracle verified a ride there
+    Poll(u32), // poll_id -> AssetPoll
+    RecycleTarget(Address), // investor -> follow-on asset, empty for the LP vault
+    VaultBalance(Address), // investor -> LP vault balance
+    VaultHolders, // Every investor that has held an LP vault balance, in order of first deposit
+    OperatorSla(Symbol), // asset_id -> committed SLA
+    PenaltyReserve(Symbol), // asset_id -> operator payouts withheld for SLA breaches
+    WindDown(Symbol), // program -> wind-down plan
+    Exposure(Address), // investor -> InvestorExposure
+    ExposureLimit(Address), // investor -> self-set caps
+    FundingOpened(Symbol), // asset_id -> when the asset opened for funding
+    PoolAllocations, // asset_id -> LP vault capital moved into the asset by rebalances
+    Exit(u32), // request_id -> ExitRequest
+    ExitQueue(Symbol), // asset_id -> ids of its queued exit requests, oldest first
+    InvestorExits(Address, Symbol), // (investor, asset_id) -> ids of every exit request the investor made
 }
 
 const DATA_KEY: Symbol = symbol_short!("DATA_KEY");
 
+// Set once by initialize to the storage layout version; its presence marks the contract initialized
+const INIT_KEY: Symbol = symbol_short!("INIT");
+const CONTRACT_VERSION: u32 = 3;
+// Index into the asset ids of the next asset migrate_asset_shares moves off layout 2
+const SHARE_MIGRATION_KEY: Symbol = symbol_short!("SHARE_MIG");
+
+// Persistent entries: (SESSION_KEY, owner, session_key) -> SessionBudget
+const SESSION_KEY: Symbol = symbol_short!("SESSION");
+// Persistent entries: (KPI_KEY, epoch) -> investor -> capital financed that epoch
+const KPI_KEY: Symbol = symbol_short!("KPI");
+// Persistent entries: (SERVICE_KEY, epoch) -> ServicingStats for repayments and defaults that epoch
+const SERVICE_KEY: Symbol = symbol_short!("SERVICE");
+
+// Journal streams of BoostAuditRecords, by (BOOST_BY_SUBJECT, subject) and (BOOST_BY_RULE, rule)
+const BOOST_BY_SUBJECT: Symbol = symbol_short!("BOOST_SUB");
+const BOOST_BY_RULE: Symbol = symbol_short!("BOOST_RUL");
+// Journal streams: (RECYCLED_KEY, investor) -> RecycleRecords, (PRINCIPAL_KEY, investor) -> PrincipalEvents
+const RECYCLED_KEY: Symbol = symbol_short!("RECYCLED");
+const PRINCIPAL_KEY: Symbol = symbol_short!("PRINCIPAL");
+// Journal streams: (BULLETIN_KEY, asset_id) -> BulletinEntries, oldest first
+const BULLETIN_KEY: Symbol = symbol_short!("BULLETIN");
+
+// Investors get a month after a wind-down starts to claim and withdraw before final settlement
+const WIND_DOWN_NOTICE: u64 = 30 * 86_400;
+
+// Investments that bring any capped dimension to 90% of its limit raise an alert
+const EXPOSURE_ALERT_PERCENT: i128 = 90;
+
+// Session budgets last at most about a month
+const MAX_SESSION_DURATION: u64 = 31 * 86_400;
+
+// Investor equity bonuses on accessible assets are scaled by 1.5x by default, and never more than 2x
+const DEFAULT_ACCESSIBILITY_MULTIPLIER_BPS: u32 = 15_000;
+const MAX_ACCESSIBILITY_MULTIPLIER_BPS: u32 = 20_000;
+
+// Governance caps on premium pricing: each input, and the total quoted rate
+const MAX_PREMIUM_INPUT_BPS: u32 = 500;
+const MAX_PREMIUM_BPS: u32 = 1_500;
+
+// Loyalty credits accrue for rides in zones at or above this equity score
+const UNDERSERVED_EQUITY_SCORE: i32 = 60;
+const CREDITS_PER_UNDERSERVED_RIDE: i128 = 10;
+// Investment units per loyalty credit
+const LOYALTY_CREDIT_VALUE: i128 = 1;
+
+// Time past a missed due date before each delinquency stage is entered
+const GRACE_PERIOD: u64 = 7 * 86_400;
+const RESTRUCTURE_AFTER: u64 = 30 * 86_400;
+const DELINQUENT_AFTER: u64 = 60 * 86_400;
+const DEFAULT_AFTER: u64 = 90 * 86_400;
+
+// How long pledgers get exclusive access once an asset opens for funding
+const PLEDGE_PRIORITY_WINDOW: u64 = 2 * 86_400;
+
+// Assets have a quarter to reach their target once they open for funding
+const DEFAULT_FUNDING_PERIOD: u64 = 90 * 86_400;
+
+// SLA breaches start cutting the operator's payout share after this many epochs in a row,
+// by one step per further breached epoch up to the cap
+const SLA_BREACH_EPOCHS: u32 = 2;
+const SLA_HAIRCUT_STEP_BPS: u32 = 500;
+const SLA_MAX_HAIRCUT_BPS: u32 = 2_500;
+
+// Allocation targets are evaluated at each quarter end of the target year
+const SECONDS_PER_QUARTER: u64 = 7_889_400;
+const SECONDS_PER_YEAR: u64 = 365 * 86_400;
+const QUARTERS_PER_YEAR: u32 = 4;
+
 #[contract]
 pub struct LoanPool;
 
 #[contractimpl]
 impl LoanPool {
-    /// Initialize the contract with admin and AI oracle
-    pub fn initialize(env: &Env, admin: Address, equity_oracle: Address) {
+    /// Initialize the contract with admin and AI oracle. Can only be called once.
+    pub fn initialize(env: &Env, admin: Address, equity_oracle: Address) -> Result<(), ContractError> {
+        admin.require_auth();
+
+        if env.storage().instance().has(&INIT_KEY) || env.storage().instance().has(&DATA_KEY) {
+            return Err(ContractError::AlreadyInitialized);
+        }
+
         let data = DataKey {
             admin,
-            assets: Map::new(env),
-            investments: vec![env],
             total_pool_balance: 0,
             equity_oracle,
+            loyalty_reserve: 0,
+            next_exit_id: 1,
+            delinquency_alert_bps: 500, // Alert governance above 5% delinquent principal
+            delinquency_alert_active: false,
+            payouts_owed: 0,
+            next_poll_id: 1,
+            lp_vault_total: 0,
+            paused: false,
+            disabled_features: Vec::new(env),
+            auto_pause: false,
+            approval_rule: ApprovalRule::disabled(env),
+            pending_actions: Map::new(env),
+            next_action_id: 1,
+            accessibility_multiplier_bps: DEFAULT_ACCESSIBILITY_MULTIPLIER_BPS,
+            next_rebalance_id: 1,
+            funding_period: DEFAULT_FUNDING_PERIOD,
         };
-        env.storage().instance().set(&DATA_KEY, &data);
+        ttl::set_instance(env, &DATA_KEY, &data);
+        ttl::set_instance(env, &INIT_KEY, &CONTRACT_VERSION);
+
+        Ok(())
     }
 
-    /// Create a new mobility asset for funding
-    pub fn create_asset(
-        env: &Env,
-        asset_id: Symbol,
-        name: Symbol,
-        asset_type: Symbol,
-        target_amount: i128,
-        location: Symbol,
-    ) -> Result<(), Symbol> {
+    /// Change the equity oracle set at initialization without touching other state (governance only)
+    pub fn reconfigure(env: &Env, equity_oracle: Address) -> Result<(), ContractError> {
         let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
-        
-        // Only admin can create assets
-        if env.current_contract_address() != data.admin {
-            return Err(symbol_short!("UNAUTHORIZED"));
-        }
 
-        // Check if asset already exists
-        if data.assets.contains_key(&asset_id) {
-            return Err(symbol_short!("ASSET_EXISTS"));
-        }
+        Self::governance(env).ok_or(ContractError::NoAddress)?.require_auth();
 
-        // Calculate equity score using AI oracle (mocked for demo)
-        let equity_score = Self::calculate_equity_score(env, &location);
+        data.equity_oracle = equity_oracle.clone();
+        ttl::set_instance(env, &DATA_KEY, &data);
 
-        let asset = MobilityAsset {
-            id: asset_id.clone(),
-            name,
-            asset_type,
-            target_amount,
-            funded_amount: 0,
-            location,
-            equity_score,
-            status: symbol_short!("funding"),
-            investors: vec![env],
-            created_at: env.ledger().timestamp(),
-        };
+        env.events().publish((symbol_short!("reconfig"),), equity_oracle);
 
-        data.assets.set(&asset_id, &asset);
-        env.storage().instance().set(&DATA_KEY, &data);
-        
         Ok(())
     }
 
-    /// Invest in a mobility asset with AI-adjusted equity bonuses
-    pub fn invest(
-        env: &Env,
-        investor: Address,
-        asset_id: Symbol,
-        amount: i128,
-    ) -> Result<i32, Symbol> {
-        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
-        
-        // Validate amount
-        if amount <= 0 {
-            return Err(symbol_short!("INVALID_AMOUNT"));
-        }
+    /// Get the storage layout version recorded at initialization, or 0 if uninitialized
+    pub fn get_version(env: &Env) -> u32 {
+        env.storage().instance().get(&INIT_KEY).unwrap_or(0)
+    }
 
-        // Get asset
-        let mut asset = data.assets.get(&asset_id).ok_or(symbol_short!("ASSET_NOT_FOUND"))?;
-        
-        // Check if asset is still funding
-        if asset.status != symbol_short!("funding") {
-            return Err(symbol_short!("ASSET_NOT_FUNDING"));
-        }
+    /// Version of the running code; `get_version` lags behind it until `migrate` runs
+    pub fn version(_env: &Env) -> u32 {
+        CONTRACT_VERSION
+    }
 
-        // Calculate equity bonus based on investor and location
-        let equity_bonus = Self::calculate_investor_equity_bonus(env, &investor, &asset.location);
+    /// Replace the contract's code with an uploaded wasm, keeping its storage (admin only).
+    /// The new code's `migrate` must run before storage written by the old layout is used
+    pub fn upgrade(env: &Env, caller: Address, new_wasm_hash: BytesN<32>) -> Result<(), ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
 
-        // Create investment record
-        let investment = Investment {
-            investor: investor.clone(),
-            asset_id: asset_id.clone(),
-            amount,
-            equity_bonus,
-            timestamp: env.ledger().timestamp(),
-        };
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+        upgrade::upgrade(env, new_wasm_hash);
 
-        // Update asset
-        asset.funded_amount += amount;
-        asset.investors.push_back(&investor);
+        Ok(())
+    }
 
-        // Check if funding target reached
-        if asset.funded_amount >= asset.target_amount {
-            asset.status = symbol_short!("funded");
-        }
+    /// Bring storage up to this code's layout version after an upgrade (admin only)
+    pub fn migrate(env: &Env, caller: Address) -> Result<u32, ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
 
-        // Update data
-        data.assets.set(&asset_id, &asset);
-        data.investments.push_back(&investment);
-        data.total_pool_balance += amount;
-        
-        env.storage().instance().set(&DATA_KEY, &data);
-        
-        Ok(equity_bonus)
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+
+        let stored: u32 = env.storage().instance().get(&INIT_KEY).unwrap_or(0);
+        let version = upgrade::migrate(env, stored, CONTRACT_VERSION, Self::migrate_step)?;
+        ttl::set_instance(env, &INIT_KEY, &version);
+
+        Ok(version)
     }
 
-    /// Get asset details
-    pub fn get_asset(env: &Env, asset_id: Symbol) -> Result<MobilityAsset, Symbol> {
-        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
-        data.assets.get(&asset_id).ok_or(symbol_short!("ASSET_NOT_FOUND"))
+    /// Rewrite storage from layout version `from` to `from + 1`. Each release that changes
+    /// the layout adds its step here
+    fn migrate_step(env: &Env, from: u32) -> Result<(), ContractError> {
+        if from == 2 {
+            // Assets are moved in pages by migrate_asset_shares, which must have reached the end
+            let migrated: u32 = env.storage().instance().get(&SHARE_MIGRATION_KEY).unwrap_or(0);
+            if migrated < Self::asset_ids(env).len() {
+                return Err(ContractError::Pending);
+            }
+            env.storage().instance().remove(&SHARE_MIGRATION_KEY);
+        }
+        Ok(())
     }
 
-    /// Get all assets
-    pub fn get_all_assets(env: &Env) -> Vec<MobilityAsset> {
+    /// Layout 2 -> 3, one page of assets per call (admin only): drop each asset's investor list
+    /// and issue share tokens for the units its investors still hold, net of filled redemptions,
+    /// recycled principal and refunds. Progress is saved, so each page must start at the cursor
+    /// the previous call returned. Returns the next cursor, or None once every asset is moved
+    /// and `migrate` can run
+    pub fn migrate_asset_shares(env: &Env, caller: Address, page: PageRequest) -> Result<Option<u32>, ContractError> {
         let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
-        let mut assets = vec![env];
-        
-        for (_, asset) in data.assets.iter() {
-            assets.push_back(&asset);
+
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+
+        let stored: u32 = env.storage().instance().get(&INIT_KEY).unwrap_or(0);
+        if stored != 2 {
+            return Err(ContractError::InvalidStatus);
         }
-        
-        assets
+        let migrated: u32 = env.storage().instance().get(&SHARE_MIGRATION_KEY).unwrap_or(0);
+        if page.cursor != migrated || page.limit == 0 {
+            return Err(ContractError::InvalidInput);
+        }
+
+        let asset_ids = Self::asset_ids(env);
+        let (batch, next_cursor) = page.collect(env, &asset_ids, |asset_id| asset_id);
+        for asset_id in batch.iter() {
+            Self::migrate_asset(env, asset_id)?;
+        }
+        ttl::set_instance(env, &SHARE_MIGRATION_KEY, &next_cursor.unwrap_or(asset_ids.len()));
+        env.events().publish((symbol_short!("migrate"), symbol_short!("shares")), next_cursor);
+
+        Ok(next_cursor)
     }
 
-    /// Get investments for an asset
-    pub fn get_asset_investments(env: &Env, asset_id: Symbol) -> Vec<Investment> {
-        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
-        let mut asset_investments = vec![env];
-        
-        for investment in data.investments.iter() {
-            if investment.asset_id == asset_id {
-                asset_investments.push_back(&investment);
+    /// Move one asset off layout 2
+    fn migrate_asset(env: &Env, asset_id: Symbol) -> Result<(), ContractError> {
+        // Legacy assets are read field by field, since their optional fields have no fixed layout
+        let legacy: Map<Symbol, Val> = env
+            .storage()
+            .persistent()
+            .get(&StorageKey::Asset(asset_id.clone()))
+            .ok_or(ContractError::BadLegacy)?;
+        let investors: Vec<Address> = Self::legacy_field(env, &legacy, "investors")?;
+        let status: Symbol = Self::legacy_field(env, &legacy, "status")?;
+        let risk_disclosure: Option<RiskData> = Self::legacy_field(env, &legacy, "risk_disclosure")?;
+        let accessibility: Option<Symbol> = Self::legacy_field(env, &legacy, "accessibility")?;
+
+        let mut seen: Vec<Address> = vec![env];
+        for investor in investors.iter() {
+            if seen.contains(&investor) {
+                continue;
+            }
+            seen.push_back(investor.clone());
+            Self::add_share_holder(env, &asset_id, &investor);
+
+            // Cancelled assets refunded everyone; expired ones refund on request
+            let refunded = status == symbol_short!("cancelled")
+                || env.storage().persistent().has(&StorageKey::Refunded(investor.clone(), asset_id.clone()));
+            if refunded {
+                continue;
+            }
+
+            let mut units = Self::invested_amount(env, &investor, &asset_id);
+            for request in Self::exits_of(env, &investor, &asset_id).iter() {
+                units -= request.filled_units;
+            }
+            let recycled = (RECYCLED_KEY, investor.clone());
+            for index in 0..journal::len(env, &recycled) {
+                let record: RecycleRecord = journal::get(env, &recycled, index).unwrap();
+                if record.from_asset == asset_id {
+                    units -= record.units;
+                }
+            }
+            if units > 0 {
+                Self::mint_shares(env, &asset_id, &investor, units);
             }
         }
-        
-        asset_investments
+
+        if let Some(risk) = risk_disclosure {
+            ttl::set_persistent(env, &StorageKey::RiskDisclosure(asset_id.clone()), &risk);
+        }
+        Self::save_asset(env, &MobilityAsset {
+            id: asset_id.clone(),
+            metadata: Self::legacy_field(env, &legacy, "metadata")?,
+            asset_type: Self::legacy_field(env, &legacy, "asset_type")?,
+            target_amount: Self::legacy_field(env, &legacy, "target_amount")?,
+            funded_amount: Self::legacy_field(env, &legacy, "funded_amount")?,
+            location: Self::legacy_field(env, &legacy, "location")?,
+            equity_score: Self::legacy_field(env, &legacy, "equity_score")?,
+            status,
+            created_at: Self::legacy_field(env, &legacy, "created_at")?,
+            program: Self::legacy_field(env, &legacy, "program")?,
+            poll_results: Self::legacy_field(env, &legacy, "poll_results")?,
+            accessibility: accessibility.unwrap_or(Symbol::new(env, "")),
+            funding_deadline: Self::legacy_field(env, &legacy, "funding_deadline")?,
+        });
+
+        Ok(())
     }
 
-    /// Get total pool balance
-    pub fn get_pool_balance(env: &Env) -> i128 {
-        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
-        data.total_pool_balance
+    /// Read a named field of a legacy record
+    fn legacy_field<T: TryFromVal<Env, Val>>(env: &Env, legacy: &Map<Symbol, Val>, name: &str) -> Result<T, ContractError> {
+        let value = legacy.get(Symbol::new(env, name)).ok_or(ContractError::BadLegacy)?;
+        T::try_from_val(env, &value).map_err(|_| ContractError::BadLegacy)
     }
 
-    /// AI-driven equity score calculation (mocked for demo)
-    fn calculate_equity_score(env: &Env, location: &Symbol) -> i32 {
-        // In a real implementation, this would call the AI oracle
-        // For demo purposes, we'll use a simple algorithm based on location hash
-        let location_str = location.to_string();
-        let hash = env.crypto().sha256(&location_str.as_bytes());
-        
-        // Convert first byte to equity score (0-100)
-        let score = (hash[0] as i32) % 101;
-        
-        // Ensure underserved areas get higher equity scores
-        if location_str.contains("low_income") || location_str.contains("underserved") {
-            score + 20
-        } else {
+
+    /// Create a new mobility asset for funding
+    pub fn create_asset(
+        env: &Env,
+        caller: Address,
+        asset_id: Symbol,
+        metadata: Metadata,
+        asset_type: Symbol,
+        target_amount: i128,
+        location: Symbol,
+        program: Symbol,
+    ) -> Result<(), ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        
+        // Admin creates assets directly; governance creates them by executing asset_funding proposals
+        caller.require_auth();
+        if !access::holds(env, &data.admin, Role::Admin, &caller) && Self::governance(env) != Some(caller) {
+            return Err(ContractError::Unauthorized);
+        }
+
+        Self::list_asset(env, &mut data, asset_id, metadata, asset_type, target_amount, location, program, symbol_short!("funding"))?;
+        ttl::set_instance(env, &DATA_KEY, &data);
+        
+        Ok(())
+    }
+
+    /// Propose an asset for pledging before it opens for funding (admin only)
+    pub fn propose_asset(
+        env: &Env,
+        caller: Address,
+        asset_id: Symbol,
+        metadata: Metadata,
+        asset_type: Symbol,
+        target_amount: i128,
+        location: Symbol,
+        program: Symbol,
+    ) -> Result<(), ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        // Only admin can propose assets
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+
+        Self::list_asset(env, &mut data, asset_id, metadata, asset_type, target_amount, location, program, symbol_short!("proposed"))?;
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        Ok(())
+    }
+
+    /// Pledge a non-binding amount toward a proposed asset.
+    /// Once pledges reach the target the asset opens for funding with a priority window for pledgers.
+    pub fn pledge(env: &Env, investor: Address, asset_id: Symbol, amount: i128) -> Result<i128, ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        Gate::new(data.paused, &data.disabled_features, &symbol_short!("pledge")).check()?;
+
+        investor.require_auth();
+
+        if amount <= 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        let mut asset = Self::load_asset(env, &asset_id).ok_or(ContractError::AssetNotFound)?;
+        if asset.status != symbol_short!("proposed") {
+            return Err(ContractError::InvalidStatus);
+        }
+
+        let mut pledges: Map<Address, i128> = env.storage().persistent().get(&StorageKey::Pledges(asset_id.clone())).unwrap_or(Map::new(env));
+        let pledged = pledges.get(investor.clone()).unwrap_or(0) + amount;
+        pledges.set(investor, pledged);
+        ttl::set_persistent(env, &StorageKey::Pledges(asset_id.clone()), &pledges);
+
+        let total_pledged = Self::total_pledged(&pledges);
+
+        // Demand confirmed: open for real funding with a priority window for pledgers
+        if total_pledged >= asset.target_amount {
+            asset.status = symbol_short!("funding");
+            asset.funding_deadline = env.ledger().timestamp() + data.funding_period;
+            Self::save_asset(env, &asset);
+            changes::touch(env, symbol_short!("asset"), asset_id.clone());
+            env.events().publish((symbol_short!("status"), asset_id.clone()), asset.status.clone());
+            ttl::set_persistent(env, &StorageKey::PriorityWindow(asset_id.clone()), &(env.ledger().timestamp() + PLEDGE_PRIORITY_WINDOW));
+            ttl::set_persistent(env, &StorageKey::FundingOpened(asset_id.clone()), &env.ledger().timestamp());
+        }
+
+        Ok(total_pledged)
+    }
+
+    /// Withdraw a pledge from an asset that is still proposed
+    pub fn withdraw_pledge(env: &Env, investor: Address, asset_id: Symbol) -> Result<i128, ContractError> {
+        investor.require_auth();
+
+        let asset = Self::load_asset(env, &asset_id).ok_or(ContractError::AssetNotFound)?;
+        if asset.status != symbol_short!("proposed") {
+            return Err(ContractError::InvalidStatus);
+        }
+
+        let mut pledges = env.storage().persistent().get::<_, Map<Address, i128>>(&StorageKey::Pledges(asset_id.clone())).ok_or(ContractError::NotFound)?;
+        let withdrawn = pledges.get(investor.clone()).ok_or(ContractError::NotFound)?;
+        pledges.remove(investor);
+        ttl::set_persistent(env, &StorageKey::Pledges(asset_id.clone()), &pledges);
+
+        Ok(withdrawn)
+    }
+
+    /// Get pledges for an asset
+    pub fn get_pledges(env: &Env, asset_id: Symbol) -> Map<Address, i128> {
+        env.storage().persistent().get(&StorageKey::Pledges(asset_id.clone())).unwrap_or(Map::new(env))
+    }
+
+    /// Get the total pledged toward an asset
+    pub fn get_total_pledged(env: &Env, asset_id: Symbol) -> i128 {
+        match env.storage().persistent().get(&StorageKey::Pledges(asset_id.clone())) {
+            Some(pledges) => Self::total_pledged(&pledges),
+            None => 0,
+        }
+    }
+
+    /// Get the end of the pledger priority window for an asset (0 if none)
+    pub fn get_priority_window_end(env: &Env, asset_id: Symbol) -> u64 {
+        env.storage().persistent().get(&StorageKey::PriorityWindow(asset_id.clone())).unwrap_or(0)
+    }
+
+    /// Set the minimum funding pace below which listings can be cancelled, or None to turn it off (admin only)
+    pub fn set_funding_velocity(env: &Env, caller: Address, rule: Option<FundingVelocity>) -> Result<(), ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+
+        if let Some(rule) = &rule {
+            if rule.min_funded_bps == 0 || rule.min_funded_bps > 10_000 || rule.window == 0 {
+                return Err(ContractError::InvalidInput);
+            }
+        }
+
+        match rule {
+            Some(rule) => ttl::set_persistent(env, &StorageKey::FundingVelocity, &rule),
+            None => env.storage().persistent().remove(&StorageKey::FundingVelocity),
+        }
+
+        Ok(())
+    }
+
+    /// Get the minimum funding pace, if one is set
+    pub fn get_funding_velocity(env: &Env) -> Option<FundingVelocity> {
+        Self::funding_velocity(env)
+    }
+
+    /// Cancel a funding-stage asset that missed the minimum funding pace and return each investor's
+    /// capital in the settlement asset. Anyone can call this once the window has passed.
+    /// Returns the amount refunded
+    pub fn cancel_stalled_asset(env: &Env, asset_id: Symbol) -> Result<i128, ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        let rule = Self::funding_velocity(env).ok_or(ContractError::NotFound)?;
+        let mut asset = Self::load_asset(env, &asset_id).ok_or(ContractError::AssetNotFound)?;
+        if asset.status != symbol_short!("funding") {
+            return Err(ContractError::InvalidStatus);
+        }
+
+        let opened_at = env.storage().persistent().get(&StorageKey::FundingOpened(asset_id.clone())).unwrap_or(asset.created_at);
+        if env.ledger().timestamp() < opened_at + rule.window {
+            return Err(ContractError::TooEarly);
+        }
+        if asset.funded_amount * 10_000 >= asset.target_amount * rule.min_funded_bps as i128 {
+            return Err(ContractError::NotEligible);
+        }
+
+        let refunded = Self::refund_investors(env, &mut data, &asset)?;
+
+        // Pledges, the priority window and any rebalance allocation go with the listing
+        env.storage().persistent().remove(&StorageKey::Pledges(asset_id.clone()));
+        env.storage().persistent().remove(&StorageKey::PriorityWindow(asset_id.clone()));
+        env.storage().persistent().remove(&StorageKey::FundingOpened(asset_id.clone()));
+        Self::clear_pool_allocation(env, &asset_id);
+
+        asset.status = symbol_short!("cancelled");
+        Self::save_asset(env, &asset);
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        changes::touch(env, symbol_short!("asset"), asset_id.clone());
+        env.events().publish((symbol_short!("status"), asset_id.clone()), asset.status.clone());
+        env.events().publish((symbol_short!("cancelled"), asset_id), (asset.funded_amount, refunded));
+
+        Ok(refunded)
+    }
+
+    /// Return each investor's capital in an asset to them
+    fn refund_investors(env: &Env, data: &mut DataKey, asset: &MobilityAsset) -> Result<i128, ContractError> {
+        let mut refunded: i128 = 0;
+
+        for investor in Self::holders_of(env, &asset.id).iter() {
+            let amount = Self::invested_amount(env, &investor, &asset.id);
+            if amount <= 0 {
+                continue;
+            }
+
+            Self::return_contribution(env, data, asset, &investor, amount)?;
+            refunded += amount;
+        }
+
+        Ok(refunded)
+    }
+
+    /// Send a contribution to an asset that never finished raising back to its investor out of escrow
+    fn return_contribution(env: &Env, data: &mut DataKey, asset: &MobilityAsset, investor: &Address, amount: i128) -> Result<(), ContractError> {
+        let token = Self::settlement_asset(env).ok_or(ContractError::NoSettlementAsset)?;
+        TokenClient::new(env, &token).transfer(&env.current_contract_address(), investor, &amount);
+        data.total_pool_balance -= amount;
+
+        Self::log_principal_event(env, &PrincipalEvent {
+            investor: investor.clone(),
+            asset_id: asset.id.clone(),
+            kind: symbol_short!("refunded"),
+            amount,
+            timestamp: env.ledger().timestamp(),
+        });
+        Self::reduce_exposure(env, investor, &asset.id, amount);
+        Self::burn_shares(env, &asset.id, investor, amount);
+
+        // The zone's allocation target no longer counts the returned capital
+        if let Some(mut target) = env.storage().persistent().get::<_, AllocationTarget>(&StorageKey::AllocationTarget(asset.location.clone())) {
+            target.funded_amount = (target.funded_amount - amount).max(0);
+            ttl::set_persistent(env, &StorageKey::AllocationTarget(asset.location.clone()), &target);
+        }
+
+        Ok(())
+    }
+
+    /// Queue how long assets have to reach their target once they open for funding behind the
+    /// timelock; returns the change id (admin only). Applies to assets that open after the change executes
+    pub fn set_funding_period(env: &Env, caller: Address, period: u64) -> Result<u32, ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+
+        let value = ChangeValue::Number(period as i128);
+        timelock::queue(env, symbol_short!("fund_per"), value, timelock::MIN_DELAY, timelock::MIN_DELAY, |change| {
+            Self::validate_admin_change(env, change)
+        })
+    }
+
+    /// Close funding for an asset past its deadline: it is marked funded if it reached its target,
+    /// otherwise it expires and investors can reclaim their contributions with `refund`.
+    /// Anyone can call this. Returns the new status
+    pub fn finalize_funding(env: &Env, asset_id: Symbol) -> Result<Symbol, ContractError> {
+        let mut asset = Self::load_asset(env, &asset_id).ok_or(ContractError::AssetNotFound)?;
+        if asset.status != symbol_short!("funding") {
+            return Err(ContractError::InvalidStatus);
+        }
+        if env.ledger().timestamp() < asset.funding_deadline {
+            return Err(ContractError::TooEarly);
+        }
+
+        if asset.funded_amount >= asset.target_amount {
+            asset.status = symbol_short!("funded");
+        } else {
+            asset.status = symbol_short!("expired");
+            env.storage().persistent().remove(&StorageKey::Pledges(asset_id.clone()));
+            Self::clear_pool_allocation(env, &asset_id);
+        }
+        env.storage().persistent().remove(&StorageKey::PriorityWindow(asset_id.clone()));
+        env.storage().persistent().remove(&StorageKey::FundingOpened(asset_id.clone()));
+
+        Self::save_asset(env, &asset);
+
+        changes::touch(env, symbol_short!("asset"), asset_id.clone());
+        env.events().publish((symbol_short!("status"), asset_id), asset.status.clone());
+
+        Ok(asset.status)
+    }
+
+    /// Return an investor's contributions to an expired asset
+    pub fn refund(env: &Env, investor: Address, asset_id: Symbol) -> Result<i128, ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        Gate::new(data.paused, &data.disabled_features, &symbol_short!("refund")).check()?;
+
+        investor.require_auth();
+
+        let asset = Self::load_asset(env, &asset_id).ok_or(ContractError::AssetNotFound)?;
+        if asset.status != symbol_short!("expired") {
+            return Err(ContractError::InvalidStatus);
+        }
+
+        let refunded_key = StorageKey::Refunded(investor.clone(), asset_id.clone());
+        if env.storage().persistent().has(&refunded_key) {
+            return Err(ContractError::Done);
+        }
+        let amount = Self::invested_amount(env, &investor, &asset_id);
+        if amount <= 0 {
+            return Err(ContractError::Nothing);
+        }
+
+        Self::return_contribution(env, &mut data, &asset, &investor, amount)?;
+        ttl::set_persistent(env, &refunded_key, &amount);
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        env.events().publish((symbol_short!("refund"), asset_id), (investor, amount));
+
+        Ok(amount)
+    }
+
+    /// Sum of all pledges for an asset
+    fn total_pledged(pledges: &Map<Address, i128>) -> i128 {
+        let mut total: i128 = 0;
+        for (_, amount) in pledges.iter() {
+            total += amount;
+        }
+        total
+    }
+
+    /// Validate and store a new asset with the given initial status
+    fn list_asset(
+        env: &Env,
+        data: &mut DataKey,
+        asset_id: Symbol,
+        metadata: Metadata,
+        asset_type: Symbol,
+        target_amount: i128,
+        location: Symbol,
+        program: Symbol,
+        status: Symbol,
+    ) -> Result<(), ContractError> {
+        // Check if asset already exists
+        if Self::load_asset(env, &asset_id).is_some() {
+            return Err(ContractError::AssetExists);
+        }
+
+        Gate::new(data.paused, &data.disabled_features, &symbol_short!("list"))
+            .program(env.storage().persistent().has(&StorageKey::WindDown(program.clone())))
+            .check()?;
+        metadata.check()?;
+
+        // Calculate equity score using AI oracle (mocked for demo)
+        let equity_score = Self::calculate_equity_score(env, &location);
+
+        // Enforce the program's equity score floor
+        let equity_floor = env.storage().persistent().get(&StorageKey::EquityFloor(program.clone())).unwrap_or(0);
+        if equity_score < equity_floor {
+            return Err(ContractError::LowEquityScore);
+        }
+
+        if let Some(risk) = Self::location_risk(env, &location) {
+            ttl::set_persistent(env, &StorageKey::RiskDisclosure(asset_id.clone()), &risk);
+        }
+
+        let mut asset = MobilityAsset {
+            id: asset_id.clone(),
+            metadata,
+            asset_type,
+            target_amount,
+            funded_amount: 0,
+            location,
+            equity_score,
+            status,
+            created_at: env.ledger().timestamp(),
+            program,
+            poll_results: Map::new(env),
+            accessibility: Symbol::new(env, ""),
+            funding_deadline: 0,
+        };
+
+        if asset.status == symbol_short!("funding") {
+            asset.funding_deadline = asset.created_at + data.funding_period;
+            ttl::set_persistent(env, &StorageKey::FundingOpened(asset_id.clone()), &asset.created_at);
+        }
+        Self::save_asset(env, &asset);
+
+        changes::touch(env, symbol_short!("asset"), asset_id.clone());
+        env.events().publish(
+            (symbol_short!("listed"), asset_id),
+            (asset.program, asset.location, asset.target_amount, asset.status),
+        );
+
+        Ok(())
+    }
+
+    /// Queue the minimum location equity score for assets listed under a program behind the
+    /// timelock; returns the change id (governance only)
+    pub fn set_program_equity_floor(env: &Env, program: Symbol, min_equity_score: i32) -> Result<u32, ContractError> {
+        // Only governance can set equity floors
+        let governance = Self::governance(env).ok_or(ContractError::NoAddress)?;
+        governance.require_auth();
+
+        let value = ChangeValue::Encoded((program, min_equity_score).to_xdr(env));
+        timelock::queue(env, symbol_short!("eq_floor"), value, timelock::MIN_DELAY, timelock::MIN_DELAY, |change| {
+            Self::validate_admin_change(env, change)
+        })
+    }
+
+    /// Get the minimum location equity score for a program
+    pub fn get_program_equity_floor(env: &Env, program: Symbol) -> i32 {
+        env.storage().persistent().get(&StorageKey::EquityFloor(program.clone())).unwrap_or(0)
+    }
+
+    /// Set a zone's capital allocation target for the year starting now
+    pub fn set_allocation_target(env: &Env, zone: Symbol, annual_target: i128) -> Result<(), ContractError> {
+        // Only governance can set allocation targets
+        let governance = Self::governance(env).ok_or(ContractError::NoAddress)?;
+        governance.require_auth();
+
+        if annual_target <= 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        let key = StorageKey::AllocationTarget(zone.clone());
+        if !env.storage().persistent().has(&key) {
+            let mut zones = Self::allocation_zones(env);
+            zones.push_back(zone.clone());
+            ttl::set_persistent(env, &StorageKey::AllocationZones, &zones);
+        }
+        ttl::set_persistent(env, &key, &AllocationTarget {
+            zone: zone.clone(),
+            annual_target,
+            funded_amount: 0,
+            period_start: env.ledger().timestamp(),
+            quarters_checked: 0,
+        });
+
+        Ok(())
+    }
+
+    /// Evaluate any quarter ends that have passed for a zone, alerting on shortfalls
+    pub fn check_allocation_target(env: &Env, zone: Symbol) -> Result<AllocationTarget, ContractError> {
+        let mut target = env.storage().persistent().get(&StorageKey::AllocationTarget(zone.clone())).ok_or(ContractError::NotFound)?;
+        Self::check_allocation_quarters(env, &mut target);
+        ttl::set_persistent(env, &StorageKey::AllocationTarget(zone.clone()), &target);
+
+        Ok(target)
+    }
+
+    /// Get funded amounts against targets for every zone
+    pub fn get_allocation_progress(env: &Env) -> Vec<AllocationTarget> {
+        let mut progress = vec![env];
+
+        for zone in Self::allocation_zones(env).iter() {
+            if let Some(target) = env.storage().persistent().get(&StorageKey::AllocationTarget(zone)) {
+                progress.push_back(target);
+            }
+        }
+
+        progress
+    }
+
+    /// Every zone with an allocation target, in order of first target
+    fn allocation_zones(env: &Env) -> Vec<Symbol> {
+        env.storage().persistent().get(&StorageKey::AllocationZones).unwrap_or(vec![env])
+    }
+
+    /// Invest LP vault capital in assets still raising by a governance-approved weight vector
+    /// (governance only). Every target is checked before anything moves. Each asset's share is drawn
+    /// from the vault holders pro rata and invested on their behalf, within the asset's remaining
+    /// target and each holder's investment limits; what can't be placed stays in the vault. The
+    /// allocation before and after is kept as a rebalance record
+    pub fn rebalance_pool(env: &Env, proposal_id: Symbol, targets: Vec<AllocationWeight>) -> Result<RebalanceRecord, ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        Self::governance(env).ok_or(ContractError::NoAddress)?.require_auth();
+        Gate::new(data.paused, &data.disabled_features, &symbol_short!("rebalance")).check()?;
+        rebalance::validate_targets(&targets)?;
+
+        // Vault capital belongs to no asset until it is invested
+        let pool_capital = data.lp_vault_total;
+
+        // Each asset's share is capped by what it still has to raise
+        let mut shares: Map<Symbol, i128> = Map::new(env);
+        for target in targets.iter() {
+            let asset = Self::load_asset(env, &target.asset_id).ok_or(ContractError::AssetNotFound)?;
+            if asset.status != symbol_short!("funding") {
+                return Err(ContractError::Locked);
+            }
+            if env.storage().persistent().has(&StorageKey::WindDown(asset.program.clone())) {
+                return Err(ContractError::WindDown);
+            }
+            let share = (pool_capital * target.weight_bps as i128 / 10_000).min(asset.target_amount - asset.funded_amount);
+            shares.set(target.asset_id, share);
+        }
+
+        let before = Self::pool_allocations(env);
+        let mut after = before.clone();
+        for holder in Self::vault_holders(env).iter() {
+            let mut balance = Self::get_lp_vault_balance(env, holder.clone());
+            let holding = balance;
+            for (asset_id, share) in shares.iter() {
+                let amount = share * holding / pool_capital;
+                if amount <= 0 {
+                    continue;
+                }
+
+                // The capital is already held by the pool, so don't count it twice
+                data.total_pool_balance -= amount;
+                if Self::record_investment(env, &mut data, &holder, &asset_id, amount).is_err() {
+                    data.total_pool_balance += amount;
+                    continue;
+                }
+                balance -= amount;
+                data.lp_vault_total -= amount;
+                after.set(asset_id.clone(), after.get(asset_id).unwrap_or(0) + amount);
+            }
+            if balance != holding {
+                ttl::set_persistent(env, &StorageKey::VaultBalance(holder.clone()), &balance);
+            }
+        }
+
+        let record = RebalanceRecord {
+            id: data.next_rebalance_id,
+            proposal_id: proposal_id.clone(),
+            pool_capital,
+            zones_before: Self::allocations_by_zone(env, &before),
+            zones_after: Self::allocations_by_zone(env, &after),
+            before,
+            after: after.clone(),
+            timestamp: env.ledger().timestamp(),
+        };
+
+        ttl::set_persistent(env, &StorageKey::PoolAllocations, &after);
+        data.next_rebalance_id += 1;
+        ttl::set_persistent(env, &StorageKey::Rebalance(record.id), &record);
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        env.events().publish((symbol_short!("rebalance"), proposal_id), (record.id, pool_capital));
+
+        Ok(record)
+    }
+
+    /// Get the LP vault capital each asset has drawn through rebalances
+    pub fn get_pool_allocations(env: &Env) -> Map<Symbol, i128> {
+        Self::pool_allocations(env)
+    }
+
+    /// LP vault capital each asset still raising or live has drawn through rebalances
+    fn pool_allocations(env: &Env) -> Map<Symbol, i128> {
+        env.storage().persistent().get(&StorageKey::PoolAllocations).unwrap_or(Map::new(env))
+    }
+
+    /// Every investor that has held an LP vault balance. Investors who have since withdrawn
+    /// or been rebalanced out stay listed with a zero balance
+    fn vault_holders(env: &Env) -> Vec<Address> {
+        env.storage().persistent().get(&StorageKey::VaultHolders).unwrap_or(vec![env])
+    }
+
+    /// Drop an asset's rebalance allocation when its listing ends
+    fn clear_pool_allocation(env: &Env, asset_id: &Symbol) {
+        let mut allocations = Self::pool_allocations(env);
+        if allocations.contains_key(asset_id.clone()) {
+            allocations.remove(asset_id.clone());
+            ttl::set_persistent(env, &StorageKey::PoolAllocations, &allocations);
+        }
+    }
+
+    /// Get an executed rebalance with its before and after allocations
+    pub fn get_rebalance(env: &Env, rebalance_id: u32) -> Result<RebalanceRecord, ContractError> {
+        env.storage()
+            .persistent()
+            .get(&StorageKey::Rebalance(rebalance_id))
+            .ok_or(ContractError::NotFound)
+    }
+
+    /// Sum per-asset allocations by the zone each asset is located in
+    fn allocations_by_zone(env: &Env, allocations: &Map<Symbol, i128>) -> Map<Symbol, i128> {
+        let mut zones: Map<Symbol, i128> = Map::new(env);
+        for (asset_id, amount) in allocations.iter() {
+            if let Some(asset) = Self::load_asset(env, &asset_id) {
+                let total = zones.get(asset.location.clone()).unwrap_or(0) + amount;
+                zones.set(asset.location, total);
+            }
+        }
+        zones
+    }
+
+    /// Compare funding with the pro-rata target at each elapsed quarter end.
+    /// Only funding recorded before the check counts toward that quarter.
+    fn check_allocation_quarters(env: &Env, target: &mut AllocationTarget) {
+        let elapsed = env.ledger().timestamp().saturating_sub(target.period_start);
+        let quarters_ended = ((elapsed / SECONDS_PER_QUARTER) as u32).min(QUARTERS_PER_YEAR);
+
+        while target.quarters_checked < quarters_ended {
+            target.quarters_checked += 1;
+            let expected = target.annual_target * target.quarters_checked as i128 / QUARTERS_PER_YEAR as i128;
+            if target.funded_amount < expected {
+                env.events().publish(
+                    (symbol_short!("alloc_low"), target.zone.clone()),
+                    (target.quarters_checked, target.funded_amount, expected),
+                );
+            }
+        }
+    }
+
+    /// Invest in a mobility asset with AI-adjusted equity bonuses. The amount moves from the
+    /// investor to the pool in the settlement asset and stays escrowed until the asset's loan draws it
+    pub fn invest(
+        env: &Env,
+        investor: Address,
+        asset_id: Symbol,
+        amount: i128,
+    ) -> Result<i32, ContractError> {
+        investor.require_auth();
+
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        
+        // Validate amount
+        if amount <= 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        let token = Self::settlement_asset(env).ok_or(ContractError::NoSettlementAsset)?;
+        let equity_bonus = Self::record_investment(env, &mut data, &investor, &asset_id, amount)?;
+        TokenClient::new(env, &token).transfer(&investor, &env.current_contract_address(), &amount);
+
+        ttl::set_instance(env, &DATA_KEY, &data);
+        
+        Ok(equity_bonus)
+    }
+
+    /// Pre-authorize a session key to invest up to `budget` on the owner's behalf until it expires
+    pub fn grant_session(
+        env: &Env,
+        owner: Address,
+        session_key: Address,
+        budget: i128,
+        duration: u64,
+    ) -> Result<(), ContractError> {
+        owner.require_auth();
+
+        if budget <= 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        if duration == 0 || duration > MAX_SESSION_DURATION {
+            return Err(ContractError::InvalidInput);
+        }
+
+        let session = SessionBudget {
+            owner: owner.clone(),
+            session_key: session_key.clone(),
+            budget,
+            spent: 0,
+            expires_at: env.ledger().timestamp() + duration,
+        };
+        ttl::set_persistent(env, &(SESSION_KEY, owner.clone(), session_key.clone()), &session);
+
+        env.events().publish((symbol_short!("session"), owner), (session_key, budget, session.expires_at));
+
+        Ok(())
+    }
+
+    /// Invest on an owner's behalf, signed by a session key and charged to its budget. The amount
+    /// is pulled through the settlement token's allowance, which the owner grants the pool
+    pub fn invest_with_session(
+        env: &Env,
+        session_key: Address,
+        owner: Address,
+        asset_id: Symbol,
+        amount: i128,
+    ) -> Result<i32, ContractError> {
+        session_key.require_auth();
+
+        let key = (SESSION_KEY, owner.clone(), session_key.clone());
+        let mut session: SessionBudget = env.storage().persistent().get(&key).ok_or(ContractError::NotFound)?;
+
+        if env.ledger().timestamp() >= session.expires_at {
+            return Err(ContractError::Expired);
+        }
+
+        if amount <= 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        if session.spent + amount > session.budget {
+            return Err(ContractError::OverBudget);
+        }
+
+        let token = Self::settlement_asset(env).ok_or(ContractError::NoSettlementAsset)?;
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        let equity_bonus = Self::record_investment(env, &mut data, &owner, &asset_id, amount)?;
+        let pool = env.current_contract_address();
+        TokenClient::new(env, &token).transfer_from(&pool, &owner, &pool, &amount);
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        session.spent += amount;
+        ttl::set_persistent(env, &key, &session);
+
+        Ok(equity_bonus)
+    }
+
+    /// Revoke a session key's remaining budget
+    pub fn revoke_session(env: &Env, owner: Address, session_key: Address) -> Result<(), ContractError> {
+        owner.require_auth();
+
+        let key = (SESSION_KEY, owner.clone(), session_key.clone());
+        if !env.storage().persistent().has(&key) {
+            return Err(ContractError::NotFound);
+        }
+        env.storage().persistent().remove(&key);
+
+        env.events().publish((symbol_short!("sess_rvk"), owner), session_key);
+
+        Ok(())
+    }
+
+    /// Get a session key's budget and spend
+    pub fn get_session(env: &Env, owner: Address, session_key: Address) -> Result<SessionBudget, ContractError> {
+        env.storage()
+            .persistent()
+            .get(&(SESSION_KEY, owner, session_key))
+            .ok_or(ContractError::NotFound)
+    }
+
+    /// Record verified rides for a rider (oracle only).
+    /// Rides in underserved zones accrue loyalty credits for that zone.
+    pub fn record_rider_rides(
+        env: &Env,
+        rider: Address,
+        location: Symbol,
+        ride_count: i32,
+    ) -> Result<i128, ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        // Only oracle can verify rides
+        data.equity_oracle.require_auth();
+
+        Gate::new(data.paused, &data.disabled_features, &symbol_short!("oracle")).check()?;
+
+        if ride_count <= 0 {
+            return Err(ContractError::InvalidInput);
+        }
+
+        // Verified rides make the rider eligible for polls on assets in this zone
+        ttl::set_persistent(env, &StorageKey::LocalRider(location.clone(), rider.clone()), &true);
+
+        // Only rides in underserved zones earn credits
+        let equity_score = Self::calculate_equity_score(env, &location);
+        if equity_score < UNDERSERVED_EQUITY_SCORE {
+            return Ok(0);
+        }
+
+        let earned = ride_count as i128 * CREDITS_PER_UNDERSERVED_RIDE;
+        let mut zone_credits: Map<Symbol, i128> = env.storage().persistent().get(&StorageKey::LoyaltyCredits(rider.clone())).unwrap_or(Map::new(env));
+        let balance = zone_credits.get(location.clone()).unwrap_or(0) + earned;
+        zone_credits.set(location, balance);
+        ttl::set_persistent(env, &StorageKey::LoyaltyCredits(rider.clone()), &zone_credits);
+
+        Ok(balance)
+    }
+
+    /// Open an operational poll on an asset (admin only)
+    pub fn create_asset_poll(
+        env: &Env,
+        caller: Address,
+        asset_id: Symbol,
+        question: Symbol,
+        options: Vec<Symbol>,
+        duration: u64,
+    ) -> Result<u32, ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        // Only admin can open asset polls
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+
+        if Self::load_asset(env, &asset_id).is_none() {
+            return Err(ContractError::AssetNotFound);
+        }
+
+        if options.len() < 2 || duration == 0 {
+            return Err(ContractError::InvalidInput);
+        }
+
+        let poll_id = data.next_poll_id;
+        let mut tallies = Map::new(env);
+        for option in options.iter() {
+            tallies.set(option, 0);
+        }
+
+        ttl::set_persistent(env, &StorageKey::Poll(poll_id), &AssetPoll {
+            id: poll_id,
+            asset_id,
+            question,
+            options,
+            tallies,
+            voters: Map::new(env),
+            end_time: env.ledger().timestamp() + duration,
+            status: symbol_short!("open"),
+            result: Symbol::new(env, ""),
+        });
+        data.next_poll_id += 1;
+
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        Ok(poll_id)
+    }
+
+    /// Vote in an asset poll as one of its investors or an attested local rider
+    pub fn vote_asset_poll(env: &Env, voter: Address, poll_id: u32, option: Symbol) -> Result<(), ContractError> {
+        voter.require_auth();
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        Gate::new(data.paused, &data.disabled_features, &symbol_short!("poll")).check()?;
+
+        let mut poll = env.storage().persistent().get::<_, AssetPoll>(&StorageKey::Poll(poll_id)).ok_or(ContractError::NotFound)?;
+
+        if poll.status != symbol_short!("open") || env.ledger().timestamp() > poll.end_time {
+            return Err(ContractError::Expired);
+        }
+
+        let asset = Self::load_asset(env, &poll.asset_id).ok_or(ContractError::AssetNotFound)?;
+        let is_local_rider = env.storage().persistent().has(&StorageKey::LocalRider(asset.location.clone(), voter.clone()));
+        if Self::shares_of(env, &asset.id, &voter) <= 0 && !is_local_rider {
+            return Err(ContractError::NotEligible);
+        }
+
+        if poll.voters.contains_key(voter.clone()) {
+            return Err(ContractError::AlreadyVoted);
+        }
+
+        let count = poll.tallies.get(option.clone()).ok_or(ContractError::InvalidInput)?;
+        poll.tallies.set(option, count + 1);
+        poll.voters.set(voter, true);
+        ttl::set_persistent(env, &StorageKey::Poll(poll_id), &poll);
+
+        Ok(())
+    }
+
+    /// Close an asset poll after it ends and record the result on the asset
+    pub fn close_asset_poll(env: &Env, poll_id: u32) -> Result<Option<Symbol>, ContractError> {
+        let mut poll = env.storage().persistent().get::<_, AssetPoll>(&StorageKey::Poll(poll_id)).ok_or(ContractError::NotFound)?;
+
+        if poll.status != symbol_short!("open") {
+            return Err(ContractError::Expired);
+        }
+
+        if env.ledger().timestamp() <= poll.end_time {
+            return Err(ContractError::TooEarly);
+        }
+
+        // Highest tally wins; a tie for first place has no result
+        let mut result: Option<Symbol> = None;
+        let mut best: u32 = 0;
+        for (option, count) in poll.tallies.iter() {
+            if count > best {
+                best = count;
+                result = Some(option);
+            } else if count == best {
+                result = None;
+            }
+        }
+
+        poll.status = symbol_short!("closed");
+        poll.result = result.clone().unwrap_or(Symbol::new(env, ""));
+        ttl::set_persistent(env, &StorageKey::Poll(poll_id), &poll);
+
+        if let Some(winner) = result.clone() {
+            let mut asset = Self::load_asset(env, &poll.asset_id).ok_or(ContractError::AssetNotFound)?;
+            asset.poll_results.set(poll_id, winner);
+            Self::save_asset(env, &asset);
+            changes::touch(env, symbol_short!("asset"), poll.asset_id.clone());
+        }
+
+        // Surface the outcome to the operator
+        env.events().publish((symbol_short!("poll_res"), poll.asset_id), (poll_id, poll.question, result.clone()));
+
+        Ok(result)
+    }
+
+    /// Get an asset poll
+    pub fn get_asset_poll(env: &Env, poll_id: u32) -> Result<AssetPoll, ContractError> {
+        env.storage().persistent().get(&StorageKey::Poll(poll_id)).ok_or(ContractError::NotFound)
+    }
+
+    /// Post a material update to an asset's investor bulletin (asset operator only)
+    pub fn post_bulletin(
+        env: &Env,
+        operator: Address,
+        asset_id: Symbol,
+        metadata: Metadata,
+        category: Symbol,
+    ) -> Result<u32, ContractError> {
+        operator.require_auth();
+
+        metadata.check()?;
+        if Self::load_asset(env, &asset_id).is_none() {
+            return Err(ContractError::AssetNotFound);
+        }
+
+        // The operator is the first recipient of the asset's payout split
+        let splits = env.storage().persistent().get::<_, Vec<PayoutSplit>>(&StorageKey::PayoutSplits(asset_id.clone())).ok_or(ContractError::NotFound)?;
+        if splits.get(0).map(|split| split.recipient) != Some(operator.clone()) {
+            return Err(ContractError::Unauthorized);
+        }
+
+        if category != symbol_short!("maint")
+            && category != symbol_short!("route")
+            && category != symbol_short!("finance")
+            && category != symbol_short!("safety")
+            && category != symbol_short!("other")
+        {
+            return Err(ContractError::InvalidInput);
+        }
+
+        let stream = (BULLETIN_KEY, asset_id.clone());
+        let entry_id = journal::len(env, &stream);
+        journal::append(env, &stream, &BulletinEntry {
+            id: entry_id,
+            asset_id: asset_id.clone(),
+            metadata: metadata.clone(),
+            category: category.clone(),
+            posted_by: operator,
+            timestamp: env.ledger().timestamp(),
+        });
+
+        env.events().publish((symbol_short!("bulletin"), asset_id), (entry_id, category, metadata.label, metadata.content_hash));
+
+        Ok(entry_id)
+    }
+
+    /// Get a page of an asset's bulletin, oldest first
+    pub fn get_bulletin(env: &Env, asset_id: Symbol, page: PageRequest) -> BulletinPage {
+        let (items, next_cursor) = journal::page(env, &(BULLETIN_KEY, asset_id), &page);
+        BulletinPage { items, next_cursor }
+    }
+
+    /// Convert loyalty credits into a micro-investment in a local asset.
+    /// Credits can only be used for assets in the zone where they were earned.
+    pub fn convert_credits(
+        env: &Env,
+        rider: Address,
+        asset_id: Symbol,
+        credits: i128,
+    ) -> Result<i128, ContractError> {
+        rider.require_auth();
+
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        Gate::new(data.paused, &data.disabled_features, &symbol_short!("credits")).check()?;
+
+        if credits <= 0 {
+            return Err(ContractError::InvalidInput);
+        }
+
+        let asset = Self::load_asset(env, &asset_id).ok_or(ContractError::AssetNotFound)?;
+
+        let mut zone_credits = env.storage().persistent().get::<_, Map<Symbol, i128>>(&StorageKey::LoyaltyCredits(rider.clone())).ok_or(ContractError::NoFunds)?;
+        let balance = zone_credits.get(asset.location.clone()).unwrap_or(0);
+        if balance < credits {
+            return Err(ContractError::NoFunds);
+        }
+
+        // Conversions are backed by the loyalty reserve
+        let amount = credits * LOYALTY_CREDIT_VALUE;
+        if data.loyalty_reserve < amount {
+            return Err(ContractError::NoFunds);
+        }
+
+        // Minimum investment thresholds do not apply to credit conversions
+        Self::record_investment(env, &mut data, &rider, &asset_id, amount)?;
+
+        zone_credits.set(asset.location, balance - credits);
+        ttl::set_persistent(env, &StorageKey::LoyaltyCredits(rider.clone()), &zone_credits);
+        data.loyalty_reserve -= amount;
+
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        Ok(amount)
+    }
+
+    /// Fund the reserve backing loyalty credit conversions (treasurer only).
+    /// The funds are pulled from the treasurer into the pool in the settlement asset.
+    pub fn fund_loyalty_reserve(env: &Env, caller: Address, amount: i128) -> Result<i128, ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        // Only treasurers can fund the reserve
+        access::check(env, &data.admin, Role::Treasurer, &caller)?;
+
+        if amount <= 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        let token = Self::settlement_asset(env).ok_or(ContractError::NoSettlementAsset)?;
+        TokenClient::new(env, &token).transfer(&caller, &env.current_contract_address(), &amount);
+
+        data.loyalty_reserve += amount;
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        Ok(data.loyalty_reserve)
+    }
+
+    /// Get a rider's loyalty credits in a zone
+    pub fn get_loyalty_credits(env: &Env, rider: Address, location: Symbol) -> i128 {
+        match env.storage().persistent().get::<_, Map<Symbol, i128>>(&StorageKey::LoyaltyCredits(rider.clone())) {
+            Some(zone_credits) => zone_credits.get(location).unwrap_or(0),
+            None => 0,
+        }
+    }
+
+    /// Record an investment against an asset and return the equity bonus. Callers must have
+    /// the investor's authorization: their own signature, a session key's, or an opt-in
+    fn record_investment(
+        env: &Env,
+        data: &mut DataKey,
+        investor: &Address,
+        asset_id: &Symbol,
+        amount: i128,
+    ) -> Result<i32, ContractError> {
+        // Get asset
+        let mut asset = Self::load_asset(env, asset_id).ok_or(ContractError::AssetNotFound)?;
+        
+        // Check if asset is still funding
+        if asset.status != symbol_short!("funding") {
+            return Err(ContractError::InvalidStatus);
+        }
+        if asset.funding_deadline > 0 && env.ledger().timestamp() >= asset.funding_deadline {
+            return Err(ContractError::Expired);
+        }
+
+        Gate::new(data.paused, &data.disabled_features, &symbol_short!("invest"))
+            .program(env.storage().persistent().has(&StorageKey::WindDown(asset.program.clone())))
+            .check()?;
+
+        // During the priority window only pledgers may invest
+        if let Some(priority_until) = env.storage().persistent().get(&StorageKey::PriorityWindow(asset_id.clone())) {
+            if env.ledger().timestamp() < priority_until {
+                let pledged = match env.storage().persistent().get::<_, Map<Address, i128>>(&StorageKey::Pledges(asset_id.clone())) {
+                    Some(pledges) => pledges.get(investor.clone()).unwrap_or(0),
+                    None => 0,
+                };
+                if pledged == 0 {
+                    return Err(ContractError::Priority);
+                }
+            }
+        }
+
+        Self::check_trustline(env, investor)?;
+
+        // Calculate equity bonus based on investor and location, with the accessibility lane's multiplier.
+        // Priced before the exposure is recorded, which would end the investor's first-time bonus
+        let mut equity_bonus = Self::calculate_investor_equity_bonus(env, investor, &asset.location);
+        if Self::is_accessible(env, &asset) {
+            equity_bonus = equity_bonus * data.accessibility_multiplier_bps as i32 / 10_000;
+        }
+
+        Self::add_exposure(env, investor, &asset, amount)?;
+
+        // Create investment record
+        let investment = Investment {
+            investor: investor.clone(),
+            asset_id: asset_id.clone(),
+            amount,
+            equity_bonus,
+            timestamp: env.ledger().timestamp(),
+        };
+
+        // Update asset
+        asset.funded_amount += amount;
+
+        // Check if funding target reached
+        if asset.funded_amount >= asset.target_amount {
+            asset.status = symbol_short!("funded");
+        }
+
+        // Audit the investor equity bonus
+        if equity_bonus > 0 {
+            Self::log_boost(env, investor, equity_bonus as i128, symbol_short!("inv_bonus"), asset.created_at);
+        }
+
+        // Count the capital toward the zone's allocation target
+        if let Some(mut target) = env.storage().persistent().get(&StorageKey::AllocationTarget(asset.location.clone())) {
+            Self::check_allocation_quarters(env, &mut target);
+            target.funded_amount += amount;
+            ttl::set_persistent(env, &StorageKey::AllocationTarget(asset.location.clone()), &target);
+        }
+
+        // Update data
+        Self::save_asset(env, &asset);
+        changes::touch(env, symbol_short!("asset"), asset_id.clone());
+        Self::add_investment(env, &investment);
+        Self::mint_shares(env, asset_id, investor, amount);
+        env.events().publish(
+            (symbol_short!("invested"), asset_id.clone()),
+            (investment.investor.clone(), amount, equity_bonus),
+        );
+        if asset.status == symbol_short!("funded") {
+            env.events().publish((symbol_short!("status"), asset_id.clone()), asset.status.clone());
+        }
+        data.total_pool_balance += amount;
+        Self::count_financing(env, investor, amount);
+
+        Ok(equity_bonus)
+    }
+
+    /// Add an investment to the current epoch's financing counters
+    fn count_financing(env: &Env, investor: &Address, amount: i128) {
+        let key = (KPI_KEY, Self::current_epoch(env));
+
+        let mut amounts: Map<Address, i128> = env.storage().persistent().get(&key).unwrap_or(Map::new(env));
+        let total = amounts.get(investor.clone()).unwrap_or(0) + amount;
+        amounts.set(investor.clone(), total);
+        ttl::set_persistent(env, &key, &amounts);
+    }
+
+    /// Add repayments and defaults to the current epoch's servicing counters
+    fn count_servicing(env: &Env, principal: i128, interest: i128, defaults: u32) {
+        let key = (SERVICE_KEY, Self::current_epoch(env));
+        let mut stats: ServicingStats = env.storage().persistent().get(&key).unwrap_or_default();
+        stats.repaid_principal += principal;
+        stats.repaid_interest += interest;
+        stats.defaults += defaults;
+        ttl::set_persistent(env, &key, &stats);
+    }
+
+    /// Calendar epoch the current ledger falls in, on governance's schedule
+    fn current_epoch(env: &Env) -> u32 {
+        let schedule = match Self::governance(env) {
+            Some(governance) => GovernanceClient::new(env, &governance).get_epoch_schedule(),
+            None => EpochSchedule::default_schedule(),
+        };
+        schedule.epoch_at(env.ledger().timestamp())
+    }
+
+    /// Load an asset from its own entry
+    fn load_asset(env: &Env, asset_id: &Symbol) -> Option<MobilityAsset> {
+        env.storage().persistent().get(&StorageKey::Asset(asset_id.clone()))
+    }
+
+    /// Write an asset, indexing it the first time it is written
+    fn save_asset(env: &Env, asset: &MobilityAsset) {
+        let key = StorageKey::Asset(asset.id.clone());
+        if !env.storage().persistent().has(&key) {
+            let mut ids = Self::asset_ids(env);
+            ids.push_back(asset.id.clone());
+            ttl::set_persistent(env, &StorageKey::AssetIds, &ids);
+        }
+        ttl::set_persistent(env, &key, asset);
+    }
+
+    /// Every listed asset id, in listing order
+    fn asset_ids(env: &Env) -> Vec<Symbol> {
+        env.storage().persistent().get(&StorageKey::AssetIds).unwrap_or(vec![env])
+    }
+
+    /// An investor's investments in one asset, oldest first
+    fn investments_of(env: &Env, investor: &Address, asset_id: &Symbol) -> Vec<Investment> {
+        env.storage()
+            .persistent()
+            .get(&StorageKey::Investments(investor.clone(), asset_id.clone()))
+            .unwrap_or(vec![env])
+    }
+
+    /// Total an investor has put into one asset
+    fn invested_amount(env: &Env, investor: &Address, asset_id: &Symbol) -> i128 {
+        Self::investments_of(env, investor, asset_id).iter().map(|investment| investment.amount).sum()
+    }
+
+    /// Append an investment to its investor's record for the asset
+    fn add_investment(env: &Env, investment: &Investment) {
+        let mut investments = Self::investments_of(env, &investment.investor, &investment.asset_id);
+        investments.push_back(investment.clone());
+        ttl::set_persistent(env, &StorageKey::Investments(investment.investor.clone(), investment.asset_id.clone()), &investments);
+    }
+
+    /// Every account that has held an asset's shares, in order of first receipt. Holders who
+    /// have since redeemed or sold out stay listed with a zero balance
+    fn holders_of(env: &Env, asset_id: &Symbol) -> Vec<Address> {
+        env.storage()
+            .persistent()
+            .get(&StorageKey::ShareHolders(asset_id.clone()))
+            .unwrap_or(vec![env])
+    }
+
+    fn add_share_holder(env: &Env, asset_id: &Symbol, holder: &Address) {
+        let mut holders = Self::holders_of(env, asset_id);
+        if !holders.contains(holder) {
+            holders.push_back(holder.clone());
+            ttl::set_persistent(env, &StorageKey::ShareHolders(asset_id.clone()), &holders);
+        }
+    }
+
+    /// Share tokens an account holds in an asset
+    fn shares_of(env: &Env, asset_id: &Symbol, holder: &Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&StorageKey::ShareBalance(asset_id.clone(), holder.clone()))
+            .unwrap_or(0)
+    }
+
+    /// Share tokens outstanding for an asset: its funded units less those redeemed, recycled or refunded
+    fn outstanding_shares(env: &Env, asset_id: &Symbol) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&StorageKey::ShareSupply(asset_id.clone()))
+            .unwrap_or(0)
+    }
+
+    fn set_shares(env: &Env, asset_id: &Symbol, holder: &Address, balance: i128) {
+        Self::add_share_holder(env, asset_id, holder);
+        ttl::set_persistent(env, &StorageKey::ShareBalance(asset_id.clone(), holder.clone()), &balance);
+    }
+
+    /// Issue shares one for one with invested units
+    fn mint_shares(env: &Env, asset_id: &Symbol, to: &Address, amount: i128) {
+        Self::set_shares(env, asset_id, to, Self::shares_of(env, asset_id, to) + amount);
+        let supply = Self::outstanding_shares(env, asset_id) + amount;
+        ttl::set_persistent(env, &StorageKey::ShareSupply(asset_id.clone()), &supply);
+        env.events().publish(
+            (symbol_short!("mint"), env.current_contract_address(), to.clone(), asset_id.clone()),
+            amount,
+        );
+    }
+
+    /// Retire shares whose units were redeemed, recycled or refunded
+    fn burn_shares(env: &Env, asset_id: &Symbol, from: &Address, amount: i128) {
+        Self::set_shares(env, asset_id, from, Self::shares_of(env, asset_id, from) - amount);
+        let supply = Self::outstanding_shares(env, asset_id) - amount;
+        ttl::set_persistent(env, &StorageKey::ShareSupply(asset_id.clone()), &supply);
+        env.events().publish((symbol_short!("burn"), from.clone(), asset_id.clone()), amount);
+    }
+
+    /// Move an investor's holding in an asset to another account: the shares, the investment
+    /// records behind them oldest first with timestamps and bonuses intact, and the exposure
+    /// that goes with them, so redemptions and revenue distributions follow the holding
+    fn move_holding(
+        env: &Env,
+        asset: &MobilityAsset,
+        from: &Address,
+        to: &Address,
+        amount: i128,
+    ) -> Result<(), ContractError> {
+        if from == to {
+            return Err(ContractError::SameParty);
+        }
+        Self::check_trustline(env, to)?;
+        Self::add_exposure(env, to, asset, amount)?;
+        Self::reduce_exposure(env, from, &asset.id, amount);
+
+        let mut remaining = amount;
+        let mut kept = vec![env];
+        for mut investment in Self::investments_of(env, from, &asset.id).iter() {
+            let moved = investment.amount.min(remaining);
+            if moved > 0 {
+                remaining -= moved;
+                Self::add_investment(env, &Investment {
+                    investor: to.clone(),
+                    amount: moved,
+                    ..investment.clone()
+                });
+                investment.amount -= moved;
+            }
+            if investment.amount > 0 {
+                kept.push_back(investment);
+            }
+        }
+        ttl::set_persistent(env, &StorageKey::Investments(from.clone(), asset.id.clone()), &kept);
+
+        Self::set_shares(env, &asset.id, from, Self::shares_of(env, &asset.id, from) - amount);
+        Self::set_shares(env, &asset.id, to, Self::shares_of(env, &asset.id, to) + amount);
+        changes::touch(env, symbol_short!("asset"), asset.id.clone());
+        env.events().publish(
+            (symbol_short!("transfer"), from.clone(), to.clone(), asset.id.clone()),
+            amount,
+        );
+
+        Ok(())
+    }
+
+    /// Get asset details
+    pub fn get_asset(env: &Env, asset_id: Symbol) -> Result<MobilityAsset, ContractError> {
+        Self::load_asset(env, &asset_id).ok_or(ContractError::AssetNotFound)
+    }
+
+    /// Check an off-chain name and description document against an asset's metadata commitment
+    pub fn verify_asset_metadata(env: &Env, asset_id: Symbol, content: Bytes) -> Result<bool, ContractError> {
+        let asset = Self::load_asset(env, &asset_id).ok_or(ContractError::AssetNotFound)?;
+        Ok(asset.metadata.verify(env, &content))
+    }
+
+    /// Get all assets, accessible assets first
+    pub fn get_all_assets(env: &Env) -> Vec<MobilityAsset> {
+        let mut assets = vec![env];
+        let mut others = vec![env];
+        
+        for asset_id in Self::asset_ids(env).iter() {
+            let asset = Self::load_asset(env, &asset_id).unwrap();
+            if Self::is_accessible(env, &asset) {
+                assets.push_back(asset);
+            } else {
+                others.push_back(asset);
+            }
+        }
+        assets.append(&others);
+        
+        assets
+    }
+
+    /// Get a page of assets in listing order
+    pub fn get_assets_page(env: &Env, page: PageRequest) -> AssetPage {
+        let (items, next_cursor) = page.collect(env, &Self::asset_ids(env), |id| Self::load_asset(env, &id).unwrap());
+        AssetPage { items, next_cursor }
+    }
+
+    /// Get assets with an attested accessibility feature
+    pub fn get_accessible_assets(env: &Env) -> Vec<MobilityAsset> {
+        let mut assets = vec![env];
+        for asset_id in Self::asset_ids(env).iter() {
+            let asset = Self::load_asset(env, &asset_id).unwrap();
+            if Self::is_accessible(env, &asset) {
+                assets.push_back(asset);
+            }
+        }
+        assets
+    }
+
+    /// Whether an asset carries an attested accessibility feature
+    fn is_accessible(env: &Env, asset: &MobilityAsset) -> bool {
+        asset.accessibility != Symbol::new(env, "")
+    }
+
+    /// Attest or clear an asset's accessibility feature: "wheelchr" or "adaptive" (oracle only)
+    pub fn attest_accessibility(env: &Env, asset_id: Symbol, feature: Option<Symbol>) -> Result<(), ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        data.equity_oracle.require_auth();
+
+        Gate::new(data.paused, &data.disabled_features, &symbol_short!("oracle")).check()?;
+
+        if let Some(feature) = &feature {
+            if *feature != symbol_short!("wheelchr") && *feature != symbol_short!("adaptive") {
+                return Err(ContractError::InvalidInput);
+            }
+        }
+
+        let mut asset = Self::load_asset(env, &asset_id).ok_or(ContractError::AssetNotFound)?;
+        asset.accessibility = feature.clone().unwrap_or(Symbol::new(env, ""));
+        Self::save_asset(env, &asset);
+        changes::touch(env, symbol_short!("asset"), asset_id.clone());
+
+        env.events().publish((symbol_short!("access"), asset_id), feature);
+
+        Ok(())
+    }
+
+    /// Set the equity bonus multiplier for accessible assets, at most 2x (governance only)
+    pub fn set_accessibility_multiplier(env: &Env, multiplier_bps: u32) -> Result<(), ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        Self::governance(env).ok_or(ContractError::NoAddress)?.require_auth();
+
+        if !(10_000..=MAX_ACCESSIBILITY_MULTIPLIER_BPS).contains(&multiplier_bps) {
+            return Err(ContractError::InvalidInput);
+        }
+
+        data.accessibility_multiplier_bps = multiplier_bps;
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        Ok(())
+    }
+
+    /// Get investments for an asset, grouped by investor
+    pub fn get_asset_investments(env: &Env, asset_id: Symbol) -> Vec<Investment> {
+        let mut asset_investments = vec![env];
+        
+        for investor in Self::holders_of(env, &asset_id).iter() {
+            asset_investments.append(&Self::investments_of(env, &investor, &asset_id));
+        }
+        
+        asset_investments
+    }
+
+    /// Get total pool balance
+    pub fn get_pool_balance(env: &Env) -> i128 {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        data.total_pool_balance
+    }
+
+    /// Check core accounting invariants; emits an alarm and optionally pauses on violation
+    pub fn verify_invariants(env: &Env) -> Vec<Symbol> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        let mut violations: Vec<Symbol> = vec![env];
+
+        // Each asset's investment records must sum to its funded amount, its share balances
+        // to its share supply, and operator releases can't exceed what it raised
+        let mut shares_ok = true;
+        let mut supply_ok = true;
+        let mut releases_ok = true;
+        for asset_id in Self::asset_ids(env).iter() {
+            let asset = Self::load_asset(env, &asset_id).unwrap();
+            let mut invested: i128 = 0;
+            let mut held: i128 = 0;
+            for investor in Self::holders_of(env, &asset_id).iter() {
+                invested += Self::invested_amount(env, &investor, &asset_id);
+                held += Self::shares_of(env, &asset_id, &investor);
+            }
+            if invested != asset.funded_amount {
+                shares_ok = false;
+            }
+            if held != Self::outstanding_shares(env, &asset_id) {
+                supply_ok = false;
+            }
+            if env.storage().persistent().get(&StorageKey::Released(asset_id.clone())).unwrap_or(0) > asset.funded_amount {
+                releases_ok = false;
+            }
+        }
+        if !shares_ok {
+            violations.push_back(symbol_short!("shares"));
+        }
+        if !supply_ok {
+            violations.push_back(symbol_short!("supply"));
+        }
+        if !releases_ok {
+            violations.push_back(symbol_short!("releases"));
+        }
+
+        // Withdrawable payouts must be covered by the pool
+        if data.payouts_owed > data.total_pool_balance {
+            violations.push_back(symbol_short!("payouts"));
+        }
+
+        if data.total_pool_balance < 0 || data.loyalty_reserve < 0 {
+            violations.push_back(symbol_short!("balance"));
+        }
+
+        if !violations.is_empty() {
+            env.events().publish((symbol_short!("inv_alarm"),), violations.clone());
+            if data.auto_pause && !data.paused {
+                data.paused = true;
+                ttl::set_instance(env, &DATA_KEY, &data);
+            }
+        }
+
+        violations
+    }
+
+    /// Enable or disable pausing when an invariant check fails (pauser only)
+    pub fn set_auto_pause(env: &Env, caller: Address, enabled: bool) -> Result<(), ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Pauser, &caller)?;
+
+        data.auto_pause = enabled;
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        Ok(())
+    }
+
+    /// Halt state-changing entrypoints, e.g. while an oracle is compromised; views keep working (pauser only)
+    pub fn pause(env: &Env, caller: Address) -> Result<(), ContractError> {
+        Self::set_paused(env, caller, true)
+    }
+
+    /// Resume state-changing entrypoints after a pause (pauser only)
+    pub fn unpause(env: &Env, caller: Address) -> Result<(), ContractError> {
+        Self::set_paused(env, caller, false)
+    }
+
+    /// Whether state-changing entrypoints are halted
+    pub fn is_paused(env: &Env) -> bool {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        data.paused
+    }
+
+    fn set_paused(env: &Env, caller: Address, paused: bool) -> Result<(), ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Pauser, &caller)?;
+
+        data.paused = paused;
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        let topic = if paused { symbol_short!("paused") } else { symbol_short!("unpaused") };
+        env.events().publish((topic,), env.ledger().timestamp());
+
+        Ok(())
+    }
+
+    /// Switch one entrypoint feature on or off without pausing the whole contract (pauser only)
+    pub fn set_feature(env: &Env, caller: Address, feature: Symbol, enabled: bool) -> Result<(), ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Pauser, &caller)?;
+
+        if let Some(index) = data.disabled_features.first_index_of(&feature) {
+            data.disabled_features.remove(index);
+        }
+        if !enabled {
+            data.disabled_features.push_back(feature.clone());
+        }
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        env.events().publish((symbol_short!("feature"), feature), enabled);
+
+        Ok(())
+    }
+
+    /// Whether an entrypoint feature is switched on
+    pub fn is_feature_enabled(env: &Env, feature: Symbol) -> bool {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        !data.disabled_features.contains(&feature)
+    }
+
+    /// Grant a role to an account (admin only)
+    pub fn grant_role(env: &Env, caller: Address, role: Role, account: Address) -> Result<(), ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+        access::grant(env, role, &account);
+
+        Ok(())
+    }
+
+    /// Revoke a role previously granted to an account (admin only)
+    pub fn revoke_role(env: &Env, caller: Address, role: Role, account: Address) -> Result<(), ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+        access::revoke(env, role, &account).map_err(ContractError::from)
+    }
+
+    /// Whether an account holds a role; the admin holds every role
+    pub fn has_role(env: &Env, role: Role, account: Address) -> bool {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        access::holds(env, &data.admin, role, &account)
+    }
+
+    /// Extend the TTL of the contract instance and its code. Open to anyone, so a keeper can
+    /// stop a quiet contract from being archived between writes
+    pub fn bump_storage(env: &Env) {
+        ttl::extend_instance(env);
+    }
+
+    /// Set the remaining TTL below which writes extend an entry, and the TTL they extend it to (admin only)
+    pub fn set_ttl_config(env: &Env, caller: Address, threshold: u32, extend_to: u32) -> Result<(), ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+        ttl::set_config(env, threshold, extend_to).map_err(ContractError::from)
+    }
+
+    /// Get the storage TTL configuration
+    pub fn get_ttl_config(env: &Env) -> TtlConfig {
+        ttl::config(env)
+    }
+
+    /// Push change notifications to a relay contract, or stop with None (admin only)
+    pub fn set_relay(env: &Env, caller: Address, relay: Option<Address>) -> Result<(), ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+        changes::set_relay(env, relay);
+
+        Ok(())
+    }
+
+    /// Queue an admin change behind the timelock (admin only).
+    /// Supported actions: "oracle" (equity oracle address), "gov", "dlq_bps", "eq_src", "settle"
+    pub fn queue_admin_change(
+        env: &Env,
+        caller: Address,
+        action: Symbol,
+        value: i128,
+        new_address: Option<Address>,
+        delay: u64,
+    ) -> Result<u32, ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        // Only admin can queue changes
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+
+        timelock::queue(env, action, ChangeValue::new(value, new_address), delay, timelock::MIN_DELAY, |change| {
+            Self::validate_admin_change(env, change)
+        })
+    }
+
+    /// Execute a queued admin change once its timelock has elapsed (executor only)
+    pub fn execute_admin_change(env: &Env, caller: Address, change_id: u32) -> Result<(), ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        // Only executors can execute changes
+        access::check(env, &data.admin, Role::Executor, &caller)?;
+
+        timelock::execute(env, change_id, |change| Self::apply_admin_change(env, &mut data, change))?;
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        Ok(())
+    }
+
+    /// Cancel a queued admin change during its cancellation window (admin only)
+    pub fn cancel_admin_change(env: &Env, caller: Address, change_id: u32) -> Result<(), ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        // Only admin can cancel changes
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+
+        timelock::cancel(env, change_id).map_err(ContractError::from)
+    }
+
+    /// Get all admin changes waiting out their timelock
+    pub fn get_pending_changes(env: &Env) -> Vec<PendingChange> {
+        timelock::pending(env)
+    }
+
+    /// Get a page of the append-only log of executed and cancelled admin changes
+    pub fn get_change_log(env: &Env, page: PageRequest) -> ChangeLogPage {
+        timelock::log(env, page)
+    }
+
+    /// Check that a queued change names a known action with a valid value
+    fn validate_admin_change(env: &Env, change: &PendingChange) -> Result<(), ContractError> {
+        if change.action == symbol_short!("oracle")
+            || change.action == symbol_short!("gov")
+            || change.action == symbol_short!("eq_src")
+            || change.action == symbol_short!("settle")
+        {
+            change.address()?;
+        } else if change.action == symbol_short!("dlq_bps") {
+            // Alert threshold (1-10000 bps)
+            if !(1..=10_000).contains(&change.number()?) {
+                return Err(ContractError::InvalidInput);
+            }
+        } else if change.action == symbol_short!("fund_per") {
+            if change.number()? <= 0 {
+                return Err(ContractError::InvalidInput);
+            }
+        } else if change.action == symbol_short!("eq_floor") {
+            // Equity score (0-100)
+            let (_, min_equity_score): (Symbol, i32) = change.decode(env)?;
+            if !(0..=100).contains(&min_equity_score) {
+                return Err(ContractError::InvalidInput);
+            }
+        } else if change.action == symbol_short!("premium") {
+            Self::check_premium_params(&change.decode(env)?)?;
+        } else if change.action == symbol_short!("approval") {
+            let rule: ApprovalRule = change.decode(env)?;
+            if !rule.is_valid() {
+                return Err(ContractError::InvalidInput);
+            }
+        } else {
+            return Err(ContractError::InvalidInput);
+        }
+
+        Ok(())
+    }
+
+    /// Apply an admin change to contract state
+    fn apply_admin_change(env: &Env, data: &mut DataKey, change: &PendingChange) -> Result<(), ContractError> {
+        Self::validate_admin_change(env, change)?;
+
+        if change.action == symbol_short!("oracle") {
+            data.equity_oracle = change.address()?;
+        } else if change.action == symbol_short!("gov") {
+            ttl::set_persistent(env, &StorageKey::Governance, &change.address()?);
+        } else if change.action == symbol_short!("dlq_bps") {
+            data.delinquency_alert_bps = change.number()? as i32;
+        } else if change.action == symbol_short!("eq_src") {
+            ttl::set_persistent(env, &StorageKey::EquitySource, &change.address()?);
+        } else if change.action == symbol_short!("settle") {
+            ttl::set_persistent(env, &StorageKey::SettlementAsset, &change.address()?);
+        } else if change.action == symbol_short!("fund_per") {
+            data.funding_period = change.number()? as u64;
+        } else if change.action == symbol_short!("eq_floor") {
+            let (program, min_equity_score): (Symbol, i32) = change.decode(env)?;
+            ttl::set_persistent(env, &StorageKey::EquityFloor(program.clone()), &min_equity_score);
+        } else if change.action == symbol_short!("premium") {
+            ttl::set_persistent(env, &StorageKey::PremiumParams, &change.decode::<PremiumParams>(env)?);
+        } else if change.action == symbol_short!("approval") {
+            data.approval_rule = change.decode(env)?;
+        }
+
+        Ok(())
+    }
+
+    /// Withdrawable payouts held for a recipient
+    fn payout_balance(env: &Env, recipient: &Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&StorageKey::PayoutBalance(recipient.clone()))
+            .unwrap_or(0)
+    }
+
+    /// Add to a recipient's withdrawable payouts, counting them as owed out of the pool
+    fn credit_payout(env: &Env, data: &mut DataKey, recipient: &Address, amount: i128) {
+        ttl::set_persistent(env, &StorageKey::PayoutBalance(recipient.clone()), &(Self::payout_balance(env, recipient) + amount));
+        data.payouts_owed += amount;
+    }
+
+    /// Get a page of boost audit records for an address, oldest first
+    pub fn get_boost_audit_by_address(env: &Env, subject: Address, page: PageRequest) -> BoostAuditPage {
+        let (items, next_cursor) = journal::page(env, &(BOOST_BY_SUBJECT, subject), &page);
+        BoostAuditPage { items, next_cursor }
+    }
+
+    /// Get a page of boost audit records for a rule ("inv_bonus"), oldest first
+    pub fn get_boost_audit_by_rule(env: &Env, rule: Symbol, page: PageRequest) -> BoostAuditPage {
+        let (items, next_cursor) = journal::page(env, &(BOOST_BY_RULE, rule), &page);
+        BoostAuditPage { items, next_cursor }
+    }
+
+    /// Append a boost audit record to its subject's and its rule's streams
+    fn log_boost(env: &Env, subject: &Address, amount: i128, rule: Symbol, data_version: u64) {
+        let record = BoostAuditRecord {
+            subject: subject.clone(),
+            amount,
+            rule: rule.clone(),
+            data_version,
+            timestamp: env.ledger().timestamp(),
+        };
+        journal::append(env, &(BOOST_BY_SUBJECT, subject.clone()), &record);
+        journal::append(env, &(BOOST_BY_RULE, rule), &record);
+    }
+
+    /// Append a principal event to its investor's stream
+    fn log_principal_event(env: &Env, event: &PrincipalEvent) {
+        journal::append(env, &(PRINCIPAL_KEY, event.investor.clone()), event);
+    }
+
+    /// Operating risk for a location from the canonical equity source, if one is set
+    fn location_risk(env: &Env, location: &Symbol) -> Option<RiskData> {
+        let source = Self::equity_source(env)?;
+        EquityOracleClient::new(env, &source).peek_risk_data(location)
+    }
+
+    /// Refresh an asset's risk disclosure from the latest oracle data. Anyone can call this.
+    pub fn refresh_risk_disclosure(env: &Env, asset_id: Symbol) -> Result<Option<RiskData>, ContractError> {
+        let asset = Self::load_asset(env, &asset_id).ok_or(ContractError::AssetNotFound)?;
+
+        let risk = Self::location_risk(env, &asset.location);
+        let key = StorageKey::RiskDisclosure(asset_id.clone());
+        match &risk {
+            Some(risk) => ttl::set_persistent(env, &key, risk),
+            None => env.storage().persistent().remove(&key),
+        }
+        changes::touch(env, symbol_short!("asset"), asset_id.clone());
+
+        Ok(risk)
+    }
+
+    /// Get an asset's risk disclosure as last refreshed from the oracle
+    pub fn get_risk_disclosure(env: &Env, asset_id: Symbol) -> Option<RiskData> {
+        env.storage().persistent().get(&StorageKey::RiskDisclosure(asset_id))
+    }
+
+    /// Queue the insurance premium pricing inputs, each capped at MAX_PREMIUM_INPUT_BPS, behind
+    /// the timelock; returns the change id (governance only)
+    pub fn set_premium_params(env: &Env, params: PremiumParams) -> Result<u32, ContractError> {
+        Self::governance(env).ok_or(ContractError::NoAddress)?.require_auth();
+
+        let value = ChangeValue::Encoded(params.to_xdr(env));
+        timelock::queue(env, symbol_short!("premium"), value, timelock::MIN_DELAY, timelock::MIN_DELAY, |change| {
+            Self::validate_admin_change(env, change)
+        })
+    }
+
+    /// Reject premium inputs above MAX_PREMIUM_INPUT_BPS
+    fn check_premium_params(params: &PremiumParams) -> Result<(), ContractError> {
+        if params.base_bps > MAX_PREMIUM_INPUT_BPS
+            || params.risk_point_bps > MAX_PREMIUM_INPUT_BPS
+            || params.breach_bps > MAX_PREMIUM_INPUT_BPS
+            || params.type_loadings.values().iter().any(|bps| bps > MAX_PREMIUM_INPUT_BPS)
+        {
+            return Err(ContractError::InvalidInput);
+        }
+
+        Ok(())
+    }
+
+    /// Get the insurance premium pricing inputs
+    pub fn get_premium_params(env: &Env) -> Option<PremiumParams> {
+        Self::premium_params(env)
+    }
+
+    /// Quote an asset's annual insurance premium from its location's current risk feed, its type
+    /// and its operator's SLA record, capped at MAX_PREMIUM_BPS of the asset's target amount.
+    /// There is no separate insurance contract: the pool owns the asset registry, the risk
+    /// disclosures and the operator SLAs a quote is priced from, so quoting lives here
+    pub fn quote_premium(env: &Env, asset_id: Symbol) -> Result<PremiumQuote, ContractError> {
+        let params = Self::premium_params(env).ok_or(ContractError::NotFound)?;
+        let asset = Self::load_asset(env, &asset_id).ok_or(ContractError::AssetNotFound)?;
+
+        // Prefer the live feed; fall back to the disclosure recorded at listing
+        let risk_points = Self::location_risk(env, &asset.location)
+            .or(Self::get_risk_disclosure(env, asset_id.clone()))
+            .map(|risk| risk.premium())
+            .unwrap_or(0);
+        let operator_breaches = env.storage().persistent().get::<_, OperatorSla>(&StorageKey::OperatorSla(asset_id.clone())).map(|sla| sla.consecutive_breaches).unwrap_or(0);
+
+        let premium_bps = (params.base_bps
+            + params.risk_point_bps * risk_points as u32
+            + params.type_loadings.get(asset.asset_type).unwrap_or(0)
+            + params.breach_bps * operator_breaches)
+            .min(MAX_PREMIUM_BPS);
+
+        Ok(PremiumQuote {
+            asset_id,
+            insured_value: asset.target_amount,
+            risk_points,
+            operator_breaches,
+            premium_bps,
+            annual_premium: asset.target_amount * premium_bps as i128 / 10_000,
+            quoted_at: env.ledger().timestamp(),
+        })
+    }
+
+    /// AI-driven equity score calculation (mocked for demo)
+    fn calculate_equity_score(env: &Env, location: &Symbol) -> i32 {
+        // Prefer the rate adjuster's canonical cached score when one is available
+        if let Some(source) = Self::equity_source(env) {
+            let cached = EquityOracleClient::new(env, &source).peek_equity_score(location);
+            if let Some(score) = cached {
+                return score;
+            }
+        }
+
+        // In a real implementation, this would call the AI oracle
+        // For demo purposes, we'll use a simple algorithm based on location hash
+        let hash = env.crypto().sha256(&location.clone().to_xdr(env)).to_array();
+        
+        // Convert first byte to equity score (0-100)
+        let score = (hash[0] as i32) % 101;
+        
+        // Ensure underserved areas get higher equity scores
+        if Self::is_underserved_zone(env, location) {
+            score + 20
+        } else {
             score
         }
     }
 
-    /// Calculate investor equity bonus based on location and investment history
-    fn calculate_investor_equity_bonus(env: &Env, investor: &Address, location: &Symbol) -> i32 {
-        // In a real implementation, this would analyze:
-        // - Investor's location (lower income areas get higher bonuses)
-        // - Investment history (first-time investors get bonuses)
-        // - Community impact metrics
-        
-        let investor_str = investor.to_string();
-        let location_str = location.to_string();
-        
-        let mut bonus = 0;
-        
-        // Bonus for underserved area investments
-        if location_str.contains("low_income") || location_str.contains("underserved") {
-            bonus += 15;
+    /// Whether a zone's name marks it as low-income or underserved
+    fn is_underserved_zone(env: &Env, location: &Symbol) -> bool {
+        let name = location.clone().to_xdr(env);
+        Self::bytes_contain(&name, b"low_income") || Self::bytes_contain(&name, b"underserved")
+    }
+
+    fn bytes_contain(haystack: &Bytes, needle: &[u8]) -> bool {
+        let len = needle.len() as u32;
+        if haystack.len() < len {
+            return false;
+        }
+        (0..=haystack.len() - len).any(|start| (0..len).all(|i| haystack.get_unchecked(start + i) == needle[i as usize]))
+    }
+
+    /// Calculate investor equity bonus based on location and investment history
+    fn calculate_investor_equity_bonus(env: &Env, investor: &Address, location: &Symbol) -> i32 {
+        // In a real implementation, this would analyze:
+        // - Investor's location (lower income areas get higher bonuses)
+        // - Investment history (first-time investors get bonuses)
+        // - Community impact metrics
+        
+        let mut bonus = 0;
+        
+        // Bonus for underserved area investments
+        if Self::is_underserved_zone(env, location) {
+            bonus += 15;
+        }
+        
+        // Bonus for first-time investors: no exposure has been recorded for them yet
+        if !env.storage().persistent().has(&StorageKey::Exposure(investor.clone())) {
+            bonus += 10;
+        }
+        
+        // Cap bonus at 25%
+        if bonus > 25 {
+            bonus = 25;
+        }
+        
+        bonus
+    }
+
+    /// Update an asset's accrued interest and expected losses (oracle only)
+    pub fn update_asset_valuation(
+        env: &Env,
+        asset_id: Symbol,
+        accrued_interest: i128,
+        expected_loss: i128,
+    ) -> Result<i128, ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        // Only oracle can update valuations
+        data.equity_oracle.require_auth();
+
+        Gate::new(data.paused, &data.disabled_features, &symbol_short!("oracle")).check()?;
+
+        if accrued_interest < 0 || expected_loss < 0 {
+            return Err(ContractError::InvalidInput);
+        }
+
+        let asset = Self::load_asset(env, &asset_id).ok_or(ContractError::AssetNotFound)?;
+        let mut valuation = Self::get_or_create_valuation(env, &asset_id);
+        valuation.accrued_interest = accrued_interest;
+        valuation.expected_loss = expected_loss;
+        ttl::set_persistent(env, &StorageKey::Valuation(asset_id.clone()), &valuation);
+
+        Ok(Self::calculate_nav(&asset, &valuation))
+    }
+
+    /// Build an amortizing schedule for a deployed asset from its approved loan application (admin only).
+    /// Principal is what the loan drew from the asset, split evenly; each installment's interest accrues
+    /// on the principal still outstanding at the application's adjusted rate
+    pub fn create_repayment_schedule(
+        env: &Env,
+        caller: Address,
+        asset_id: Symbol,
+        application_id: Symbol,
+        installment_count: u32,
+        interval: u64,
+    ) -> Result<RepaymentSchedule, ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+
+        if installment_count == 0 || interval == 0 {
+            return Err(ContractError::InvalidInput);
+        }
+
+        let asset = Self::load_asset(env, &asset_id).ok_or(ContractError::AssetNotFound)?;
+        if asset.status != symbol_short!("deployed") {
+            return Err(ContractError::InvalidStatus);
+        }
+
+        if Self::load_schedule(env, &asset_id).is_some() {
+            return Err(ContractError::InvalidStatus);
+        }
+
+        // Terms come from the rate adjuster's approved application for this asset
+        let source = Self::equity_source(env).ok_or(ContractError::ApplicationNotFound)?;
+        let (borrower, loan_asset, annual_rate, requested) = EquityOracleClient::new(env, &source)
+            .peek_approved_loan(&application_id)
+            .ok_or(ContractError::ApplicationNotFound)?;
+        if loan_asset != asset_id {
+            return Err(ContractError::InvalidInput);
+        }
+
+        let now = env.ledger().timestamp();
+        let principal = requested.min(asset.funded_amount);
+        let per_installment = principal / installment_count as i128;
+        let mut remaining = principal;
+        let mut installments = vec![env];
+
+        for i in 0..installment_count {
+            // The last installment picks up the rounding remainder
+            let principal_part = if i + 1 == installment_count { remaining } else { per_installment };
+            let interest = remaining * annual_rate as i128 * interval as i128 / (100 * SECONDS_PER_YEAR as i128);
+            installments.push_back(Installment {
+                number: i + 1,
+                due_at: now + interval * (i as u64 + 1),
+                principal: principal_part,
+                interest,
+                paid: 0,
+            });
+            remaining -= principal_part;
+        }
+
+        let schedule = RepaymentSchedule {
+            asset_id: asset_id.clone(),
+            application_id,
+            borrower,
+            principal,
+            annual_rate,
+            interval,
+            installments,
+            next_installment: 0,
+            total_paid: 0,
+            created_at: now,
+            completed_at: None,
+        };
+
+        // Keep the delinquency pipeline on the schedule's due dates
+        let first = schedule.installments.get(0).unwrap();
+        ttl::set_persistent(env, &StorageKey::Servicing(asset_id.clone()), &LoanServicing {
+                asset_id: asset_id.clone(),
+                payment_interval: interval,
+                amount_due: first.principal + first.interest,
+                next_due_at: first.due_at,
+                paid_this_period: 0,
+                stage: symbol_short!("current"),
+                stage_entered_at: now,
+            });
+
+        Self::save_schedule(env, &schedule);
+
+        env.events().publish((symbol_short!("schedule"), asset_id), (principal, annual_rate, installment_count));
+
+        Ok(schedule)
+    }
+
+    /// Pay an asset's scheduled installments in the settlement asset. Payments cover the oldest
+    /// installment first, interest before principal. Returns the amount paid out to the exit queue
+    pub fn record_repayment(env: &Env, payer: Address, asset_id: Symbol, amount: i128) -> Result<i128, ContractError> {
+        payer.require_auth();
+
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        let mut schedule = Self::load_schedule(env, &asset_id).ok_or(ContractError::NotFound)?;
+
+        if amount <= 0 || amount > Self::schedule_outstanding(&schedule) {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        let token = Self::settlement_asset(env).ok_or(ContractError::NoSettlementAsset)?;
+        TokenClient::new(env, &token).transfer(&payer, &env.current_contract_address(), &amount);
+
+        let paid = Self::apply_scheduled_payment(env, &mut data, &mut schedule, &payer, amount);
+
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        Ok(paid)
+    }
+
+    /// Apply a received payment to a schedule's installments, oldest first, then to the asset's
+    /// valuation and servicing. Returns the amount paid out to the exit queue
+    fn apply_scheduled_payment(
+        env: &Env,
+        data: &mut DataKey,
+        schedule: &mut RepaymentSchedule,
+        payer: &Address,
+        amount: i128,
+    ) -> i128 {
+        let asset_id = schedule.asset_id.clone();
+
+        // Walk installments oldest first, splitting the payment into interest and principal
+        let mut left = amount;
+        let mut principal: i128 = 0;
+        let mut interest: i128 = 0;
+        while left > 0 && schedule.next_installment < schedule.installments.len() {
+            let mut installment = schedule.installments.get(schedule.next_installment).unwrap();
+
+            let interest_due = if installment.paid < installment.interest { installment.interest - installment.paid } else { 0 };
+            let to_interest = if left < interest_due { left } else { interest_due };
+            left -= to_interest;
+
+            let principal_due = installment.principal + installment.interest - installment.paid - to_interest;
+            let to_principal = if left < principal_due { left } else { principal_due };
+            left -= to_principal;
+
+            installment.paid += to_interest + to_principal;
+            interest += to_interest;
+            principal += to_principal;
+
+            let fully_paid = installment.paid == installment.principal + installment.interest;
+            schedule.installments.set(schedule.next_installment, installment);
+            if fully_paid {
+                schedule.next_installment += 1;
+            }
+        }
+
+        schedule.total_paid += amount;
+        if schedule.next_installment == schedule.installments.len() {
+            schedule.completed_at = Some(env.ledger().timestamp());
+            env.events().publish((symbol_short!("repaid"), asset_id.clone()), schedule.total_paid);
+        }
+        Self::save_schedule(env, schedule);
+
+        env.events().publish((symbol_short!("repay"), asset_id.clone()), (payer.clone(), principal, interest));
+
+        Self::apply_repayment(env, data, &asset_id, principal, interest, Some(&*schedule))
+    }
+
+    /// Record a repayment settled outside the contract (treasurer only) and service the exit queue with it
+    pub fn record_offchain_repayment(
+        env: &Env,
+        caller: Address,
+        asset_id: Symbol,
+        principal: i128,
+        interest: i128,
+    ) -> Result<i128, ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        // Only treasurers can record repayments
+        access::check(env, &data.admin, Role::Treasurer, &caller)?;
+
+        if principal < 0 || interest < 0 || principal + interest == 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        Self::load_asset(env, &asset_id).ok_or(ContractError::AssetNotFound)?;
+
+        let paid = Self::apply_repayment(env, &mut data, &asset_id, principal, interest, None);
+
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        Ok(paid)
+    }
+
+    /// Get an asset's repayment schedule
+    pub fn get_repayment_schedule(env: &Env, asset_id: Symbol) -> Result<RepaymentSchedule, ContractError> {
+        Self::load_schedule(env, &asset_id).ok_or(ContractError::NotFound)
+    }
+
+    /// Get the principal and interest still owed on an asset's schedule
+    pub fn get_outstanding_balance(env: &Env, asset_id: Symbol) -> Result<i128, ContractError> {
+        let schedule = Self::load_schedule(env, &asset_id).ok_or(ContractError::NotFound)?;
+        Ok(Self::schedule_outstanding(&schedule))
+    }
+
+    /// Get the oldest installment not yet fully paid
+    pub fn get_next_installment(env: &Env, asset_id: Symbol) -> Result<Installment, ContractError> {
+        let schedule = Self::load_schedule(env, &asset_id).ok_or(ContractError::NotFound)?;
+        schedule.installments.get(schedule.next_installment).ok_or(ContractError::Done)
+    }
+
+    /// Enroll in autopay for an asset's schedule, capping each pull (schedule borrower only).
+    /// The borrower must also approve the pool as spender on the settlement token
+    pub fn enroll_autopay(env: &Env, borrower: Address, asset_id: Symbol, max_amount: i128) -> Result<(), ContractError> {
+        borrower.require_auth();
+
+        let schedule = Self::load_schedule(env, &asset_id).ok_or(ContractError::NotFound)?;
+        if schedule.borrower != borrower {
+            return Err(ContractError::Unauthorized);
+        }
+
+        if schedule.completed_at.is_some() {
+            return Err(ContractError::Done);
+        }
+
+        if max_amount <= 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        let last_installment = Self::load_autopay(env, &asset_id).map(|autopay| autopay.last_installment).unwrap_or(0);
+        Self::save_autopay(env, &Autopay {
+            asset_id: asset_id.clone(),
+            borrower: borrower.clone(),
+            max_amount,
+            enrolled_at: env.ledger().timestamp(),
+            last_installment,
+            active: true,
+        });
+
+        env.events().publish((symbol_short!("autopay"), asset_id), (borrower, max_amount));
+
+        Ok(())
+    }
+
+    /// Stop autopay for an asset's schedule (schedule borrower only)
+    pub fn cancel_autopay(env: &Env, borrower: Address, asset_id: Symbol) -> Result<(), ContractError> {
+        borrower.require_auth();
+
+        let mut autopay = Self::load_autopay(env, &asset_id).ok_or(ContractError::NotFound)?;
+        if autopay.borrower != borrower {
+            return Err(ContractError::Unauthorized);
+        }
+
+        autopay.active = false;
+        Self::save_autopay(env, &autopay);
+
+        Ok(())
+    }
+
+    /// Pull the installment now due from an enrolled borrower (keeper call, anyone can trigger it).
+    /// Each installment is pulled at most once. If the allowance or balance can't cover the pull,
+    /// autopay is suspended and the schedule falls back to manual payment, starting the grace period.
+    /// Returns the amount collected
+    pub fn collect_autopay(env: &Env, asset_id: Symbol) -> Result<i128, ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        let mut autopay = Self::load_autopay(env, &asset_id).ok_or(ContractError::NotFound)?;
+        if !autopay.active {
+            return Err(ContractError::InvalidStatus);
+        }
+
+        let mut schedule = Self::load_schedule(env, &asset_id).ok_or(ContractError::NotFound)?;
+        let installment = schedule.installments.get(schedule.next_installment).ok_or(ContractError::Done)?;
+
+        let now = env.ledger().timestamp();
+        if now < installment.due_at || installment.number <= autopay.last_installment {
+            return Err(ContractError::TooEarly);
+        }
+
+        let due = installment.principal + installment.interest - installment.paid;
+        let amount = if due < autopay.max_amount { due } else { autopay.max_amount };
+
+        let token_address = Self::settlement_asset(env).ok_or(ContractError::NoSettlementAsset)?;
+        let token = TokenClient::new(env, &token_address);
+        let pool = env.current_contract_address();
+
+        if token.allowance(&autopay.borrower, &pool) < amount || token.balance(&autopay.borrower) < amount {
+            autopay.active = false;
+            Self::save_autopay(env, &autopay);
+
+            // Manual payment from here on, with the grace period running from the failed pull
+            if let Some(mut servicing) = env.storage().persistent().get::<_, LoanServicing>(&StorageKey::Servicing(asset_id.clone())) {
+                if servicing.stage == symbol_short!("current") {
+                    servicing.stage = symbol_short!("grace");
+                    servicing.stage_entered_at = now;
+                    ttl::set_persistent(env, &StorageKey::Servicing(asset_id.clone()), &servicing);
+                    env.events().publish((symbol_short!("delinq"), asset_id.clone()), servicing.stage.clone());
+                }
+            }
+            ttl::set_instance(env, &DATA_KEY, &data);
+
+            env.events().publish((symbol_short!("autopay_f"), asset_id), (installment.number, amount));
+
+            return Ok(0);
+        }
+
+        token.transfer_from(&pool, &autopay.borrower, &pool, &amount);
+        autopay.last_installment = installment.number;
+        Self::save_autopay(env, &autopay);
+
+        Self::apply_scheduled_payment(env, &mut data, &mut schedule, &autopay.borrower, amount);
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        Ok(amount)
+    }
+
+    /// Get an asset's autopay enrollment
+    pub fn get_autopay(env: &Env, asset_id: Symbol) -> Result<Autopay, ContractError> {
+        Self::load_autopay(env, &asset_id).ok_or(ContractError::NotFound)
+    }
+
+    /// Load an asset's autopay enrollment from its own entry
+    fn load_autopay(env: &Env, asset_id: &Symbol) -> Option<Autopay> {
+        env.storage().persistent().get(&StorageKey::Autopay(asset_id.clone()))
+    }
+
+    /// Write an asset's autopay enrollment
+    fn save_autopay(env: &Env, autopay: &Autopay) {
+        ttl::set_persistent(env, &StorageKey::Autopay(autopay.asset_id.clone()), autopay);
+    }
+
+    /// Load an asset's repayment schedule from its own entry
+    fn load_schedule(env: &Env, asset_id: &Symbol) -> Option<RepaymentSchedule> {
+        env.storage().persistent().get(&StorageKey::Schedule(asset_id.clone()))
+    }
+
+    /// Write an asset's repayment schedule
+    fn save_schedule(env: &Env, schedule: &RepaymentSchedule) {
+        ttl::set_persistent(env, &StorageKey::Schedule(schedule.asset_id.clone()), schedule);
+    }
+
+    /// Scheduled principal and interest not yet paid
+    fn schedule_outstanding(schedule: &RepaymentSchedule) -> i128 {
+        let mut outstanding: i128 = 0;
+        for installment in schedule.installments.iter() {
+            outstanding += installment.principal + installment.interest - installment.paid;
+        }
+        outstanding
+    }
+
+    /// Credit a repayment to the asset's valuation and servicing state, then service the exit queue.
+    /// Scheduled repayments move servicing to the next unpaid installment instead of fixed periods
+    fn apply_repayment(
+        env: &Env,
+        data: &mut DataKey,
+        asset_id: &Symbol,
+        principal: i128,
+        interest: i128,
+        schedule: Option<&RepaymentSchedule>,
+    ) -> i128 {
+        let mut valuation = Self::get_or_create_valuation(env, asset_id);
+        valuation.principal_repaid += principal;
+        valuation.accrued_interest = if valuation.accrued_interest > interest {
+            valuation.accrued_interest - interest
+        } else {
+            0
+        };
+        valuation.liquidity += principal + interest;
+        ttl::set_persistent(env, &StorageKey::Valuation(asset_id.clone()), &valuation);
+        data.total_pool_balance += principal + interest;
+        Self::count_servicing(env, principal, interest, 0);
+
+        // Apply the payment to the servicing schedule
+        if let Some(mut servicing) = env.storage().persistent().get::<_, LoanServicing>(&StorageKey::Servicing(asset_id.clone())) {
+            match schedule.and_then(|schedule| schedule.installments.get(schedule.next_installment)) {
+                Some(next) => {
+                    servicing.amount_due = next.principal + next.interest;
+                    servicing.paid_this_period = next.paid;
+                    servicing.next_due_at = next.due_at;
+                }
+                None if schedule.is_some() => {
+                    // Fully repaid: nothing further falls due
+                    servicing.amount_due = 0;
+                    servicing.paid_this_period = 0;
+                    servicing.next_due_at = u64::MAX;
+                }
+                None => {
+                    servicing.paid_this_period += principal + interest;
+                    while servicing.amount_due > 0 && servicing.paid_this_period >= servicing.amount_due {
+                        servicing.paid_this_period -= servicing.amount_due;
+                        servicing.next_due_at += servicing.payment_interval;
+                    }
+                }
+            }
+            if env.ledger().timestamp() <= servicing.next_due_at && servicing.stage != symbol_short!("current") {
+                servicing.stage = symbol_short!("current");
+                servicing.stage_entered_at = env.ledger().timestamp();
+                env.events().publish((symbol_short!("delinq"), asset_id.clone()), servicing.stage.clone());
+            }
+            ttl::set_persistent(env, &StorageKey::Servicing(asset_id.clone()), &servicing);
+            Self::check_portfolio_delinquency(env, data);
+        }
+
+        Self::service_exit_queue(env, data, asset_id)
+    }
+
+    /// Request redemption of investment units at net asset value
+    pub fn request_redemption(
+        env: &Env,
+        investor: Address,
+        asset_id: Symbol,
+        units: i128,
+    ) -> Result<u32, ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        Gate::new(data.paused, &data.disabled_features, &symbol_short!("redeem")).check()?;
+
+        if units <= 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        let asset = Self::load_asset(env, &asset_id).ok_or(ContractError::AssetNotFound)?;
+
+        // Only long-lived (deployed or completed) assets can be redeemed
+        if asset.status != symbol_short!("deployed") && asset.status != symbol_short!("completed") {
+            return Err(ContractError::InvalidStatus);
+        }
+
+        // Units already queued or redeemed, or backing a minted position, cannot be requested again
+        let invested = Self::invested_amount(env, &investor, &asset_id);
+        let committed = Self::exit_committed(env, &investor, &asset_id) + Self::locked_position(env, &investor, &asset_id);
+        if invested - committed < units {
+            return Err(ContractError::NoFunds);
+        }
+
+        let request_id = data.next_exit_id;
+        let request = ExitRequest {
+            id: request_id,
+            investor: investor.clone(),
+            asset_id: asset_id.clone(),
+            units,
+            filled_units: 0,
+            paid_amount: 0,
+            requested_at: env.ledger().timestamp(),
+            status: symbol_short!("queued"),
+        };
+        ttl::set_persistent(env, &StorageKey::Exit(request_id), &request);
+        data.next_exit_id += 1;
+
+        let mut queue = Self::queued_exit_ids(env, &asset_id);
+        queue.push_back(request_id);
+        ttl::set_persistent(env, &StorageKey::ExitQueue(asset_id.clone()), &queue);
+        let investor_key = StorageKey::InvestorExits(investor, asset_id.clone());
+        let mut ids: Vec<u32> = env.storage().persistent().get(&investor_key).unwrap_or(vec![env]);
+        ids.push_back(request_id);
+        ttl::set_persistent(env, &investor_key, &ids);
+
+        // Fill immediately if repaid cash is already waiting
+        Self::service_exit_queue(env, &mut data, &asset_id);
+
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        Ok(request_id)
+    }
+
+    /// Cancel the unfilled remainder of an exit request
+    pub fn cancel_redemption(env: &Env, request_id: u32) -> Result<(), ContractError> {
+        let mut request = Self::exit_request(env, request_id)?;
+
+        if request.status != symbol_short!("queued") {
+            return Err(ContractError::InvalidStatus);
+        }
+
+        // Partially filled requests keep the units already paid out
+        request.units = request.filled_units;
+        request.status = if request.filled_units > 0 {
+            symbol_short!("filled")
+        } else {
+            symbol_short!("cancelled")
+        };
+        ttl::set_persistent(env, &StorageKey::Exit(request_id), &request);
+        Self::dequeue_exits(env, &request.asset_id, &vec![env, request_id]);
+
+        Ok(())
+    }
+
+    /// Set payment terms for a deployed asset (admin only)
+    pub fn set_payment_terms(
+        env: &Env,
+        caller: Address,
+        asset_id: Symbol,
+        payment_interval: u64,
+        amount_due: i128,
+    ) -> Result<(), ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        // Only admin can set payment terms
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+
+        if payment_interval == 0 || amount_due <= 0 {
+            return Err(ContractError::InvalidInput);
+        }
+
+        let asset = Self::load_asset(env, &asset_id).ok_or(ContractError::AssetNotFound)?;
+        if asset.status != symbol_short!("deployed") {
+            return Err(ContractError::InvalidStatus);
+        }
+
+        let now = env.ledger().timestamp();
+        let servicing = LoanServicing {
+            asset_id: asset_id.clone(),
+            payment_interval,
+            amount_due,
+            next_due_at: now + payment_interval,
+            paid_this_period: 0,
+            stage: symbol_short!("current"),
+            stage_entered_at: now,
+        };
+        ttl::set_persistent(env, &StorageKey::Servicing(asset_id.clone()), &servicing);
+
+        Ok(())
+    }
+
+    /// Advance an overdue asset one step through the delinquency pipeline (keeper call).
+    /// grace -> reminder -> restructuring offer -> delinquent -> default
+    pub fn advance_delinquency(env: &Env, asset_id: Symbol) -> Result<Symbol, ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        let mut servicing = env.storage().persistent().get::<_, LoanServicing>(&StorageKey::Servicing(asset_id.clone())).ok_or(ContractError::NotFound)?;
+        let now = env.ledger().timestamp();
+
+        if now <= servicing.next_due_at || servicing.stage == symbol_short!("default") {
+            return Ok(servicing.stage);
+        }
+
+        // Every stage waits longer in a seasonal low
+        let overdue_for = now - servicing.next_due_at;
+        let factor = Self::seasonal_factor(env, &asset_id);
+        let after = |period: u64| seasonality::stretch_period(period, factor);
+        let next_stage = if servicing.stage == symbol_short!("current") {
+            symbol_short!("grace")
+        } else if servicing.stage == symbol_short!("grace") && overdue_for > after(GRACE_PERIOD) {
+            symbol_short!("reminder")
+        } else if servicing.stage == symbol_short!("reminder") && overdue_for > after(RESTRUCTURE_AFTER) {
+            symbol_short!("restruct")
+        } else if servicing.stage == symbol_short!("restruct") && overdue_for > after(DELINQUENT_AFTER) {
+            symbol_short!("delinq")
+        } else if servicing.stage == symbol_short!("delinq") && overdue_for > after(DEFAULT_AFTER) {
+            symbol_short!("default")
+        } else {
+            return Ok(servicing.stage);
+        };
+
+        servicing.stage = next_stage.clone();
+        servicing.stage_entered_at = now;
+        ttl::set_persistent(env, &StorageKey::Servicing(asset_id.clone()), &servicing);
+
+        // Reminder and restructuring offers are delivered off-chain from these events
+        env.events().publish((symbol_short!("delinq"), asset_id.clone()), next_stage.clone());
+
+        if next_stage == symbol_short!("default") {
+            Self::record_writeoffs(env, &mut data, &asset_id);
+            Self::count_servicing(env, 0, 0, 1);
+        }
+
+        Self::check_portfolio_delinquency(env, &mut data);
+
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        Ok(next_stage)
+    }
+
+    /// Get payment terms and delinquency state for an asset
+    pub fn get_loan_servicing(env: &Env, asset_id: Symbol) -> Result<LoanServicing, ContractError> {
+        env.storage().persistent().get(&StorageKey::Servicing(asset_id.clone())).ok_or(ContractError::NotFound)
+    }
+
+    /// Get the share of outstanding principal in delinquent or defaulted assets (basis points)
+    pub fn get_portfolio_delinquency(env: &Env) -> i32 {
+        Self::calculate_portfolio_delinquency(env)
+    }
+
+    /// Share of outstanding principal in delinquent or defaulted assets (basis points)
+    fn calculate_portfolio_delinquency(env: &Env) -> i32 {
+        let mut total_outstanding: i128 = 0;
+        let mut delinquent_outstanding: i128 = 0;
+
+        for asset_id in Self::asset_ids(env).iter() {
+            let servicing: LoanServicing = match env.storage().persistent().get(&StorageKey::Servicing(asset_id.clone())) {
+                Some(servicing) => servicing,
+                None => continue,
+            };
+            let asset = match Self::load_asset(env, &asset_id) {
+                Some(asset) => asset,
+                None => continue,
+            };
+            let valuation = Self::get_or_create_valuation(env, &asset_id);
+            let outstanding = asset.funded_amount - valuation.principal_repaid;
+            total_outstanding += outstanding;
+            if servicing.stage == symbol_short!("delinq") || servicing.stage == symbol_short!("default") {
+                delinquent_outstanding += outstanding;
+            }
+        }
+
+        if total_outstanding <= 0 {
+            return 0;
+        }
+        (delinquent_outstanding * 10_000 / total_outstanding) as i32
+    }
+
+    /// Notify governance when portfolio delinquency crosses the alert threshold
+    fn check_portfolio_delinquency(env: &Env, data: &mut DataKey) {
+        let delinquency_bps = Self::calculate_portfolio_delinquency(env);
+        let above = delinquency_bps >= data.delinquency_alert_bps;
+
+        // Only notify on threshold crossings, not on every call
+        if above == data.delinquency_alert_active {
+            return;
+        }
+        data.delinquency_alert_active = above;
+
+        env.events().publish((symbol_short!("port_dlq"),), (delinquency_bps, above));
+
+        if let Some(governance) = Self::governance(env) {
+            env.invoke_contract::<()>(
+                &governance,
+                &Symbol::new(env, "record_portfolio_alert"),
+                vec![
+                    env,
+                    env.current_contract_address().into_val(env),
+                    delinquency_bps.into_val(env),
+                    above.into_val(env),
+                ],
+            );
+        }
+    }
+
+    /// Get an asset's net asset value: outstanding principal + cash + accrued interest - expected losses
+    pub fn get_asset_nav(env: &Env, asset_id: Symbol) -> Result<i128, ContractError> {
+        let asset = Self::load_asset(env, &asset_id).ok_or(ContractError::AssetNotFound)?;
+        let valuation = Self::get_or_create_valuation(env, &asset_id);
+        Ok(Self::calculate_nav(&asset, &valuation))
+    }
+
+    /// Get an exit request
+    pub fn get_exit_request(env: &Env, request_id: u32) -> Result<ExitRequest, ContractError> {
+        Self::exit_request(env, request_id)
+    }
+
+    fn exit_request(env: &Env, request_id: u32) -> Result<ExitRequest, ContractError> {
+        env.storage()
+            .persistent()
+            .get(&StorageKey::Exit(request_id))
+            .ok_or(ContractError::NotFound)
+    }
+
+    /// Ids of an asset's queued exit requests, oldest first
+    fn queued_exit_ids(env: &Env, asset_id: &Symbol) -> Vec<u32> {
+        env.storage()
+            .persistent()
+            .get(&StorageKey::ExitQueue(asset_id.clone()))
+            .unwrap_or(vec![env])
+    }
+
+    /// Take requests that are no longer queued off an asset's exit queue
+    fn dequeue_exits(env: &Env, asset_id: &Symbol, done: &Vec<u32>) {
+        let mut queue = vec![env];
+        for id in Self::queued_exit_ids(env, asset_id).iter() {
+            if !done.contains(id) {
+                queue.push_back(id);
+            }
+        }
+        ttl::set_persistent(env, &StorageKey::ExitQueue(asset_id.clone()), &queue);
+    }
+
+    /// Every exit request an investor made against an asset, oldest first
+    fn exits_of(env: &Env, investor: &Address, asset_id: &Symbol) -> Vec<ExitRequest> {
+        let ids: Vec<u32> = env
+            .storage()
+            .persistent()
+            .get(&StorageKey::InvestorExits(investor.clone(), asset_id.clone()))
+            .unwrap_or(vec![env]);
+        let mut requests = vec![env];
+        for id in ids.iter() {
+            if let Ok(request) = Self::exit_request(env, id) {
+                requests.push_back(request);
+            }
+        }
+        requests
+    }
+
+    /// Get an exit request's queue position and the NAV value queued ahead of it
+    pub fn get_queue_position(env: &Env, request_id: u32) -> Result<(u32, i128), ContractError> {
+        let request = Self::exit_request(env, request_id)?;
+
+        if request.status != symbol_short!("queued") {
+            return Err(ContractError::InvalidStatus);
+        }
+
+        let asset = Self::load_asset(env, &request.asset_id).ok_or(ContractError::AssetNotFound)?;
+        let valuation = Self::get_or_create_valuation(env, &request.asset_id);
+
+        let mut position: u32 = 0;
+        let mut units_ahead: i128 = 0;
+        for id in Self::queued_exit_ids(env, &request.asset_id).iter() {
+            if id >= request_id {
+                break;
+            }
+            let other = Self::exit_request(env, id).unwrap();
+            position += 1;
+            units_ahead += other.units - other.filled_units;
+        }
+
+        Ok((position, Self::units_to_value(&asset, &valuation, units_ahead)))
+    }
+
+    /// Estimate the further repayments needed before an exit request is fully filled
+    pub fn get_estimated_fulfillment(env: &Env, request_id: u32) -> Result<i128, ContractError> {
+        let request = Self::exit_request(env, request_id)?;
+
+        if request.status != symbol_short!("queued") {
+            return Ok(0);
+        }
+
+        let (_, value_ahead) = Self::get_queue_position(env, request_id)?;
+        let asset = Self::load_asset(env, &request.asset_id).ok_or(ContractError::AssetNotFound)?;
+        let valuation = Self::get_or_create_valuation(env, &request.asset_id);
+        let own_value = Self::units_to_value(&asset, &valuation, request.units - request.filled_units);
+
+        let needed = value_ahead + own_value - valuation.liquidity;
+        Ok(if needed > 0 { needed } else { 0 })
+    }
+
+    /// Pay queued exit requests for an asset in FIFO order from available liquidity, crediting
+    /// each investor a withdrawable payout
+    fn service_exit_queue(env: &Env, data: &mut DataKey, asset_id: &Symbol) -> i128 {
+        let asset = match Self::load_asset(env, asset_id) {
+            Some(asset) => asset,
+            None => return 0,
+        };
+        let mut total_paid: i128 = 0;
+        let mut filled = vec![env];
+
+        for id in Self::queued_exit_ids(env, asset_id).iter() {
+            let mut request = Self::exit_request(env, id).unwrap();
+
+            let mut valuation = Self::get_or_create_valuation(env, asset_id);
+            if valuation.liquidity <= 0 {
+                break;
+            }
+
+            // Price remaining units at the current NAV per unit
+            let remaining_units = request.units - request.filled_units;
+            let value = Self::units_to_value(&asset, &valuation, remaining_units);
+            let (units, paid) = if value <= valuation.liquidity {
+                (remaining_units, value)
+            } else {
+                let units = remaining_units * valuation.liquidity / value;
+                (units, Self::units_to_value(&asset, &valuation, units))
+            };
+
+            if units == 0 {
+                break;
+            }
+
+            valuation.liquidity -= paid;
+            valuation.redeemed_units += units;
+            ttl::set_persistent(env, &StorageKey::Valuation(asset_id.clone()), &valuation);
+
+            request.filled_units += units;
+            request.paid_amount += paid;
+            if request.filled_units == request.units {
+                request.status = symbol_short!("filled");
+                filled.push_back(id);
+            }
+            ttl::set_persistent(env, &StorageKey::Exit(id), &request);
+
+            Self::log_principal_event(env, &PrincipalEvent {
+                investor: request.investor.clone(),
+                asset_id: asset_id.clone(),
+                kind: symbol_short!("redeemed"),
+                amount: paid,
+                timestamp: env.ledger().timestamp(),
+            });
+            Self::reduce_exposure(env, &request.investor, asset_id, paid);
+            Self::burn_shares(env, asset_id, &request.investor, units);
+            Self::credit_payout(env, data, &request.investor, paid);
+
+            total_paid += paid;
+        }
+
+        if !filled.is_empty() {
+            Self::dequeue_exits(env, asset_id, &filled);
+        }
+
+        total_paid
+    }
+
+    /// Opt in to rolling principal returned by completed assets into a follow-on asset,
+    /// or into the LP vault when no follow-on asset is given
+    pub fn set_recycling(env: &Env, investor: Address, follow_on: Option<Symbol>) -> Result<(), ContractError> {
+        investor.require_auth();
+
+        // An empty target stands for the LP vault
+        let target_id = match follow_on {
+            Some(target_id) => {
+                if Self::load_asset(env, &target_id).is_none() {
+                    return Err(ContractError::AssetNotFound);
+                }
+                target_id
+            }
+            None => Symbol::new(env, ""),
+        };
+
+        ttl::set_persistent(env, &StorageKey::RecycleTarget(investor), &target_id);
+
+        Ok(())
+    }
+
+    /// Opt out of capital recycling
+    pub fn clear_recycling(env: &Env, investor: Address) -> Result<(), ContractError> {
+        investor.require_auth();
+
+        env.storage().persistent().remove(&StorageKey::RecycleTarget(investor));
+
+        Ok(())
+    }
+
+    /// Record each investor's share of unrepaid principal as written off when an asset defaults
+    fn record_writeoffs(env: &Env, data: &mut DataKey, asset_id: &Symbol) {
+        let asset = match Self::load_asset(env, asset_id) {
+            Some(asset) => asset,
+            None => return,
+        };
+        let valuation = Self::get_or_create_valuation(env, asset_id);
+        let outstanding_units = Self::outstanding_shares(env, asset_id);
+        let unrepaid = asset.funded_amount - valuation.principal_repaid;
+        if outstanding_units <= 0 || unrepaid <= 0 {
+            return;
+        }
+
+        // Program sponsors absorb losses first, then zone sponsors
+        let covered = Self::absorb_first_loss(env, symbol_short!("program"), &asset.program, asset_id, unrepaid);
+        let covered = covered + Self::absorb_first_loss(env, symbol_short!("zone"), &asset.location, asset_id, unrepaid - covered);
+
+        for investor in Self::holders_of(env, asset_id).iter() {
+            let units = Self::shares_of(env, asset_id, &investor);
+            if units <= 0 {
+                continue;
+            }
+
+            let lost = units * unrepaid / outstanding_units;
+            Self::reduce_exposure(env, &investor, asset_id, lost);
+
+            // The guaranteed part comes back as a withdrawable payout
+            let recovered = units * covered / outstanding_units;
+            if recovered > 0 {
+                Self::credit_payout(env, data, &investor, recovered);
+                Self::log_principal_event(env, &PrincipalEvent {
+                    investor: investor.clone(),
+                    asset_id: asset_id.clone(),
+                    kind: symbol_short!("covered"),
+                    amount: recovered,
+                    timestamp: env.ledger().timestamp(),
+                });
+            }
+            if lost > recovered {
+                Self::log_principal_event(env, &PrincipalEvent {
+                    investor,
+                    asset_id: asset_id.clone(),
+                    kind: symbol_short!("writeoff"),
+                    amount: lost - recovered,
+                    timestamp: env.ledger().timestamp(),
+                });
+            }
+        }
+    }
+
+    /// Cover up to `loss` of a defaulted asset from the first-loss guarantee for a scope, if any.
+    /// Returns the amount covered
+    fn absorb_first_loss(env: &Env, kind: Symbol, scope: &Symbol, asset_id: &Symbol, loss: i128) -> i128 {
+        let key = StorageKey::Guarantee(kind, scope.clone());
+        let mut guarantee: FirstLossGuarantee = match env.storage().persistent().get(&key) {
+            Some(guarantee) => guarantee,
+            None => return 0,
+        };
+        if guarantee.closed || loss <= 0 {
+            return 0;
+        }
+
+        let covered = loss.min(guarantee.cap - guarantee.absorbed);
+        if covered <= 0 {
+            return 0;
+        }
+        guarantee.absorbed += covered;
+        ttl::set_persistent(env, &key, &guarantee);
+
+        env.events().publish(
+            (symbol_short!("guar_use"), scope.clone()),
+            (asset_id.clone(), covered, guarantee.absorbed, guarantee.cap),
+        );
+
+        covered
+    }
+
+    /// Get a page of an investor's recycled positions, oldest first
+    pub fn get_recycle_history(env: &Env, investor: Address, page: PageRequest) -> RecyclePage {
+        let (items, next_cursor) = journal::page(env, &(RECYCLED_KEY, investor), &page);
+        RecyclePage { items, next_cursor }
+    }
+
+    /// Get an investor's LP vault balance
+    pub fn get_lp_vault_balance(env: &Env, investor: Address) -> i128 {
+        env.storage().persistent().get(&StorageKey::VaultBalance(investor)).unwrap_or(0)
+    }
+
+    /// Withdraw an investor's whole LP vault balance, paid out of the pool in the settlement asset
+    pub fn withdraw_lp_vault(env: &Env, investor: Address) -> Result<i128, ContractError> {
+        investor.require_auth();
+
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        Gate::new(data.paused, &data.disabled_features, &symbol_short!("withdraw")).check()?;
+
+        let balance = Self::get_lp_vault_balance(env, investor.clone());
+        if balance <= 0 {
+            return Err(ContractError::Nothing);
+        }
+
+        let token = Self::settlement_asset(env).ok_or(ContractError::NoSettlementAsset)?;
+        env.storage().persistent().remove(&StorageKey::VaultBalance(investor.clone()));
+        data.lp_vault_total -= balance;
+        data.total_pool_balance -= balance;
+        ttl::set_instance(env, &DATA_KEY, &data);
+        TokenClient::new(env, &token).transfer(&env.current_contract_address(), &investor, &balance);
+
+        env.events().publish((symbol_short!("vault_out"), investor), balance);
+
+        Ok(balance)
+    }
+
+    /// Move opted-in investors' remaining units in a completed asset, valued at NAV
+    /// and limited by repaid liquidity, into their follow-on asset or the LP vault
+    fn recycle_principal(env: &Env, data: &mut DataKey, asset: &MobilityAsset) {
+        for investor in Self::holders_of(env, &asset.id).iter() {
+            let follow_on: Symbol = match env.storage().persistent().get(&StorageKey::RecycleTarget(investor.clone())) {
+                Some(target) => target,
+                None => continue,
+            };
+
+            // Units still held after any redemptions
+            let mut units = Self::shares_of(env, &asset.id, &investor);
+
+            let mut valuation = Self::get_or_create_valuation(env, &asset.id);
+            if units <= 0 || valuation.liquidity <= 0 {
+                continue;
+            }
+
+            let value = Self::units_to_value(asset, &valuation, units);
+            if value > valuation.liquidity {
+                units = units * valuation.liquidity / value;
+            }
+            let amount = Self::units_to_value(asset, &valuation, units);
+            if units == 0 || amount <= 0 {
+                continue;
+            }
+
+            valuation.liquidity -= amount;
+            valuation.redeemed_units += units;
+            ttl::set_persistent(env, &StorageKey::Valuation(asset.id.clone()), &valuation);
+            Self::burn_shares(env, &asset.id, &investor, units);
+
+            // Fall back to the vault if the follow-on asset is no longer raising
+            let vault = Symbol::new(env, "");
+            let mut to_asset = vault.clone();
+            if follow_on != vault {
+                // The capital is already held by the pool, so don't count it twice
+                data.total_pool_balance -= amount;
+                if Self::record_investment(env, data, &investor, &follow_on, amount).is_ok() {
+                    to_asset = follow_on;
+                } else {
+                    data.total_pool_balance += amount;
+                }
+            }
+            if to_asset == vault {
+                let balance = Self::get_lp_vault_balance(env, investor.clone());
+                ttl::set_persistent(env, &StorageKey::VaultBalance(investor.clone()), &(balance + amount));
+                data.lp_vault_total += amount;
+
+                let mut holders = Self::vault_holders(env);
+                if !holders.contains(&investor) {
+                    holders.push_back(investor.clone());
+                    ttl::set_persistent(env, &StorageKey::VaultHolders, &holders);
+                }
+            }
+
+            Self::log_principal_event(env, &PrincipalEvent {
+                investor: investor.clone(),
+                asset_id: asset.id.clone(),
+                kind: symbol_short!("recycled"),
+                amount,
+                timestamp: env.ledger().timestamp(),
+            });
+            Self::reduce_exposure(env, &investor, &asset.id, amount);
+            journal::append(env, &(RECYCLED_KEY, investor.clone()), &RecycleRecord {
+                investor: investor.clone(),
+                from_asset: asset.id.clone(),
+                to_asset: to_asset.clone(),
+                units,
+                amount,
+                timestamp: env.ledger().timestamp(),
+            });
+            env.events().publish((symbol_short!("recycled"), investor), (asset.id.clone(), to_asset, amount));
+        }
+    }
+
+    /// Get stored valuation inputs for an asset, or an empty valuation
+    fn get_or_create_valuation(env: &Env, asset_id: &Symbol) -> AssetValuation {
+        env.storage().persistent().get(&StorageKey::Valuation(asset_id.clone())).unwrap_or(AssetValuation {
+            asset_id: asset_id.clone(),
+            principal_repaid: 0,
+            accrued_interest: 0,
+            expected_loss: 0,
+            liquidity: 0,
+            redeemed_units: 0,
+        })
+    }
+
+    /// Net asset value of an asset's outstanding units
+    fn calculate_nav(asset: &MobilityAsset, valuation: &AssetValuation) -> i128 {
+        let outstanding_principal = asset.funded_amount - valuation.principal_repaid;
+        let nav = outstanding_principal + valuation.liquidity + valuation.accrued_interest - valuation.expected_loss;
+        if nav > 0 {
+            nav
+        } else {
+            0
+        }
+    }
+
+    /// Convert investment units to value at the current NAV per unit
+    fn units_to_value(asset: &MobilityAsset, valuation: &AssetValuation, units: i128) -> i128 {
+        let outstanding_units = asset.funded_amount - valuation.redeemed_units;
+        if outstanding_units <= 0 {
+            return 0;
+        }
+        units * Self::calculate_nav(asset, valuation) / outstanding_units
+    }
+
+    /// Define how operator-facing releases for an asset are split (admin only).
+    /// The split is fixed once the asset is deployed.
+    pub fn set_payout_split(env: &Env, caller: Address, asset_id: Symbol, splits: Vec<PayoutSplit>) -> Result<(), ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        // Only admin can configure payout splits
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+
+        let asset = Self::load_asset(env, &asset_id).ok_or(ContractError::AssetNotFound)?;
+        if asset.status != symbol_short!("proposed")
+            && asset.status != symbol_short!("funding")
+            && asset.status != symbol_short!("funded")
+        {
+            return Err(ContractError::Locked);
+        }
+
+        // Shares must cover the whole release
+        let mut total_bps: u32 = 0;
+        for split in splits.iter() {
+            if split.share_bps == 0 {
+                return Err(ContractError::InvalidInput);
+            }
+            total_bps += split.share_bps;
+        }
+        if splits.is_empty() || total_bps != 10_000 {
+            return Err(ContractError::InvalidInput);
+        }
+
+        ttl::set_persistent(env, &StorageKey::PayoutSplits(asset_id.clone()), &splits);
+
+        Ok(())
+    }
+
+    /// Release capital from a deployed asset to its operator side, applying the payout split (treasurer only)
+    pub fn release_to_operator(env: &Env, caller: Address, asset_id: Symbol, amount: i128) -> Result<(), ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        // Only treasurers can release capital
+        caller.require_auth();
+        Gate::new(data.paused, &data.disabled_features, &symbol_short!("release"))
+            .role(access::holds(env, &data.admin, Role::Treasurer, &caller))
+            .check()?;
+
+        if amount <= 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        let asset = Self::load_asset(env, &asset_id).ok_or(ContractError::AssetNotFound)?;
+        if asset.status != symbol_short!("deployed") {
+            return Err(ContractError::InvalidStatus);
+        }
+
+        let released = env.storage().persistent().get(&StorageKey::Released(asset_id.clone())).unwrap_or(0);
+        if released + amount > asset.funded_amount {
+            return Err(ContractError::OverLimit);
+        }
+
+        Self::apply_payout_split(env, &mut data, &asset_id, amount)?;
+        ttl::set_persistent(env, &StorageKey::Released(asset_id.clone()), &(released + amount));
+
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        Ok(())
+    }
+
+    /// Withdraw accumulated payouts for a recipient, paid out of the pool in the settlement asset
+    pub fn withdraw_payout(env: &Env, recipient: Address) -> Result<i128, ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        Gate::new(data.paused, &data.disabled_features, &symbol_short!("withdraw")).check()?;
+
+        recipient.require_auth();
+
+        let balance = Self::payout_balance(env, &recipient);
+        if balance <= 0 {
+            return Err(ContractError::Nothing);
+        }
+
+        // Large withdrawals need a pending action approved by enough signers
+        Self::consume_approval(
+            env,
+            &mut data,
+            symbol_short!("withdraw"),
+            symbol_short!("payout"),
+            Some(recipient.clone()),
+            balance,
+        )?;
+
+        let token = Self::settlement_asset(env).ok_or(ContractError::NoSettlementAsset)?;
+        env.storage().persistent().remove(&StorageKey::PayoutBalance(recipient.clone()));
+        data.payouts_owed -= balance;
+        data.total_pool_balance -= balance;
+        ttl::set_instance(env, &DATA_KEY, &data);
+        TokenClient::new(env, &token).transfer(&env.current_contract_address(), &recipient, &balance);
+
+        Ok(balance)
+    }
+
+    /// Queue the amount above which disbursements need approvals, and who can give them, behind
+    /// the timelock; returns the change id (admin only)
+    pub fn set_approval_rule(env: &Env, caller: Address, rule: ApprovalRule) -> Result<u32, ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+
+        let value = ChangeValue::Encoded(rule.to_xdr(env));
+        timelock::queue(env, symbol_short!("approval"), value, timelock::MIN_DELAY, timelock::MIN_DELAY, |change| {
+            Self::validate_admin_change(env, change)
+        })
+    }
+
+    /// Open a pending action for a large disbursement ("withdraw" of a recipient's payouts), counting the proposer's approval
+    pub fn propose_action(
+        env: &Env,
+        approver: Address,
+        kind: Symbol,
+        reference: Symbol,
+        recipient: Option<Address>,
+        amount: i128,
+    ) -> Result<u32, ContractError> {
+        approver.require_auth();
+
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        if kind != symbol_short!("withdraw") {
+            return Err(ContractError::InvalidInput);
+        }
+
+        if amount <= 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        let action_id = data.next_action_id;
+        let now = env.ledger().timestamp();
+        let mut action = PendingAction {
+            id: action_id,
+            kind: kind.clone(),
+            reference,
+            recipient: recipient.into(),
+            amount,
+            approvals: vec![env],
+            created_at: now,
+        };
+        action.approve(&data.approval_rule, &approver, now)?;
+
+        data.pending_actions.set(action_id, action);
+        data.next_action_id += 1;
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        env.events().publish((symbol_short!("act_new"), action_id), (kind, amount));
+
+        Ok(action_id)
+    }
+
+    /// Approve a pending action and return its approval count
+    pub fn approve_action(env: &Env, approver: Address, action_id: u32) -> Result<u32, ContractError> {
+        approver.require_auth();
+
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        let mut action = data.pending_actions.get(action_id).ok_or(ContractError::NotFound)?;
+
+        let approvals = action.approve(&data.approval_rule, &approver, env.ledger().timestamp())?;
+        data.pending_actions.set(action_id, action);
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        env.events().publish((symbol_short!("act_appr"), action_id), approver);
+
+        Ok(approvals)
+    }
+
+    /// Drop pending actions whose approval window has passed. Anyone can call this.
+    pub fn prune_expired_actions(env: &Env) -> u32 {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        let now = env.ledger().timestamp();
+
+        let mut expired = vec![env];
+        for (action_id, action) in data.pending_actions.iter() {
+            if action.is_expired(now) {
+                expired.push_back(action_id);
+            }
+        }
+        for action_id in expired.iter() {
+            data.pending_actions.remove(action_id);
+        }
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        expired.len()
+    }
+
+    /// Get a pending action
+    pub fn get_pending_action(env: &Env, action_id: u32) -> Result<PendingAction, ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        data.pending_actions.get(action_id).ok_or(ContractError::NotFound)
+    }
+
+    /// Get the large-disbursement approval rule
+    pub fn get_approval_rule(env: &Env) -> ApprovalRule {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        data.approval_rule
+    }
+
+    /// Start winding down a program: new assets and investments stop, while repayments,
+    /// claims and withdrawals carry on (governance only)
+    pub fn start_wind_down(env: &Env, program: Symbol, recipients: Vec<PayoutSplit>) -> Result<(), ContractError> {
+        Self::governance(env).ok_or(ContractError::NoAddress)?.require_auth();
+
+        if env.storage().persistent().has(&StorageKey::WindDown(program.clone())) {
+            return Err(ContractError::WindDown);
+        }
+
+        // Recipients must cover the whole residual
+        let mut total_bps: u32 = 0;
+        for split in recipients.iter() {
+            if split.share_bps == 0 {
+                return Err(ContractError::InvalidInput);
+            }
+            total_bps += split.share_bps;
+        }
+        if recipients.is_empty() || total_bps != 10_000 {
+            return Err(ContractError::InvalidInput);
+        }
+
+        ttl::set_persistent(env, &StorageKey::WindDown(program.clone()), &WindDown {
+            program: program.clone(),
+            recipients,
+            started_at: env.ledger().timestamp(),
+            settled_at: None,
+            residual: 0,
+        });
+
+        env.events().publish((symbol_short!("wind_down"), program), env.ledger().timestamp());
+
+        Ok(())
+    }
+
+    /// Settle a wound-down program after the notice period by crediting its funded assets'
+    /// unreleased capital to the plan's recipients as withdrawable payouts (governance only)
+    pub fn settle_wind_down(env: &Env, program: Symbol) -> Result<i128, ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        Self::governance(env).ok_or(ContractError::NoAddress)?.require_auth();
+
+        let mut wind_down = env.storage().persistent().get::<_, WindDown>(&StorageKey::WindDown(program.clone())).ok_or(ContractError::NotFound)?;
+        if wind_down.settled_at.is_some() {
+            return Err(ContractError::Done);
+        }
+        if env.ledger().timestamp() < wind_down.started_at + WIND_DOWN_NOTICE {
+            return Err(ContractError::Notice);
+        }
+
+        // Capital still held for funded or deployed assets; assets still funding are refunded to investors instead
+        let mut residual: i128 = 0;
+        for asset_id in Self::asset_ids(env).iter() {
+            let asset = Self::load_asset(env, &asset_id).unwrap();
+            if asset.program != program
+                || (asset.status != symbol_short!("funded") && asset.status != symbol_short!("deployed"))
+            {
+                continue;
+            }
+            let released = env.storage().persistent().get(&StorageKey::Released(asset_id.clone())).unwrap_or(0);
+            if asset.funded_amount > released {
+                residual += asset.funded_amount - released;
+                ttl::set_persistent(env, &StorageKey::Released(asset_id.clone()), &asset.funded_amount);
+            }
+        }
+
+        // Rounding dust goes to the first recipient
+        let mut credited: i128 = 0;
+        for (i, split) in wind_down.recipients.iter().enumerate() {
+            let mut share = residual * split.share_bps as i128 / 10_000;
+            if i == 0 {
+                share += residual - Self::sum_split_shares(&wind_down.recipients, residual);
+            }
+            Self::credit_payout(env, &mut data, &split.recipient, share);
+            credited += share;
+        }
+
+        wind_down.settled_at = Some(env.ledger().timestamp());
+        wind_down.residual = credited;
+        ttl::set_persistent(env, &StorageKey::WindDown(program.clone()), &wind_down);
+
+        // The program has ended, so its sponsor's unused first-loss escrow goes back
+        let guarantee_key = StorageKey::Guarantee(symbol_short!("program"), program.clone());
+        if let Some(guarantee) = env.storage().persistent().get::<_, FirstLossGuarantee>(&guarantee_key) {
+            if !guarantee.closed {
+                Self::close_guarantee(env, &mut data, guarantee);
+            }
+        }
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        env.events().publish((symbol_short!("wind_stl"), program), credited);
+
+        Ok(credited)
+    }
+
+    /// Escrow sponsor capital as a first-loss guarantee for a program or zone, or top up the
+    /// sponsor's existing guarantee. Unused escrow can be released once `expires_at` passes,
+    /// and a program's guarantee is released when its wind-down settles
+    pub fn post_guarantee(
+        env: &Env,
+        sponsor: Address,
+        kind: Symbol,
+        scope: Symbol,
+        amount: i128,
+        expires_at: u64,
+    ) -> Result<FirstLossGuarantee, ContractError> {
+        sponsor.require_auth();
+
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        Gate::new(data.paused, &data.disabled_features, &symbol_short!("guarantee")).check()?;
+
+        if kind != symbol_short!("program") && kind != symbol_short!("zone") {
+            return Err(ContractError::InvalidInput);
+        }
+        if amount <= 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+        if expires_at <= env.ledger().timestamp() {
+            return Err(ContractError::InvalidInput);
+        }
+
+        let key = StorageKey::Guarantee(kind.clone(), scope.clone());
+        let mut guarantee = match env.storage().persistent().get::<_, FirstLossGuarantee>(&key) {
+            Some(existing) if !existing.closed => {
+                if existing.sponsor != sponsor {
+                    return Err(ContractError::Unauthorized);
+                }
+                existing
+            }
+            _ => FirstLossGuarantee {
+                sponsor: sponsor.clone(),
+                kind,
+                scope: scope.clone(),
+                cap: 0,
+                absorbed: 0,
+                expires_at,
+                released: 0,
+                closed: false,
+            },
+        };
+
+        let token = Self::settlement_asset(env).ok_or(ContractError::NoSettlementAsset)?;
+        TokenClient::new(env, &token).transfer(&sponsor, &env.current_contract_address(), &amount);
+
+        guarantee.cap += amount;
+        guarantee.expires_at = guarantee.expires_at.max(expires_at);
+        data.total_pool_balance += amount;
+        ttl::set_persistent(env, &key, &guarantee);
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        env.events().publish((symbol_short!("guarantee"), scope), (sponsor, amount, guarantee.cap));
+
+        Ok(guarantee)
+    }
+
+    /// Return a guarantee's unused escrow to its sponsor once it has expired. Anyone can call this.
+    /// Returns the amount released
+    pub fn release_guarantee(env: &Env, kind: Symbol, scope: Symbol) -> Result<i128, ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        let guarantee: FirstLossGuarantee = env.storage()
+            .persistent()
+            .get(&StorageKey::Guarantee(kind, scope))
+            .ok_or(ContractError::NotFound)?;
+        if guarantee.closed {
+            return Err(ContractError::Done);
+        }
+        if env.ledger().timestamp() < guarantee.expires_at {
+            return Err(ContractError::TooEarly);
+        }
+
+        let released = Self::close_guarantee(env, &mut data, guarantee);
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        Ok(released)
+    }
+
+    /// Get a first-loss guarantee with its utilization
+    pub fn get_guarantee(env: &Env, kind: Symbol, scope: Symbol) -> Result<FirstLossGuarantee, ContractError> {
+        env.storage()
+            .persistent()
+            .get(&StorageKey::Guarantee(kind, scope))
+            .ok_or(ContractError::NotFound)
+    }
+
+    /// Close a guarantee and send what it did not absorb back to the sponsor
+    fn close_guarantee(env: &Env, data: &mut DataKey, mut guarantee: FirstLossGuarantee) -> i128 {
+        let unused = guarantee.cap - guarantee.absorbed;
+        if unused > 0 {
+            if let Some(token) = Self::settlement_asset(env) {
+                TokenClient::new(env, &token).transfer(&env.current_contract_address(), &guarantee.sponsor, &unused);
+            }
+            data.total_pool_balance -= unused;
+        }
+
+        guarantee.released = unused.max(0);
+        guarantee.closed = true;
+        ttl::set_persistent(env, &StorageKey::Guarantee(guarantee.kind.clone(), guarantee.scope.clone()), &guarantee);
+
+        env.events().publish(
+            (symbol_short!("guar_rel"), guarantee.scope),
+            (guarantee.sponsor, guarantee.absorbed, guarantee.released),
+        );
+
+        guarantee.released
+    }
+
+    /// Get a program's wind-down plan
+    pub fn get_wind_down(env: &Env, program: Symbol) -> Result<WindDown, ContractError> {
+        env.storage().persistent().get(&StorageKey::WindDown(program.clone())).ok_or(ContractError::NotFound)
+    }
+
+    /// Get a page of assets created or modified after a ledger with their last-modified ledger,
+    /// in ledger order, so indexers can sync incrementally. Supported kinds: "asset"
+    pub fn get_changes_since(env: &Env, ledger_seq: u32, kind: Symbol, page: PageRequest) -> Result<RecordChangePage, ContractError> {
+        if kind != symbol_short!("asset") {
+            return Err(ContractError::InvalidInput);
+        }
+        let (items, next_cursor) = changes::changes_since(env, kind, ledger_seq, &page, |id, ledger| RecordChange { id, ledger });
+        Ok(RecordChangePage { items, next_cursor })
+    }
+
+    /// Get the payout split for an asset
+    pub fn get_payout_split(env: &Env, asset_id: Symbol) -> Vec<PayoutSplit> {
+        env.storage().persistent().get(&StorageKey::PayoutSplits(asset_id.clone())).unwrap_or(vec![env])
+    }
+
+    /// Get a recipient's withdrawable payout balance
+    pub fn get_payout_balance(env: &Env, recipient: Address) -> i128 {
+        Self::payout_balance(env, &recipient)
+    }
+
+    /// Commit an operator to service levels for an asset before it is deployed
+    pub fn commit_sla(
+        env: &Env,
+        operator: Address,
+        asset_id: Symbol,
+        min_availability_bps: u32,
+        max_response_secs: u64,
+        min_underserved_bps: u32,
+    ) -> Result<(), ContractError> {
+        operator.require_auth();
+
+
+        let asset = Self::load_asset(env, &asset_id).ok_or(ContractError::AssetNotFound)?;
+        if asset.status != symbol_short!("proposed")
+            && asset.status != symbol_short!("funding")
+            && asset.status != symbol_short!("funded")
+        {
+            return Err(ContractError::Locked);
+        }
+
+        if env.storage().persistent().has(&StorageKey::OperatorSla(asset_id.clone())) {
+            return Err(ContractError::Done);
+        }
+
+        if min_availability_bps > 10_000 || min_underserved_bps > 10_000 || max_response_secs == 0 {
+            return Err(ContractError::InvalidInput);
+        }
+
+        // The SLA binds the operator's share of the payout split
+        let splits = env.storage().persistent().get::<_, Vec<PayoutSplit>>(&StorageKey::PayoutSplits(asset_id.clone())).ok_or(ContractError::NotFound)?;
+        if !splits.iter().any(|split| split.recipient == operator) {
+            return Err(ContractError::Unauthorized);
+        }
+
+        ttl::set_persistent(env, &StorageKey::OperatorSla(asset_id.clone()), &OperatorSla {
+            asset_id: asset_id.clone(),
+            operator,
+            min_availability_bps,
+            max_response_secs,
+            min_underserved_bps,
+            epochs_reported: 0,
+            consecutive_breaches: 0,
+            haircut_bps: 0,
+            last_reported_at: 0,
+        });
+
+        Ok(())
+    }
+
+    /// Publish the seasonal revenue baseline for a zone and asset type (oracle only). SLA floors and
+    /// delinquency periods for matching assets follow the current month's factor
+    pub fn set_seasonal_baseline(
+        env: &Env,
+        zone: Symbol,
+        asset_type: Symbol,
+        monthly_factor_bps: Vec<u32>,
+    ) -> Result<(), ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        data.equity_oracle.require_auth();
+
+        Gate::new(data.paused, &data.disabled_features, &symbol_short!("oracle")).check()?;
+
+        let baseline = SeasonalBaseline {
+            zone: zone.clone(),
+            asset_type: asset_type.clone(),
+            monthly_factor_bps,
+            updated_at: env.ledger().timestamp(),
+        };
+        if !baseline.is_valid() {
+            return Err(ContractError::InvalidInput);
+        }
+        ttl::set_persistent(env, &StorageKey::Baseline(zone.clone(), asset_type.clone()), &baseline);
+
+        env.events().publish((symbol_short!("baseline"), zone), asset_type);
+
+        Ok(())
+    }
+
+    /// Get the seasonal revenue baseline for a zone and asset type
+    pub fn get_seasonal_baseline(env: &Env, zone: Symbol, asset_type: Symbol) -> Result<SeasonalBaseline, ContractError> {
+        env.storage()
+            .persistent()
+            .get(&StorageKey::Baseline(zone, asset_type))
+            .ok_or(ContractError::NotFound)
+    }
+
+    /// Current month's seasonal factor for an asset's zone and type; 10000 without a baseline
+    fn seasonal_factor(env: &Env, asset_id: &Symbol) -> u32 {
+        let Some(asset) = Self::load_asset(env, asset_id) else {
+            return 10_000;
+        };
+        env.storage()
+            .persistent()
+            .get::<_, SeasonalBaseline>(&StorageKey::Baseline(asset.location, asset.asset_type))
+            .map_or(10_000, |baseline| baseline.factor_at(env.ledger().timestamp()))
+    }
+
+    /// Report an epoch of SLA compliance data for an asset (oracle only).
+    /// Returns the operator's payout haircut in basis points after the report.
+    pub fn report_sla_compliance(
+        env: &Env,
+        asset_id: Symbol,
+        availability_bps: u32,
+        response_secs: u64,
+        underserved_bps: u32,
+    ) -> Result<u32, ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        // Only oracle can report compliance
+        data.equity_oracle.require_auth();
+
+        Gate::new(data.paused, &data.disabled_features, &symbol_short!("oracle")).check()?;
+
+        let mut sla = env.storage().persistent().get::<_, OperatorSla>(&StorageKey::OperatorSla(asset_id.clone())).ok_or(ContractError::NotFound)?;
+
+        // Floors relax in a seasonal low so expected dips don't count as breaches
+        let factor = Self::seasonal_factor(env, &asset_id);
+        let breached = availability_bps < seasonality::relax_floor(sla.min_availability_bps, factor)
+            || response_secs > sla.max_response_secs
+            || underserved_bps < seasonality::relax_floor(sla.min_underserved_bps, factor);
+
+        sla.epochs_reported += 1;
+        sla.last_reported_at = env.ledger().timestamp();
+
+        // A compliant epoch restores the full payout share
+        if breached {
+            sla.consecutive_breaches += 1;
+            if sla.consecutive_breaches >= SLA_BREACH_EPOCHS {
+                let steps = sla.consecutive_breaches - SLA_BREACH_EPOCHS + 1;
+                sla.haircut_bps = (steps * SLA_HAIRCUT_STEP_BPS).min(SLA_MAX_HAIRCUT_BPS);
+            }
+            env.events().publish(
+                (symbol_short!("sla_miss"), asset_id.clone()),
+                (sla.consecutive_breaches, sla.haircut_bps),
+            );
+        } else {
+            sla.consecutive_breaches = 0;
+            sla.haircut_bps = 0;
+        }
+
+        let haircut_bps = sla.haircut_bps;
+        ttl::set_persistent(env, &StorageKey::OperatorSla(asset_id.clone()), &sla);
+
+        Ok(haircut_bps)
+    }
+
+    /// Pay out an asset's SLA penalty reserve, e.g. to the maintenance reserve or back to
+    /// the operator once service recovers (governance only)
+    pub fn release_penalty_reserve(env: &Env, asset_id: Symbol, recipient: Address) -> Result<i128, ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        Self::governance(env).ok_or(ContractError::NoAddress)?.require_auth();
+
+        let reserve = env.storage().persistent().get(&StorageKey::PenaltyReserve(asset_id.clone())).unwrap_or(0);
+        if reserve <= 0 {
+            return Err(ContractError::NoFunds);
+        }
+
+        Self::credit_payout(env, &mut data, &recipient, reserve);
+        env.storage().persistent().remove(&StorageKey::PenaltyReserve(asset_id.clone()));
+        data.payouts_owed -= reserve;
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        Ok(reserve)
+    }
+
+    /// Get the SLA committed for an asset
+    pub fn get_operator_sla(env: &Env, asset_id: Symbol) -> Result<OperatorSla, ContractError> {
+        env.storage().persistent().get(&StorageKey::OperatorSla(asset_id.clone())).ok_or(ContractError::NotFound)
+    }
+
+    /// Get the operator payouts withheld for an asset's SLA breaches
+    pub fn get_penalty_reserve(env: &Env, asset_id: Symbol) -> i128 {
+        env.storage().persistent().get(&StorageKey::PenaltyReserve(asset_id.clone())).unwrap_or(0)
+    }
+
+    /// Use up the pending action approving a disbursement above the threshold
+    fn consume_approval(
+        env: &Env,
+        data: &mut DataKey,
+        kind: Symbol,
+        reference: Symbol,
+        recipient: Option<Address>,
+        amount: i128,
+    ) -> Result<(), ContractError> {
+        if !data.approval_rule.applies(amount) {
+            return Ok(());
+        }
+
+        let now = env.ledger().timestamp();
+        let payee = Payee::from(recipient);
+        for (action_id, action) in data.pending_actions.iter() {
+            if action.covers(&data.approval_rule, &kind, &reference, &payee, amount, now) {
+                data.pending_actions.remove(action_id);
+                env.events().publish((symbol_short!("act_exec"), action_id), (kind, amount));
+                return Ok(());
+            }
+        }
+
+        Err(ContractError::NeedsApproval)
+    }
+
+    /// Set caps on your own exposure; an all-zero limit removes them
+    pub fn set_exposure_limit(env: &Env, investor: Address, limit: ExposureLimit) -> Result<(), ContractError> {
+        investor.require_auth();
+
+        if limit.max_total < 0 || limit.max_per_asset < 0 || limit.max_per_zone < 0 || limit.max_per_program < 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        if limit.max_total == 0 && limit.max_per_asset == 0 && limit.max_per_zone == 0 && limit.max_per_program == 0 {
+            env.storage().persistent().remove(&StorageKey::ExposureLimit(investor.clone()));
+        } else {
+            ttl::set_persistent(env, &StorageKey::ExposureLimit(investor.clone()), &limit);
+        }
+
+        Ok(())
+    }
+
+    /// Get an investor's exposure breakdown
+    pub fn get_exposure(env: &Env, investor: Address) -> InvestorExposure {
+        env.storage().persistent().get(&StorageKey::Exposure(investor.clone())).unwrap_or(InvestorExposure {
+            total: 0,
+            by_asset: Map::new(env),
+            by_zone: Map::new(env),
+            by_program: Map::new(env),
+        })
+    }
+
+    /// Get an investor's self-set exposure caps
+    pub fn get_exposure_limit(env: &Env, investor: Address) -> Option<ExposureLimit> {
+        env.storage().persistent().get(&StorageKey::ExposureLimit(investor.clone()))
+    }
+
+    /// Add an investment to the investor's exposure, rejecting it if it breaks a self-set cap
+    fn add_exposure(env: &Env, investor: &Address, asset: &MobilityAsset, amount: i128) -> Result<(), ContractError> {
+        let mut exposure = Self::get_exposure(env, investor.clone());
+        exposure.total += amount;
+        let per_asset = exposure.by_asset.get(asset.id.clone()).unwrap_or(0) + amount;
+        let per_zone = exposure.by_zone.get(asset.location.clone()).unwrap_or(0) + amount;
+        let per_program = exposure.by_program.get(asset.program.clone()).unwrap_or(0) + amount;
+        exposure.by_asset.set(asset.id.clone(), per_asset);
+        exposure.by_zone.set(asset.location.clone(), per_zone);
+        exposure.by_program.set(asset.program.clone(), per_program);
+
+        if let Some(limit) = env.storage().persistent().get::<_, ExposureLimit>(&StorageKey::ExposureLimit(investor.clone())) {
+            let checks = [
+                (exposure.total, limit.max_total),
+                (per_asset, limit.max_per_asset),
+                (per_zone, limit.max_per_zone),
+                (per_program, limit.max_per_program),
+            ];
+            let mut near_limit = false;
+            for (value, max) in checks {
+                if max == 0 {
+                    continue;
+                }
+                if value > max {
+                    return Err(ContractError::OverLimit);
+                }
+                near_limit |= value * 100 >= max * EXPOSURE_ALERT_PERCENT;
+            }
+            if near_limit {
+                env.events().publish((symbol_short!("exp_alert"), investor.clone()), (asset.id.clone(), exposure.total));
+            }
         }
-        
-        // Bonus for first-time investors (simplified check)
-        if investor_str.len() < 10 {
-            bonus += 10;
+
+        ttl::set_persistent(env, &StorageKey::Exposure(investor.clone()), &exposure);
+
+        Ok(())
+    }
+
+    /// Take returned or written-off principal out of an investor's exposure
+    fn reduce_exposure(env: &Env, investor: &Address, asset_id: &Symbol, amount: i128) {
+        let (mut exposure, asset) = match (env.storage().persistent().get::<_, InvestorExposure>(&StorageKey::Exposure(investor.clone())), Self::load_asset(env, asset_id)) {
+            (Some(exposure), Some(asset)) => (exposure, asset),
+            _ => return,
+        };
+
+        exposure.total = (exposure.total - amount).max(0);
+        for (map, key) in [
+            (&mut exposure.by_asset, &asset.id),
+            (&mut exposure.by_zone, &asset.location),
+            (&mut exposure.by_program, &asset.program),
+        ] {
+            let remaining = map.get(key.clone()).unwrap_or(0) - amount;
+            if remaining > 0 {
+                map.set(key.clone(), remaining);
+            } else {
+                map.remove(key.clone());
+            }
         }
-        
-        // Cap bonus at 25%
-        if bonus > 25 {
-            bonus = 25;
+
+        ttl::set_persistent(env, &StorageKey::Exposure(investor.clone()), &exposure);
+    }
+
+    /// Check a holder's trustline for the settlement asset, if one is configured
+    fn check_trustline(env: &Env, holder: &Address) -> Result<(), ContractError> {
+        match &Self::settlement_asset(env) {
+            Some(asset) => trustline::check_trustline(env, asset, holder).map_err(ContractError::from),
+            None => Ok(()),
         }
-        
-        bonus
+    }
+
+    /// Check ahead of time whether a holder can receive payouts or invest
+    pub fn preflight_trustline(env: &Env, holder: Address) -> Result<(), ContractError> {
+        Self::check_trustline(env, &holder)
+    }
+
+    /// Credit an operator-facing release to each split recipient.
+    /// Rounding dust goes to the first recipient (the operator).
+    /// An operator in breach of its SLA has part of its share held in the penalty reserve.
+    fn apply_payout_split(env: &Env, data: &mut DataKey, asset_id: &Symbol, amount: i128) -> Result<(), ContractError> {
+        let splits = env.storage().persistent().get::<_, Vec<PayoutSplit>>(&StorageKey::PayoutSplits(asset_id.clone())).ok_or(ContractError::NotFound)?;
+        let sla: Option<OperatorSla> = env.storage().persistent().get(&StorageKey::OperatorSla(asset_id.clone()));
+
+        // Fail before crediting anyone if a recipient can't receive the asset
+        for split in splits.iter() {
+            Self::check_trustline(env, &split.recipient)?;
+        }
+
+        let mut credited: i128 = 0;
+        let mut withheld: i128 = 0;
+        for i in 0..splits.len() {
+            let split = splits.get(i).unwrap();
+            let mut share = amount * split.share_bps as i128 / 10_000;
+            if i == 0 {
+                share += amount - Self::sum_split_shares(&splits, amount);
+            }
+            if let Some(sla) = &sla {
+                if sla.haircut_bps > 0 && sla.operator == split.recipient {
+                    let held = share * sla.haircut_bps as i128 / 10_000;
+                    share -= held;
+                    withheld += held;
+                }
+            }
+            Self::credit_payout(env, data, &split.recipient, share);
+            credited += share;
+        }
+
+        env.events().publish((symbol_short!("payout"), asset_id.clone()), credited);
+
+        if withheld > 0 {
+            let reserve = env.storage().persistent().get(&StorageKey::PenaltyReserve(asset_id.clone())).unwrap_or(0);
+            ttl::set_persistent(env, &StorageKey::PenaltyReserve(asset_id.clone()), &(reserve + withheld));
+            data.payouts_owed += withheld;
+            env.events().publish((symbol_short!("sla_hold"), asset_id.clone()), withheld);
+        }
+
+        Ok(())
+    }
+
+    /// Total of all rounded-down split shares for an amount
+    fn sum_split_shares(splits: &Vec<PayoutSplit>, amount: i128) -> i128 {
+        let mut total: i128 = 0;
+        for split in splits.iter() {
+            total += amount * split.share_bps as i128 / 10_000;
+        }
+        total
     }
 
     /// Deploy a funded asset (admin only)
-    pub fn deploy_asset(env: &Env, asset_id: Symbol) -> Result<(), Symbol> {
-        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+    pub fn deploy_asset(env: &Env, caller: Address, asset_id: Symbol) -> Result<(), ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
         
         // Only admin can deploy assets
-        if env.current_contract_address() != data.admin {
-            return Err(symbol_short!("UNAUTHORIZED"));
-        }
+        access::check(env, &data.admin, Role::Admin, &caller)?;
 
-        let mut asset = data.assets.get(&asset_id).ok_or(symbol_short!("ASSET_NOT_FOUND"))?;
+        let mut asset = Self::load_asset(env, &asset_id).ok_or(ContractError::AssetNotFound)?;
         
         if asset.status != symbol_short!("funded") {
-            return Err(symbol_short!("ASSET_NOT_FUNDED"));
+            return Err(ContractError::InvalidStatus);
         }
 
         asset.status = symbol_short!("deployed");
-        data.assets.set(&asset_id, &asset);
-        
-        env.storage().instance().set(&DATA_KEY, &data);
+        Self::save_asset(env, &asset);
+        changes::touch(env, symbol_short!("asset"), asset_id.clone());
+        env.events().publish((symbol_short!("status"), asset_id.clone()), asset.status.clone());
         
         Ok(())
     }
 
     /// Complete an asset (admin only) - triggers revenue distribution
-    pub fn complete_asset(env: &Env, asset_id: Symbol) -> Result<(), Symbol> {
+    pub fn complete_asset(env: &Env, caller: Address, asset_id: Symbol) -> Result<(), ContractError> {
         let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
         
         // Only admin can complete assets
-        if env.current_contract_address() != data.admin {
-            return Err(symbol_short!("UNAUTHORIZED"));
-        }
+        access::check(env, &data.admin, Role::Admin, &caller)?;
 
-        let mut asset = data.assets.get(&asset_id).ok_or(symbol_short!("ASSET_NOT_FOUND"))?;
+        let mut asset = Self::load_asset(env, &asset_id).ok_or(ContractError::AssetNotFound)?;
         
         if asset.status != symbol_short!("deployed") {
-            return Err(symbol_short!("ASSET_NOT_DEPLOYED"));
+            return Err(ContractError::InvalidStatus);
         }
 
         asset.status = symbol_short!("completed");
-        data.assets.set(&asset_id, &asset);
+        Self::save_asset(env, &asset);
+        changes::touch(env, symbol_short!("asset"), asset_id.clone());
+        env.events().publish((symbol_short!("status"), asset_id.clone()), asset.status.clone());
+
+        // Roll returned principal for investors who opted in
+        Self::recycle_principal(env, &mut data, &asset);
         
-        env.storage().instance().set(&DATA_KEY, &data);
+        ttl::set_instance(env, &DATA_KEY, &data);
         
         Ok(())
     }
+
+    /// Retire a deployed asset at the end of its service life (admin only). Its salvage sale is
+    /// recorded next with record_salvage
+    pub fn retire_asset(env: &Env, caller: Address, asset_id: Symbol) -> Result<(), ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+
+        let mut asset = Self::load_asset(env, &asset_id).ok_or(ContractError::AssetNotFound)?;
+        if asset.status != symbol_short!("deployed") {
+            return Err(ContractError::InvalidStatus);
+        }
+
+        asset.status = symbol_short!("retired");
+        Self::save_asset(env, &asset);
+        changes::touch(env, symbol_short!("asset"), asset_id.clone());
+        env.events().publish((symbol_short!("status"), asset_id), asset.status.clone());
+
+        Ok(())
+    }
+
+    /// Record a retired asset's salvage sale (oracle only) and close its ledger. Proceeds settle the
+    /// outstanding loan balance first and the rest is paid to investors by share; principal the
+    /// sale doesn't cover is written off
+    pub fn record_salvage(env: &Env, asset_id: Symbol, proceeds: i128) -> Result<SalvageRecord, ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        data.equity_oracle.require_auth();
+
+        Gate::new(data.paused, &data.disabled_features, &symbol_short!("oracle")).check()?;
+
+        if proceeds < 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        let mut asset = Self::load_asset(env, &asset_id).ok_or(ContractError::AssetNotFound)?;
+        if asset.status != symbol_short!("retired") {
+            return Err(ContractError::InvalidStatus);
+        }
+
+        // A defaulted loan was already written off, so the whole sale is a recovery for investors
+        let defaulted = env.storage().persistent().get::<_, LoanServicing>(&StorageKey::Servicing(asset_id.clone())).is_some_and(|servicing| servicing.stage == symbol_short!("default"));
+        let valuation = Self::get_or_create_valuation(env, &asset_id);
+        let schedule = Self::load_schedule(env, &asset_id);
+        let outstanding = match &schedule {
+            _ if defaulted => 0,
+            Some(schedule) => Self::schedule_outstanding(schedule),
+            None => (asset.funded_amount - valuation.principal_repaid).max(0),
+        };
+
+        let loan_settled = proceeds.min(outstanding);
+        if loan_settled > 0 {
+            match schedule {
+                Some(mut schedule) => {
+                    let oracle = data.equity_oracle.clone();
+                    Self::apply_scheduled_payment(env, &mut data, &mut schedule, &oracle, loan_settled);
+                }
+                None => {
+                    Self::apply_repayment(env, &mut data, &asset_id, loan_settled, 0, None);
+                }
+            }
+        }
+
+        // The remainder goes to investors in proportion to the units they still hold
+        let remainder = proceeds - loan_settled;
+        let outstanding_units = Self::outstanding_shares(env, &asset_id);
+        let mut distributed: i128 = 0;
+        if remainder > 0 && outstanding_units > 0 {
+            for investor in Self::holders_of(env, &asset_id).iter() {
+                let share = Self::shares_of(env, &asset_id, &investor) * remainder / outstanding_units;
+                if share <= 0 {
+                    continue;
+                }
+                Self::credit_payout(env, &mut data, &investor, share);
+                Self::log_principal_event(env, &PrincipalEvent {
+                    investor,
+                    asset_id: asset_id.clone(),
+                    kind: symbol_short!("salvage"),
+                    amount: share,
+                    timestamp: env.ledger().timestamp(),
+                });
+                distributed += share;
+            }
+        }
+        data.total_pool_balance += distributed;
+
+        if !defaulted {
+            Self::record_writeoffs(env, &mut data, &asset_id);
+        }
+        env.storage().persistent().remove(&StorageKey::Servicing(asset_id.clone()));
+
+        let record = SalvageRecord {
+            asset_id: asset_id.clone(),
+            proceeds,
+            loan_settled,
+            distributed,
+            recorded_at: env.ledger().timestamp(),
+        };
+        ttl::set_persistent(env, &StorageKey::Salvage(asset_id.clone()), &record);
+
+        asset.status = symbol_short!("closed");
+        Self::save_asset(env, &asset);
+        changes::touch(env, symbol_short!("asset"), asset_id.clone());
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        env.events().publish((symbol_short!("salvage"), asset_id.clone()), (proceeds, loan_settled, distributed));
+        env.events().publish((symbol_short!("status"), asset_id), asset.status.clone());
+
+        Ok(record)
+    }
+
+    /// Units of an investor's position in an asset queued for or paid out by redemption
+    fn exit_committed(env: &Env, investor: &Address, asset_id: &Symbol) -> i128 {
+        let mut committed: i128 = 0;
+        for request in Self::exits_of(env, investor, asset_id).iter() {
+            if request.status != symbol_short!("cancelled") {
+                committed += request.units;
+            }
+        }
+        committed
+    }
+
+    /// Set the secondary market investment positions are minted into (admin only)
+    pub fn set_position_market(env: &Env, caller: Address, market: Address) -> Result<(), ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+
+        ttl::set_persistent(env, &StorageKey::PositionMarket, &market);
+        env.events().publish((symbol_short!("pos_mkt"),), market);
+
+        Ok(())
+    }
+
+    /// Lock part of an investor's principal in an asset and mint it as a transferable position
+    /// on the secondary market. Returns the market's position id
+    pub fn mint_position(env: &Env, investor: Address, asset_id: Symbol, amount: i128) -> Result<u32, ContractError> {
+        investor.require_auth();
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        Gate::new(data.paused, &data.disabled_features, &symbol_short!("position")).check()?;
+
+        let market = Self::position_market(env)?;
+        if amount <= 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        // Principal queued for redemption or already backing a position can't back another one
+        let investments = Self::investments_of(env, &investor, &asset_id);
+        let invested: i128 = investments.iter().map(|investment| investment.amount).sum();
+        let locked = Self::locked_position(env, &investor, &asset_id);
+        if invested - Self::exit_committed(env, &investor, &asset_id) - locked < amount {
+            return Err(ContractError::NoFunds);
+        }
+
+        // The position carries the principal-weighted equity bonus of the investments behind it
+        let weighted_bonus: i128 = investments.iter().map(|investment| investment.amount * investment.equity_bonus as i128).sum();
+        let equity_bonus = (weighted_bonus / invested) as i32;
+
+        ttl::set_persistent(env, &StorageKey::PositionLocked(investor.clone(), asset_id.clone()), &(locked + amount));
+        let position_id = PositionMarketClient::new(env, &market).mint_position(&investor, &asset_id, &amount, &equity_bonus);
+
+        env.events().publish((symbol_short!("pos_mint"), asset_id), (investor, position_id, amount));
+
+        Ok(position_id)
+    }
+
+    /// Principal of an investor's holding in an asset that backs minted positions
+    pub fn get_locked_position(env: &Env, investor: Address, asset_id: Symbol) -> i128 {
+        Self::locked_position(env, &investor, &asset_id)
+    }
+
+    fn locked_position(env: &Env, investor: &Address, asset_id: &Symbol) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&StorageKey::PositionLocked(investor.clone(), asset_id.clone()))
+            .unwrap_or(0)
+    }
+
+    fn set_locked_position(env: &Env, investor: &Address, asset_id: &Symbol, amount: i128) {
+        let key = StorageKey::PositionLocked(investor.clone(), asset_id.clone());
+        if amount > 0 {
+            ttl::set_persistent(env, &key, &amount);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+    }
+
+    fn position_market(env: &Env) -> Result<Address, ContractError> {
+        env.storage()
+            .persistent()
+            .get(&StorageKey::PositionMarket)
+            .ok_or(ContractError::NoAddress)
+    }
+
+    /// Governance contract, once linked through an admin change
+    fn governance(env: &Env) -> Option<Address> {
+        env.storage().persistent().get(&StorageKey::Governance)
+    }
+
+    /// Rate adjuster holding the canonical equity score cache, once linked through an admin change
+    fn equity_source(env: &Env) -> Option<Address> {
+        env.storage().persistent().get(&StorageKey::EquitySource)
+    }
+
+    /// Stellar Asset Contract investments and payouts settle in, once set through an admin change
+    fn settlement_asset(env: &Env) -> Option<Address> {
+        env.storage().persistent().get(&StorageKey::SettlementAsset)
+    }
+
+    /// Insurance premium pricing inputs, once set through an admin change
+    fn premium_params(env: &Env) -> Option<PremiumParams> {
+        env.storage().persistent().get(&StorageKey::PremiumParams)
+    }
+
+    /// Minimum funding pace, if one is set
+    fn funding_velocity(env: &Env) -> Option<FundingVelocity> {
+        env.storage().persistent().get(&StorageKey::FundingVelocity)
+    }
+
+    /// Link the SEP-41 token that fronts an asset's shares, so wallets can hold and move them
+    /// through the standard token interface (admin only). The token must name this pool and the asset
+    pub fn set_share_token(env: &Env, caller: Address, asset_id: Symbol, token: Address) -> Result<(), ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+
+        Self::load_asset(env, &asset_id).ok_or(ContractError::AssetNotFound)?;
+        let share_token = ShareTokenClient::new(env, &token);
+        if share_token.get_loan_pool() != env.current_contract_address() || share_token.get_asset_id() != asset_id {
+            return Err(ContractError::InvalidInput);
+        }
+
+        ttl::set_persistent(env, &StorageKey::ShareToken(asset_id.clone()), &token);
+        env.events().publish((symbol_short!("share_tok"), asset_id), token);
+
+        Ok(())
+    }
+
+    /// Get the SEP-41 token linked to an asset's shares, if any
+    pub fn get_share_token(env: &Env, asset_id: Symbol) -> Option<Address> {
+        env.storage().persistent().get(&StorageKey::ShareToken(asset_id))
+    }
+
+    /// Move shares in an asset. Shares queued for redemption or backing minted positions
+    /// can't move; the rest carry their investment records and revenue claims
+    fn transfer_shares(env: &Env, asset_id: &Symbol, from: &Address, to: &Address, amount: i128) -> Result<(), ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        Gate::new(data.paused, &data.disabled_features, &symbol_short!("shares")).check()?;
+
+        if amount <= 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+        let asset = Self::load_asset(env, asset_id).ok_or(ContractError::AssetNotFound)?;
+
+        // Units committed to the exit queue or a minted position stay with the holder
+        let locked = Self::locked_position(env, from, asset_id);
+        let uncommitted = Self::invested_amount(env, from, asset_id) - Self::exit_committed(env, from, asset_id);
+        let free = uncommitted.min(Self::shares_of(env, asset_id, from)) - locked;
+        if free < amount {
+            return Err(ContractError::NoFunds);
+        }
+
+        Self::move_holding(env, &asset, from, to, amount)?;
+
+        Ok(())
+    }
+
+    /// Get the salvage sale recorded for a closed asset
+    pub fn get_salvage(env: &Env, asset_id: Symbol) -> Result<SalvageRecord, ContractError> {
+        env.storage()
+            .persistent()
+            .get(&StorageKey::Salvage(asset_id))
+            .ok_or(ContractError::NotFound)
+    }
+}
+
+#[contractimpl]
+impl LoanPoolInterface for LoanPool {
+    /// Get the repayments and defaults serviced in a calendar epoch, for governance annual reports
+    fn get_epoch_servicing(env: &Env, epoch: u32) -> ServicingStats {
+        env.storage().persistent().get(&(SERVICE_KEY, epoch)).unwrap_or_default()
+    }
+
+    /// Get the capital financed in a calendar epoch, for the governance KPI dashboard
+    fn get_epoch_financing(env: &Env, epoch: u32) -> FinancingStats {
+        let amounts: Map<Address, i128> = env.storage().persistent().get(&(KPI_KEY, epoch)).unwrap_or(Map::new(env));
+        FinancingStats {
+            financed: amounts.iter().map(|(_, amount)| amount).sum(),
+            investor_count: amounts.len(),
+            diversity_bps: metrics::diversity_bps(&amounts),
+        }
+    }
+
+    /// Get an investor's position in an asset as
+    /// (invested, capital-seconds at work since `since`, redeemed units, redeemed amount)
+    fn get_investor_position(env: &Env, investor: Address, asset_id: Symbol, since: u64) -> (i128, i128, i128, i128) {
+        let now = env.ledger().timestamp();
+
+        let mut invested: i128 = 0;
+        let mut capital_seconds: i128 = 0;
+        for investment in Self::investments_of(env, &investor, &asset_id).iter() {
+            invested += investment.amount;
+            let start = if investment.timestamp > since { investment.timestamp } else { since };
+            if now > start {
+                capital_seconds += investment.amount * (now - start) as i128;
+            }
+        }
+
+        let mut redeemed_units: i128 = 0;
+        let mut redeemed_amount: i128 = 0;
+        for request in Self::exits_of(env, &investor, &asset_id).iter() {
+            redeemed_units += request.filled_units;
+            redeemed_amount += request.paid_amount;
+        }
+
+        (invested, capital_seconds, redeemed_units, redeemed_amount)
+    }
+
+    /// Get the equity score of an asset's zone, for fee and bonus lookups by other contracts
+    fn get_asset_equity_score(env: &Env, asset_id: Symbol) -> Option<i32> {
+        Self::load_asset(env, &asset_id).map(|asset| asset.equity_score)
+    }
+
+    /// Get the funding program an asset is listed under
+    fn get_asset_program(env: &Env, asset_id: Symbol) -> Option<Symbol> {
+        Self::load_asset(env, &asset_id).map(|asset| asset.program)
+    }
+
+    /// Get an asset's attested accessibility feature
+    fn get_asset_accessibility(env: &Env, asset_id: Symbol) -> Option<Symbol> {
+        Self::load_asset(env, &asset_id)
+            .filter(|asset| Self::is_accessible(env, asset))
+            .map(|asset| asset.accessibility)
+    }
+
+    /// Check whether an asset's program is winding down
+    fn is_winding_down(env: &Env, asset_id: Symbol) -> bool {
+        match Self::load_asset(env, &asset_id) {
+            Some(asset) => env.storage().persistent().has(&StorageKey::WindDown(asset.program.clone())),
+            None => false,
+        }
+    }
+
+    /// Release an approved loan's principal to the rate adjuster disbursing it, deploying the asset.
+    /// The draw is capped by the capital escrowed for the asset; the rest stays in the pool.
+    /// Only the configured rate adjuster can draw, once per asset
+    fn release_loan_capital(env: &Env, application_id: Symbol) -> Result<i128, Error> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        Gate::new(data.paused, &data.disabled_features, &symbol_short!("disburse")).check()?;
+
+        let source = Self::equity_source(env).ok_or(ContractError::ApplicationNotFound)?;
+        source.require_auth();
+
+        let (_, asset_id, _, requested) = EquityOracleClient::new(env, &source)
+            .peek_approved_loan(&application_id)
+            .ok_or(ContractError::ApplicationNotFound)?;
+        let mut asset = Self::load_asset(env, &asset_id).ok_or(ContractError::AssetNotFound)?;
+        if asset.status != symbol_short!("funded") {
+            return Err(ContractError::InvalidStatus.into());
+        }
+
+        let token = Self::settlement_asset(env).ok_or(ContractError::NoSettlementAsset)?;
+        let principal = requested.min(asset.funded_amount);
+        if principal <= 0 {
+            return Err(ContractError::InvalidAmount.into());
+        }
+        TokenClient::new(env, &token).transfer(&env.current_contract_address(), &source, &principal);
+        data.total_pool_balance -= principal;
+
+        asset.status = symbol_short!("deployed");
+        Self::save_asset(env, &asset);
+        changes::touch(env, symbol_short!("asset"), asset_id.clone());
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        env.events().publish((symbol_short!("drawn"), asset_id.clone()), (application_id, principal));
+        env.events().publish((symbol_short!("status"), asset_id), asset.status.clone());
+
+        Ok(principal)
+    }
+
+    /// Take a payment on an asset's loan from its borrower on the configured rate adjuster's behalf.
+    /// The payment goes through the asset's repayment schedule when it has one, keeping installments,
+    /// servicing and autopay on the same ledger as the adjuster's own accrual
+    fn record_loan_repayment(env: &Env, payer: Address, asset_id: Symbol, principal: i128, interest: i128) -> Result<i128, Error> {
+        let source = Self::equity_source(env).ok_or(ContractError::NoAddress)?;
+        source.require_auth();
+        payer.require_auth();
+
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        let amount = principal + interest;
+        if principal < 0 || interest < 0 || amount == 0 {
+            return Err(ContractError::InvalidAmount.into());
+        }
+        Self::load_asset(env, &asset_id).ok_or(ContractError::AssetNotFound)?;
+
+        let token = Self::settlement_asset(env).ok_or(ContractError::NoSettlementAsset)?;
+        TokenClient::new(env, &token).transfer(&payer, &env.current_contract_address(), &amount);
+
+        let paid = match Self::load_schedule(env, &asset_id) {
+            Some(mut schedule) => Self::apply_scheduled_payment(env, &mut data, &mut schedule, &payer, amount),
+            None => Self::apply_repayment(env, &mut data, &asset_id, principal, interest, None),
+        };
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        Ok(paid)
+    }
+
+    /// Whether an asset's loan has defaulted in the pool's delinquency pipeline or at the rate adjuster
+    fn is_asset_defaulted(env: &Env, asset_id: Symbol) -> bool {
+        if env.storage().persistent().get::<_, LoanServicing>(&StorageKey::Servicing(asset_id.clone())).is_some_and(|servicing| servicing.stage == symbol_short!("default")) {
+            return true;
+        }
+        Self::equity_source(env)
+            .is_some_and(|source| EquityOracleClient::new(env, &source).is_asset_defaulted(&asset_id))
+    }
+
+    /// Get the Stellar Asset Contract investments and payouts settle in
+    fn get_settlement_asset(env: &Env) -> Option<Address> {
+        Self::settlement_asset(env)
+    }
+
+    /// Get an investor's principal returns and write-offs between two timestamps (inclusive start)
+    fn get_principal_events(env: &Env, investor: Address, from: u64, to: u64) -> Vec<(Symbol, Symbol, i128, u64)> {
+        let stream = (PRINCIPAL_KEY, investor);
+        let mut events = vec![env];
+
+        for index in 0..journal::len(env, &stream) {
+            let event: PrincipalEvent = journal::get(env, &stream, index).unwrap();
+            if event.timestamp >= from && event.timestamp < to {
+                events.push_back((event.kind, event.asset_id, event.amount, event.timestamp));
+            }
+        }
+
+        events
+    }
+
+    /// Move locked principal from a position's seller to its buyer (position market only). The buyer
+    /// takes over the shares and the investment records behind them, so redemptions and revenue
+    /// distributions follow the position
+    fn transfer_position(env: &Env, from: Address, to: Address, asset_id: Symbol, amount: i128) -> Result<(), Error> {
+        Self::position_market(env)?.require_auth();
+
+        let locked = Self::locked_position(env, &from, &asset_id);
+        if amount <= 0 || amount > locked {
+            return Err(ContractError::NoFunds.into());
+        }
+        let asset = Self::load_asset(env, &asset_id).ok_or(ContractError::AssetNotFound)?;
+
+        Self::move_holding(env, &asset, &from, &to, amount)?;
+
+        Self::set_locked_position(env, &from, &asset_id, locked - amount);
+        Self::set_locked_position(env, &to, &asset_id, Self::locked_position(env, &to, &asset_id) + amount);
+
+        env.events().publish((symbol_short!("pos_xfer"), asset_id), (from, to, amount));
+
+        Ok(())
+    }
+
+    /// Get the share tokens an account holds in an asset
+    fn share_balance(env: &Env, asset_id: Symbol, id: Address) -> i128 {
+        Self::shares_of(env, &asset_id, &id)
+    }
+
+    /// Get the share tokens outstanding for an asset
+    fn share_total_supply(env: &Env, asset_id: Symbol) -> i128 {
+        Self::outstanding_shares(env, &asset_id)
+    }
+
+    /// Get every account that has held an asset's shares, in order of first receipt
+    fn share_holders(env: &Env, asset_id: Symbol) -> Vec<Address> {
+        Self::holders_of(env, &asset_id)
+    }
+
+    /// Move an asset's shares once its linked share token has authorized the transfer (share token only)
+    fn move_shares(env: &Env, asset_id: Symbol, from: Address, to: Address, amount: i128) -> Result<(), Error> {
+        Self::get_share_token(env, asset_id.clone())
+            .ok_or(ContractError::NoAddress)?
+            .require_auth();
+
+        Self::transfer_shares(env, &asset_id, &from, &to, amount)?;
+
+        Ok(())
+    }
+
+    /// Unlock principal backing a position its holder burned (position market only)
+    fn release_position(env: &Env, holder: Address, asset_id: Symbol, amount: i128) -> Result<(), Error> {
+        Self::position_market(env)?.require_auth();
+
+        let locked = Self::locked_position(env, &holder, &asset_id);
+        if amount <= 0 || amount > locked {
+            return Err(ContractError::NoFunds.into());
+        }
+        Self::set_locked_position(env, &holder, &asset_id, locked - amount);
+
+        env.events().publish((symbol_short!("pos_free"), asset_id), (holder, amount));
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -1,22 +1,153 @@
 #![cfg(test)]
 
+extern crate std;
+
 use super::*;
 use soroban_sdk::{
-    symbol_short, vec, Address, Env, Symbol,
-    testutils::{Address as _, AuthorizedFunction, AuthorizedInvocation},
+    symbol_short, vec, Address, Bytes, Env, IntoVal, String, Symbol,
+    testutils::{Address as _, AuthorizedFunction, AuthorizedInvocation, Events, Ledger, MockAuth, MockAuthInvoke},
+    token::StellarAssetClient,
 };
+use mobility_types::{EquityOracleInterface, RateStats, ShareTokenInterface};
+
+/// Stands in for the rate adjuster, serving the loan terms and location risk a test reports
+#[contract]
+struct StubRateAdjuster;
+
+#[contractimpl]
+impl StubRateAdjuster {
+    pub fn approve(env: Env, application_id: Symbol, borrower: Address, asset_id: Symbol, principal: i128) {
+        env.storage().persistent().set(&application_id, &(borrower, asset_id, 8i32, principal));
+    }
+
+    pub fn report_risk(env: Env, risk: RiskData) {
+        env.storage().persistent().set(&risk.location.clone(), &risk);
+    }
+}
+
+#[contractimpl]
+impl EquityOracleInterface for StubRateAdjuster {
+    fn peek_equity_score(_env: &Env, _location: Symbol) -> Option<i32> {
+        None
+    }
+
+    fn peek_risk_data(env: &Env, location: Symbol) -> Option<RiskData> {
+        env.storage().persistent().get(&location)
+    }
+
+    fn peek_approved_loan(env: &Env, application_id: Symbol) -> Option<(Address, Symbol, i32, i128)> {
+        env.storage().persistent().get(&application_id)
+    }
+
+    fn get_epoch_rate_stats(_env: &Env, _epoch: u32) -> RateStats {
+        RateStats::default()
+    }
+
+    fn is_asset_defaulted(_env: &Env, _asset_id: Symbol) -> bool {
+        false
+    }
+}
+
+/// Stands in for governance, serving the default epoch calendar
+#[contract]
+struct StubGovernance;
+
+#[contractimpl]
+impl StubGovernance {
+    pub fn get_epoch_schedule(_env: Env) -> EpochSchedule {
+        EpochSchedule::default_schedule()
+    }
+
+    pub fn record_portfolio_alert(env: Env, _source: Address, delinquency_bps: i32, above_threshold: bool) {
+        env.storage().instance().set(&symbol_short!("alert"), &(delinquency_bps, above_threshold));
+    }
+
+    pub fn last_alert(env: Env) -> Option<(i32, bool)> {
+        env.storage().instance().get(&symbol_short!("alert"))
+    }
+}
+
+/// Stands in for an asset's share token, forwarding transfers to the loan pool
+#[contract]
+struct StubShareToken;
+
+#[contractimpl]
+impl StubShareToken {
+    pub fn link(env: Env, loan_pool: Address, asset_id: Symbol) {
+        env.storage().instance().set(&symbol_short!("pool"), &loan_pool);
+        env.storage().instance().set(&symbol_short!("asset"), &asset_id);
+    }
+
+    pub fn transfer(env: Env, from: Address, to: Address, amount: i128) {
+        LoanPoolClient::new(&env, &Self::get_loan_pool(&env)).move_shares(&Self::get_asset_id(&env), &from, &to, &amount);
+    }
+}
+
+#[contractimpl]
+impl ShareTokenInterface for StubShareToken {
+    fn get_loan_pool(env: &Env) -> Address {
+        env.storage().instance().get(&symbol_short!("pool")).unwrap()
+    }
+
+    fn get_asset_id(env: &Env) -> Symbol {
+        env.storage().instance().get(&symbol_short!("asset")).unwrap()
+    }
+}
+
+/// Metadata committing to a document that is just the asset's display name
+fn metadata(env: &Env, name: &str) -> Metadata {
+    Metadata {
+        content_hash: env.crypto().sha256(&Bytes::from_slice(env, name.as_bytes())),
+        uri: String::from_str(env, ""),
+        label: Symbol::new(env, ""),
+    }
+}
+
+/// Settle the pool in a new Stellar Asset Contract through a timelocked admin change and
+/// mint `balance` of it to each investor
+fn settle_in_token(env: &Env, client: &LoanPoolClient, admin: &Address, investors: &[&Address], balance: i128) -> Address {
+    let token = env.register_stellar_asset_contract(Address::generate(env));
+    let change_id = client.queue_admin_change(admin, &symbol_short!("settle"), &0, &Some(token.clone()), &86_400);
+    env.ledger().with_mut(|ledger| ledger.timestamp += 86_400);
+    client.execute_admin_change(admin, &change_id);
+
+    for investor in investors {
+        StellarAssetClient::new(env, &token).mint(investor, &balance);
+    }
+    token
+}
+
+/// Link a stub rate adjuster as the pool's equity source through a timelocked admin change
+fn link_rate_adjuster<'a>(env: &Env, client: &LoanPoolClient, admin: &Address) -> (Address, StubRateAdjusterClient<'a>) {
+    let adjuster_id = env.register_contract(None, StubRateAdjuster);
+    let change_id = client.queue_admin_change(admin, &symbol_short!("eq_src"), &0, &Some(adjuster_id.clone()), &86_400);
+    env.ledger().with_mut(|ledger| ledger.timestamp += 86_400);
+    client.execute_admin_change(admin, &change_id);
+    (adjuster_id.clone(), StubRateAdjusterClient::new(env, &adjuster_id))
+}
+
+/// Link a stub governance contract to the pool through a timelocked admin change
+fn link_governance(env: &Env, client: &LoanPoolClient, admin: &Address) -> Address {
+    let governance = env.register_contract(None, StubGovernance);
+    let change_id = client.queue_admin_change(admin, &symbol_short!("gov"), &0, &Some(governance.clone()), &86_400);
+    env.ledger().with_mut(|ledger| ledger.timestamp += 86_400);
+    client.execute_admin_change(admin, &change_id);
+    governance
+}
 
 #[test]
 fn test_initialize() {
     let env = Env::default();
+    env.mock_all_auths();
     let contract_id = env.register_contract(None, LoanPool);
+    let client = LoanPoolClient::new(&env, &contract_id);
     let admin = Address::generate(&env);
     let oracle = Address::generate(&env);
 
-    LoanPool::initialize(&env, &admin, &oracle);
+    client.initialize(&admin, &oracle);
 
     // Verify initialization
-    let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+    let data: DataKey = env.as_contract(&contract_id, || env.storage().instance().get(&DATA_KEY).unwrap());
     assert_eq!(data.admin, admin);
     assert_eq!(data.equity_oracle, oracle);
     assert_eq!(data.total_pool_balance, 0);
@@ -25,25 +156,27 @@ fn test_initialize() {
 #[test]
 fn test_create_asset() {
     let env = Env::default();
+    env.mock_all_auths();
     let contract_id = env.register_contract(None, LoanPool);
+    let client = LoanPoolClient::new(&env, &contract_id);
     let admin = Address::generate(&env);
     let oracle = Address::generate(&env);
 
-    LoanPool::initialize(&env, &admin, &oracle);
+    client.initialize(&admin, &oracle);
 
     let asset_id = symbol_short!("ebike_001");
-    let name = symbol_short!("Downtown E-Bikes");
-    let asset_type = symbol_short!("e-bike");
+    let metadata = metadata(&env, "Downtown E-Bikes");
+    let asset_type = symbol_short!("ebike");
     let target_amount = 10000;
-    let location = symbol_short!("downtown_low_income");
+    let location = Symbol::new(&env, "downtown_low_income");
 
     // Create asset
-    LoanPool::create_asset(&env, &asset_id, &name, &asset_type, &target_amount, &location);
+    client.create_asset(&admin, &asset_id, &metadata, &asset_type, &target_amount, &location, &symbol_short!("default"));
 
     // Verify asset creation
-    let asset = LoanPool::get_asset(&env, &asset_id).unwrap();
+    let asset = client.get_asset(&asset_id);
     assert_eq!(asset.id, asset_id);
-    assert_eq!(asset.name, name);
+    assert_eq!(asset.metadata, metadata);
     assert_eq!(asset.asset_type, asset_type);
     assert_eq!(asset.target_amount, target_amount);
     assert_eq!(asset.funded_amount, 0);
@@ -55,266 +188,1905 @@ fn test_create_asset() {
 #[test]
 fn test_invest_with_equity_bonus() {
     let env = Env::default();
+    env.mock_all_auths();
     let contract_id = env.register_contract(None, LoanPool);
+    let client = LoanPoolClient::new(&env, &contract_id);
     let admin = Address::generate(&env);
     let oracle = Address::generate(&env);
     let investor = Address::generate(&env);
 
-    LoanPool::initialize(&env, &admin, &oracle);
+    client.initialize(&admin, &oracle);
+    let token = settle_in_token(&env, &client, &admin, &[&investor], 1_000);
 
     // Create asset in underserved area
     let asset_id = symbol_short!("ebike_002");
-    let location = symbol_short!("underserved_zone");
+    let location = Symbol::new(&env, "underserved_zone");
     
-    LoanPool::create_asset(
-        &env, 
-        &asset_id, 
-        &symbol_short!("Community E-Bikes"), 
-        &symbol_short!("e-bike"), 
+    client.create_asset(&admin, &asset_id, 
+        &metadata(&env, "Community E-Bikes"), 
+        &symbol_short!("ebike"), 
         &5000, 
-        &location
+        &location,
+        &symbol_short!("default")
     );
 
     // Invest in the asset
     let investment_amount = 1000;
-    let equity_bonus = LoanPool::invest(&env, &investor, &asset_id, &investment_amount).unwrap();
+    let equity_bonus = client.invest(&investor, &asset_id, &investment_amount);
 
     // Verify investment
     assert!(equity_bonus > 0); // Should have equity bonus for underserved area
     
-    let asset = LoanPool::get_asset(&env, &asset_id).unwrap();
+    let asset = client.get_asset(&asset_id);
     assert_eq!(asset.funded_amount, investment_amount);
-    assert_eq!(asset.investors.len(), 1);
-    assert_eq!(asset.investors.get(0).unwrap(), investor);
+    assert_eq!(client.share_balance(&asset_id, &investor), investment_amount);
+    assert_eq!(client.share_holders(&asset_id).get(0).unwrap(), investor);
 
-    // Verify pool balance
-    assert_eq!(LoanPool::get_pool_balance(&env), investment_amount);
+    // Verify pool balance, backed by the escrowed tokens
+    assert_eq!(client.get_pool_balance(), investment_amount);
+    assert_eq!(TokenClient::new(&env, &token).balance(&contract_id), investment_amount);
+    assert_eq!(TokenClient::new(&env, &token).balance(&investor), 0);
 }
 
 #[test]
 fn test_multiple_investments() {
     let env = Env::default();
+    env.mock_all_auths();
     let contract_id = env.register_contract(None, LoanPool);
+    let client = LoanPoolClient::new(&env, &contract_id);
     let admin = Address::generate(&env);
     let oracle = Address::generate(&env);
     let investor1 = Address::generate(&env);
     let investor2 = Address::generate(&env);
 
-    LoanPool::initialize(&env, &admin, &oracle);
+    client.initialize(&admin, &oracle);
+    let token = settle_in_token(&env, &client, &admin, &[&investor1, &investor2], 12_000);
 
-    let asset_id = symbol_short!("shuttle_001");
-    LoanPool::create_asset(
-        &env, 
-        &asset_id, 
-        &symbol_short!("Community Shuttle"), 
+    let asset_id = Symbol::new(&env, "shuttle_001");
+    client.create_asset(&admin, &asset_id, 
+        &metadata(&env, "Community Shuttle"), 
         &symbol_short!("shuttle"), 
         &20000, 
-        &symbol_short!("suburban_area")
+        &Symbol::new(&env, "suburban_area"),
+        &symbol_short!("default")
     );
 
     // First investment
-    LoanPool::invest(&env, &investor1, &asset_id, &8000).unwrap();
+    client.invest(&investor1, &asset_id, &8000);
     
     // Second investment
-    LoanPool::invest(&env, &investor2, &asset_id, &12000).unwrap();
+    client.invest(&investor2, &asset_id, &12000);
 
-    let asset = LoanPool::get_asset(&env, &asset_id).unwrap();
+    let asset = client.get_asset(&asset_id);
     assert_eq!(asset.funded_amount, 20000);
     assert_eq!(asset.status, symbol_short!("funded")); // Should be fully funded
-    assert_eq!(asset.investors.len(), 2);
+    assert_eq!(client.share_holders(&asset_id).len(), 2);
+    assert_eq!(client.share_total_supply(&asset_id), 20000);
 
     // Verify investments
-    let investments = LoanPool::get_asset_investments(&env, &asset_id);
+    let investments = client.get_asset_investments(&asset_id);
     assert_eq!(investments.len(), 2);
+    assert_eq!(TokenClient::new(&env, &token).balance(&contract_id), 20000);
+    assert_eq!(TokenClient::new(&env, &token).balance(&investor1), 4000);
 }
 
 #[test]
 fn test_equity_score_calculation() {
     let env = Env::default();
+    env.mock_all_auths();
     let contract_id = env.register_contract(None, LoanPool);
+    let client = LoanPoolClient::new(&env, &contract_id);
     let admin = Address::generate(&env);
     let oracle = Address::generate(&env);
 
-    LoanPool::initialize(&env, &admin, &oracle);
+    client.initialize(&admin, &oracle);
 
     // Create asset in low-income area
-    let low_income_asset = symbol_short!("low_income_asset");
-    LoanPool::create_asset(
-        &env, 
-        &low_income_asset, 
-        &symbol_short!("Low Income E-Bikes"), 
-        &symbol_short!("e-bike"), 
+    let low_income_asset = Symbol::new(&env, "low_income_asset");
+    client.create_asset(&admin, &low_income_asset, 
+        &metadata(&env, "Low Income E-Bikes"), 
+        &symbol_short!("ebike"), 
         &5000, 
-        &symbol_short!("low_income_zone")
+        &Symbol::new(&env, "low_income_zone"),
+        &symbol_short!("default")
     );
 
     // Create asset in high-income area
-    let high_income_asset = symbol_short!("high_income_asset");
-    LoanPool::create_asset(
-        &env, 
-        &high_income_asset, 
-        &symbol_short!("High Income E-Bikes"), 
-        &symbol_short!("e-bike"), 
+    let high_income_asset = Symbol::new(&env, "high_income_asset");
+    client.create_asset(&admin, &high_income_asset, 
+        &metadata(&env, "High Income E-Bikes"), 
+        &symbol_short!("ebike"), 
         &5000, 
-        &symbol_short!("high_income_zone")
+        &Symbol::new(&env, "high_income_zone"),
+        &symbol_short!("default")
     );
 
-    let low_income_equity = LoanPool::get_asset(&env, &low_income_asset).unwrap().equity_score;
-    let high_income_equity = LoanPool::get_asset(&env, &high_income_asset).unwrap().equity_score;
+    let low_income_equity = client.get_asset(&low_income_asset).equity_score;
+    let high_income_equity = client.get_asset(&high_income_asset).equity_score;
 
     // Low-income areas should generally have higher equity scores
-    assert!(low_income_equity >= 0 && low_income_equity <= 100);
-    assert!(high_income_equity >= 0 && high_income_equity <= 100);
+    assert!((0..=100).contains(&low_income_equity));
+    assert!((0..=100).contains(&high_income_equity));
 }
 
 #[test]
 fn test_asset_lifecycle() {
     let env = Env::default();
+    env.mock_all_auths();
     let contract_id = env.register_contract(None, LoanPool);
+    let client = LoanPoolClient::new(&env, &contract_id);
     let admin = Address::generate(&env);
     let oracle = Address::generate(&env);
     let investor = Address::generate(&env);
 
-    LoanPool::initialize(&env, &admin, &oracle);
+    client.initialize(&admin, &oracle);
+    settle_in_token(&env, &client, &admin, &[&investor], 1_000);
 
-    let asset_id = symbol_short!("lifecycle_test");
-    LoanPool::create_asset(
-        &env, 
-        &asset_id, 
-        &symbol_short!("Lifecycle Test"), 
-        &symbol_short!("e-bike"), 
+    let asset_id = Symbol::new(&env, "lifecycle_test");
+    client.create_asset(&admin, &asset_id, 
+        &metadata(&env, "Lifecycle Test"), 
+        &symbol_short!("ebike"), 
         &1000, 
-        &symbol_short!("test_zone")
+        &symbol_short!("test_zone"),
+        &symbol_short!("default")
     );
 
     // Fund the asset
-    LoanPool::invest(&env, &investor, &asset_id, &1000).unwrap();
+    client.invest(&investor, &asset_id, &1000);
     
-    let asset = LoanPool::get_asset(&env, &asset_id).unwrap();
+    let asset = client.get_asset(&asset_id);
     assert_eq!(asset.status, symbol_short!("funded"));
 
     // Deploy the asset
-    LoanPool::deploy_asset(&env, &asset_id).unwrap();
+    client.deploy_asset(&admin, &asset_id);
     
-    let asset = LoanPool::get_asset(&env, &asset_id).unwrap();
+    let asset = client.get_asset(&asset_id);
     assert_eq!(asset.status, symbol_short!("deployed"));
 
     // Complete the asset
-    LoanPool::complete_asset(&env, &asset_id).unwrap();
+    client.complete_asset(&admin, &asset_id);
     
-    let asset = LoanPool::get_asset(&env, &asset_id).unwrap();
+    let asset = client.get_asset(&asset_id);
     assert_eq!(asset.status, symbol_short!("completed"));
 }
 
 #[test]
 fn test_get_all_assets() {
     let env = Env::default();
+    env.mock_all_auths();
     let contract_id = env.register_contract(None, LoanPool);
+    let client = LoanPoolClient::new(&env, &contract_id);
     let admin = Address::generate(&env);
     let oracle = Address::generate(&env);
 
-    LoanPool::initialize(&env, &admin, &oracle);
+    client.initialize(&admin, &oracle);
 
     // Create multiple assets
-    LoanPool::create_asset(
-        &env, 
-        &symbol_short!("asset1"), 
-        &symbol_short!("Asset 1"), 
-        &symbol_short!("e-bike"), 
+    client.create_asset(&admin, &symbol_short!("asset1"), 
+        &metadata(&env, "Asset 1"), 
+        &symbol_short!("ebike"), 
         &1000, 
-        &symbol_short!("zone1")
+        &symbol_short!("zone1"),
+        &symbol_short!("default")
     );
 
-    LoanPool::create_asset(
-        &env, 
-        &symbol_short!("asset2"), 
-        &symbol_short!("Asset 2"), 
+    client.create_asset(&admin, &symbol_short!("asset2"), 
+        &metadata(&env, "Asset 2"), 
         &symbol_short!("shuttle"), 
         &2000, 
-        &symbol_short!("zone2")
+        &symbol_short!("zone2"),
+        &symbol_short!("default")
     );
 
-    let all_assets = LoanPool::get_all_assets(&env);
+    let all_assets = client.get_all_assets();
     assert_eq!(all_assets.len(), 2);
 }
 
 #[test]
-#[should_panic(expected = "ASSET_NOT_FOUND")]
+#[should_panic(expected = "Error(Contract, #12)")]
 fn test_get_nonexistent_asset() {
     let env = Env::default();
+    env.mock_all_auths();
     let contract_id = env.register_contract(None, LoanPool);
+    let client = LoanPoolClient::new(&env, &contract_id);
     let admin = Address::generate(&env);
     let oracle = Address::generate(&env);
 
-    LoanPool::initialize(&env, &admin, &oracle);
+    client.initialize(&admin, &oracle);
     
     // Try to get non-existent asset
-    LoanPool::get_asset(&env, &symbol_short!("nonexistent")).unwrap();
+    client.get_asset(&Symbol::new(&env, "nonexistent"));
 }
 
 #[test]
-#[should_panic(expected = "INVALID_AMOUNT")]
+#[should_panic(expected = "Error(Contract, #5)")]
 fn test_invest_invalid_amount() {
     let env = Env::default();
+    env.mock_all_auths();
     let contract_id = env.register_contract(None, LoanPool);
+    let client = LoanPoolClient::new(&env, &contract_id);
     let admin = Address::generate(&env);
     let oracle = Address::generate(&env);
     let investor = Address::generate(&env);
 
-    LoanPool::initialize(&env, &admin, &oracle);
+    client.initialize(&admin, &oracle);
 
-    let asset_id = symbol_short!("test_asset");
-    LoanPool::create_asset(
-        &env, 
-        &asset_id, 
-        &symbol_short!("Test Asset"), 
-        &symbol_short!("e-bike"), 
+    let asset_id = Symbol::new(&env, "test_asset");
+    client.create_asset(&admin, &asset_id, 
+        &metadata(&env, "Test Asset"), 
+        &symbol_short!("ebike"), 
         &1000, 
-        &symbol_short!("test_zone")
+        &symbol_short!("test_zone"),
+        &symbol_short!("default")
     );
 
     // Try to invest with invalid amount
-    LoanPool::invest(&env, &investor, &asset_id, &0).unwrap();
+    client.invest(&investor, &asset_id, &0);
 }
 
 #[test]
 fn test_equity_bonus_calculation() {
     let env = Env::default();
+    env.mock_all_auths();
     let contract_id = env.register_contract(None, LoanPool);
+    let client = LoanPoolClient::new(&env, &contract_id);
     let admin = Address::generate(&env);
     let oracle = Address::generate(&env);
     let investor = Address::generate(&env);
 
-    LoanPool::initialize(&env, &admin, &oracle);
+    client.initialize(&admin, &oracle);
+    settle_in_token(&env, &client, &admin, &[&investor], 1_000);
 
     // Test investment in underserved area (should get higher bonus)
-    let underserved_asset = symbol_short!("underserved_asset");
-    LoanPool::create_asset(
-        &env, 
-        &underserved_asset, 
-        &symbol_short!("Underserved Asset"), 
-        &symbol_short!("e-bike"), 
+    let underserved_asset = Symbol::new(&env, "underserved_asset");
+    client.create_asset(&admin, &underserved_asset, 
+        &metadata(&env, "Underserved Asset"), 
+        &symbol_short!("ebike"), 
         &1000, 
-        &symbol_short!("underserved_zone")
+        &Symbol::new(&env, "underserved_zone"),
+        &symbol_short!("default")
     );
 
-    let underserved_bonus = LoanPool::invest(&env, &investor, &underserved_asset, &500).unwrap();
+    let underserved_bonus = client.invest(&investor, &underserved_asset, &500);
 
     // Test investment in regular area (should get lower bonus)
-    let regular_asset = symbol_short!("regular_asset");
-    LoanPool::create_asset(
-        &env, 
-        &regular_asset, 
-        &symbol_short!("Regular Asset"), 
-        &symbol_short!("e-bike"), 
+    let regular_asset = Symbol::new(&env, "regular_asset");
+    client.create_asset(&admin, &regular_asset, 
+        &metadata(&env, "Regular Asset"), 
+        &symbol_short!("ebike"), 
         &1000, 
-        &symbol_short!("regular_zone")
+        &Symbol::new(&env, "regular_zone"),
+        &symbol_short!("default")
     );
 
-    let regular_bonus = LoanPool::invest(&env, &investor, &regular_asset, &500).unwrap();
+    let regular_bonus = client.invest(&investor, &regular_asset, &500);
 
     // Underserved areas should generally get higher equity bonuses
     assert!(underserved_bonus >= regular_bonus);
     assert!(underserved_bonus <= 25); // Max bonus cap
     assert!(regular_bonus <= 25); // Max bonus cap
 }
+
+#[test]
+fn test_equity_bonus_is_audited() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, LoanPool);
+    let client = LoanPoolClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let investor = Address::generate(&env);
+
+    client.initialize(&admin, &oracle);
+    settle_in_token(&env, &client, &admin, &[&investor], 500);
+
+    let asset_id = Symbol::new(&env, "audit_asset");
+    client.create_asset(&admin, &asset_id, 
+        &metadata(&env, "Audit Asset"), 
+        &symbol_short!("ebike"), 
+        &1000, 
+        &Symbol::new(&env, "underserved_zone"),
+        &symbol_short!("default")
+    );
+
+    let bonus = client.invest(&investor, &asset_id, &500);
+
+    // Every granted bonus should leave an audit record
+    let by_address = client.get_boost_audit_by_address(&investor, &PageRequest { cursor: 0, limit: 10 });
+    assert_eq!(by_address.items.len(), 1);
+    assert_eq!(by_address.items.get(0).unwrap().amount, bonus as i128);
+
+    let by_rule = client.get_boost_audit_by_rule(&symbol_short!("inv_bonus"), &PageRequest { cursor: 0, limit: 10 });
+    assert_eq!(by_rule.items.len(), 1);
+}
+
+#[test]
+fn test_session_budget_limits_repeat_investments() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, LoanPool);
+    let client = LoanPoolClient::new(&env, &contract_id);
+    let oracle = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let session_key = Address::generate(&env);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &oracle);
+    let token = settle_in_token(&env, &client, &admin, &[&owner], 1_000);
+    let asset_id = symbol_short!("sess_bike");
+    client.create_asset(&admin, &asset_id, &metadata(&env, "Bikes"), &symbol_short!("ebike"), &10_000, &symbol_short!("downtown"), &symbol_short!("default"));
+
+    client.grant_session(&owner, &session_key, &300, &(7 * 86_400));
+    TokenClient::new(&env, &token).approve(&owner, &contract_id, &1_000, &(env.ledger().sequence() + 100));
+    client.invest_with_session(&session_key, &owner, &asset_id, &200);
+
+    // The session key signed, not the owner
+    assert_eq!(env.auths().first().unwrap().0, session_key);
+    assert_eq!(client.get_session(&owner, &session_key).spent, 200);
+    assert_eq!(TokenClient::new(&env, &token).balance(&owner), 800);
+
+    // Spending past the budget is rejected and pulls nothing, though funds and allowance remain
+    assert_eq!(
+        client.try_invest_with_session(&session_key, &owner, &asset_id, &200),
+        Err(Ok(ContractError::OverBudget))
+    );
+    assert_eq!(TokenClient::new(&env, &token).balance(&owner), 800);
+    assert_eq!(client.get_asset(&asset_id).funded_amount, 200);
+
+    client.revoke_session(&owner, &session_key);
+    assert_eq!(
+        client.try_invest_with_session(&session_key, &owner, &asset_id, &50),
+        Err(Ok(ContractError::NotFound))
+    );
+}
+
+#[test]
+fn test_expired_asset_refunds_each_investor_once() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, LoanPool);
+    let client = LoanPoolClient::new(&env, &contract_id);
+    let oracle = Address::generate(&env);
+    let investor = Address::generate(&env);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &oracle);
+    let token = TokenClient::new(&env, &settle_in_token(&env, &client, &admin, &[&investor], 2_500));
+    let change_id = client.set_funding_period(&admin, &(30 * 86_400));
+    env.ledger().with_mut(|ledger| ledger.timestamp += 86_400);
+    client.execute_admin_change(&admin, &change_id);
+    let asset_id = symbol_short!("slow_bus");
+    client.create_asset(&admin, &asset_id, &metadata(&env, "Bus"), &symbol_short!("shuttle"), &10_000, &symbol_short!("downtown"), &symbol_short!("default"));
+    client.invest(&investor, &asset_id, &2_500);
+
+    // Funding stays open until the deadline
+    assert_eq!(client.try_finalize_funding(&asset_id), Err(Ok(ContractError::TooEarly)));
+    assert_eq!(client.try_refund(&investor, &asset_id), Err(Ok(ContractError::InvalidStatus)));
+
+    env.ledger().with_mut(|ledger| ledger.timestamp += 30 * 86_400);
+    assert_eq!(client.try_invest(&investor, &asset_id, &100), Err(Ok(ContractError::Expired)));
+    assert_eq!(client.finalize_funding(&asset_id), symbol_short!("expired"));
+
+    assert_eq!(client.refund(&investor, &asset_id), 2_500);
+    assert_eq!(client.get_pool_balance(), 0);
+    assert_eq!(token.balance(&investor), 2_500);
+    assert_eq!(token.balance(&contract_id), 0);
+    assert_eq!(client.try_refund(&investor, &asset_id), Err(Ok(ContractError::Done)));
+}
+
+#[test]
+fn test_seasonal_low_relaxes_sla_floors() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, LoanPool);
+    let client = LoanPoolClient::new(&env, &contract_id);
+    let operator = Address::generate(&env);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    client.initialize(&admin, &oracle);
+    let asset_id = symbol_short!("win_bike");
+    let zone = symbol_short!("downtown");
+    let bike = symbol_short!("ebike");
+    client.create_asset(&admin, &asset_id, &metadata(&env, "Bikes"), &bike, &10_000, &zone, &symbol_short!("default"));
+    client.set_payout_split(&admin, &asset_id, &vec![&env, PayoutSplit { recipient: operator.clone(), share_bps: 10_000 }]);
+    client.commit_sla(&operator, &asset_id, &9_000, &3_600, &0);
+
+    // Eleven factors, or one outside the accepted range, are rejected
+    let mut factors = vec![&env, 5_000u32];
+    for _ in 1..11 {
+        factors.push_back(10_000);
+    }
+    assert_eq!(client.try_set_seasonal_baseline(&zone, &bike, &factors), Err(Ok(ContractError::InvalidInput)));
+    factors.push_back(50_000);
+    assert_eq!(client.try_set_seasonal_baseline(&zone, &bike, &factors), Err(Ok(ContractError::InvalidInput)));
+    factors.set(11, 10_000);
+    client.set_seasonal_baseline(&zone, &bike, &factors);
+
+    // January runs at half of an average month, so 60% availability clears the relaxed 45% floor
+    assert_eq!(client.report_sla_compliance(&asset_id, &6_000, &600, &0), 0);
+    assert_eq!(client.report_sla_compliance(&asset_id, &6_000, &600, &0), 0);
+
+    // The same service in July breaches the full 90% floor
+    env.ledger().with_mut(|ledger| ledger.timestamp = 6 * 2_629_800);
+    client.report_sla_compliance(&asset_id, &6_000, &600, &0);
+    assert!(client.report_sla_compliance(&asset_id, &6_000, &600, &0) > 0);
+}
+
+#[test]
+fn test_pause_halts_writes_but_not_views() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, LoanPool);
+    let client = LoanPoolClient::new(&env, &contract_id);
+    let investor = Address::generate(&env);
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    client.initialize(&admin, &oracle);
+    settle_in_token(&env, &client, &admin, &[&investor], 2_000);
+    let asset_id = symbol_short!("halt_bike");
+    client.create_asset(&admin, &asset_id, &metadata(&env, "Bikes"), &symbol_short!("ebike"), &10_000, &symbol_short!("downtown"), &symbol_short!("default"));
+    client.invest(&investor, &asset_id, &1_000);
+
+    // Pausing takes the pauser role, which the admin can hand to another account
+    let guardian = Address::generate(&env);
+    assert_eq!(client.try_pause(&guardian), Err(Ok(ContractError::Unauthorized)));
+    client.grant_role(&admin, &Role::Pauser, &guardian);
+    client.pause(&guardian);
+    assert!(client.is_paused());
+    assert_eq!(client.try_invest(&investor, &asset_id, &1_000), Err(Ok(ContractError::Paused)));
+    assert_eq!(client.try_update_asset_valuation(&asset_id, &100, &0), Err(Ok(ContractError::Paused)));
+    assert_eq!(client.get_asset(&asset_id).funded_amount, 1_000);
+    assert_eq!(client.get_pool_balance(), 1_000);
+
+    client.unpause(&guardian);
+    assert!(!client.is_paused());
+    client.invest(&investor, &asset_id, &1_000);
+    assert_eq!(client.get_asset(&asset_id).funded_amount, 2_000);
+}
+
+#[test]
+fn test_share_transfers_carry_investment_records() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, LoanPool);
+    let client = LoanPoolClient::new(&env, &contract_id);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &oracle);
+    settle_in_token(&env, &client, &admin, &[&alice, &bob], 3_000);
+    let asset_id = symbol_short!("share_bus");
+    client.create_asset(&admin, &asset_id, &metadata(&env, "Bus"), &symbol_short!("shuttle"), &10_000, &symbol_short!("downtown"), &symbol_short!("default"));
+    client.invest(&alice, &asset_id, &3_000);
+    client.invest(&bob, &asset_id, &1_000);
+    assert_eq!(client.share_total_supply(&asset_id), 4_000);
+
+    // Only a token that names this pool and the asset can be linked, and only it moves shares
+    let share_token = StubShareTokenClient::new(&env, &env.register_contract(None, StubShareToken));
+    share_token.link(&contract_id, &symbol_short!("other"));
+    assert_eq!(
+        client.try_set_share_token(&admin, &asset_id, &share_token.address),
+        Err(Ok(ContractError::InvalidInput))
+    );
+    assert_eq!(
+        client.try_move_shares(&asset_id, &alice, &carol, &1_200),
+        Err(Ok(ContractError::NoAddress.into()))
+    );
+    share_token.link(&contract_id, &asset_id);
+    client.set_share_token(&admin, &asset_id, &share_token.address);
+    assert_eq!(client.get_share_token(&asset_id), Some(share_token.address.clone()));
+
+    // Shares move with the investment records behind them
+    share_token.transfer(&alice, &carol, &1_200);
+    assert_eq!(client.share_balance(&asset_id, &alice), 1_800);
+    assert_eq!(client.share_balance(&asset_id, &carol), 1_200);
+    assert_eq!(client.get_asset_investments(&asset_id).iter().map(|investment| investment.amount).sum::<i128>(), 4_000);
+    assert_eq!(
+        client.try_move_shares(&asset_id, &bob, &carol, &1_001),
+        Err(Ok(ContractError::NoFunds.into()))
+    );
+    assert_eq!(client.share_balance(&asset_id, &bob), 1_000);
+    share_token.transfer(&bob, &carol, &400);
+
+    assert_eq!(client.share_holders(&asset_id).len(), 3);
+    assert_eq!(client.share_total_supply(&asset_id), 4_000);
+    assert!(client.verify_invariants().is_empty());
+}
+
+#[test]
+fn test_calls_require_the_acting_accounts_signature() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, LoanPool);
+    let client = LoanPoolClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let investor = Address::generate(&env);
+
+    client.initialize(&admin, &oracle);
+    let token = settle_in_token(&env, &client, &admin, &[&investor], 500);
+    let asset_id = symbol_short!("auth_bike");
+    client.create_asset(&admin, &asset_id, &metadata(&env, "Bikes"), &symbol_short!("ebike"), &10_000, &symbol_short!("downtown"), &symbol_short!("default"));
+
+    // A failed signature check traps inside the contract, which aborts native test runs, so each
+    // call is made with only the expected signer mocked and the recorded auths are checked
+    let transfer = MockAuthInvoke {
+        contract: &token,
+        fn_name: "transfer",
+        args: (&investor, &contract_id, 500i128).into_val(&env),
+        sub_invokes: &[],
+    };
+    let invest = MockAuthInvoke {
+        contract: &contract_id,
+        fn_name: "invest",
+        args: (&investor, &asset_id, 500i128).into_val(&env),
+        sub_invokes: &[transfer],
+    };
+    client.mock_auths(&[MockAuth { address: &investor, invoke: &invest }]).invest(&investor, &asset_id, &500);
+    assert_eq!(
+        env.auths(),
+        std::vec![(
+            investor.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    contract_id.clone(),
+                    symbol_short!("invest"),
+                    (&investor, &asset_id, 500i128).into_val(&env),
+                )),
+                sub_invocations: std::vec![AuthorizedInvocation {
+                    function: AuthorizedFunction::Contract((
+                        token.clone(),
+                        symbol_short!("transfer"),
+                        (&investor, &contract_id, 500i128).into_val(&env),
+                    )),
+                    sub_invocations: std::vec![],
+                }],
+            }
+        )]
+    );
+
+    // Valuations carry the oracle's signature, not the contract's own address
+    let update_valuation = MockAuthInvoke {
+        contract: &contract_id,
+        fn_name: "update_asset_valuation",
+        args: (&asset_id, 100i128, 0i128).into_val(&env),
+        sub_invokes: &[],
+    };
+    client
+        .mock_auths(&[MockAuth { address: &oracle, invoke: &update_valuation }])
+        .update_asset_valuation(&asset_id, &100, &0);
+    assert_eq!(env.auths().first().unwrap().0, oracle);
+
+    // Role-gated calls carry the named caller's signature as well as the role
+    env.mock_all_auths();
+    let guardian = Address::generate(&env);
+    client.grant_role(&admin, &Role::Pauser, &guardian);
+    client.pause(&guardian);
+    assert_eq!(env.auths().first().unwrap().0, guardian);
+}
+
+#[test]
+fn test_loan_draws_the_approved_principal_capped_by_escrow() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, LoanPool);
+    let client = LoanPoolClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let investor = Address::generate(&env);
+    let borrower = Address::generate(&env);
+
+    client.initialize(&admin, &oracle);
+    let token = TokenClient::new(&env, &settle_in_token(&env, &client, &admin, &[&investor], 6_000));
+    let (adjuster_id, adjuster) = link_rate_adjuster(&env, &client, &admin);
+
+    let bus = symbol_short!("loan_bus");
+    let bike = symbol_short!("loan_bike");
+    client.create_asset(&admin, &bus, &metadata(&env, "Bus"), &symbol_short!("shuttle"), &4_000, &symbol_short!("downtown"), &symbol_short!("default"));
+    client.create_asset(&admin, &bike, &metadata(&env, "Bikes"), &symbol_short!("ebike"), &2_000, &symbol_short!("downtown"), &symbol_short!("default"));
+    client.invest(&investor, &bus, &4_000);
+    client.invest(&investor, &bike, &2_000);
+
+    // A loan smaller than the raise draws only its principal; the rest stays escrowed
+    adjuster.approve(&symbol_short!("app_bus"), &borrower, &bus, &3_000);
+    assert_eq!(client.release_loan_capital(&symbol_short!("app_bus")), 3_000);
+    assert_eq!(token.balance(&adjuster_id), 3_000);
+    assert_eq!(client.get_asset(&bus).status, symbol_short!("deployed"));
+
+    // A loan larger than the raise draws no more than the asset escrowed
+    adjuster.approve(&symbol_short!("app_bike"), &borrower, &bike, &9_000);
+    assert_eq!(client.release_loan_capital(&symbol_short!("app_bike")), 2_000);
+    assert_eq!(token.balance(&contract_id), 1_000);
+    assert_eq!(client.get_pool_balance(), 1_000);
+
+    // Each asset's capital is drawn once
+    assert!(client.try_release_loan_capital(&symbol_short!("app_bus")).is_err());
+}
+
+#[test]
+fn test_rate_adjuster_repayments_land_on_the_pools_schedule() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, LoanPool);
+    let client = LoanPoolClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let investor = Address::generate(&env);
+    let borrower = Address::generate(&env);
+
+    client.initialize(&admin, &Address::generate(&env));
+    let token = TokenClient::new(&env, &settle_in_token(&env, &client, &admin, &[&investor, &borrower], 4_000));
+    let (_, adjuster) = link_rate_adjuster(&env, &client, &admin);
+
+    let asset_id = symbol_short!("loan_bus");
+    client.create_asset(&admin, &asset_id, &metadata(&env, "Bus"), &symbol_short!("shuttle"), &4_000, &symbol_short!("downtown"), &symbol_short!("default"));
+    client.invest(&investor, &asset_id, &4_000);
+    adjuster.approve(&symbol_short!("app_bus"), &borrower, &asset_id, &3_000);
+    client.release_loan_capital(&symbol_short!("app_bus"));
+
+    // The schedule amortizes what the loan drew, not the whole raise
+    let schedule = client.create_repayment_schedule(&admin, &asset_id, &symbol_short!("app_bus"), &3, &(30 * 86_400));
+    assert_eq!(schedule.principal, 3_000);
+
+    // A payment taken by the rate adjuster moves the borrower's tokens and the pool's schedule together
+    let first = client.get_next_installment(&asset_id);
+    let due = first.principal + first.interest;
+    client.record_loan_repayment(&borrower, &asset_id, &first.principal, &first.interest);
+    assert_eq!(token.balance(&borrower), 4_000 - due);
+    assert_eq!(token.balance(&contract_id), 1_000 + due);
+    assert_eq!(client.get_repayment_schedule(&asset_id).next_installment, 1);
+    assert_eq!(client.get_repayment_schedule(&asset_id).total_paid, due);
+    assert_eq!(client.get_loan_servicing(&asset_id).next_due_at, client.get_next_installment(&asset_id).due_at);
+    assert_eq!(
+        client.try_record_loan_repayment(&borrower, &asset_id, &0, &0),
+        Err(Ok(ContractError::InvalidAmount.into()))
+    );
+}
+
+#[test]
+fn test_withdrawn_payouts_arrive_in_the_settlement_asset() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, LoanPool);
+    let client = LoanPoolClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let investor = Address::generate(&env);
+    let operator = Address::generate(&env);
+    let maintenance = Address::generate(&env);
+    let treasurer = Address::generate(&env);
+
+    client.initialize(&admin, &Address::generate(&env));
+    let token = TokenClient::new(&env, &settle_in_token(&env, &client, &admin, &[&investor], 1_000));
+    let asset_id = symbol_short!("split_bus");
+    client.create_asset(&admin, &asset_id, &metadata(&env, "Bus"), &symbol_short!("shuttle"), &1_000, &symbol_short!("downtown"), &symbol_short!("default"));
+    client.set_payout_split(&admin, &asset_id, &vec![
+        &env,
+        PayoutSplit { recipient: operator.clone(), share_bps: 7_000 },
+        PayoutSplit { recipient: maintenance.clone(), share_bps: 3_000 },
+    ]);
+    client.invest(&investor, &asset_id, &1_000);
+    client.deploy_asset(&admin, &asset_id);
+    client.grant_role(&admin, &Role::Treasurer, &treasurer);
+    client.release_to_operator(&treasurer, &asset_id, &1_000);
+    assert_eq!(client.get_payout_balance(&operator), 700);
+
+    // Withdrawing moves the credited tokens out of the pool, once
+    assert_eq!(client.withdraw_payout(&operator), 700);
+    assert_eq!(token.balance(&operator), 700);
+    assert_eq!(token.balance(&contract_id), 300);
+    assert_eq!(client.get_payout_balance(&operator), 0);
+    assert_eq!(client.try_withdraw_payout(&operator), Err(Ok(ContractError::Nothing)));
+
+    assert_eq!(client.withdraw_payout(&maintenance), 300);
+    assert_eq!(token.balance(&maintenance), 300);
+    assert_eq!(token.balance(&contract_id), client.get_pool_balance());
+}
+
+#[test]
+fn test_wind_down_pays_its_recipients_in_tokens() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, LoanPool);
+    let client = LoanPoolClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let (alice, bob) = (Address::generate(&env), Address::generate(&env));
+
+    client.initialize(&admin, &Address::generate(&env));
+    let token = TokenClient::new(&env, &settle_in_token(&env, &client, &admin, &[&alice, &bob], 3_000));
+    link_governance(&env, &client, &admin);
+
+    let program = symbol_short!("transit");
+    let asset_id = symbol_short!("wind_bus");
+    client.create_asset(&admin, &asset_id, &metadata(&env, "Bus"), &symbol_short!("shuttle"), &5_000, &symbol_short!("downtown"), &program);
+    client.invest(&alice, &asset_id, &3_000);
+    client.invest(&bob, &asset_id, &2_000);
+
+    // The plan returns the funded capital to the asset's investors in proportion to their stakes
+    client.start_wind_down(&program, &vec![
+        &env,
+        PayoutSplit { recipient: alice.clone(), share_bps: 6_000 },
+        PayoutSplit { recipient: bob.clone(), share_bps: 4_000 },
+    ]);
+    assert_eq!(client.try_settle_wind_down(&program), Err(Ok(ContractError::Notice)));
+    env.ledger().with_mut(|ledger| ledger.timestamp += 30 * 86_400);
+    assert_eq!(client.settle_wind_down(&program), 5_000);
+
+    assert_eq!(client.withdraw_payout(&alice), 3_000);
+    assert_eq!(client.withdraw_payout(&bob), 2_000);
+    assert_eq!(token.balance(&alice), 3_000);
+    assert_eq!(token.balance(&bob), 3_000);
+    assert_eq!(token.balance(&contract_id), 0);
+    assert_eq!(client.get_pool_balance(), 0);
+}
+
+#[test]
+fn test_released_penalty_reserve_reaches_the_recipient_in_tokens() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, LoanPool);
+    let client = LoanPoolClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let investor = Address::generate(&env);
+    let operator = Address::generate(&env);
+    let maintenance = Address::generate(&env);
+    let treasurer = Address::generate(&env);
+
+    client.initialize(&admin, &Address::generate(&env));
+    let token = TokenClient::new(&env, &settle_in_token(&env, &client, &admin, &[&investor], 1_000));
+    link_governance(&env, &client, &admin);
+    let asset_id = symbol_short!("sla_bus");
+    client.create_asset(&admin, &asset_id, &metadata(&env, "Bus"), &symbol_short!("shuttle"), &1_000, &symbol_short!("downtown"), &symbol_short!("default"));
+    client.set_payout_split(&admin, &asset_id, &vec![&env, PayoutSplit { recipient: operator.clone(), share_bps: 10_000 }]);
+    client.commit_sla(&operator, &asset_id, &9_000, &3_600, &0);
+    client.invest(&investor, &asset_id, &1_000);
+    client.deploy_asset(&admin, &asset_id);
+    client.grant_role(&admin, &Role::Treasurer, &treasurer);
+
+    // Repeated breaches hold part of the operator's release in the penalty reserve
+    client.report_sla_compliance(&asset_id, &6_000, &600, &0);
+    let haircut_bps = client.report_sla_compliance(&asset_id, &6_000, &600, &0);
+    assert!(haircut_bps > 0);
+    client.release_to_operator(&treasurer, &asset_id, &1_000);
+    let held = 1_000 * haircut_bps as i128 / 10_000;
+    assert_eq!(client.get_penalty_reserve(&asset_id), held);
+    assert_eq!(client.withdraw_payout(&operator), 1_000 - held);
+
+    // Releasing the reserve credits the recipient, who withdraws it in tokens
+    assert_eq!(client.release_penalty_reserve(&asset_id, &maintenance), held);
+    assert_eq!(client.get_penalty_reserve(&asset_id), 0);
+    assert_eq!(client.withdraw_payout(&maintenance), held);
+    assert_eq!(token.balance(&maintenance), held);
+    assert_eq!(token.balance(&operator), 1_000 - held);
+    assert_eq!(token.balance(&contract_id), 0);
+    assert_eq!(client.try_release_penalty_reserve(&asset_id, &maintenance), Err(Ok(ContractError::NoFunds)));
+}
+
+#[test]
+fn test_first_loss_recoveries_reach_investors_in_tokens() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, LoanPool);
+    let client = LoanPoolClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let (alice, bob, sponsor) = (Address::generate(&env), Address::generate(&env), Address::generate(&env));
+
+    client.initialize(&admin, &Address::generate(&env));
+    let token_id = settle_in_token(&env, &client, &admin, &[&sponsor], 600);
+    let token = TokenClient::new(&env, &token_id);
+    StellarAssetClient::new(&env, &token_id).mint(&alice, &600);
+    StellarAssetClient::new(&env, &token_id).mint(&bob, &400);
+
+    let zone = symbol_short!("downtown");
+    let asset_id = symbol_short!("loss_bus");
+    client.post_guarantee(&sponsor, &symbol_short!("zone"), &zone, &600, &(env.ledger().timestamp() + 365 * 86_400));
+    client.create_asset(&admin, &asset_id, &metadata(&env, "Bus"), &symbol_short!("shuttle"), &1_000, &zone, &symbol_short!("default"));
+    client.invest(&alice, &asset_id, &600);
+    client.invest(&bob, &asset_id, &400);
+    client.deploy_asset(&admin, &asset_id);
+    client.set_payment_terms(&admin, &asset_id, &(30 * 86_400), &100);
+
+    // Nothing is ever paid, so the loan walks the pipeline to default
+    env.ledger().with_mut(|ledger| ledger.timestamp += 30 * 86_400 + 91 * 86_400);
+    let mut stage = symbol_short!("current");
+    while stage != symbol_short!("default") {
+        stage = client.advance_delinquency(&asset_id);
+    }
+    assert_eq!(client.get_guarantee(&symbol_short!("zone"), &zone).absorbed, 600);
+
+    // The sponsor's escrow covers 60% of the loss, paid out pro rata in tokens
+    assert_eq!(client.withdraw_payout(&alice), 360);
+    assert_eq!(client.withdraw_payout(&bob), 240);
+    assert_eq!(token.balance(&alice), 360);
+    assert_eq!(token.balance(&bob), 240);
+    assert_eq!(token.balance(&contract_id), 1_000);
+}
+
+#[test]
+fn test_recycled_principal_withdraws_from_the_lp_vault() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, LoanPool);
+    let client = LoanPoolClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let investor = Address::generate(&env);
+    let borrower = Address::generate(&env);
+
+    client.initialize(&admin, &Address::generate(&env));
+    let token = TokenClient::new(&env, &settle_in_token(&env, &client, &admin, &[&investor, &borrower], 4_000));
+    let (_, adjuster) = link_rate_adjuster(&env, &client, &admin);
+
+    let asset_id = symbol_short!("vault_bus");
+    client.create_asset(&admin, &asset_id, &metadata(&env, "Bus"), &symbol_short!("shuttle"), &4_000, &symbol_short!("downtown"), &symbol_short!("default"));
+    client.invest(&investor, &asset_id, &4_000);
+    adjuster.approve(&symbol_short!("app_bus"), &borrower, &asset_id, &2_000);
+    client.release_loan_capital(&symbol_short!("app_bus"));
+    let schedule = client.create_repayment_schedule(&admin, &asset_id, &symbol_short!("app_bus"), &3, &(30 * 86_400));
+    let owed: i128 = schedule.installments.iter().map(|installment| installment.principal + installment.interest).sum();
+    client.record_repayment(&borrower, &asset_id, &owed);
+    assert_eq!(token.balance(&contract_id), client.get_pool_balance());
+
+    // Completing the asset rolls the opted-in investor's principal into the vault
+    client.set_recycling(&investor, &None);
+    client.complete_asset(&admin, &asset_id);
+    let recycled = client.get_lp_vault_balance(&investor);
+    assert!(recycled > 0);
+    assert_eq!(client.get_recycle_history(&investor, &PageRequest { cursor: 0, limit: 10 }).items.get(0).unwrap().amount, recycled);
+
+    assert_eq!(client.withdraw_lp_vault(&investor), recycled);
+    assert_eq!(token.balance(&investor), recycled);
+    assert_eq!(client.get_lp_vault_balance(&investor), 0);
+    assert_eq!(token.balance(&contract_id), client.get_pool_balance());
+    assert_eq!(client.try_withdraw_lp_vault(&investor), Err(Ok(ContractError::Nothing)));
+    assert!(client.verify_invariants().is_empty());
+}
+
+#[test]
+fn test_loyalty_conversions_are_backed_by_reserve_tokens() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, LoanPool);
+    let client = LoanPoolClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let treasurer = Address::generate(&env);
+    let rider = Address::generate(&env);
+
+    client.initialize(&admin, &Address::generate(&env));
+    let token = TokenClient::new(&env, &settle_in_token(&env, &client, &admin, &[&treasurer], 1_000));
+    client.grant_role(&admin, &Role::Treasurer, &treasurer);
+
+    let zone = Symbol::new(&env, "underserved_east");
+    let asset_id = symbol_short!("loyal_bik");
+    client.create_asset(&admin, &asset_id, &metadata(&env, "Bikes"), &symbol_short!("ebike"), &5_000, &zone, &symbol_short!("default"));
+    let credits = client.record_rider_rides(&rider, &zone, &10);
+    assert!(credits > 0);
+
+    // The reserve is pulled from the treasurer, but only becomes pool capital on conversion
+    client.fund_loyalty_reserve(&treasurer, &credits);
+    assert_eq!(token.balance(&treasurer), 1_000 - credits);
+    assert_eq!(token.balance(&contract_id), credits);
+    assert_eq!(client.get_pool_balance(), 0);
+
+    assert_eq!(client.convert_credits(&rider, &asset_id, &credits), credits);
+    assert_eq!(client.get_loyalty_credits(&rider, &zone), 0);
+    assert_eq!(token.balance(&contract_id), client.get_pool_balance());
+    assert!(client.verify_invariants().is_empty());
+}
+
+#[test]
+fn test_rebalance_invests_vault_capital_in_the_target_assets() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, LoanPool);
+    let client = LoanPoolClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let investor = Address::generate(&env);
+    let borrower = Address::generate(&env);
+
+    client.initialize(&admin, &Address::generate(&env));
+    let token = TokenClient::new(&env, &settle_in_token(&env, &client, &admin, &[&investor, &borrower], 4_000));
+    let (_, adjuster) = link_rate_adjuster(&env, &client, &admin);
+    link_governance(&env, &client, &admin);
+
+    // Recycle a repaid asset's principal into the investor's vault balance
+    let asset_id = symbol_short!("done_bus");
+    client.create_asset(&admin, &asset_id, &metadata(&env, "Bus"), &symbol_short!("shuttle"), &4_000, &symbol_short!("downtown"), &symbol_short!("default"));
+    client.invest(&investor, &asset_id, &4_000);
+    adjuster.approve(&symbol_short!("app_bus"), &borrower, &asset_id, &2_000);
+    client.release_loan_capital(&symbol_short!("app_bus"));
+    let schedule = client.create_repayment_schedule(&admin, &asset_id, &symbol_short!("app_bus"), &3, &(30 * 86_400));
+    let owed: i128 = schedule.installments.iter().map(|installment| installment.principal + installment.interest).sum();
+    client.record_repayment(&borrower, &asset_id, &owed);
+    client.set_recycling(&investor, &None);
+    client.complete_asset(&admin, &asset_id);
+    let vault = client.get_lp_vault_balance(&investor);
+    assert!(vault > 0);
+
+    // Half the vault moves into one raising asset and a quarter into another
+    let east = symbol_short!("east_bik");
+    let west = symbol_short!("west_bik");
+    client.create_asset(&admin, &east, &metadata(&env, "East"), &symbol_short!("ebike"), &10_000, &symbol_short!("eastside"), &symbol_short!("default"));
+    client.create_asset(&admin, &west, &metadata(&env, "West"), &symbol_short!("ebike"), &10_000, &symbol_short!("westside"), &symbol_short!("default"));
+    let targets = vec![
+        &env,
+        AllocationWeight { asset_id: east.clone(), weight_bps: 5_000 },
+        AllocationWeight { asset_id: west.clone(), weight_bps: 2_500 },
+    ];
+    let record = client.rebalance_pool(&symbol_short!("prop_1"), &targets);
+    assert_eq!(record.pool_capital, vault);
+    assert_eq!(record.after.get(east.clone()), Some(vault / 2));
+    assert_eq!(record.after.get(west.clone()), Some(vault / 4));
+
+    // The capital is invested on the holder's behalf and leaves the vault
+    assert_eq!(client.get_asset(&east).funded_amount, vault / 2);
+    assert_eq!(client.get_asset(&west).funded_amount, vault / 4);
+    assert_eq!(client.get_lp_vault_balance(&investor), vault - vault / 2 - vault / 4);
+    assert_eq!(client.get_pool_allocations(), record.after);
+    assert_eq!(token.balance(&contract_id), client.get_pool_balance());
+    assert!(client.verify_invariants().is_empty());
+
+    // Only assets still raising can receive capital
+    let locked = vec![&env, AllocationWeight { asset_id: asset_id.clone(), weight_bps: 1_000 }];
+    assert_eq!(client.try_rebalance_pool(&symbol_short!("prop_2"), &locked), Err(Ok(ContractError::Locked)));
+}
+
+#[test]
+fn test_premium_quote_prices_from_governance_capped_params() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, LoanPool);
+    let client = LoanPoolClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+
+    client.initialize(&admin, &oracle);
+    link_governance(&env, &client, &admin);
+
+    let asset_id = symbol_short!("ebike_001");
+    client.create_asset(&admin, &asset_id, &metadata(&env, "Downtown E-Bikes"), &symbol_short!("ebike"), &10_000, &symbol_short!("downtown"), &symbol_short!("default"));
+
+    // Nothing to price from until governance sets the inputs
+    assert_eq!(client.try_quote_premium(&asset_id), Err(Ok(ContractError::NotFound)));
+
+    // Inputs above the per-input cap are refused when queued
+    let greedy = PremiumParams { base_bps: 600, risk_point_bps: 0, type_loadings: Map::new(&env), breach_bps: 0 };
+    assert_eq!(client.try_set_premium_params(&greedy), Err(Ok(ContractError::InvalidInput)));
+
+    let params = PremiumParams {
+        base_bps: 100,
+        risk_point_bps: 10,
+        type_loadings: Map::from_array(&env, [(symbol_short!("ebike"), 50)]),
+        breach_bps: 25,
+    };
+    let change_id = client.set_premium_params(&params);
+    env.ledger().with_mut(|ledger| ledger.timestamp += timelock::MIN_DELAY);
+    client.execute_admin_change(&admin, &change_id);
+    assert_eq!(client.get_premium_params(), Some(params));
+
+    // No risk feed and a clean operator record: base plus the e-bike loading
+    let quote = client.quote_premium(&asset_id);
+    assert_eq!(quote.insured_value, 10_000);
+    assert_eq!(quote.risk_points, 0);
+    assert_eq!(quote.operator_breaches, 0);
+    assert_eq!(quote.premium_bps, 150);
+    assert_eq!(quote.annual_premium, 150);
+}
+
+/// Rewrite an asset as layout 2 stored it: a field map with an investor list, before share tokens
+fn downgrade_asset(env: &Env, contract_id: &Address, asset_id: &Symbol, investors: Vec<Address>) {
+    env.as_contract(contract_id, || {
+        let asset: MobilityAsset = env.storage().persistent().get(&StorageKey::Asset(asset_id.clone())).unwrap();
+        let mut legacy: Map<Symbol, Val> = Map::new(env);
+        legacy.set(Symbol::new(env, "metadata"), asset.metadata.into_val(env));
+        legacy.set(Symbol::new(env, "asset_type"), asset.asset_type.into_val(env));
+        legacy.set(Symbol::new(env, "target_amount"), asset.target_amount.into_val(env));
+        legacy.set(Symbol::new(env, "funded_amount"), asset.funded_amount.into_val(env));
+        legacy.set(Symbol::new(env, "location"), asset.location.into_val(env));
+        legacy.set(Symbol::new(env, "equity_score"), asset.equity_score.into_val(env));
+        legacy.set(Symbol::new(env, "status"), asset.status.into_val(env));
+        legacy.set(Symbol::new(env, "created_at"), asset.created_at.into_val(env));
+        legacy.set(Symbol::new(env, "program"), asset.program.into_val(env));
+        legacy.set(Symbol::new(env, "poll_results"), asset.poll_results.into_val(env));
+        legacy.set(Symbol::new(env, "funding_deadline"), asset.funding_deadline.into_val(env));
+        legacy.set(Symbol::new(env, "risk_disclosure"), Option::<RiskData>::None.into_val(env));
+        legacy.set(Symbol::new(env, "accessibility"), Option::<Symbol>::None.into_val(env));
+        legacy.set(Symbol::new(env, "investors"), investors.into_val(env));
+        env.storage().persistent().set(&StorageKey::Asset(asset_id.clone()), &legacy);
+
+        env.storage().persistent().remove(&StorageKey::ShareHolders(asset_id.clone()));
+        env.storage().persistent().remove(&StorageKey::ShareSupply(asset_id.clone()));
+        for investor in investors.iter() {
+            env.storage().persistent().remove(&StorageKey::ShareBalance(asset_id.clone(), investor));
+        }
+        env.storage().instance().set(&INIT_KEY, &2u32);
+    });
+}
+
+#[test]
+fn test_share_migration_resumes_from_its_saved_cursor() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, LoanPool);
+    let client = LoanPoolClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    client.initialize(&admin, &oracle);
+    settle_in_token(&env, &client, &admin, &[&alice, &bob], 10_000);
+
+    let assets = [symbol_short!("bike_1"), symbol_short!("bike_2"), symbol_short!("bike_3")];
+    for asset_id in assets.iter() {
+        client.create_asset(&admin, asset_id, &metadata(&env, "E-Bikes"), &symbol_short!("ebike"), &10_000, &symbol_short!("downtown"), &symbol_short!("default"));
+    }
+    client.invest(&alice, &assets[0], &1_000);
+    client.invest(&bob, &assets[0], &500);
+    client.invest(&alice, &assets[2], &700);
+
+    downgrade_asset(&env, &contract_id, &assets[0], vec![&env, alice.clone(), bob.clone(), alice.clone()]);
+    downgrade_asset(&env, &contract_id, &assets[1], vec![&env]);
+    downgrade_asset(&env, &contract_id, &assets[2], vec![&env, alice.clone()]);
+
+    // The first page moves two assets and saves where it stopped
+    assert_eq!(client.migrate_asset_shares(&admin, &PageRequest::first(2)), Some(2));
+    assert_eq!(client.share_balance(&assets[0], &alice), 1_000);
+    assert_eq!(client.share_balance(&assets[0], &bob), 500);
+    assert_eq!(client.share_balance(&assets[2], &alice), 0);
+
+    // The layout can't be bumped, nor a page skipped or repeated, until the rest is moved
+    assert_eq!(client.try_migrate(&admin), Err(Ok(ContractError::Pending)));
+    assert_eq!(client.try_migrate_asset_shares(&admin, &PageRequest::first(2)), Err(Ok(ContractError::InvalidInput)));
+
+    assert_eq!(client.migrate_asset_shares(&admin, &PageRequest { cursor: 2, limit: 2 }), None);
+    assert_eq!(client.share_balance(&assets[2], &alice), 700);
+    assert_eq!(client.share_holders(&assets[0]), vec![&env, alice.clone(), bob.clone()]);
+
+    assert_eq!(client.migrate(&admin), 3);
+    assert_eq!(client.get_version(), 3);
+    assert_eq!(client.get_asset(&assets[2]).funded_amount, 700);
+    assert_eq!(client.try_migrate_asset_shares(&admin, &PageRequest::first(2)), Err(Ok(ContractError::InvalidStatus)));
+}
+
+#[test]
+fn test_exit_queue_pays_redemptions_at_nav_in_request_order() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, LoanPool);
+    let client = LoanPoolClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let early = Address::generate(&env);
+    let late = Address::generate(&env);
+    let borrower = Address::generate(&env);
+
+    client.initialize(&admin, &Address::generate(&env));
+    let token = TokenClient::new(&env, &settle_in_token(&env, &client, &admin, &[&early, &late, &borrower], 3_000));
+    let (_, adjuster) = link_rate_adjuster(&env, &client, &admin);
+
+    let asset_id = symbol_short!("exit_bus");
+    client.create_asset(&admin, &asset_id, &metadata(&env, "Bus"), &symbol_short!("shuttle"), &4_000, &symbol_short!("downtown"), &symbol_short!("default"));
+    client.invest(&early, &asset_id, &3_000);
+    client.invest(&late, &asset_id, &1_000);
+    adjuster.approve(&symbol_short!("app_bus"), &borrower, &asset_id, &4_000);
+    client.release_loan_capital(&symbol_short!("app_bus"));
+    client.create_repayment_schedule(&admin, &asset_id, &symbol_short!("app_bus"), &4, &(30 * 86_400));
+
+    // Nothing has been repaid, so both requests wait in line at a NAV of one per unit
+    let first = client.request_redemption(&early, &asset_id, &1_500);
+    let second = client.request_redemption(&late, &asset_id, &1_000);
+    assert_eq!(client.get_asset_nav(&asset_id), 4_000);
+    assert_eq!(client.get_queue_position(&first), (0, 0));
+    assert_eq!(client.get_queue_position(&second), (1, 1_500));
+    assert_eq!(client.try_request_redemption(&late, &asset_id, &1), Err(Ok(ContractError::NoFunds)));
+
+    // A repayment fills the oldest request first and leaves the rest queued
+    let paid = client.record_repayment(&borrower, &asset_id, &2_000);
+    let filled = client.get_exit_request(&first);
+    assert_eq!(filled.status, symbol_short!("filled"));
+    let partial = client.get_exit_request(&second);
+    assert_eq!(partial.status, symbol_short!("queued"));
+    assert!(partial.filled_units > 0 && partial.filled_units < 1_000);
+    assert_eq!(paid, filled.paid_amount + partial.paid_amount);
+    assert_eq!(client.get_queue_position(&second), (0, 0));
+    assert_eq!(client.share_balance(&asset_id, &early), 1_500);
+
+    // Redemption proceeds are paid out in the settlement asset
+    assert_eq!(client.withdraw_payout(&early), filled.paid_amount);
+    assert_eq!(token.balance(&early), filled.paid_amount);
+    assert_eq!(client.withdraw_payout(&late), partial.paid_amount);
+    assert_eq!(token.balance(&late), 2_000 + partial.paid_amount);
+    assert_eq!(token.balance(&contract_id), client.get_pool_balance());
+
+    // Filled requests are journaled as principal returns for the investors' tax exports
+    let now = env.ledger().timestamp();
+    assert_eq!(
+        client.get_principal_events(&early, &now, &(now + 1)),
+        vec![&env, (symbol_short!("redeemed"), asset_id.clone(), filled.paid_amount, now)]
+    );
+    assert_eq!(client.get_principal_events(&early, &(now + 1), &u64::MAX), vec![&env]);
+}
+
+#[test]
+fn test_overdue_assets_escalate_stage_by_stage_and_alert_governance() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, LoanPool);
+    let client = LoanPoolClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let investor = Address::generate(&env);
+
+    client.initialize(&admin, &Address::generate(&env));
+    settle_in_token(&env, &client, &admin, &[&investor], 4_000);
+    let governance = StubGovernanceClient::new(&env, &link_governance(&env, &client, &admin));
+
+    let late = symbol_short!("late_bus");
+    let paying = symbol_short!("good_bus");
+    for (asset_id, target) in [(&late, 1_000), (&paying, 3_000)] {
+        client.create_asset(&admin, asset_id, &metadata(&env, "Bus"), &symbol_short!("shuttle"), &target, &symbol_short!("downtown"), &symbol_short!("default"));
+        client.invest(&investor, asset_id, &target);
+        client.deploy_asset(&admin, asset_id);
+        client.set_payment_terms(&admin, asset_id, &(30 * 86_400), &100);
+    }
+    let due = client.get_loan_servicing(&late).next_due_at;
+
+    // Not yet due: nothing happens
+    assert_eq!(client.advance_delinquency(&late), symbol_short!("current"));
+
+    let day = 86_400;
+    for (overdue, stage) in [
+        (1, "grace"),
+        (2 * day, "grace"),
+        (8 * day, "reminder"),
+        (31 * day, "restruct"),
+        (61 * day, "delinq"),
+    ] {
+        env.ledger().with_mut(|ledger| ledger.timestamp = due + overdue);
+        assert_eq!(client.advance_delinquency(&late), Symbol::new(&env, stage));
+    }
+    assert_eq!(client.get_loan_servicing(&late).stage, symbol_short!("delinq"));
+
+    // A quarter of outstanding principal is delinquent, past the 5% alert threshold
+    assert_eq!(client.get_portfolio_delinquency(), 2_500);
+    assert_eq!(governance.last_alert(), Some((2_500, true)));
+
+    env.ledger().with_mut(|ledger| ledger.timestamp = due + 91 * day);
+    assert_eq!(client.advance_delinquency(&late), symbol_short!("default"));
+    assert_eq!(client.advance_delinquency(&late), symbol_short!("default"));
+    assert_eq!(client.get_portfolio_delinquency(), 2_500);
+}
+
+#[test]
+fn test_program_equity_floor_blocks_listings_below_it() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, LoanPool);
+    let client = LoanPoolClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+
+    client.initialize(&admin, &Address::generate(&env));
+    link_governance(&env, &client, &admin);
+
+    let location = symbol_short!("downtown");
+    let program = symbol_short!("equity");
+    client.create_asset(&admin, &symbol_short!("probe"), &metadata(&env, "Bus"), &symbol_short!("shuttle"), &1_000, &location, &symbol_short!("default"));
+    let score = client.get_asset(&symbol_short!("probe")).equity_score;
+
+    // The floor only binds once its timelock has run
+    let change_id = client.set_program_equity_floor(&program, &(score + 1));
+    assert_eq!(client.get_program_equity_floor(&program), 0);
+    env.ledger().with_mut(|ledger| ledger.timestamp += timelock::MIN_DELAY);
+    client.execute_admin_change(&admin, &change_id);
+    assert_eq!(client.get_program_equity_floor(&program), score + 1);
+
+    assert_eq!(
+        client.try_create_asset(&admin, &symbol_short!("bus_1"), &metadata(&env, "Bus"), &symbol_short!("shuttle"), &1_000, &location, &program),
+        Err(Ok(ContractError::LowEquityScore))
+    );
+    // Other programs are unaffected, and a location at the floor lists
+    client.create_asset(&admin, &symbol_short!("bus_2"), &metadata(&env, "Bus"), &symbol_short!("shuttle"), &1_000, &location, &symbol_short!("default"));
+
+    let change_id = client.set_program_equity_floor(&program, &score);
+    env.ledger().with_mut(|ledger| ledger.timestamp += timelock::MIN_DELAY);
+    client.execute_admin_change(&admin, &change_id);
+    client.create_asset(&admin, &symbol_short!("bus_3"), &metadata(&env, "Bus"), &symbol_short!("shuttle"), &1_000, &location, &program);
+    assert_eq!(client.get_asset(&symbol_short!("bus_3")).program, program);
+}
+
+#[test]
+fn test_pledges_open_funding_with_a_pledger_priority_window() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, LoanPool);
+    let client = LoanPoolClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+
+    client.initialize(&admin, &Address::generate(&env));
+    let token = TokenClient::new(&env, &settle_in_token(&env, &client, &admin, &[&alice, &bob, &carol], 1_000));
+
+    let asset_id = symbol_short!("plg_bus");
+    client.propose_asset(&admin, &asset_id, &metadata(&env, "Bus"), &symbol_short!("shuttle"), &1_000, &symbol_short!("downtown"), &symbol_short!("default"));
+    assert_eq!(client.try_invest(&alice, &asset_id, &100), Err(Ok(ContractError::InvalidStatus)));
+
+    // Pledges are signed by the pledger and move no tokens
+    assert_eq!(client.pledge(&alice, &asset_id, &600), 600);
+    assert_eq!(env.auths().first().unwrap().0, alice);
+    assert_eq!(client.pledge(&bob, &asset_id, &300), 900);
+    assert_eq!(client.withdraw_pledge(&bob, &asset_id), 300);
+    assert_eq!(client.get_total_pledged(&asset_id), 600);
+    assert_eq!(token.balance(&contract_id), 0);
+
+    // Reaching the target opens funding, with pledgers first in line
+    assert_eq!(client.pledge(&bob, &asset_id, &400), 1_000);
+    assert_eq!(client.get_asset(&asset_id).status, symbol_short!("funding"));
+    let window_end = client.get_priority_window_end(&asset_id);
+    assert_eq!(window_end, env.ledger().timestamp() + 2 * 86_400);
+    assert_eq!(client.try_pledge(&carol, &asset_id, &100), Err(Ok(ContractError::InvalidStatus)));
+    assert_eq!(client.try_invest(&carol, &asset_id, &400), Err(Ok(ContractError::Priority)));
+
+    client.invest(&alice, &asset_id, &600);
+    env.ledger().with_mut(|ledger| ledger.timestamp = window_end);
+    client.invest(&carol, &asset_id, &400);
+    assert_eq!(client.get_asset(&asset_id).funded_amount, 1_000);
+    assert_eq!(token.balance(&contract_id), 1_000);
+}
+
+#[test]
+fn test_zone_allocation_target_flags_quarters_that_fall_behind() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, LoanPool);
+    let client = LoanPoolClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let investor = Address::generate(&env);
+
+    client.initialize(&admin, &Address::generate(&env));
+    settle_in_token(&env, &client, &admin, &[&investor], 4_000);
+    link_governance(&env, &client, &admin);
+
+    let zone = symbol_short!("eastside");
+    assert_eq!(client.try_set_allocation_target(&zone, &0), Err(Ok(ContractError::InvalidAmount)));
+    client.set_allocation_target(&zone, &4_000);
+    client.create_asset(&admin, &symbol_short!("east_bus"), &metadata(&env, "Bus"), &symbol_short!("shuttle"), &4_000, &zone, &symbol_short!("default"));
+    client.create_asset(&admin, &symbol_short!("west_bus"), &metadata(&env, "Bus"), &symbol_short!("shuttle"), &4_000, &symbol_short!("westside"), &symbol_short!("default"));
+
+    // Only capital placed in the zone counts
+    client.invest(&investor, &symbol_short!("east_bus"), &1_500);
+    client.invest(&investor, &symbol_short!("west_bus"), &500);
+    assert_eq!(client.get_allocation_progress().get(0).unwrap().funded_amount, 1_500);
+
+    // Ahead of the first quarter's 1,000, behind the second quarter's 2,000
+    let low_alerts = |env: &Env| {
+        env.events().all().iter().filter(|(_, topics, _)| {
+            let topic: Symbol = topics.get(0).unwrap().into_val(env);
+            topic == symbol_short!("alloc_low")
+        }).count()
+    };
+    env.ledger().with_mut(|ledger| ledger.timestamp += SECONDS_PER_QUARTER);
+    assert_eq!(client.check_allocation_target(&zone).quarters_checked, 1);
+    assert_eq!(low_alerts(&env), 0);
+
+    env.ledger().with_mut(|ledger| ledger.timestamp += SECONDS_PER_QUARTER);
+    let target = client.check_allocation_target(&zone);
+    assert_eq!(target.quarters_checked, 2);
+    assert_eq!(low_alerts(&env), 1);
+
+    // A quarter is only judged once
+    assert_eq!(client.check_allocation_target(&zone), target);
+    assert_eq!(low_alerts(&env), 1);
+    assert_eq!(client.try_check_allocation_target(&symbol_short!("westside")), Err(Ok(ContractError::NotFound)));
+}
+
+#[test]
+fn test_asset_polls_count_one_vote_per_investor_and_record_the_winner() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, LoanPool);
+    let client = LoanPoolClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let (alice, bob, carol) = (Address::generate(&env), Address::generate(&env), Address::generate(&env));
+
+    client.initialize(&admin, &Address::generate(&env));
+    settle_in_token(&env, &client, &admin, &[&alice, &bob, &carol], 1_000);
+    let asset_id = symbol_short!("poll_bus");
+    client.create_asset(&admin, &asset_id, &metadata(&env, "Bus"), &symbol_short!("shuttle"), &3_000, &symbol_short!("downtown"), &symbol_short!("default"));
+    for investor in [&alice, &bob] {
+        client.invest(investor, &asset_id, &1_000);
+    }
+
+    let (keep, extend) = (symbol_short!("keep"), symbol_short!("extend"));
+    assert_eq!(
+        client.try_create_asset_poll(&admin, &asset_id, &symbol_short!("hours"), &vec![&env, keep.clone()], &86_400),
+        Err(Ok(ContractError::InvalidInput))
+    );
+    let poll_id = client.create_asset_poll(&admin, &asset_id, &symbol_short!("hours"), &vec![&env, keep.clone(), extend.clone()], &86_400);
+
+    client.vote_asset_poll(&alice, &poll_id, &extend);
+    client.vote_asset_poll(&bob, &poll_id, &keep);
+    assert_eq!(client.try_vote_asset_poll(&alice, &poll_id, &keep), Err(Ok(ContractError::AlreadyVoted)));
+    assert_eq!(client.try_vote_asset_poll(&carol, &poll_id, &keep), Err(Ok(ContractError::NotEligible)));
+    assert_eq!(client.try_vote_asset_poll(&bob, &poll_id, &symbol_short!("close")), Err(Ok(ContractError::AlreadyVoted)));
+
+    // Carol invests and breaks the tie
+    client.invest(&carol, &asset_id, &1_000);
+    assert_eq!(client.try_vote_asset_poll(&carol, &poll_id, &symbol_short!("close")), Err(Ok(ContractError::InvalidInput)));
+    client.vote_asset_poll(&carol, &poll_id, &extend);
+    assert_eq!(client.try_close_asset_poll(&poll_id), Err(Ok(ContractError::TooEarly)));
+
+    env.ledger().with_mut(|ledger| ledger.timestamp += 86_401);
+    assert_eq!(client.try_vote_asset_poll(&alice, &poll_id, &keep), Err(Ok(ContractError::Expired)));
+    assert_eq!(client.close_asset_poll(&poll_id), Some(extend.clone()));
+    assert_eq!(client.get_asset_poll(&poll_id).tallies.get(extend.clone()), Some(2));
+    assert_eq!(client.get_asset(&asset_id).poll_results.get(poll_id), Some(extend));
+    assert_eq!(client.try_close_asset_poll(&poll_id), Err(Ok(ContractError::Expired)));
+}
+
+#[test]
+fn test_invariant_check_flags_drift_and_can_pause() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, LoanPool);
+    let client = LoanPoolClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let investor = Address::generate(&env);
+
+    client.initialize(&admin, &Address::generate(&env));
+    settle_in_token(&env, &client, &admin, &[&investor], 1_000);
+    let asset_id = symbol_short!("inv_bus");
+    client.create_asset(&admin, &asset_id, &metadata(&env, "Bus"), &symbol_short!("shuttle"), &1_000, &symbol_short!("downtown"), &symbol_short!("default"));
+    client.invest(&investor, &asset_id, &1_000);
+    assert_eq!(client.verify_invariants(), vec![&env]);
+
+    // Share balances that no longer add up to the supply are reported, without pausing by default
+    env.as_contract(&contract_id, || {
+        env.storage().persistent().set(&StorageKey::ShareBalance(asset_id.clone(), investor.clone()), &1_200i128);
+    });
+    assert_eq!(client.verify_invariants(), vec![&env, symbol_short!("supply")]);
+    assert!(!client.is_paused());
+
+    client.set_auto_pause(&admin, &true);
+    assert_eq!(client.verify_invariants(), vec![&env, symbol_short!("supply")]);
+    assert!(client.is_paused());
+    assert_eq!(client.try_withdraw_payout(&investor), Err(Ok(ContractError::Paused)));
+}
+
+#[test]
+fn test_operator_bulletin_is_published_in_posting_order() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, LoanPool);
+    let client = LoanPoolClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let operator = Address::generate(&env);
+    client.initialize(&admin, &Address::generate(&env));
+
+    let asset_id = symbol_short!("blt_bike");
+    let notice = metadata(&env, "Depot closed for maintenance");
+    let maint = symbol_short!("maint");
+    assert_eq!(
+        client.try_post_bulletin(&operator, &asset_id, &notice, &maint),
+        Err(Ok(ContractError::AssetNotFound))
+    );
+    client.create_asset(&admin, &asset_id, &metadata(&env, "Bikes"), &symbol_short!("ebike"), &10_000, &symbol_short!("downtown"), &symbol_short!("default"));
+    assert_eq!(client.try_post_bulletin(&operator, &asset_id, &notice, &maint), Err(Ok(ContractError::NotFound)));
+    client.set_payout_split(&admin, &asset_id, &vec![&env, PayoutSplit { recipient: operator.clone(), share_bps: 10_000 }]);
+
+    // Only the asset's operator posts, under a known category
+    assert_eq!(
+        client.try_post_bulletin(&Address::generate(&env), &asset_id, &notice, &maint),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_post_bulletin(&operator, &asset_id, &notice, &symbol_short!("gossip")),
+        Err(Ok(ContractError::InvalidInput))
+    );
+    assert_eq!(client.post_bulletin(&operator, &asset_id, &notice, &maint), 0);
+    let (_, topics, payload) = env.events().all().last().unwrap();
+    assert_eq!(topics, (symbol_short!("bulletin"), asset_id.clone()).into_val(&env));
+    let payload: (u32, Symbol, Symbol, BytesN<32>) = payload.into_val(&env);
+    assert_eq!(payload, (0, maint.clone(), notice.label.clone(), notice.content_hash.clone()));
+
+    env.ledger().with_mut(|ledger| ledger.timestamp += 3_600);
+    let reroute = metadata(&env, "Route 4 detour");
+    assert_eq!(client.post_bulletin(&operator, &asset_id, &reroute, &symbol_short!("route")), 1);
+
+    let first = client.get_bulletin(&asset_id, &PageRequest::first(1));
+    assert_eq!(first.items.len(), 1);
+    let entry = first.items.get(0).unwrap();
+    assert_eq!((entry.id, entry.metadata, entry.category, entry.posted_by), (0, notice, maint, operator));
+    let rest = client.get_bulletin(&asset_id, &PageRequest { cursor: first.next_cursor.unwrap(), limit: 10 });
+    assert_eq!(rest.items.get(0).unwrap().metadata, reroute);
+    assert_eq!(rest.items.get(0).unwrap().timestamp, env.ledger().timestamp());
+    assert_eq!(rest.next_cursor, None);
+}
+
+#[test]
+fn test_large_withdrawals_wait_for_enough_approvals() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, LoanPool);
+    let client = LoanPoolClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let investor = Address::generate(&env);
+    let (operator, maintenance) = (Address::generate(&env), Address::generate(&env));
+    let (first, second) = (Address::generate(&env), Address::generate(&env));
+
+    client.initialize(&admin, &Address::generate(&env));
+    let token = TokenClient::new(&env, &settle_in_token(&env, &client, &admin, &[&investor], 1_000));
+    let asset_id = symbol_short!("apr_bus");
+    client.create_asset(&admin, &asset_id, &metadata(&env, "Bus"), &symbol_short!("shuttle"), &1_000, &symbol_short!("downtown"), &symbol_short!("default"));
+    client.set_payout_split(&admin, &asset_id, &vec![
+        &env,
+        PayoutSplit { recipient: operator.clone(), share_bps: 7_000 },
+        PayoutSplit { recipient: maintenance.clone(), share_bps: 3_000 },
+    ]);
+    client.invest(&investor, &asset_id, &1_000);
+    client.deploy_asset(&admin, &asset_id);
+    client.release_to_operator(&admin, &asset_id, &1_000);
+
+    // Withdrawals above 500 need two of the two approvers
+    let rule = ApprovalRule { threshold: 500, required: 2, approvers: vec![&env, first.clone(), second.clone()] };
+    let change_id = client.set_approval_rule(&admin, &rule);
+    env.ledger().with_mut(|ledger| ledger.timestamp += timelock::MIN_DELAY);
+    client.execute_admin_change(&admin, &change_id);
+    assert_eq!(client.get_approval_rule(), rule);
+
+    assert_eq!(client.withdraw_payout(&maintenance), 300);
+    assert_eq!(client.try_withdraw_payout(&operator), Err(Ok(ContractError::NeedsApproval)));
+
+    let withdraw = symbol_short!("withdraw");
+    let payout = symbol_short!("payout");
+    assert_eq!(
+        client.try_propose_action(&operator, &withdraw, &payout, &Some(operator.clone()), &700),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    let other = client.propose_action(&first, &withdraw, &payout, &Some(maintenance.clone()), &700);
+    client.approve_action(&second, &other);
+    let action_id = client.propose_action(&first, &withdraw, &payout, &Some(operator.clone()), &700);
+    assert_eq!(client.try_withdraw_payout(&operator), Err(Ok(ContractError::NeedsApproval)));
+    assert_eq!(client.try_approve_action(&first, &action_id), Err(Ok(ContractError::DuplicateApproval)));
+    assert_eq!(client.approve_action(&second, &action_id), 2);
+
+    // The approved action is spent by the withdrawal it covers
+    assert_eq!(client.withdraw_payout(&operator), 700);
+    assert_eq!(token.balance(&operator), 700);
+    assert_eq!(token.balance(&maintenance), 300);
+    assert_eq!(client.try_get_pending_action(&action_id), Err(Ok(ContractError::NotFound)));
+    assert_eq!(client.get_pending_action(&other).approvals.len(), 2);
+}
+
+#[test]
+fn test_risk_disclosure_follows_the_oracles_location_risk() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, LoanPool);
+    let client = LoanPoolClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &Address::generate(&env));
+
+    let asset_id = symbol_short!("risk_bike");
+    let zone = symbol_short!("downtown");
+    assert_eq!(client.try_refresh_risk_disclosure(&asset_id), Err(Ok(ContractError::AssetNotFound)));
+    client.create_asset(&admin, &asset_id, &metadata(&env, "Bikes"), &symbol_short!("ebike"), &10_000, &zone, &symbol_short!("default"));
+
+    // Without an equity source there is nothing to disclose
+    assert_eq!(client.refresh_risk_disclosure(&asset_id), None);
+    let (_, adjuster) = link_rate_adjuster(&env, &client, &admin);
+    let risk = RiskData { location: zone.clone(), theft_rate: 45, vandalism_incidents: 10, weather_downtime: 0, timestamp: env.ledger().timestamp() };
+    adjuster.report_risk(&risk);
+    assert_eq!(client.get_risk_disclosure(&asset_id), None);
+
+    assert_eq!(client.refresh_risk_disclosure(&asset_id), Some(risk.clone()));
+    assert_eq!(client.get_risk_disclosure(&asset_id), Some(risk.clone()));
+    assert_eq!(client.get_risk_disclosure(&asset_id).unwrap().premium(), 2);
+
+    // A newer report only shows up once the disclosure is refreshed
+    let calmer = RiskData { theft_rate: 5, timestamp: risk.timestamp + 3_600, ..risk.clone() };
+    adjuster.report_risk(&calmer);
+    assert_eq!(client.get_risk_disclosure(&asset_id), Some(risk));
+    client.refresh_risk_disclosure(&asset_id);
+    assert_eq!(client.get_risk_disclosure(&asset_id).unwrap().premium(), 0);
+}
+
+#[test]
+fn test_self_set_exposure_limits_block_concentrated_investments() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, LoanPool);
+    let client = LoanPoolClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let investor = Address::generate(&env);
+    client.initialize(&admin, &Address::generate(&env));
+    let token = TokenClient::new(&env, &settle_in_token(&env, &client, &admin, &[&investor], 5_000));
+
+    let (downtown, eastside) = (symbol_short!("downtown"), symbol_short!("eastside"));
+    let (bikes, bus) = (symbol_short!("exp_bike"), symbol_short!("exp_bus"));
+    let program = symbol_short!("default");
+    client.create_asset(&admin, &bikes, &metadata(&env, "Bikes"), &symbol_short!("ebike"), &5_000, &downtown, &program);
+    client.create_asset(&admin, &bus, &metadata(&env, "Bus"), &symbol_short!("shuttle"), &5_000, &eastside, &program);
+
+    let limit = ExposureLimit { max_total: 3_000, max_per_asset: 0, max_per_zone: 2_000, max_per_program: 0 };
+    assert_eq!(
+        client.try_set_exposure_limit(&investor, &ExposureLimit { max_total: -1, ..limit.clone() }),
+        Err(Ok(ContractError::InvalidAmount))
+    );
+    client.set_exposure_limit(&investor, &limit);
+    assert_eq!(client.get_exposure_limit(&investor), Some(limit));
+
+    client.invest(&investor, &bikes, &1_500);
+    client.invest(&investor, &bus, &1_000);
+    let exposure = client.get_exposure(&investor);
+    assert_eq!(exposure.total, 2_500);
+    assert_eq!(exposure.by_zone.get(downtown.clone()), Some(1_500));
+    assert_eq!(exposure.by_program.get(program.clone()), Some(2_500));
+
+    // A zone cap is enforced on its own, and a blocked investment moves no tokens
+    assert_eq!(client.try_invest(&investor, &bikes, &600), Err(Ok(ContractError::OverLimit)));
+    assert_eq!(token.balance(&investor), 2_500);
+    client.invest(&investor, &bikes, &500);
+    let (_, topics, _) = env.events().all().iter().find(|(_, topics, _)| {
+        topics.get(0).map(|topic| Symbol::try_from_val(&env, &topic) == Ok(symbol_short!("exp_alert"))) == Some(true)
+    }).unwrap();
+    assert_eq!(topics.get(1).map(|topic| Address::try_from_val(&env, &topic).unwrap()), Some(investor.clone()));
+    assert_eq!(client.try_invest(&investor, &bus, &1), Err(Ok(ContractError::OverLimit)));
+    assert_eq!(client.get_exposure(&investor).total, 3_000);
+
+    // Clearing every cap lifts the limit
+    client.set_exposure_limit(&investor, &ExposureLimit { max_total: 0, max_per_asset: 0, max_per_zone: 0, max_per_program: 0 });
+    assert_eq!(client.get_exposure_limit(&investor), None);
+    client.invest(&investor, &bus, &1_000);
+    assert_eq!(token.balance(&investor), 1_000);
+    assert_eq!(client.get_exposure(&investor).by_zone.get(eastside), Some(2_000));
+}
+
+/// First topic of every event the contract published so far, in order
+fn event_names(env: &Env, contract_id: &Address) -> Vec<Symbol> {
+    let mut names = vec![env];
+    for (contract, topics, _) in env.events().all().iter() {
+        if contract == *contract_id {
+            names.push_back(Symbol::try_from_val(env, &topics.get(0).unwrap()).unwrap());
+        }
+    }
+    names
+}
+
+#[test]
+fn test_asset_lifecycle_is_observable_from_events() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, LoanPool);
+    let client = LoanPoolClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let investor = Address::generate(&env);
+    client.initialize(&admin, &Address::generate(&env));
+    settle_in_token(&env, &client, &admin, &[&investor], 1_000);
+    let before = event_names(&env, &contract_id).len();
+
+    let asset_id = symbol_short!("evt_bike");
+    client.create_asset(&admin, &asset_id, &metadata(&env, "Bikes"), &symbol_short!("ebike"), &1_000, &symbol_short!("downtown"), &symbol_short!("default"));
+    client.invest(&investor, &asset_id, &1_000);
+    client.deploy_asset(&admin, &asset_id);
+    client.complete_asset(&admin, &asset_id);
+
+    let names = event_names(&env, &contract_id).slice(before..);
+    let status = symbol_short!("status");
+    let lifecycle = vec![&env, symbol_short!("listed"), symbol_short!("invested"), status.clone()];
+    assert_eq!(
+        names,
+        vec![&env, symbol_short!("listed"), symbol_short!("mint"), symbol_short!("invested"), status.clone(), status.clone(), status]
+    );
+
+    // Lifecycle events name the asset, and status events carry the new status
+    let mut statuses = vec![&env];
+    for (contract, topics, payload) in env.events().all().iter() {
+        if contract != contract_id || topics.len() < 2 {
+            continue;
+        }
+        let topic = Symbol::try_from_val(&env, &topics.get(0).unwrap()).unwrap();
+        if lifecycle.contains(&topic) {
+            assert_eq!(Symbol::try_from_val(&env, &topics.get(1).unwrap()).unwrap(), asset_id);
+        }
+        if topic == symbol_short!("status") {
+            statuses.push_back(Symbol::try_from_val(&env, &payload).unwrap());
+        }
+    }
+    assert_eq!(statuses, vec![&env, symbol_short!("funded"), symbol_short!("deployed"), symbol_short!("completed")]);
+}
+
+/// Equity bonus published with the most recent investment
+fn last_equity_bonus(env: &Env, contract_id: &Address) -> i32 {
+    let (_, _, payload) = env
+        .events()
+        .all()
+        .iter()
+        .filter(|(contract, topics, _)| {
+            contract == contract_id && Symbol::try_from_val(env, &topics.get(0).unwrap()) == Ok(symbol_short!("invested"))
+        })
+        .last()
+        .unwrap();
+    let (_, _, equity_bonus): (Address, i128, i32) = payload.into_val(env);
+    equity_bonus
+}
+
+#[test]
+fn test_first_time_and_underserved_investors_earn_equity_bonuses() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, LoanPool);
+    let client = LoanPoolClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let (newcomer, local) = (Address::generate(&env), Address::generate(&env));
+    client.initialize(&admin, &Address::generate(&env));
+    settle_in_token(&env, &client, &admin, &[&newcomer, &local], 1_000);
+
+    let (bikes, shuttle) = (symbol_short!("bns_bike"), symbol_short!("bns_bus"));
+    let program = symbol_short!("default");
+    client.create_asset(&admin, &bikes, &metadata(&env, "Bikes"), &symbol_short!("ebike"), &5_000, &symbol_short!("downtown"), &program);
+    client.create_asset(&admin, &shuttle, &metadata(&env, "Bus"), &symbol_short!("shuttle"), &5_000, &Symbol::new(&env, "underserved_n"), &program);
+
+    client.invest(&newcomer, &bikes, &100);
+    assert_eq!(last_equity_bonus(&env, &contract_id), 10);
+    client.invest(&newcomer, &bikes, &100);
+    assert_eq!(last_equity_bonus(&env, &contract_id), 0);
+    client.invest(&newcomer, &shuttle, &100);
+    assert_eq!(last_equity_bonus(&env, &contract_id), 15);
+
+    // Both bonuses together are capped at 25
+    client.invest(&local, &shuttle, &100);
+    assert_eq!(last_equity_bonus(&env, &contract_id), 25);
+}
+
+#[test]
+fn test_attested_accessible_assets_list_first_and_boost_bonuses() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, LoanPool);
+    let client = LoanPoolClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let investors = [Address::generate(&env), Address::generate(&env), Address::generate(&env)];
+    client.initialize(&admin, &Address::generate(&env));
+    settle_in_token(&env, &client, &admin, &[&investors[0], &investors[1], &investors[2]], 1_000);
+    link_governance(&env, &client, &admin);
+
+    let (bikes, shuttle) = (symbol_short!("acc_bike"), symbol_short!("acc_bus"));
+    let program = symbol_short!("default");
+    client.create_asset(&admin, &bikes, &metadata(&env, "Bikes"), &symbol_short!("ebike"), &5_000, &symbol_short!("downtown"), &program);
+    client.create_asset(&admin, &shuttle, &metadata(&env, "Bus"), &symbol_short!("shuttle"), &5_000, &symbol_short!("downtown"), &program);
+
+    assert_eq!(
+        client.try_attest_accessibility(&shuttle, &Some(symbol_short!("ramp"))),
+        Err(Ok(ContractError::InvalidInput))
+    );
+    client.attest_accessibility(&shuttle, &Some(symbol_short!("wheelchr")));
+    assert_eq!(client.get_all_assets().get(0).unwrap().id, shuttle);
+    assert_eq!(client.get_accessible_assets().len(), 1);
+    assert_eq!(client.get_asset_accessibility(&shuttle), Some(symbol_short!("wheelchr")));
+    assert_eq!(client.get_asset_accessibility(&bikes), None);
+
+    // A first-time investor's 10 point bonus is worth 15 on an accessible asset by default
+    client.invest(&investors[0], &shuttle, &100);
+    assert_eq!(last_equity_bonus(&env, &contract_id), 15);
+    assert_eq!(client.try_set_accessibility_multiplier(&20_001), Err(Ok(ContractError::InvalidInput)));
+    client.set_accessibility_multiplier(&20_000);
+    client.invest(&investors[1], &shuttle, &100);
+    assert_eq!(last_equity_bonus(&env, &contract_id), 20);
+
+    // Clearing the attestation drops the asset out of the lane
+    client.attest_accessibility(&shuttle, &None);
+    assert_eq!(client.get_accessible_assets().len(), 0);
+    assert_eq!(client.get_all_assets().get(0).unwrap().id, bikes);
+    client.invest(&investors[2], &shuttle, &100);
+    assert_eq!(last_equity_bonus(&env, &contract_id), 10);
+}
+
+#[test]
+fn test_repayments_pay_installments_oldest_first_in_tokens() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, LoanPool);
+    let client = LoanPoolClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let investor = Address::generate(&env);
+    let borrower = Address::generate(&env);
+    client.initialize(&admin, &Address::generate(&env));
+    let token = TokenClient::new(&env, &settle_in_token(&env, &client, &admin, &[&investor, &borrower], 5_000));
+    let (adjuster_id, adjuster) = link_rate_adjuster(&env, &client, &admin);
+
+    let asset_id = symbol_short!("rep_bus");
+    client.create_asset(&admin, &asset_id, &metadata(&env, "Bus"), &symbol_short!("shuttle"), &4_000, &symbol_short!("downtown"), &symbol_short!("default"));
+    client.invest(&investor, &asset_id, &4_000);
+    adjuster.approve(&symbol_short!("app_rep"), &borrower, &asset_id, &4_000);
+    let interval = 30 * 86_400;
+    assert_eq!(
+        client.try_create_repayment_schedule(&admin, &asset_id, &symbol_short!("app_rep"), &4, &interval),
+        Err(Ok(ContractError::InvalidStatus))
+    );
+    client.release_loan_capital(&symbol_short!("app_rep"));
+    assert_eq!(token.balance(&adjuster_id), 4_000);
+    assert_eq!(client.try_get_outstanding_balance(&asset_id), Err(Ok(ContractError::NotFound)));
+
+    // Four equal principal installments, each with interest at 8% on the principal still owed
+    let schedule = client.create_repayment_schedule(&admin, &asset_id, &symbol_short!("app_rep"), &4, &interval);
+    let interest_on = |remaining: i128| remaining * 8 * interval as i128 / (100 * SECONDS_PER_YEAR as i128);
+    let mut owed = 0;
+    for (i, installment) in schedule.installments.iter().enumerate() {
+        assert_eq!(installment.principal, 1_000);
+        assert_eq!(installment.interest, interest_on(4_000 - 1_000 * i as i128));
+        assert_eq!(installment.due_at, env.ledger().timestamp() + interval * (i as u64 + 1));
+        owed += installment.principal + installment.interest;
+    }
+    assert_eq!(client.get_outstanding_balance(&asset_id), owed);
+    assert_eq!(
+        client.try_create_repayment_schedule(&admin, &asset_id, &symbol_short!("app_rep"), &4, &interval),
+        Err(Ok(ContractError::InvalidStatus))
+    );
+
+    // Paying the first installment and some more leaves the second part-paid
+    let first = schedule.installments.get(0).unwrap();
+    let before = token.balance(&borrower);
+    client.record_repayment(&borrower, &asset_id, &(first.principal + first.interest + 100));
+    assert_eq!(token.balance(&borrower), before - first.principal - first.interest - 100);
+    let next = client.get_next_installment(&asset_id);
+    assert_eq!((next.number, next.paid), (2, 100));
+    assert_eq!(client.get_outstanding_balance(&asset_id), owed - first.principal - first.interest - 100);
+
+    assert_eq!(client.try_record_repayment(&borrower, &asset_id, &(owed + 1)), Err(Ok(ContractError::InvalidAmount)));
+    let rest = client.get_outstanding_balance(&asset_id);
+    client.record_repayment(&borrower, &asset_id, &rest);
+    assert_eq!(token.balance(&borrower), before - owed);
+    assert_eq!(client.get_outstanding_balance(&asset_id), 0);
+    assert_eq!(client.try_get_next_installment(&asset_id), Err(Ok(ContractError::Done)));
+    let schedule = client.get_repayment_schedule(&asset_id);
+    assert_eq!(schedule.total_paid, owed);
+    assert_eq!(schedule.completed_at, Some(env.ledger().timestamp()));
+}
+
+#[test]
+fn test_autopay_pulls_due_installments_and_falls_back_to_manual_payment() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, LoanPool);
+    let client = LoanPoolClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let investor = Address::generate(&env);
+    let borrower = Address::generate(&env);
+    client.initialize(&admin, &Address::generate(&env));
+    let token = TokenClient::new(&env, &settle_in_token(&env, &client, &admin, &[&investor, &borrower], 5_000));
+    let (_, adjuster) = link_rate_adjuster(&env, &client, &admin);
+
+    let asset_id = symbol_short!("auto_bus");
+    client.create_asset(&admin, &asset_id, &metadata(&env, "Bus"), &symbol_short!("shuttle"), &4_000, &symbol_short!("downtown"), &symbol_short!("default"));
+    client.invest(&investor, &asset_id, &4_000);
+    adjuster.approve(&symbol_short!("app_auto"), &borrower, &asset_id, &4_000);
+    client.release_loan_capital(&symbol_short!("app_auto"));
+    let schedule = client.create_repayment_schedule(&admin, &asset_id, &symbol_short!("app_auto"), &4, &(30 * 86_400));
+
+    // Only the schedule's borrower can enroll
+    assert_eq!(
+        client.try_enroll_autopay(&investor, &asset_id, &10_000),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    client.enroll_autopay(&borrower, &asset_id, &10_000);
+
+    // The pool may pull exactly the first installment
+    let first = schedule.installments.get(0).unwrap();
+    let first_due = first.principal + first.interest;
+    token.approve(&borrower, &contract_id, &first_due, &(env.ledger().sequence() + 100));
+    assert_eq!(client.try_collect_autopay(&asset_id), Err(Ok(ContractError::TooEarly)));
+
+    env.ledger().with_mut(|ledger| ledger.timestamp = first.due_at);
+    assert_eq!(client.collect_autopay(&asset_id), first_due);
+    assert_eq!(token.balance(&borrower), 5_000 - first_due);
+    assert_eq!(token.allowance(&borrower, &contract_id), 0);
+    assert_eq!(client.get_next_installment(&asset_id).number, 2);
+    assert_eq!(client.get_autopay(&asset_id).last_installment, 1);
+    assert_eq!(client.try_collect_autopay(&asset_id), Err(Ok(ContractError::TooEarly)));
+
+    // With the allowance spent the next pull fails, suspending autopay and starting the grace period
+    let second = schedule.installments.get(1).unwrap();
+    env.ledger().with_mut(|ledger| ledger.timestamp = second.due_at);
+    assert_eq!(client.collect_autopay(&asset_id), 0);
+    assert_eq!(token.balance(&borrower), 5_000 - first_due);
+    assert!(!client.get_autopay(&asset_id).active);
+    assert_eq!(client.get_loan_servicing(&asset_id).stage, symbol_short!("grace"));
+    assert_eq!(client.try_collect_autopay(&asset_id), Err(Ok(ContractError::InvalidStatus)));
+
+    // Paying by hand brings the loan current again
+    client.record_repayment(&borrower, &asset_id, &(second.principal + second.interest));
+    assert_eq!(token.balance(&borrower), 5_000 - first_due - second.principal - second.interest);
+    assert_eq!(client.get_loan_servicing(&asset_id).stage, symbol_short!("current"));
+}
+
+#[test]
+fn test_stalled_listings_are_cancelled_and_refunded_in_tokens() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, LoanPool);
+    let client = LoanPoolClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let (investor1, investor2) = (Address::generate(&env), Address::generate(&env));
+    client.initialize(&admin, &Address::generate(&env));
+    let token = TokenClient::new(&env, &settle_in_token(&env, &client, &admin, &[&investor1, &investor2], 5_000));
+
+    let (slow, steady) = (symbol_short!("slow_bus"), symbol_short!("steady"));
+    for asset_id in [&slow, &steady] {
+        client.create_asset(&admin, asset_id, &metadata(&env, "Bus"), &symbol_short!("shuttle"), &10_000, &symbol_short!("downtown"), &symbol_short!("default"));
+    }
+    client.invest(&investor1, &slow, &2_000);
+    client.invest(&investor2, &slow, &1_000);
+    client.invest(&investor1, &steady, &3_000);
+    client.invest(&investor2, &steady, &2_000);
+    assert_eq!(client.try_cancel_stalled_asset(&slow), Err(Ok(ContractError::NotFound)));
+
+    // Listings must reach half their target within a week
+    let week = 7 * 86_400;
+    assert_eq!(
+        client.try_set_funding_velocity(&admin, &Some(FundingVelocity { min_funded_bps: 10_001, window: week })),
+        Err(Ok(ContractError::InvalidInput))
+    );
+    client.set_funding_velocity(&admin, &Some(FundingVelocity { min_funded_bps: 5_000, window: week }));
+    assert_eq!(client.try_cancel_stalled_asset(&slow), Err(Ok(ContractError::TooEarly)));
+
+    env.ledger().with_mut(|ledger| ledger.timestamp += week);
+    assert_eq!(client.try_cancel_stalled_asset(&steady), Err(Ok(ContractError::NotEligible)));
+
+    // Anyone can cancel the listing that fell behind, returning each investor's capital
+    assert_eq!(client.cancel_stalled_asset(&slow), 3_000);
+    assert_eq!(client.get_asset(&slow).status, symbol_short!("cancelled"));
+    assert_eq!(token.balance(&investor1), 5_000 - 3_000);
+    assert_eq!(token.balance(&investor2), 5_000 - 2_000);
+    assert_eq!(token.balance(&contract_id), 5_000);
+    assert_eq!(client.get_pool_balance(), 5_000);
+    assert_eq!(client.try_cancel_stalled_asset(&slow), Err(Ok(ContractError::InvalidStatus)));
+}
+
+#[test]
+fn test_salvage_settles_the_loan_then_pays_investors_by_share() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, LoanPool);
+    let client = LoanPoolClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let (investor1, investor2, borrower) = (Address::generate(&env), Address::generate(&env), Address::generate(&env));
+    client.initialize(&admin, &Address::generate(&env));
+    let token_id = settle_in_token(&env, &client, &admin, &[&investor1, &investor2], 3_000);
+    let token = TokenClient::new(&env, &token_id);
+    let (_, adjuster) = link_rate_adjuster(&env, &client, &admin);
+
+    let asset_id = symbol_short!("old_bus");
+    client.create_asset(&admin, &asset_id, &metadata(&env, "Bus"), &symbol_short!("shuttle"), &4_000, &symbol_short!("downtown"), &symbol_short!("default"));
+    client.invest(&investor1, &asset_id, &3_000);
+    client.invest(&investor2, &asset_id, &1_000);
+    assert_eq!(client.try_retire_asset(&admin, &asset_id), Err(Ok(ContractError::InvalidStatus)));
+    adjuster.approve(&symbol_short!("app_old"), &borrower, &asset_id, &4_000);
+    client.release_loan_capital(&symbol_short!("app_old"));
+    client.create_repayment_schedule(&admin, &asset_id, &symbol_short!("app_old"), &4, &(30 * 86_400));
+    let outstanding = client.get_outstanding_balance(&asset_id);
+
+    assert_eq!(client.try_record_salvage(&asset_id, &1_000), Err(Ok(ContractError::InvalidStatus)));
+    client.retire_asset(&admin, &asset_id);
+    assert_eq!(client.get_asset(&asset_id).status, symbol_short!("retired"));
+
+    // The operator remits the sale proceeds to the pool; the loan is settled before investors see any of it
+    let proceeds = outstanding + 2_000;
+    StellarAssetClient::new(&env, &token_id).mint(&contract_id, &proceeds);
+    let record = client.record_salvage(&asset_id, &proceeds);
+    assert_eq!((record.proceeds, record.loan_settled, record.distributed), (proceeds, outstanding, 2_000));
+    assert_eq!(client.get_salvage(&asset_id), record);
+    assert_eq!(client.get_asset(&asset_id).status, symbol_short!("closed"));
+    assert_eq!(client.get_outstanding_balance(&asset_id), 0);
+    assert_eq!(client.try_record_salvage(&asset_id, &proceeds), Err(Ok(ContractError::InvalidStatus)));
+
+    // The remainder splits 3:1 like the investments
+    assert_eq!(client.get_payout_balance(&investor1), 1_500);
+    assert_eq!(client.get_payout_balance(&investor2), 500);
+    client.withdraw_payout(&investor1);
+    client.withdraw_payout(&investor2);
+    assert_eq!(token.balance(&investor1), 1_500);
+    assert_eq!(token.balance(&investor2), 2_000 + 500);
+}
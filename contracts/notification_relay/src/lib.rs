@@ -0,0 +1,395 @@
+#![no_std]
+//! Notification relay for off-chain services.
+//!
+//! Platform contracts configured with this relay push one compact record per
+//! change-journal entry: who published it, the record kind and the record id.
+//! Services register a subscription with topic and publisher filters and the
+//! hash of their callback identifier, then poll the shared journal from their
+//! own cursor. A relay operator delivers callbacks off-chain, so consumers
+//! follow platform activity without running a full indexer.
+use soroban_sdk::{
+    contract, contractimpl, contracttype, symbol_short, vec, xdr::ToXdr, Address, Bytes, BytesN, Env, Error, Symbol, Val, Vec,
+};
+
+use mobility_types::{access, page_result, ttl, upgrade, Gate, PageRequest, RelayInterface, Role, MAX_PAGE_SIZE, TtlConfig};
+
+mobility_types::contract_errors! {}
+
+/// One change pushed by a platform contract
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Notification {
+    pub seq: u32, // Position in the relay journal
+    pub publisher: Address,
+    pub topic: Symbol, // Record kind, e.g. "asset", "proposal", "dist"
+    pub subject: Bytes, // XDR of the record id
+    pub ledger: u32,
+    pub timestamp: u64,
+}
+
+/// An off-chain service's interest in platform activity
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Subscription {
+    pub id: u32,
+    pub owner: Address,
+    pub topics: Vec<Symbol>, // Empty matches every topic
+    pub publishers: Vec<Address>, // Empty matches every publisher
+    pub callback_hash: BytesN<32>, // sha256 of the callback identifier held off-chain
+    pub created_at: u64,
+    pub active: bool,
+}
+
+impl Subscription {
+    /// Whether a notification passes the subscription's filters
+    pub fn matches(&self, notification: &Notification) -> bool {
+        (self.topics.is_empty() || self.topics.contains(&notification.topic))
+            && (self.publishers.is_empty() || self.publishers.contains(&notification.publisher))
+    }
+}
+
+page_result!(NotificationPage, Notification);
+
+/// Contract data structure
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DataKey {
+    pub admin: Address,
+    pub publishers: Vec<Address>, // Platform contracts allowed to push notifications
+    pub notification_count: u32,
+    pub next_subscription_id: u32,
+    pub paused: bool,
+    pub disabled_features: Vec<Symbol>, // Entrypoint features switched off by admin
+}
+
+/// Persistent entries for per-record state
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum StorageKey {
+    Notification(u32),
+    Subscription(u32),
+}
+
+const DATA_KEY: Symbol = symbol_short!("DATA_KEY");
+
+// Set once by initialize to the storage layout version; its presence marks the contract initialized
+const INIT_KEY: Symbol = symbol_short!("INIT");
+const CONTRACT_VERSION: u32 = 1;
+
+// Filters are matched on every poll, so keep them short
+const MAX_FILTERS: u32 = 10;
+
+#[contract]
+pub struct NotificationRelay;
+
+#[contractimpl]
+impl NotificationRelay {
+    /// Initialize the contract. Can only be called once.
+    pub fn initialize(env: &Env, admin: Address) -> Result<(), ContractError> {
+        admin.require_auth();
+
+        if env.storage().instance().has(&INIT_KEY) || env.storage().instance().has(&DATA_KEY) {
+            return Err(ContractError::AlreadyInitialized);
+        }
+
+        let data = DataKey {
+            admin,
+            publishers: vec![env],
+            notification_count: 0,
+            next_subscription_id: 1,
+            paused: false,
+            disabled_features: Vec::new(env),
+        };
+        ttl::set_instance(env, &DATA_KEY, &data);
+        ttl::set_instance(env, &INIT_KEY, &CONTRACT_VERSION);
+
+        Ok(())
+    }
+
+    /// Get the storage layout version recorded at initialization, or 0 if uninitialized
+    pub fn get_version(env: &Env) -> u32 {
+        env.storage().instance().get(&INIT_KEY).unwrap_or(0)
+    }
+
+    /// Version of the running code; `get_version` lags behind it until `migrate` runs
+    pub fn version(_env: &Env) -> u32 {
+        CONTRACT_VERSION
+    }
+
+    /// Replace the contract's code with an uploaded wasm, keeping its storage (admin only).
+    /// The new code's `migrate` must run before storage written by the old layout is used
+    pub fn upgrade(env: &Env, caller: Address, new_wasm_hash: BytesN<32>) -> Result<(), ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+        upgrade::upgrade(env, new_wasm_hash);
+
+        Ok(())
+    }
+
+    /// Bring storage up to this code's layout version after an upgrade (admin only)
+    pub fn migrate(env: &Env, caller: Address) -> Result<u32, ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+
+        let stored: u32 = env.storage().instance().get(&INIT_KEY).unwrap_or(0);
+        let version = upgrade::migrate(env, stored, CONTRACT_VERSION, Self::migrate_step)?;
+        ttl::set_instance(env, &INIT_KEY, &version);
+
+        Ok(version)
+    }
+
+    /// Rewrite storage from layout version `from` to `from + 1`. Each release that changes
+    /// the layout adds its step here
+    fn migrate_step(_env: &Env, _from: u32) -> Result<(), ContractError> {
+        Ok(())
+    }
+
+    /// Allow or stop a platform contract pushing notifications (admin only)
+    pub fn set_publisher(env: &Env, caller: Address, publisher: Address, allowed: bool) -> Result<(), ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+
+        if let Some(index) = data.publishers.first_index_of(&publisher) {
+            data.publishers.remove(index);
+        }
+        if allowed {
+            data.publishers.push_back(publisher.clone());
+        }
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        env.events().publish((symbol_short!("publisher"), publisher), allowed);
+
+        Ok(())
+    }
+
+    /// Register interest in platform activity. Returns the subscription id
+    pub fn subscribe(
+        env: &Env,
+        owner: Address,
+        topics: Vec<Symbol>,
+        publishers: Vec<Address>,
+        callback_hash: BytesN<32>,
+    ) -> Result<u32, ContractError> {
+        owner.require_auth();
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        Gate::new(data.paused, &data.disabled_features, &symbol_short!("subscribe")).check()?;
+
+        if topics.len() > MAX_FILTERS || publishers.len() > MAX_FILTERS {
+            return Err(ContractError::OverLimit);
+        }
+
+        let subscription_id = data.next_subscription_id;
+        data.next_subscription_id += 1;
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        let subscription = Subscription {
+            id: subscription_id,
+            owner: owner.clone(),
+            topics,
+            publishers,
+            callback_hash,
+            created_at: env.ledger().timestamp(),
+            active: true,
+        };
+        ttl::set_persistent(env, &StorageKey::Subscription(subscription_id), &subscription);
+
+        env.events().publish((symbol_short!("subscribe"), owner), subscription_id);
+
+        Ok(subscription_id)
+    }
+
+    /// Deactivate a subscription (owner only)
+    pub fn unsubscribe(env: &Env, owner: Address, subscription_id: u32) -> Result<(), ContractError> {
+        owner.require_auth();
+
+        let mut subscription = Self::load_subscription(env, subscription_id)?;
+        if subscription.owner != owner {
+            return Err(ContractError::Unauthorized);
+        }
+
+        subscription.active = false;
+        ttl::set_persistent(env, &StorageKey::Subscription(subscription_id), &subscription);
+
+        env.events().publish((symbol_short!("unsub"), owner), subscription_id);
+
+        Ok(())
+    }
+
+    /// Scan up to `page.limit` journal entries from `page.cursor` and return those matching a
+    /// subscription's filters. `next_cursor` is where the scan stopped, so a page can come
+    /// back empty while later entries remain
+    pub fn poll(env: &Env, subscription_id: u32, page: PageRequest) -> Result<NotificationPage, ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        let subscription = Self::load_subscription(env, subscription_id)?;
+        if !subscription.active {
+            return Err(ContractError::InvalidStatus);
+        }
+
+        let end = page.cursor.saturating_add(page.limit.min(MAX_PAGE_SIZE)).min(data.notification_count);
+        let mut items = vec![env];
+        for seq in page.cursor..end {
+            let notification: Notification = env.storage().persistent().get(&StorageKey::Notification(seq)).unwrap();
+            if subscription.matches(&notification) {
+                items.push_back(notification);
+            }
+        }
+
+        Ok(NotificationPage {
+            items,
+            next_cursor: (end < data.notification_count).then_some(end),
+        })
+    }
+
+    /// Get a subscription
+    pub fn get_subscription(env: &Env, subscription_id: u32) -> Result<Subscription, ContractError> {
+        Self::load_subscription(env, subscription_id)
+    }
+
+    /// Number of notifications recorded; the cursor a new subscriber starts from to skip history
+    pub fn get_notification_count(env: &Env) -> u32 {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        data.notification_count
+    }
+
+    /// Whether a platform contract may push notifications
+    pub fn is_publisher(env: &Env, publisher: Address) -> bool {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        data.publishers.contains(&publisher)
+    }
+
+    /// Halt state-changing entrypoints, e.g. while an oracle is compromised; views keep working (pauser only)
+    pub fn pause(env: &Env, caller: Address) -> Result<(), ContractError> {
+        Self::set_paused(env, caller, true)
+    }
+
+    /// Resume state-changing entrypoints after a pause (pauser only)
+    pub fn unpause(env: &Env, caller: Address) -> Result<(), ContractError> {
+        Self::set_paused(env, caller, false)
+    }
+
+    /// Whether state-changing entrypoints are halted
+    pub fn is_paused(env: &Env) -> bool {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        data.paused
+    }
+
+    fn set_paused(env: &Env, caller: Address, paused: bool) -> Result<(), ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Pauser, &caller)?;
+
+        data.paused = paused;
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        let topic = if paused { symbol_short!("paused") } else { symbol_short!("unpaused") };
+        env.events().publish((topic,), env.ledger().timestamp());
+
+        Ok(())
+    }
+
+    /// Switch one entrypoint feature on or off without pausing the whole contract (pauser only)
+    pub fn set_feature(env: &Env, caller: Address, feature: Symbol, enabled: bool) -> Result<(), ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Pauser, &caller)?;
+
+        if let Some(index) = data.disabled_features.first_index_of(&feature) {
+            data.disabled_features.remove(index);
+        }
+        if !enabled {
+            data.disabled_features.push_back(feature.clone());
+        }
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        env.events().publish((symbol_short!("feature"), feature), enabled);
+
+        Ok(())
+    }
+
+    /// Grant a role to an account (admin only)
+    pub fn grant_role(env: &Env, caller: Address, role: Role, account: Address) -> Result<(), ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+        access::grant(env, role, &account);
+
+        Ok(())
+    }
+
+    /// Revoke a role previously granted to an account (admin only)
+    pub fn revoke_role(env: &Env, caller: Address, role: Role, account: Address) -> Result<(), ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+        access::revoke(env, role, &account).map_err(ContractError::from)
+    }
+
+    /// Whether an account holds a role; the admin holds every role
+    pub fn has_role(env: &Env, role: Role, account: Address) -> bool {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        access::holds(env, &data.admin, role, &account)
+    }
+
+    /// Extend the TTL of the contract instance and its code. Open to anyone, so a keeper can
+    /// stop a quiet contract from being archived between writes
+    pub fn bump_storage(env: &Env) {
+        ttl::extend_instance(env);
+    }
+
+    /// Set the remaining TTL below which writes extend an entry, and the TTL they extend it to (admin only)
+    pub fn set_ttl_config(env: &Env, caller: Address, threshold: u32, extend_to: u32) -> Result<(), ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+        ttl::set_config(env, threshold, extend_to).map_err(ContractError::from)
+    }
+
+    /// Get the storage TTL configuration
+    pub fn get_ttl_config(env: &Env) -> TtlConfig {
+        ttl::config(env)
+    }
+
+    fn load_subscription(env: &Env, subscription_id: u32) -> Result<Subscription, ContractError> {
+        env.storage()
+            .persistent()
+            .get(&StorageKey::Subscription(subscription_id))
+            .ok_or(ContractError::NotFound)
+    }
+}
+
+#[contractimpl]
+impl RelayInterface for NotificationRelay {
+    /// Append a notification from an allowed publisher to the journal
+    fn notify(env: &Env, publisher: Address, topic: Symbol, subject: Val) -> Result<u32, Error> {
+        publisher.require_auth();
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        Gate::new(data.paused, &data.disabled_features, &symbol_short!("notify"))
+            .role(data.publishers.contains(&publisher))
+            .check()?;
+
+        let seq = data.notification_count;
+        data.notification_count += 1;
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        let notification = Notification {
+            seq,
+            publisher,
+            topic,
+            subject: subject.to_xdr(env),
+            ledger: env.ledger().sequence(),
+            timestamp: env.ledger().timestamp(),
+        };
+        ttl::set_persistent(env, &StorageKey::Notification(seq), &notification);
+
+        Ok(seq)
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,70 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    Address, BytesN, Env, IntoVal,
+    testutils::Address as _,
+};
+
+#[test]
+fn test_poll_filters_journal_by_topic_and_publisher() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, NotificationRelay);
+    let client = NotificationRelayClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    // Publishers are registered by an account granted the admin role
+    let operator = Address::generate(&env);
+    client.grant_role(&admin, &Role::Admin, &operator);
+
+    let loan_pool = Address::generate(&env);
+    let governance = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    client.set_publisher(&operator, &loan_pool, &true);
+    client.set_publisher(&operator, &governance, &true);
+
+    // Unregistered contracts can't push notifications
+    assert_eq!(
+        client.try_notify(&stranger, &symbol_short!("asset"), &1u64.into_val(&env)),
+        Err(Ok(ContractError::Unauthorized.into()))
+    );
+
+    client.notify(&loan_pool, &symbol_short!("asset"), &1u64.into_val(&env));
+    client.notify(&governance, &symbol_short!("proposal"), &7u64.into_val(&env));
+    client.notify(&loan_pool, &symbol_short!("asset"), &2u64.into_val(&env));
+    client.notify(&governance, &symbol_short!("asset"), &3u64.into_val(&env));
+    assert_eq!(client.get_notification_count(), 4);
+
+    let service = Address::generate(&env);
+    let callback_hash = BytesN::from_array(&env, &[7; 32]);
+    let subscription_id = client.subscribe(
+        &service,
+        &vec![&env, symbol_short!("asset")],
+        &vec![&env, loan_pool.clone()],
+        &callback_hash,
+    );
+
+    // The first page scans two entries and matches one; the cursor resumes the scan
+    let first = client.poll(&subscription_id, &PageRequest::first(2));
+    assert_eq!(first.items.len(), 1);
+    assert_eq!(first.next_cursor, Some(2));
+
+    let second = client.poll(&subscription_id, &PageRequest { cursor: 2, limit: 2 });
+    assert_eq!(second.items.len(), 1);
+    assert_eq!(second.items.get(0).unwrap().seq, 2);
+    assert_eq!(second.next_cursor, None);
+
+    // Only the owner unsubscribes, and inactive subscriptions can't poll
+    assert_eq!(
+        client.try_unsubscribe(&stranger, &subscription_id),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    client.unsubscribe(&service, &subscription_id);
+    assert_eq!(
+        client.try_poll(&subscription_id, &PageRequest::first(10)),
+        Err(Ok(ContractError::InvalidStatus))
+    );
+}
@@ -0,0 +1,505 @@
+#![no_std]
+//! Secondary market for investment positions.
+//!
+//! An investor who wants out before an asset completes asks the loan pool to
+//! lock part of their principal and mint it here as a transferable position.
+//! The holder can list it at an asking price or accept an escrowed bid. At
+//! settlement the market pays the seller in the loan pool's settlement asset
+//! and has the loan pool move the locked principal to the buyer, so future
+//! redemptions and revenue distributions follow the position.
+use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, vec, Address, BytesN, Env, Error, Symbol, Vec};
+
+use mobility_types::{
+    access, page_result, ttl, upgrade, Gate, LoanPoolClient, PageRequest, PositionMarketInterface, Role, TokenClient, TtlConfig,
+};
+
+mobility_types::contract_errors! {}
+
+/// Principal in one asset, locked at the loan pool and owned by its holder
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Position {
+    pub id: u32,
+    pub holder: Address,
+    pub asset_id: Symbol,
+    pub amount: i128, // Principal the position stands for
+    pub equity_bonus: i32, // Principal-weighted equity bonus of the investments behind it
+    pub minted_at: u64,
+}
+
+/// A position offered at a fixed asking price
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Listing {
+    pub position_id: u32,
+    pub seller: Address,
+    pub price: i128, // In the loan pool's settlement asset
+    pub listed_at: u64,
+}
+
+/// An offer on a position, with the price held in escrow until accepted or withdrawn
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Bid {
+    pub id: u32,
+    pub position_id: u32,
+    pub bidder: Address,
+    pub price: i128,
+    pub placed_at: u64,
+    pub active: bool,
+}
+
+page_result!(BidPage, Bid);
+
+/// Contract data structure
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DataKey {
+    pub admin: Address,
+    pub loan_pool: Address, // Mints positions and holds the principal behind them
+    pub next_position_id: u32,
+    pub next_bid_id: u32,
+    pub paused: bool,
+    pub disabled_features: Vec<Symbol>, // Entrypoint features switched off by admin
+}
+
+/// Persistent entries for per-record state
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum StorageKey {
+    Position(u32),
+    Listing(u32), // position_id -> open listing
+    Bid(u32),
+    PositionBids(u32), // position_id -> bid ids, in placement order
+}
+
+const DATA_KEY: Symbol = symbol_short!("DATA_KEY");
+
+// Set once by initialize to the storage layout version; its presence marks the contract initialized
+const INIT_KEY: Symbol = symbol_short!("INIT");
+const CONTRACT_VERSION: u32 = 1;
+
+#[contract]
+pub struct PositionMarket;
+
+#[contractimpl]
+impl PositionMarket {
+    /// Initialize the contract. Can only be called once.
+    pub fn initialize(env: &Env, admin: Address, loan_pool: Address) -> Result<(), ContractError> {
+        admin.require_auth();
+
+        if env.storage().instance().has(&INIT_KEY) || env.storage().instance().has(&DATA_KEY) {
+            return Err(ContractError::AlreadyInitialized);
+        }
+
+        let data = DataKey {
+            admin,
+            loan_pool,
+            next_position_id: 1,
+            next_bid_id: 1,
+            paused: false,
+            disabled_features: Vec::new(env),
+        };
+        ttl::set_instance(env, &DATA_KEY, &data);
+        ttl::set_instance(env, &INIT_KEY, &CONTRACT_VERSION);
+
+        Ok(())
+    }
+
+    /// Get the storage layout version recorded at initialization, or 0 if uninitialized
+    pub fn get_version(env: &Env) -> u32 {
+        env.storage().instance().get(&INIT_KEY).unwrap_or(0)
+    }
+
+    /// Version of the running code; `get_version` lags behind it until `migrate` runs
+    pub fn version(_env: &Env) -> u32 {
+        CONTRACT_VERSION
+    }
+
+    /// Replace the contract's code with an uploaded wasm, keeping its storage (admin only).
+    /// The new code's `migrate` must run before storage written by the old layout is used
+    pub fn upgrade(env: &Env, caller: Address, new_wasm_hash: BytesN<32>) -> Result<(), ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+        upgrade::upgrade(env, new_wasm_hash);
+
+        Ok(())
+    }
+
+    /// Bring storage up to this code's layout version after an upgrade (admin only)
+    pub fn migrate(env: &Env, caller: Address) -> Result<u32, ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+
+        let stored: u32 = env.storage().instance().get(&INIT_KEY).unwrap_or(0);
+        let version = upgrade::migrate(env, stored, CONTRACT_VERSION, Self::migrate_step)?;
+        ttl::set_instance(env, &INIT_KEY, &version);
+
+        Ok(version)
+    }
+
+    /// Rewrite storage from layout version `from` to `from + 1`. Each release that changes
+    /// the layout adds its step here
+    fn migrate_step(_env: &Env, _from: u32) -> Result<(), ContractError> {
+        Ok(())
+    }
+
+    /// Offer a position at a fixed price, replacing any earlier listing (holder only)
+    pub fn list(env: &Env, seller: Address, position_id: u32, price: i128) -> Result<(), ContractError> {
+        seller.require_auth();
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        Gate::new(data.paused, &data.disabled_features, &symbol_short!("list")).check()?;
+
+        let position = Self::load_position(env, position_id)?;
+        if position.holder != seller {
+            return Err(ContractError::Unauthorized);
+        }
+        if price <= 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        let listing = Listing {
+            position_id,
+            seller: seller.clone(),
+            price,
+            listed_at: env.ledger().timestamp(),
+        };
+        ttl::set_persistent(env, &StorageKey::Listing(position_id), &listing);
+
+        env.events().publish((symbol_short!("listed"), position_id), (seller, price));
+
+        Ok(())
+    }
+
+    /// Withdraw a position's listing (seller only)
+    pub fn cancel_listing(env: &Env, seller: Address, position_id: u32) -> Result<(), ContractError> {
+        seller.require_auth();
+
+        let listing = Self::load_listing(env, position_id)?;
+        if listing.seller != seller {
+            return Err(ContractError::Unauthorized);
+        }
+        env.storage().persistent().remove(&StorageKey::Listing(position_id));
+
+        env.events().publish((symbol_short!("delisted"), position_id), seller);
+
+        Ok(())
+    }
+
+    /// Buy a listed position at its asking price
+    pub fn buy(env: &Env, buyer: Address, position_id: u32) -> Result<(), ContractError> {
+        buyer.require_auth();
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        Gate::new(data.paused, &data.disabled_features, &symbol_short!("trade")).check()?;
+
+        let listing = Self::load_listing(env, position_id)?;
+        let position = Self::load_position(env, position_id)?;
+        if buyer == listing.seller {
+            return Err(ContractError::SameParty);
+        }
+
+        Self::settlement_token(env, &data)?.transfer(&buyer, &listing.seller, &listing.price);
+        Self::settle(env, &data, position, buyer, listing.price);
+
+        Ok(())
+    }
+
+    /// Offer a price for a position, escrowing it until the bid is accepted or withdrawn.
+    /// Returns the bid id
+    pub fn bid(env: &Env, bidder: Address, position_id: u32, price: i128) -> Result<u32, ContractError> {
+        bidder.require_auth();
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        Gate::new(data.paused, &data.disabled_features, &symbol_short!("bid")).check()?;
+
+        let position = Self::load_position(env, position_id)?;
+        if bidder == position.holder {
+            return Err(ContractError::SameParty);
+        }
+        if price <= 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        Self::settlement_token(env, &data)?.transfer(&bidder, &env.current_contract_address(), &price);
+
+        let bid_id = data.next_bid_id;
+        data.next_bid_id += 1;
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        let bid = Bid {
+            id: bid_id,
+            position_id,
+            bidder: bidder.clone(),
+            price,
+            placed_at: env.ledger().timestamp(),
+            active: true,
+        };
+        ttl::set_persistent(env, &StorageKey::Bid(bid_id), &bid);
+
+        let mut bids = Self::bid_ids(env, position_id);
+        bids.push_back(bid_id);
+        ttl::set_persistent(env, &StorageKey::PositionBids(position_id), &bids);
+
+        env.events().publish((symbol_short!("bid"), position_id), (bidder, bid_id, price));
+
+        Ok(bid_id)
+    }
+
+    /// Withdraw an active bid and refund its escrow (bidder only)
+    pub fn withdraw_bid(env: &Env, bidder: Address, bid_id: u32) -> Result<i128, ContractError> {
+        bidder.require_auth();
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        let mut bid = Self::load_active_bid(env, bid_id)?;
+        if bid.bidder != bidder {
+            return Err(ContractError::Unauthorized);
+        }
+
+        bid.active = false;
+        ttl::set_persistent(env, &StorageKey::Bid(bid_id), &bid);
+        Self::settlement_token(env, &data)?.transfer(&env.current_contract_address(), &bidder, &bid.price);
+
+        env.events().publish((symbol_short!("bid_out"), bid.position_id), (bidder, bid_id));
+
+        Ok(bid.price)
+    }
+
+    /// Sell a position to an active bid, paying the seller from its escrow (holder only)
+    pub fn accept_bid(env: &Env, seller: Address, bid_id: u32) -> Result<(), ContractError> {
+        seller.require_auth();
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        Gate::new(data.paused, &data.disabled_features, &symbol_short!("trade")).check()?;
+
+        let mut bid = Self::load_active_bid(env, bid_id)?;
+        let position = Self::load_position(env, bid.position_id)?;
+        if position.holder != seller {
+            return Err(ContractError::Unauthorized);
+        }
+
+        bid.active = false;
+        ttl::set_persistent(env, &StorageKey::Bid(bid_id), &bid);
+        Self::settlement_token(env, &data)?.transfer(&env.current_contract_address(), &seller, &bid.price);
+        Self::settle(env, &data, position, bid.bidder, bid.price);
+
+        Ok(())
+    }
+
+    /// Retire a position and unlock its principal at the loan pool (holder only)
+    pub fn burn(env: &Env, holder: Address, position_id: u32) -> Result<i128, ContractError> {
+        holder.require_auth();
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        let position = Self::load_position(env, position_id)?;
+        if position.holder != holder {
+            return Err(ContractError::Unauthorized);
+        }
+
+        LoanPoolClient::new(env, &data.loan_pool).release_position(&holder, &position.asset_id, &position.amount);
+        env.storage().persistent().remove(&StorageKey::Listing(position_id));
+        env.storage().persistent().remove(&StorageKey::Position(position_id));
+
+        env.events().publish((symbol_short!("burned"), position_id), (holder, position.amount));
+
+        Ok(position.amount)
+    }
+
+    /// Get a position
+    pub fn get_position(env: &Env, position_id: u32) -> Result<Position, ContractError> {
+        Self::load_position(env, position_id)
+    }
+
+    /// Get a position's open listing, if any
+    pub fn get_listing(env: &Env, position_id: u32) -> Option<Listing> {
+        env.storage().persistent().get(&StorageKey::Listing(position_id))
+    }
+
+    /// Get a bid
+    pub fn get_bid(env: &Env, bid_id: u32) -> Result<Bid, ContractError> {
+        env.storage().persistent().get(&StorageKey::Bid(bid_id)).ok_or(ContractError::NotFound)
+    }
+
+    /// Get a page of the bids placed on a position, active or not, in placement order
+    pub fn get_position_bids(env: &Env, position_id: u32, page: PageRequest) -> BidPage {
+        let (items, next_cursor) = page.collect(env, &Self::bid_ids(env, position_id), |bid_id| {
+            env.storage().persistent().get(&StorageKey::Bid(bid_id)).unwrap()
+        });
+        BidPage { items, next_cursor }
+    }
+
+    /// Halt state-changing entrypoints, e.g. while an oracle is compromised; views keep working (pauser only)
+    pub fn pause(env: &Env, caller: Address) -> Result<(), ContractError> {
+        Self::set_paused(env, caller, true)
+    }
+
+    /// Resume state-changing entrypoints after a pause (pauser only)
+    pub fn unpause(env: &Env, caller: Address) -> Result<(), ContractError> {
+        Self::set_paused(env, caller, false)
+    }
+
+    /// Whether state-changing entrypoints are halted
+    pub fn is_paused(env: &Env) -> bool {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        data.paused
+    }
+
+    fn set_paused(env: &Env, caller: Address, paused: bool) -> Result<(), ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Pauser, &caller)?;
+
+        data.paused = paused;
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        let topic = if paused { symbol_short!("paused") } else { symbol_short!("unpaused") };
+        env.events().publish((topic,), env.ledger().timestamp());
+
+        Ok(())
+    }
+
+    /// Switch one entrypoint feature on or off without pausing the whole contract (pauser only)
+    pub fn set_feature(env: &Env, caller: Address, feature: Symbol, enabled: bool) -> Result<(), ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Pauser, &caller)?;
+
+        if let Some(index) = data.disabled_features.first_index_of(&feature) {
+            data.disabled_features.remove(index);
+        }
+        if !enabled {
+            data.disabled_features.push_back(feature.clone());
+        }
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        env.events().publish((symbol_short!("feature"), feature), enabled);
+
+        Ok(())
+    }
+
+    /// Check whether an entrypoint feature is enabled
+    pub fn is_feature_enabled(env: &Env, feature: Symbol) -> bool {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        !data.disabled_features.contains(&feature)
+    }
+
+    /// Grant a role to an account (admin only)
+    pub fn grant_role(env: &Env, caller: Address, role: Role, account: Address) -> Result<(), ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+        access::grant(env, role, &account);
+
+        Ok(())
+    }
+
+    /// Revoke a role previously granted to an account (admin only)
+    pub fn revoke_role(env: &Env, caller: Address, role: Role, account: Address) -> Result<(), ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+        access::revoke(env, role, &account).map_err(ContractError::from)
+    }
+
+    /// Whether an account holds a role; the admin holds every role
+    pub fn has_role(env: &Env, role: Role, account: Address) -> bool {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        access::holds(env, &data.admin, role, &account)
+    }
+
+    /// Extend the TTL of the contract instance and its code. Open to anyone, so a keeper can
+    /// stop a quiet contract from being archived between writes
+    pub fn bump_storage(env: &Env) {
+        ttl::extend_instance(env);
+    }
+
+    /// Set the remaining TTL below which writes extend an entry, and the TTL they extend it to (admin only)
+    pub fn set_ttl_config(env: &Env, caller: Address, threshold: u32, extend_to: u32) -> Result<(), ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+        ttl::set_config(env, threshold, extend_to).map_err(ContractError::from)
+    }
+
+    /// Get the storage TTL configuration
+    pub fn get_ttl_config(env: &Env) -> TtlConfig {
+        ttl::config(env)
+    }
+
+    /// Hand a paid-for position to its buyer and move the principal behind it at the loan pool
+    fn settle(env: &Env, data: &DataKey, mut position: Position, buyer: Address, price: i128) {
+        let seller = position.holder.clone();
+        LoanPoolClient::new(env, &data.loan_pool).transfer_position(&seller, &buyer, &position.asset_id, &position.amount);
+
+        position.holder = buyer.clone();
+        ttl::set_persistent(env, &StorageKey::Position(position.id), &position);
+        env.storage().persistent().remove(&StorageKey::Listing(position.id));
+
+        env.events().publish((symbol_short!("settled"), position.id), (seller, buyer, price));
+    }
+
+    fn settlement_token<'a>(env: &'a Env, data: &DataKey) -> Result<TokenClient<'a>, ContractError> {
+        let asset = LoanPoolClient::new(env, &data.loan_pool)
+            .get_settlement_asset()
+            .ok_or(ContractError::NoSettlementAsset)?;
+        Ok(TokenClient::new(env, &asset))
+    }
+
+    fn load_position(env: &Env, position_id: u32) -> Result<Position, ContractError> {
+        env.storage()
+            .persistent()
+            .get(&StorageKey::Position(position_id))
+            .ok_or(ContractError::NotFound)
+    }
+
+    fn load_listing(env: &Env, position_id: u32) -> Result<Listing, ContractError> {
+        env.storage()
+            .persistent()
+            .get(&StorageKey::Listing(position_id))
+            .ok_or(ContractError::NotFound)
+    }
+
+    fn load_active_bid(env: &Env, bid_id: u32) -> Result<Bid, ContractError> {
+        let bid = Self::get_bid(env, bid_id)?;
+        if !bid.active {
+            return Err(ContractError::InvalidStatus);
+        }
+        Ok(bid)
+    }
+
+    fn bid_ids(env: &Env, position_id: u32) -> Vec<u32> {
+        env.storage().persistent().get(&StorageKey::PositionBids(position_id)).unwrap_or(vec![env])
+    }
+}
+
+#[contractimpl]
+impl PositionMarketInterface for PositionMarket {
+    /// Record a position backed by principal the loan pool has locked (loan pool only)
+    fn mint_position(env: &Env, investor: Address, asset_id: Symbol, amount: i128, equity_bonus: i32) -> Result<u32, Error> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        data.loan_pool.require_auth();
+
+        let position_id = data.next_position_id;
+        data.next_position_id += 1;
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        let position = Position {
+            id: position_id,
+            holder: investor.clone(),
+            asset_id: asset_id.clone(),
+            amount,
+            equity_bonus,
+            minted_at: env.ledger().timestamp(),
+        };
+        ttl::set_persistent(env, &StorageKey::Position(position_id), &position);
+
+        env.events().publish((symbol_short!("minted"), position_id), (investor, asset_id, amount));
+
+        Ok(position_id)
+    }
+}
+
+mod test;
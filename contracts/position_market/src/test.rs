@@ -0,0 +1,219 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    contract, contractimpl, symbol_short, testutils::Address as _, token::StellarAssetClient, Address, Env, Symbol,
+};
+
+/// Stands in for the loan pool: settles in one token and tracks the principal locked behind
+/// each holder's positions
+#[contract]
+struct StubLoanPool;
+
+#[contractimpl]
+impl StubLoanPool {
+    pub fn set_settlement_asset(env: Env, asset: Address) {
+        env.storage().instance().set(&symbol_short!("settle"), &asset);
+    }
+
+    pub fn get_settlement_asset(env: Env) -> Option<Address> {
+        env.storage().instance().get(&symbol_short!("settle"))
+    }
+
+    pub fn lock(env: Env, holder: Address, asset_id: Symbol, amount: i128) {
+        let locked = Self::locked(env.clone(), holder.clone(), asset_id.clone());
+        env.storage().persistent().set(&(holder, asset_id), &(locked + amount));
+    }
+
+    pub fn locked(env: Env, holder: Address, asset_id: Symbol) -> i128 {
+        env.storage().persistent().get(&(holder, asset_id)).unwrap_or(0)
+    }
+
+    pub fn transfer_position(env: Env, from: Address, to: Address, asset_id: Symbol, amount: i128) {
+        Self::lock(env.clone(), from, asset_id.clone(), -amount);
+        Self::lock(env, to, asset_id, amount);
+    }
+
+    pub fn release_position(env: Env, holder: Address, asset_id: Symbol, amount: i128) {
+        Self::lock(env, holder, asset_id, -amount);
+    }
+}
+
+struct Setup<'a> {
+    client: PositionMarketClient<'a>,
+    pool: StubLoanPoolClient<'a>,
+    token: TokenClient<'a>,
+    admin: Address,
+}
+
+/// Register the market against a stub loan pool settling in a fresh token, and mint
+/// `principal` locked in ebike_001 to `holder` as position 1
+fn setup<'a>(env: &'a Env, holder: &Address, principal: i128) -> Setup<'a> {
+    let pool = StubLoanPoolClient::new(env, &env.register_contract(None, StubLoanPool));
+    let asset = env.register_stellar_asset_contract(Address::generate(env));
+    pool.set_settlement_asset(&asset);
+
+    let client = PositionMarketClient::new(env, &env.register_contract(None, PositionMarket));
+    let admin = Address::generate(env);
+    client.initialize(&admin, &pool.address);
+
+    pool.lock(holder, &symbol_short!("ebike_001"), &principal);
+    assert_eq!(client.mint_position(holder, &symbol_short!("ebike_001"), &principal, &12), 1);
+
+    Setup { client, pool, token: TokenClient::new(env, &asset), admin }
+}
+
+#[test]
+fn test_mint_records_position_for_holder() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let holder = Address::generate(&env);
+    let Setup { client, pool, .. } = setup(&env, &holder, 500);
+
+    let position = client.get_position(&1);
+    assert_eq!(position.holder, holder);
+    assert_eq!(position.asset_id, symbol_short!("ebike_001"));
+    assert_eq!((position.amount, position.equity_bonus), (500, 12));
+
+    // Ids keep counting up, and only the loan pool mints
+    assert_eq!(client.mint_position(&holder, &symbol_short!("ebike_001"), &200, &0), 2);
+    assert_eq!(env.auths()[0].0, pool.address);
+    assert_eq!(client.try_get_position(&3), Err(Ok(ContractError::NotFound)));
+}
+
+#[test]
+fn test_list_and_buy_moves_payment_and_principal() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let seller = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let Setup { client, pool, token, .. } = setup(&env, &seller, 500);
+    StellarAssetClient::new(&env, &token.address).mint(&buyer, &1_000);
+
+    assert_eq!(client.try_list(&seller, &1, &0), Err(Ok(ContractError::InvalidAmount)));
+    client.list(&seller, &1, &450);
+    assert_eq!(client.get_listing(&1).unwrap().price, 450);
+
+    // The seller can't buy their own listing
+    assert_eq!(client.try_buy(&seller, &1), Err(Ok(ContractError::SameParty)));
+    client.buy(&buyer, &1);
+
+    assert_eq!(token.balance(&buyer), 550);
+    assert_eq!(token.balance(&seller), 450);
+    assert_eq!(pool.locked(&seller, &symbol_short!("ebike_001")), 0);
+    assert_eq!(pool.locked(&buyer, &symbol_short!("ebike_001")), 500);
+    assert_eq!(client.get_position(&1).holder, buyer);
+    assert_eq!(client.get_listing(&1), None);
+
+    // The sold listing is gone, and the old holder can no longer relist
+    assert_eq!(client.try_buy(&buyer, &1), Err(Ok(ContractError::NotFound)));
+    assert_eq!(client.try_list(&seller, &1, &450), Err(Ok(ContractError::Unauthorized)));
+}
+
+#[test]
+fn test_non_holder_cannot_sell() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let holder = Address::generate(&env);
+    let impostor = Address::generate(&env);
+    let bidder = Address::generate(&env);
+    let Setup { client, pool, token, .. } = setup(&env, &holder, 500);
+    StellarAssetClient::new(&env, &token.address).mint(&bidder, &400);
+
+    // Neither listing nor accepting a bid works for someone who doesn't hold the position
+    assert_eq!(client.try_list(&impostor, &1, &100), Err(Ok(ContractError::Unauthorized)));
+    let bid_id = client.bid(&bidder, &1, &400);
+    assert_eq!(client.try_accept_bid(&impostor, &bid_id), Err(Ok(ContractError::Unauthorized)));
+    assert_eq!(client.try_burn(&impostor, &1), Err(Ok(ContractError::Unauthorized)));
+
+    assert_eq!(client.get_position(&1).holder, holder);
+    assert_eq!(pool.locked(&holder, &symbol_short!("ebike_001")), 500);
+    assert_eq!(token.balance(&impostor), 0);
+    assert_eq!(token.balance(&client.address), 400);
+}
+
+#[test]
+fn test_cancel_listing_is_seller_only() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let seller = Address::generate(&env);
+    let Setup { client, .. } = setup(&env, &seller, 500);
+
+    client.list(&seller, &1, &450);
+    assert_eq!(
+        client.try_cancel_listing(&Address::generate(&env), &1),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    client.cancel_listing(&seller, &1);
+    assert_eq!(client.get_listing(&1), None);
+    assert_eq!(client.try_cancel_listing(&seller, &1), Err(Ok(ContractError::NotFound)));
+    assert_eq!(client.try_buy(&Address::generate(&env), &1), Err(Ok(ContractError::NotFound)));
+}
+
+#[test]
+fn test_bids_escrow_until_accepted_or_withdrawn() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let seller = Address::generate(&env);
+    let (low, high) = (Address::generate(&env), Address::generate(&env));
+    let Setup { client, pool, token, .. } = setup(&env, &seller, 500);
+    let minter = StellarAssetClient::new(&env, &token.address);
+    minter.mint(&low, &300);
+    minter.mint(&high, &480);
+
+    let low_bid = client.bid(&low, &1, &300);
+    let high_bid = client.bid(&high, &1, &480);
+    assert_eq!(token.balance(&client.address), 780);
+    let first = client.get_position_bids(&1, &PageRequest::first(1));
+    assert_eq!(first.items.get(0).unwrap().id, low_bid);
+    let rest = client.get_position_bids(&1, &PageRequest { cursor: first.next_cursor.unwrap(), limit: 1 });
+    assert_eq!(rest.items.get(0).unwrap().id, high_bid);
+    assert_eq!(rest.next_cursor, None);
+
+    // Only the bidder withdraws, and only once
+    assert_eq!(client.try_withdraw_bid(&high, &low_bid), Err(Ok(ContractError::Unauthorized)));
+    assert_eq!(client.withdraw_bid(&low, &low_bid), 300);
+    assert_eq!(client.try_withdraw_bid(&low, &low_bid), Err(Ok(ContractError::InvalidStatus)));
+    assert_eq!(token.balance(&low), 300);
+
+    client.accept_bid(&seller, &high_bid);
+    assert_eq!(token.balance(&seller), 480);
+    assert_eq!(token.balance(&client.address), 0);
+    assert_eq!(pool.locked(&high, &symbol_short!("ebike_001")), 500);
+    assert_eq!(client.get_position(&1).holder, high);
+    assert!(!client.get_bid(&high_bid).active);
+}
+
+#[test]
+fn test_burn_releases_principal_and_drops_listing() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let holder = Address::generate(&env);
+    let Setup { client, pool, .. } = setup(&env, &holder, 500);
+
+    client.list(&holder, &1, &450);
+    assert_eq!(client.burn(&holder, &1), 500);
+
+    assert_eq!(pool.locked(&holder, &symbol_short!("ebike_001")), 0);
+    assert_eq!(client.try_get_position(&1), Err(Ok(ContractError::NotFound)));
+    assert_eq!(client.get_listing(&1), None);
+    assert_eq!(client.try_burn(&holder, &1), Err(Ok(ContractError::NotFound)));
+}
+
+#[test]
+fn test_trading_gate_blocks_sales() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let seller = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let Setup { client, token, admin, .. } = setup(&env, &seller, 500);
+    StellarAssetClient::new(&env, &token.address).mint(&buyer, &450);
+
+    client.list(&seller, &1, &450);
+    client.set_feature(&admin, &symbol_short!("trade"), &false);
+    assert_eq!(client.try_buy(&buyer, &1), Err(Ok(ContractError::FeatureDisabled)));
+
+    client.set_feature(&admin, &symbol_short!("trade"), &true);
+    client.buy(&buyer, &1);
+    assert_eq!(client.get_position(&1).holder, buyer);
+}
@@ -1,8 +1,23 @@
 #![no_std]
+// Entrypoints take their arguments flat, so signed oracle payloads and epoch distributions carry many
+#![allow(clippy::too_many_arguments)]
 use soroban_sdk::{
-    contract, contractimpl, contracttype, symbol_short, vec, Address, Env, Map, Symbol, Vec,
+    contract, contractimpl, contracttype, symbol_short, vec, xdr::ToXdr, Address, BytesN, Env,
+    Map, Symbol, Vec,
 };
 
+use mobility_types::{access, attestation, changes, ids, journal, page_result, timelock, trustline, ttl, upgrade, ApprovalRule, ChangeLogPage, ChangeValue, PageRequest, RecordChange, RecordChangePage, PendingChange, Payee, FeederKey, DistributionStats, DistributorInterface, Gate, EpochSchedule, GovernanceClient, LoanPoolClient, PendingAction, RideStats, TokenClient, Role, TtlConfig};
+
+mobility_types::contract_errors! {
+    AuditDue = 16,
+    Claimable = 43,
+    Disputed = 46,
+    Escalated = 51,
+    Frozen = 57,
+    OutOfBand = 109,
+    AdvanceOpen = 141,
+}
+
 /// Represents a revenue distribution event
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -14,6 +29,13 @@ pub struct RevenueDistribution {
     pub equity_bonus_pool: i128,
     pub timestamp: u64,
     pub distributions: Vec<InvestorDistribution>,
+    pub treasury_fee: i128, // Revenue retained by the treasury after investor payouts
+    pub platform_fee: i128, // Sliding-scale platform fee taken before investor payouts
+    pub emergency_topup: i128, // Share of net revenue moved into the program's emergency fund
+    pub fee_released: bool,
+    pub epoch: u32, // Per-asset distribution sequence number
+    pub claimed: Map<Address, bool>, // investor -> has claimed their line
+    pub advance_repayment: i128, // Operator advance recovered from this distribution before the emergency top-up
 }
 
 /// Represents an investor's revenue distribution
@@ -28,6 +50,20 @@ pub struct InvestorDistribution {
     pub impact_multiplier: i32, // Multiplier based on social impact
 }
 
+/// A distribution line item selected for mandatory off-chain audit
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AuditSample {
+    pub distribution_id: Symbol,
+    pub line_index: u32, // Index into RevenueDistribution.distributions
+    pub investor: Address,
+    pub amount: i128,
+    pub sampled_ledger: u32, // Ledger whose PRNG seed produced the sample
+    pub attested: bool, // Set once the auditor posts the report's hash
+}
+
+page_result!(AuditSamplePage, AuditSample);
+
 /// Represents ride revenue data from oracle
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -40,6 +76,153 @@ pub struct RideRevenue {
     pub timestamp: u64,
 }
 
+/// Compact audit record for an equity boost, bonus, or rate discount
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BoostAuditRecord {
+    pub subject: Address, // Who received the boost
+    pub amount: i128, // Size of the boost (units depend on rule)
+    pub rule: Symbol, // Which fairness rule granted it
+    pub data_version: u64, // Timestamp of the data the rule was evaluated against
+    pub timestamp: u64,
+}
+
+page_result!(BoostAuditPage, BoostAuditRecord);
+
+/// Revenue report awaiting countersignature by the other party
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RevenueReport {
+    pub asset_id: Symbol,
+    pub revenue_amount: i128,
+    pub ride_count: i32,
+    pub co2_saved: i32,
+    pub underserved_rides: i32,
+    pub submitted_by: Symbol, // "oracle" or "operator"
+    pub submitted_at: u64,
+    pub status: Symbol, // "pending", "disputed", "escalated"
+}
+
+/// One taxable event for an investor
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TaxEvent {
+    pub kind: Symbol, // "dist", "fee", "redeemed", "recycled", "writeoff", "refunded", "covered", "salvage"
+    pub asset_id: Symbol,
+    pub amount: i128,
+    pub timestamp: u64, // Ledger timestamp of the event
+}
+
+/// Per-investor, per-year tax export; totals cover the whole year, events one page
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TaxSummary {
+    pub investor: Address,
+    pub year: u32,
+    pub total_distributions: i128,
+    pub total_fees: i128, // Investor's share of platform fees on their distributions
+    pub total_principal_returned: i128,
+    pub total_writeoffs: i128,
+    pub events: Vec<TaxEvent>,
+    pub next_cursor: u32, // 0 once every event has been returned
+}
+
+/// Guardian request to pay out of a program's emergency fund
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EmergencyRequest {
+    pub id: u32,
+    pub program: Symbol,
+    pub guardian: Address,
+    pub recipient: Address,
+    pub amount: i128,
+    pub reason: Symbol, // e.g. "theft", "disaster"
+    pub requested_at: u64,
+    pub status: Symbol, // "pending", "paid", "expired"
+}
+
+/// An operator's advance against an asset's next distributions
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RevenueAdvance {
+    pub asset_id: Symbol,
+    pub operator: Address,
+    pub principal: i128,
+    pub fee: i128,
+    pub outstanding: i128, // Principal plus fee not yet recovered from distributions
+    pub drawn_at: u64,
+    pub drawn_epoch: u32, // Asset's latest distribution epoch when the advance was drawn
+}
+
+/// Distribution policy an asset's investors voted for
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AssetPolicy {
+    pub reserve_bps: u32, // Share of net revenue held back in the asset's maintenance reserve
+    pub reinvest: bool, // Reinvest the next distribution's investor share instead of paying it out
+    pub referendum_id: u32, // Referendum that adopted the policy; 0 for the default
+}
+
+/// Governance-set bounds on the policies an asset's investors can vote for
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PolicyBounds {
+    pub max_reserve_bps: u32,
+    pub allow_reinvest: bool,
+}
+
+impl PolicyBounds {
+    /// Whether a referendum may propose this policy
+    pub fn allows(&self, reserve_bps: u32, reinvest: bool) -> bool {
+        reserve_bps <= self.max_reserve_bps && (self.allow_reinvest || !reinvest)
+    }
+}
+
+/// Revenue held back from an asset's distributions by its policy
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct AssetReserve {
+    pub maintenance: i128,
+    pub reinvested: i128,
+}
+
+/// Governance-set floor on the payout of investors holding a small share of an asset
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct MinPayout {
+    pub share_threshold_bps: u32, // Investors holding less than this share of the asset qualify
+    pub min_payout: i128, // Per-distribution floor, topped up from the unspent equity bonus pool
+}
+
+/// Share-weighted vote of an asset's investors on its distribution policy
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PolicyReferendum {
+    pub id: u32,
+    pub asset_id: Symbol,
+    pub proposer: Address,
+    pub reserve_bps: u32,
+    pub reinvest: bool,
+    pub yes_weight: i128, // Sum of voters' open positions in the asset
+    pub no_weight: i128,
+    pub ends_at: u64,
+    pub status: Symbol, // "open", "passed", "failed"
+}
+
+/// The distributor's settlement-token balance against what it owes
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Reconciliation {
+    pub token_balance: i128,
+    pub claimables: i128, // Unclaimed investor lines on distributions that aren't frozen
+    pub reserves: i128, // Emergency fund balances across programs and policy reserves across assets
+    pub fees: i128, // Treasury fees not yet released
+    pub advanced: i128, // Advance principal paid to operators and not yet recovered
+    pub discrepancy: i128, // token_balance + advanced - (claimables + reserves + fees)
+    pub within_tolerance: bool,
+    pub checked_at: u64,
+}
+
 /// Contract data structure
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -47,37 +230,223 @@ pub struct DataKey {
     pub admin: Address,
     pub oracle: Address, // Revenue oracle address
     pub loan_pool: Address, // Loan pool contract address
-    pub distributions: Map<Symbol, RevenueDistribution>,
     pub ride_revenues: Map<Symbol, RideRevenue>,
     pub equity_bonus_rate: i32, // Percentage of revenue for equity bonuses
     pub impact_bonus_rate: i32, // Additional bonus for high-impact zones
+    pub auditor: Address, // Posts attestations for sampled line items
+    pub audit_samples: Map<Symbol, Vec<AuditSample>>, // distribution_id -> samples
+    pub asset_epochs: Map<Symbol, u32>, // asset_id -> latest distribution epoch
+    pub epoch_index: Map<(Symbol, u32), Symbol>, // (asset_id, epoch) -> distribution_id
+    pub distribution_order: Vec<Symbol>, // distribution ids in creation order, for cursors
+    pub claimed_totals: Map<Address, i128>, // investor -> lifetime claimed amount
+    pub paused: bool, // Set by admin or by a failed invariant check when auto_pause is on
+    pub disabled_features: Vec<Symbol>, // Entrypoint features switched off by admin
+    pub auto_pause: bool,
+    pub asset_operators: Map<Symbol, Address>, // asset_id -> operator who countersigns revenue
+    pub revenue_reports: Map<Symbol, RevenueReport>, // asset_id -> report awaiting countersignature
+    pub emergency_bps: u32, // Share of each distribution's net revenue topped up into the emergency fund
+    pub emergency_funds: Map<Symbol, i128>, // program -> emergency fund balance
+    pub emergency_guardians: Map<Symbol, Address>, // program -> guardian who can request payouts
+    pub emergency_requests: Map<u32, EmergencyRequest>,
+    pub next_emergency_id: u32,
+    pub approval_rule: ApprovalRule, // Threshold and approvers for large disbursements
+    pub pending_actions: Map<u32, PendingAction>, // action_id -> large disbursement awaiting approvals
+    pub next_action_id: u32,
+    pub reconcile_tolerance: i128, // Largest discrepancy reconcile accepts without raising an alert
+    pub reconcile_pauses_claims: bool, // Switch off the "claim" feature when reconcile raises an alert
+    pub advances: Map<Symbol, RevenueAdvance>, // asset_id -> operator advance being repaid
+    pub advance_cap_bps: u32, // Share of trailing average revenue an operator can draw
+    pub advance_fee_bps: u32, // Fee on the advance principal, repaid with it
+}
+
+/// Persistent entries for per-record state, so a call reads and rewrites only
+/// the records it touches instead of the whole instance entry
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum StorageKey {
+    Distribution(Symbol),
+    DistributionIds, // Every distribution id, in recording order
+    Claimable(Address, Symbol), // (investor, distribution_id) -> unclaimed amount
+    ClaimableTotal(Address), // investor -> unclaimed amount across all distributions
+    Policy(Symbol), // asset_id -> AssetPolicy
+    Reserve(Symbol), // asset_id -> AssetReserve
+    Referendum(u32),
+    ReferendumVote(u32, Address), // (referendum_id, investor) -> weight cast
+    ReferendumCount,
+    PolicyBounds,
+    ReservesTotal, // Maintenance and reinvested balances held across assets
+    MinPayout,
+    Beneficiary(Address), // investor -> fallback beneficiary for stale claims
+    Reminder(Symbol, Address), // (distribution_id, investor) -> last reminder level sent
+    Governance, // Source of the platform fee schedule and epoch calendar
+    IntegrityHash(Symbol), // distribution_id -> hash of its line items, set when it is frozen
+    Attestation(Symbol, u32), // (distribution_id, line_index) -> hash of the posted audit report
 }
 
 const DATA_KEY: Symbol = symbol_short!("DATA_KEY");
 
+// Set once by initialize to the storage layout version; its presence marks the contract initialized
+const INIT_KEY: Symbol = symbol_short!("INIT");
+const CONTRACT_VERSION: u32 = 2;
+
+// Journal streams of BoostAuditRecords, by (BOOST_BY_SUBJECT, subject) and (BOOST_BY_RULE, rule)
+const BOOST_BY_SUBJECT: Symbol = symbol_short!("BOOST_SUB");
+const BOOST_BY_RULE: Symbol = symbol_short!("BOOST_RUL");
+
+const SECONDS_PER_YEAR: u64 = 365 * 86_400;
+
+// Persistent entries: (KPI_KEY, epoch) -> RideStats for revenue accepted that epoch
+const KPI_KEY: Symbol = symbol_short!("KPI");
+// Persistent entries: (DIST_KPI_KEY, epoch) -> DistributionStats for distributions recorded that epoch
+const DIST_KPI_KEY: Symbol = symbol_short!("DIST_KPI");
+
+// Unclaimed lines can be claimed for a year, after which the distribution can be frozen
+const CLAIM_WINDOW: u64 = 365 * 86_400;
+
+// Unclaimed lines start drawing reminders after the soft deadline, one level higher per interval
+const CLAIM_SOFT_DEADLINE: u64 = 90 * 86_400;
+const REMINDER_INTERVAL: u64 = 30 * 86_400;
+
+// A designated fallback beneficiary can claim for this long after the claim window closes,
+// and the distribution can't be frozen until it has passed
+const BENEFICIARY_WINDOW: u64 = 90 * 86_400;
+
+// Emergency fund top-ups are capped at 5% of net revenue
+const MAX_EMERGENCY_BPS: u32 = 500;
+
+// Guardian requests lapse if governance has not approved them within a week
+const EMERGENCY_REQUEST_TTL: u64 = 7 * 86_400;
+
+// Unconfirmed or disputed revenue reports can be escalated to governance after this long
+const REPORT_TIMEOUT: u64 = 3 * 86_400;
+
+// Advances are sized from the asset's last three distributions, capped at 80% of their
+// average with a fee of at most 5%
+const ADVANCE_TRAILING_EPOCHS: u32 = 3;
+const MAX_ADVANCE_CAP_BPS: u32 = 8_000;
+const MAX_ADVANCE_FEE_BPS: u32 = 500;
+
+// Investor referenda on an asset's distribution policy stay open for three days, and
+// governance can let investors hold back at most 20% of net revenue for maintenance
+const POLICY_VOTE_DURATION: u64 = 3 * 86_400;
+const MAX_POLICY_RESERVE_BPS: u32 = 2_000;
+
 #[contract]
 pub struct RevenueDistributor;
 
 #[contractimpl]
 impl RevenueDistributor {
-    /// Initialize the contract
+    /// Initialize the contract. Can only be called once.
     pub fn initialize(
         env: &Env,
         admin: Address,
         oracle: Address,
         loan_pool: Address,
         equity_bonus_rate: i32,
-    ) {
+    ) -> Result<(), ContractError> {
+        admin.require_auth();
+
+        if env.storage().instance().has(&INIT_KEY) || env.storage().instance().has(&DATA_KEY) {
+            return Err(ContractError::AlreadyInitialized);
+        }
+
         let data = DataKey {
-            admin,
+            admin: admin.clone(),
             oracle,
             loan_pool,
-            distributions: Map::new(env),
             ride_revenues: Map::new(env),
             equity_bonus_rate,
             impact_bonus_rate: 10, // 10% additional bonus for high-impact zones
+            auditor: admin.clone(),
+            audit_samples: Map::new(env),
+            asset_epochs: Map::new(env),
+            epoch_index: Map::new(env),
+            distribution_order: vec![env],
+            claimed_totals: Map::new(env),
+            paused: false,
+            disabled_features: Vec::new(env),
+            auto_pause: false,
+            asset_operators: Map::new(env),
+            revenue_reports: Map::new(env),
+            emergency_bps: 100, // 1% of every distribution
+            emergency_funds: Map::new(env),
+            emergency_guardians: Map::new(env),
+            emergency_requests: Map::new(env),
+            next_emergency_id: 1,
+            approval_rule: ApprovalRule::disabled(env),
+            pending_actions: Map::new(env),
+            next_action_id: 1,
+            reconcile_tolerance: 0,
+            reconcile_pauses_claims: false,
+            advances: Map::new(env),
+            advance_cap_bps: 5_000, // Half of a typical epoch's revenue
+            advance_fee_bps: 100, // 1% of the principal
         };
-        env.storage().instance().set(&DATA_KEY, &data);
+        ttl::set_instance(env, &DATA_KEY, &data);
+        ttl::set_instance(env, &INIT_KEY, &CONTRACT_VERSION);
+
+        Ok(())
+    }
+
+    /// Change the oracle, loan pool and equity bonus rate set at initialization
+    /// without touching other state (governance only)
+    pub fn reconfigure(env: &Env, oracle: Address, loan_pool: Address, equity_bonus_rate: i32) -> Result<(), ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        Self::governance(env).ok_or(ContractError::NoAddress)?.require_auth();
+
+        if !(0..=50).contains(&equity_bonus_rate) {
+            return Err(ContractError::InvalidInput);
+        }
+
+        data.oracle = oracle.clone();
+        data.loan_pool = loan_pool.clone();
+        data.equity_bonus_rate = equity_bonus_rate;
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        env.events().publish((symbol_short!("reconfig"),), (oracle, loan_pool, equity_bonus_rate));
+
+        Ok(())
+    }
+
+    /// Get the storage layout version recorded at initialization, or 0 if uninitialized
+    pub fn get_version(env: &Env) -> u32 {
+        env.storage().instance().get(&INIT_KEY).unwrap_or(0)
+    }
+
+    /// Version of the running code; `get_version` lags behind it until `migrate` runs
+    pub fn version(_env: &Env) -> u32 {
+        CONTRACT_VERSION
+    }
+
+    /// Replace the contract's code with an uploaded wasm, keeping its storage (admin only).
+    /// The new code's `migrate` must run before storage written by the old layout is used
+    pub fn upgrade(env: &Env, caller: Address, new_wasm_hash: BytesN<32>) -> Result<(), ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+        upgrade::upgrade(env, new_wasm_hash);
+
+        Ok(())
+    }
+
+    /// Bring storage up to this code's layout version after an upgrade (admin only)
+    pub fn migrate(env: &Env, caller: Address) -> Result<u32, ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+
+        let stored: u32 = env.storage().instance().get(&INIT_KEY).unwrap_or(0);
+        let version = upgrade::migrate(env, stored, CONTRACT_VERSION, Self::migrate_step)?;
+        ttl::set_instance(env, &INIT_KEY, &version);
+
+        Ok(version)
+    }
+
+    /// Rewrite storage from layout version `from` to `from + 1`. Each release that changes
+    /// the layout adds its step here
+    fn migrate_step(_env: &Env, _from: u32) -> Result<(), ContractError> {
+        Ok(())
     }
 
     /// Record ride revenue from oracle
@@ -88,147 +457,1922 @@ impl RevenueDistributor {
         ride_count: i32,
         co2_saved: i32,
         underserved_rides: i32,
-    ) -> Result<(), Symbol> {
-        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+    ) -> Result<(), ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
         
         // Only oracle can record revenue
-        if env.current_contract_address() != data.oracle {
-            return Err(symbol_short!("UNAUTHORIZED"));
-        }
+        data.oracle.require_auth();
 
-        let revenue = RideRevenue {
+        Self::store_revenue(env, data, asset_id, revenue_amount, ride_count, co2_saved, underserved_rides)
+    }
+
+    /// Record revenue from a payload signed by a registered feeder key; anyone may relay it
+    pub fn submit_signed_revenue(
+        env: &Env,
+        feeder: BytesN<32>,
+        asset_id: Symbol,
+        revenue_amount: i128,
+        ride_count: i32,
+        co2_saved: i32,
+        underserved_rides: i32,
+        nonce: u64,
+        timestamp: u64,
+        signature: BytesN<64>,
+    ) -> Result<(), ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        attestation::verify_feeder(
+            env,
+            &feeder,
+            Symbol::new(env, "record_revenue"),
+            (asset_id.clone(), revenue_amount, ride_count, co2_saved, underserved_rides),
+            nonce,
+            timestamp,
+            &signature,
+        )?;
+
+        Self::store_revenue(env, data, asset_id, revenue_amount, ride_count, co2_saved, underserved_rides)
+    }
+
+    /// Register an ed25519 feeder key that signs oracle submissions, and how long a signed payload
+    /// stays valid (oracle manager only)
+    pub fn register_feeder(env: &Env, caller: Address, public_key: BytesN<32>, max_age: u64) -> Result<(), ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::OracleManager, &caller)?;
+        attestation::register_feeder(env, &public_key, max_age, env.ledger().timestamp()).map_err(ContractError::from)
+    }
+
+    /// Enable or disable one feeder key, leaving the others signing (oracle manager only)
+    pub fn set_feeder_enabled(env: &Env, caller: Address, public_key: BytesN<32>, enabled: bool) -> Result<(), ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::OracleManager, &caller)?;
+        attestation::set_feeder_enabled(env, &public_key, enabled).map_err(ContractError::from)
+    }
+
+    /// Schedule a new feeder key to replace an old one at `effective_at` (oracle manager only)
+    pub fn rotate_feeder(
+        env: &Env,
+        caller: Address,
+        old_key: BytesN<32>,
+        new_key: BytesN<32>,
+        max_age: u64,
+        effective_at: u64,
+    ) -> Result<(), ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::OracleManager, &caller)?;
+        attestation::rotate_feeder(env, &old_key, &new_key, max_age, effective_at).map_err(ContractError::from)
+    }
+
+    /// Get every registered feeder key with its state
+    pub fn get_feeders(env: &Env) -> Vec<FeederKey> {
+        attestation::feeders(env)
+    }
+
+    /// Queue or accept an oracle revenue report once the submitting oracle is authenticated
+    fn store_revenue(
+        env: &Env,
+        mut data: DataKey,
+        asset_id: Symbol,
+        revenue_amount: i128,
+        ride_count: i32,
+        co2_saved: i32,
+        underserved_rides: i32,
+    ) -> Result<(), ContractError> {
+        Gate::new(data.paused, &data.disabled_features, &symbol_short!("oracle")).check()?;
+
+        let report = RevenueReport {
             asset_id: asset_id.clone(),
             revenue_amount,
             ride_count,
             co2_saved,
             underserved_rides,
-            timestamp: env.ledger().timestamp(),
+            submitted_by: symbol_short!("oracle"),
+            submitted_at: env.ledger().timestamp(),
+            status: symbol_short!("pending"),
         };
 
-        data.ride_revenues.set(&asset_id, &revenue);
-        env.storage().instance().set(&DATA_KEY, &data);
+        // Assets without a registered operator accept oracle reports directly
+        if data.asset_operators.contains_key(asset_id) {
+            Self::submit_revenue_report(env, &mut data, report)?;
+        } else {
+            Self::accept_revenue(env, &mut data, &report);
+        }
+        ttl::set_instance(env, &DATA_KEY, &data);
         
         Ok(())
     }
 
-    /// Distribute revenue to investors with equity bonuses
-    pub fn distribute_revenue(
+    /// Register the operator who countersigns an asset's revenue reports (admin only)
+    pub fn set_asset_operator(env: &Env, caller: Address, asset_id: Symbol, operator: Address) -> Result<(), ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+
+        data.asset_operators.set(asset_id, operator);
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        Ok(())
+    }
+
+    /// Submit or countersign an asset's revenue report as its operator
+    pub fn operator_report_revenue(
         env: &Env,
+        operator: Address,
         asset_id: Symbol,
-        investors: Vec<Address>,
-        investment_amounts: Vec<i128>,
-        equity_scores: Vec<i32>,
-    ) -> Result<Symbol, Symbol> {
+        revenue_amount: i128,
+        ride_count: i32,
+        co2_saved: i32,
+        underserved_rides: i32,
+    ) -> Result<Symbol, ContractError> {
+        operator.require_auth();
         let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
-        
-        // Only admin can trigger distribution
-        if env.current_contract_address() != data.admin {
-            return Err(symbol_short!("UNAUTHORIZED"));
-        }
 
-        // Get revenue data
-        let revenue = data.ride_revenues.get(&asset_id).ok_or(symbol_short!("REVENUE_NOT_FOUND"))?;
-        
-        // Validate input arrays
-        if investors.len() != investment_amounts.len() || investors.len() != equity_scores.len() {
-            return Err(symbol_short!("INVALID_INPUT"));
+        if data.asset_operators.get(asset_id.clone()) != Some(operator) {
+            return Err(ContractError::Unauthorized);
         }
 
-        let total_investment = investment_amounts.iter().sum();
-        let equity_bonus_pool = revenue.revenue_amount * data.equity_bonus_rate / 100;
-        let distribution_amount = revenue.revenue_amount - equity_bonus_pool;
+        let status = Self::submit_revenue_report(env, &mut data, RevenueReport {
+            asset_id,
+            revenue_amount,
+            ride_count,
+            co2_saved,
+            underserved_rides,
+            submitted_by: symbol_short!("operator"),
+            submitted_at: env.ledger().timestamp(),
+            status: symbol_short!("pending"),
+        })?;
+        ttl::set_instance(env, &DATA_KEY, &data);
 
-        let mut distributions = vec![env];
-        let mut total_distributed = 0;
+        Ok(status)
+    }
 
-        // Calculate distributions for each investor
-        for i in 0..investors.len() {
-            let investor = &investors.get(i).unwrap();
-            let investment_amount = investment_amounts.get(i).unwrap();
-            let equity_score = equity_scores.get(i).unwrap();
+    /// Escalate a report that was never countersigned or is disputed
+    pub fn escalate_revenue_report(env: &Env, asset_id: Symbol) -> Result<(), ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
 
-            // Calculate base distribution proportional to investment
-            let base_amount = if total_investment > 0 {
-                distribution_amount * investment_amount / total_investment
-            } else {
-                0
-            };
+        let mut report = data.revenue_reports.get(asset_id.clone()).ok_or(ContractError::NotFound)?;
 
-            // Calculate equity bonus
-            let equity_bonus = Self::calculate_equity_bonus(
-                env,
-                &equity_bonus_pool,
-                &equity_score,
-                &revenue.underserved_rides,
-                &revenue.ride_count,
-            );
+        if report.status == symbol_short!("escalated") {
+            return Err(ContractError::InvalidStatus);
+        }
 
-            // Calculate impact multiplier for high-impact zones
-            let impact_multiplier = Self::calculate_impact_multiplier(
-                env,
-                &revenue.co2_saved,
-                &revenue.underserved_rides,
-                &revenue.ride_count,
-            );
+        if env.ledger().timestamp() < report.submitted_at + REPORT_TIMEOUT {
+            return Err(ContractError::TooEarly);
+        }
 
-            let total_amount = base_amount + equity_bonus;
-            total_distributed += total_amount;
+        report.status = symbol_short!("escalated");
+        data.revenue_reports.set(asset_id.clone(), report.clone());
+        ttl::set_instance(env, &DATA_KEY, &data);
 
-            let distribution = InvestorDistribution {
-                investor: investor.clone(),
-                base_amount,
-                equity_bonus,
-                total_amount,
-                equity_score: equity_score.clone(),
-                impact_multiplier,
-            };
+        env.events().publish((symbol_short!("rev_esc"), asset_id), report.revenue_amount);
+
+        Ok(())
+    }
+
+    /// Settle an escalated report with the figures governance decided on (governance only)
+    pub fn resolve_revenue_report(
+        env: &Env,
+        asset_id: Symbol,
+        revenue_amount: i128,
+        ride_count: i32,
+        co2_saved: i32,
+        underserved_rides: i32,
+    ) -> Result<(), ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        let governance = Self::governance(env).ok_or(ContractError::NoAddress)?;
+        governance.require_auth();
 
-            distributions.push_back(&distribution);
+        let report = data.revenue_reports.get(asset_id).ok_or(ContractError::NotFound)?;
+        if report.status != symbol_short!("escalated") {
+            return Err(ContractError::InvalidStatus);
         }
 
-        // Create distribution record
-        let distribution_id = Self::generate_distribution_id(env, &asset_id);
-        let distribution = RevenueDistribution {
-            id: distribution_id.clone(),
-            asset_id,
-            total_revenue: revenue.revenue_amount,
-            distribution_amount,
-            equity_bonus_pool,
-            timestamp: env.ledger().timestamp(),
-            distributions,
-        };
+        Self::accept_revenue(env, &mut data, &RevenueReport {
+            revenue_amount,
+            ride_count,
+            co2_saved,
+            underserved_rides,
+            ..report
+        });
+        ttl::set_instance(env, &DATA_KEY, &data);
 
-        data.distributions.set(&distribution_id, &distribution);
-        env.storage().instance().set(&DATA_KEY, &data);
-        
-        Ok(distribution_id)
+        Ok(())
     }
 
-    /// Get distribution details
-    pub fn get_distribution(env: &Env, distribution_id: Symbol) -> Result<RevenueDistribution, Symbol> {
+    /// Get the revenue report awaiting countersignature for an asset
+    pub fn get_revenue_report(env: &Env, asset_id: Symbol) -> Result<RevenueReport, ContractError> {
         let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
-        data.distributions.get(&distribution_id).ok_or(symbol_short!("DISTRIBUTION_NOT_FOUND"))
+        data.revenue_reports.get(asset_id).ok_or(ContractError::NotFound)
     }
 
-    /// Get revenue data for an asset
-    pub fn get_revenue(env: &Env, asset_id: Symbol) -> Result<RideRevenue, Symbol> {
-        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
-        data.ride_revenues.get(&asset_id).ok_or(symbol_short!("REVENUE_NOT_FOUND"))
-    }
+    /// Match a report against the other party's pending one: equal figures make the
+    /// revenue distributable, different figures mark the report disputed
+    fn submit_revenue_report(env: &Env, data: &mut DataKey, report: RevenueReport) -> Result<Symbol, ContractError> {
+        let asset_id = report.asset_id.clone();
 
-    /// Get all distributions for an asset
-    pub fn get_asset_distributions(env: &Env, asset_id: Symbol) -> Vec<RevenueDistribution> {
-        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
-        let mut asset_distributions = vec![env];
-        
-        for (_, distribution) in data.distributions.iter() {
-            if distribution.asset_id == asset_id {
-                asset_distributions.push_back(&distribution);
+        let pending = match data.revenue_reports.get(asset_id.clone()) {
+            Some(pending) => pending,
+            None => {
+                data.revenue_reports.set(asset_id, report);
+                return Ok(symbol_short!("pending"));
             }
+        };
+
+        if pending.status == symbol_short!("escalated") {
+            return Err(ContractError::Escalated);
         }
-        
-        asset_distributions
-    }
+
+        // A party may revise its own report until the other side responds
+        if pending.submitted_by == report.submitted_by {
+            if pending.status == symbol_short!("disputed") {
+                return Err(ContractError::Disputed);
+            }
+            data.revenue_reports.set(asset_id, report);
+            return Ok(symbol_short!("pending"));
+        }
+
+        if pending.revenue_amount == report.revenue_amount
+            && pending.ride_count == report.ride_count
+            && pending.co2_saved == report.co2_saved
+            && pending.underserved_rides == report.underserved_rides
+        {
+            Self::accept_revenue(env, data, &report);
+            return Ok(symbol_short!("confirmed"));
+        }
+
+        let mut disputed = pending;
+        disputed.status = symbol_short!("disputed");
+        data.revenue_reports.set(asset_id.clone(), disputed.clone());
+        env.events().publish((symbol_short!("rev_disp"), asset_id), (disputed.revenue_amount, report.revenue_amount));
+
+        Ok(symbol_short!("disputed"))
+    }
+
+    /// Make reported revenue distributable and clear any pending report
+    fn accept_revenue(env: &Env, data: &mut DataKey, report: &RevenueReport) {
+        let revenue = RideRevenue {
+            asset_id: report.asset_id.clone(),
+            revenue_amount: report.revenue_amount,
+            ride_count: report.ride_count,
+            co2_saved: report.co2_saved,
+            underserved_rides: report.underserved_rides,
+            timestamp: env.ledger().timestamp(),
+        };
+
+        data.ride_revenues.set(report.asset_id.clone(), revenue.clone());
+        data.revenue_reports.remove(report.asset_id.clone());
+
+        // Count the rides toward the epoch's KPIs
+        let key = (KPI_KEY, Self::epoch_schedule(env).epoch_at(revenue.timestamp));
+        let mut stats: RideStats = env.storage().persistent().get(&key).unwrap_or_default();
+        stats.rides += report.ride_count as i128;
+        stats.underserved_rides += report.underserved_rides as i128;
+        ttl::set_persistent(env, &key, &stats);
+        env.events().publish(
+            (symbol_short!("revenue"), report.asset_id.clone()),
+            (report.revenue_amount, report.ride_count),
+        );
+    }
+
+    /// Distribute revenue pro rata to the asset's share token balances at the loan pool,
+    /// with every holder's equity bonus scored on the asset's zone
+    pub fn distribute_revenue(env: &Env, caller: Address, asset_id: Symbol) -> Result<Symbol, ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        Gate::new(data.paused, &data.disabled_features, &symbol_short!("distrib")).check()?;
+
+        // Only treasurers can trigger distribution
+        access::check(env, &data.admin, Role::Treasurer, &caller)?;
+
+        let loan_pool = LoanPoolClient::new(env, &data.loan_pool);
+        let equity_score = loan_pool.get_asset_equity_score(&asset_id).ok_or(ContractError::AssetNotFound)?;
+
+        // Holders who have sold or redeemed out are skipped, so the balances sum to the supply
+        let mut investors = vec![env];
+        let mut balances = vec![env];
+        let mut equity_scores = vec![env];
+        for holder in loan_pool.share_holders(&asset_id).iter() {
+            let balance = loan_pool.share_balance(&asset_id, &holder);
+            if balance > 0 {
+                investors.push_back(holder);
+                balances.push_back(balance);
+                equity_scores.push_back(equity_score);
+            }
+        }
+        if balances.iter().sum::<i128>() != loan_pool.share_total_supply(&asset_id) {
+            return Err(ContractError::InvalidInput);
+        }
+
+        let distribution_id = Self::record_distribution(
+            env,
+            &mut data,
+            asset_id,
+            &investors,
+            &balances,
+            &equity_scores,
+        )?;
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        Ok(distribution_id)
+    }
+
+    /// Distribute a finished calendar epoch's revenue using time-weighted shares, so investors
+    /// who joined mid-epoch only earn for the time their capital was actually at work
+    pub fn distribute_epoch_revenue(env: &Env, caller: Address, asset_id: Symbol, epoch: u32) -> Result<Symbol, ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        Gate::new(data.paused, &data.disabled_features, &symbol_short!("epoch")).check()?;
+
+        // Only treasurers can trigger distribution
+        access::check(env, &data.admin, Role::Treasurer, &caller)?;
+
+        // Epoch bounds come from the shared calendar
+        let schedule = Self::epoch_schedule(env);
+        let epoch_start = schedule.epoch_start(epoch);
+        let epoch_end = schedule.epoch_end(epoch);
+        if epoch_end > env.ledger().timestamp() {
+            return Err(ContractError::TooEarly);
+        }
+
+        let loan_pool = LoanPoolClient::new(env, &data.loan_pool);
+        let equity_score = loan_pool.get_asset_equity_score(&asset_id).ok_or(ContractError::AssetNotFound)?;
+
+        // A holding's capital-seconds since the epoch started, less those since it ended, is the
+        // time it was at work during the epoch; dividing by the epoch's length puts it back in units
+        let epoch_duration = (epoch_end - epoch_start) as i128;
+        let mut investors = vec![env];
+        let mut weighted_amounts = vec![env];
+        let mut equity_scores = vec![env];
+        for holder in loan_pool.share_holders(&asset_id).iter() {
+            let (_, since_start, _, _) = loan_pool.get_investor_position(&holder, &asset_id, &epoch_start);
+            let (_, since_end, _, _) = loan_pool.get_investor_position(&holder, &asset_id, &epoch_end);
+            let weighted = (since_start - since_end) / epoch_duration;
+            if weighted > 0 {
+                investors.push_back(holder);
+                weighted_amounts.push_back(weighted);
+                equity_scores.push_back(equity_score);
+            }
+        }
+
+        let distribution_id = Self::record_distribution(
+            env,
+            &mut data,
+            asset_id,
+            &investors,
+            &weighted_amounts,
+            &equity_scores,
+        )?;
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        Ok(distribution_id)
+    }
+
+    /// Use up the pending action approving a disbursement above the threshold
+    fn consume_approval(
+        env: &Env,
+        data: &mut DataKey,
+        kind: Symbol,
+        reference: Symbol,
+        recipient: Option<Address>,
+        amount: i128,
+    ) -> Result<(), ContractError> {
+        if !data.approval_rule.applies(amount) {
+            return Ok(());
+        }
+
+        let now = env.ledger().timestamp();
+        let payee = Payee::from(recipient);
+        for (action_id, action) in data.pending_actions.iter() {
+            if action.covers(&data.approval_rule, &kind, &reference, &payee, amount, now) {
+                data.pending_actions.remove(action_id);
+                env.events().publish((symbol_short!("act_exec"), action_id), (kind, amount));
+                return Ok(());
+            }
+        }
+
+        Err(ContractError::NeedsApproval)
+    }
+
+    /// Shared epoch calendar from governance, or the default schedule when none is set
+    fn epoch_schedule(env: &Env) -> EpochSchedule {
+        match Self::governance(env) {
+            Some(governance) => GovernanceClient::new(env, &governance).get_epoch_schedule(),
+            None => EpochSchedule::default_schedule(),
+        }
+    }
+
+    /// Look up the platform fee rate for an asset's zone from the governance fee schedule
+    fn platform_fee_rate(env: &Env, data: &DataKey, asset_id: &Symbol) -> (i32, u32) {
+        let governance = match Self::governance(env) {
+            Some(governance) => governance,
+            None => return (0, 0),
+        };
+
+        let equity_score = match LoanPoolClient::new(env, &data.loan_pool).get_asset_equity_score(asset_id) {
+            Some(score) => score,
+            None => return (0, 0),
+        };
+
+        let fee_bps = GovernanceClient::new(env, &governance).get_fee_bps(&equity_score);
+
+        (equity_score, fee_bps)
+    }
+
+    /// Build and store a distribution record from investor weights
+    fn record_distribution(
+        env: &Env,
+        data: &mut DataKey,
+        asset_id: Symbol,
+        investors: &Vec<Address>,
+        investment_amounts: &Vec<i128>,
+        equity_scores: &Vec<i32>,
+    ) -> Result<Symbol, ContractError> {
+        // Get revenue data
+        let revenue = data.ride_revenues.get(asset_id.clone()).ok_or(ContractError::NotFound)?;
+
+        // Large distributions need a pending action approved by enough signers
+        Self::consume_approval(env, data, symbol_short!("dist"), asset_id.clone(), None, revenue.revenue_amount)?;
+
+        // Every investor must be able to receive the settlement asset before any line is queued
+        if let Some(asset) = LoanPoolClient::new(env, &data.loan_pool).get_settlement_asset() {
+            for investor in investors.iter() {
+                trustline::check_trustline(env, &asset, &investor)?;
+            }
+        }
+
+        let total_investment: i128 = investment_amounts.iter().sum();
+        // Platform fee comes off the top at the zone's sliding-scale rate
+        let (zone_equity_score, fee_bps) = Self::platform_fee_rate(env, data, &asset_id);
+        let platform_fee = revenue.revenue_amount * fee_bps as i128 / 10_000;
+        let after_fee = revenue.revenue_amount - platform_fee;
+
+        // Recover any operator advance next, then top up the asset's program emergency fund
+        let advance_repayment = Self::repay_advance(env, data, &asset_id, after_fee);
+        let after_advance = after_fee - advance_repayment;
+        let emergency_topup = after_advance * data.emergency_bps as i128 / 10_000;
+        let after_emergency = after_advance - emergency_topup;
+        if emergency_topup > 0 {
+            if let Some(program) = LoanPoolClient::new(env, &data.loan_pool).get_asset_program(&asset_id) {
+                let balance = data.emergency_funds.get(program.clone()).unwrap_or(0);
+                data.emergency_funds.set(program, balance + emergency_topup);
+            }
+        }
+
+        // The asset's investors may have voted to hold back a maintenance reserve or reinvest this payout
+        let net_revenue = Self::apply_asset_policy(env, &asset_id, after_emergency);
+
+        // Equity bonuses pause while the asset's loan is in default
+        let equity_bonus_pool = if LoanPoolClient::new(env, &data.loan_pool).is_asset_defaulted(&asset_id) {
+            env.events().publish((symbol_short!("bonus_off"), asset_id.clone()), net_revenue);
+            0
+        } else {
+            net_revenue * data.equity_bonus_rate as i128 / 100
+        };
+        let distribution_amount = net_revenue - equity_bonus_pool;
+
+        let mut distributions = vec![env];
+        let mut total_distributed = 0;
+
+        // Calculate distributions for each investor
+        for i in 0..investors.len() {
+            let investor = &investors.get(i).unwrap();
+            let investment_amount = investment_amounts.get(i).unwrap();
+            let equity_score = equity_scores.get(i).unwrap();
+
+            // Calculate base distribution proportional to investment
+            let base_amount = if total_investment > 0 {
+                distribution_amount * investment_amount / total_investment
+            } else {
+                0
+            };
+
+            // Calculate equity bonus
+            let equity_bonus = Self::calculate_equity_bonus(
+                equity_bonus_pool,
+                investment_amount,
+                total_investment,
+                equity_score,
+                revenue.underserved_rides,
+                revenue.ride_count,
+            );
+
+            // Calculate impact multiplier for high-impact zones
+            let impact_multiplier = Self::calculate_impact_multiplier(
+                data,
+                revenue.co2_saved,
+                revenue.underserved_rides,
+                revenue.ride_count,
+            );
+
+            let total_amount = base_amount + equity_bonus;
+            total_distributed += total_amount;
+
+            // Audit the equity bonus
+            if equity_bonus > 0 {
+                Self::log_boost(env, investor, equity_bonus, symbol_short!("eq_bonus"), revenue.timestamp);
+            }
+
+            let distribution = InvestorDistribution {
+                investor: investor.clone(),
+                base_amount,
+                equity_bonus,
+                total_amount,
+                equity_score,
+                impact_multiplier,
+            };
+
+            distributions.push_back(distribution);
+        }
+
+        // Micro-investors are topped up to the floor from whatever the bonus pool didn't pay out,
+        // in investor order until it runs dry. Bonuses come out of each investor's slice of the
+        // pool, so this never goes negative and the top-ups stay within the pool
+        let floor = Self::min_payout(env);
+        let bonus_paid: i128 = distributions.iter().map(|line| line.equity_bonus).sum();
+        let mut floor_budget = equity_bonus_pool - bonus_paid;
+        if floor.min_payout > 0 && total_investment > 0 {
+            for i in 0..distributions.len() {
+                let mut line = distributions.get(i).unwrap();
+                let share_bps = investment_amounts.get(i).unwrap() * 10_000 / total_investment;
+                let topup = (floor.min_payout - line.total_amount).min(floor_budget);
+                if share_bps >= floor.share_threshold_bps as i128 || topup <= 0 {
+                    continue;
+                }
+
+                floor_budget -= topup;
+                line.equity_bonus += topup;
+                line.total_amount += topup;
+                total_distributed += topup;
+                Self::log_boost(env, &line.investor, topup, symbol_short!("min_pay"), revenue.timestamp);
+                distributions.set(i, line);
+            }
+        }
+
+        // Lines are paid in tokens, so one distribution can never spend another's revenue
+        if total_distributed > net_revenue {
+            return Err(ContractError::OverLimit);
+        }
+
+        // Create distribution record
+        let epoch = data.asset_epochs.get(asset_id.clone()).unwrap_or(0) + 1;
+        let distribution_id = Self::generate_distribution_id(env, &asset_id, epoch);
+        data.asset_epochs.set(asset_id.clone(), epoch);
+        data.epoch_index.set((asset_id.clone(), epoch), distribution_id.clone());
+        data.distribution_order.push_back(distribution_id.clone());
+
+        let distribution = RevenueDistribution {
+            id: distribution_id.clone(),
+            asset_id: asset_id.clone(),
+            total_revenue: revenue.revenue_amount,
+            distribution_amount,
+            equity_bonus_pool,
+            timestamp: env.ledger().timestamp(),
+            distributions,
+            treasury_fee: net_revenue - total_distributed,
+            platform_fee,
+            emergency_topup,
+            fee_released: false,
+            epoch,
+            claimed: Map::new(env),
+            advance_repayment,
+        };
+
+        Self::save_distribution(env, &distribution);
+
+        let key = (DIST_KPI_KEY, Self::epoch_schedule(env).epoch_at(distribution.timestamp));
+        let mut stats: DistributionStats = env.storage().persistent().get(&key).unwrap_or_default();
+        stats.distributions += 1;
+        stats.distributed += total_distributed;
+        stats.platform_fees += platform_fee;
+        ttl::set_persistent(env, &key, &stats);
+
+        // Allocate each line as a claimable balance for the investor to pull
+        for line in distribution.distributions.iter() {
+            Self::add_claimable(env, &line.investor, &distribution_id, line.total_amount);
+        }
+
+        changes::touch(env, symbol_short!("dist"), distribution_id.clone());
+        env.events().publish(
+            (symbol_short!("distrib"), distribution_id.clone()),
+            (asset_id.clone(), epoch, distribution.distribution_amount, distribution.distributions.len()),
+        );
+
+        if platform_fee > 0 {
+            env.events().publish(
+                (symbol_short!("fee"), distribution_id.clone()),
+                (symbol_short!("dist"), zone_equity_score, fee_bps, platform_fee),
+            );
+        }
+
+        Ok(distribution_id)
+    }
+
+    /// Claim an investor's balance in one distribution, paid in the settlement asset
+    pub fn claim(env: &Env, investor: Address, distribution_id: Symbol) -> Result<i128, ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        Gate::new(data.paused, &data.disabled_features, &symbol_short!("claim")).check()?;
+
+        investor.require_auth();
+
+        if Self::load_distribution(env, &distribution_id).is_none() {
+            return Err(ContractError::NotFound);
+        }
+        let amount = Self::settle_claim(env, &mut data, &investor, &distribution_id);
+        if amount == 0 {
+            return Err(ContractError::Nothing);
+        }
+
+        Self::pay_out(env, &data, &investor, amount)?;
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        Ok(amount)
+    }
+
+    /// Claim an investor's share of one asset epoch's distribution
+    pub fn claim_epoch(env: &Env, investor: Address, asset_id: Symbol, epoch: u32) -> Result<i128, ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        let distribution_id = data.epoch_index.get((asset_id, epoch)).ok_or(ContractError::NotFound)?;
+        Self::claim(env, investor, distribution_id)
+    }
+
+    /// Claim an investor's share of several (asset_id, epoch) distributions at once.
+    /// Entries that are unknown or already claimed are skipped.
+    pub fn claim_many(env: &Env, investor: Address, claims: Vec<(Symbol, u32)>) -> Result<i128, ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        Gate::new(data.paused, &data.disabled_features, &symbol_short!("claim")).check()?;
+
+        investor.require_auth();
+
+        let mut total: i128 = 0;
+        for key in claims.iter() {
+            if let Some(distribution_id) = data.epoch_index.get(key) {
+                total += Self::settle_claim(env, &mut data, &investor, &distribution_id);
+            }
+        }
+
+        if total == 0 {
+            return Err(ContractError::Nothing);
+        }
+
+        Self::pay_out(env, &data, &investor, total)?;
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        Ok(total)
+    }
+
+    /// Claim every outstanding balance for an investor, scanning up to `limit` distributions
+    /// from `cursor`. Returns (claimed amount, next cursor); the cursor equals the
+    /// distribution count once every distribution has been scanned.
+    pub fn claim_all_for(env: &Env, investor: Address, cursor: u32, limit: u32) -> Result<(i128, u32), ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        Gate::new(data.paused, &data.disabled_features, &symbol_short!("claim")).check()?;
+
+        investor.require_auth();
+
+        let count = data.distribution_order.len();
+        if cursor > count {
+            return Err(ContractError::InvalidInput);
+        }
+
+        let end = if cursor + limit > count { count } else { cursor + limit };
+        let mut total: i128 = 0;
+        for i in cursor..end {
+            let distribution_id = data.distribution_order.get(i).unwrap();
+            total += Self::settle_claim(env, &mut data, &investor, &distribution_id);
+        }
+
+        if total > 0 {
+            Self::pay_out(env, &data, &investor, total)?;
+        }
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        Ok((total, end))
+    }
+
+    /// Get an investor's unclaimed balance across all distributions
+    pub fn get_claimable_total(env: &Env, investor: Address) -> i128 {
+        env.storage().persistent().get(&StorageKey::ClaimableTotal(investor)).unwrap_or(0)
+    }
+
+    /// Get an investor's unclaimed balance in one distribution
+    pub fn get_claimable(env: &Env, investor: Address, distribution_id: Symbol) -> i128 {
+        env.storage().persistent().get(&StorageKey::Claimable(investor, distribution_id)).unwrap_or(0)
+    }
+
+    /// Designate who may claim an investor's stale lines once the claim window closes, e.g. a
+    /// family member or community fund; None removes the designation
+    pub fn set_claim_beneficiary(env: &Env, investor: Address, beneficiary: Option<Address>) -> Result<(), ContractError> {
+        investor.require_auth();
+
+        let key = StorageKey::Beneficiary(investor.clone());
+        match &beneficiary {
+            Some(beneficiary) if *beneficiary == investor => return Err(ContractError::SameParty),
+            Some(beneficiary) => ttl::set_persistent(env, &key, beneficiary),
+            None => env.storage().persistent().remove(&key),
+        }
+
+        env.events().publish((symbol_short!("benefic"), investor), beneficiary);
+
+        Ok(())
+    }
+
+    /// Get an investor's fallback beneficiary, if designated
+    pub fn get_claim_beneficiary(env: &Env, investor: Address) -> Option<Address> {
+        env.storage().persistent().get(&StorageKey::Beneficiary(investor))
+    }
+
+    /// Claim an investor's line in a distribution whose claim window has closed, paid to the
+    /// beneficiary the investor designated (beneficiary only)
+    pub fn claim_as_beneficiary(
+        env: &Env,
+        beneficiary: Address,
+        investor: Address,
+        distribution_id: Symbol,
+    ) -> Result<i128, ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        Gate::new(data.paused, &data.disabled_features, &symbol_short!("claim")).check()?;
+
+        beneficiary.require_auth();
+
+        if Self::get_claim_beneficiary(env, investor.clone()) != Some(beneficiary.clone()) {
+            return Err(ContractError::Unauthorized);
+        }
+        let distribution = Self::load_distribution(env, &distribution_id).ok_or(ContractError::NotFound)?;
+        if env.ledger().timestamp() < distribution.timestamp + CLAIM_WINDOW {
+            return Err(ContractError::TooEarly);
+        }
+
+        let amount = Self::settle_claim(env, &mut data, &investor, &distribution_id);
+        if amount == 0 {
+            return Err(ContractError::Nothing);
+        }
+
+        Self::pay_out(env, &data, &beneficiary, amount)?;
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        env.events().publish((symbol_short!("fallback"), distribution_id), (investor, beneficiary, amount));
+
+        Ok(amount)
+    }
+
+    /// Emit reminders for a distribution's lines still unclaimed past the soft deadline. The
+    /// level rises each reminder interval and each level is sent once per line. Anyone can
+    /// call this; returns the number of reminders sent
+    pub fn remind_stale_claims(env: &Env, distribution_id: Symbol) -> Result<u32, ContractError> {
+        let distribution = Self::load_distribution(env, &distribution_id).ok_or(ContractError::NotFound)?;
+        if Self::is_frozen(env, &distribution.id) {
+            return Err(ContractError::Frozen);
+        }
+
+        let now = env.ledger().timestamp();
+        let soft_deadline = distribution.timestamp + CLAIM_SOFT_DEADLINE;
+        if now < soft_deadline {
+            return Err(ContractError::TooEarly);
+        }
+        let level = ((now - soft_deadline) / REMINDER_INTERVAL + 1) as u32;
+        let hard_deadline = distribution.timestamp + CLAIM_WINDOW;
+
+        let mut sent = 0;
+        for line in distribution.distributions.iter() {
+            if line.total_amount == 0 || distribution.claimed.get(line.investor.clone()).unwrap_or(false) {
+                continue;
+            }
+
+            let key = StorageKey::Reminder(distribution_id.clone(), line.investor.clone());
+            if env.storage().persistent().get::<_, u32>(&key).unwrap_or(0) >= level {
+                continue;
+            }
+            ttl::set_persistent(env, &key, &level);
+
+            env.events().publish(
+                (symbol_short!("remind"), line.investor.clone()),
+                (distribution_id.clone(), line.total_amount, level, hard_deadline),
+            );
+            sent += 1;
+        }
+
+        Ok(sent)
+    }
+
+    /// Freeze a distribution once every line is claimed or its claim window has passed,
+    /// storing a hash of its line items. Anyone can call this.
+    pub fn finalize_distribution(env: &Env, distribution_id: Symbol) -> Result<BytesN<32>, ContractError> {
+        let distribution = Self::load_distribution(env, &distribution_id).ok_or(ContractError::NotFound)?;
+
+        if Self::is_frozen(env, &distribution.id) {
+            return Err(ContractError::Frozen);
+        }
+
+        let fully_claimed = distribution
+            .distributions
+            .iter()
+            .all(|line| line.total_amount == 0 || distribution.claimed.get(line.investor.clone()).unwrap_or(false));
+        // Unclaimed lines with a fallback beneficiary hold the freeze until the beneficiary window ends
+        let has_fallback = distribution.distributions.iter().any(|line| {
+            line.total_amount > 0
+                && !distribution.claimed.get(line.investor.clone()).unwrap_or(false)
+                && env.storage().persistent().has(&StorageKey::Beneficiary(line.investor.clone()))
+        });
+        let window = if has_fallback { CLAIM_WINDOW + BENEFICIARY_WINDOW } else { CLAIM_WINDOW };
+        let window_closed = env.ledger().timestamp() >= distribution.timestamp + window;
+        if !fully_claimed && !window_closed {
+            return Err(ContractError::Claimable);
+        }
+
+        // Lines left unclaimed past the window stop being claimable
+        for line in distribution.distributions.iter() {
+            Self::take_claimable(env, &line.investor, &distribution_id);
+        }
+
+        let hash = Self::distribution_hash(env, &distribution);
+        ttl::set_persistent(env, &StorageKey::IntegrityHash(distribution_id.clone()), &hash);
+        changes::touch(env, symbol_short!("dist"), distribution_id.clone());
+        env.events().publish((symbol_short!("frozen"), distribution_id), hash.clone());
+
+        Ok(hash)
+    }
+
+    /// Check that a distribution is frozen, matches the given hash, and still hashes to it
+    pub fn verify_distribution(env: &Env, distribution_id: Symbol, hash: BytesN<32>) -> Result<bool, ContractError> {
+        let distribution = Self::load_distribution(env, &distribution_id).ok_or(ContractError::NotFound)?;
+
+        let stored: BytesN<32> = env
+            .storage()
+            .persistent()
+            .get(&StorageKey::IntegrityHash(distribution_id))
+            .ok_or(ContractError::InvalidStatus)?;
+
+        Ok(stored == hash && Self::distribution_hash(env, &distribution) == hash)
+    }
+
+    /// Whether a distribution has been frozen by `finalize_distribution`
+    fn is_frozen(env: &Env, distribution_id: &Symbol) -> bool {
+        env.storage().persistent().has(&StorageKey::IntegrityHash(distribution_id.clone()))
+    }
+
+    /// Hash of a distribution's amounts and line items
+    fn distribution_hash(env: &Env, distribution: &RevenueDistribution) -> BytesN<32> {
+        let payload = (
+            distribution.id.clone(),
+            distribution.asset_id.clone(),
+            distribution.epoch,
+            distribution.total_revenue,
+            distribution.distribution_amount,
+            distribution.equity_bonus_pool,
+            distribution.treasury_fee,
+            distribution.platform_fee,
+            distribution.emergency_topup,
+            distribution.distributions.clone(),
+        );
+        env.crypto().sha256(&payload.to_xdr(env))
+    }
+
+    /// Mark an investor's line in a distribution as claimed and return its amount
+    fn settle_claim(env: &Env, data: &mut DataKey, investor: &Address, distribution_id: &Symbol) -> i128 {
+        let mut distribution = match Self::load_distribution(env, distribution_id) {
+            Some(distribution) => distribution,
+            None => return 0,
+        };
+
+        if Self::is_frozen(env, &distribution.id) || distribution.claimed.get(investor.clone()).unwrap_or(false) {
+            return 0;
+        }
+
+        let mut amount = Self::take_claimable(env, investor, distribution_id);
+        if amount == 0 {
+            // Distributions recorded before claimable balances were allocated
+            for line in distribution.distributions.iter() {
+                if line.investor == *investor {
+                    amount += line.total_amount;
+                }
+            }
+        }
+
+        if amount == 0 {
+            return 0;
+        }
+
+        distribution.claimed.set(investor.clone(), true);
+        Self::save_distribution(env, &distribution);
+        changes::touch(env, symbol_short!("dist"), distribution_id.clone());
+        env.events().publish((symbol_short!("claimed"), distribution_id.clone()), (investor.clone(), amount));
+
+        let claimed = data.claimed_totals.get(investor.clone()).unwrap_or(0);
+        data.claimed_totals.set(investor.clone(), claimed + amount);
+
+        amount
+    }
+
+    /// Transfer funds the distributor holds to a recipient in the settlement asset, failing with
+    /// NoFunds instead of overdrawing when its token balance can't cover the amount
+    fn pay_out(env: &Env, data: &DataKey, recipient: &Address, amount: i128) -> Result<(), ContractError> {
+        let token = LoanPoolClient::new(env, &data.loan_pool)
+            .get_settlement_asset()
+            .ok_or(ContractError::NoSettlementAsset)?;
+        let token = TokenClient::new(env, &token);
+        if token.balance(&env.current_contract_address()) < amount {
+            return Err(ContractError::NoFunds);
+        }
+        token.transfer(&env.current_contract_address(), recipient, &amount);
+        Ok(())
+    }
+
+    /// Add to an investor's claimable balance in a distribution and their running total
+    fn add_claimable(env: &Env, investor: &Address, distribution_id: &Symbol, amount: i128) {
+        if amount <= 0 {
+            return;
+        }
+        let key = StorageKey::Claimable(investor.clone(), distribution_id.clone());
+        let balance: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        ttl::set_persistent(env, &key, &(balance + amount));
+
+        let total_key = StorageKey::ClaimableTotal(investor.clone());
+        let total: i128 = env.storage().persistent().get(&total_key).unwrap_or(0);
+        ttl::set_persistent(env, &total_key, &(total + amount));
+    }
+
+    /// Remove an investor's claimable balance in a distribution, returning it
+    fn take_claimable(env: &Env, investor: &Address, distribution_id: &Symbol) -> i128 {
+        let key = StorageKey::Claimable(investor.clone(), distribution_id.clone());
+        let amount: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        if amount == 0 {
+            return 0;
+        }
+        env.storage().persistent().remove(&key);
+
+        let total_key = StorageKey::ClaimableTotal(investor.clone());
+        let total: i128 = env.storage().persistent().get(&total_key).unwrap_or(0);
+        ttl::set_persistent(env, &total_key, &(total - amount));
+
+        amount
+    }
+
+    /// Select line items of a distribution for mandatory audit (admin only).
+    /// Items are drawn without replacement using the ledger PRNG, weighted by payout size.
+    pub fn sample_distribution_audits(
+        env: &Env,
+        caller: Address,
+        distribution_id: Symbol,
+        sample_count: u32,
+    ) -> Result<Vec<AuditSample>, ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        // Only admin can trigger sampling
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+
+        let distribution = Self::load_distribution(env, &distribution_id).ok_or(ContractError::NotFound)?;
+
+        if Self::is_frozen(env, &distribution.id) {
+            return Err(ContractError::Frozen);
+        }
+
+        if data.audit_samples.contains_key(distribution_id.clone()) {
+            return Err(ContractError::Done);
+        }
+
+        if distribution.fee_released {
+            return Err(ContractError::Done);
+        }
+
+        // The treasury fee waits on this sample, so a distribution with lines can't skip its audit
+        if sample_count == 0 && !distribution.distributions.is_empty() {
+            return Err(ContractError::InvalidInput);
+        }
+
+        // Weight each line item by its payout (minimum weight of 1)
+        let mut weights: Vec<i128> = vec![env];
+        for line in distribution.distributions.iter() {
+            weights.push_back(if line.total_amount > 0 { line.total_amount } else { 1 });
+        }
+
+        let mut samples = vec![env];
+        let mut remaining = if sample_count > weights.len() { weights.len() } else { sample_count };
+
+        while remaining > 0 {
+            let mut total_weight: i128 = 0;
+            for weight in weights.iter() {
+                total_weight += weight;
+            }
+
+            // Walk the cumulative weights to the drawn point
+            let draw = env.prng().gen_range::<u64>(0..total_weight as u64) as i128;
+            let mut cumulative: i128 = 0;
+            for i in 0..weights.len() {
+                let weight = weights.get(i).unwrap();
+                if weight == 0 {
+                    continue;
+                }
+                cumulative += weight;
+                if draw < cumulative {
+                    let line = distribution.distributions.get(i).unwrap();
+                    samples.push_back(AuditSample {
+                        distribution_id: distribution_id.clone(),
+                        line_index: i,
+                        investor: line.investor.clone(),
+                        amount: line.total_amount,
+                        sampled_ledger: env.ledger().sequence(),
+                        attested: false,
+                    });
+                    // Sample without replacement
+                    weights.set(i, 0);
+                    break;
+                }
+            }
+
+            remaining -= 1;
+        }
+
+        data.audit_samples.set(distribution_id, samples.clone());
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        Ok(samples)
+    }
+
+    /// Post an audit attestation for a sampled line item (auditor only)
+    pub fn post_audit_attestation(
+        env: &Env,
+        distribution_id: Symbol,
+        line_index: u32,
+        attestation: BytesN<32>,
+    ) -> Result<(), ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        // Only the auditor can attest
+        data.auditor.require_auth();
+
+        let mut samples = data.audit_samples.get(distribution_id.clone()).ok_or(ContractError::NotFound)?;
+
+        for i in 0..samples.len() {
+            let mut sample = samples.get(i).unwrap();
+            if sample.line_index == line_index {
+                if sample.attested {
+                    return Err(ContractError::Done);
+                }
+                sample.attested = true;
+                samples.set(i, sample);
+                ttl::set_persistent(env, &StorageKey::Attestation(distribution_id.clone(), line_index), &attestation);
+                data.audit_samples.set(distribution_id, samples);
+                ttl::set_instance(env, &DATA_KEY, &data);
+                return Ok(());
+            }
+        }
+
+        Err(ContractError::InvalidStatus)
+    }
+
+    /// Release a distribution's treasury fee once it has been sampled for audit and every sampled
+    /// item is attested (treasurer only)
+    pub fn release_treasury_fee(env: &Env, caller: Address, distribution_id: Symbol) -> Result<i128, ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        // Only treasurers can release fees
+        access::check(env, &data.admin, Role::Treasurer, &caller)?;
+
+        let mut distribution = Self::load_distribution(env, &distribution_id).ok_or(ContractError::NotFound)?;
+
+        if Self::is_frozen(env, &distribution.id) {
+            return Err(ContractError::Frozen);
+        }
+
+        if distribution.fee_released {
+            return Err(ContractError::Done);
+        }
+
+        // Fees stay blocked until the distribution is sampled and all sampled items carry an attestation
+        let samples = data.audit_samples.get(distribution_id.clone()).ok_or(ContractError::AuditDue)?;
+        for sample in samples.iter() {
+            if !sample.attested {
+                return Err(ContractError::AuditDue);
+            }
+        }
+
+        Self::consume_approval(
+            env,
+            &mut data,
+            symbol_short!("spend"),
+            distribution_id.clone(),
+            None,
+            distribution.treasury_fee,
+        )?;
+
+        distribution.fee_released = true;
+        Self::save_distribution(env, &distribution);
+        changes::touch(env, symbol_short!("dist"), distribution_id.clone());
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        Ok(distribution.treasury_fee)
+    }
+
+    /// Set the amount above which disbursements need approvals, and who can give them (admin only)
+    pub fn set_approval_rule(env: &Env, caller: Address, rule: ApprovalRule) -> Result<(), ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+
+        if !rule.is_valid() {
+            return Err(ContractError::InvalidInput);
+        }
+
+        data.approval_rule = rule;
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        Ok(())
+    }
+
+    /// Open a pending action for a large disbursement ("dist" on an asset or "spend" on a distribution's treasury fee), counting the proposer's approval
+    pub fn propose_action(
+        env: &Env,
+        approver: Address,
+        kind: Symbol,
+        reference: Symbol,
+        recipient: Option<Address>,
+        amount: i128,
+    ) -> Result<u32, ContractError> {
+        approver.require_auth();
+
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        if kind != symbol_short!("dist") && kind != symbol_short!("spend") {
+            return Err(ContractError::InvalidInput);
+        }
+
+        if amount <= 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        let action_id = data.next_action_id;
+        let now = env.ledger().timestamp();
+        let mut action = PendingAction {
+            id: action_id,
+            kind: kind.clone(),
+            reference,
+            recipient: recipient.into(),
+            amount,
+            approvals: vec![env],
+            created_at: now,
+        };
+        action.approve(&data.approval_rule, &approver, now)?;
+
+        data.pending_actions.set(action_id, action);
+        data.next_action_id += 1;
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        env.events().publish((symbol_short!("act_new"), action_id), (kind, amount));
+
+        Ok(action_id)
+    }
+
+    /// Approve a pending action and return its approval count
+    pub fn approve_action(env: &Env, approver: Address, action_id: u32) -> Result<u32, ContractError> {
+        approver.require_auth();
+
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        let mut action = data.pending_actions.get(action_id).ok_or(ContractError::NotFound)?;
+
+        let approvals = action.approve(&data.approval_rule, &approver, env.ledger().timestamp())?;
+        data.pending_actions.set(action_id, action);
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        env.events().publish((symbol_short!("act_appr"), action_id), approver);
+
+        Ok(approvals)
+    }
+
+    /// Drop pending actions whose approval window has passed. Anyone can call this.
+    pub fn prune_expired_actions(env: &Env) -> u32 {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        let now = env.ledger().timestamp();
+
+        let mut expired = vec![env];
+        for (action_id, action) in data.pending_actions.iter() {
+            if action.is_expired(now) {
+                expired.push_back(action_id);
+            }
+        }
+        for action_id in expired.iter() {
+            data.pending_actions.remove(action_id);
+        }
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        expired.len()
+    }
+
+    /// Get a pending action
+    pub fn get_pending_action(env: &Env, action_id: u32) -> Result<PendingAction, ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        data.pending_actions.get(action_id).ok_or(ContractError::NotFound)
+    }
+
+    /// Get the large-disbursement approval rule
+    pub fn get_approval_rule(env: &Env) -> ApprovalRule {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        data.approval_rule
+    }
+
+    /// Assign the guardian who can request emergency payouts for a program (admin only)
+    pub fn set_emergency_guardian(env: &Env, caller: Address, program: Symbol, guardian: Address) -> Result<(), ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+
+        data.emergency_guardians.set(program, guardian);
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        Ok(())
+    }
+
+    /// Request a payout from a program's emergency fund; governance approves it with a short vote
+    pub fn request_emergency_payout(
+        env: &Env,
+        guardian: Address,
+        program: Symbol,
+        recipient: Address,
+        amount: i128,
+        reason: Symbol,
+    ) -> Result<u32, ContractError> {
+        guardian.require_auth();
+
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        if data.emergency_guardians.get(program.clone()) != Some(guardian.clone()) {
+            return Err(ContractError::Unauthorized);
+        }
+
+        if amount <= 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        if data.emergency_funds.get(program.clone()).unwrap_or(0) < amount {
+            return Err(ContractError::NoFunds);
+        }
+
+        let request_id = data.next_emergency_id;
+        data.next_emergency_id += 1;
+
+        let request = EmergencyRequest {
+            id: request_id,
+            program: program.clone(),
+            guardian,
+            recipient,
+            amount,
+            reason,
+            requested_at: env.ledger().timestamp(),
+            status: symbol_short!("pending"),
+        };
+        data.emergency_requests.set(request_id, request);
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        env.events().publish((symbol_short!("emg_req"), program), (request_id, amount));
+
+        Ok(request_id)
+    }
+
+    /// Pay out a pending emergency request in the settlement asset once governance has voted for it (governance only)
+    pub fn approve_emergency_payout(env: &Env, request_id: u32) -> Result<i128, ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        Self::governance(env).ok_or(ContractError::NoAddress)?.require_auth();
+
+        let mut request = data.emergency_requests.get(request_id).ok_or(ContractError::NotFound)?;
+
+        if request.status != symbol_short!("pending") {
+            return Err(ContractError::InvalidStatus);
+        }
+
+        if env.ledger().timestamp() > request.requested_at + EMERGENCY_REQUEST_TTL {
+            request.status = symbol_short!("expired");
+            data.emergency_requests.set(request_id, request);
+            ttl::set_instance(env, &DATA_KEY, &data);
+            return Err(ContractError::Expired);
+        }
+
+        let balance = data.emergency_funds.get(request.program.clone()).unwrap_or(0);
+        if balance < request.amount {
+            return Err(ContractError::NoFunds);
+        }
+
+        Self::pay_out(env, &data, &request.recipient, request.amount)?;
+        data.emergency_funds.set(request.program.clone(), balance - request.amount);
+        request.status = symbol_short!("paid");
+        data.emergency_requests.set(request_id, request.clone());
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        env.events().publish(
+            (symbol_short!("emg_paid"), request.program.clone()),
+            (request_id, request.recipient.clone(), request.amount),
+        );
+
+        Ok(request.amount)
+    }
+
+    /// Queue new bounds investor referenda on asset distribution policy must stay within behind
+    /// the admin timelock (governance only)
+    pub fn set_policy_bounds(env: &Env, max_reserve_bps: u32, allow_reinvest: bool) -> Result<u32, ContractError> {
+        Self::governance(env).ok_or(ContractError::NoAddress)?.require_auth();
+
+        let value = ChangeValue::Encoded(PolicyBounds { max_reserve_bps, allow_reinvest }.to_xdr(env));
+        timelock::queue(env, symbol_short!("pol_bound"), value, timelock::MIN_DELAY, timelock::MIN_DELAY, |change| {
+            Self::validate_admin_change(env, change)
+        })
+    }
+
+    /// Queue a guarantee that investors below `share_threshold_bps` of an asset get at least
+    /// `min_payout` per distribution, funded from the equity bonus pool, behind the admin timelock.
+    /// A zero payout turns the floor off (governance only)
+    pub fn set_min_payout(env: &Env, share_threshold_bps: u32, min_payout: i128) -> Result<u32, ContractError> {
+        Self::governance(env).ok_or(ContractError::NoAddress)?.require_auth();
+
+        let value = ChangeValue::Encoded(MinPayout { share_threshold_bps, min_payout }.to_xdr(env));
+        timelock::queue(env, symbol_short!("min_pay"), value, timelock::MIN_DELAY, timelock::MIN_DELAY, |change| {
+            Self::validate_admin_change(env, change)
+        })
+    }
+
+    /// Get the micro-investor payout floor
+    pub fn get_min_payout(env: &Env) -> MinPayout {
+        Self::min_payout(env)
+    }
+
+    fn min_payout(env: &Env) -> MinPayout {
+        env.storage().persistent().get(&StorageKey::MinPayout).unwrap_or_default()
+    }
+
+    /// Open a referendum among an asset's investors on its maintenance reserve and whether to
+    /// reinvest the next payout. The proposer must hold a position in the asset
+    pub fn open_policy_referendum(
+        env: &Env,
+        proposer: Address,
+        asset_id: Symbol,
+        reserve_bps: u32,
+        reinvest: bool,
+    ) -> Result<u32, ContractError> {
+        proposer.require_auth();
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        Gate::new(data.paused, &data.disabled_features, &symbol_short!("policy")).check()?;
+
+        if !Self::policy_bounds(env).allows(reserve_bps, reinvest) {
+            return Err(ContractError::OutOfBand);
+        }
+        if Self::policy_weight(env, &data, &proposer, &asset_id) <= 0 {
+            return Err(ContractError::NotEligible);
+        }
+
+        let referendum_id = env.storage().persistent().get(&StorageKey::ReferendumCount).unwrap_or(0u32) + 1;
+        ttl::set_persistent(env, &StorageKey::ReferendumCount, &referendum_id);
+
+        let referendum = PolicyReferendum {
+            id: referendum_id,
+            asset_id: asset_id.clone(),
+            proposer,
+            reserve_bps,
+            reinvest,
+            yes_weight: 0,
+            no_weight: 0,
+            ends_at: env.ledger().timestamp() + POLICY_VOTE_DURATION,
+            status: symbol_short!("open"),
+        };
+        ttl::set_persistent(env, &StorageKey::Referendum(referendum_id), &referendum);
+
+        env.events().publish((symbol_short!("pol_open"), asset_id), (referendum_id, reserve_bps, reinvest));
+
+        Ok(referendum_id)
+    }
+
+    /// Vote on an asset's policy referendum, weighted by the voter's open position in the asset.
+    /// Returns the weight cast
+    pub fn vote_policy_referendum(env: &Env, voter: Address, referendum_id: u32, support: bool) -> Result<i128, ContractError> {
+        voter.require_auth();
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        Gate::new(data.paused, &data.disabled_features, &symbol_short!("policy")).check()?;
+
+        let mut referendum = Self::load_referendum(env, referendum_id)?;
+        if referendum.status != symbol_short!("open") || env.ledger().timestamp() >= referendum.ends_at {
+            return Err(ContractError::Expired);
+        }
+
+        let vote_key = StorageKey::ReferendumVote(referendum_id, voter.clone());
+        if env.storage().persistent().has(&vote_key) {
+            return Err(ContractError::AlreadyVoted);
+        }
+
+        let weight = Self::policy_weight(env, &data, &voter, &referendum.asset_id);
+        if weight <= 0 {
+            return Err(ContractError::NotEligible);
+        }
+
+        if support {
+            referendum.yes_weight += weight;
+        } else {
+            referendum.no_weight += weight;
+        }
+        ttl::set_persistent(env, &vote_key, &weight);
+        ttl::set_persistent(env, &StorageKey::Referendum(referendum_id), &referendum);
+
+        env.events().publish((symbol_short!("pol_vote"), referendum_id), (voter, support, weight));
+
+        Ok(weight)
+    }
+
+    /// Close a policy referendum once voting has ended; a majority of the weight cast adopts its
+    /// policy for the asset's next distributions. Anyone may call this
+    pub fn close_policy_referendum(env: &Env, referendum_id: u32) -> Result<Symbol, ContractError> {
+        let mut referendum = Self::load_referendum(env, referendum_id)?;
+        if referendum.status != symbol_short!("open") {
+            return Err(ContractError::InvalidStatus);
+        }
+        if env.ledger().timestamp() < referendum.ends_at {
+            return Err(ContractError::TooEarly);
+        }
+
+        // Governance may have tightened the bounds while the vote was open
+        let in_bounds = Self::policy_bounds(env).allows(referendum.reserve_bps, referendum.reinvest);
+        if in_bounds && referendum.yes_weight > referendum.no_weight {
+            referendum.status = symbol_short!("passed");
+            ttl::set_persistent(
+                env,
+                &StorageKey::Policy(referendum.asset_id.clone()),
+                &AssetPolicy {
+                    reserve_bps: referendum.reserve_bps,
+                    reinvest: referendum.reinvest,
+                    referendum_id,
+                },
+            );
+        } else {
+            referendum.status = symbol_short!("failed");
+        }
+        ttl::set_persistent(env, &StorageKey::Referendum(referendum_id), &referendum);
+
+        env.events().publish(
+            (symbol_short!("pol_close"), referendum.asset_id.clone()),
+            (referendum_id, referendum.status.clone()),
+        );
+
+        Ok(referendum.status)
+    }
+
+    /// Pay out of an asset's "maint" or "reinvest" policy reserve to a recipient in the settlement
+    /// asset (treasurer only)
+    pub fn release_asset_reserve(
+        env: &Env,
+        caller: Address,
+        asset_id: Symbol,
+        kind: Symbol,
+        recipient: Address,
+        amount: i128,
+    ) -> Result<i128, ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Treasurer, &caller)?;
+
+        if amount <= 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        let key = StorageKey::Reserve(asset_id.clone());
+        let mut reserve: AssetReserve = env.storage().persistent().get(&key).unwrap_or_default();
+        let balance = if kind == symbol_short!("maint") {
+            &mut reserve.maintenance
+        } else if kind == symbol_short!("reinvest") {
+            &mut reserve.reinvested
+        } else {
+            return Err(ContractError::InvalidInput);
+        };
+        if *balance < amount {
+            return Err(ContractError::NoFunds);
+        }
+        *balance -= amount;
+        Self::pay_out(env, &data, &recipient, amount)?;
+        ttl::set_persistent(env, &key, &reserve);
+        Self::adjust_reserves_total(env, -amount);
+
+        env.events().publish((symbol_short!("res_paid"), asset_id), (kind, recipient, amount));
+
+        Ok(amount)
+    }
+
+    /// Get the bounds governance set on asset distribution policies
+    pub fn get_policy_bounds(env: &Env) -> PolicyBounds {
+        Self::policy_bounds(env)
+    }
+
+    /// Get the distribution policy in force for an asset
+    pub fn get_asset_policy(env: &Env, asset_id: Symbol) -> AssetPolicy {
+        Self::asset_policy(env, &asset_id)
+    }
+
+    /// Get the revenue an asset's policy has held back
+    pub fn get_asset_reserve(env: &Env, asset_id: Symbol) -> AssetReserve {
+        env.storage().persistent().get(&StorageKey::Reserve(asset_id)).unwrap_or_default()
+    }
+
+    /// Get a policy referendum
+    pub fn get_policy_referendum(env: &Env, referendum_id: u32) -> Result<PolicyReferendum, ContractError> {
+        Self::load_referendum(env, referendum_id)
+    }
+
+    fn load_referendum(env: &Env, referendum_id: u32) -> Result<PolicyReferendum, ContractError> {
+        env.storage()
+            .persistent()
+            .get(&StorageKey::Referendum(referendum_id))
+            .ok_or(ContractError::NotFound)
+    }
+
+    fn policy_bounds(env: &Env) -> PolicyBounds {
+        env.storage().persistent().get(&StorageKey::PolicyBounds).unwrap_or_default()
+    }
+
+    fn adjust_reserves_total(env: &Env, delta: i128) {
+        let total: i128 = env.storage().persistent().get(&StorageKey::ReservesTotal).unwrap_or(0);
+        ttl::set_persistent(env, &StorageKey::ReservesTotal, &(total + delta));
+    }
+
+    fn asset_policy(env: &Env, asset_id: &Symbol) -> AssetPolicy {
+        env.storage()
+            .persistent()
+            .get(&StorageKey::Policy(asset_id.clone()))
+            .unwrap_or(AssetPolicy { reserve_bps: 0, reinvest: false, referendum_id: 0 })
+    }
+
+    /// An investor's open position in an asset: capital invested less principal redeemed
+    fn policy_weight(env: &Env, data: &DataKey, investor: &Address, asset_id: &Symbol) -> i128 {
+        let (invested, _, _, redeemed_amount) =
+            LoanPoolClient::new(env, &data.loan_pool).get_investor_position(investor, asset_id, &0);
+        (invested - redeemed_amount).max(0)
+    }
+
+    /// Hold back the asset's voted maintenance reserve and, if its investors voted to reinvest,
+    /// the rest of this payout. Returns the revenue left to distribute
+    fn apply_asset_policy(env: &Env, asset_id: &Symbol, net_revenue: i128) -> i128 {
+        let mut policy = Self::asset_policy(env, asset_id);
+        let maintenance = net_revenue * policy.reserve_bps as i128 / 10_000;
+        let reinvested = if policy.reinvest { net_revenue - maintenance } else { 0 };
+        if maintenance == 0 && reinvested == 0 {
+            return net_revenue;
+        }
+
+        let key = StorageKey::Reserve(asset_id.clone());
+        let mut reserve: AssetReserve = env.storage().persistent().get(&key).unwrap_or_default();
+        reserve.maintenance += maintenance;
+        reserve.reinvested += reinvested;
+        ttl::set_persistent(env, &key, &reserve);
+        Self::adjust_reserves_total(env, maintenance + reinvested);
+
+        // A reinvest vote covers one payout
+        if policy.reinvest {
+            policy.reinvest = false;
+            ttl::set_persistent(env, &StorageKey::Policy(asset_id.clone()), &policy);
+        }
+
+        env.events().publish((symbol_short!("policy"), asset_id.clone()), (maintenance, reinvested));
+
+        net_revenue - maintenance - reinvested
+    }
+
+    /// Draw an advance against an asset's next distributions as its registered operator, paid out
+    /// of the distributor's balance in the settlement asset. Returns the amount owed, which is
+    /// recovered from later distributions before investor payouts
+    pub fn draw_advance(env: &Env, operator: Address, asset_id: Symbol, amount: i128) -> Result<i128, ContractError> {
+        operator.require_auth();
+
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        Gate::new(data.paused, &data.disabled_features, &symbol_short!("advance")).check()?;
+
+        if data.asset_operators.get(asset_id.clone()) != Some(operator.clone()) {
+            return Err(ContractError::Unauthorized);
+        }
+
+        if amount <= 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        if data.advances.contains_key(asset_id.clone()) {
+            return Err(ContractError::AdvanceOpen);
+        }
+
+        if amount > Self::advance_limit(env, &data, &asset_id) {
+            return Err(ContractError::OverLimit);
+        }
+
+        let fee = amount * data.advance_fee_bps as i128 / 10_000;
+        let advance = RevenueAdvance {
+            asset_id: asset_id.clone(),
+            operator: operator.clone(),
+            principal: amount,
+            fee,
+            outstanding: amount + fee,
+            drawn_at: env.ledger().timestamp(),
+            drawn_epoch: data.asset_epochs.get(asset_id.clone()).unwrap_or(0),
+        };
+        Self::pay_out(env, &data, &operator, amount)?;
+        data.advances.set(asset_id.clone(), advance.clone());
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        env.events().publish((symbol_short!("adv_draw"), asset_id), (operator, amount, fee));
+
+        Ok(advance.outstanding)
+    }
+
+    /// Largest advance an asset's operator can draw right now
+    pub fn get_advance_limit(env: &Env, asset_id: Symbol) -> i128 {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        if data.advances.contains_key(asset_id.clone()) {
+            return 0;
+        }
+        Self::advance_limit(env, &data, &asset_id)
+    }
+
+    /// Get the advance being repaid from an asset's distributions
+    pub fn get_advance(env: &Env, asset_id: Symbol) -> Result<RevenueAdvance, ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        data.advances.get(asset_id).ok_or(ContractError::NotFound)
+    }
+
+    /// Queue a new advance cap (share of trailing average revenue) and fee behind the admin timelock (admin only)
+    pub fn set_advance_terms(env: &Env, caller: Address, cap_bps: u32, fee_bps: u32) -> Result<u32, ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+
+        let value = ChangeValue::Encoded((cap_bps, fee_bps).to_xdr(env));
+        timelock::queue(env, symbol_short!("adv_terms"), value, timelock::MIN_DELAY, timelock::MIN_DELAY, |change| {
+            Self::validate_admin_change(env, change)
+        })
+    }
+
+    /// Cap on an asset's advance from the average revenue of its trailing distributions
+    fn advance_limit(env: &Env, data: &DataKey, asset_id: &Symbol) -> i128 {
+        let latest = data.asset_epochs.get(asset_id.clone()).unwrap_or(0);
+        let mut total: i128 = 0;
+        let mut count: i128 = 0;
+
+        let mut epoch = latest;
+        while epoch > 0 && latest - epoch < ADVANCE_TRAILING_EPOCHS {
+            if let Some(id) = data.epoch_index.get((asset_id.clone(), epoch)) {
+                total += Self::load_distribution(env, &id).unwrap().total_revenue;
+                count += 1;
+            }
+            epoch -= 1;
+        }
+
+        if count == 0 {
+            return 0;
+        }
+
+        total / count * data.advance_cap_bps as i128 / 10_000
+    }
+
+    /// Take as much of an asset's outstanding advance as the available revenue covers
+    fn repay_advance(env: &Env, data: &mut DataKey, asset_id: &Symbol, available: i128) -> i128 {
+        let mut advance = match data.advances.get(asset_id.clone()) {
+            Some(advance) => advance,
+            None => return 0,
+        };
+
+        let repaid = if advance.outstanding < available { advance.outstanding } else { available };
+        if repaid <= 0 {
+            return 0;
+        }
+
+        advance.outstanding -= repaid;
+        if advance.outstanding == 0 {
+            data.advances.remove(asset_id.clone());
+        } else {
+            data.advances.set(asset_id.clone(), advance.clone());
+        }
+
+        env.events().publish((symbol_short!("adv_repay"), asset_id.clone()), (repaid, advance.outstanding));
+
+        repaid
+    }
+
+    /// Get a program's emergency fund balance
+    pub fn get_emergency_fund(env: &Env, program: Symbol) -> i128 {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        data.emergency_funds.get(program).unwrap_or(0)
+    }
+
+    /// Get an emergency payout request
+    pub fn get_emergency_request(env: &Env, request_id: u32) -> Result<EmergencyRequest, ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        data.emergency_requests.get(request_id).ok_or(ContractError::NotFound)
+    }
+
+    /// Get the audit report hash posted for a sampled line item
+    pub fn get_audit_attestation(env: &Env, distribution_id: Symbol, line_index: u32) -> Option<BytesN<32>> {
+        env.storage().persistent().get(&StorageKey::Attestation(distribution_id, line_index))
+    }
+
+    /// Get a page of the audit samples drawn for a distribution
+    pub fn get_audit_samples(env: &Env, distribution_id: Symbol, page: PageRequest) -> AuditSamplePage {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        let samples = data.audit_samples.get(distribution_id).unwrap_or(vec![env]);
+        let (items, next_cursor) = page.collect(env, &samples, |sample| sample);
+        AuditSamplePage { items, next_cursor }
+    }
+
+    /// Get an investor's realized annualized yield on an asset over the trailing 12 months,
+    /// in basis points: (distributions + redemption gains) / time-weighted capital
+    pub fn get_investor_yield(env: &Env, investor: Address, asset_id: Symbol) -> i128 {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        let now = env.ledger().timestamp();
+        let since = now.saturating_sub(SECONDS_PER_YEAR);
+
+        // Revenue distributed to the investor within the window
+        let mut distributed: i128 = 0;
+        for id in Self::distribution_ids(env).iter() {
+            let distribution = Self::load_distribution(env, &id).unwrap();
+            if distribution.asset_id != asset_id || distribution.timestamp < since {
+                continue;
+            }
+            for line in distribution.distributions.iter() {
+                if line.investor == investor {
+                    distributed += line.total_amount;
+                }
+            }
+        }
+
+        // Principal position from the loan pool
+        let (_, capital_seconds, redeemed_units, redeemed_amount) =
+            LoanPoolClient::new(env, &data.loan_pool).get_investor_position(&investor, &asset_id, &since);
+
+        if capital_seconds <= 0 {
+            return 0;
+        }
+
+        // Units are issued at par, so redemption gain is amount paid above units redeemed
+        let realized = distributed + redeemed_amount - redeemed_units;
+        realized * 10_000 * SECONDS_PER_YEAR as i128 / capital_seconds
+    }
+
+    /// Export an investor's taxable events for a calendar year in the schedule's timezone, paginated by cursor
+    pub fn get_tax_summary(env: &Env, investor: Address, year: u32, cursor: u32, limit: u32) -> Result<TaxSummary, ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        if year < 1970 || limit == 0 {
+            return Err(ContractError::InvalidInput);
+        }
+
+        let schedule = Self::epoch_schedule(env);
+        let from = schedule.year_start(year);
+        let to = schedule.year_start(year + 1);
+
+        let mut summary = TaxSummary {
+            investor: investor.clone(),
+            year,
+            total_distributions: 0,
+            total_fees: 0,
+            total_principal_returned: 0,
+            total_writeoffs: 0,
+            events: vec![env],
+            next_cursor: 0,
+        };
+        let mut all_events: Vec<TaxEvent> = vec![env];
+
+        // Distributions and the investor's share of their platform fees
+        for distribution_id in data.distribution_order.iter() {
+            let distribution = Self::load_distribution(env, &distribution_id).unwrap();
+            if distribution.timestamp < from || distribution.timestamp >= to {
+                continue;
+            }
+            let net_revenue = distribution.total_revenue - distribution.platform_fee;
+            for line in distribution.distributions.iter() {
+                if line.investor != investor {
+                    continue;
+                }
+                summary.total_distributions += line.total_amount;
+                all_events.push_back(TaxEvent {
+                    kind: symbol_short!("dist"),
+                    asset_id: distribution.asset_id.clone(),
+                    amount: line.total_amount,
+                    timestamp: distribution.timestamp,
+                });
+
+                if distribution.platform_fee > 0 && net_revenue > 0 {
+                    let fee = distribution.platform_fee * line.total_amount / net_revenue;
+                    summary.total_fees += fee;
+                    all_events.push_back(TaxEvent {
+                        kind: symbol_short!("fee"),
+                        asset_id: distribution.asset_id.clone(),
+                        amount: fee,
+                        timestamp: distribution.timestamp,
+                    });
+                }
+            }
+        }
+
+        // Principal returns and write-offs from the loan pool
+        let principal_events =
+            LoanPoolClient::new(env, &data.loan_pool).get_principal_events(&investor, &from, &to);
+        for (kind, asset_id, amount, timestamp) in principal_events.iter() {
+            if kind == symbol_short!("writeoff") {
+                summary.total_writeoffs += amount;
+            } else {
+                summary.total_principal_returned += amount;
+            }
+            all_events.push_back(TaxEvent { kind, asset_id, amount, timestamp });
+        }
+
+        let end = (cursor + limit).min(all_events.len());
+        for i in cursor..end {
+            summary.events.push_back(all_events.get(i).unwrap());
+        }
+        if end < all_events.len() {
+            summary.next_cursor = end;
+        }
+
+        Ok(summary)
+    }
+
+    /// Get a page of distributions created or modified after a ledger with their last-modified ledger,
+    /// in ledger order, so indexers can sync incrementally. Supported kinds: "dist"
+    pub fn get_changes_since(env: &Env, ledger_seq: u32, kind: Symbol, page: PageRequest) -> Result<RecordChangePage, ContractError> {
+        if kind != symbol_short!("dist") {
+            return Err(ContractError::InvalidInput);
+        }
+        let (items, next_cursor) = changes::changes_since(env, kind, ledger_seq, &page, |id, ledger| RecordChange { id, ledger });
+        Ok(RecordChangePage { items, next_cursor })
+    }
+
+    /// Load a distribution from its own entry
+    fn load_distribution(env: &Env, distribution_id: &Symbol) -> Option<RevenueDistribution> {
+        env.storage().persistent().get(&StorageKey::Distribution(distribution_id.clone()))
+    }
+
+    /// Write a distribution, indexing it the first time it is written
+    fn save_distribution(env: &Env, distribution: &RevenueDistribution) {
+        let key = StorageKey::Distribution(distribution.id.clone());
+        if !env.storage().persistent().has(&key) {
+            let mut ids = Self::distribution_ids(env);
+            ids.push_back(distribution.id.clone());
+            ttl::set_persistent(env, &StorageKey::DistributionIds, &ids);
+        }
+        ttl::set_persistent(env, &key, distribution);
+    }
+
+    /// Every distribution id, in recording order
+    fn distribution_ids(env: &Env) -> Vec<Symbol> {
+        env.storage().persistent().get(&StorageKey::DistributionIds).unwrap_or(vec![env])
+    }
+
+    /// Get distribution details
+    pub fn get_distribution(env: &Env, distribution_id: Symbol) -> Result<RevenueDistribution, ContractError> {
+        Self::load_distribution(env, &distribution_id).ok_or(ContractError::NotFound)
+    }
+
+    /// Get revenue data for an asset
+    pub fn get_revenue(env: &Env, asset_id: Symbol) -> Result<RideRevenue, ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        data.ride_revenues.get(asset_id).ok_or(ContractError::NotFound)
+    }
+
+    /// Get all distributions for an asset
+    pub fn get_asset_distributions(env: &Env, asset_id: Symbol) -> Vec<RevenueDistribution> {
+        let mut asset_distributions = vec![env];
+        
+        for id in Self::distribution_ids(env).iter() {
+            let distribution = Self::load_distribution(env, &id).unwrap();
+            if distribution.asset_id == asset_id {
+                asset_distributions.push_back(distribution);
+            }
+        }
+        
+        asset_distributions
+    }
+
+    /// Get a page of boost audit records for an address, oldest first
+    pub fn get_boost_audit_by_address(env: &Env, subject: Address, page: PageRequest) -> BoostAuditPage {
+        let (items, next_cursor) = journal::page(env, &(BOOST_BY_SUBJECT, subject), &page);
+        BoostAuditPage { items, next_cursor }
+    }
+
+    /// Get a page of boost audit records for a rule ("eq_bonus", "min_pay"), oldest first
+    pub fn get_boost_audit_by_rule(env: &Env, rule: Symbol, page: PageRequest) -> BoostAuditPage {
+        let (items, next_cursor) = journal::page(env, &(BOOST_BY_RULE, rule), &page);
+        BoostAuditPage { items, next_cursor }
+    }
+
+    /// Append a boost audit record to its subject's and its rule's streams
+    fn log_boost(env: &Env, subject: &Address, amount: i128, rule: Symbol, data_version: u64) {
+        let record = BoostAuditRecord {
+            subject: subject.clone(),
+            amount,
+            rule: rule.clone(),
+            data_version,
+            timestamp: env.ledger().timestamp(),
+        };
+        journal::append(env, &(BOOST_BY_SUBJECT, subject.clone()), &record);
+        journal::append(env, &(BOOST_BY_RULE, rule), &record);
+    }
 
     /// Calculate total impact metrics
     pub fn get_impact_metrics(env: &Env) -> (i32, i32, i32) {
@@ -247,101 +2391,483 @@ impl RevenueDistributor {
         (total_co2_saved, total_rides, total_underserved_rides)
     }
 
-    /// Update equity bonus rate (admin only)
-    pub fn update_equity_bonus_rate(env: &Env, new_rate: i32) -> Result<(), Symbol> {
-        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
-        
+    /// Impact metrics for accessible assets only, tracked apart from the totals
+    pub fn get_accessible_impact_metrics(env: &Env) -> (i32, i32, i32) {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        let loan_pool = LoanPoolClient::new(env, &data.loan_pool);
+
+        let mut total_co2_saved = 0;
+        let mut total_rides = 0;
+        let mut total_underserved_rides = 0;
+
+        for (asset_id, revenue) in data.ride_revenues.iter() {
+            if loan_pool.get_asset_accessibility(&asset_id).is_none() {
+                continue;
+            }
+            total_co2_saved += revenue.co2_saved;
+            total_rides += revenue.ride_count;
+            total_underserved_rides += revenue.underserved_rides;
+        }
+
+        (total_co2_saved, total_rides, total_underserved_rides)
+    }
+
+    /// Queue an update to the equity bonus rate behind the admin timelock (admin only)
+    pub fn update_equity_bonus_rate(env: &Env, caller: Address, new_rate: i32, delay: u64) -> Result<u32, ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        // Only admin can update rates
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+
+        // Rate is validated (0-50%) when queued and again when executed
+        let value = ChangeValue::Number(new_rate as i128);
+        timelock::queue(env, symbol_short!("eq_rate"), value, delay, timelock::MIN_DELAY, |change| {
+            Self::validate_admin_change(env, change)
+        })
+    }
+
+    /// Queue an update to the impact bonus rate behind the admin timelock (admin only)
+    pub fn update_impact_bonus_rate(env: &Env, caller: Address, new_rate: i32, delay: u64) -> Result<u32, ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
         // Only admin can update rates
-        if env.current_contract_address() != data.admin {
-            return Err(symbol_short!("UNAUTHORIZED"));
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+
+        // Rate is validated (0-25%) when queued and again when executed
+        let value = ChangeValue::Number(new_rate as i128);
+        timelock::queue(env, symbol_short!("imp_rate"), value, delay, timelock::MIN_DELAY, |change| {
+            Self::validate_admin_change(env, change)
+        })
+    }
+
+    /// Check core accounting invariants; emits an alarm and optionally pauses on violation
+    pub fn verify_invariants(env: &Env) -> Vec<Symbol> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        let mut violations: Vec<Symbol> = vec![env];
+
+        // Investor lines plus the treasury fee can't exceed the recorded revenue
+        for id in Self::distribution_ids(env).iter() {
+            let distribution = Self::load_distribution(env, &id).unwrap();
+            let mut paid: i128 = 0;
+            for line in distribution.distributions.iter() {
+                paid += line.total_amount;
+            }
+            if paid
+                + distribution.treasury_fee
+                + distribution.platform_fee
+                + distribution.advance_repayment
+                + distribution.emergency_topup
+                > distribution.total_revenue
+            {
+                violations.push_back(symbol_short!("payouts"));
+                break;
+            }
+        }
+
+        if !violations.is_empty() {
+            env.events().publish((symbol_short!("inv_alarm"),), violations.clone());
+            if data.auto_pause && !data.paused {
+                data.paused = true;
+                ttl::set_instance(env, &DATA_KEY, &data);
+            }
         }
 
-        // Validate rate (0-50%)
-        if new_rate < 0 || new_rate > 50 {
-            return Err(symbol_short!("INVALID_RATE"));
+        violations
+    }
+
+    /// Compare the settlement-token balance against outstanding claimables, reserves and fees
+    pub fn get_reconciliation(env: &Env) -> Result<Reconciliation, ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        Self::compute_reconciliation(env, &data)
+    }
+
+    /// Reconcile balances, emitting a discrepancy event and optionally switching off
+    /// claims when they diverge beyond the tolerance. Anyone can call this.
+    pub fn reconcile(env: &Env) -> Result<Reconciliation, ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        let report = Self::compute_reconciliation(env, &data)?;
+
+        if !report.within_tolerance {
+            env.events().publish((symbol_short!("recon"),), report.discrepancy);
+            let claim = symbol_short!("claim");
+            if data.reconcile_pauses_claims && !data.disabled_features.contains(&claim) {
+                data.disabled_features.push_back(claim.clone());
+                ttl::set_instance(env, &DATA_KEY, &data);
+                env.events().publish((symbol_short!("feature"), claim), false);
+            }
         }
 
-        data.equity_bonus_rate = new_rate;
-        env.storage().instance().set(&DATA_KEY, &data);
-        
+        Ok(report)
+    }
+
+    /// Set the reconciliation tolerance and whether an alert switches off claims (admin only)
+    pub fn set_reconcile_policy(env: &Env, caller: Address, tolerance: i128, pause_claims: bool) -> Result<(), ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+
+        if tolerance < 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        data.reconcile_tolerance = tolerance;
+        data.reconcile_pauses_claims = pause_claims;
+        ttl::set_instance(env, &DATA_KEY, &data);
+
         Ok(())
     }
 
-    /// Update impact bonus rate (admin only)
-    pub fn update_impact_bonus_rate(env: &Env, new_rate: i32) -> Result<(), Symbol> {
+    fn compute_reconciliation(env: &Env, data: &DataKey) -> Result<Reconciliation, ContractError> {
+        let token = LoanPoolClient::new(env, &data.loan_pool)
+            .get_settlement_asset()
+            .ok_or(ContractError::NoSettlementAsset)?;
+        let token_balance = TokenClient::new(env, &token).balance(&env.current_contract_address());
+
+        let mut claimables: i128 = 0;
+        let mut fees: i128 = 0;
+        for id in Self::distribution_ids(env).iter() {
+            let distribution = Self::load_distribution(env, &id).unwrap();
+            if !distribution.fee_released {
+                fees += distribution.treasury_fee;
+            }
+            if Self::is_frozen(env, &distribution.id) {
+                continue;
+            }
+            for line in distribution.distributions.iter() {
+                if !distribution.claimed.get(line.investor.clone()).unwrap_or(false) {
+                    claimables += line.total_amount;
+                }
+            }
+        }
+
+        let mut reserves: i128 = 0;
+        for (_, balance) in data.emergency_funds.iter() {
+            reserves += balance;
+        }
+        reserves += env.storage().persistent().get::<_, i128>(&StorageKey::ReservesTotal).unwrap_or(0);
+
+        // Recoveries count against an advance's principal before its fee
+        let mut advanced: i128 = 0;
+        for (_, advance) in data.advances.iter() {
+            advanced += (advance.outstanding - advance.fee).max(0);
+        }
+
+        let discrepancy = token_balance + advanced - (claimables + reserves + fees);
+        Ok(Reconciliation {
+            token_balance,
+            claimables,
+            reserves,
+            fees,
+            advanced,
+            discrepancy,
+            within_tolerance: discrepancy.abs() <= data.reconcile_tolerance,
+            checked_at: env.ledger().timestamp(),
+        })
+    }
+
+    /// Enable or disable pausing when an invariant check fails (pauser only)
+    pub fn set_auto_pause(env: &Env, caller: Address, enabled: bool) -> Result<(), ContractError> {
         let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
-        
-        // Only admin can update rates
-        if env.current_contract_address() != data.admin {
-            return Err(symbol_short!("UNAUTHORIZED"));
+
+        access::check(env, &data.admin, Role::Pauser, &caller)?;
+
+        data.auto_pause = enabled;
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        Ok(())
+    }
+
+    /// Halt state-changing entrypoints, e.g. while an oracle is compromised; views keep working (pauser only)
+    pub fn pause(env: &Env, caller: Address) -> Result<(), ContractError> {
+        Self::set_paused(env, caller, true)
+    }
+
+    /// Resume state-changing entrypoints after a pause (pauser only)
+    pub fn unpause(env: &Env, caller: Address) -> Result<(), ContractError> {
+        Self::set_paused(env, caller, false)
+    }
+
+    /// Whether state-changing entrypoints are halted
+    pub fn is_paused(env: &Env) -> bool {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        data.paused
+    }
+
+    fn set_paused(env: &Env, caller: Address, paused: bool) -> Result<(), ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Pauser, &caller)?;
+
+        data.paused = paused;
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        let topic = if paused { symbol_short!("paused") } else { symbol_short!("unpaused") };
+        env.events().publish((topic,), env.ledger().timestamp());
+
+        Ok(())
+    }
+
+    /// Switch one entrypoint feature on or off without pausing the whole contract (pauser only)
+    pub fn set_feature(env: &Env, caller: Address, feature: Symbol, enabled: bool) -> Result<(), ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Pauser, &caller)?;
+
+        if let Some(index) = data.disabled_features.first_index_of(&feature) {
+            data.disabled_features.remove(index);
+        }
+        if !enabled {
+            data.disabled_features.push_back(feature.clone());
+        }
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        env.events().publish((symbol_short!("feature"), feature), enabled);
+
+        Ok(())
+    }
+
+    /// Whether an entrypoint feature is switched on
+    pub fn is_feature_enabled(env: &Env, feature: Symbol) -> bool {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        !data.disabled_features.contains(&feature)
+    }
+
+    /// Grant a role to an account (admin only)
+    pub fn grant_role(env: &Env, caller: Address, role: Role, account: Address) -> Result<(), ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+        access::grant(env, role, &account);
+
+        Ok(())
+    }
+
+    /// Revoke a role previously granted to an account (admin only)
+    pub fn revoke_role(env: &Env, caller: Address, role: Role, account: Address) -> Result<(), ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+        access::revoke(env, role, &account).map_err(ContractError::from)
+    }
+
+    /// Whether an account holds a role; the admin holds every role
+    pub fn has_role(env: &Env, role: Role, account: Address) -> bool {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+        access::holds(env, &data.admin, role, &account)
+    }
+
+    /// Extend the TTL of the contract instance and its code. Open to anyone, so a keeper can
+    /// stop a quiet contract from being archived between writes
+    pub fn bump_storage(env: &Env) {
+        ttl::extend_instance(env);
+    }
+
+    /// Set the remaining TTL below which writes extend an entry, and the TTL they extend it to (admin only)
+    pub fn set_ttl_config(env: &Env, caller: Address, threshold: u32, extend_to: u32) -> Result<(), ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+        ttl::set_config(env, threshold, extend_to).map_err(ContractError::from)
+    }
+
+    /// Get the storage TTL configuration
+    pub fn get_ttl_config(env: &Env) -> TtlConfig {
+        ttl::config(env)
+    }
+
+    /// Push change notifications to a relay contract, or stop with None (admin only)
+    pub fn set_relay(env: &Env, caller: Address, relay: Option<Address>) -> Result<(), ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+        changes::set_relay(env, relay);
+
+        Ok(())
+    }
+
+    /// Queue an admin change behind the timelock (admin only).
+    /// Supported actions: "eq_rate", "imp_rate", "oracle", "loan_pool", "auditor", "gov", "emg_bps"
+    pub fn queue_admin_change(
+        env: &Env,
+        caller: Address,
+        action: Symbol,
+        value: i128,
+        new_address: Option<Address>,
+        delay: u64,
+    ) -> Result<u32, ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        // Only admin can queue changes
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+
+        timelock::queue(env, action, ChangeValue::new(value, new_address), delay, timelock::MIN_DELAY, |change| {
+            Self::validate_admin_change(env, change)
+        })
+    }
+
+    /// Execute a queued admin change once its timelock has elapsed (executor only)
+    pub fn execute_admin_change(env: &Env, caller: Address, change_id: u32) -> Result<(), ContractError> {
+        let mut data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        // Only executors can execute changes
+        access::check(env, &data.admin, Role::Executor, &caller)?;
+
+        timelock::execute(env, change_id, |change| Self::apply_admin_change(env, &mut data, change))?;
+        ttl::set_instance(env, &DATA_KEY, &data);
+
+        Ok(())
+    }
+
+    /// Cancel a queued admin change during its cancellation window (admin only)
+    pub fn cancel_admin_change(env: &Env, caller: Address, change_id: u32) -> Result<(), ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        // Only admin can cancel changes
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+
+        timelock::cancel(env, change_id).map_err(ContractError::from)
+    }
+
+    /// Get all admin changes waiting out their timelock
+    pub fn get_pending_changes(env: &Env) -> Vec<PendingChange> {
+        timelock::pending(env)
+    }
+
+    /// Get a page of the append-only log of executed and cancelled admin changes
+    pub fn get_change_log(env: &Env, page: PageRequest) -> ChangeLogPage {
+        timelock::log(env, page)
+    }
+
+    /// Check that a queued change names a known action with a valid value
+    fn validate_admin_change(env: &Env, change: &PendingChange) -> Result<(), ContractError> {
+        if change.action == symbol_short!("eq_rate") {
+            // Validate rate (0-50%)
+            if !(0..=50).contains(&change.number()?) {
+                return Err(ContractError::InvalidInput);
+            }
+        } else if change.action == symbol_short!("imp_rate") {
+            // Validate rate (0-25%)
+            if !(0..=25).contains(&change.number()?) {
+                return Err(ContractError::InvalidInput);
+            }
+        } else if change.action == symbol_short!("oracle")
+            || change.action == symbol_short!("loan_pool")
+            || change.action == symbol_short!("auditor")
+            || change.action == symbol_short!("gov")
+        {
+            change.address()?;
+        } else if change.action == symbol_short!("emg_bps") {
+            // Emergency top-up between 0% and 5%
+            if !(0..=MAX_EMERGENCY_BPS as i128).contains(&change.number()?) {
+                return Err(ContractError::InvalidInput);
+            }
+        } else if change.action == symbol_short!("pol_bound") {
+            let bounds: PolicyBounds = change.decode(env)?;
+            if bounds.max_reserve_bps > MAX_POLICY_RESERVE_BPS {
+                return Err(ContractError::InvalidInput);
+            }
+        } else if change.action == symbol_short!("min_pay") {
+            let floor: MinPayout = change.decode(env)?;
+            if floor.share_threshold_bps > 10_000 || floor.min_payout < 0 {
+                return Err(ContractError::InvalidInput);
+            }
+        } else if change.action == symbol_short!("adv_terms") {
+            let (cap_bps, fee_bps): (u32, u32) = change.decode(env)?;
+            if cap_bps > MAX_ADVANCE_CAP_BPS || fee_bps > MAX_ADVANCE_FEE_BPS {
+                return Err(ContractError::InvalidInput);
+            }
+        } else {
+            return Err(ContractError::InvalidInput);
         }
 
-        // Validate rate (0-25%)
-        if new_rate < 0 || new_rate > 25 {
-            return Err(symbol_short!("INVALID_RATE"));
+        Ok(())
+    }
+
+    /// Apply an admin change to contract state
+    fn apply_admin_change(env: &Env, data: &mut DataKey, change: &PendingChange) -> Result<(), ContractError> {
+        Self::validate_admin_change(env, change)?;
+
+        if change.action == symbol_short!("eq_rate") {
+            data.equity_bonus_rate = change.number()? as i32;
+        } else if change.action == symbol_short!("imp_rate") {
+            data.impact_bonus_rate = change.number()? as i32;
+        } else if change.action == symbol_short!("oracle") {
+            data.oracle = change.address()?;
+        } else if change.action == symbol_short!("loan_pool") {
+            data.loan_pool = change.address()?;
+        } else if change.action == symbol_short!("auditor") {
+            data.auditor = change.address()?;
+        } else if change.action == symbol_short!("gov") {
+            ttl::set_persistent(env, &StorageKey::Governance, &change.address()?);
+        } else if change.action == symbol_short!("emg_bps") {
+            data.emergency_bps = change.number()? as u32;
+        } else if change.action == symbol_short!("pol_bound") {
+            let bounds: PolicyBounds = change.decode(env)?;
+            env.events().publish((symbol_short!("pol_bound"),), (bounds.max_reserve_bps, bounds.allow_reinvest));
+            ttl::set_persistent(env, &StorageKey::PolicyBounds, &bounds);
+        } else if change.action == symbol_short!("min_pay") {
+            let floor: MinPayout = change.decode(env)?;
+            env.events().publish((symbol_short!("min_pay"),), (floor.share_threshold_bps, floor.min_payout));
+            ttl::set_persistent(env, &StorageKey::MinPayout, &floor);
+        } else if change.action == symbol_short!("adv_terms") {
+            (data.advance_cap_bps, data.advance_fee_bps) = change.decode(env)?;
         }
 
-        data.impact_bonus_rate = new_rate;
-        env.storage().instance().set(&DATA_KEY, &data);
-        
         Ok(())
     }
 
+    /// Governance contract, once linked through an admin change
+    fn governance(env: &Env) -> Option<Address> {
+        env.storage().persistent().get(&StorageKey::Governance)
+    }
+
     /// Generate unique distribution ID
-    fn generate_distribution_id(env: &Env, asset_id: &Symbol) -> Symbol {
-        let asset_str = asset_id.to_string();
+    fn generate_distribution_id(env: &Env, asset_id: &Symbol, epoch: u32) -> Symbol {
         let timestamp = env.ledger().timestamp();
-        
-        let combined = format!("dist_{}_{}", asset_str, timestamp);
-        let hash = env.crypto().sha256(&combined.as_bytes());
-        
-        // Convert first 8 bytes to symbol
-        let mut id_bytes = [0u8; 8];
-        id_bytes.copy_from_slice(&hash[0..8]);
-        
-        Symbol::from_bytes(&id_bytes)
+
+        // The asset's epoch number keeps IDs unique within a single ledger
+        let preimage = (symbol_short!("dist"), asset_id.clone(), timestamp, epoch).to_xdr(env);
+        ids::derive(env, &preimage)
     }
 
-    /// Calculate equity bonus for an investor
+    /// Calculate equity bonus for an investor out of their pro-rata slice of the bonus pool,
+    /// so the bonuses across a distribution never add up to more than the pool
     fn calculate_equity_bonus(
-        env: &Env,
-        equity_bonus_pool: &i128,
-        equity_score: &i32,
-        underserved_rides: &i32,
-        total_rides: &i32,
+        equity_bonus_pool: i128,
+        investment_amount: i128,
+        total_investment: i128,
+        equity_score: i32,
+        underserved_rides: i32,
+        total_rides: i32,
     ) -> i128 {
-        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
-        
+        if total_investment <= 0 {
+            return 0;
+        }
+        let slice = equity_bonus_pool * investment_amount / total_investment;
+
         // Base equity bonus based on equity score
-        let base_bonus = equity_bonus_pool * equity_score / 100;
-        
+        let base_bonus = slice * equity_score.max(0) as i128 / 100;
+
         // Additional bonus for underserved area focus
         let underserved_bonus = if total_rides > 0 {
-            let underserved_ratio = underserved_rides * 100 / total_rides;
+            let underserved_ratio = underserved_rides as i128 * 100 / total_rides as i128;
             base_bonus * underserved_ratio / 100
         } else {
             0
         };
-        
-        base_bonus + underserved_bonus
+
+        (base_bonus + underserved_bonus).min(slice)
     }
 
     /// Calculate impact multiplier for high-impact zones
-    fn calculate_impact_multiplier(
-        env: &Env,
-        co2_saved: &i32,
-        underserved_rides: &i32,
-        total_rides: &i32,
-    ) -> i32 {
-        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
-        
+    fn calculate_impact_multiplier(data: &DataKey, co2_saved: i32, underserved_rides: i32, total_rides: i32) -> i32 {
         let mut multiplier = 100; // Base 100%
-        
+
         // Bonus for CO2 savings
         if co2_saved > 1000 {
             multiplier += 10; // 10% bonus for significant CO2 savings
         }
-        
+
         // Bonus for underserved area focus
         if total_rides > 0 {
             let underserved_ratio = underserved_rides * 100 / total_rides;
@@ -349,7 +2875,7 @@ impl RevenueDistributor {
                 multiplier += data.impact_bonus_rate; // Additional bonus for high underserved ratio
             }
         }
-        
+
         multiplier
     }
 
@@ -361,7 +2887,8 @@ impl RevenueDistributor {
         let mut total_revenue_distributed = 0;
         let mut total_assets = 0;
         
-        for (_, distribution) in data.distributions.iter() {
+        for id in Self::distribution_ids(env).iter() {
+            let distribution = Self::load_distribution(env, &id).unwrap();
             total_distributions += 1;
             total_revenue_distributed += distribution.total_revenue;
         }
@@ -374,5 +2901,18 @@ impl RevenueDistributor {
     }
 }
 
+#[contractimpl]
+impl DistributorInterface for RevenueDistributor {
+    /// Get the rides accepted in a calendar epoch, for the governance KPI dashboard
+    fn get_epoch_rides(env: &Env, epoch: u32) -> RideStats {
+        env.storage().persistent().get(&(KPI_KEY, epoch)).unwrap_or_default()
+    }
+
+    /// Get the revenue distributions recorded in a calendar epoch, for governance annual reports
+    fn get_epoch_distributions(env: &Env, epoch: u32) -> DistributionStats {
+        env.storage().persistent().get(&(DIST_KPI_KEY, epoch)).unwrap_or_default()
+    }
+}
+
 #[cfg(test)]
 mod test;
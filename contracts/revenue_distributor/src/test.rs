@@ -0,0 +1,804 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    contract, contractimpl, symbol_short, testutils::{Address as _, Ledger}, token::StellarAssetClient, vec, Address, Env, Symbol, Vec,
+};
+
+/// Stands in for the loan pool: one settlement token, per-asset zone data and share holdings
+/// backed by a single investment each
+#[contract]
+struct StubLoanPool;
+
+#[contractimpl]
+impl StubLoanPool {
+    pub fn set_settlement_asset(env: Env, asset: Address) {
+        env.storage().instance().set(&symbol_short!("settle"), &asset);
+    }
+
+    pub fn get_settlement_asset(env: Env) -> Option<Address> {
+        env.storage().instance().get(&symbol_short!("settle"))
+    }
+
+    pub fn list_asset(env: Env, asset_id: Symbol, equity_score: i32, program: Symbol) {
+        env.storage().persistent().set(&(symbol_short!("asset"), asset_id), &(equity_score, program));
+    }
+
+    /// Give `holder` shares in an asset, backed by one investment made at `invested_at`
+    pub fn hold(env: Env, asset_id: Symbol, holder: Address, amount: i128, invested_at: u64) {
+        let mut holders = Self::share_holders(env.clone(), asset_id.clone());
+        if !holders.contains(&holder) {
+            holders.push_back(holder.clone());
+        }
+        env.storage().persistent().set(&(symbol_short!("holders"), asset_id.clone()), &holders);
+        env.storage().persistent().set(&(symbol_short!("held"), asset_id, holder), &(amount, invested_at));
+    }
+
+    pub fn get_asset_equity_score(env: Env, asset_id: Symbol) -> Option<i32> {
+        Self::asset(&env, asset_id).map(|(score, _)| score)
+    }
+
+    pub fn get_asset_program(env: Env, asset_id: Symbol) -> Option<Symbol> {
+        Self::asset(&env, asset_id).map(|(_, program)| program)
+    }
+
+    pub fn attest_accessibility(env: Env, asset_id: Symbol, feature: Symbol) {
+        env.storage().persistent().set(&(symbol_short!("access"), asset_id), &feature);
+    }
+
+    pub fn get_asset_accessibility(env: Env, asset_id: Symbol) -> Option<Symbol> {
+        env.storage().persistent().get(&(symbol_short!("access"), asset_id))
+    }
+
+    pub fn is_asset_defaulted(_env: Env, _asset_id: Symbol) -> bool {
+        false
+    }
+
+    pub fn share_holders(env: Env, asset_id: Symbol) -> Vec<Address> {
+        env.storage().persistent().get(&(symbol_short!("holders"), asset_id)).unwrap_or(vec![&env])
+    }
+
+    pub fn share_balance(env: Env, asset_id: Symbol, id: Address) -> i128 {
+        Self::held(&env, asset_id, id).0
+    }
+
+    pub fn share_total_supply(env: Env, asset_id: Symbol) -> i128 {
+        let mut supply = 0;
+        for holder in Self::share_holders(env.clone(), asset_id.clone()).iter() {
+            supply += Self::held(&env, asset_id.clone(), holder).0;
+        }
+        supply
+    }
+
+    pub fn get_investor_position(env: Env, investor: Address, asset_id: Symbol, since: u64) -> (i128, i128, i128, i128) {
+        let (amount, invested_at) = Self::held(&env, asset_id, investor);
+        let start = invested_at.max(since);
+        let now = env.ledger().timestamp();
+        let capital_seconds = if now > start { amount * (now - start) as i128 } else { 0 };
+        (amount, capital_seconds, 0, 0)
+    }
+
+    /// Record a principal return or write-off for `investor`
+    pub fn log_principal(env: Env, investor: Address, kind: Symbol, asset_id: Symbol, amount: i128, timestamp: u64) {
+        let mut events = Self::get_principal_events(env.clone(), investor.clone(), 0, u64::MAX);
+        events.push_back((kind, asset_id, amount, timestamp));
+        env.storage().persistent().set(&(symbol_short!("principal"), investor), &events);
+    }
+
+    pub fn get_principal_events(env: Env, investor: Address, from: u64, to: u64) -> Vec<(Symbol, Symbol, i128, u64)> {
+        let events: Vec<(Symbol, Symbol, i128, u64)> =
+            env.storage().persistent().get(&(symbol_short!("principal"), investor)).unwrap_or(vec![&env]);
+        let mut in_range = vec![&env];
+        for event in events.iter() {
+            if event.3 >= from && event.3 < to {
+                in_range.push_back(event);
+            }
+        }
+        in_range
+    }
+
+    fn asset(env: &Env, asset_id: Symbol) -> Option<(i32, Symbol)> {
+        env.storage().persistent().get(&(symbol_short!("asset"), asset_id))
+    }
+
+    fn held(env: &Env, asset_id: Symbol, holder: Address) -> (i128, u64) {
+        env.storage().persistent().get(&(symbol_short!("held"), asset_id, holder)).unwrap_or((0, 0))
+    }
+}
+
+/// Stands in for governance: a flat platform fee for scores below 80 (none by default) and the
+/// default epoch calendar
+#[contract]
+struct StubGovernance;
+
+#[contractimpl]
+impl StubGovernance {
+    pub fn set_fee_bps(env: Env, fee_bps: u32) {
+        env.storage().instance().set(&symbol_short!("fee"), &fee_bps);
+    }
+
+    pub fn get_fee_bps(env: Env, equity_score: i32) -> u32 {
+        if equity_score >= 80 {
+            return 0;
+        }
+        env.storage().instance().get(&symbol_short!("fee")).unwrap_or(0)
+    }
+
+    pub fn get_epoch_schedule(_env: Env) -> EpochSchedule {
+        EpochSchedule::default_schedule()
+    }
+}
+
+struct Setup<'a> {
+    client: RevenueDistributorClient<'a>,
+    pool: StubLoanPoolClient<'a>,
+    token: TokenClient<'a>,
+    admin: Address,
+}
+
+/// Register a distributor with no equity bonus against a stub loan pool settling in a fresh
+/// token, with ebike_001 listed in the "metro" program
+fn setup(env: &Env) -> Setup<'_> {
+    let pool = StubLoanPoolClient::new(env, &env.register_contract(None, StubLoanPool));
+    let asset = env.register_stellar_asset_contract(Address::generate(env));
+    pool.set_settlement_asset(&asset);
+    pool.list_asset(&symbol_short!("ebike_001"), &60, &symbol_short!("metro"));
+
+    let client = RevenueDistributorClient::new(env, &env.register_contract(None, RevenueDistributor));
+    let admin = Address::generate(env);
+    client.initialize(&admin, &Address::generate(env), &pool.address, &0);
+
+    Setup { client, pool, token: TokenClient::new(env, &asset), admin }
+}
+
+/// Record revenue for an asset and fund the distributor with it
+fn fund_revenue(env: &Env, setup: &Setup, asset_id: &Symbol, revenue: i128, rides: i32, underserved: i32) {
+    setup.client.record_revenue(asset_id, &revenue, &rides, &0, &underserved);
+    StellarAssetClient::new(env, &setup.token.address).mint(&setup.client.address, &revenue);
+}
+
+#[test]
+fn test_share_distribution_pays_pro_rata_claims() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = setup(&env);
+    let asset_id = symbol_short!("ebike_001");
+    let (alice, bob) = (Address::generate(&env), Address::generate(&env));
+    setup.pool.hold(&asset_id, &alice, &300, &0);
+    setup.pool.hold(&asset_id, &bob, &700, &0);
+    fund_revenue(&env, &setup, &asset_id, 1_000, 100, 0);
+
+    // 1% of the revenue tops up the program's emergency fund; the rest follows the shares
+    let distribution_id = setup.client.distribute_revenue(&setup.admin, &asset_id);
+    let distribution = setup.client.get_distribution(&distribution_id);
+    assert_eq!(distribution.emergency_topup, 10);
+    assert_eq!(setup.client.get_emergency_fund(&symbol_short!("metro")), 10);
+    assert_eq!(setup.client.get_claimable(&alice, &distribution_id), 297);
+    assert_eq!(setup.client.get_claimable_total(&bob), 693);
+
+    assert_eq!(setup.client.claim_epoch(&alice, &asset_id, &1), 297);
+    assert_eq!(setup.token.balance(&alice), 297);
+    assert_eq!(setup.client.try_claim(&alice, &distribution_id), Err(Ok(ContractError::Nothing)));
+}
+
+#[test]
+fn test_epoch_distribution_weights_holdings_by_time_in_the_epoch() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = setup(&env);
+    let asset_id = symbol_short!("ebike_001");
+    let schedule = EpochSchedule::default_schedule();
+    let (epoch_start, epoch_end) = (schedule.epoch_start(1), schedule.epoch_end(1));
+
+    // Alice was invested all epoch, Bob for its second half and Carol only after it ended
+    let (alice, bob, carol) = (Address::generate(&env), Address::generate(&env), Address::generate(&env));
+    setup.pool.hold(&asset_id, &alice, &600, &0);
+    setup.pool.hold(&asset_id, &bob, &600, &(epoch_start + (epoch_end - epoch_start) / 2));
+    setup.pool.hold(&asset_id, &carol, &600, &(epoch_end + 1));
+    env.ledger().with_mut(|ledger| ledger.timestamp = epoch_end + 86_400);
+    fund_revenue(&env, &setup, &asset_id, 900, 90, 0);
+
+    assert_eq!(
+        setup.client.try_distribute_epoch_revenue(&setup.admin, &asset_id, &2),
+        Err(Ok(ContractError::TooEarly))
+    );
+    let distribution_id = setup.client.distribute_epoch_revenue(&setup.admin, &asset_id, &1);
+    assert_eq!(setup.client.get_claimable(&alice, &distribution_id), 594);
+    assert_eq!(setup.client.get_claimable(&bob, &distribution_id), 297);
+    assert_eq!(setup.client.get_claimable(&carol, &distribution_id), 0);
+}
+
+#[test]
+fn test_treasury_fee_waits_for_a_completed_audit() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = setup(&env);
+    let asset_id = symbol_short!("ebike_001");
+    setup.pool.hold(&asset_id, &Address::generate(&env), &1_000, &0);
+    fund_revenue(&env, &setup, &asset_id, 1_000, 100, 0);
+    let distribution_id = setup.client.distribute_revenue(&setup.admin, &asset_id);
+
+    // Skipping the sample doesn't skip the audit
+    assert_eq!(
+        setup.client.try_release_treasury_fee(&setup.admin, &distribution_id),
+        Err(Ok(ContractError::AuditDue))
+    );
+    assert_eq!(
+        setup.client.try_sample_distribution_audits(&setup.admin, &distribution_id, &0),
+        Err(Ok(ContractError::InvalidInput))
+    );
+
+    let samples = setup.client.sample_distribution_audits(&setup.admin, &distribution_id, &1);
+    assert_eq!(
+        setup.client.try_release_treasury_fee(&setup.admin, &distribution_id),
+        Err(Ok(ContractError::AuditDue))
+    );
+
+    let line_index = samples.get(0).unwrap().line_index;
+    let report = BytesN::from_array(&env, &[7; 32]);
+    setup.client.post_audit_attestation(&distribution_id, &line_index, &report);
+    assert_eq!(setup.client.get_audit_attestation(&distribution_id, &line_index), Some(report));
+    let sampled = setup.client.get_audit_samples(&distribution_id, &PageRequest::first(10));
+    assert!(sampled.items.get(0).unwrap().attested);
+    assert_eq!(sampled.next_cursor, None);
+    setup.client.release_treasury_fee(&setup.admin, &distribution_id);
+    assert!(setup.client.get_distribution(&distribution_id).fee_released);
+    assert_eq!(
+        setup.client.try_release_treasury_fee(&setup.admin, &distribution_id),
+        Err(Ok(ContractError::Done))
+    );
+}
+
+/// Wait out the admin timelock and execute a queued change
+fn execute_after_delay(env: &Env, setup: &Setup, change_id: u32) {
+    env.ledger().with_mut(|ledger| ledger.timestamp += timelock::MIN_DELAY);
+    setup.client.execute_admin_change(&setup.admin, &change_id);
+}
+
+/// Link a stub governance contract through a timelocked admin change
+fn link_governance(env: &Env, setup: &Setup) -> Address {
+    let governance = env.register_contract(None, StubGovernance);
+    let change_id = setup.client.queue_admin_change(&setup.admin, &symbol_short!("gov"), &0, &Some(governance.clone()), &86_400);
+    execute_after_delay(env, setup, change_id);
+    governance
+}
+
+#[test]
+fn test_payout_floor_policy_bounds_and_advance_terms_wait_out_the_timelock() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = setup(&env);
+
+    assert_eq!(setup.client.try_set_min_payout(&500, &10), Err(Ok(ContractError::NoAddress)));
+    link_governance(&env, &setup);
+
+    let floor_id = setup.client.set_min_payout(&500, &10);
+    let bounds_id = setup.client.set_policy_bounds(&1_000, &true);
+    let terms_id = setup.client.set_advance_terms(&setup.admin, &4_000, &50);
+    assert_eq!(env.auths()[0].0, setup.admin);
+    assert_eq!(setup.client.get_pending_changes().len(), 3);
+    assert_eq!(setup.client.try_set_advance_terms(&setup.admin, &9_000, &50), Err(Ok(ContractError::InvalidInput)));
+    assert_eq!(setup.client.try_set_policy_bounds(&5_000, &true), Err(Ok(ContractError::InvalidInput)));
+
+    // Nothing applies until the delay has passed
+    assert_eq!(setup.client.try_execute_admin_change(&setup.admin, &floor_id), Err(Ok(ContractError::Timelock)));
+    assert_eq!(setup.client.get_min_payout(), MinPayout::default());
+
+    env.ledger().with_mut(|ledger| ledger.timestamp += timelock::MIN_DELAY);
+    for change_id in [floor_id, bounds_id, terms_id] {
+        setup.client.execute_admin_change(&setup.admin, &change_id);
+    }
+    assert_eq!(setup.client.get_min_payout(), MinPayout { share_threshold_bps: 500, min_payout: 10 });
+    assert_eq!(setup.client.get_policy_bounds(), PolicyBounds { max_reserve_bps: 1_000, allow_reinvest: true });
+    assert!(setup.client.get_pending_changes().is_empty());
+}
+
+#[test]
+fn test_claim_many_and_claim_all_for_settle_several_distributions() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = setup(&env);
+    let asset_id = symbol_short!("ebike_001");
+    let (alice, bob) = (Address::generate(&env), Address::generate(&env));
+    setup.pool.hold(&asset_id, &alice, &500, &0);
+    setup.pool.hold(&asset_id, &bob, &500, &0);
+    for _ in 0..2 {
+        fund_revenue(&env, &setup, &asset_id, 1_000, 100, 0);
+        setup.client.distribute_revenue(&setup.admin, &asset_id);
+    }
+
+    // Unknown epochs are skipped, and everything is paid in one transfer
+    let claims = vec![&env, (asset_id.clone(), 1), (asset_id.clone(), 2), (asset_id.clone(), 9)];
+    assert_eq!(setup.client.claim_many(&alice, &claims), 990);
+    assert_eq!(setup.token.balance(&alice), 990);
+    assert_eq!(setup.client.try_claim_many(&alice, &claims), Err(Ok(ContractError::Nothing)));
+
+    // The cursor resumes where the last page stopped
+    assert_eq!(setup.client.claim_all_for(&bob, &0, &1), (495, 1));
+    assert_eq!(setup.client.claim_all_for(&bob, &1, &5), (495, 2));
+    assert_eq!(setup.client.claim_all_for(&bob, &2, &5), (0, 2));
+    assert_eq!(setup.client.try_claim_all_for(&bob, &3, &5), Err(Ok(ContractError::InvalidInput)));
+    assert_eq!(setup.token.balance(&bob), 990);
+    assert_eq!(setup.client.get_claimable_total(&bob), 0);
+}
+
+#[test]
+fn test_operator_reports_need_a_countersignature() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = setup(&env);
+    let asset_id = symbol_short!("ebike_001");
+    let operator = Address::generate(&env);
+    setup.client.set_asset_operator(&setup.admin, &asset_id, &operator);
+
+    // Matching figures from the oracle and the operator make the revenue distributable
+    setup.client.record_revenue(&asset_id, &1_000, &100, &0, &10);
+    assert_eq!(setup.client.get_revenue_report(&asset_id).status, symbol_short!("pending"));
+    assert_eq!(setup.client.try_get_revenue(&asset_id), Err(Ok(ContractError::NotFound)));
+    assert_eq!(
+        setup.client.operator_report_revenue(&operator, &asset_id, &1_000, &100, &0, &10),
+        symbol_short!("confirmed")
+    );
+    assert_eq!(setup.client.get_revenue(&asset_id).revenue_amount, 1_000);
+    assert_eq!(
+        setup.client.try_operator_report_revenue(&Address::generate(&env), &asset_id, &1_000, &100, &0, &10),
+        Err(Ok(ContractError::Unauthorized))
+    );
+
+    // Disagreement escalates to governance after the timeout
+    setup.client.record_revenue(&asset_id, &2_000, &200, &0, &20);
+    assert_eq!(
+        setup.client.operator_report_revenue(&operator, &asset_id, &1_500, &200, &0, &20),
+        symbol_short!("disputed")
+    );
+    assert_eq!(setup.client.try_escalate_revenue_report(&asset_id), Err(Ok(ContractError::TooEarly)));
+    link_governance(&env, &setup);
+    env.ledger().with_mut(|ledger| ledger.timestamp += 3 * 86_400);
+    setup.client.escalate_revenue_report(&asset_id);
+    assert_eq!(
+        setup.client.try_operator_report_revenue(&operator, &asset_id, &2_000, &200, &0, &20),
+        Err(Ok(ContractError::Escalated))
+    );
+
+    setup.client.resolve_revenue_report(&asset_id, &1_800, &200, &0, &20);
+    assert_eq!(setup.client.get_revenue(&asset_id).revenue_amount, 1_800);
+    assert_eq!(setup.client.try_get_revenue_report(&asset_id), Err(Ok(ContractError::NotFound)));
+}
+
+#[test]
+fn test_finalized_distributions_are_frozen_and_verifiable() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = setup(&env);
+    let asset_id = symbol_short!("ebike_001");
+    let (alice, bob) = (Address::generate(&env), Address::generate(&env));
+    setup.pool.hold(&asset_id, &alice, &500, &0);
+    setup.pool.hold(&asset_id, &bob, &500, &0);
+    fund_revenue(&env, &setup, &asset_id, 1_000, 100, 0);
+    let distribution_id = setup.client.distribute_revenue(&setup.admin, &asset_id);
+
+    // Bob's line is still claimable until the claim window closes
+    setup.client.claim(&alice, &distribution_id);
+    assert_eq!(setup.client.try_finalize_distribution(&distribution_id), Err(Ok(ContractError::Claimable)));
+    assert_eq!(
+        setup.client.try_verify_distribution(&distribution_id, &BytesN::from_array(&env, &[0; 32])),
+        Err(Ok(ContractError::InvalidStatus))
+    );
+
+    env.ledger().with_mut(|ledger| ledger.timestamp += 365 * 86_400);
+    let hash = setup.client.finalize_distribution(&distribution_id);
+    assert!(setup.client.verify_distribution(&distribution_id, &hash));
+    assert!(!setup.client.verify_distribution(&distribution_id, &BytesN::from_array(&env, &[0; 32])));
+
+    // Nothing about a frozen distribution changes, and its lapsed lines can't be claimed
+    assert_eq!(setup.client.get_claimable(&bob, &distribution_id), 0);
+    assert_eq!(setup.client.try_claim(&bob, &distribution_id), Err(Ok(ContractError::Nothing)));
+    assert_eq!(setup.client.try_finalize_distribution(&distribution_id), Err(Ok(ContractError::Frozen)));
+    assert_eq!(
+        setup.client.try_sample_distribution_audits(&setup.admin, &distribution_id, &1),
+        Err(Ok(ContractError::Frozen))
+    );
+    assert_eq!(setup.client.try_remind_stale_claims(&distribution_id), Err(Ok(ContractError::Frozen)));
+}
+
+#[test]
+fn test_reconciliation_flags_unexplained_balances() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = setup(&env);
+    let asset_id = symbol_short!("ebike_001");
+    let alice = Address::generate(&env);
+    setup.pool.hold(&asset_id, &alice, &1_000, &0);
+    fund_revenue(&env, &setup, &asset_id, 1_000, 100, 0);
+    let distribution_id = setup.client.distribute_revenue(&setup.admin, &asset_id);
+
+    // The balance covers the open claim and the emergency top-up exactly
+    let report = setup.client.reconcile();
+    assert_eq!((report.token_balance, report.claimables, report.reserves, report.fees), (1_000, 990, 10, 0));
+    assert_eq!(report.discrepancy, 0);
+    assert!(report.within_tolerance);
+
+    // An unexplained surplus beyond the tolerance switches claims off
+    setup.client.set_reconcile_policy(&setup.admin, &2, &true);
+    StellarAssetClient::new(&env, &setup.token.address).mint(&setup.client.address, &5);
+    assert_eq!(setup.client.get_reconciliation().discrepancy, 5);
+    assert!(!setup.client.reconcile().within_tolerance);
+    assert!(!setup.client.is_feature_enabled(&symbol_short!("claim")));
+    assert_eq!(setup.client.try_claim(&alice, &distribution_id), Err(Ok(ContractError::FeatureDisabled)));
+}
+
+#[test]
+fn test_operator_advance_is_capped_and_repaid_from_the_next_distribution() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = setup(&env);
+    let asset_id = symbol_short!("ebike_001");
+    let (operator, alice) = (Address::generate(&env), Address::generate(&env));
+    setup.client.set_asset_operator(&setup.admin, &asset_id, &operator);
+    setup.pool.hold(&asset_id, &alice, &1_000, &0);
+
+    // No trailing revenue, no advance
+    assert_eq!(setup.client.get_advance_limit(&asset_id), 0);
+    setup.client.record_revenue(&asset_id, &1_000, &100, &0, &0);
+    setup.client.operator_report_revenue(&operator, &asset_id, &1_000, &100, &0, &0);
+    StellarAssetClient::new(&env, &setup.token.address).mint(&setup.client.address, &1_000);
+    setup.client.distribute_revenue(&setup.admin, &asset_id);
+
+    // Half the trailing average, with a 1% fee on top
+    assert_eq!(setup.client.get_advance_limit(&asset_id), 500);
+    assert_eq!(setup.client.try_draw_advance(&operator, &asset_id, &600), Err(Ok(ContractError::OverLimit)));
+    assert_eq!(setup.client.try_draw_advance(&alice, &asset_id, &400), Err(Ok(ContractError::Unauthorized)));
+    assert_eq!(setup.client.draw_advance(&operator, &asset_id, &400), 404);
+    assert_eq!(setup.client.try_draw_advance(&operator, &asset_id, &50), Err(Ok(ContractError::AdvanceOpen)));
+    assert_eq!(setup.client.get_advance_limit(&asset_id), 0);
+
+    // The operator is paid up front, and reconciliation counts the advance as owed back
+    assert_eq!(setup.token.balance(&operator), 400);
+    assert_eq!(setup.token.balance(&setup.client.address), 600);
+    let report = setup.client.get_reconciliation();
+    assert_eq!((report.advanced, report.discrepancy), (400, 0));
+
+    // The next distribution recovers the advance before investors are paid
+    setup.client.record_revenue(&asset_id, &1_000, &100, &0, &0);
+    setup.client.operator_report_revenue(&operator, &asset_id, &1_000, &100, &0, &0);
+    StellarAssetClient::new(&env, &setup.token.address).mint(&setup.client.address, &1_000);
+    let distribution_id = setup.client.distribute_revenue(&setup.admin, &asset_id);
+    let distribution = setup.client.get_distribution(&distribution_id);
+    assert_eq!((distribution.advance_repayment, distribution.emergency_topup), (404, 5));
+    assert_eq!(setup.client.get_claimable(&alice, &distribution_id), 591);
+    assert_eq!(setup.client.try_get_advance(&asset_id), Err(Ok(ContractError::NotFound)));
+
+    // Only the recovered fee is left over
+    let report = setup.client.get_reconciliation();
+    assert_eq!((report.advanced, report.discrepancy), (0, 4));
+}
+
+#[test]
+fn test_investor_referendum_sets_the_assets_reserve() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = setup(&env);
+    let asset_id = symbol_short!("ebike_001");
+    let (alice, bob) = (Address::generate(&env), Address::generate(&env));
+    setup.pool.hold(&asset_id, &alice, &700, &0);
+    setup.pool.hold(&asset_id, &bob, &300, &0);
+
+    // Referenda stay within the bounds governance set
+    assert_eq!(setup.client.try_open_policy_referendum(&alice, &asset_id, &1_000, &false), Err(Ok(ContractError::OutOfBand)));
+    link_governance(&env, &setup);
+    let change_id = setup.client.set_policy_bounds(&1_500, &false);
+    execute_after_delay(&env, &setup, change_id);
+    assert_eq!(
+        setup.client.try_open_policy_referendum(&Address::generate(&env), &asset_id, &1_000, &false),
+        Err(Ok(ContractError::NotEligible))
+    );
+
+    // Votes are weighted by each investor's position
+    let referendum_id = setup.client.open_policy_referendum(&alice, &asset_id, &1_000, &false);
+    assert_eq!(setup.client.vote_policy_referendum(&alice, &referendum_id, &true), 700);
+    assert_eq!(setup.client.vote_policy_referendum(&bob, &referendum_id, &false), 300);
+    assert_eq!(setup.client.try_vote_policy_referendum(&bob, &referendum_id, &true), Err(Ok(ContractError::AlreadyVoted)));
+    assert_eq!(setup.client.try_close_policy_referendum(&referendum_id), Err(Ok(ContractError::TooEarly)));
+    env.ledger().with_mut(|ledger| ledger.timestamp += 3 * 86_400);
+    assert_eq!(setup.client.close_policy_referendum(&referendum_id), symbol_short!("passed"));
+
+    // The next distribution holds back 10% for maintenance
+    fund_revenue(&env, &setup, &asset_id, 1_000, 100, 0);
+    let distribution_id = setup.client.distribute_revenue(&setup.admin, &asset_id);
+    assert_eq!(setup.client.get_asset_reserve(&asset_id).maintenance, 99);
+    assert_eq!(setup.client.get_claimable(&alice, &distribution_id), 623);
+    assert_eq!(setup.client.get_claimable(&bob, &distribution_id), 267);
+
+    let recipient = Address::generate(&env);
+    assert_eq!(
+        setup.client.try_release_asset_reserve(&setup.admin, &asset_id, &symbol_short!("maint"), &recipient, &100),
+        Err(Ok(ContractError::NoFunds))
+    );
+    setup.client.release_asset_reserve(&setup.admin, &asset_id, &symbol_short!("maint"), &recipient, &99);
+    assert_eq!(setup.client.get_asset_reserve(&asset_id).maintenance, 0);
+    assert_eq!(setup.token.balance(&recipient), 99);
+    assert_eq!(setup.token.balance(&setup.client.address), 1_000 - 99);
+    assert_eq!(setup.client.get_reconciliation().discrepancy, 0);
+}
+
+#[test]
+fn test_micro_investors_are_topped_up_to_the_payout_floor() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = setup(&env);
+    let asset_id = symbol_short!("scooter1");
+    setup.pool.list_asset(&asset_id, &10, &symbol_short!("metro"));
+    let (whale, micro) = (Address::generate(&env), Address::generate(&env));
+    setup.pool.hold(&asset_id, &whale, &9_990, &0);
+    setup.pool.hold(&asset_id, &micro, &10, &0);
+
+    // A 20% equity bonus pool funds the floor for holders under 1% of the asset
+    let rate_id = setup.client.update_equity_bonus_rate(&setup.admin, &20, &86_400);
+    execute_after_delay(&env, &setup, rate_id);
+    link_governance(&env, &setup);
+    let floor_id = setup.client.set_min_payout(&100, &50);
+    execute_after_delay(&env, &setup, floor_id);
+
+    fund_revenue(&env, &setup, &asset_id, 1_000, 100, 0);
+    let distribution_id = setup.client.distribute_revenue(&setup.admin, &asset_id);
+    let distribution = setup.client.get_distribution(&distribution_id);
+    let micro_line = distribution.distributions.iter().find(|line| line.investor == micro).unwrap();
+    assert_eq!((micro_line.base_amount, micro_line.total_amount), (0, 50));
+    assert_eq!(setup.client.get_claimable(&whale, &distribution_id), 810);
+
+    let topups = setup.client.get_boost_audit_by_rule(&symbol_short!("min_pay"), &PageRequest { cursor: 0, limit: 10 });
+    assert_eq!(topups.items.len(), 1);
+    assert_eq!((topups.items.get(0).unwrap().subject, topups.items.get(0).unwrap().amount), (micro, 50));
+}
+
+#[test]
+fn test_stale_claims_are_reminded_then_claimable_by_the_beneficiary() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = setup(&env);
+    let asset_id = symbol_short!("ebike_001");
+    let (alice, fund) = (Address::generate(&env), Address::generate(&env));
+    setup.pool.hold(&asset_id, &alice, &1_000, &0);
+    fund_revenue(&env, &setup, &asset_id, 1_000, 100, 0);
+    let distribution_id = setup.client.distribute_revenue(&setup.admin, &asset_id);
+
+    // Reminders start at the soft deadline and escalate once per interval
+    assert_eq!(setup.client.try_remind_stale_claims(&distribution_id), Err(Ok(ContractError::TooEarly)));
+    env.ledger().with_mut(|ledger| ledger.timestamp += 90 * 86_400);
+    assert_eq!(setup.client.remind_stale_claims(&distribution_id), 1);
+    assert_eq!(setup.client.remind_stale_claims(&distribution_id), 0);
+    env.ledger().with_mut(|ledger| ledger.timestamp += 30 * 86_400);
+    assert_eq!(setup.client.remind_stale_claims(&distribution_id), 1);
+
+    assert_eq!(setup.client.try_set_claim_beneficiary(&alice, &Some(alice.clone())), Err(Ok(ContractError::SameParty)));
+    setup.client.set_claim_beneficiary(&alice, &Some(fund.clone()));
+    assert_eq!(setup.client.get_claim_beneficiary(&alice), Some(fund.clone()));
+    assert_eq!(
+        setup.client.try_claim_as_beneficiary(&fund, &alice, &distribution_id),
+        Err(Ok(ContractError::TooEarly))
+    );
+
+    // Past the hard deadline the beneficiary holds off finalization and collects the line
+    env.ledger().with_mut(|ledger| ledger.timestamp += 245 * 86_400);
+    assert_eq!(setup.client.try_finalize_distribution(&distribution_id), Err(Ok(ContractError::Claimable)));
+    assert_eq!(
+        setup.client.try_claim_as_beneficiary(&Address::generate(&env), &alice, &distribution_id),
+        Err(Ok(ContractError::Unauthorized))
+    );
+    assert_eq!(setup.client.claim_as_beneficiary(&fund, &alice, &distribution_id), 990);
+    assert_eq!(setup.token.balance(&fund), 990);
+    setup.client.finalize_distribution(&distribution_id);
+}
+
+#[test]
+fn test_approved_emergency_payout_is_paid_from_the_distributors_balance() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = setup(&env);
+    let asset_id = symbol_short!("ebike_001");
+    let program = symbol_short!("metro");
+    let (guardian, recipient) = (Address::generate(&env), Address::generate(&env));
+    setup.pool.hold(&asset_id, &Address::generate(&env), &1_000, &0);
+    link_governance(&env, &setup);
+    setup.client.set_emergency_guardian(&setup.admin, &program, &guardian);
+
+    // Revenue recorded but never funded leaves the emergency fund without tokens behind it
+    setup.client.record_revenue(&asset_id, &1_000, &100, &0, &0);
+    setup.client.distribute_revenue(&setup.admin, &asset_id);
+    assert_eq!(setup.client.get_emergency_fund(&program), 10);
+    let request_id = setup.client.request_emergency_payout(&guardian, &program, &recipient, &10, &symbol_short!("flood"));
+    assert_eq!(setup.client.try_approve_emergency_payout(&request_id), Err(Ok(ContractError::NoFunds)));
+    assert_eq!(setup.client.get_emergency_request(&request_id).status, symbol_short!("pending"));
+
+    StellarAssetClient::new(&env, &setup.token.address).mint(&setup.client.address, &1_000);
+    assert_eq!(setup.client.approve_emergency_payout(&request_id), 10);
+    assert_eq!(setup.token.balance(&recipient), 10);
+    assert_eq!(setup.token.balance(&setup.client.address), 990);
+    assert_eq!(setup.client.get_emergency_fund(&program), 0);
+    assert_eq!(setup.client.get_emergency_request(&request_id).status, symbol_short!("paid"));
+}
+
+#[test]
+fn test_equity_bonuses_share_the_pool_instead_of_each_drawing_on_it() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = setup(&env);
+    let asset_id = symbol_short!("bus_0001");
+    setup.pool.list_asset(&asset_id, &90, &symbol_short!("metro"));
+    let mut holders = Vec::<Address>::new(&env);
+    for _ in 0..10 {
+        let holder = Address::generate(&env);
+        setup.pool.hold(&asset_id, &holder, &100, &0);
+        holders.push_back(holder);
+    }
+    let rate_id = setup.client.update_equity_bonus_rate(&setup.admin, &20, &86_400);
+    execute_after_delay(&env, &setup, rate_id);
+
+    // A high-scoring, mostly underserved zone still pays each holder no more than their slice of the pool
+    fund_revenue(&env, &setup, &asset_id, 1_000, 100, 60);
+    let distribution_id = setup.client.distribute_revenue(&setup.admin, &asset_id);
+    let distribution = setup.client.get_distribution(&distribution_id);
+    let bonuses: i128 = distribution.distributions.iter().map(|line| line.equity_bonus).sum();
+    let paid: i128 = distribution.distributions.iter().map(|line| line.total_amount).sum();
+    assert_eq!(distribution.equity_bonus_pool, 198);
+    assert_eq!(bonuses, 190);
+    assert_eq!(paid + distribution.treasury_fee, 990);
+
+    // Every holder can claim in full without touching the emergency fund
+    for holder in holders.iter() {
+        assert_eq!(setup.client.claim(&holder, &distribution_id), 98);
+    }
+    assert_eq!(setup.token.balance(&setup.client.address), 10 + distribution.treasury_fee);
+    assert_eq!(setup.client.get_reconciliation().discrepancy, 0);
+}
+
+#[test]
+fn test_payout_floor_budget_runs_dry_across_many_micro_investors() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = setup(&env);
+    let asset_id = symbol_short!("scooter2");
+    setup.pool.list_asset(&asset_id, &10, &symbol_short!("metro"));
+    let whale = Address::generate(&env);
+    setup.pool.hold(&asset_id, &whale, &8_000, &0);
+    let mut micros = Vec::<Address>::new(&env);
+    for _ in 0..20 {
+        let micro = Address::generate(&env);
+        setup.pool.hold(&asset_id, &micro, &100, &0);
+        micros.push_back(micro);
+    }
+
+    let rate_id = setup.client.update_equity_bonus_rate(&setup.admin, &20, &86_400);
+    execute_after_delay(&env, &setup, rate_id);
+    link_governance(&env, &setup);
+    let floor_id = setup.client.set_min_payout(&150, &20);
+    execute_after_delay(&env, &setup, floor_id);
+
+    // The 198 bonus pool pays the whale 15, leaving 183 to lift micro lines from 7 toward 20
+    fund_revenue(&env, &setup, &asset_id, 1_000, 100, 0);
+    let distribution_id = setup.client.distribute_revenue(&setup.admin, &asset_id);
+    let distribution = setup.client.get_distribution(&distribution_id);
+    let paid: i128 = distribution.distributions.iter().map(|line| line.total_amount).sum();
+    assert_eq!(paid, 633 + 15 + 20 * 7 + 183);
+    assert_eq!(paid + distribution.treasury_fee, 990);
+
+    // Fourteen are topped up in full, the fifteenth gets what's left and the rest keep their base
+    assert_eq!(setup.client.get_claimable(&micros.get(13).unwrap(), &distribution_id), 20);
+    assert_eq!(setup.client.get_claimable(&micros.get(14).unwrap(), &distribution_id), 8);
+    assert_eq!(setup.client.get_claimable(&micros.get(19).unwrap(), &distribution_id), 7);
+    let topups = setup.client.get_boost_audit_by_rule(&symbol_short!("min_pay"), &PageRequest { cursor: 0, limit: 50 });
+    assert_eq!(topups.items.iter().map(|record| record.amount).sum::<i128>(), 183);
+}
+
+#[test]
+fn test_investor_yield_annualizes_the_trailing_year_of_distributions() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = setup(&env);
+    let asset_id = symbol_short!("ebike_001");
+    let alice = Address::generate(&env);
+    setup.pool.hold(&asset_id, &alice, &1_000, &0);
+
+    // Two years in, only the last year of capital counts against 990 distributed
+    env.ledger().with_mut(|ledger| ledger.timestamp = 2 * SECONDS_PER_YEAR);
+    fund_revenue(&env, &setup, &asset_id, 1_000, 100, 0);
+    let distribution_id = setup.client.distribute_revenue(&setup.admin, &asset_id);
+    assert_eq!(setup.client.get_claimable(&alice, &distribution_id), 990);
+    assert_eq!(setup.client.get_investor_yield(&alice, &asset_id), 9_900);
+
+    // Half a year later the same distribution is spread over the same year of capital
+    env.ledger().with_mut(|ledger| ledger.timestamp += SECONDS_PER_YEAR / 2);
+    assert_eq!(setup.client.get_investor_yield(&alice, &asset_id), 9_900);
+
+    // Once it falls out of the trailing window it no longer counts
+    env.ledger().with_mut(|ledger| ledger.timestamp += SECONDS_PER_YEAR);
+    assert_eq!(setup.client.get_investor_yield(&alice, &asset_id), 0);
+    assert_eq!(setup.client.get_investor_yield(&Address::generate(&env), &asset_id), 0);
+}
+
+#[test]
+fn test_platform_fee_follows_the_zones_tier_before_payouts() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = setup(&env);
+    let governance = StubGovernanceClient::new(&env, &link_governance(&env, &setup));
+    governance.set_fee_bps(&200);
+    let (ebike, bus) = (symbol_short!("ebike_001"), symbol_short!("bus_001"));
+    setup.pool.list_asset(&bus, &90, &symbol_short!("metro"));
+    let alice = Address::generate(&env);
+    setup.pool.hold(&ebike, &alice, &1_000, &0);
+    setup.pool.hold(&bus, &alice, &1_000, &0);
+
+    // A score-60 zone pays 2% off the top before the emergency topup
+    fund_revenue(&env, &setup, &ebike, 1_000, 100, 0);
+    let distribution_id = setup.client.distribute_revenue(&setup.admin, &ebike);
+    let distribution = setup.client.get_distribution(&distribution_id);
+    assert_eq!(distribution.platform_fee, 20);
+    assert_eq!(distribution.emergency_topup, 9);
+    assert_eq!(setup.client.claim(&alice, &distribution_id), 971);
+    assert_eq!(setup.token.balance(&alice), 971);
+
+    // A score-90 zone is in the free tier
+    fund_revenue(&env, &setup, &bus, 1_000, 100, 0);
+    let distribution_id = setup.client.distribute_revenue(&setup.admin, &bus);
+    assert_eq!(setup.client.get_distribution(&distribution_id).platform_fee, 0);
+    assert_eq!(setup.client.claim(&alice, &distribution_id), 990);
+    assert_eq!(setup.token.balance(&alice), 1_961);
+}
+
+#[test]
+fn test_tax_summary_totals_the_year_and_pages_its_events() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = setup(&env);
+    StubGovernanceClient::new(&env, &link_governance(&env, &setup)).set_fee_bps(&200);
+    let asset_id = symbol_short!("ebike_001");
+    let alice = Address::generate(&env);
+    setup.pool.hold(&asset_id, &alice, &1_000, &0);
+    let schedule = EpochSchedule::default_schedule();
+    let (year_start, next_year) = (schedule.year_start(2025), schedule.year_start(2026));
+
+    env.ledger().with_mut(|ledger| ledger.timestamp = year_start + 86_400);
+    fund_revenue(&env, &setup, &asset_id, 1_000, 100, 0);
+    setup.client.distribute_revenue(&setup.admin, &asset_id);
+    setup.pool.log_principal(&alice, &symbol_short!("redeemed"), &asset_id, &500, &(year_start + 2 * 86_400));
+    setup.pool.log_principal(&alice, &symbol_short!("writeoff"), &asset_id, &100, &(year_start + 3 * 86_400));
+    setup.pool.log_principal(&alice, &symbol_short!("redeemed"), &asset_id, &300, &next_year);
+
+    // 971 paid out of 980 net revenue, carrying 19 of the 20 platform fee
+    let summary = setup.client.get_tax_summary(&alice, &2025, &0, &3);
+    assert_eq!(summary.total_distributions, 971);
+    assert_eq!(summary.total_fees, 19);
+    assert_eq!(summary.total_principal_returned, 500);
+    assert_eq!(summary.total_writeoffs, 100);
+    let kinds = [symbol_short!("dist"), symbol_short!("fee"), symbol_short!("redeemed")];
+    assert!(summary.events.iter().map(|event| event.kind).eq(kinds));
+    assert_eq!(summary.events.get(0).unwrap().timestamp, year_start + 86_400);
+    assert_eq!(summary.next_cursor, 3);
+
+    // Totals cover the whole year on every page
+    let rest = setup.client.get_tax_summary(&alice, &2025, &summary.next_cursor, &3);
+    assert_eq!(rest.total_distributions, 971);
+    assert_eq!(rest.events.len(), 1);
+    assert_eq!(rest.events.get(0).unwrap().kind, symbol_short!("writeoff"));
+    assert_eq!(rest.next_cursor, 0);
+
+    let next = setup.client.get_tax_summary(&alice, &2026, &0, &10);
+    assert_eq!((next.total_distributions, next.total_principal_returned), (0, 300));
+    assert_eq!(setup.client.try_get_tax_summary(&alice, &2025, &0, &0), Err(Ok(ContractError::InvalidInput)));
+}
+
+#[test]
+fn test_accessible_assets_impact_is_tracked_apart_from_the_totals() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let setup = setup(&env);
+    let (bikes, shuttle) = (symbol_short!("ebike_001"), symbol_short!("bus_001"));
+    setup.pool.list_asset(&shuttle, &60, &symbol_short!("metro"));
+    setup.pool.attest_accessibility(&shuttle, &symbol_short!("wheelchr"));
+
+    setup.client.record_revenue(&bikes, &1_000, &100, &40, &10);
+    setup.client.record_revenue(&shuttle, &500, &30, &25, &20);
+    assert_eq!(setup.client.get_impact_metrics(), (65, 130, 30));
+    assert_eq!(setup.client.get_accessible_impact_metrics(), (25, 30, 20));
+}
@@ -0,0 +1,253 @@
+#![no_std]
+//! SEP-41 token for one asset's shares.
+//!
+//! The loan pool keeps the share ledger, one share per open investment unit.
+//! Each asset gets its own instance of this contract, linked to it with the
+//! loan pool's `set_share_token`, so wallets and exchanges can hold and move
+//! the shares through the standard token interface. Balances are read from
+//! the loan pool and transfers settle there, so the investment records and
+//! revenue claims behind the shares go with them. Allowances are kept here.
+//! Shares only leave circulation by redemption at the loan pool, so `burn`
+//! and `burn_from` always fail.
+use soroban_sdk::{
+    contract, contractimpl, contracttype, panic_with_error, symbol_short, token::TokenInterface, Address, BytesN, Env,
+    String, Symbol,
+};
+
+use mobility_types::{access, ttl, upgrade, LoanPoolClient, Role, ShareTokenInterface, TokenClient, TtlConfig};
+
+mobility_types::contract_errors! {}
+
+/// Shares a spender may move on a holder's behalf
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Allowance {
+    pub amount: i128,
+    pub expiration_ledger: u32, // Allowance is void after this ledger
+}
+
+/// Contract data structure
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DataKey {
+    pub admin: Address,
+    pub loan_pool: Address, // Keeps the share ledger
+    pub asset_id: Symbol,
+    pub name: String,
+    pub symbol: String,
+}
+
+/// Persistent entries for per-record state
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum StorageKey {
+    Allowance(Address, Address), // (holder, spender) -> Allowance
+}
+
+const DATA_KEY: Symbol = symbol_short!("DATA_KEY");
+
+// Set once by initialize to the storage layout version; its presence marks the contract initialized
+const INIT_KEY: Symbol = symbol_short!("INIT");
+const CONTRACT_VERSION: u32 = 1;
+
+// Decimals when the loan pool has no settlement asset configured, matching Stellar Asset Contracts
+const DEFAULT_DECIMALS: u32 = 7;
+
+#[contract]
+pub struct ShareToken;
+
+#[contractimpl]
+impl ShareToken {
+    /// Initialize the token for an asset's shares at a loan pool. Can only be called once.
+    pub fn initialize(
+        env: &Env,
+        admin: Address,
+        loan_pool: Address,
+        asset_id: Symbol,
+        name: String,
+        symbol: String,
+    ) -> Result<(), ContractError> {
+        admin.require_auth();
+
+        if env.storage().instance().has(&INIT_KEY) || env.storage().instance().has(&DATA_KEY) {
+            return Err(ContractError::AlreadyInitialized);
+        }
+
+        let data = DataKey { admin, loan_pool, asset_id, name, symbol };
+        ttl::set_instance(env, &DATA_KEY, &data);
+        ttl::set_instance(env, &INIT_KEY, &CONTRACT_VERSION);
+
+        Ok(())
+    }
+
+    /// Get the storage layout version recorded at initialization, or 0 if uninitialized
+    pub fn get_version(env: &Env) -> u32 {
+        env.storage().instance().get(&INIT_KEY).unwrap_or(0)
+    }
+
+    /// Version of the running code; `get_version` lags behind it until `migrate` runs
+    pub fn version(_env: &Env) -> u32 {
+        CONTRACT_VERSION
+    }
+
+    /// Replace the contract's code with an uploaded wasm, keeping its storage (admin only).
+    /// The new code's `migrate` must run before storage written by the old layout is used
+    pub fn upgrade(env: &Env, caller: Address, new_wasm_hash: BytesN<32>) -> Result<(), ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+        upgrade::upgrade(env, new_wasm_hash);
+
+        Ok(())
+    }
+
+    /// Bring storage up to this code's layout version after an upgrade (admin only)
+    pub fn migrate(env: &Env, caller: Address) -> Result<u32, ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+
+        let stored: u32 = env.storage().instance().get(&INIT_KEY).unwrap_or(0);
+        let version = upgrade::migrate(env, stored, CONTRACT_VERSION, Self::migrate_step)?;
+        ttl::set_instance(env, &INIT_KEY, &version);
+
+        Ok(version)
+    }
+
+    /// Per-version migration steps; layout version 1 has nothing to migrate
+    fn migrate_step(_env: &Env, _from: u32) -> Result<(), ContractError> {
+        Ok(())
+    }
+
+    /// Set the remaining TTL below which writes extend an entry, and the TTL they extend it to (admin only)
+    pub fn set_ttl_config(env: &Env, caller: Address, threshold: u32, extend_to: u32) -> Result<(), ContractError> {
+        let data: DataKey = env.storage().instance().get(&DATA_KEY).unwrap();
+
+        access::check(env, &data.admin, Role::Admin, &caller)?;
+        ttl::set_config(env, threshold, extend_to).map_err(ContractError::from)
+    }
+
+    /// Get the storage TTL configuration
+    pub fn get_ttl_config(env: &Env) -> TtlConfig {
+        ttl::config(env)
+    }
+
+    fn data(env: &Env) -> DataKey {
+        env.storage().instance().get(&DATA_KEY).unwrap()
+    }
+
+    /// Allowance a holder granted a spender, zero once it expires
+    fn allowance_of(env: &Env, from: &Address, spender: &Address) -> Allowance {
+        let allowance: Option<Allowance> = env
+            .storage()
+            .persistent()
+            .get(&StorageKey::Allowance(from.clone(), spender.clone()));
+        match allowance {
+            Some(allowance) if allowance.expiration_ledger >= env.ledger().sequence() => allowance,
+            _ => Allowance { amount: 0, expiration_ledger: 0 },
+        }
+    }
+
+    fn spend_allowance(env: &Env, from: &Address, spender: &Address, amount: i128) {
+        let mut allowance = Self::allowance_of(env, from, spender);
+        if allowance.amount < amount {
+            panic_with_error!(env, ContractError::NoFunds);
+        }
+
+        allowance.amount -= amount;
+        ttl::set_persistent(env, &StorageKey::Allowance(from.clone(), spender.clone()), &allowance);
+    }
+
+    /// Settle a transfer at the loan pool, which refuses shares queued for redemption or
+    /// backing minted positions
+    fn move_shares(env: &Env, from: Address, to: Address, amount: i128) {
+        if amount < 0 {
+            panic_with_error!(env, ContractError::InvalidAmount);
+        }
+        let data = Self::data(env);
+        LoanPoolClient::new(env, &data.loan_pool).move_shares(&data.asset_id, &from, &to, &amount);
+        env.events().publish((symbol_short!("transfer"), from, to), amount);
+    }
+}
+
+#[contractimpl]
+impl TokenInterface for ShareToken {
+    fn allowance(env: Env, from: Address, spender: Address) -> i128 {
+        Self::allowance_of(&env, &from, &spender).amount
+    }
+
+    fn approve(env: Env, from: Address, spender: Address, amount: i128, expiration_ledger: u32) {
+        from.require_auth();
+
+        if amount < 0 {
+            panic_with_error!(&env, ContractError::InvalidAmount);
+        }
+        if amount > 0 && expiration_ledger < env.ledger().sequence() {
+            panic_with_error!(&env, ContractError::Expired);
+        }
+
+        let key = StorageKey::Allowance(from.clone(), spender.clone());
+        if amount == 0 {
+            env.storage().persistent().remove(&key);
+        } else {
+            ttl::set_persistent(&env, &key, &Allowance { amount, expiration_ledger });
+        }
+        env.events().publish((symbol_short!("approve"), from, spender), (amount, expiration_ledger));
+    }
+
+    fn balance(env: Env, id: Address) -> i128 {
+        let data = Self::data(&env);
+        LoanPoolClient::new(&env, &data.loan_pool).share_balance(&data.asset_id, &id)
+    }
+
+    fn transfer(env: Env, from: Address, to: Address, amount: i128) {
+        from.require_auth();
+        Self::move_shares(&env, from, to, amount);
+    }
+
+    fn transfer_from(env: Env, spender: Address, from: Address, to: Address, amount: i128) {
+        spender.require_auth();
+        Self::spend_allowance(&env, &from, &spender, amount);
+        Self::move_shares(&env, from, to, amount);
+    }
+
+    fn burn(env: Env, _from: Address, _amount: i128) {
+        panic_with_error!(&env, ContractError::InvalidStatus);
+    }
+
+    fn burn_from(env: Env, _spender: Address, _from: Address, _amount: i128) {
+        panic_with_error!(&env, ContractError::InvalidStatus);
+    }
+
+    /// Units are issued at par, so shares carry the settlement asset's decimals
+    fn decimals(env: Env) -> u32 {
+        let data = Self::data(&env);
+        match LoanPoolClient::new(&env, &data.loan_pool).get_settlement_asset() {
+            Some(token) => TokenClient::new(&env, &token).decimals(),
+            None => DEFAULT_DECIMALS,
+        }
+    }
+
+    fn name(env: Env) -> String {
+        Self::data(&env).name
+    }
+
+    fn symbol(env: Env) -> String {
+        Self::data(&env).symbol
+    }
+}
+
+#[contractimpl]
+impl ShareTokenInterface for ShareToken {
+    /// Get the loan pool that keeps the share ledger
+    fn get_loan_pool(env: &Env) -> Address {
+        Self::data(env).loan_pool
+    }
+
+    /// Get the asset whose shares the token stands for
+    fn get_asset_id(env: &Env) -> Symbol {
+        Self::data(env).asset_id
+    }
+}
+
+mod test;
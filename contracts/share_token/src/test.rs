@@ -0,0 +1,105 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    contract, contractimpl, symbol_short, testutils::{Address as _, Ledger}, Address, Env, Error, Symbol,
+};
+
+/// Stands in for the loan pool: keeps one asset's share balances and moves them for the token
+#[contract]
+struct StubLoanPool;
+
+#[contractimpl]
+impl StubLoanPool {
+    pub fn issue(env: Env, holder: Address, amount: i128) {
+        env.storage().persistent().set(&holder, &amount);
+    }
+
+    pub fn get_settlement_asset(_env: Env) -> Option<Address> {
+        None
+    }
+
+    pub fn share_balance(env: Env, _asset_id: Symbol, id: Address) -> i128 {
+        env.storage().persistent().get(&id).unwrap_or(0)
+    }
+
+    pub fn move_shares(env: Env, asset_id: Symbol, from: Address, to: Address, amount: i128) -> Result<(), Error> {
+        let balance = Self::share_balance(env.clone(), asset_id.clone(), from.clone());
+        if balance < amount {
+            return Err(ContractError::NoFunds.into());
+        }
+        Self::issue(env.clone(), from, balance - amount);
+        let received = Self::share_balance(env.clone(), asset_id, to.clone());
+        Self::issue(env, to, received + amount);
+        Ok(())
+    }
+}
+
+/// Register a token for ebike_001's shares against a stub loan pool, with `holder` holding 500
+fn setup<'a>(env: &'a Env, holder: &Address) -> (ShareTokenClient<'a>, StubLoanPoolClient<'a>) {
+    let pool = StubLoanPoolClient::new(env, &env.register_contract(None, StubLoanPool));
+    pool.issue(holder, &500);
+
+    let client = ShareTokenClient::new(env, &env.register_contract(None, ShareToken));
+    client.initialize(
+        &Address::generate(env),
+        &pool.address,
+        &symbol_short!("ebike_001"),
+        &String::from_str(env, "E-bike 001 shares"),
+        &String::from_str(env, "EBK001"),
+    );
+
+    (client, pool)
+}
+
+#[test]
+fn test_token_describes_the_assets_shares() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let holder = Address::generate(&env);
+    let (client, pool) = setup(&env, &holder);
+
+    assert_eq!(client.name(), String::from_str(&env, "E-bike 001 shares"));
+    assert_eq!(client.symbol(), String::from_str(&env, "EBK001"));
+    assert_eq!(client.decimals(), 7);
+    assert_eq!(client.get_loan_pool(), pool.address);
+    assert_eq!(client.get_asset_id(), symbol_short!("ebike_001"));
+    assert_eq!(client.balance(&holder), 500);
+}
+
+#[test]
+fn test_transfer_settles_at_the_loan_pool() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (holder, recipient) = (Address::generate(&env), Address::generate(&env));
+    let (client, pool) = setup(&env, &holder);
+
+    client.transfer(&holder, &recipient, &200);
+    assert_eq!(env.auths()[0].0, holder);
+    assert_eq!(client.balance(&holder), 300);
+    assert_eq!(pool.share_balance(&symbol_short!("ebike_001"), &recipient), 200);
+}
+
+#[test]
+fn test_transfer_from_spends_the_allowance() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (holder, spender, recipient) = (Address::generate(&env), Address::generate(&env), Address::generate(&env));
+    let (client, _) = setup(&env, &holder);
+    let expiration_ledger = env.ledger().sequence() + 100;
+
+    client.approve(&holder, &spender, &300, &expiration_ledger);
+    client.transfer_from(&spender, &holder, &recipient, &200);
+    assert_eq!(env.auths()[0].0, spender);
+    assert_eq!(client.allowance(&holder, &spender), 100);
+    assert_eq!(client.balance(&recipient), 200);
+
+    // Allowances lapse after their ledger, and approving zero revokes one
+    env.ledger().with_mut(|ledger| ledger.sequence_number = expiration_ledger + 1);
+    assert_eq!(client.allowance(&holder, &spender), 0);
+    client.approve(&holder, &spender, &300, &(expiration_ledger + 100));
+    assert_eq!(client.allowance(&holder, &spender), 300);
+    client.approve(&holder, &spender, &0, &0);
+    assert_eq!(client.allowance(&holder, &spender), 0);
+}
+
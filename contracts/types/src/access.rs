@@ -0,0 +1,58 @@
+//! Role-based access control.
+//!
+//! Each contract keeps a role table in persistent storage and every privileged
+//! entrypoint takes its caller, requires the caller's authorization and checks
+//! the one role its action needs, instead of comparing against a single admin
+//! address. The admin set at initialization holds every role implicitly, and
+//! holders of `Role::Admin` pass any check.
+use soroban_sdk::{contracttype, symbol_short, Address, Env, Symbol};
+
+use crate::{ttl, ContractError};
+
+// (ROLE_KEY, role, account) -> true while the account holds the role
+const ROLE_KEY: Symbol = symbol_short!("ROLE");
+
+/// Privileges an entrypoint can require
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Role {
+    Admin, // Configuration, listings and role management
+    OracleManager, // Oracle addresses and signing keys
+    Treasurer, // Moving funds out of or into contract balances
+    Pauser, // Pause switches and feature flags
+    Executor, // Executing passed proposals and timelocked changes
+}
+
+/// Whether an account holds a role, counting the contract's admin as holding all of them
+pub fn holds(env: &Env, admin: &Address, role: Role, account: &Address) -> bool {
+    let storage = env.storage().persistent();
+    account == admin
+        || storage.has(&(ROLE_KEY, Role::Admin, account.clone()))
+        || storage.has(&(ROLE_KEY, role, account.clone()))
+}
+
+/// Require `caller` to authorize the invocation and hold a role
+pub fn check(env: &Env, admin: &Address, role: Role, caller: &Address) -> Result<(), ContractError> {
+    caller.require_auth();
+    if !holds(env, admin, role, caller) {
+        return Err(ContractError::Unauthorized);
+    }
+    Ok(())
+}
+
+/// Give an account a role
+pub fn grant(env: &Env, role: Role, account: &Address) {
+    ttl::set_persistent(env, &(ROLE_KEY, role, account.clone()), &true);
+    env.events().publish((symbol_short!("role_add"), account.clone()), role);
+}
+
+/// Take a role away from an account that was granted it
+pub fn revoke(env: &Env, role: Role, account: &Address) -> Result<(), ContractError> {
+    let key = (ROLE_KEY, role, account.clone());
+    if !env.storage().persistent().has(&key) {
+        return Err(ContractError::NotFound);
+    }
+    env.storage().persistent().remove(&key);
+    env.events().publish((symbol_short!("role_del"), account.clone()), role);
+    Ok(())
+}
@@ -0,0 +1,121 @@
+//! Multi-signer approvals for large disbursements.
+//!
+//! Each contract holds an `ApprovalRule` and a map of `PendingAction`s. Any
+//! disbursement above the rule's threshold has to match a pending action that
+//! K distinct approvers signed off on before it expired.
+use soroban_sdk::{contracttype, vec, Address, Env, Symbol, Vec};
+
+use crate::calendar::SECONDS_PER_DAY;
+use crate::ContractError;
+
+/// Pending actions that haven't executed within this window can no longer be used
+pub const APPROVAL_TTL: u64 = 3 * SECONDS_PER_DAY;
+
+/// Amount above which disbursements need approvals, and who can give them
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ApprovalRule {
+    pub threshold: i128, // Disbursements above this need approvals
+    pub required: u32, // Distinct approvals needed (K); 0 turns the rule off
+    pub approvers: Vec<Address>, // Admin and guardian addresses allowed to approve
+}
+
+/// A large disbursement waiting on approvals
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingAction {
+    pub id: u32,
+    pub kind: Symbol, // e.g. "withdraw", "spend", "dist"
+    pub reference: Symbol, // Asset, proposal or distribution the action applies to
+    pub recipient: Payee,
+    pub amount: i128, // Most the action may disburse
+    pub approvals: Vec<Address>,
+    pub created_at: u64,
+}
+
+/// Who a pending action may pay
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Payee {
+    Any, // Not tied to one payee, e.g. a distribution
+    Account(Address), // The one payee of a withdrawal
+}
+
+impl From<Option<Address>> for Payee {
+    fn from(recipient: Option<Address>) -> Self {
+        match recipient {
+            Some(account) => Payee::Account(account),
+            None => Payee::Any,
+        }
+    }
+}
+
+impl ApprovalRule {
+    /// Rule with no threshold, so nothing needs approvals
+    pub fn disabled(env: &Env) -> Self {
+        ApprovalRule {
+            threshold: 0,
+            required: 0,
+            approvers: vec![env],
+        }
+    }
+
+    /// Whether the rule can be met by its own approvers
+    pub fn is_valid(&self) -> bool {
+        if self.threshold < 0 || self.required > self.approvers.len() {
+            return false;
+        }
+        for (i, approver) in self.approvers.iter().enumerate() {
+            if self.approvers.first_index_of(&approver) != Some(i as u32) {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Whether a disbursement of this size needs approvals
+    pub fn applies(&self, amount: i128) -> bool {
+        self.required > 0 && amount > self.threshold
+    }
+}
+
+impl PendingAction {
+    /// Whether the action is past its approval window
+    pub fn is_expired(&self, now: u64) -> bool {
+        now >= self.created_at + APPROVAL_TTL
+    }
+
+    /// Record an approval from one of the rule's approvers
+    pub fn approve(&mut self, rule: &ApprovalRule, approver: &Address, now: u64) -> Result<u32, ContractError> {
+        if !rule.approvers.contains(approver) {
+            return Err(ContractError::Unauthorized);
+        }
+        if self.is_expired(now) {
+            return Err(ContractError::Expired);
+        }
+        if self.approvals.contains(approver) {
+            return Err(ContractError::DuplicateApproval);
+        }
+        self.approvals.push_back(approver.clone());
+        Ok(self.approvals.len())
+    }
+
+    /// Whether the action has enough approvals from current approvers to cover a disbursement
+    pub fn covers(
+        &self,
+        rule: &ApprovalRule,
+        kind: &Symbol,
+        reference: &Symbol,
+        recipient: &Payee,
+        amount: i128,
+        now: u64,
+    ) -> bool {
+        let approved = self.approvals.iter().filter(|a| rule.approvers.contains(a)).count() as u32;
+        self.kind == *kind
+            && self.reference == *reference
+            && self.recipient == *recipient
+            && amount <= self.amount
+            && approved >= rule.required
+            && !self.is_expired(now)
+    }
+}
@@ -0,0 +1,182 @@
+//! Signed oracle submissions.
+//!
+//! The oracle signs `(contract, function, nonce, timestamp, values)` with an
+//! ed25519 key registered on the receiving contract. Nonces must strictly
+//! increase and the timestamp must fall inside the signer's freshness window,
+//! so a captured payload can be neither replayed nor submitted late.
+//!
+//! A contract can register several feeder keys, each with its own nonce. A
+//! feeder can be disabled on its own, and a rotation schedules a replacement
+//! key to take over at a set time, so a compromised key is cut off without
+//! touching the oracle address consumers were initialized with.
+use soroban_sdk::{contracttype, symbol_short, vec, xdr::ToXdr, BytesN, Env, IntoVal, Symbol, Val, Vec};
+
+use crate::{ttl, ContractError};
+
+/// Oracle key that signs data submissions, with its replay state
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OracleSigner {
+    pub public_key: BytesN<32>,
+    pub last_nonce: u64, // Highest nonce accepted; the next payload must exceed it
+    pub max_age: u64, // Seconds a signed payload stays valid after its timestamp
+}
+
+impl OracleSigner {
+    pub fn new(public_key: BytesN<32>, max_age: u64) -> Self {
+        OracleSigner { public_key, last_nonce: 0, max_age }
+    }
+
+    /// Check the signature over a submission to `function` and consume its nonce
+    pub fn verify<V>(
+        &mut self,
+        env: &Env,
+        function: Symbol,
+        values: V,
+        nonce: u64,
+        timestamp: u64,
+        signature: &BytesN<64>,
+    ) -> Result<(), ContractError>
+    where
+        V: IntoVal<Env, Val>,
+    {
+        if nonce <= self.last_nonce {
+            return Err(ContractError::BadNonce);
+        }
+        let now = env.ledger().timestamp();
+        if timestamp > now {
+            return Err(ContractError::TooEarly);
+        }
+        if now - timestamp > self.max_age {
+            return Err(ContractError::Stale);
+        }
+
+        let payload = (env.current_contract_address(), function, nonce, timestamp, values.into_val(env)).to_xdr(env);
+
+        // Panics if the signature does not match
+        env.crypto().ed25519_verify(&self.public_key, &payload, signature);
+
+        self.last_nonce = nonce;
+        Ok(())
+    }
+}
+
+// (FEEDER_KEY, public_key) -> FeederKey
+const FEEDER_KEY: Symbol = symbol_short!("FEEDER");
+// FEEDERS_KEY -> Vec<BytesN<32>> of every registered feeder, in registration order
+const FEEDERS_KEY: Symbol = symbol_short!("FEEDERS");
+
+/// Feeder key allowed to sign oracle submissions during its active window
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeederKey {
+    pub signer: OracleSigner,
+    pub enabled: bool,
+    pub active_from: u64, // Keys scheduled by a rotation verify nothing before this
+    pub retires_at: Option<u64>, // Set when a rotation schedules this key's replacement
+}
+
+impl FeederKey {
+    /// Whether the key may sign submissions at `now`
+    pub fn is_active(&self, now: u64) -> bool {
+        self.enabled && now >= self.active_from && self.retires_at.is_none_or(|at| now < at)
+    }
+}
+
+/// Register a feeder key that becomes active at `active_from`
+pub fn register_feeder(env: &Env, public_key: &BytesN<32>, max_age: u64, active_from: u64) -> Result<(), ContractError> {
+    let key = (FEEDER_KEY, public_key.clone());
+    if env.storage().persistent().has(&key) {
+        return Err(ContractError::KeyExists);
+    }
+
+    ttl::set_persistent(
+        env,
+        &key,
+        &FeederKey {
+            signer: OracleSigner::new(public_key.clone(), max_age),
+            enabled: true,
+            active_from,
+            retires_at: None,
+        },
+    );
+    let mut feeders: Vec<BytesN<32>> = env.storage().persistent().get(&FEEDERS_KEY).unwrap_or(vec![env]);
+    feeders.push_back(public_key.clone());
+    ttl::set_persistent(env, &FEEDERS_KEY, &feeders);
+
+    env.events().publish((symbol_short!("feeder"), public_key.clone()), active_from);
+    Ok(())
+}
+
+/// Enable or disable one feeder key without affecting the others
+pub fn set_feeder_enabled(env: &Env, public_key: &BytesN<32>, enabled: bool) -> Result<(), ContractError> {
+    let mut feeder = load_feeder(env, public_key)?;
+    feeder.enabled = enabled;
+    ttl::set_persistent(env, &(FEEDER_KEY, public_key.clone()), &feeder);
+
+    env.events().publish((symbol_short!("feeder_on"), public_key.clone()), enabled);
+    Ok(())
+}
+
+/// Schedule `new_key` to replace `old_key` at `effective_at`; the old key keeps signing until then
+pub fn rotate_feeder(
+    env: &Env,
+    old_key: &BytesN<32>,
+    new_key: &BytesN<32>,
+    max_age: u64,
+    effective_at: u64,
+) -> Result<(), ContractError> {
+    let mut old = load_feeder(env, old_key)?;
+    if effective_at < env.ledger().timestamp() {
+        return Err(ContractError::InvalidInput);
+    }
+
+    register_feeder(env, new_key, max_age, effective_at)?;
+    old.retires_at = Some(effective_at);
+    ttl::set_persistent(env, &(FEEDER_KEY, old_key.clone()), &old);
+
+    env.events().publish((symbol_short!("rotate"), old_key.clone()), (new_key.clone(), effective_at));
+    Ok(())
+}
+
+/// Check a submission signed by a registered feeder and consume that feeder's nonce
+pub fn verify_feeder<V>(
+    env: &Env,
+    public_key: &BytesN<32>,
+    function: Symbol,
+    values: V,
+    nonce: u64,
+    timestamp: u64,
+    signature: &BytesN<64>,
+) -> Result<(), ContractError>
+where
+    V: IntoVal<Env, Val>,
+{
+    let mut feeder = load_feeder(env, public_key)?;
+    if !feeder.is_active(env.ledger().timestamp()) {
+        return Err(ContractError::KeyInactive);
+    }
+
+    feeder.signer.verify(env, function, values, nonce, timestamp, signature)?;
+    ttl::set_persistent(env, &(FEEDER_KEY, public_key.clone()), &feeder);
+    Ok(())
+}
+
+/// Every registered feeder key, in registration order
+pub fn feeders(env: &Env) -> Vec<FeederKey> {
+    let keys: Vec<BytesN<32>> = env.storage().persistent().get(&FEEDERS_KEY).unwrap_or(vec![env]);
+    let mut feeders = vec![env];
+    for key in keys.iter() {
+        if let Ok(feeder) = load_feeder(env, &key) {
+            feeders.push_back(feeder);
+        }
+    }
+    feeders
+}
+
+fn load_feeder(env: &Env, public_key: &BytesN<32>) -> Result<FeederKey, ContractError> {
+    env.storage()
+        .persistent()
+        .get(&(FEEDER_KEY, public_key.clone()))
+        .ok_or(ContractError::NotFound)
+}
@@ -0,0 +1,81 @@
+//! Shared epoch calendar.
+//!
+//! Distribution epochs, budget cycles and calendar years are all derived from
+//! one `EpochSchedule`, held by governance and read by the other contracts.
+use soroban_sdk::contracttype;
+
+pub const SECONDS_PER_DAY: u64 = 86_400;
+
+/// Configurable schedule that every epoch, cycle and year boundary is derived from
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EpochSchedule {
+    pub start: u64, // Timestamp at which epoch 0 begins
+    pub length: u64, // Epoch length in seconds
+    pub epochs_per_cycle: u32, // Epochs in one budget cycle (e.g. rebate quarter)
+    pub tz_offset: i64, // Seconds east of UTC for day and year boundaries
+}
+
+impl EpochSchedule {
+    /// Monthly epochs from the Unix epoch, three per quarterly cycle, in UTC
+    pub fn default_schedule() -> Self {
+        EpochSchedule {
+            start: 0,
+            length: 2_629_800,
+            epochs_per_cycle: 3,
+            tz_offset: 0,
+        }
+    }
+
+    /// Whether the schedule can be used for epoch math
+    pub fn is_valid(&self) -> bool {
+        self.length > 0 && self.epochs_per_cycle > 0
+    }
+
+    /// Epoch containing a timestamp; times before the schedule start fall in epoch 0
+    pub fn epoch_at(&self, timestamp: u64) -> u32 {
+        if timestamp < self.start {
+            return 0;
+        }
+        ((timestamp - self.start) / self.length) as u32
+    }
+
+    /// First second of an epoch
+    pub fn epoch_start(&self, epoch: u32) -> u64 {
+        self.start + epoch as u64 * self.length
+    }
+
+    /// First second after an epoch
+    pub fn epoch_end(&self, epoch: u32) -> u64 {
+        self.epoch_start(epoch + 1)
+    }
+
+    /// Budget cycle containing a timestamp
+    pub fn cycle_at(&self, timestamp: u64) -> u32 {
+        self.epoch_at(timestamp) / self.epochs_per_cycle
+    }
+
+    /// Epochs that begin within a year, as [first, end); each epoch counts toward one year only
+    pub fn epochs_in_year(&self, year: u32) -> (u32, u32) {
+        let first_epoch_from = |timestamp: u64| {
+            let epoch = self.epoch_at(timestamp);
+            if self.epoch_start(epoch) < timestamp { epoch + 1 } else { epoch }
+        };
+        (first_epoch_from(self.year_start(year)), first_epoch_from(self.year_start(year + 1)))
+    }
+
+    /// Local midnight on Jan 1 of a year (1970 or later), as a UTC timestamp
+    pub fn year_start(&self, year: u32) -> u64 {
+        let leap_years = |y: u64| y / 4 - y / 100 + y / 400;
+        let y = year as u64;
+        let days = 365 * (y - 1970) + leap_years(y - 1) - leap_years(1969);
+        let midnight = days * SECONDS_PER_DAY;
+
+        // Midnight local time is earlier in UTC for zones east of Greenwich
+        if self.tz_offset >= 0 {
+            midnight.saturating_sub(self.tz_offset as u64)
+        } else {
+            midnight + self.tz_offset.unsigned_abs()
+        }
+    }
+}
@@ -0,0 +1,120 @@
+//! Last-modified metadata for incremental sync by off-chain indexers.
+//!
+//! Every write to a tracked record stamps the record's last-modified ledger and
+//! appends `(ledger, id)` to a per-kind journal in persistent storage. Journal
+//! entries superseded by a later write to the same record are skipped on read,
+//! so each changed record is returned once, at its latest ledger. The journal is
+//! read a page at a time, with the cursor pointing into the journal itself.
+//!
+//! When a notification relay is configured, each journal entry is also pushed
+//! to it as a compact record that external services can poll without running
+//! an indexer.
+use soroban_sdk::{contracttype, symbol_short, vec, Address, Env, IntoVal, Symbol, TryFromVal, Val, Vec};
+
+use crate::{ttl, PageRequest, RelayClient, MAX_PAGE_SIZE};
+
+// (MODIFIED_KEY, kind, id) -> last-modified ledger
+const MODIFIED_KEY: Symbol = symbol_short!("MODIFIED");
+// (JOURNAL_KEY, kind) -> entry count; (JOURNAL_KEY, kind, index) -> (ledger, id)
+const JOURNAL_KEY: Symbol = symbol_short!("JOURNAL");
+// RELAY_KEY -> notification relay that journal entries are pushed to
+const RELAY_KEY: Symbol = symbol_short!("RELAY");
+
+/// A record that changed, at the ledger it was last modified in
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecordChange {
+    pub id: Symbol,
+    pub ledger: u32,
+}
+
+crate::page_result!(RecordChangePage, RecordChange);
+
+/// Record that a record of the given kind changed in the current ledger
+pub fn touch<K>(env: &Env, kind: Symbol, id: K)
+where
+    K: IntoVal<Env, Val> + TryFromVal<Env, Val> + Clone,
+    Val: TryFromVal<Env, K>,
+{
+    let ledger = env.ledger().sequence();
+    let storage = env.storage().persistent();
+
+    // A record touched twice in one ledger keeps its existing journal entry
+    let modified_key = (MODIFIED_KEY, kind.clone(), id.clone());
+    if storage.get::<_, u32>(&modified_key) == Some(ledger) {
+        return;
+    }
+    ttl::set_persistent(env, &modified_key, &ledger);
+
+    let count_key = (JOURNAL_KEY, kind.clone());
+    let count: u32 = storage.get(&count_key).unwrap_or(0);
+    ttl::set_persistent(env, &(JOURNAL_KEY, kind.clone(), count), &(ledger, id.clone()));
+    ttl::set_persistent(env, &count_key, &(count + 1));
+
+    // A relay that rejects the notification must not block the write itself
+    if let Some(relay) = relay(env) {
+        let _ = RelayClient::new(env, &relay).try_notify(&env.current_contract_address(), &kind, &id.into_val(env));
+    }
+}
+
+/// Push journal entries to a notification relay, or stop pushing them with None
+pub fn set_relay(env: &Env, relay: Option<Address>) {
+    match relay {
+        Some(relay) => ttl::set_persistent(env, &RELAY_KEY, &relay),
+        None => env.storage().persistent().remove(&RELAY_KEY),
+    }
+}
+
+/// Notification relay journal entries are pushed to, if one is configured
+pub fn relay(env: &Env) -> Option<Address> {
+    env.storage().persistent().get(&RELAY_KEY)
+}
+
+/// One page of the records of a kind modified after `ledger_seq`, in ledger order, each mapped
+/// with `load` from its id and last-modified ledger. The first page starts at the first journal
+/// entry after `ledger_seq`; later pages follow `next_cursor`. A page scans up to `limit` journal
+/// entries, so it can hold fewer items when entries were superseded, and returns None as the
+/// cursor once the journal is exhausted
+pub fn changes_since<K, T, F>(env: &Env, kind: Symbol, ledger_seq: u32, page: &PageRequest, load: F) -> (Vec<T>, Option<u32>)
+where
+    K: IntoVal<Env, Val> + TryFromVal<Env, Val> + Clone,
+    Val: TryFromVal<Env, K>,
+    T: IntoVal<Env, Val> + TryFromVal<Env, Val>,
+    F: Fn(K, u32) -> T,
+{
+    let storage = env.storage().persistent();
+    let count: u32 = storage.get(&(JOURNAL_KEY, kind.clone())).unwrap_or(0);
+
+    // Journal ledgers never decrease, so binary search for the first entry after ledger_seq
+    let (mut lo, mut hi) = (0u32, count);
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        let (ledger, _): (u32, K) = storage.get(&(JOURNAL_KEY, kind.clone(), mid)).unwrap();
+        if ledger <= ledger_seq {
+            lo = mid + 1;
+        } else {
+            hi = mid;
+        }
+    }
+
+    let start = lo.max(page.cursor).min(count);
+    let end = start.saturating_add(page.limit.min(MAX_PAGE_SIZE)).min(count);
+    let mut items = vec![env];
+    for index in start..end {
+        let (ledger, id): (u32, K) = storage.get(&(JOURNAL_KEY, kind.clone(), index)).unwrap();
+        let latest: Option<u32> = storage.get(&(MODIFIED_KEY, kind.clone(), id.clone()));
+        if latest == Some(ledger) {
+            items.push_back(load(id, ledger));
+        }
+    }
+    (items, (end < count).then_some(end))
+}
+
+/// Ledger a record was last modified in, if it has been tracked
+pub fn last_modified<K>(env: &Env, kind: Symbol, id: K) -> Option<u32>
+where
+    K: IntoVal<Env, Val> + TryFromVal<Env, Val>,
+    Val: TryFromVal<Env, K>,
+{
+    env.storage().persistent().get(&(MODIFIED_KEY, kind, id))
+}
@@ -0,0 +1,94 @@
+//! Error codes.
+//!
+//! `#[contracterror]` enums are capped at 50 variants, so codes are split:
+//! the shared codes below are returned by this crate's helpers and by most
+//! contracts, and each contract declares its own enum with `contract_errors!`,
+//! which repeats the shared codes under the same numbers and appends the
+//! contract's own. Codes are part of the public interface: clients match on
+//! the number, so a code keeps its number in every enum it appears in and is
+//! never renumbered.
+
+/// Expand `$callback` with the shared variants and their codes
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __shared_errors {
+    ($callback:ident $($rest:tt)*) => {
+        $crate::$callback! {
+            [
+                Unauthorized = 1,
+                AlreadyInitialized = 2,
+                NotFound = 3,
+                InvalidInput = 4,
+                InvalidAmount = 5,
+                InvalidStatus = 6,
+                AlreadyVoted = 7,
+                AssetNotFound = 12,
+                BadLegacy = 28,
+                BadNonce = 29,
+                Done = 48,
+                DuplicateApproval = 49,
+                Expired = 53,
+                NeedsApproval = 60,
+                NoAddress = 61,
+                NoFunds = 69,
+                NoTrustline = 92,
+                NotEligible = 97,
+                Nothing = 107,
+                OverLimit = 111,
+                Paused = 112,
+                SameParty = 124,
+                Stale = 129,
+                Timelock = 130,
+                TooEarly = 131,
+                WindDown = 137,
+                FeatureDisabled = 138,
+                NoSettlementAsset = 140,
+                KeyExists = 147,
+                KeyInactive = 148,
+            ]
+            $($rest)*
+        }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __error_enum {
+    ([$($shared:ident = $shared_code:tt,)*] [$($attr:tt)*] $($name:ident = $code:tt,)*) => {
+        #[soroban_sdk::contracterror $($attr)*]
+        #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+        #[repr(u32)]
+        pub enum ContractError {
+            $($shared = $shared_code,)*
+            $($name = $code,)*
+        }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __contract_errors {
+    ([$($shared:ident = $shared_code:tt,)*] $($name:ident = $code:tt,)*) => {
+        $crate::__error_enum!([$($shared = $shared_code,)*] [] $($name = $code,)*);
+
+        impl From<$crate::ContractError> for ContractError {
+            fn from(error: $crate::ContractError) -> Self {
+                match error {
+                    $($crate::ContractError::$shared => ContractError::$shared,)*
+                }
+            }
+        }
+    };
+}
+
+/// Declare a contract's `ContractError`: the shared codes followed by the contract's own.
+/// Errors from this crate's helpers convert into it with `?`
+#[macro_export]
+macro_rules! contract_errors {
+    ($($name:ident = $code:tt),* $(,)?) => {
+        $crate::__shared_errors!(__contract_errors $($name = $code,)*);
+    };
+}
+
+// Contracts export their own enum's spec, so this one stays out of theirs
+__shared_errors!(__error_enum [(export = false)]);
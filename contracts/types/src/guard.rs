@@ -0,0 +1,58 @@
+//! Uniform entrypoint gating.
+//!
+//! State-changing entrypoints start with one `Gate` check instead of their own
+//! inline mix of pause, feature-flag, program-state and role checks, so the same
+//! conditions fail with the same error, in the same order, in every contract.
+use soroban_sdk::{Symbol, Vec};
+
+use crate::ContractError;
+
+/// Conditions one entrypoint call is gated on, read from the contract's own storage
+#[derive(Clone, Debug)]
+pub struct Gate {
+    pub paused: bool,
+    pub feature_disabled: bool,
+    pub winding_down: bool, // The program the call touches is winding down
+    pub has_role: bool, // The caller holds the role the entrypoint requires
+}
+
+impl Gate {
+    /// Gate on the contract's pause flag and the entrypoint's feature flag
+    pub fn new(paused: bool, disabled_features: &Vec<Symbol>, feature: &Symbol) -> Self {
+        Gate {
+            paused,
+            feature_disabled: disabled_features.contains(feature),
+            winding_down: false,
+            has_role: true,
+        }
+    }
+
+    /// Also refuse calls against a program that is winding down
+    pub fn program(mut self, winding_down: bool) -> Self {
+        self.winding_down = winding_down;
+        self
+    }
+
+    /// Also require the caller to hold a role
+    pub fn role(mut self, has_role: bool) -> Self {
+        self.has_role = has_role;
+        self
+    }
+
+    /// Checked in a fixed order: pause, feature flag, program state, role
+    pub fn check(&self) -> Result<(), ContractError> {
+        if self.paused {
+            return Err(ContractError::Paused);
+        }
+        if self.feature_disabled {
+            return Err(ContractError::FeatureDisabled);
+        }
+        if self.winding_down {
+            return Err(ContractError::WindDown);
+        }
+        if !self.has_role {
+            return Err(ContractError::Unauthorized);
+        }
+        Ok(())
+    }
+}
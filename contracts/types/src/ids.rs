@@ -0,0 +1,23 @@
+//! Hash-derived record ids.
+//!
+//! Proposal and application ids are short symbols so they stay cheap to use in
+//! storage keys and events. The caller serializes whatever makes the record
+//! unique (creator, timestamp, per-creator sequence) and the first bytes of its
+//! sha256 are mapped onto the symbol alphabet.
+use soroban_sdk::{Bytes, Env, Symbol};
+
+/// Characters in a derived id; the most a `Symbol` packs into a single value
+pub const ID_LEN: usize = 9;
+
+const ALPHABET: &[u8] = b"abcdefghijklmnopqrstuvwxyz0123456789";
+
+/// Derive an id from the serialized fields that make a record unique
+pub fn derive(env: &Env, preimage: &Bytes) -> Symbol {
+    let hash = env.crypto().sha256(preimage).to_array();
+    let mut id = [0u8; ID_LEN];
+    for (slot, byte) in id.iter_mut().zip(hash.iter()) {
+        *slot = ALPHABET[*byte as usize % ALPHABET.len()];
+    }
+    // Every byte comes from ALPHABET, so the id is valid UTF-8 and a valid symbol
+    Symbol::new(env, core::str::from_utf8(&id).unwrap())
+}
@@ -0,0 +1,66 @@
+//! Append-only record streams.
+//!
+//! Audit trails and histories grow for the life of a contract, so they can't
+//! live in its instance data. Each record is its own persistent entry under
+//! its stream and position, with the stream's length kept beside it. A stream
+//! is named by any key value, e.g. a symbol for a contract-wide log or a
+//! `(symbol, address)` pair for a per-account one, and is read a page at a time.
+use soroban_sdk::{symbol_short, vec, Env, IntoVal, Symbol, TryFromVal, Val, Vec};
+
+use crate::{ttl, PageRequest, MAX_PAGE_SIZE};
+
+// (ENTRY_KEY, stream, index) -> record
+const ENTRY_KEY: Symbol = symbol_short!("JRNL");
+// (LEN_KEY, stream) -> records in the stream
+const LEN_KEY: Symbol = symbol_short!("JRNL_LEN");
+
+/// Append a record to the end of a stream; returns its index
+pub fn append<S, T>(env: &Env, stream: &S, record: &T) -> u32
+where
+    S: Clone,
+    Val: TryFromVal<Env, S>,
+    T: IntoVal<Env, Val>,
+{
+    let index = len(env, stream);
+    ttl::set_persistent(env, &(ENTRY_KEY, stream.clone(), index), record);
+    ttl::set_persistent(env, &(LEN_KEY, stream.clone()), &(index + 1));
+    index
+}
+
+/// Records in a stream
+pub fn len<S>(env: &Env, stream: &S) -> u32
+where
+    S: Clone,
+    Val: TryFromVal<Env, S>,
+{
+    env.storage().persistent().get(&(LEN_KEY, stream.clone())).unwrap_or(0)
+}
+
+/// The record at `index` in a stream
+pub fn get<S, T>(env: &Env, stream: &S, index: u32) -> Option<T>
+where
+    S: Clone,
+    Val: TryFromVal<Env, S>,
+    T: TryFromVal<Env, Val>,
+{
+    env.storage().persistent().get(&(ENTRY_KEY, stream.clone(), index))
+}
+
+/// One page of a stream, oldest first. Returns the records and the cursor of the next
+/// page, or None once the stream is exhausted
+pub fn page<S, T>(env: &Env, stream: &S, page: &PageRequest) -> (Vec<T>, Option<u32>)
+where
+    S: Clone,
+    Val: TryFromVal<Env, S>,
+    T: IntoVal<Env, Val> + TryFromVal<Env, Val>,
+{
+    let len = len(env, stream);
+    let end = page.cursor.saturating_add(page.limit.min(MAX_PAGE_SIZE)).min(len);
+    let mut items = vec![env];
+    for index in page.cursor..end {
+        if let Some(record) = get(env, stream, index) {
+            items.push_back(record);
+        }
+    }
+    (items, (end < len).then_some(end))
+}
@@ -0,0 +1,178 @@
+#![no_std]
+//! Shared cross-contract interfaces, error codes, access control, entrypoint
+//! gating, calendar, approval, id derivation, journal, metadata, KPI, oracle
+//! attestation, pagination, rebalance, risk, seasonality, timelock, trustline, TTL, upgrade
+//! and change-tracking helpers.
+//!
+//! Each contract that serves one of these interfaces implements the trait in a
+//! `#[contractimpl]` block, and callers use the generated client instead of raw
+//! `invoke_contract` calls, so a signature mismatch fails at build time.
+//! Fallible calls return a raw `Error` carrying the serving contract's own
+//! error code, since every contract declares its own error enum.
+use soroban_sdk::{contractclient, Address, Env, Error, Symbol, Val, Vec};
+
+pub mod access;
+pub mod approvals;
+pub mod attestation;
+pub mod calendar;
+pub mod changes;
+pub mod errors;
+pub mod guard;
+pub mod ids;
+pub mod journal;
+pub mod metadata;
+pub mod metrics;
+pub mod page;
+pub mod rebalance;
+pub mod risk;
+pub mod seasonality;
+pub mod timelock;
+pub mod trustline;
+pub mod ttl;
+pub mod upgrade;
+
+pub use access::Role;
+pub use approvals::{ApprovalRule, Payee, PendingAction};
+pub use attestation::{FeederKey, OracleSigner};
+pub use calendar::EpochSchedule;
+pub use changes::{RecordChange, RecordChangePage};
+pub use errors::ContractError;
+pub use guard::Gate;
+pub use metadata::Metadata;
+pub use metrics::{DistributionStats, EpochKpis, FinancingStats, RateStats, RideStats, ServicingStats};
+pub use page::{PageRequest, MAX_PAGE_SIZE};
+pub use rebalance::{AllocationWeight, RebalanceRecord};
+pub use risk::RiskData;
+pub use seasonality::SeasonalBaseline;
+pub use timelock::{AdminChangeRecord, ChangeLogPage, ChangeValue, PendingChange};
+pub use soroban_sdk::token::TokenClient;
+pub use ttl::TtlConfig;
+
+/// Loan pool reads used by the other contracts, and the capital draw made by the rate adjuster
+#[contractclient(name = "LoanPoolClient")]
+pub trait LoanPoolInterface {
+    /// (invested, capital_seconds, redeemed_units, redeemed_amount) since a timestamp
+    fn get_investor_position(env: &Env, investor: Address, asset_id: Symbol, since: u64) -> (i128, i128, i128, i128);
+
+    /// Equity score of an asset's zone, if the asset exists
+    fn get_asset_equity_score(env: &Env, asset_id: Symbol) -> Option<i32>;
+
+    /// Funding program an asset is listed under, if the asset exists
+    fn get_asset_program(env: &Env, asset_id: Symbol) -> Option<Symbol>;
+
+    /// Oracle-attested accessibility feature of an asset ("wheelchr", "adaptive"), if any
+    fn get_asset_accessibility(env: &Env, asset_id: Symbol) -> Option<Symbol>;
+
+    /// (kind, asset_id, amount, timestamp) principal returns and write-offs in [from, to)
+    fn get_principal_events(env: &Env, investor: Address, from: u64, to: u64) -> Vec<(Symbol, Symbol, i128, u64)>;
+
+    /// Whether the program an asset is listed under is winding down
+    fn is_winding_down(env: &Env, asset_id: Symbol) -> bool;
+
+    /// Stellar Asset Contract that investments and payouts settle in, if configured
+    fn get_settlement_asset(env: &Env) -> Option<Address>;
+
+    /// Capital financed in a calendar epoch and how widely it was spread across investors
+    fn get_epoch_financing(env: &Env, epoch: u32) -> FinancingStats;
+
+    /// Repayments and defaults serviced in a calendar epoch
+    fn get_epoch_servicing(env: &Env, epoch: u32) -> ServicingStats;
+
+    /// Send a funded asset's capital to the calling rate adjuster for an approved application
+    fn release_loan_capital(env: &Env, application_id: Symbol) -> Result<i128, Error>;
+
+    /// Take a borrower's payment on a loan the calling rate adjuster services, split as the adjuster
+    /// applied it, into the pool's ledger for the asset. Returns the amount paid out to the exit queue
+    fn record_loan_repayment(env: &Env, payer: Address, asset_id: Symbol, principal: i128, interest: i128) -> Result<i128, Error>;
+
+    /// Whether an asset's loan has defaulted, in the pool's servicing or at the rate adjuster
+    fn is_asset_defaulted(env: &Env, asset_id: Symbol) -> bool;
+
+    /// Move principal backing a sold position, and the revenue claims that follow it, from seller to buyer
+    fn transfer_position(env: &Env, from: Address, to: Address, asset_id: Symbol, amount: i128) -> Result<(), Error>;
+
+    /// Unlock principal backing a position its holder burned
+    fn release_position(env: &Env, holder: Address, asset_id: Symbol, amount: i128) -> Result<(), Error>;
+
+    /// Share tokens an account holds in an asset, one per open investment unit
+    fn share_balance(env: &Env, asset_id: Symbol, id: Address) -> i128;
+
+    /// Share tokens outstanding for an asset
+    fn share_total_supply(env: &Env, asset_id: Symbol) -> i128;
+
+    /// Every account that has held an asset's shares, in order of first receipt; some may hold none now
+    fn share_holders(env: &Env, asset_id: Symbol) -> Vec<Address>;
+
+    /// Move an asset's shares, and the investment records and revenue claims behind them, for the
+    /// asset's linked share token once it has checked the holder's or spender's authorization
+    fn move_shares(env: &Env, asset_id: Symbol, from: Address, to: Address, amount: i128) -> Result<(), Error>;
+}
+
+/// Canonical equity score source served by the rate adjuster
+#[contractclient(name = "EquityOracleClient")]
+pub trait EquityOracleInterface {
+    /// Cached canonical equity score for a location, if one has been computed
+    fn peek_equity_score(env: &Env, location: Symbol) -> Option<i32>;
+
+    /// Latest oracle-reported operating risk for a location, if any
+    fn peek_risk_data(env: &Env, location: Symbol) -> Option<RiskData>;
+
+    /// Borrower, asset, adjusted rate (percentage) and approved principal of an approved or active application
+    fn peek_approved_loan(env: &Env, application_id: Symbol) -> Option<(Address, Symbol, i32, i128)>;
+
+    /// Rate discounts delivered on loans approved in a calendar epoch
+    fn get_epoch_rate_stats(env: &Env, epoch: u32) -> RateStats;
+
+    /// Whether the rate adjuster's loan on an asset has defaulted
+    fn is_asset_defaulted(env: &Env, asset_id: Symbol) -> bool;
+}
+
+/// Ride and distribution reporting read by governance for the public goods dashboard and annual reports
+#[contractclient(name = "DistributorClient")]
+pub trait DistributorInterface {
+    /// Rides and underserved rides accepted in a calendar epoch
+    fn get_epoch_rides(env: &Env, epoch: u32) -> RideStats;
+
+    /// Revenue distributions recorded in a calendar epoch
+    fn get_epoch_distributions(env: &Env, epoch: u32) -> DistributionStats;
+}
+
+/// Governance parameters read by other contracts
+#[contractclient(name = "GovernanceClient")]
+pub trait GovernanceInterface {
+    /// Platform fee rate in basis points for a zone equity score
+    fn get_fee_bps(env: &Env, equity_score: i32) -> u32;
+
+    /// Shared epoch calendar
+    fn get_epoch_schedule(env: &Env) -> EpochSchedule;
+
+    /// Whether a voter holds a participation badge for a proposal
+    fn has_badge(env: &Env, voter: Address, proposal_id: Symbol) -> bool;
+
+    /// Number of participation badges a voter holds
+    fn get_badge_count(env: &Env, voter: Address) -> u32;
+}
+
+/// Notification relay that the platform contracts' change tracking writes to
+#[contractclient(name = "RelayClient")]
+pub trait RelayInterface {
+    /// Record that a publisher's record of kind `topic` changed; returns the notification's sequence number
+    fn notify(env: &Env, publisher: Address, topic: Symbol, subject: Val) -> Result<u32, Error>;
+}
+
+/// SEP-41 token fronting one asset's shares at the loan pool
+#[contractclient(name = "ShareTokenClient")]
+pub trait ShareTokenInterface {
+    /// Loan pool that keeps the share ledger
+    fn get_loan_pool(env: &Env) -> Address;
+
+    /// Asset whose shares the token stands for
+    fn get_asset_id(env: &Env) -> Symbol;
+}
+
+/// Secondary market the loan pool mints investment positions into
+#[contractclient(name = "PositionMarketClient")]
+pub trait PositionMarketInterface {
+    /// Record a transferable position backed by principal the loan pool has locked; returns its id
+    fn mint_position(env: &Env, investor: Address, asset_id: Symbol, amount: i128, equity_bonus: i32) -> Result<u32, Error>;
+}
@@ -0,0 +1,37 @@
+//! Content-hash commitments for user-facing text.
+//!
+//! Titles, descriptions and names live off-chain as a single canonical
+//! document that holds every translation. The chain keeps only its sha256,
+//! the URI the document is published at and a short label for listings,
+//! either of which may be left empty. Anyone holding the document can check
+//! it against the commitment with `verify()`.
+use soroban_sdk::{contracttype, Bytes, BytesN, Env, String, Symbol};
+
+use crate::ContractError;
+
+/// Longest document URI accepted, in bytes
+pub const MAX_URI_LEN: u32 = 256;
+
+/// On-chain commitment to an off-chain metadata document
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Metadata {
+    pub content_hash: BytesN<32>, // sha256 of the canonical document, all languages included
+    pub uri: String, // Where the document is published, e.g. an ipfs:// or https:// URI; empty if unpublished
+    pub label: Symbol, // Short untranslated tag for listings and events; empty if none
+}
+
+impl Metadata {
+    /// Whether `content` is the document this metadata commits to
+    pub fn verify(&self, env: &Env, content: &Bytes) -> bool {
+        env.crypto().sha256(content) == self.content_hash
+    }
+
+    /// Reject URIs too long to keep in a record
+    pub fn check(&self) -> Result<(), ContractError> {
+        if self.uri.len() > MAX_URI_LEN {
+            return Err(ContractError::InvalidInput);
+        }
+        Ok(())
+    }
+}
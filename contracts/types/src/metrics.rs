@@ -0,0 +1,97 @@
+//! Per-epoch public goods KPIs.
+//!
+//! Each contract keeps raw counters for the calendar epoch a write lands in:
+//! capital financed and repaid at the loan pool, rate discounts granted by the
+//! rate adjuster, and rides and distributions at the distributor. Governance
+//! reads the counters through the shared interfaces and derives the dashboard
+//! KPIs and annual reports, so every figure can be reproduced from chain data
+//! alone.
+use soroban_sdk::{contracttype, Address, Map};
+
+/// Capital the loan pool financed in one epoch
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct FinancingStats {
+    pub financed: i128,
+    pub investor_count: u32,
+    pub diversity_bps: u32, // 10000 minus the Herfindahl index of investor shares
+}
+
+/// Repayments and defaults the loan pool serviced in one epoch
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ServicingStats {
+    pub repaid_principal: i128,
+    pub repaid_interest: i128,
+    pub defaults: u32, // Assets whose loan moved to default
+}
+
+/// Revenue distributions the distributor recorded in one epoch
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct DistributionStats {
+    pub distributions: u32,
+    pub distributed: i128, // Allocated to investor lines, bonuses included
+    pub platform_fees: i128,
+}
+
+/// Rate discounts delivered on loans approved in one epoch
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct RateStats {
+    pub loans: u32,
+    pub discount_bps: i128, // Sum over loans of (base rate - adjusted rate); negative for premiums
+}
+
+/// Rides accepted by the distributor in one epoch
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct RideStats {
+    pub rides: i128,
+    pub underserved_rides: i128,
+}
+
+/// Dashboard KPIs for one epoch
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EpochKpis {
+    pub epoch: u32,
+    pub financed: i128,
+    pub underserved_rides: i128,
+    pub cost_per_underserved_ride: Option<i128>, // None when no underserved rides were reported
+    pub avg_rate_discount_bps: Option<i128>, // None when no loans were approved
+    pub investor_count: u32,
+    pub investor_diversity_bps: u32,
+}
+
+impl EpochKpis {
+    /// Derive an epoch's KPIs from each contract's raw counters
+    pub fn derive(epoch: u32, financing: &FinancingStats, rates: &RateStats, rides: &RideStats) -> Self {
+        EpochKpis {
+            epoch,
+            financed: financing.financed,
+            underserved_rides: rides.underserved_rides,
+            cost_per_underserved_ride: (rides.underserved_rides > 0)
+                .then(|| financing.financed / rides.underserved_rides),
+            avg_rate_discount_bps: (rates.loans > 0).then(|| rates.discount_bps / rates.loans as i128),
+            investor_count: financing.investor_count,
+            investor_diversity_bps: financing.diversity_bps,
+        }
+    }
+}
+
+/// Diversity of a set of contributions as 10000 minus their Herfindahl index, in basis points:
+/// 0 when one investor supplied everything, approaching 10000 as capital spreads out
+pub fn diversity_bps(amounts: &Map<Address, i128>) -> u32 {
+    let total: i128 = amounts.iter().map(|(_, amount)| amount).sum();
+    if total <= 0 {
+        return 0;
+    }
+
+    let mut concentration: i128 = 0;
+    for (_, amount) in amounts.iter() {
+        let share_bps = amount * 10_000 / total;
+        concentration += share_bps * share_bps / 10_000;
+    }
+    (10_000 - concentration).max(0) as u32
+}
@@ -0,0 +1,55 @@
+//! Offset-cursor pagination shared by every list endpoint.
+//!
+//! A list endpoint takes a `PageRequest` and returns a page type declared
+//! with `page_result!`, which always has the shape `{ items, next_cursor }`.
+//! Contract specs can't carry generic types, so each item type gets its own
+//! named page struct, but they encode identically and SDKs can follow
+//! `next_cursor` the same way for all of them.
+use soroban_sdk::{contracttype, vec, Env, IntoVal, TryFromVal, Val, Vec};
+
+/// Most records returned by one page of a paginated getter
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+/// Position and size of one requested page
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PageRequest {
+    pub cursor: u32, // 0 for the first page, then the previous page's next_cursor
+    pub limit: u32, // Capped at MAX_PAGE_SIZE
+}
+
+impl PageRequest {
+    /// Request the first page
+    pub fn first(limit: u32) -> Self {
+        PageRequest { cursor: 0, limit }
+    }
+
+    /// Collect this page of `entries`, mapping each one with `load`. Returns the items and the
+    /// cursor of the next page, or None once the list is exhausted
+    pub fn collect<E, T, F>(&self, env: &Env, entries: &Vec<E>, load: F) -> (Vec<T>, Option<u32>)
+    where
+        E: IntoVal<Env, Val> + TryFromVal<Env, Val>,
+        T: IntoVal<Env, Val> + TryFromVal<Env, Val>,
+        F: Fn(E) -> T,
+    {
+        let mut items = vec![env];
+        let end = self.cursor.saturating_add(self.limit.min(MAX_PAGE_SIZE)).min(entries.len());
+        for i in self.cursor..end {
+            items.push_back(load(entries.get(i).unwrap()));
+        }
+        (items, (end < entries.len()).then_some(end))
+    }
+}
+
+/// Declare the page type a list endpoint returns for one item type
+#[macro_export]
+macro_rules! page_result {
+    ($name:ident, $item:ident) => {
+        #[soroban_sdk::contracttype]
+        #[derive(Clone, Debug, Eq, PartialEq)]
+        pub struct $name {
+            pub items: soroban_sdk::Vec<$item>,
+            pub next_cursor: Option<u32>, // Cursor of the following page; None on the last page
+        }
+    };
+}
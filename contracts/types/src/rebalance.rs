@@ -0,0 +1,54 @@
+//! Target allocations for rebalancing pooled capital.
+//!
+//! Governance votes a weight vector over assets that are still raising.
+//! Executing it invests the loan pool's LP vault capital by those weights on
+//! behalf of the vault holders, and the pool keeps the allocation from before
+//! and after the move.
+use soroban_sdk::{contracttype, Map, Symbol, Vec};
+
+use crate::ContractError;
+
+/// One asset's share of a rebalance target
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AllocationWeight {
+    pub asset_id: Symbol,
+    pub weight_bps: u32, // Share of pooled capital; weights sum to at most 10000
+}
+
+/// Allocation of pooled capital before and after an executed rebalance
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RebalanceRecord {
+    pub id: u32,
+    pub proposal_id: Symbol, // Governance proposal that approved the targets
+    pub pool_capital: i128, // LP vault capital available to allocate
+    pub before: Map<Symbol, i128>, // asset_id -> vault capital moved into the asset
+    pub after: Map<Symbol, i128>,
+    pub zones_before: Map<Symbol, i128>, // zone -> vault capital moved into its assets
+    pub zones_after: Map<Symbol, i128>,
+    pub timestamp: u64,
+}
+
+/// Check that targets name each asset once with a non-zero weight, summing to at most 10000
+pub fn validate_targets(targets: &Vec<AllocationWeight>) -> Result<(), ContractError> {
+    if targets.is_empty() {
+        return Err(ContractError::InvalidInput);
+    }
+
+    let mut total_bps: u32 = 0;
+    for (i, target) in targets.iter().enumerate() {
+        if target.weight_bps == 0 {
+            return Err(ContractError::InvalidInput);
+        }
+        if targets.iter().position(|other| other.asset_id == target.asset_id) != Some(i) {
+            return Err(ContractError::InvalidInput);
+        }
+        total_bps += target.weight_bps;
+    }
+    if total_bps > 10_000 {
+        return Err(ContractError::InvalidInput);
+    }
+
+    Ok(())
+}
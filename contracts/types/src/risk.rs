@@ -0,0 +1,28 @@
+//! Per-location operating risk reported by the urban data oracle.
+//!
+//! The rate adjuster adds `premium()` to its model output, and the loan pool
+//! copies the record onto asset listings as a risk disclosure.
+use soroban_sdk::{contracttype, Symbol};
+
+/// Highest risk premium, in rate percentage points, any location can carry
+pub const MAX_RISK_PREMIUM: i32 = 3;
+
+/// Oracle-reported operating risk for a location
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RiskData {
+    pub location: Symbol,
+    pub theft_rate: u32, // Thefts per 1,000 vehicles per year
+    pub vandalism_incidents: u32, // Reported incidents over the last quarter
+    pub weather_downtime: u32, // Hours of weather-related downtime over the last year
+    pub timestamp: u64,
+}
+
+impl RiskData {
+    /// Rate premium in percentage points: one point per 20 thefts, 50 incidents
+    /// or 200 downtime hours, capped at MAX_RISK_PREMIUM
+    pub fn premium(&self) -> i32 {
+        let points = self.theft_rate / 20 + self.vandalism_incidents / 50 + self.weather_downtime / 200;
+        points.min(MAX_RISK_PREMIUM as u32) as i32
+    }
+}
@@ -0,0 +1,57 @@
+//! Seasonal revenue baselines.
+//!
+//! The oracle publishes, per zone and asset type, how each calendar month's
+//! revenue compares to an average month. Contracts use the factor for the
+//! current month to relax thresholds in a seasonal low, so a winter dip in
+//! bike revenue reads as expected rather than as distress.
+use soroban_sdk::{contracttype, Symbol, Vec};
+
+// Average Gregorian month, matching the default epoch length
+const SECONDS_PER_MONTH: u64 = 2_629_800;
+
+/// Factors outside this range are rejected; they bound how far a season can stretch a threshold
+pub const MIN_SEASONAL_FACTOR_BPS: u32 = 2_500;
+pub const MAX_SEASONAL_FACTOR_BPS: u32 = 40_000;
+
+/// Expected revenue by calendar month for one zone and asset type
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SeasonalBaseline {
+    pub zone: Symbol,
+    pub asset_type: Symbol,
+    pub monthly_factor_bps: Vec<u32>, // January first; 10000 is an average month
+    pub updated_at: u64,
+}
+
+impl SeasonalBaseline {
+    /// Whether there is one factor per month, each within the accepted range
+    pub fn is_valid(&self) -> bool {
+        self.monthly_factor_bps.len() == 12
+            && self
+                .monthly_factor_bps
+                .iter()
+                .all(|factor| (MIN_SEASONAL_FACTOR_BPS..=MAX_SEASONAL_FACTOR_BPS).contains(&factor))
+    }
+
+    /// Factor for the calendar month containing a timestamp
+    pub fn factor_at(&self, timestamp: u64) -> u32 {
+        let month = (timestamp / SECONDS_PER_MONTH) % 12;
+        self.monthly_factor_bps.get(month as u32).unwrap_or(10_000)
+    }
+}
+
+/// Scale a minimum threshold down in a seasonal low; peak seasons leave it unchanged
+pub fn relax_floor(floor: u32, factor_bps: u32) -> u32 {
+    if factor_bps >= 10_000 {
+        return floor;
+    }
+    (floor as u64 * factor_bps as u64 / 10_000) as u32
+}
+
+/// Stretch a waiting period in a seasonal low; peak seasons leave it unchanged
+pub fn stretch_period(period: u64, factor_bps: u32) -> u64 {
+    if factor_bps >= 10_000 {
+        return period;
+    }
+    period * 10_000 / factor_bps.max(MIN_SEASONAL_FACTOR_BPS) as u64
+}
@@ -0,0 +1,208 @@
+//! Timelocked admin changes.
+//!
+//! Settings that move money or shift risk are queued and only take effect
+//! once their delay has passed, so users can react before they apply. This
+//! module owns the queue, the delay bounds and the append-only change log;
+//! each contract checks that a queued action is one it knows and applies it
+//! when the change executes. Queued changes and log records are persistent
+//! entries keyed by id, so neither grows the contract instance.
+use soroban_sdk::{
+    contracttype, symbol_short, vec, xdr::FromXdr, Address, Bytes, Env, Symbol, Vec,
+};
+
+use crate::{journal, ttl, ContractError, PageRequest};
+
+// NEXT_KEY -> id the next queued change gets, kept in instance storage
+const NEXT_KEY: Symbol = symbol_short!("TL_NEXT");
+// (CHANGE_KEY, id) -> PendingChange while it waits out its delay
+const CHANGE_KEY: Symbol = symbol_short!("TL_CHANGE");
+// PENDING_KEY -> ids of queued changes, oldest first
+const PENDING_KEY: Symbol = symbol_short!("TL_QUEUE");
+// Journal stream of AdminChangeRecords
+const LOG_KEY: Symbol = symbol_short!("TL_LOG");
+
+/// Shortest delay a change can be queued with
+pub const MIN_DELAY: u64 = 86_400;
+/// Longest delay a change can be queued with
+pub const MAX_DELAY: u64 = 259_200;
+
+/// New setting carried by a queued change
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ChangeValue {
+    Number(i128), // Rates, thresholds and periods
+    Address(Address), // Oracle, governance and linked contract addresses
+    Encoded(Bytes), // Structured settings (bands, rules, terms), XDR-encoded by the contract
+}
+
+/// Admin change waiting out its timelock
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingChange {
+    pub id: u32,
+    pub action: Symbol, // Which setting is being changed
+    pub value: ChangeValue,
+    pub queued_at: u64,
+    pub eta: u64, // Earliest execution time
+}
+
+/// Executed or cancelled admin change, kept in an append-only log
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminChangeRecord {
+    pub id: u32,
+    pub action: Symbol,
+    pub value: ChangeValue,
+    pub status: Symbol, // "executed", "cancelled"
+    pub timestamp: u64,
+}
+
+crate::page_result!(ChangeLogPage, AdminChangeRecord);
+
+impl PendingChange {
+    /// The new value of a numeric setting
+    pub fn number(&self) -> Result<i128, ContractError> {
+        match &self.value {
+            ChangeValue::Number(value) => Ok(*value),
+            _ => Err(ContractError::InvalidInput),
+        }
+    }
+
+    /// The new value of an address setting
+    pub fn address(&self) -> Result<Address, ContractError> {
+        match &self.value {
+            ChangeValue::Address(address) => Ok(address.clone()),
+            _ => Err(ContractError::NoAddress),
+        }
+    }
+
+    /// The new value of a structured setting
+    pub fn decode<T>(&self, env: &Env) -> Result<T, ContractError>
+    where
+        T: FromXdr,
+    {
+        match &self.value {
+            ChangeValue::Encoded(bytes) => T::from_xdr(env, bytes).map_err(|_| ContractError::InvalidInput),
+            _ => Err(ContractError::InvalidInput),
+        }
+    }
+}
+
+impl ChangeValue {
+    /// Value of a change queued through `queue_admin_change`: the address if one was given,
+    /// otherwise the number
+    pub fn new(value: i128, new_address: Option<Address>) -> Self {
+        match new_address {
+            Some(address) => ChangeValue::Address(address),
+            None => ChangeValue::Number(value),
+        }
+    }
+}
+
+/// Queue a change `delay` seconds out, after `validate` accepts it; returns its id.
+/// `min_delay` is normally `MIN_DELAY`
+pub fn queue<E, F>(
+    env: &Env,
+    action: Symbol,
+    value: ChangeValue,
+    delay: u64,
+    min_delay: u64,
+    validate: F,
+) -> Result<u32, E>
+where
+    E: From<ContractError>,
+    F: FnOnce(&PendingChange) -> Result<(), E>,
+{
+    if delay < min_delay || delay > MAX_DELAY {
+        return Err(ContractError::InvalidInput.into());
+    }
+
+    let id: u32 = env.storage().instance().get(&NEXT_KEY).unwrap_or(1);
+    let now = env.ledger().timestamp();
+    let change = PendingChange {
+        id,
+        action,
+        value,
+        queued_at: now,
+        eta: now + delay,
+    };
+    validate(&change)?;
+
+    ttl::set_instance(env, &NEXT_KEY, &(id + 1));
+    ttl::set_persistent(env, &(CHANGE_KEY, id), &change);
+    let mut queued = pending_ids(env);
+    queued.push_back(id);
+    ttl::set_persistent(env, &PENDING_KEY, &queued);
+
+    Ok(id)
+}
+
+/// Apply a queued change with `apply` once its delay has passed, and log it as executed
+pub fn execute<E, F>(env: &Env, id: u32, apply: F) -> Result<(), E>
+where
+    E: From<ContractError>,
+    F: FnOnce(&PendingChange) -> Result<(), E>,
+{
+    let change = load(env, id)?;
+    if env.ledger().timestamp() < change.eta {
+        return Err(ContractError::Timelock.into());
+    }
+
+    apply(&change)?;
+    close(env, &change, symbol_short!("executed"));
+    Ok(())
+}
+
+/// Drop a queued change and log it as cancelled
+pub fn cancel(env: &Env, id: u32) -> Result<(), ContractError> {
+    let change = load(env, id)?;
+    close(env, &change, symbol_short!("cancelled"));
+    Ok(())
+}
+
+/// Every change waiting out its delay, oldest first
+pub fn pending(env: &Env) -> Vec<PendingChange> {
+    let mut changes = vec![env];
+    for id in pending_ids(env).iter() {
+        if let Ok(change) = load(env, id) {
+            changes.push_back(change);
+        }
+    }
+    changes
+}
+
+/// One page of the change log, oldest first
+pub fn log(env: &Env, page: PageRequest) -> ChangeLogPage {
+    let (items, next_cursor) = journal::page(env, &LOG_KEY, &page);
+    ChangeLogPage { items, next_cursor }
+}
+
+fn load(env: &Env, id: u32) -> Result<PendingChange, ContractError> {
+    env.storage()
+        .persistent()
+        .get(&(CHANGE_KEY, id))
+        .ok_or(ContractError::NotFound)
+}
+
+fn pending_ids(env: &Env) -> Vec<u32> {
+    env.storage().persistent().get(&PENDING_KEY).unwrap_or(vec![env])
+}
+
+/// Remove a change from the queue and append it to the change log
+fn close(env: &Env, change: &PendingChange, status: Symbol) {
+    env.storage().persistent().remove(&(CHANGE_KEY, change.id));
+    let mut queued = pending_ids(env);
+    if let Some(index) = queued.first_index_of(change.id) {
+        queued.remove(index);
+    }
+    ttl::set_persistent(env, &PENDING_KEY, &queued);
+
+    let record = AdminChangeRecord {
+        id: change.id,
+        action: change.action.clone(),
+        value: change.value.clone(),
+        status,
+        timestamp: env.ledger().timestamp(),
+    };
+    journal::append(env, &LOG_KEY, &record);
+}
@@ -0,0 +1,24 @@
+//! Pre-flight checks against a Stellar Asset Contract.
+//!
+//! Payouts and investments settle in a SAC-wrapped classic asset. A holder
+//! with no trustline, or whose trustline the issuer hasn't authorized, would
+//! make a transfer fail partway through a batch; checking first fails early.
+use soroban_sdk::{
+    token::{StellarAssetClient, TokenClient},
+    Address, Env,
+};
+
+use crate::ContractError;
+
+/// Check that a holder has an authorized trustline for a SAC asset
+pub fn check_trustline(env: &Env, asset: &Address, holder: &Address) -> Result<(), ContractError> {
+    // Balance reads fail for classic accounts without a trustline
+    if !matches!(TokenClient::new(env, asset).try_balance(holder), Ok(Ok(_))) {
+        return Err(ContractError::NoTrustline);
+    }
+
+    match StellarAssetClient::new(env, asset).try_authorized(holder) {
+        Ok(Ok(true)) => Ok(()),
+        _ => Err(ContractError::NoTrustline),
+    }
+}
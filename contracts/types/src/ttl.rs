@@ -0,0 +1,88 @@
+//! Storage TTL management.
+//!
+//! Entries whose TTL runs out are archived and must be restored before the
+//! contract can read them again. Every write goes through `set_instance` or
+//! `set_persistent`, which extend the written entry's TTL once it drops below
+//! the contract's threshold, so live state never ages out between writes.
+//! `extend_instance` backs the `bump_storage` maintenance entrypoint for
+//! contracts that go quiet for long stretches.
+use soroban_sdk::{contracttype, symbol_short, Env, IntoVal, Symbol, Val};
+
+use crate::ContractError;
+
+// TTL_KEY -> TtlConfig, kept in instance storage beside the contract's data
+const TTL_KEY: Symbol = symbol_short!("TTL");
+
+/// Ledgers closed per day at a five-second close time
+pub const DAY_IN_LEDGERS: u32 = 17_280;
+/// Remaining TTL below which a write extends its entry
+pub const DEFAULT_THRESHOLD: u32 = 30 * DAY_IN_LEDGERS;
+/// TTL an entry is extended to
+pub const DEFAULT_EXTEND_TO: u32 = 120 * DAY_IN_LEDGERS;
+/// Longest TTL the network grants
+pub const MAX_EXTEND_TO: u32 = 180 * DAY_IN_LEDGERS;
+
+/// When and how far writes extend storage TTLs
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TtlConfig {
+    pub threshold: u32, // Ledgers of TTL left before a write extends the entry
+    pub extend_to: u32, // Ledgers of TTL the entry is extended to
+}
+
+/// The contract's TTL configuration, or the defaults if none was set
+pub fn config(env: &Env) -> TtlConfig {
+    env.storage().instance().get(&TTL_KEY).unwrap_or(TtlConfig {
+        threshold: DEFAULT_THRESHOLD,
+        extend_to: DEFAULT_EXTEND_TO,
+    })
+}
+
+/// Replace the contract's TTL configuration
+pub fn set_config(env: &Env, threshold: u32, extend_to: u32) -> Result<(), ContractError> {
+    if threshold >= extend_to || extend_to > MAX_EXTEND_TO {
+        return Err(ContractError::InvalidInput);
+    }
+    set_instance(env, &TTL_KEY, &TtlConfig { threshold, extend_to });
+    Ok(())
+}
+
+/// Extend the TTL of the contract instance, its code and every instance entry
+pub fn extend_instance(env: &Env) {
+    let config = config(env);
+    env.storage().instance().extend_ttl(config.threshold, config.extend_to);
+}
+
+/// Extend the TTL of one persistent entry if it exists
+pub fn extend_persistent<K>(env: &Env, key: &K)
+where
+    K: IntoVal<Env, Val>,
+{
+    let config = config(env);
+    let storage = env.storage().persistent();
+    if storage.has(key) {
+        storage.extend_ttl(key, config.threshold, config.extend_to);
+    }
+}
+
+/// Write an instance entry and extend the instance's TTL
+pub fn set_instance<K, V>(env: &Env, key: &K, value: &V)
+where
+    K: IntoVal<Env, Val>,
+    V: IntoVal<Env, Val>,
+{
+    env.storage().instance().set(key, value);
+    extend_instance(env);
+}
+
+/// Write a persistent entry and extend its TTL
+pub fn set_persistent<K, V>(env: &Env, key: &K, value: &V)
+where
+    K: IntoVal<Env, Val>,
+    V: IntoVal<Env, Val>,
+{
+    let config = config(env);
+    let storage = env.storage().persistent();
+    storage.set(key, value);
+    storage.extend_ttl(key, config.threshold, config.extend_to);
+}
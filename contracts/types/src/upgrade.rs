@@ -0,0 +1,37 @@
+//! In-place contract upgrades.
+//!
+//! `upgrade` swaps in new wasm and keeps every storage entry. The new code's
+//! `migrate` then walks the stored layout version up to the version it was
+//! built for, one step per release, so a layout change ships as one migration
+//! branch and never as a redeploy.
+use soroban_sdk::{symbol_short, BytesN, Env};
+
+use crate::ContractError;
+
+/// Replace the running contract's code with an uploaded wasm
+pub fn upgrade(env: &Env, new_wasm_hash: BytesN<32>) {
+    env.deployer().update_current_contract_wasm(new_wasm_hash.clone());
+    env.events().publish((symbol_short!("upgraded"),), new_wasm_hash);
+}
+
+/// Run `step(env, from)` for every stored layout version below `current`, oldest first,
+/// and return the version storage ends up at
+pub fn migrate<E, F>(env: &Env, stored: u32, current: u32, mut step: F) -> Result<u32, E>
+where
+    E: From<ContractError>,
+    F: FnMut(&Env, u32) -> Result<(), E>,
+{
+    // Code older than its storage would misread it
+    if stored > current {
+        return Err(ContractError::BadLegacy.into());
+    }
+
+    for from in stored..current {
+        step(env, from)?;
+    }
+    if stored < current {
+        env.events().publish((symbol_short!("migrated"),), (stored, current));
+    }
+
+    Ok(current)
+}